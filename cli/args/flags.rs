@@ -14,11 +14,13 @@ use std::str::FromStr;
 
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
 use clap::ValueHint;
 use clap::builder::FalseyValueParser;
+use clap::builder::PossibleValue;
 use clap::builder::styling::AnsiColor;
 use clap::error::ErrorKind;
 use clap::value_parser;
@@ -119,6 +121,10 @@ pub struct BenchFlags {
   pub no_run: bool,
   pub permit_no_files: bool,
   pub watch: Option<WatchFlags>,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  /// Percentage by which a benchmark may be slower than the baseline.
+  pub regression_threshold: Option<u32>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -167,15 +173,30 @@ pub enum CoverageType {
   Detailed,
   Lcov,
   Html,
+  Cobertura,
+  JsonSummary,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct CoverageFlags {
   pub files: FileFlags,
   pub output: Option<String>,
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub r#type: CoverageType,
+  pub thresholds: CoverageThresholdFlags,
+  /// Only check the thresholds against the lines changed relative to this
+  /// git revision.
+  pub diff_base: Option<String>,
+}
+
+/// Minimum coverage percentages, which take precedence over the ones in the
+/// config file.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct CoverageThresholdFlags {
+  pub lines: Option<f64>,
+  pub branches: Option<f64>,
+  pub functions: Option<f64>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -371,6 +392,8 @@ pub struct ServeFlags {
   pub host: String,
   pub parallel: bool,
   pub open_site: bool,
+  pub tls_cert: Option<String>,
+  pub tls_key: Option<String>,
 }
 
 impl ServeFlags {
@@ -383,6 +406,8 @@ impl ServeFlags {
       host: host.to_owned(),
       parallel: false,
       open_site: false,
+      tls_cert: None,
+      tls_key: None,
     }
   }
 }
@@ -424,6 +449,16 @@ pub enum TestReporterConfig {
   Dot,
   Junit,
   Tap,
+  /// Used by `run()` of `node:test`, not listed in the help.
+  NodeTest,
+}
+
+/// A subset of the test modules selected with `--shard=<INDEX>/<COUNT>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TestShard {
+  /// 1-based index of the shard.
+  pub index: usize,
+  pub count: usize,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -434,6 +469,7 @@ pub struct TestFlags {
   pub coverage_raw_data_only: bool,
   pub clean: bool,
   pub fail_fast: Option<NonZeroUsize>,
+  pub retry: usize,
   pub files: FileFlags,
   pub parallel: bool,
   pub permit_no_files: bool,
@@ -444,6 +480,8 @@ pub struct TestFlags {
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
   pub hide_stacktraces: bool,
+  pub shard: Option<TestShard>,
+  pub shard_timings: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -478,6 +516,46 @@ pub struct CleanFlags {
   pub dry_run: bool,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KvFlags {
+  Backup {
+    database: String,
+    output: String,
+  },
+  Restore {
+    backup: String,
+    database: String,
+  },
+  Export {
+    database: String,
+    prefix: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    output: Option<String>,
+  },
+  Import {
+    database: String,
+    input: Option<String>,
+  },
+  QueueList {
+    database: String,
+    states: Vec<String>,
+    limit: Option<usize>,
+  },
+  QueuePurge {
+    database: String,
+    states: Vec<String>,
+  },
+  QueueRedrive {
+    database: String,
+    ids: Vec<String>,
+  },
+  QueueTrack {
+    database: String,
+    disable: bool,
+  },
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BundleFlags {
   pub entrypoints: Vec<String>,
@@ -494,7 +572,7 @@ pub struct BundleFlags {
   pub watch: bool,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DenoSubcommand {
   Add(AddFlags),
   Remove(RemoveFlags),
@@ -515,6 +593,7 @@ pub enum DenoSubcommand {
   Install(InstallFlags),
   JSONReference(JSONReferenceFlags),
   Jupyter(JupyterFlags),
+  Kv(KvFlags),
   Uninstall(UninstallFlags),
   Lsp,
   Lint(LintFlags),
@@ -687,6 +766,48 @@ impl Default for TypeCheckMode {
   }
 }
 
+fn parse_test_shard(s: &str) -> Result<TestShard, AnyError> {
+  let parsed = s.split_once('/').and_then(|(index, count)| {
+    Some(TestShard {
+      index: index.parse().ok()?,
+      count: count.parse().ok()?,
+    })
+  });
+  match parsed {
+    Some(shard) if shard.index >= 1 && shard.index <= shard.count => Ok(shard),
+    _ => bail!(
+      "Invalid shard '{}'. Expected <INDEX>/<COUNT> where 1 <= INDEX <= COUNT, e.g. 1/3",
+      s
+    ),
+  }
+}
+
+fn parse_bench_baseline_name(s: &str) -> Result<String, AnyError> {
+  let is_valid = !s.is_empty()
+    && !s.starts_with('.')
+    && s
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+  if !is_valid {
+    bail!(
+      "Invalid baseline name '{}'. Only letters, digits, '-', '_' and '.' are allowed.",
+      s
+    );
+  }
+  Ok(s.to_string())
+}
+
+/// Parses a percentage like the thresholds of the `coverage` config.
+fn parse_coverage_threshold(s: &str) -> Result<f64, AnyError> {
+  match s.parse::<f64>() {
+    Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(percent),
+    _ => bail!(
+      "Invalid coverage threshold '{}'. Expected a percentage between 0 and 100",
+      s
+    ),
+  }
+}
+
 fn parse_packages_allowed_scripts(s: &str) -> Result<String, AnyError> {
   if !s.starts_with("npm:") {
     bail!(
@@ -709,7 +830,7 @@ pub struct InternalFlags {
   pub lockfile_skip_write: bool,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct Flags {
   /// Vector of CLI arguments - these are user script arguments, all Deno
   /// specific flags are removed.
@@ -1330,6 +1451,7 @@ static DENO_HELP: &str = cstr!(
                   <p(245)>deno fmt  |  deno fmt main.ts</>
     <g>info</>         Show info about cache or info related to source file
     <g>jupyter</>      Deno kernel for Jupyter notebooks
+    <g>kv</>           Back up, restore, export, import and manage local Deno KV databases
     <g>lint</>         Lint source files
     <g>init</>         Initialize a new project
     <g>test</>         Run tests
@@ -1509,6 +1631,7 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
         "install" => install_parse(&mut flags, &mut m, app)?,
         "json_reference" => json_reference_parse(&mut flags, &mut m, app),
         "jupyter" => jupyter_parse(&mut flags, &mut m),
+        "kv" => kv_parse(&mut flags, &mut m),
         "lint" => lint_parse(&mut flags, &mut m)?,
         "lsp" => lsp_parse(&mut flags, &mut m),
         "outdated" => outdated_parse(&mut flags, &mut m, false)?,
@@ -1772,6 +1895,7 @@ pub fn clap_root() -> Command {
         .subcommand(install_subcommand())
         .subcommand(json_reference_subcommand())
         .subcommand(jupyter_subcommand())
+        .subcommand(kv_subcommand())
         .subcommand(uninstall_subcommand())
         .subcommand(outdated_subcommand())
         .subcommand(lsp_subcommand())
@@ -1906,6 +2030,10 @@ Evaluate the given files, run all benches declared with 'Deno.bench()' and repor
 If you specify a directory instead of a file, the path is expanded to all contained files matching the glob <c>{*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}</>:
   <p(245)>deno bench src/</>
 
+Save the results as a baseline and compare a later run against it:
+  <p(245)>deno bench --save-baseline=main</>
+  <p(245)>deno bench --baseline=main --regression-threshold=5</>
+
 <y>Read more:</> <c>https://docs.deno.com/go/bench</>"),
     UnstableArgsConfig::ResolutionAndRuntime,
   )
@@ -1946,6 +2074,34 @@ If you specify a directory instead of a file, the path is expanded to all contai
           .help("Cache bench modules, but don't run benchmarks")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("save-baseline")
+          .long("save-baseline")
+          .value_name("NAME")
+          .require_equals(true)
+          .value_parser(parse_bench_baseline_name)
+          .conflicts_with("watch")
+          .help("Save the results under the given name to compare later runs against them"),
+      )
+      .arg(
+        Arg::new("baseline")
+          .long("baseline")
+          .value_name("NAME")
+          .require_equals(true)
+          .value_parser(parse_bench_baseline_name)
+          .conflicts_with("watch")
+          .help(cstr!("Compare the results against the baseline saved with the given name.
+  <p(245)>Fails if a benchmark is significantly slower than the regression threshold allows.</>")),
+      )
+      .arg(
+        Arg::new("regression-threshold")
+          .long("regression-threshold")
+          .value_name("PERCENT")
+          .require_equals(true)
+          .value_parser(value_parser!(u32))
+          .requires("baseline")
+          .help("Percentage by which a benchmark may be slower than the baseline before failing [default: 10]"),
+      )
       .arg(permit_no_files_arg())
       .arg(watch_arg(false))
       .arg(watch_exclude_arg())
@@ -2351,9 +2507,15 @@ for an url to match it must match the include pattern and not match the exclude
 Write a report using the lcov format:
   <p(245)>deno coverage --lcov --output=cov.lcov cov_profile/</>
 
+Write a report using the Cobertura XML format, as consumed by GitLab and Azure Pipelines:
+  <p(245)>deno coverage --cobertura --output=cobertura.xml cov_profile/</>
+
 Generate html reports from lcov:
   <p(245)>genhtml -o html_cov cov.lcov</>
 
+Fail if less than 80% of the lines changed since the main branch are covered:
+  <p(245)>deno coverage --threshold-lines=80 --diff-base=main cov_profile</>
+
 <y>Read more:</> <c>https://docs.deno.com/go/coverage</>"),
     UnstableArgsConfig::None,
   )
@@ -2394,13 +2556,32 @@ Generate html reports from lcov:
           .help("Output coverage report in lcov format")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("cobertura")
+          .long("cobertura")
+          .help("Output coverage report in Cobertura XML format")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("json-summary")
+          .long("json-summary")
+          .help("Output a JSON summary of the line, branch and function coverage of each file")
+          .action(ArgAction::SetTrue),
+      )
+      .group(
+        ArgGroup::new("file-report").args([
+          "lcov",
+          "cobertura",
+          "json-summary",
+        ]),
+      )
       .arg(
         Arg::new("output")
-          .requires("lcov")
+          .requires("file-report")
           .long("output")
           .value_parser(value_parser!(String))
           .help(
-            cstr!("Exports the coverage report in lcov format to the given file.
+            cstr!("Exports the coverage report in lcov, Cobertura or JSON summary format to the given file.
   <p(245)>If no --output arg is specified then the report is written to stdout.</>",
           ))
           .require_equals(true)
@@ -2418,6 +2599,38 @@ Generate html reports from lcov:
           .help("Output coverage report in detailed format in the terminal")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("threshold-lines")
+          .long("threshold-lines")
+          .value_name("PERCENT")
+          .help("Exit with an error if the line coverage is below this percentage")
+          .require_equals(true)
+          .value_parser(parse_coverage_threshold),
+      )
+      .arg(
+        Arg::new("threshold-branches")
+          .long("threshold-branches")
+          .value_name("PERCENT")
+          .help("Exit with an error if the branch coverage is below this percentage")
+          .require_equals(true)
+          .value_parser(parse_coverage_threshold),
+      )
+      .arg(
+        Arg::new("threshold-functions")
+          .long("threshold-functions")
+          .value_name("PERCENT")
+          .help("Exit with an error if the function coverage is below this percentage")
+          .require_equals(true)
+          .value_parser(parse_coverage_threshold),
+      )
+      .arg(
+        Arg::new("diff-base")
+          .long("diff-base")
+          .value_name("REF")
+          .help(cstr!("Only check coverage thresholds against the lines changed relative to this git revision.
+  <p(245)>Uncommitted changes are included.</>"))
+          .require_equals(true),
+      )
       .arg(
         Arg::new("files")
           .num_args(0..)
@@ -3009,6 +3222,172 @@ fn jupyter_subcommand() -> Command {
         .conflicts_with("install"))
 }
 
+fn kv_database_arg() -> Arg {
+  Arg::new("database")
+    .required(true)
+    .help("Path to the SQLite file of the database")
+    .value_hint(ValueHint::FilePath)
+}
+
+fn kv_queue_state_arg() -> Arg {
+  Arg::new("state")
+    .long("state")
+    .help("Only act on messages in this state")
+    .value_parser(["pending", "running", "dead"])
+    .action(ArgAction::Append)
+    .value_name("STATE")
+}
+
+fn kv_subcommand() -> Command {
+  command(
+    "kv",
+    cstr!(
+      "Back up, restore, export and import local Deno KV databases, and manage
+their queues.
+
+Take a consistent backup of a database, even while it is in use:
+  <p(245)>deno kv backup kv.sqlite3 backup.sqlite3</>
+
+Replace the contents of a database with a backup:
+  <p(245)>deno kv restore backup.sqlite3 kv.sqlite3</>
+
+Export entries as newline delimited JSON, optionally limited to a key range:
+  <p(245)>deno kv export kv.sqlite3 --prefix '[\"users\"]' > users.ndjson</>
+
+Import entries from an export:
+  <p(245)>deno kv import kv.sqlite3 users.ndjson</>
+
+Record delivery attempts and messages that failed their last delivery, list
+them, and enqueue them again:
+  <p(245)>deno kv queue track kv.sqlite3</>
+  <p(245)>deno kv queue list kv.sqlite3 --state dead</>
+  <p(245)>deno kv queue redrive kv.sqlite3</>"
+    ),
+    UnstableArgsConfig::None,
+  )
+  .subcommand_required(true)
+  .defer(|cmd| {
+    cmd
+      .subcommand(
+        Command::new("backup")
+          .about("Write a point-in-time copy of a database to a new file")
+          .arg(kv_database_arg())
+          .arg(
+            Arg::new("output")
+              .required(true)
+              .help("Path of the backup file to create")
+              .value_hint(ValueHint::FilePath),
+          ),
+      )
+      .subcommand(
+        Command::new("restore")
+          .about("Replace the contents of a database with a backup")
+          .arg(
+            Arg::new("backup")
+              .required(true)
+              .help("Path of the backup file")
+              .value_hint(ValueHint::FilePath),
+          )
+          .arg(kv_database_arg()),
+      )
+      .subcommand(
+        Command::new("export")
+          .about("Write entries of a database as newline delimited JSON")
+          .arg(kv_database_arg())
+          .arg(
+            Arg::new("prefix")
+              .long("prefix")
+              .help("Only export keys under this key, given as a JSON array")
+              .value_name("KEY"),
+          )
+          .arg(
+            Arg::new("start")
+              .long("start")
+              .help("First key to export, given as a JSON array")
+              .value_name("KEY"),
+          )
+          .arg(
+            Arg::new("end")
+              .long("end")
+              .help(
+                "Key to stop the export at (exclusive), given as a JSON array",
+              )
+              .value_name("KEY"),
+          )
+          .arg(
+            Arg::new("output")
+              .long("output")
+              .short('o')
+              .help("Write the export to a file instead of stdout")
+              .value_hint(ValueHint::FilePath),
+          ),
+      )
+      .subcommand(
+        Command::new("import")
+          .about("Set the entries of an export in a database")
+          .arg(kv_database_arg())
+          .arg(
+            Arg::new("input")
+              .help("Path of the export to read; reads stdin if omitted")
+              .value_hint(ValueHint::FilePath),
+          ),
+      )
+      .subcommand(
+        Command::new("queue")
+          .about("Inspect and manage the queue of a database")
+          .subcommand_required(true)
+          .subcommand(
+            Command::new("list")
+              .about(
+                "Write the messages of the queue as newline delimited JSON",
+              )
+              .arg(kv_database_arg())
+              .arg(
+                kv_queue_state_arg()
+                  .default_values(["pending", "running", "dead"]),
+              )
+              .arg(
+                Arg::new("limit")
+                  .long("limit")
+                  .help("Maximum number of messages to list")
+                  .value_parser(value_parser!(usize))
+                  .value_name("N"),
+              ),
+          )
+          .subcommand(
+            Command::new("purge")
+              .about("Delete the messages of the queue")
+              .arg(kv_database_arg())
+              .arg(kv_queue_state_arg().default_value("pending")),
+          )
+          .subcommand(
+            Command::new("redrive")
+              .about("Enqueue messages that failed their last delivery again")
+              .arg(kv_database_arg())
+              .arg(
+                Arg::new("ids")
+                  .num_args(0..)
+                  .action(ArgAction::Append)
+                  .help("Ids of the messages to redrive; all if omitted"),
+              ),
+          )
+          .subcommand(
+            Command::new("track")
+              .about(
+                "Record delivery attempts and messages that failed their last delivery",
+              )
+              .arg(kv_database_arg())
+              .arg(
+                Arg::new("disable")
+                  .long("disable")
+                  .help("Stop recording deliveries; dead messages are kept")
+                  .action(ArgAction::SetTrue),
+              ),
+          ),
+      )
+  })
+}
+
 fn update_and_outdated_args() -> [Arg; 4] {
   [
     Arg::new("filters")
@@ -3413,6 +3792,12 @@ Start a server defined in server.ts:
 Start a server defined in server.ts, watching for changes and running on port 5050:
   <p(245)>deno serve --watch --port 5050 server.ts</>
 
+Serve HTTPS, picking up renewed certificates without a restart:
+  <p(245)>deno serve --tls-cert=cert.pem --tls-key=key.pem server.ts</>
+
+With <c>--parallel</>, workers that crash are restarted. Send <c>SIGHUP</> to restart them one at a time
+and <c>SIGUSR1</> to print their health.
+
 <y>Read more:</> <c>https://docs.deno.com/go/serve</>"), UnstableArgsConfig::ResolutionAndRuntime), true, true, true)
     .arg(
       Arg::new("port")
@@ -3431,6 +3816,22 @@ Start a server defined in server.ts, watching for changes and running on port 50
       .help("Open the browser on the address that the server is running on.")
       .action(ArgAction::SetTrue)
     )
+    .arg(
+      Arg::new("tls-cert")
+        .long("tls-cert")
+        .value_name("FILE")
+        .help("Serve HTTPS with the PEM encoded certificate chain in this file, which is read again when it changes")
+        .requires("tls-key")
+        .value_hint(ValueHint::FilePath),
+    )
+    .arg(
+      Arg::new("tls-key")
+        .long("tls-key")
+        .value_name("FILE")
+        .help("The PEM encoded private key of the certificate passed to --tls-cert")
+        .requires("tls-cert")
+        .value_hint(ValueHint::FilePath),
+    )
     .arg(
       parallel_arg("multiple server workers")
     )
@@ -3561,6 +3962,16 @@ or <c>**/__tests__/**</>:
           .value_name("N")
           .value_parser(value_parser!(NonZeroUsize))
           .help_heading(TEST_HEADING))
+      .arg(
+        Arg::new("retry")
+          .long("retry")
+          .value_name("N")
+          .help(cstr!("Retry failed tests up to N times. Tests that pass on a retry are reported as flaky.
+  <p(245)>Can be overridden per test with <c>Deno.test({ retry })</>.</>"))
+          .require_equals(true)
+          .value_parser(value_parser!(usize))
+          .help_heading(TEST_HEADING),
+      )
       .arg(permit_no_files_arg().help_heading(TEST_HEADING))
       .arg(
         Arg::new("filter")
@@ -3579,6 +3990,26 @@ or <c>**/__tests__/**</>:
           .value_parser(value_parser!(u64))
           .help_heading(TEST_HEADING),
       )
+      .arg(
+        Arg::new("shard")
+          .long("shard")
+          .value_name("INDEX/COUNT")
+          .help(cstr!("Only run the test modules belonging to the given shard, e.g. <c>--shard=1/3</>.
+  <p(245)>Modules are split deterministically, so each shard can run in a separate process or CI job.</>"))
+          .require_equals(true)
+          .value_parser(parse_test_shard)
+          .conflicts_with("watch")
+          .help_heading(TEST_HEADING),
+      )
+      .arg(
+        Arg::new("shard-timings")
+          .long("shard-timings")
+          .value_name("PATH")
+          .value_hint(ValueHint::FilePath)
+          .help("Balance shards using the test durations recorded in a JUnit report of a previous run")
+          .requires("shard")
+          .help_heading(TEST_HEADING),
+      )
       .arg(
         Arg::new("coverage")
           .long("coverage")
@@ -3638,7 +4069,13 @@ or <c>**/__tests__/**</>:
         Arg::new("reporter")
           .long("reporter")
           .help("Select reporter to use. Default to 'pretty'")
-          .value_parser(["pretty", "dot", "junit", "tap"])
+          .value_parser([
+            PossibleValue::new("pretty"),
+            PossibleValue::new("dot"),
+            PossibleValue::new("junit"),
+            PossibleValue::new("tap"),
+            PossibleValue::new("node-test").hide(true),
+          ])
           .help_heading(TEST_HEADING)
       )
       .arg(
@@ -5035,6 +5472,9 @@ fn bench_parse(
     no_run,
     permit_no_files: permit_no_files_parse(matches),
     watch: watch_arg_parse(matches)?,
+    save_baseline: matches.remove_one::<String>("save-baseline"),
+    baseline: matches.remove_one::<String>("baseline"),
+    regression_threshold: matches.remove_one::<u32>("regression-threshold"),
   });
 
   Ok(())
@@ -5220,6 +5660,10 @@ fn coverage_parse(
   };
   let r#type = if matches.get_flag("lcov") {
     CoverageType::Lcov
+  } else if matches.get_flag("cobertura") {
+    CoverageType::Cobertura
+  } else if matches.get_flag("json-summary") {
+    CoverageType::JsonSummary
   } else if matches.get_flag("html") {
     CoverageType::Html
   } else if matches.get_flag("detailed") {
@@ -5228,6 +5672,11 @@ fn coverage_parse(
     CoverageType::Summary
   };
   let output = matches.remove_one::<String>("output");
+  let thresholds = CoverageThresholdFlags {
+    lines: matches.remove_one::<f64>("threshold-lines"),
+    branches: matches.remove_one::<f64>("threshold-branches"),
+    functions: matches.remove_one::<f64>("threshold-functions"),
+  };
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -5237,6 +5686,8 @@ fn coverage_parse(
     include,
     exclude,
     r#type,
+    thresholds,
+    diff_base: matches.remove_one::<String>("diff-base"),
   });
   Ok(())
 }
@@ -5614,6 +6065,67 @@ fn jupyter_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   });
 }
 
+fn kv_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let (subcommand, mut matches) = matches.remove_subcommand().unwrap();
+  if subcommand == "queue" {
+    kv_queue_parse(flags, &mut matches);
+    return;
+  }
+  let database = matches.remove_one::<String>("database").unwrap();
+  let kv_flags = match subcommand.as_str() {
+    "backup" => KvFlags::Backup {
+      database,
+      output: matches.remove_one::<String>("output").unwrap(),
+    },
+    "restore" => KvFlags::Restore {
+      backup: matches.remove_one::<String>("backup").unwrap(),
+      database,
+    },
+    "export" => KvFlags::Export {
+      database,
+      prefix: matches.remove_one::<String>("prefix"),
+      start: matches.remove_one::<String>("start"),
+      end: matches.remove_one::<String>("end"),
+      output: matches.remove_one::<String>("output"),
+    },
+    "import" => KvFlags::Import {
+      database,
+      input: matches.remove_one::<String>("input"),
+    },
+    _ => unreachable!(),
+  };
+  flags.subcommand = DenoSubcommand::Kv(kv_flags);
+}
+
+fn kv_queue_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let (subcommand, mut matches) = matches.remove_subcommand().unwrap();
+  let database = matches.remove_one::<String>("database").unwrap();
+  let kv_flags = match subcommand.as_str() {
+    "list" => KvFlags::QueueList {
+      database,
+      states: matches.remove_many::<String>("state").unwrap().collect(),
+      limit: matches.remove_one::<usize>("limit"),
+    },
+    "purge" => KvFlags::QueuePurge {
+      database,
+      states: matches.remove_many::<String>("state").unwrap().collect(),
+    },
+    "redrive" => KvFlags::QueueRedrive {
+      database,
+      ids: matches
+        .remove_many::<String>("ids")
+        .map(|ids| ids.collect())
+        .unwrap_or_default(),
+    },
+    "track" => KvFlags::QueueTrack {
+      database,
+      disable: matches.get_flag("disable"),
+    },
+    _ => unreachable!(),
+  };
+  flags.subcommand = DenoSubcommand::Kv(kv_flags);
+}
+
 fn uninstall_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   lock_args_parse(flags, matches);
   let name = matches.remove_one::<String>("name-or-package").unwrap();
@@ -5795,6 +6307,8 @@ fn serve_parse(
     .remove_one::<String>("host")
     .unwrap_or_else(|| "0.0.0.0".to_owned());
   let open_site = matches.remove_one::<bool>("open").unwrap_or(false);
+  let tls_cert = matches.remove_one::<String>("tls-cert");
+  let tls_key = matches.remove_one::<String>("tls-key");
 
   runtime_args_parse(flags, matches, true, true, true)?;
   // If the user didn't pass --allow-net, add this port to the network
@@ -5842,6 +6356,8 @@ fn serve_parse(
     host,
     parallel: matches.get_flag("parallel"),
     open_site,
+    tls_cert,
+    tls_key,
   });
 
   Ok(())
@@ -5971,13 +6487,19 @@ fn test_parse(
         "junit" => TestReporterConfig::Junit,
         "dot" => TestReporterConfig::Dot,
         "tap" => TestReporterConfig::Tap,
+        "node-test" => TestReporterConfig::NodeTest,
         _ => unreachable!(),
       }
     } else {
       TestReporterConfig::Pretty
     };
 
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+  if matches!(
+    reporter,
+    TestReporterConfig::Dot
+      | TestReporterConfig::Tap
+      | TestReporterConfig::NodeTest
+  ) {
     flags.log_level = Some(Level::Error);
   }
 
@@ -5990,6 +6512,7 @@ fn test_parse(
     coverage_raw_data_only: matches.get_flag("coverage-raw-data-only"),
     clean,
     fail_fast,
+    retry: matches.remove_one::<usize>("retry").unwrap_or(0),
     files: FileFlags { include, ignore },
     filter,
     shuffle,
@@ -6000,6 +6523,8 @@ fn test_parse(
     reporter,
     junit_path,
     hide_stacktraces,
+    shard: matches.remove_one::<TestShard>("shard"),
+    shard_timings: matches.remove_one::<String>("shard-timings"),
   });
   Ok(())
 }
@@ -7267,6 +7792,45 @@ mod tests {
     );
   }
 
+  #[test]
+  fn serve_tls() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "serve",
+      "--tls-cert=cert.pem",
+      "--tls-key",
+      "key.pem",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Serve(ServeFlags {
+          tls_cert: Some("cert.pem".to_string()),
+          tls_key: Some("key.pem".to_string()),
+          ..ServeFlags::new_default("main.ts".to_string(), 8000, "0.0.0.0")
+        }),
+        permissions: PermissionFlags {
+          allow_net: Some(vec![
+            "0.0.0.0:8000".to_string(),
+            "127.0.0.1:8000".to_string(),
+            "localhost:8000".to_string()
+          ]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "serve", "--tls-cert=cert.pem", "main.ts"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "serve", "--tls-key=key.pem", "main.ts"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn has_permission() {
     let r = flags_from_vec(svec!["deno", "--allow-read", "x.ts"]);
@@ -9999,6 +10563,7 @@ mod tests {
           no_run: true,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: Some("- foo".to_string()),
           permit_no_files: true,
           files: FileFlags {
@@ -10015,6 +10580,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         no_npm: true,
         no_remote: true,
@@ -10106,6 +10673,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: Some(NonZeroUsize::new(3).unwrap()),
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: None,
@@ -10122,6 +10690,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -10150,6 +10720,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: None,
@@ -10166,6 +10737,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10250,6 +10823,24 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=node-test"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::NodeTest,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
       "test",
@@ -10288,6 +10879,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: Some(1),
@@ -10304,6 +10896,64 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--shard=2/3",
+      "--shard-timings",
+      "report.xml"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          shard: Some(TestShard { index: 2, count: 3 }),
+          shard_timings: Some("report.xml".to_string()),
+          ..TestFlags::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    for invalid in ["0/3", "4/3", "1", "a/b", "1/0"] {
+      let r =
+        flags_from_vec(svec!["deno", "test", format!("--shard={invalid}")]);
+      assert!(r.is_err(), "{invalid}");
+    }
+
+    let r = flags_from_vec(svec!["deno", "test", "--shard-timings=report.xml"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_retry() {
+    let r = flags_from_vec(svec!["deno", "test", "--retry=2"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          retry: 2,
+          ..TestFlags::default()
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10313,6 +10963,9 @@ mod tests {
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "test", "--retry=-1"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -10325,6 +10978,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: None,
@@ -10341,6 +10995,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10361,6 +11017,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: None,
@@ -10377,6 +11034,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -10399,6 +11058,7 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retry: 0,
           filter: None,
           permit_no_files: false,
           shuffle: None,
@@ -10420,6 +11080,8 @@ mod tests {
           reporter: Default::default(),
           junit_path: None,
           hide_stacktraces: false,
+          shard: None,
+          shard_timings: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -11144,10 +11806,59 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(String::from("foo.lcov")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_with_cobertura_and_json_summary() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--cobertura",
+      "--output=cobertura.xml",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Cobertura,
+          output: Some(String::from("cobertura.xml")),
+          ..CoverageFlags::default()
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--json-summary"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Coverage(CoverageFlags {
+        files: FileFlags {
+          include: vec!["coverage".to_string()],
+          ignore: vec![],
+        },
+        include: vec![r"^file:".to_string()],
+        exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+        r#type: CoverageType::JsonSummary,
+        ..CoverageFlags::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--output=out.txt"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--lcov", "--cobertura"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -11170,6 +11881,43 @@ mod tests {
     );
   }
 
+  #[test]
+  fn coverage_with_thresholds() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--threshold-lines=80",
+      "--threshold-functions=99.5",
+      "--diff-base=origin/main",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          thresholds: CoverageThresholdFlags {
+            lines: Some(80.0),
+            branches: None,
+            functions: Some(99.5),
+          },
+          diff_base: Some("origin/main".to_string()),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--threshold-lines=101"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "coverage", "--threshold-lines=NaN"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn location_with_bad_scheme() {
     #[rustfmt::skip]
@@ -11602,6 +12350,9 @@ mod tests {
           },
           watch: Default::default(),
           permit_no_files: false,
+          save_baseline: None,
+          baseline: None,
+          regression_threshold: None,
         }),
         no_npm: true,
         no_remote: true,
@@ -11633,7 +12384,10 @@ mod tests {
             ignore: vec![],
           },
           watch: Some(Default::default()),
-          permit_no_files: false
+          permit_no_files: false,
+          save_baseline: None,
+          baseline: None,
+          regression_threshold: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -11660,7 +12414,10 @@ mod tests {
             ignore: vec![],
           },
           watch: None,
-          permit_no_files: true
+          permit_no_files: true,
+          save_baseline: None,
+          baseline: None,
+          regression_threshold: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -11672,6 +12429,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn bench_baselines() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--save-baseline=pr-123",
+      "--baseline=main",
+      "--regression-threshold=5",
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bench(BenchFlags {
+        save_baseline: Some("pr-123".to_string()),
+        baseline: Some("main".to_string()),
+        regression_threshold: Some(5),
+        ..BenchFlags::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "bench", "--baseline=../main"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "bench", "--regression-threshold=5"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "bench", "--save-baseline=main", "--watch"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_with_check() {
     let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
@@ -12733,6 +13518,163 @@ Usage: deno repl [OPTIONS] [-- [ARGS]...]\n"
     }
   }
 
+  #[test]
+  fn kv_subcommand() {
+    let r = flags_from_vec(svec!["deno", "kv", "backup", "kv.db", "out.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::Backup {
+          database: "kv.db".to_string(),
+          output: "out.db".to_string(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv", "restore", "out.db", "kv.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::Restore {
+          backup: "out.db".to_string(),
+          database: "kv.db".to_string(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "kv",
+      "export",
+      "kv.db",
+      "--prefix",
+      "[\"users\"]",
+      "-o",
+      "users.ndjson"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::Export {
+          database: "kv.db".to_string(),
+          prefix: Some("[\"users\"]".to_string()),
+          start: None,
+          end: None,
+          output: Some("users.ndjson".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv", "import", "kv.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::Import {
+          database: "kv.db".to_string(),
+          input: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv", "queue", "list", "kv.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueueList {
+          database: "kv.db".to_string(),
+          states: svec!["pending", "running", "dead"],
+          limit: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno", "kv", "queue", "list", "kv.db", "--state", "dead", "--limit",
+      "10"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueueList {
+          database: "kv.db".to_string(),
+          states: svec!["dead"],
+          limit: Some(10),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv", "queue", "purge", "kv.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueuePurge {
+          database: "kv.db".to_string(),
+          states: svec!["pending"],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno", "kv", "queue", "redrive", "kv.db", "a", "b"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueueRedrive {
+          database: "kv.db".to_string(),
+          ids: svec!["a", "b"],
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv", "queue", "track", "kv.db"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueueTrack {
+          database: "kv.db".to_string(),
+          disable: false,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "kv",
+      "queue",
+      "track",
+      "kv.db",
+      "--disable"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags::QueueTrack {
+          database: "kv.db".to_string(),
+          disable: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno", "kv", "queue", "purge", "kv.db", "--state", "done"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "kv"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn clean_subcommand() {
     let cases = [
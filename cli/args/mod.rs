@@ -51,6 +51,7 @@ use deno_resolver::factory::resolve_jsr_url;
 use deno_runtime::deno_permissions::AllowRunDescriptor;
 use deno_runtime::deno_permissions::PathDescriptor;
 use deno_runtime::deno_permissions::PermissionsOptions;
+use deno_runtime::deno_tls::TlsKeyFiles;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_semver::StackString;
 use deno_semver::npm::NpmPackageReqReference;
@@ -85,6 +86,9 @@ pub struct WorkspaceBenchOptions {
   pub json: bool,
   pub no_run: bool,
   pub permit_no_files: bool,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  pub regression_threshold: u32,
 }
 
 impl WorkspaceBenchOptions {
@@ -94,6 +98,9 @@ impl WorkspaceBenchOptions {
       json: bench_flags.json,
       no_run: bench_flags.no_run,
       permit_no_files: bench_flags.permit_no_files,
+      save_baseline: bench_flags.save_baseline.clone(),
+      baseline: bench_flags.baseline.clone(),
+      regression_threshold: bench_flags.regression_threshold.unwrap_or(10),
     }
   }
 }
@@ -198,6 +205,7 @@ pub struct WorkspaceTestOptions {
   pub doc: bool,
   pub no_run: bool,
   pub fail_fast: Option<NonZeroUsize>,
+  pub retry: usize,
   pub permit_no_files: bool,
   pub filter: Option<String>,
   pub shuffle: Option<u64>,
@@ -206,6 +214,8 @@ pub struct WorkspaceTestOptions {
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
   pub hide_stacktraces: bool,
+  pub shard: Option<TestShard>,
+  pub shard_timings: Option<String>,
 }
 
 impl WorkspaceTestOptions {
@@ -215,6 +225,7 @@ impl WorkspaceTestOptions {
       concurrent_jobs: parallelism_count(test_flags.parallel),
       doc: test_flags.doc,
       fail_fast: test_flags.fail_fast,
+      retry: test_flags.retry,
       filter: test_flags.filter.clone(),
       no_run: test_flags.no_run,
       shuffle: test_flags.shuffle,
@@ -222,6 +233,8 @@ impl WorkspaceTestOptions {
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path.clone(),
       hide_stacktraces: test_flags.hide_stacktraces,
+      shard: test_flags.shard,
+      shard_timings: test_flags.shard_timings.clone(),
     }
   }
 }
@@ -586,6 +599,10 @@ impl CliOptions {
     )
   }
 
+  pub fn permission_args(&self) -> Vec<String> {
+    self.flags.to_permission_args()
+  }
+
   pub fn node_ipc_fd(&self) -> Option<i64> {
     let maybe_node_channel_fd = std::env::var("NODE_CHANNEL_FD").ok();
     if let Some(node_channel_fd) = maybe_node_channel_fd {
@@ -617,6 +634,25 @@ impl CliOptions {
     }
   }
 
+  /// The certificate and key that `deno serve` serves HTTPS with, from the
+  /// flags or else the `serve` config.
+  pub fn serve_tls(&self) -> Result<Option<TlsKeyFiles>, AnyError> {
+    let DenoSubcommand::Serve(flags) = self.sub_command() else {
+      return Ok(None);
+    };
+    if let (Some(cert), Some(key)) = (&flags.tls_cert, &flags.tls_key) {
+      return Ok(Some(TlsKeyFiles {
+        cert: self.initial_cwd().join(cert),
+        key: self.initial_cwd().join(key),
+      }));
+    }
+    let config = self.start_dir.to_serve_config()?;
+    Ok(config.and_then(|config| config.tls).map(|tls| TlsKeyFiles {
+      cert: tls.cert,
+      key: tls.key,
+    }))
+  }
+
   pub fn eszip(&self) -> bool {
     self.flags.eszip
   }
@@ -1090,8 +1090,10 @@ impl CliFactory {
         .unsafely_ignore_certificate_errors()
         .clone(),
       node_ipc: cli_options.node_ipc_fd(),
+      permission_args: cli_options.permission_args(),
       serve_port: cli_options.serve_port(),
       serve_host: cli_options.serve_host(),
+      serve_tls: cli_options.serve_tls()?,
       otel_config: cli_options.otel_config(),
       no_legacy_abort: cli_options.no_legacy_abort(),
       startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
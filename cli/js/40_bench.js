@@ -23,6 +23,7 @@ const {
   MathMax,
   MathMin,
   MathCeil,
+  MathSqrt,
   SymbolToStringTag,
   TypeError,
 } = primordials;
@@ -220,6 +221,15 @@ function benchStats(
   all,
   allLength,
 ) {
+  // sample standard deviation, used to tell noise apart from real changes
+  // when comparing against a baseline
+  let sum = 0;
+  for (let i = 0; i < allLength; i++) sum += all[i];
+  const mean = sum / allLength;
+  let squares = 0;
+  for (let i = 0; i < allLength; i++) {
+    squares += (all[i] - mean) * (all[i] - mean);
+  }
   return {
     n,
     min,
@@ -229,6 +239,7 @@ function benchStats(
     p995: all[MathCeil(allLength * (99.5 / 100)) - 1],
     p999: all[MathCeil(allLength * (99.9 / 100)) - 1],
     avg: !highPrecision ? (avg / n) : MathCeil(avg / n),
+    stdDev: allLength > 1 ? MathSqrt(squares / (allLength - 1)) : 0,
     highPrecision,
     usedExplicitTimers,
   };
@@ -22,6 +22,7 @@ const {
   Map,
   MapPrototypeGet,
   MapPrototypeSet,
+  NumberIsInteger,
   SafeArrayIterator,
   SymbolToStringTag,
   TypeError,
@@ -46,6 +47,7 @@ const DenoNs = globalThis.Deno;
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
  *   permissions: PermissionOptions,
+ *   retry?: number,
  * }} TestDescription
  *
  * @typedef {{
@@ -117,6 +119,10 @@ function assertExit(fn, isTest) {
 
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
+    // reset the state left behind by a previous attempt when retrying
+    const state = MapPrototypeGet(testStates, desc.id);
+    state.children = [];
+    state.completed = false;
     try {
       if (desc.ignore) {
         return "ignored";
@@ -125,7 +131,6 @@ function wrapOuter(fn, desc) {
     } catch (error) {
       return { failed: { jsError: core.destructureError(error) } };
     } finally {
-      const state = MapPrototypeGet(testStates, desc.id);
       for (const childDesc of state.children) {
         stepReportResult(childDesc, { failed: "incomplete" }, 0);
       }
@@ -292,6 +297,15 @@ function testInner(
 
   testDesc = { ...testDesc, ...overrides };
 
+  if (
+    testDesc.retry !== undefined &&
+    (!NumberIsInteger(testDesc.retry) || testDesc.retry < 0)
+  ) {
+    throw new TypeError(
+      `Invalid test retry count: expected a non-negative integer, received ${testDesc.retry}`,
+    );
+  }
+
   // Delete this prop in case the user passed it. It's used to detect steps.
   delete testDesc.parent;
 
@@ -310,6 +324,7 @@ function testInner(
     testDesc.only,
     testDesc.sanitizeOps,
     testDesc.sanitizeResources,
+    testDesc.retry,
     testDesc.location.fileName,
     testDesc.location.lineNumber,
     testDesc.location.columnNumber,
@@ -37,6 +37,7 @@ use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::deno_process::NpmProcessStateProviderRc;
 use deno_runtime::deno_telemetry::OtelConfig;
 use deno_runtime::deno_tls::RootCertStoreProvider;
+use deno_runtime::deno_tls::TlsKeyFiles;
 use deno_runtime::deno_web::BlobStore;
 use deno_runtime::fmt_errors::format_js_error;
 use deno_runtime::inspector_server::InspectorServer;
@@ -346,10 +347,12 @@ pub struct LibMainWorkerOptions {
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub skip_op_registration: bool,
   pub node_ipc: Option<i64>,
+  pub permission_args: Vec<String>,
   pub no_legacy_abort: bool,
   pub startup_snapshot: Option<&'static [u8]>,
   pub serve_port: Option<u16>,
   pub serve_host: Option<String>,
+  pub serve_tls: Option<TlsKeyFiles>,
 }
 
 #[derive(Default, Clone)]
@@ -427,6 +430,14 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
       let maybe_storage_key = shared
         .storage_key_resolver
         .resolve_storage_key(&args.main_module);
+      let origin_storage_dir = maybe_storage_key.as_ref().map(|key| {
+        shared
+          .options
+          .origin_data_folder_path
+          .as_ref()
+          .unwrap() // must be set if storage key resolver returns a value
+          .join(checksum::r#gen(&[key.as_bytes()]))
+      });
       let cache_storage_dir = maybe_storage_key.map(|key| {
         // TODO(@satyarohith): storage quota management
         get_cache_storage_dir().join(checksum::r#gen(&[key.as_bytes()]))
@@ -486,9 +497,11 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
           argv0: shared.options.argv0.clone(),
           node_debug: shared.options.node_debug.clone(),
           node_ipc_fd: None,
+          permission_args: Vec::new(),
           mode: WorkerExecutionMode::Worker,
           serve_port: shared.options.serve_port,
           serve_host: shared.options.serve_host.clone(),
+          serve_tls: shared.options.serve_tls.clone(),
           otel_config: shared.options.otel_config.clone(),
           no_legacy_abort: shared.options.no_legacy_abort,
           close_on_idle: args.close_on_idle,
@@ -506,6 +519,7 @@ impl<TSys: DenoLibSys> LibWorkerFactorySharedState<TSys> {
         worker_type: args.worker_type,
         stdio: stdio.clone(),
         cache_storage_dir,
+        origin_storage_dir,
         trace_ops: shared.options.trace_ops.clone(),
         close_on_idle: args.close_on_idle,
         maybe_worker_metadata: args.maybe_worker_metadata,
@@ -675,10 +689,12 @@ impl<TSys: DenoLibSys> LibMainWorkerFactory<TSys> {
         argv0: shared.options.argv0.clone(),
         node_debug: shared.options.node_debug.clone(),
         node_ipc_fd: shared.options.node_ipc,
+        permission_args: shared.options.permission_args.clone(),
         mode,
         no_legacy_abort: shared.options.no_legacy_abort,
         serve_port: shared.options.serve_port,
         serve_host: shared.options.serve_host.clone(),
+        serve_tls: shared.options.serve_tls.clone(),
         otel_config: shared.options.otel_config.clone(),
         close_on_idle: true,
       },
@@ -329,6 +329,7 @@ impl TestRun {
               filter,
               shuffle: None,
               trace_leaks: false,
+              retry: 0,
             },
           ))
         }
@@ -385,7 +386,9 @@ impl TestRun {
               if tests_with_result.insert(id) {
                 let description = tests.read().get(&id).unwrap().clone();
                 match &result {
-                  test::TestResult::Ok => summary.passed += 1,
+                  test::TestResult::Ok | test::TestResult::Flaky(_) => {
+                    summary.passed += 1
+                  }
                   test::TestResult::Ignored => summary.ignored += 1,
                   test::TestResult::Failed(error) => {
                     summary.failed += 1;
@@ -667,7 +670,7 @@ impl LspTestReporter {
   ) {
     self.current_test = None;
     match result {
-      test::TestResult::Ok => {
+      test::TestResult::Ok | test::TestResult::Flaky(_) => {
         let desc = self.tests.get(&desc.id).unwrap();
         self.progress(lsp_custom::TestRunProgressMessage::Passed {
           test: desc.as_test_identifier(&self.tests),
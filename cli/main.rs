@@ -181,6 +181,10 @@ async fn run_subcommand(
         coverage_flags.exclude,
         coverage_flags.output,
         &[&*reporter],
+        Some(tools::coverage::CoverageThresholdOptions {
+          flags: coverage_flags.thresholds,
+          diff_base: coverage_flags.diff_base,
+        }),
       )
     }),
     DenoSubcommand::Fmt(fmt_flags) => {
@@ -207,6 +211,9 @@ async fn run_subcommand(
     DenoSubcommand::Jupyter(jupyter_flags) => spawn_subcommand(async {
       tools::jupyter::kernel(flags, jupyter_flags).await
     }),
+    DenoSubcommand::Kv(kv_flags) => {
+      spawn_subcommand(async move { tools::kv::kv(kv_flags).await })
+    }
     DenoSubcommand::Uninstall(uninstall_flags) => spawn_subcommand(async {
       tools::installer::uninstall(flags, uninstall_flags).await
     }),
@@ -12,6 +12,7 @@ use deno_runtime::deno_permissions::ChildPermissionsArg;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use uuid::Uuid;
 
+use crate::tools::test::HeldTestStepEvents;
 use crate::tools::test::TestContainer;
 use crate::tools::test::TestDescription;
 use crate::tools::test::TestEvent;
@@ -101,6 +102,7 @@ fn op_register_test(
   only: bool,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  #[serde] retry: Option<usize>,
   #[string] file_name: String,
   #[smi] line_number: u32,
   #[smi] column_number: u32,
@@ -121,6 +123,7 @@ fn op_register_test(
     only,
     sanitize_ops,
     sanitize_resources,
+    retry,
     origin: origin.clone(),
     location: TestLocation {
       file_name,
@@ -182,15 +185,13 @@ fn op_register_test_step(
     root_id,
     root_name,
   };
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender.send(TestEvent::StepRegister(description)).ok();
+  send_step_event(state, TestEvent::StepRegister(description));
   id
 }
 
 #[op2(fast)]
 fn op_test_event_step_wait(state: &mut OpState, #[smi] id: usize) {
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender.send(TestEvent::StepWait(id)).ok();
+  send_step_event(state, TestEvent::StepWait(id));
 }
 
 #[op2(fast)]
@@ -199,10 +200,10 @@ fn op_test_event_step_result_ok(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender
-    .send(TestEvent::StepResult(id, TestStepResult::Ok, duration))
-    .ok();
+  send_step_event(
+    state,
+    TestEvent::StepResult(id, TestStepResult::Ok, duration),
+  );
 }
 
 #[op2(fast)]
@@ -211,10 +212,10 @@ fn op_test_event_step_result_ignored(
   #[smi] id: usize,
   #[smi] duration: u64,
 ) {
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender
-    .send(TestEvent::StepResult(id, TestStepResult::Ignored, duration))
-    .ok();
+  send_step_event(
+    state,
+    TestEvent::StepResult(id, TestStepResult::Ignored, duration),
+  );
 }
 
 #[op2]
@@ -224,12 +225,18 @@ fn op_test_event_step_result_failed(
   #[serde] failure: TestFailure,
   #[smi] duration: u64,
 ) {
-  let sender = state.borrow_mut::<TestEventSender>();
-  sender
-    .send(TestEvent::StepResult(
-      id,
-      TestStepResult::Failed(failure),
-      duration,
-    ))
-    .ok();
+  send_step_event(
+    state,
+    TestEvent::StepResult(id, TestStepResult::Failed(failure), duration),
+  );
+}
+
+/// Sends an event of a test step, unless the events of the current attempt
+/// are held back because it may be retried.
+fn send_step_event(state: &mut OpState, event: TestEvent) {
+  if let Some(held) = state.try_borrow_mut::<HeldTestStepEvents>() {
+    held.0.push(event);
+  } else {
+    state.borrow_mut::<TestEventSender>().send(event).ok();
+  }
 }
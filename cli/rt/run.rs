@@ -1021,8 +1021,10 @@ pub async fn run(
     unsafely_ignore_certificate_errors: metadata
       .unsafely_ignore_certificate_errors,
     node_ipc: None,
+    permission_args: Vec::new(),
     serve_port: None,
     serve_host: None,
+    serve_tls: None,
     otel_config: metadata.otel_config,
     no_legacy_abort: false,
     startup_snapshot: deno_snapshots::CLI_SNAPSHOT,
@@ -0,0 +1,363 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::path::PathBuf;
+
+use deno_core::anyhow::Context;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_lib::util::checksum;
+use deno_lib::version::DENO_VERSION_INFO;
+use deno_path_util::fs::atomic_write_file_with_retries;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::BenchDescription;
+use super::BenchStats;
+use super::mitata;
+use crate::colors;
+use crate::sys::CliSys;
+use crate::tools::test::fmt::to_relative_path_or_remote_url;
+
+const BASELINE_SCHEMA_VERSION: u8 = 1;
+
+/// The 97.5th percentile of the standard normal distribution, which makes
+/// the comparison a two-sided test at a 5% significance level.
+const Z_975: f64 = 1.959963984540054;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchBaselineFile {
+  version: u8,
+  runtime: String,
+  cpu: String,
+  benches: Vec<BenchBaselineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchBaselineEntry {
+  /// Path of the bench module relative to the workspace root, so baselines
+  /// can be compared across checkouts of the same repository.
+  origin: String,
+  group: Option<String>,
+  name: String,
+  stats: BenchStats,
+}
+
+/// Results of a previous run saved with `deno bench --save-baseline`.
+#[derive(Debug)]
+pub struct BenchBaseline {
+  name: String,
+  benches: Vec<BenchBaselineEntry>,
+}
+
+impl BenchBaseline {
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+/// Stores baselines by name for each workspace.
+pub struct BenchBaselineStore {
+  root: PathBuf,
+  workspace_root: Url,
+}
+
+impl BenchBaselineStore {
+  pub fn new(baselines_folder: PathBuf, workspace_root: Url) -> Self {
+    let root =
+      baselines_folder.join(checksum::r#gen(&[workspace_root.as_str()]));
+    Self {
+      root,
+      workspace_root,
+    }
+  }
+
+  fn path(&self, name: &str) -> PathBuf {
+    self.root.join(format!("{}.json", name))
+  }
+
+  pub fn load(&self, name: &str) -> Result<BenchBaseline, AnyError> {
+    let path = self.path(name);
+    let text = match std::fs::read_to_string(&path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        bail!(
+          "Baseline '{}' not found. Save it first with `deno bench --save-baseline={}`.",
+          name,
+          name
+        );
+      }
+      Err(err) => return Err(err.into()),
+    };
+    let file = serde_json::from_str::<BenchBaselineFile>(&text)
+      .ok()
+      .filter(|file| file.version == BASELINE_SCHEMA_VERSION)
+      .with_context(|| {
+        format!(
+          "Baseline '{}' is corrupt or was saved by an incompatible version of Deno. Save it again with `deno bench --save-baseline={}`.",
+          name, name
+        )
+      })?;
+    Ok(BenchBaseline {
+      name: name.to_string(),
+      benches: file.benches,
+    })
+  }
+
+  pub fn save(
+    &self,
+    name: &str,
+    measurements: &[(BenchDescription, BenchStats)],
+  ) -> Result<(), AnyError> {
+    let file = BenchBaselineFile {
+      version: BASELINE_SCHEMA_VERSION,
+      runtime: format!("{} {}", DENO_VERSION_INFO.user_agent, env!("TARGET")),
+      cpu: mitata::cpu::name(),
+      benches: measurements
+        .iter()
+        .filter(|(desc, _)| !desc.warmup)
+        .map(|(desc, stats)| BenchBaselineEntry {
+          origin: self.relative_origin(&desc.origin),
+          group: desc.group.clone(),
+          name: desc.name.clone(),
+          stats: stats.clone(),
+        })
+        .collect(),
+    };
+    std::fs::create_dir_all(&self.root)?;
+    atomic_write_file_with_retries(
+      &CliSys::default(),
+      &self.path(name),
+      &serde_json::to_vec_pretty(&file)?,
+      crate::cache::CACHE_PERM,
+    )
+    .with_context(|| format!("Failed saving baseline '{}'.", name))?;
+    Ok(())
+  }
+
+  fn relative_origin(&self, origin: &str) -> String {
+    to_relative_path_or_remote_url(&self.workspace_root, origin)
+  }
+
+  /// Compares the measurements of this run with the ones in the baseline.
+  pub fn compare<'a>(
+    &self,
+    baseline: &'a BenchBaseline,
+    measurements: &'a [(BenchDescription, BenchStats)],
+    regression_threshold: u32,
+  ) -> Vec<BenchComparison<'a>> {
+    measurements
+      .iter()
+      .filter(|(desc, _)| !desc.warmup)
+      .map(|(desc, current)| {
+        let origin = self.relative_origin(&desc.origin);
+        let previous = baseline
+          .benches
+          .iter()
+          .find(|entry| {
+            entry.origin == origin
+              && entry.group == desc.group
+              && entry.name == desc.name
+          })
+          .map(|entry| &entry.stats);
+        let change = previous.map(|previous| {
+          BenchChange::new(previous, current, regression_threshold)
+        });
+        BenchComparison {
+          desc,
+          current,
+          previous,
+          change,
+        }
+      })
+      .collect()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BenchChangeKind {
+  /// The difference can be explained by noise.
+  NoChange,
+  Faster,
+  /// Slower, but within the regression threshold.
+  Slower,
+  /// Slower by more than the regression threshold.
+  Regressed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchChange {
+  /// Relative difference of the average time, in percent.
+  pub percent: f64,
+  pub kind: BenchChangeKind,
+}
+
+impl BenchChange {
+  fn new(
+    previous: &BenchStats,
+    current: &BenchStats,
+    regression_threshold: u32,
+  ) -> Self {
+    let percent = if previous.avg == 0.0 {
+      0.0
+    } else {
+      (current.avg - previous.avg) * 100.0 / previous.avg
+    };
+    let kind = if !is_significant(previous, current) {
+      BenchChangeKind::NoChange
+    } else if current.avg < previous.avg {
+      BenchChangeKind::Faster
+    } else if percent > regression_threshold as f64 {
+      BenchChangeKind::Regressed
+    } else {
+      BenchChangeKind::Slower
+    };
+    Self { percent, kind }
+  }
+}
+
+pub struct BenchComparison<'a> {
+  pub desc: &'a BenchDescription,
+  pub current: &'a BenchStats,
+  pub previous: Option<&'a BenchStats>,
+  pub change: Option<BenchChange>,
+}
+
+impl BenchComparison<'_> {
+  pub fn is_regression(&self) -> bool {
+    self
+      .change
+      .is_some_and(|change| change.kind == BenchChangeKind::Regressed)
+  }
+}
+
+/// Welch's t-test of the difference between the average times, which doesn't
+/// assume both runs have the same variance.
+fn is_significant(a: &BenchStats, b: &BenchStats) -> bool {
+  if a.n < 2 || b.n < 2 {
+    return false;
+  }
+  let (n1, n2) = (a.n as f64, b.n as f64);
+  let v1 = a.std_dev * a.std_dev / n1;
+  let v2 = b.std_dev * b.std_dev / n2;
+  let standard_error = (v1 + v2).sqrt();
+  if standard_error == 0.0 {
+    return a.avg != b.avg;
+  }
+  let t = (a.avg - b.avg).abs() / standard_error;
+  let degrees_of_freedom =
+    (v1 + v2).powi(2) / (v1 * v1 / (n1 - 1.0) + v2 * v2 / (n2 - 1.0));
+  t > t_critical_value(degrees_of_freedom)
+}
+
+/// Approximates the 97.5th percentile of Student's t-distribution with the
+/// Cornish-Fisher expansion, which is accurate to a few decimals for three or
+/// more degrees of freedom.
+fn t_critical_value(degrees_of_freedom: f64) -> f64 {
+  let df = degrees_of_freedom.max(1.0);
+  let z = Z_975;
+  let z3 = z.powi(3);
+  let z5 = z.powi(5);
+  let z7 = z.powi(7);
+  z + (z3 + z) / (4.0 * df)
+    + (5.0 * z5 + 16.0 * z3 + 3.0 * z) / (96.0 * df.powi(2))
+    + (3.0 * z7 + 19.0 * z5 + 17.0 * z3 - 15.0 * z) / (384.0 * df.powi(3))
+}
+
+#[allow(clippy::print_stdout)]
+pub fn print_comparisons(
+  baseline: &BenchBaseline,
+  comparisons: &[BenchComparison],
+  store: &BenchBaselineStore,
+) {
+  println!(
+    "{}",
+    colors::gray(format!("Compared with baseline '{}'", baseline.name))
+  );
+  let mut current_origin = None;
+  for comparison in comparisons {
+    let origin = store.relative_origin(&comparison.desc.origin);
+    if current_origin.as_ref() != Some(&origin) {
+      println!("\n{}", colors::gray(&origin));
+      current_origin = Some(origin);
+    }
+    let name = match &comparison.desc.group {
+      Some(group) => {
+        format!("{} {}", colors::gray(group), comparison.desc.name)
+      }
+      None => comparison.desc.name.clone(),
+    };
+    let (Some(previous), Some(change)) =
+      (comparison.previous, comparison.change)
+    else {
+      println!("{} {}", name, colors::gray("(not in baseline)"));
+      continue;
+    };
+    let summary = format!(
+      "{} -> {} ({:+.1}%)",
+      mitata::fmt_duration(previous.avg),
+      mitata::fmt_duration(comparison.current.avg),
+      change.percent
+    );
+    let verdict = match change.kind {
+      BenchChangeKind::NoChange => colors::gray("no significant change"),
+      BenchChangeKind::Faster => colors::green("faster"),
+      BenchChangeKind::Slower => colors::yellow("slower"),
+      BenchChangeKind::Regressed => colors::red_bold("regressed"),
+    };
+    println!("{} {} {}", name, summary, verdict);
+  }
+  println!();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(n: u64, avg: f64, std_dev: f64) -> BenchStats {
+    BenchStats {
+      n,
+      min: avg - std_dev,
+      max: avg + std_dev,
+      avg,
+      std_dev,
+      p75: avg,
+      p99: avg,
+      p995: avg,
+      p999: avg,
+      high_precision: true,
+      used_explicit_timers: false,
+    }
+  }
+
+  #[test]
+  fn t_critical_values() {
+    // values from a t-distribution table
+    for (df, expected) in [(5.0, 2.571), (10.0, 2.228), (30.0, 2.042)] {
+      assert!((t_critical_value(df) - expected).abs() < 0.01, "df={df}");
+    }
+    assert!((t_critical_value(1e9) - Z_975).abs() < 1e-6);
+  }
+
+  #[test]
+  fn detects_significant_changes() {
+    let previous = stats(1000, 100.0, 10.0);
+    // noise
+    let change = BenchChange::new(&previous, &stats(1000, 100.5, 10.0), 10);
+    assert_eq!(change.kind, BenchChangeKind::NoChange);
+    // faster
+    let change = BenchChange::new(&previous, &stats(1000, 90.0, 10.0), 10);
+    assert_eq!(change.kind, BenchChangeKind::Faster);
+    // slower, within the threshold
+    let change = BenchChange::new(&previous, &stats(1000, 105.0, 10.0), 10);
+    assert_eq!(change.kind, BenchChangeKind::Slower);
+    assert_eq!(change.percent, 5.0);
+    // slower than the threshold allows
+    let change = BenchChange::new(&previous, &stats(1000, 120.0, 10.0), 10);
+    assert_eq!(change.kind, BenchChangeKind::Regressed);
+    // too noisy to tell
+    let change = BenchChange::new(&previous, &stats(3, 120.0, 50.0), 10);
+    assert_eq!(change.kind, BenchChangeKind::NoChange);
+  }
+}
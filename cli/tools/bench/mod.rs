@@ -37,6 +37,7 @@ use tokio::sync::mpsc::unbounded_channel;
 use crate::args::BenchFlags;
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::args::WorkspaceBenchOptions;
 use crate::colors;
 use crate::display::write_json_to_stdout;
 use crate::factory::CliFactory;
@@ -54,18 +55,60 @@ use crate::util::path::matches_pattern_or_exact_path;
 use crate::worker::CliMainWorkerFactory;
 use crate::worker::CreateCustomWorkerError;
 
+mod baseline;
 mod mitata;
 mod reporters;
 
+use baseline::BenchBaseline;
+use baseline::BenchBaselineStore;
 use reporters::BenchReporter;
 use reporters::ConsoleReporter;
 use reporters::JsonReporter;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 struct BenchSpecifierOptions {
   filter: TestFilter,
   json: bool,
   log_level: Option<log::Level>,
+  baselines: Option<Arc<BenchBaselineOptions>>,
+}
+
+/// Options of `--save-baseline` and `--baseline`.
+struct BenchBaselineOptions {
+  store: BenchBaselineStore,
+  save: Option<String>,
+  compare: Option<BenchBaseline>,
+  regression_threshold: u32,
+}
+
+impl BenchBaselineOptions {
+  fn resolve(
+    factory: &CliFactory,
+    options: &WorkspaceBenchOptions,
+  ) -> Result<Option<Arc<Self>>, AnyError> {
+    if options.save_baseline.is_none() && options.baseline.is_none() {
+      return Ok(None);
+    }
+    let store = BenchBaselineStore::new(
+      factory.deno_dir()?.bench_baselines_folder_path(),
+      factory
+        .cli_options()?
+        .workspace()
+        .root_dir_url()
+        .as_ref()
+        .clone(),
+    );
+    let compare = match &options.baseline {
+      Some(name) => Some(store.load(name)?),
+      None => None,
+    };
+    Ok(Some(Arc::new(Self {
+      store,
+      save: options.save_baseline.clone(),
+      compare,
+      regression_threshold: options.regression_threshold,
+    })))
+  }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -122,6 +165,7 @@ pub struct BenchStats {
   pub min: f64,
   pub max: f64,
   pub avg: f64,
+  pub std_dev: f64,
   pub p75: f64,
   pub p99: f64,
   pub p995: f64,
@@ -400,6 +444,32 @@ async fn bench_specifiers(
 
       reporter.report_end(&report);
 
+      let regressions = match options
+        .baselines
+        .as_ref()
+        .and_then(|baselines| Some((baselines, baselines.compare.as_ref()?)))
+      {
+        Some((baselines, baseline)) => {
+          let comparisons = baselines.store.compare(
+            baseline,
+            &report.measurements,
+            baselines.regression_threshold,
+          );
+          if !options.json {
+            baseline::print_comparisons(
+              baseline,
+              &comparisons,
+              &baselines.store,
+            );
+          }
+          comparisons
+            .iter()
+            .filter(|comparison| comparison.is_regression())
+            .count()
+        }
+        None => 0,
+      };
+
       if used_only {
         return Err(anyhow!(
           "Bench failed because the \"only\" option was used",
@@ -410,6 +480,28 @@ async fn bench_specifiers(
         return Err(anyhow!("Bench failed"));
       }
 
+      if let Some(baselines) = &options.baselines
+        && let Some(name) = &baselines.save
+      {
+        baselines.store.save(name, &report.measurements)?;
+        log::info!("{} baseline '{}'", colors::green("Saved"), name);
+      }
+
+      if regressions > 0 {
+        let baselines = options.baselines.as_ref().unwrap();
+        return Err(anyhow!(
+          "Bench failed because {} {} slower than baseline '{}' by more than {}%",
+          regressions,
+          if regressions == 1 {
+            "benchmark is"
+          } else {
+            "benchmarks are"
+          },
+          baselines.compare.as_ref().unwrap().name(),
+          baselines.regression_threshold,
+        ));
+      }
+
       Ok(())
     })
   };
@@ -498,6 +590,8 @@ pub async fn run_benchmarks(
     return Ok(());
   }
 
+  let baselines =
+    BenchBaselineOptions::resolve(&factory, &workspace_bench_options)?;
   let preload_modules = cli_options.preload_modules()?;
   let log_level = cli_options.log_level();
   let worker_factory =
@@ -512,6 +606,7 @@ pub async fn run_benchmarks(
       filter: TestFilter::from_flag(&workspace_bench_options.filter),
       json: workspace_bench_options.json,
       log_level,
+      baselines,
     },
   )
   .await?;
@@ -647,6 +742,7 @@ pub async fn run_benchmarks_with_watch(
             filter: TestFilter::from_flag(&workspace_bench_options.filter),
             json: workspace_bench_options.json,
             log_level,
+            baselines: None,
           },
         )
         .await?;
@@ -1,11 +1,15 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use deno_ast::MediaType;
@@ -15,18 +19,28 @@ use deno_config::glob::FileCollector;
 use deno_config::glob::FilePatterns;
 use deno_config::glob::PathOrPattern;
 use deno_config::glob::PathOrPatternSet;
+use deno_core::InspectorMsg;
+use deno_core::InspectorMsgKind;
 use deno_core::InspectorPostMessageError;
 use deno_core::InspectorPostMessageErrorKind;
+use deno_core::InspectorSessionKind;
+use deno_core::InspectorSessionOptions;
+use deno_core::JsRuntimeInspector;
 use deno_core::LocalInspectorSession;
 use deno_core::anyhow::Context;
 use deno_core::anyhow::anyhow;
 use deno_core::error::AnyError;
 use deno_core::error::CoreError;
+use deno_core::futures::channel::mpsc::UnboundedSender;
 use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use deno_core::serde_json::json;
 use deno_core::sourcemap::SourceMap;
 use deno_core::url::Url;
 use deno_error::JsErrorBox;
 use deno_resolver::npm::DenoInNpmPackageChecker;
+use deno_runtime::deno_node::V8CoverageHandler;
+use deno_runtime::deno_node::V8CoverageHandlerRc;
 use node_resolver::InNpmPackageChecker;
 use regex::Regex;
 use reporter::CoverageReporter;
@@ -52,17 +66,48 @@ mod ignore_directives;
 mod merge;
 mod range_tree;
 pub mod reporter;
+mod threshold;
 mod util;
 use merge::ProcessCoverage;
+pub use threshold::CoverageThresholdOptions;
+
+const PRECISE_COVERAGE_ARGS: cdp::StartPreciseCoverageArgs =
+  cdp::StartPreciseCoverageArgs {
+    call_count: true,
+    detailed: true,
+    allow_triggered_updates: false,
+  };
 
 pub struct CoverageCollector {
   pub dir: PathBuf,
   session: LocalInspectorSession,
+  /// Set by `v8.stopCoverage()`, nothing is written on exit afterwards.
+  stopped: Rc<Cell<bool>>,
 }
 
 impl CoverageCollector {
   pub fn new(dir: PathBuf, session: LocalInspectorSession) -> Self {
-    Self { dir, session }
+    Self {
+      dir,
+      session,
+      stopped: Rc::new(Cell::new(false)),
+    }
+  }
+
+  /// Creates the handler that lets `v8.takeCoverage()` and
+  /// `v8.stopCoverage()` of `node:v8` control this collection.
+  pub fn create_v8_coverage_handler(
+    &self,
+    inspector: Rc<RefCell<JsRuntimeInspector>>,
+  ) -> V8CoverageHandlerRc {
+    Rc::new(CoverageControl {
+      dir: self.dir.clone(),
+      inspector,
+      stopped: self.stopped.clone(),
+      session: RefCell::new(None),
+      next_id: Cell::new(1),
+      take_ids: Rc::new(RefCell::new(HashSet::new())),
+    })
   }
 
   pub async fn start_collecting(
@@ -70,49 +115,15 @@ impl CoverageCollector {
   ) -> Result<(), InspectorPostMessageError> {
     self.enable_debugger().await?;
     self.enable_profiler().await?;
-    self
-      .start_precise_coverage(cdp::StartPreciseCoverageArgs {
-        call_count: true,
-        detailed: true,
-        allow_triggered_updates: false,
-      })
-      .await?;
+    self.start_precise_coverage(PRECISE_COVERAGE_ARGS).await?;
 
     Ok(())
   }
 
   pub async fn stop_collecting(&mut self) -> Result<(), CoreError> {
-    fs::create_dir_all(&self.dir)?;
-
-    let script_coverages = self.take_precise_coverage().await?.result;
-    for script_coverage in script_coverages {
-      // Filter out internal and http/https JS files, eval'd scripts,
-      // and scripts with invalid urls from being included in coverage reports
-      if script_coverage.url.is_empty()
-        || script_coverage.url.starts_with("ext:")
-        || script_coverage.url.starts_with("[ext:")
-        || script_coverage.url.starts_with("http:")
-        || script_coverage.url.starts_with("https:")
-        || script_coverage.url.starts_with("node:")
-        || Url::parse(&script_coverage.url).is_err()
-      {
-        continue;
-      }
-
-      let filename = format!("{}.json", Uuid::new_v4());
-      let filepath = self.dir.join(filename);
-
-      let mut out = BufWriter::new(File::create(&filepath)?);
-      let coverage = serde_json::to_string(&script_coverage)
-        .map_err(JsErrorBox::from_err)?;
-      let formatted_coverage =
-        format_json(&filepath, &coverage, &Default::default())
-          .ok()
-          .flatten()
-          .unwrap_or(coverage);
-
-      out.write_all(formatted_coverage.as_bytes())?;
-      out.flush()?;
+    if !self.stopped.get() {
+      let script_coverages = self.take_precise_coverage().await?.result;
+      write_script_coverages(&self.dir, script_coverages)?;
     }
 
     self.disable_debugger().await?;
@@ -189,6 +200,136 @@ impl CoverageCollector {
   }
 }
 
+fn write_script_coverages(
+  dir: &Path,
+  script_coverages: Vec<cdp::ScriptCoverage>,
+) -> Result<(), CoreError> {
+  fs::create_dir_all(dir)?;
+
+  for script_coverage in script_coverages {
+    // Filter out internal and http/https JS files, eval'd scripts,
+    // and scripts with invalid urls from being included in coverage reports
+    if script_coverage.url.is_empty()
+      || script_coverage.url.starts_with("ext:")
+      || script_coverage.url.starts_with("[ext:")
+      || script_coverage.url.starts_with("http:")
+      || script_coverage.url.starts_with("https:")
+      || script_coverage.url.starts_with("node:")
+      || Url::parse(&script_coverage.url).is_err()
+    {
+      continue;
+    }
+
+    let filename = format!("{}.json", Uuid::new_v4());
+    let filepath = dir.join(filename);
+
+    let mut out = BufWriter::new(File::create(&filepath)?);
+    let coverage =
+      serde_json::to_string(&script_coverage).map_err(JsErrorBox::from_err)?;
+    let formatted_coverage =
+      format_json(&filepath, &coverage, &Default::default())
+        .ok()
+        .flatten()
+        .unwrap_or(coverage);
+
+    out.write_all(formatted_coverage.as_bytes())?;
+    out.flush()?;
+  }
+
+  Ok(())
+}
+
+/// Takes and stops the coverage of a `CoverageCollector` on behalf of
+/// `node:v8`. The ops that call it are sync, so it posts to an inspector
+/// session of its own and writes the coverage once V8 responds. V8 only lets
+/// the session that started precise coverage take it, so the session starts
+/// it again, in the same mode, when it's first used.
+struct CoverageControl {
+  dir: PathBuf,
+  inspector: Rc<RefCell<JsRuntimeInspector>>,
+  stopped: Rc<Cell<bool>>,
+  session: RefCell<Option<UnboundedSender<String>>>,
+  next_id: Cell<i32>,
+  /// The ids of the `Profiler.takePreciseCoverage` messages in flight.
+  take_ids: Rc<RefCell<HashSet<i32>>>,
+}
+
+impl CoverageControl {
+  fn connect(&self) -> UnboundedSender<String> {
+    let dir = self.dir.clone();
+    let take_ids = self.take_ids.clone();
+    self.inspector.borrow().create_raw_session(
+      InspectorSessionOptions {
+        kind: InspectorSessionKind::NonBlocking {
+          wait_for_disconnect: false,
+        },
+      },
+      Box::new(move |message: InspectorMsg| {
+        let InspectorMsgKind::Message(id) = message.kind else {
+          return;
+        };
+        if !take_ids.borrow_mut().remove(&id) {
+          return;
+        }
+
+        let result = serde_json::from_str::<Value>(&message.content)
+          .and_then(|mut message| {
+            serde_json::from_value::<cdp::TakePreciseCoverageResponse>(
+              message["result"].take(),
+            )
+          })
+          .map_err(|err| JsErrorBox::from_err(err).into())
+          .and_then(|response| write_script_coverages(&dir, response.result));
+        if let Err(err) = result {
+          log::warn!("Failed to write coverage: {err}");
+        }
+      }),
+    )
+  }
+
+  fn post_message(&self, method: &str, params: Option<Value>) -> i32 {
+    if self.session.borrow().is_none() {
+      *self.session.borrow_mut() = Some(self.connect());
+      self.post_message("Profiler.enable", None);
+      self.post_message(
+        "Profiler.startPreciseCoverage",
+        Some(json!(PRECISE_COVERAGE_ARGS)),
+      );
+    }
+
+    let id = self.next_id.get();
+    self.next_id.set(id + 1);
+    let mut message = json!({ "id": id, "method": method });
+    if let Some(params) = params {
+      message["params"] = params;
+    }
+    if let Some(session) = &*self.session.borrow() {
+      let _ = session.unbounded_send(message.to_string());
+    }
+    id
+  }
+}
+
+impl V8CoverageHandler for CoverageControl {
+  fn take_coverage(&self) {
+    if self.stopped.get() {
+      return;
+    }
+
+    // the response is only dispatched when the inspector is next polled
+    let id = self.post_message("Profiler.takePreciseCoverage", None);
+    self.take_ids.borrow_mut().insert(id);
+  }
+
+  fn stop_coverage(&self) {
+    if self.stopped.replace(true) {
+      return;
+    }
+
+    self.post_message("Profiler.stopPreciseCoverage", None);
+  }
+}
+
 #[derive(Debug, Clone)]
 struct BranchCoverageItem {
   line_index: usize,
@@ -603,6 +744,7 @@ fn filter_coverages(
     .collect::<Vec<cdp::ScriptCoverage>>()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn cover_files(
   flags: Arc<Flags>,
   files_include: Vec<String>,
@@ -611,6 +753,7 @@ pub fn cover_files(
   exclude: Vec<String>,
   output: Option<String>,
   reporters: &[&dyn CoverageReporter],
+  threshold_options: Option<CoverageThresholdOptions>,
 ) -> Result<(), AnyError> {
   if files_include.is_empty() {
     return Err(anyhow!("No matching coverage profiles found"));
@@ -756,7 +899,11 @@ pub fn cover_files(
   }
 
   for reporter in reporters {
-    reporter.done(&coverage_root, &file_reports);
+    reporter.done(&coverage_root, &file_reports)?;
+  }
+
+  if let Some(threshold_options) = threshold_options {
+    threshold::check_thresholds(cli_options, &file_reports, threshold_options)?;
   }
 
   Ok(())
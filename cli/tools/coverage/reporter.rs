@@ -1,6 +1,8 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::fs;
 use std::fs::File;
 use std::io::Error;
@@ -10,8 +12,10 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use deno_core::error::AnyError;
+use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_lib::version::DENO_VERSION_INFO;
+use serde::Serialize;
 
 use super::CoverageReport;
 use super::util;
@@ -24,6 +28,8 @@ pub struct CoverageStats<'a> {
   pub line_miss: usize,
   pub branch_hit: usize,
   pub branch_miss: usize,
+  pub function_hit: usize,
+  pub function_miss: usize,
   pub parent: Option<String>,
   pub file_text: Option<String>,
   pub report: Option<&'a CoverageReport>,
@@ -37,6 +43,8 @@ pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
     CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
     CoverageType::Detailed => Box::new(DetailedCoverageReporter::new()),
     CoverageType::Html => Box::new(HtmlCoverageReporter::new()),
+    CoverageType::Cobertura => Box::new(CoberturaCoverageReporter::new()),
+    CoverageType::JsonSummary => Box::new(JsonSummaryCoverageReporter::new()),
   }
 }
 
@@ -45,7 +53,7 @@ pub trait CoverageReporter {
     &self,
     coverage_root: &Path,
     file_reports: &[(CoverageReport, String)],
-  );
+  ) -> Result<(), AnyError>;
 
   /// Collects the coverage summary of each file or directory.
   fn collect_summary<'a>(
@@ -96,6 +104,16 @@ pub trait CoverageReporter {
         stats.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
         stats.branch_miss +=
           report.branches.iter().filter(|b| !b.is_hit).count();
+        stats.function_hit += report
+          .named_functions
+          .iter()
+          .filter(|f| f.execution_count > 0)
+          .count();
+        stats.function_miss += report
+          .named_functions
+          .iter()
+          .filter(|f| f.execution_count == 0)
+          .count();
 
         file_text = None;
         summary_path = path.parent();
@@ -175,7 +193,7 @@ impl CoverageReporter for SummaryCoverageReporter {
     &self,
     _coverage_root: &Path,
     file_reports: &[(CoverageReport, String)],
-  ) {
+  ) -> Result<(), AnyError> {
     let summary = self.collect_summary(file_reports);
     let root_stats = summary.get("").unwrap();
 
@@ -205,7 +223,34 @@ impl CoverageReporter for SummaryCoverageReporter {
       self.print_coverage_line(node, node_max, stats);
     });
     self.print_coverage_line("All files", node_max, root_stats);
+    Ok(())
+  }
+}
+
+/// Writes a report that covers all the files at once to the output file, or
+/// to stdout when no output file is specified.
+#[allow(clippy::print_stdout)]
+fn write_report(
+  file_reports: &[(CoverageReport, String)],
+  format_name: &str,
+  content: &str,
+) -> Result<(), AnyError> {
+  let output = file_reports
+    .first()
+    .and_then(|(report, _)| report.output.as_ref());
+  match output {
+    Some(output) => {
+      fs::write(output, content)?;
+      let url = Url::from_file_path(output.canonicalize()?).unwrap();
+      log::info!(
+        "{} coverage report has been generated at {}",
+        format_name,
+        url
+      );
+    }
+    None => print!("{}", content),
   }
+  Ok(())
 }
 
 pub struct LcovCoverageReporter {}
@@ -215,7 +260,7 @@ impl CoverageReporter for LcovCoverageReporter {
     &self,
     _coverage_root: &Path,
     file_reports: &[(CoverageReport, String)],
-  ) {
+  ) -> Result<(), AnyError> {
     file_reports.iter().for_each(|(report, file_text)| {
       self.report(report, file_text).unwrap();
     });
@@ -232,6 +277,7 @@ impl CoverageReporter for LcovCoverageReporter {
         );
       }
     }
+    Ok(())
   }
 }
 
@@ -338,10 +384,11 @@ impl CoverageReporter for DetailedCoverageReporter {
     &self,
     _coverage_root: &Path,
     file_reports: &[(CoverageReport, String)],
-  ) {
+  ) -> Result<(), AnyError> {
     file_reports.iter().for_each(|(report, file_text)| {
       self.report(report, file_text).unwrap();
     });
+    Ok(())
   }
 }
 
@@ -413,6 +460,273 @@ impl DetailedCoverageReporter {
   }
 }
 
+pub struct CoberturaCoverageReporter {}
+
+impl CoverageReporter for CoberturaCoverageReporter {
+  fn done(
+    &self,
+    _coverage_root: &Path,
+    file_reports: &[(CoverageReport, String)],
+  ) -> Result<(), AnyError> {
+    let xml = self.create_xml(file_reports);
+    write_report(file_reports, "Cobertura", &xml)
+  }
+}
+
+impl CoberturaCoverageReporter {
+  pub fn new() -> CoberturaCoverageReporter {
+    CoberturaCoverageReporter {}
+  }
+
+  fn create_xml(&self, file_reports: &[(CoverageReport, String)]) -> String {
+    let summary = self.collect_summary(file_reports);
+    let urls = file_reports.iter().map(|rep| &rep.0.url).collect();
+    let source = util::find_root(urls)
+      .and_then(|root| root.to_file_path().ok())
+      .map(|root| root.to_string_lossy().into_owned())
+      .unwrap_or_default();
+
+    // classes are the files, grouped into packages by their directory
+    let mut packages: BTreeMap<String, Vec<(String, &CoverageStats)>> =
+      BTreeMap::new();
+    for (node, stats) in &summary {
+      if stats.file_text.is_none() {
+        continue;
+      }
+      let package = match &stats.parent {
+        Some(parent) if !parent.is_empty() => parent.replace('\\', "/"),
+        _ => ".".to_string(),
+      };
+      packages
+        .entry(package)
+        .or_default()
+        .push((node.replace('\\', "/"), stats));
+    }
+
+    let root_stats = summary.get("").unwrap();
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" ?>\n");
+    xml.push_str("<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n");
+    writeln!(
+      xml,
+      "<coverage lines-valid=\"{}\" lines-covered=\"{}\" line-rate=\"{}\" branches-valid=\"{}\" branches-covered=\"{}\" branch-rate=\"{}\" timestamp=\"{}\" complexity=\"0\" version=\"{}\">",
+      root_stats.line_hit + root_stats.line_miss,
+      root_stats.line_hit,
+      format_rate(root_stats.line_hit, root_stats.line_miss),
+      root_stats.branch_hit + root_stats.branch_miss,
+      root_stats.branch_hit,
+      format_rate(root_stats.branch_hit, root_stats.branch_miss),
+      chrono::Utc::now().timestamp_millis(),
+      DENO_VERSION_INFO.deno,
+    )
+    .unwrap();
+    writeln!(xml, "  <sources>").unwrap();
+    writeln!(xml, "    <source>{}</source>", escape_xml(&source)).unwrap();
+    writeln!(xml, "  </sources>").unwrap();
+    writeln!(xml, "  <packages>").unwrap();
+    for (package, mut classes) in packages {
+      classes.sort_by(|a, b| a.0.cmp(&b.0));
+      let (line_hit, line_miss, branch_hit, branch_miss) = classes.iter().fold(
+        (0, 0, 0, 0),
+        |(line_hit, line_miss, branch_hit, branch_miss), (_, stats)| {
+          (
+            line_hit + stats.line_hit,
+            line_miss + stats.line_miss,
+            branch_hit + stats.branch_hit,
+            branch_miss + stats.branch_miss,
+          )
+        },
+      );
+      writeln!(
+        xml,
+        "    <package name=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">",
+        escape_xml(&package),
+        format_rate(line_hit, line_miss),
+        format_rate(branch_hit, branch_miss),
+      )
+      .unwrap();
+      writeln!(xml, "      <classes>").unwrap();
+      for (file_name, stats) in classes {
+        self.write_class(&mut xml, &file_name, stats);
+      }
+      writeln!(xml, "      </classes>").unwrap();
+      writeln!(xml, "    </package>").unwrap();
+    }
+    writeln!(xml, "  </packages>").unwrap();
+    writeln!(xml, "</coverage>").unwrap();
+    xml
+  }
+
+  fn write_class(
+    &self,
+    xml: &mut String,
+    file_name: &str,
+    stats: &CoverageStats,
+  ) {
+    let report = stats.report.unwrap();
+    let class_name = file_name.rsplit('/').next().unwrap_or(file_name);
+    writeln!(
+      xml,
+      "        <class name=\"{}\" filename=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">",
+      escape_xml(class_name),
+      escape_xml(file_name),
+      format_rate(stats.line_hit, stats.line_miss),
+      format_rate(stats.branch_hit, stats.branch_miss),
+    )
+    .unwrap();
+
+    writeln!(xml, "          <methods>").unwrap();
+    for function in &report.named_functions {
+      let hit = usize::from(function.execution_count > 0);
+      writeln!(
+        xml,
+        "            <method name=\"{}\" signature=\"\" hits=\"{}\" line-rate=\"{}\" branch-rate=\"1\" complexity=\"0\">",
+        escape_xml(&function.name),
+        function.execution_count,
+        format_rate(hit, 1 - hit),
+      )
+      .unwrap();
+      writeln!(xml, "              <lines>").unwrap();
+      writeln!(
+        xml,
+        "                <line number=\"{}\" hits=\"{}\"/>",
+        function.line_index + 1,
+        function.execution_count,
+      )
+      .unwrap();
+      writeln!(xml, "              </lines>").unwrap();
+      writeln!(xml, "            </method>").unwrap();
+    }
+    writeln!(xml, "          </methods>").unwrap();
+
+    writeln!(xml, "          <lines>").unwrap();
+    for (line_index, count) in &report.found_lines {
+      let (branch_hit, branch_total) = report
+        .branches
+        .iter()
+        .filter(|b| b.line_index == *line_index)
+        .fold((0, 0), |(hit, total), b| {
+          (hit + usize::from(b.is_hit), total + 1)
+        });
+      if branch_total == 0 {
+        writeln!(
+          xml,
+          "            <line number=\"{}\" hits=\"{}\" branch=\"false\"/>",
+          line_index + 1,
+          count,
+        )
+        .unwrap();
+      } else {
+        writeln!(
+          xml,
+          "            <line number=\"{}\" hits=\"{}\" branch=\"true\" condition-coverage=\"{}% ({}/{})\"/>",
+          line_index + 1,
+          count,
+          branch_hit * 100 / branch_total,
+          branch_hit,
+          branch_total,
+        )
+        .unwrap();
+      }
+    }
+    writeln!(xml, "          </lines>").unwrap();
+    writeln!(xml, "        </class>").unwrap();
+  }
+}
+
+fn format_rate(hit: usize, miss: usize) -> String {
+  let total = hit + miss;
+  if total == 0 {
+    "1".to_string()
+  } else {
+    format!("{:.4}", hit as f64 / total as f64)
+  }
+}
+
+fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+#[derive(Serialize)]
+struct JsonSummaryCounts {
+  total: usize,
+  covered: usize,
+  pct: f64,
+}
+
+impl JsonSummaryCounts {
+  fn new(hit: usize, miss: usize) -> Self {
+    let (total, percent, _) = util::calc_coverage_display_info(hit, miss);
+    Self {
+      total,
+      covered: hit,
+      // avoid float noise such as 66.66666412353516
+      pct: (percent as f64 * 100.0).round() / 100.0,
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct JsonSummaryEntry {
+  lines: JsonSummaryCounts,
+  branches: JsonSummaryCounts,
+  functions: JsonSummaryCounts,
+}
+
+impl From<&CoverageStats<'_>> for JsonSummaryEntry {
+  fn from(stats: &CoverageStats) -> Self {
+    Self {
+      lines: JsonSummaryCounts::new(stats.line_hit, stats.line_miss),
+      branches: JsonSummaryCounts::new(stats.branch_hit, stats.branch_miss),
+      functions: JsonSummaryCounts::new(
+        stats.function_hit,
+        stats.function_miss,
+      ),
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct JsonSummary {
+  total: JsonSummaryEntry,
+  /// Keyed by the path of the file relative to the common root of all files.
+  files: BTreeMap<String, JsonSummaryEntry>,
+}
+
+pub struct JsonSummaryCoverageReporter {}
+
+impl CoverageReporter for JsonSummaryCoverageReporter {
+  fn done(
+    &self,
+    _coverage_root: &Path,
+    file_reports: &[(CoverageReport, String)],
+  ) -> Result<(), AnyError> {
+    let summary = self.collect_summary(file_reports);
+    let json_summary = JsonSummary {
+      total: summary.get("").unwrap().into(),
+      files: summary
+        .iter()
+        .filter(|(_, stats)| stats.file_text.is_some())
+        .map(|(node, stats)| (node.replace('\\', "/"), stats.into()))
+        .collect(),
+    };
+    let mut json = serde_json::to_string_pretty(&json_summary).unwrap();
+    json.push('\n');
+    write_report(file_reports, "JSON summary", &json)
+  }
+}
+
+impl JsonSummaryCoverageReporter {
+  pub fn new() -> JsonSummaryCoverageReporter {
+    JsonSummaryCoverageReporter {}
+  }
+}
+
 pub struct HtmlCoverageReporter {}
 
 impl CoverageReporter for HtmlCoverageReporter {
@@ -420,7 +734,7 @@ impl CoverageReporter for HtmlCoverageReporter {
     &self,
     coverage_root: &Path,
     file_reports: &[(CoverageReport, String)],
-  ) {
+  ) -> Result<(), AnyError> {
     let summary = self.collect_summary(file_reports);
     let now = chrono::Utc::now().to_rfc2822();
 
@@ -448,6 +762,7 @@ impl CoverageReporter for HtmlCoverageReporter {
     .unwrap();
 
     log::info!("HTML coverage report has been generated at {}", root_report);
+    Ok(())
   }
 }
 
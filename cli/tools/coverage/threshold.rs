@@ -0,0 +1,338 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_config::deno_json::CoverageThresholds;
+use deno_config::glob::PathGlobMatch;
+use deno_core::anyhow::Context;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::CoverageReport;
+use crate::args::CliOptions;
+use crate::args::CoverageThresholdFlags;
+use crate::util::fs::canonicalize_path;
+
+/// Matches the header of a hunk in a unified diff, capturing the start line
+/// and line count of the new side.
+static HUNK_HEADER_RE: Lazy<Regex> =
+  lazy_regex::lazy_regex!(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@");
+
+pub struct CoverageThresholdOptions {
+  pub flags: CoverageThresholdFlags,
+  pub diff_base: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CoverageTotals {
+  line_hit: usize,
+  line_miss: usize,
+  branch_hit: usize,
+  branch_miss: usize,
+  function_hit: usize,
+  function_miss: usize,
+}
+
+impl CoverageTotals {
+  /// Adds the coverage of a file, only counting the given lines (1-based)
+  /// when provided.
+  fn add(&mut self, report: &CoverageReport, lines: Option<&HashSet<usize>>) {
+    let includes =
+      |line_index: usize| lines.is_none_or(|l| l.contains(&(line_index + 1)));
+    for (line_index, count) in &report.found_lines {
+      if includes(*line_index) {
+        if *count > 0 {
+          self.line_hit += 1;
+        } else {
+          self.line_miss += 1;
+        }
+      }
+    }
+    for branch in &report.branches {
+      if includes(branch.line_index) {
+        if branch.is_hit {
+          self.branch_hit += 1;
+        } else {
+          self.branch_miss += 1;
+        }
+      }
+    }
+    for function in &report.named_functions {
+      if includes(function.line_index) {
+        if function.execution_count > 0 {
+          self.function_hit += 1;
+        } else {
+          self.function_miss += 1;
+        }
+      }
+    }
+  }
+
+  /// Returns the failed thresholds as `(kind, actual, expected)`.
+  fn unmet(
+    &self,
+    thresholds: &CoverageThresholds,
+  ) -> Vec<(&'static str, f64, f64)> {
+    [
+      ("lines", self.line_hit, self.line_miss, thresholds.lines),
+      (
+        "branches",
+        self.branch_hit,
+        self.branch_miss,
+        thresholds.branches,
+      ),
+      (
+        "functions",
+        self.function_hit,
+        self.function_miss,
+        thresholds.functions,
+      ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, hit, miss, threshold)| {
+      let threshold = threshold?;
+      // nothing to cover, so there is nothing to enforce
+      if hit + miss == 0 {
+        return None;
+      }
+      let percent = hit as f64 * 100.0 / (hit + miss) as f64;
+      (percent < threshold).then_some((kind, percent, threshold))
+    })
+    .collect()
+  }
+}
+
+/// Checks the coverage against the thresholds of the config file and the
+/// command line, erroring when any of them is not met.
+pub fn check_thresholds(
+  cli_options: &CliOptions,
+  file_reports: &[(CoverageReport, String)],
+  options: CoverageThresholdOptions,
+) -> Result<(), AnyError> {
+  let config = cli_options
+    .start_dir
+    .to_coverage_config()?
+    .unwrap_or_default();
+  let thresholds = config.thresholds.merge(&CoverageThresholds {
+    lines: options.flags.lines,
+    branches: options.flags.branches,
+    functions: options.flags.functions,
+  });
+  if thresholds.is_empty() && config.file_thresholds.is_empty() {
+    if options.diff_base.is_some() {
+      log::warn!(
+        "Warning: --diff-base has no effect without coverage thresholds."
+      );
+    }
+    return Ok(());
+  }
+
+  let changed_lines = match &options.diff_base {
+    Some(base) => Some(git_changed_lines(cli_options.initial_cwd(), base)?),
+    None => None,
+  };
+
+  // the global totals come first, followed by one for each file threshold
+  let mut totals =
+    vec![CoverageTotals::default(); config.file_thresholds.len() + 1];
+  for (report, _) in file_reports {
+    let Ok(path) = report.url.to_file_path() else {
+      continue;
+    };
+    let lines = match &changed_lines {
+      Some(changed_lines) => {
+        let path = canonicalize_path(&path).unwrap_or_else(|_| path.clone());
+        match changed_lines.get(&path) {
+          Some(lines) => Some(lines),
+          None => continue,
+        }
+      }
+      None => None,
+    };
+    let index = config
+      .file_thresholds
+      .iter()
+      .position(|file_thresholds| {
+        file_thresholds.path_or_pattern.matches_path(&path)
+          == PathGlobMatch::Matched
+      })
+      .map(|index| index + 1)
+      .unwrap_or(0);
+    totals[index].add(report, lines);
+  }
+
+  // files matching a file threshold aren't part of the global totals
+  let global_name = if config.file_thresholds.is_empty() {
+    "All files"
+  } else {
+    "Other files"
+  };
+  let mut message = String::new();
+  let groups = std::iter::once((global_name, &thresholds)).chain(
+    config.file_thresholds.iter().map(|file_thresholds| {
+      (
+        file_thresholds.pattern.as_str(),
+        &file_thresholds.thresholds,
+      )
+    }),
+  );
+  for ((name, thresholds), totals) in groups.zip(&totals) {
+    for (kind, percent, threshold) in totals.unmet(thresholds) {
+      write!(
+        message,
+        "\n  {name}: {kind} {percent:.1}% (expected at least {threshold}%)"
+      )
+      .unwrap();
+    }
+  }
+  if message.is_empty() {
+    return Ok(());
+  }
+  match &options.diff_base {
+    Some(base) => bail!(
+      "Coverage thresholds not met for the lines changed since '{}':{}",
+      base,
+      message
+    ),
+    None => bail!("Coverage thresholds not met:{}", message),
+  }
+}
+
+/// Returns the lines (1-based) of each file that were added or modified
+/// relative to the `base` revision, including uncommitted changes.
+fn git_changed_lines(
+  cwd: &Path,
+  base: &str,
+) -> Result<HashMap<PathBuf, HashSet<usize>>, AnyError> {
+  let root = run_git(cwd, &["rev-parse", "--show-toplevel"])?;
+  let root = PathBuf::from(root.trim());
+  let root = canonicalize_path(&root).unwrap_or(root);
+  let diff = run_git(
+    cwd,
+    &[
+      "-c",
+      "core.quotePath=false",
+      "diff",
+      "--unified=0",
+      "--no-color",
+      "--no-ext-diff",
+      base,
+      "--",
+    ],
+  )?;
+  Ok(
+    parse_diff_changed_lines(&diff)
+      .into_iter()
+      .map(|(path, lines)| (root.join(path), lines))
+      .collect(),
+  )
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let bin_name = if cfg!(windows) { "git.exe" } else { "git" };
+  let output = std::process::Command::new(bin_name)
+    .current_dir(cwd)
+    .args(args)
+    .output()
+    .context("Failed to run git, which is required by --diff-base.")?;
+  if !output.status.success() {
+    bail!(
+      "Failed to run git {}: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Collects the added lines of each file in a diff with no context lines,
+/// keyed by the path relative to the repository root.
+fn parse_diff_changed_lines(diff: &str) -> HashMap<String, HashSet<usize>> {
+  let mut changed_lines: HashMap<String, HashSet<usize>> = HashMap::new();
+  let mut current_file: Option<String> = None;
+  for line in diff.lines() {
+    if let Some(path) = line.strip_prefix("+++ ") {
+      // deleted files are diffed against /dev/null
+      current_file = path.strip_prefix("b/").map(|path| path.to_string());
+    } else if let Some(captures) = HUNK_HEADER_RE.captures(line)
+      && let Some(file) = &current_file
+    {
+      let start = captures[1].parse::<usize>().unwrap_or(0);
+      let count = captures
+        .get(2)
+        .map(|count| count.as_str().parse::<usize>().unwrap_or(0))
+        .unwrap_or(1);
+      changed_lines
+        .entry(file.clone())
+        .or_default()
+        .extend(start..start + count);
+    }
+  }
+  changed_lines
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_changed_lines() {
+    let diff = r#"diff --git a/src/a.ts b/src/a.ts
+index 1111111..2222222 100644
+--- a/src/a.ts
++++ b/src/a.ts
+@@ -3 +3 @@ export function a() {
+-  return 1;
++  return 2;
+@@ -10,0 +11,3 @@ export function b() {
++export function c() {
++  return 3;
++}
+@@ -20,2 +23,0 @@
+-removed();
+-removed();
+diff --git a/old.ts b/old.ts
+deleted file mode 100644
+--- a/old.ts
++++ /dev/null
+@@ -1,2 +0,0 @@
+-a
+-b
+diff --git a/new file.ts b/new file.ts
+new file mode 100644
+--- /dev/null
++++ b/new file.ts
+@@ -0,0 +1,2 @@
++a
++b
+"#;
+    let changed_lines = parse_diff_changed_lines(diff);
+    assert_eq!(changed_lines.len(), 2);
+    assert_eq!(changed_lines["src/a.ts"], HashSet::from([3, 11, 12, 13]));
+    assert_eq!(changed_lines["new file.ts"], HashSet::from([1, 2]));
+  }
+
+  #[test]
+  fn unmet_thresholds() {
+    let totals = CoverageTotals {
+      line_hit: 3,
+      line_miss: 1,
+      branch_hit: 0,
+      branch_miss: 0,
+      function_hit: 1,
+      function_miss: 1,
+    };
+    let unmet = totals.unmet(&CoverageThresholds {
+      lines: Some(75.0),
+      branches: Some(100.0),
+      functions: Some(80.0),
+    });
+    assert_eq!(unmet, vec![("functions", 50.0, 80.0)]);
+  }
+}
@@ -0,0 +1,133 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_runtime::deno_kv::backup;
+use deno_runtime::deno_kv::backup::ExportSelector;
+use deno_runtime::deno_kv::queue_admin;
+use deno_runtime::deno_kv::queue_admin::JsonQueueMessage;
+use deno_runtime::deno_kv::queue_admin::QueueMessageState;
+
+use crate::args::KvFlags;
+use crate::colors;
+
+pub async fn kv(kv_flags: KvFlags) -> Result<(), AnyError> {
+  match kv_flags {
+    KvFlags::Backup { database, output } => {
+      backup::backup(Path::new(&database), Path::new(&output))
+        .with_context(|| format!("Failed to back up {database}"))?;
+      log::info!("{} {} to {}", colors::green("Backed up"), database, output);
+    }
+    KvFlags::Restore { backup, database } => {
+      backup::restore(Path::new(&backup), Path::new(&database))
+        .with_context(|| format!("Failed to restore {database}"))?;
+      log::info!("{} {} from {}", colors::green("Restored"), database, backup);
+    }
+    KvFlags::Export {
+      database,
+      prefix,
+      start,
+      end,
+      output,
+    } => {
+      let selector = ExportSelector {
+        prefix: prefix.as_deref().map(backup::parse_key).transpose()?,
+        start: start.as_deref().map(backup::parse_key).transpose()?,
+        end: end.as_deref().map(backup::parse_key).transpose()?,
+      };
+      let count = match &output {
+        Some(output) => {
+          let file = std::fs::File::create(output)
+            .with_context(|| format!("Failed to create {output}"))?;
+          backup::export(
+            Path::new(&database),
+            selector,
+            &mut BufWriter::new(file),
+          )
+          .await?
+        }
+        None => {
+          let stdout = std::io::stdout();
+          backup::export(
+            Path::new(&database),
+            selector,
+            &mut BufWriter::new(stdout.lock()),
+          )
+          .await?
+        }
+      };
+      if output.is_some() {
+        log::info!("{} {} entries", colors::green("Exported"), count);
+      }
+    }
+    KvFlags::Import { database, input } => {
+      let count = match &input {
+        Some(input) => {
+          let file = std::fs::File::open(input)
+            .with_context(|| format!("Failed to open {input}"))?;
+          backup::import(Path::new(&database), BufReader::new(file)).await?
+        }
+        None => {
+          backup::import(Path::new(&database), std::io::stdin().lock()).await?
+        }
+      };
+      log::info!("{} {} entries", colors::green("Imported"), count);
+    }
+    KvFlags::QueueList {
+      database,
+      states,
+      limit,
+    } => {
+      let messages = queue_admin::queue_list(
+        Path::new(&database),
+        parse_states(&states),
+        limit.unwrap_or(usize::MAX),
+      )
+      .await?;
+      let stdout = std::io::stdout();
+      let mut writer = BufWriter::new(stdout.lock());
+      for message in messages {
+        serde_json::to_writer(
+          &mut writer,
+          &JsonQueueMessage::try_from(message)?,
+        )?;
+        writer.write_all(b"\n")?;
+      }
+      writer.flush()?;
+    }
+    KvFlags::QueuePurge { database, states } => {
+      let count =
+        queue_admin::queue_purge(Path::new(&database), parse_states(&states))
+          .await?;
+      log::info!("{} {} messages", colors::green("Purged"), count);
+    }
+    KvFlags::QueueRedrive { database, ids } => {
+      let ids = if ids.is_empty() { None } else { Some(ids) };
+      let count = queue_admin::queue_redrive(Path::new(&database), ids).await?;
+      log::info!("{} {} messages", colors::green("Redrove"), count);
+    }
+    KvFlags::QueueTrack { database, disable } => {
+      queue_admin::queue_track(Path::new(&database), !disable).await?;
+      let action = if disable { "Disabled" } else { "Enabled" };
+      log::info!("{} delivery tracking", colors::green(action));
+    }
+  }
+  Ok(())
+}
+
+fn parse_states(states: &[String]) -> Vec<QueueMessageState> {
+  states
+    .iter()
+    .map(|state| match state.as_str() {
+      "pending" => QueueMessageState::Pending,
+      "running" => QueueMessageState::Running,
+      "dead" => QueueMessageState::Dead,
+      _ => unreachable!(),
+    })
+    .collect()
+}
@@ -13,6 +13,7 @@ pub mod info;
 pub mod init;
 pub mod installer;
 pub mod jupyter;
+pub mod kv;
 pub mod lint;
 pub mod pm;
 pub mod publish;
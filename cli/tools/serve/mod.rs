@@ -7,10 +7,10 @@ use std::sync::Arc;
 use deno_core::ModuleSpecifier;
 use deno_core::error::AnyError;
 use deno_core::futures::FutureExt;
-use deno_core::futures::TryFutureExt;
 use deno_lib::worker::LibWorkerFactoryRoots;
 use deno_runtime::UnconfiguredRuntime;
 
+use self::supervisor::ServeWorkerSupervisor;
 use super::run::check_permission_before_script;
 use super::run::maybe_npm_install;
 use crate::args::Flags;
@@ -22,6 +22,8 @@ use crate::factory::CliFactory;
 use crate::util::file_watcher::WatcherRestartMode;
 use crate::worker::CliMainWorkerFactory;
 
+mod supervisor;
+
 pub async fn serve(
   flags: Arc<Flags>,
   serve_flags: ServeFlags,
@@ -68,7 +70,8 @@ pub async fn serve(
   );
 
   if serve_flags.open_site {
-    let url = resolve_serve_url(serve_flags.host, serve_flags.port);
+    let tls = cli_options.serve_tls()?.is_some();
+    let url = resolve_serve_url(serve_flags.host, serve_flags.port, tls);
     let _ = open::that_detached(url);
   }
 
@@ -109,60 +112,9 @@ async fn do_serve(
   };
 
   let main = deno_core::unsync::spawn(async move { worker.run().await });
-
-  let mut channels = Vec::with_capacity(worker_count);
-  for i in 0..worker_count {
-    let worker_factory = worker_factory.clone();
-    let main_module = main_module.clone();
-    let (tx, rx) = tokio::sync::oneshot::channel();
-    channels.push(rx);
-    std::thread::Builder::new()
-      .name(format!("serve-worker-{}", i + 1))
-      .spawn(move || {
-        deno_runtime::tokio_util::create_and_run_current_thread(async move {
-          let result = run_worker(i, worker_factory, main_module, hmr).await;
-          let _ = tx.send(result);
-        });
-      })?;
-  }
-
-  let (main_result, worker_results) = tokio::try_join!(
-    main.map_err(AnyError::from),
-    deno_core::futures::future::try_join_all(
-      channels.into_iter().map(|r| r.map_err(AnyError::from))
-    )
-  )?;
-
-  let mut exit_code = main_result?;
-  for res in worker_results {
-    let ret = res?;
-    if ret != 0 && exit_code == 0 {
-      exit_code = ret;
-    }
-  }
-  Ok(exit_code)
-}
-
-async fn run_worker(
-  worker_index: usize,
-  worker_factory: Arc<CliMainWorkerFactory>,
-  main_module: ModuleSpecifier,
-  hmr: bool,
-) -> Result<i32, AnyError> {
-  let mut worker: crate::worker::CliMainWorker = worker_factory
-    .create_main_worker(
-      deno_runtime::WorkerExecutionMode::ServeWorker { worker_index },
-      main_module,
-      // TODO(bartlomieju):
-      vec![],
-    )
-    .await?;
-  if hmr {
-    worker.run_for_watcher().await?;
-    Ok(0)
-  } else {
-    worker.run().await.map_err(Into::into)
-  }
+  ServeWorkerSupervisor::new(worker_factory, main_module, worker_count, hmr)
+    .run(async move { Ok(main.await??) })
+    .await
 }
 
 async fn serve_with_watch(
@@ -213,7 +165,7 @@ async fn serve_with_watch(
   Ok(0)
 }
 
-fn resolve_serve_url(host: String, port: u16) -> String {
+fn resolve_serve_url(host: String, port: u16, tls: bool) -> String {
   let host = if matches!(host.as_str(), "0.0.0.0" | "::") {
     "127.0.0.1".to_string()
   } else if std::net::Ipv6Addr::from_str(&host).is_ok() {
@@ -221,10 +173,11 @@ fn resolve_serve_url(host: String, port: u16) -> String {
   } else {
     host
   };
-  if port == 80 {
-    format!("http://{host}/")
+  let (scheme, default_port) = if tls { ("https", 443) } else { ("http", 80) };
+  if port == default_port {
+    format!("{scheme}://{host}/")
   } else {
-    format!("http://{host}:{port}/")
+    format!("{scheme}://{host}:{port}/")
   }
 }
 
@@ -235,17 +188,28 @@ mod test {
   #[test]
   fn test_resolve_serve_url() {
     assert_eq!(
-      resolve_serve_url("localhost".to_string(), 80),
+      resolve_serve_url("localhost".to_string(), 80, false),
       "http://localhost/"
     );
     assert_eq!(
-      resolve_serve_url("0.0.0.0".to_string(), 80),
+      resolve_serve_url("0.0.0.0".to_string(), 80, false),
+      "http://127.0.0.1/"
+    );
+    assert_eq!(
+      resolve_serve_url("::".to_string(), 80, false),
       "http://127.0.0.1/"
     );
-    assert_eq!(resolve_serve_url("::".to_string(), 80), "http://127.0.0.1/");
     assert_eq!(
-      resolve_serve_url("::".to_string(), 90),
+      resolve_serve_url("::".to_string(), 90, false),
       "http://127.0.0.1:90/"
     );
+    assert_eq!(
+      resolve_serve_url("localhost".to_string(), 443, true),
+      "https://localhost/"
+    );
+    assert_eq!(
+      resolve_serve_url("localhost".to_string(), 8000, true),
+      "https://localhost:8000/"
+    );
   }
 }
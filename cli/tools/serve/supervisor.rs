@@ -0,0 +1,687 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::future::pending;
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_core::futures::FutureExt;
+use deno_core::futures::future::LocalBoxFuture;
+use deno_core::parking_lot::Mutex;
+use deno_core::v8;
+use deno_lib::util::result::js_error_downcast_ref;
+use deno_runtime::deno_http::ServeWorkerHandle;
+use deno_runtime::fmt_errors::format_js_error;
+use deno_signals::SignalStream;
+use deno_terminal::colors;
+use tokio::sync::mpsc;
+
+use crate::util::display::human_elapsed;
+use crate::worker::CliMainWorkerFactory;
+
+const RESTART_BACKOFF_MIN: Duration = Duration::from_millis(100);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// A worker that stayed up for this long is considered healthy again, so
+/// its next crash is restarted without delay.
+const RESTART_BACKOFF_RESET: Duration = Duration::from_secs(60);
+/// A worker that crashes this many times in a row, without staying up for
+/// `RESTART_BACKOFF_RESET` in between, is not restarted again.
+const RESTART_MAX_CRASHES: u32 = 10;
+/// How long a worker can take to handle its pending requests during a
+/// rolling restart before it's terminated.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServeWorkerStatus {
+  Starting,
+  Listening,
+  /// Asked to shut down gracefully by a rolling restart.
+  Stopping,
+  /// Crashed and waiting for its backoff to elapse.
+  Restarting,
+  /// Exited without crashing, crashed too many times in a row, or exited
+  /// after the main worker.
+  Stopped,
+}
+
+impl ServeWorkerStatus {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Self::Starting => "starting",
+      Self::Listening => "listening",
+      Self::Stopping => "stopping",
+      Self::Restarting => "restarting",
+      Self::Stopped => "stopped",
+    }
+  }
+}
+
+struct ServeWorkerState {
+  /// Incremented each time the worker is started, so events of a previous
+  /// run of the worker can be told apart.
+  generation: u64,
+  status: ServeWorkerStatus,
+  started_at: Instant,
+  restarts: u32,
+  /// Crashes since the worker last stayed up for `RESTART_BACKOFF_RESET`.
+  crashes: u32,
+  backoff: Duration,
+  last_exit: Option<String>,
+  handle: ServeWorkerHandle,
+  isolate: Arc<Mutex<Option<v8::IsolateHandle>>>,
+}
+
+/// Runs a worker until it exits, returning its exit code. It's called on the
+/// thread of the worker.
+type ServeWorkerRunner = Arc<
+  dyn Fn(
+      usize,
+      ServeWorkerHandle,
+      Arc<Mutex<Option<v8::IsolateHandle>>>,
+    ) -> LocalBoxFuture<'static, Result<i32, AnyError>>
+    + Send
+    + Sync,
+>;
+
+enum ServeWorkerEvent {
+  Listening {
+    index: usize,
+    generation: u64,
+  },
+  Exited {
+    index: usize,
+    generation: u64,
+    result: Result<i32, AnyError>,
+  },
+  BackoffElapsed {
+    index: usize,
+    generation: u64,
+  },
+  ShutdownTimeout {
+    index: usize,
+    generation: u64,
+  },
+}
+
+/// Runs the workers of `deno serve --parallel` on their own threads,
+/// restarting the ones that crash with an exponential backoff, until they
+/// crash `RESTART_MAX_CRASHES` times in a row.
+///
+/// On unix, `SIGHUP` restarts the workers one at a time, waiting for each
+/// one to listen again before shutting down the next, and `SIGUSR1` prints
+/// the health of every worker. The main worker is not supervised.
+pub struct ServeWorkerSupervisor {
+  runner: ServeWorkerRunner,
+  workers: Vec<ServeWorkerState>,
+  events_tx: mpsc::UnboundedSender<ServeWorkerEvent>,
+  events_rx: mpsc::UnboundedReceiver<ServeWorkerEvent>,
+  /// Workers left to restart by the rolling restart in progress.
+  rolling_restart: VecDeque<usize>,
+  /// Set once the main worker exits, after which workers aren't restarted.
+  stopping: bool,
+}
+
+impl ServeWorkerSupervisor {
+  pub fn new(
+    worker_factory: Arc<CliMainWorkerFactory>,
+    main_module: ModuleSpecifier,
+    worker_count: usize,
+    hmr: bool,
+  ) -> Self {
+    let runner: ServeWorkerRunner = Arc::new(move |index, handle, isolate| {
+      run_worker(
+        index,
+        worker_factory.clone(),
+        main_module.clone(),
+        hmr,
+        handle,
+        isolate,
+      )
+      .boxed_local()
+    });
+    Self::with_runner(runner, worker_count)
+  }
+
+  fn with_runner(runner: ServeWorkerRunner, worker_count: usize) -> Self {
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    let workers = (0..worker_count)
+      .map(|_| ServeWorkerState {
+        generation: 0,
+        status: ServeWorkerStatus::Stopped,
+        started_at: Instant::now(),
+        restarts: 0,
+        crashes: 0,
+        backoff: Duration::ZERO,
+        last_exit: None,
+        handle: ServeWorkerHandle::default(),
+        isolate: Default::default(),
+      })
+      .collect();
+    Self {
+      runner,
+      workers,
+      events_tx,
+      events_rx,
+      rolling_restart: VecDeque::new(),
+      stopping: false,
+    }
+  }
+
+  /// Starts the workers and supervises them until the main worker and all
+  /// the workers have exited, returning the exit code of the process.
+  pub async fn run(
+    mut self,
+    main: impl Future<Output = Result<i32, AnyError>>,
+  ) -> Result<i32, AnyError> {
+    #[cfg(unix)]
+    let (mut sighup, mut sigusr1) = (
+      deno_signals::signal_stream(libc::SIGHUP).ok(),
+      deno_signals::signal_stream(libc::SIGUSR1).ok(),
+    );
+    #[cfg(not(unix))]
+    let (mut sighup, mut sigusr1): (
+      Option<SignalStream>,
+      Option<SignalStream>,
+    ) = (None, None);
+
+    for index in 0..self.workers.len() {
+      self.start(index)?;
+    }
+
+    let mut main = pin!(main);
+    let mut main_result = None;
+    let mut exit_code = 0;
+    let mut worker_error = None;
+    loop {
+      if main_result.is_some()
+        && self
+          .workers
+          .iter()
+          .all(|worker| worker.status == ServeWorkerStatus::Stopped)
+      {
+        break;
+      }
+      tokio::select! {
+        result = &mut main, if main_result.is_none() => {
+          main_result = Some(result);
+          self.stopping = true;
+          self.rolling_restart.clear();
+          // workers waiting for their backoff won't be started again
+          for worker in &mut self.workers {
+            if worker.status == ServeWorkerStatus::Restarting {
+              worker.status = ServeWorkerStatus::Stopped;
+            }
+          }
+        }
+        Some(event) = self.events_rx.recv() => {
+          if let Some(result) = self.handle_event(event)? {
+            match result {
+              Ok(code) if code != 0 && exit_code == 0 => exit_code = code,
+              Ok(_) => {}
+              Err(err) => {
+                worker_error.get_or_insert(err);
+              }
+            }
+          }
+        }
+        _ = recv_signal(&mut sighup) => self.start_rolling_restart(),
+        _ = recv_signal(&mut sigusr1) => self.print_health(),
+      }
+    }
+
+    let main_exit_code = main_result.unwrap()?;
+    if let Some(err) = worker_error {
+      return Err(err);
+    }
+    Ok(if main_exit_code != 0 {
+      main_exit_code
+    } else {
+      exit_code
+    })
+  }
+
+  fn start(&mut self, index: usize) -> Result<(), AnyError> {
+    let worker = &mut self.workers[index];
+    worker.generation += 1;
+    worker.status = ServeWorkerStatus::Starting;
+    worker.started_at = Instant::now();
+    worker.handle = ServeWorkerHandle::default();
+    worker.isolate = Default::default();
+
+    let generation = worker.generation;
+    let handle = worker.handle.clone();
+    let isolate = worker.isolate.clone();
+    let events = self.events_tx.clone();
+    let runner = self.runner.clone();
+    std::thread::Builder::new()
+      .name(format!("serve-worker-{}", index + 1))
+      .spawn(move || {
+        deno_runtime::tokio_util::create_and_run_current_thread(async move {
+          deno_core::unsync::spawn({
+            let handle = handle.clone();
+            let events = events.clone();
+            async move {
+              handle.listening().await;
+              let _ =
+                events.send(ServeWorkerEvent::Listening { index, generation });
+            }
+          });
+          let result = runner(index, handle, isolate).await;
+          let _ = events.send(ServeWorkerEvent::Exited {
+            index,
+            generation,
+            result,
+          });
+        });
+      })?;
+    Ok(())
+  }
+
+  /// Returns the result of a worker that exited for good.
+  fn handle_event(
+    &mut self,
+    event: ServeWorkerEvent,
+  ) -> Result<Option<Result<i32, AnyError>>, AnyError> {
+    match event {
+      ServeWorkerEvent::Listening { index, generation } => {
+        let worker = &mut self.workers[index];
+        if worker.generation != generation
+          || worker.status != ServeWorkerStatus::Starting
+        {
+          return Ok(None);
+        }
+        worker.status = ServeWorkerStatus::Listening;
+        if self.rolling_restart.front() == Some(&index) {
+          self.rolling_restart.pop_front();
+          self.continue_rolling_restart();
+        }
+      }
+      ServeWorkerEvent::Exited {
+        index,
+        generation,
+        result,
+      } => {
+        let worker = &mut self.workers[index];
+        if worker.generation != generation {
+          return Ok(None);
+        }
+        let uptime = worker.started_at.elapsed();
+        let was_stopping = worker.status == ServeWorkerStatus::Stopping;
+        worker.last_exit = Some(match &result {
+          Ok(code) => format!("exited with code {}", code),
+          Err(_) => "uncaught error".to_string(),
+        });
+        if self.stopping {
+          worker.status = ServeWorkerStatus::Stopped;
+          return Ok(Some(result));
+        }
+        if was_stopping {
+          return self.start(index).map(|_| None);
+        }
+        let crashed = match &result {
+          Ok(code) => *code != 0,
+          Err(err) => {
+            log_worker_error(index, err);
+            true
+          }
+        };
+        if !crashed {
+          worker.status = ServeWorkerStatus::Stopped;
+          return Ok(None);
+        }
+
+        if uptime >= RESTART_BACKOFF_RESET {
+          worker.crashes = 0;
+        }
+        worker.crashes += 1;
+        let gave_up = worker.crashes >= RESTART_MAX_CRASHES;
+        if gave_up {
+          worker.status = ServeWorkerStatus::Stopped;
+          log::error!(
+            "{}: Worker {} crashed {} times in a row ({}), not restarting it",
+            colors::green("deno serve"),
+            index + 1,
+            worker.crashes,
+            worker.last_exit.as_deref().unwrap_or_default(),
+          );
+        } else {
+          worker.backoff = next_backoff(worker.backoff, uptime);
+          worker.restarts += 1;
+          worker.status = ServeWorkerStatus::Restarting;
+          log::warn!(
+            "{}: Worker {} crashed ({}), restarting in {}",
+            colors::green("deno serve"),
+            index + 1,
+            worker.last_exit.as_deref().unwrap_or_default(),
+            human_elapsed(worker.backoff.as_millis()),
+          );
+          let backoff = worker.backoff;
+          let events = self.events_tx.clone();
+          deno_core::unsync::spawn(async move {
+            tokio::time::sleep(backoff).await;
+            let _ = events
+              .send(ServeWorkerEvent::BackoffElapsed { index, generation });
+          });
+        }
+        // don't wait for a crashed worker to listen again
+        if self.rolling_restart.front() == Some(&index) {
+          self.rolling_restart.pop_front();
+          self.continue_rolling_restart();
+        }
+        if gave_up {
+          return Ok(Some(result));
+        }
+      }
+      ServeWorkerEvent::BackoffElapsed { index, generation } => {
+        let worker = &self.workers[index];
+        if worker.generation == generation
+          && worker.status == ServeWorkerStatus::Restarting
+          && !self.stopping
+        {
+          self.start(index)?;
+        }
+      }
+      ServeWorkerEvent::ShutdownTimeout { index, generation } => {
+        let worker = &self.workers[index];
+        if worker.generation == generation
+          && worker.status == ServeWorkerStatus::Stopping
+        {
+          log::warn!(
+            "{}: Worker {} didn't finish its pending requests within {}, terminating it",
+            colors::green("deno serve"),
+            index + 1,
+            human_elapsed(SHUTDOWN_TIMEOUT.as_millis()),
+          );
+          if let Some(isolate) = worker.isolate.lock().as_ref() {
+            isolate.terminate_execution();
+          }
+        }
+      }
+    }
+    Ok(None)
+  }
+
+  fn start_rolling_restart(&mut self) {
+    if self.stopping {
+      return;
+    }
+    if !self.rolling_restart.is_empty() {
+      log::info!(
+        "{}: A rolling restart is already in progress",
+        colors::green("deno serve")
+      );
+      return;
+    }
+    log::info!(
+      "{}: Restarting {} workers",
+      colors::green("deno serve"),
+      self.workers.len()
+    );
+    self.rolling_restart = (0..self.workers.len()).collect();
+    self.continue_rolling_restart();
+  }
+
+  /// Asks the next worker of the rolling restart to shut down. It's started
+  /// again once it exits, and the restart moves on when it's listening.
+  fn continue_rolling_restart(&mut self) {
+    while let Some(&index) = self.rolling_restart.front() {
+      let worker = &mut self.workers[index];
+      match worker.status {
+        ServeWorkerStatus::Listening => {
+          worker.status = ServeWorkerStatus::Stopping;
+          worker.handle.shutdown();
+          let generation = worker.generation;
+          let events = self.events_tx.clone();
+          deno_core::unsync::spawn(async move {
+            tokio::time::sleep(SHUTDOWN_TIMEOUT).await;
+            let _ = events
+              .send(ServeWorkerEvent::ShutdownTimeout { index, generation });
+          });
+          return;
+        }
+        // already waiting on this one
+        ServeWorkerStatus::Stopping => return,
+        // freshly started or not running, so there's nothing to restart
+        ServeWorkerStatus::Starting
+        | ServeWorkerStatus::Restarting
+        | ServeWorkerStatus::Stopped => {
+          self.rolling_restart.pop_front();
+        }
+      }
+    }
+  }
+
+  #[allow(clippy::print_stderr)]
+  fn print_health(&self) {
+    let mut output =
+      format!("{}: Worker health\n", colors::green("deno serve"));
+    for (index, worker) in self.workers.iter().enumerate() {
+      output.push_str(&format!(
+        "  worker {}: {}",
+        index + 1,
+        worker.status.as_str()
+      ));
+      if matches!(
+        worker.status,
+        ServeWorkerStatus::Listening | ServeWorkerStatus::Stopping
+      ) {
+        output.push_str(&format!(
+          ", up {}",
+          human_elapsed(worker.started_at.elapsed().as_millis())
+        ));
+      }
+      output.push_str(&format!(
+        ", {} restart{}",
+        worker.restarts,
+        if worker.restarts == 1 { "" } else { "s" }
+      ));
+      if let Some(last_exit) = &worker.last_exit {
+        output.push_str(&format!(", last {}", last_exit));
+      }
+      output.push('\n');
+    }
+    eprint!("{}", output);
+  }
+}
+
+async fn run_worker(
+  worker_index: usize,
+  worker_factory: Arc<CliMainWorkerFactory>,
+  main_module: ModuleSpecifier,
+  hmr: bool,
+  handle: ServeWorkerHandle,
+  isolate: Arc<Mutex<Option<v8::IsolateHandle>>>,
+) -> Result<i32, AnyError> {
+  let mut worker = worker_factory
+    .create_main_worker(
+      deno_runtime::WorkerExecutionMode::ServeWorker { worker_index },
+      main_module,
+      // TODO(bartlomieju):
+      vec![],
+    )
+    .await?;
+  worker.op_state().borrow_mut().put(handle);
+  *isolate.lock() = Some(worker.isolate_handle());
+  if hmr {
+    worker.run_for_watcher().await?;
+    Ok(0)
+  } else {
+    worker.run().await.map_err(Into::into)
+  }
+}
+
+/// Doubles the backoff of a worker that crashed, unless it stayed up long
+/// enough to be considered healthy.
+fn next_backoff(previous: Duration, uptime: Duration) -> Duration {
+  if previous.is_zero() || uptime >= RESTART_BACKOFF_RESET {
+    RESTART_BACKOFF_MIN
+  } else {
+    (previous * 2).min(RESTART_BACKOFF_MAX)
+  }
+}
+
+fn log_worker_error(index: usize, err: &AnyError) {
+  let error_string = match js_error_downcast_ref(err) {
+    Some(e) => format_js_error(e),
+    None => format!("{err:?}"),
+  };
+  log::error!(
+    "{} (worker {}): {}",
+    colors::red_bold("error"),
+    index + 1,
+    error_string.trim_start_matches("error: ")
+  );
+}
+
+async fn recv_signal(stream: &mut Option<SignalStream>) {
+  if let Some(stream) = stream
+    && stream.recv().await.is_some()
+  {
+    return;
+  }
+  pending().await
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::AtomicU32;
+  use std::sync::atomic::Ordering;
+
+  use super::*;
+
+  /// Handles the events of the supervisor until a worker exits, returning
+  /// the result of the worker if it exited for good.
+  async fn handle_next_exit(
+    supervisor: &mut ServeWorkerSupervisor,
+  ) -> Option<Result<i32, AnyError>> {
+    loop {
+      let event = supervisor.events_rx.recv().await.unwrap();
+      let exited = matches!(event, ServeWorkerEvent::Exited { .. });
+      let result = supervisor.handle_event(event).unwrap();
+      if exited {
+        return result;
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn restarts_crashed_worker() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let mut supervisor = ServeWorkerSupervisor::with_runner(
+      Arc::new({
+        let runs = runs.clone();
+        move |_, _, _| {
+          // crash twice, then exit cleanly
+          let code = if runs.fetch_add(1, Ordering::SeqCst) < 2 {
+            1
+          } else {
+            0
+          };
+          async move { Ok(code) }.boxed_local()
+        }
+      }),
+      1,
+    );
+    let start = Instant::now();
+    supervisor.start(0).unwrap();
+
+    assert!(handle_next_exit(&mut supervisor).await.is_none());
+    let worker = &supervisor.workers[0];
+    assert_eq!(worker.status, ServeWorkerStatus::Restarting);
+    assert_eq!(worker.backoff, RESTART_BACKOFF_MIN);
+    assert_eq!(worker.last_exit.as_deref(), Some("exited with code 1"));
+
+    assert!(handle_next_exit(&mut supervisor).await.is_none());
+    let worker = &supervisor.workers[0];
+    assert_eq!(worker.status, ServeWorkerStatus::Restarting);
+    assert_eq!(worker.backoff, RESTART_BACKOFF_MIN * 2);
+
+    assert!(handle_next_exit(&mut supervisor).await.is_none());
+    let worker = &supervisor.workers[0];
+    assert_eq!(worker.status, ServeWorkerStatus::Stopped);
+    assert_eq!(worker.restarts, 2);
+    assert_eq!(worker.generation, 3);
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+    // it waited for both backoffs before starting the worker again
+    assert!(start.elapsed() >= RESTART_BACKOFF_MIN * 3);
+  }
+
+  #[tokio::test]
+  async fn stops_restarting_worker_after_max_crashes() {
+    let mut supervisor = ServeWorkerSupervisor::with_runner(
+      Arc::new(|_, _, _| async { Ok(1) }.boxed_local()),
+      1,
+    );
+    supervisor.start(0).unwrap();
+    for crashes in 1..RESTART_MAX_CRASHES {
+      assert!(handle_next_exit(&mut supervisor).await.is_none());
+      let worker = &supervisor.workers[0];
+      assert_eq!(worker.status, ServeWorkerStatus::Restarting);
+      assert_eq!(worker.crashes, crashes);
+      // don't wait for the backoff
+      supervisor.start(0).unwrap();
+    }
+
+    let result = handle_next_exit(&mut supervisor).await;
+    assert_eq!(result.unwrap().unwrap(), 1);
+    let worker = &supervisor.workers[0];
+    assert_eq!(worker.status, ServeWorkerStatus::Stopped);
+    assert_eq!(worker.crashes, RESTART_MAX_CRASHES);
+    assert_eq!(worker.restarts, RESTART_MAX_CRASHES - 1);
+  }
+
+  #[tokio::test]
+  async fn stops_when_main_exits_during_backoff() {
+    let runs = Arc::new(AtomicU32::new(0));
+    let crashed = Arc::new(tokio::sync::Notify::new());
+    let supervisor = ServeWorkerSupervisor::with_runner(
+      Arc::new({
+        let runs = runs.clone();
+        let crashed = crashed.clone();
+        move |_, _, _| {
+          runs.fetch_add(1, Ordering::SeqCst);
+          crashed.notify_one();
+          async { Ok(1) }.boxed_local()
+        }
+      }),
+      1,
+    );
+    let main = async {
+      crashed.notified().await;
+      // exit after the crash was handled, before the backoff elapsed
+      tokio::time::sleep(RESTART_BACKOFF_MIN / 4).await;
+      Ok(0)
+    };
+
+    let exit_code =
+      tokio::time::timeout(Duration::from_secs(10), supervisor.run(main))
+        .await
+        .expect("supervisor didn't stop")
+        .unwrap();
+    assert_eq!(exit_code, 0);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+  }
+
+  #[test]
+  fn backoff() {
+    let short = Duration::from_secs(1);
+    let mut backoff = next_backoff(Duration::ZERO, short);
+    assert_eq!(backoff, RESTART_BACKOFF_MIN);
+    backoff = next_backoff(backoff, short);
+    assert_eq!(backoff, RESTART_BACKOFF_MIN * 2);
+    for _ in 0..20 {
+      backoff = next_backoff(backoff, short);
+    }
+    assert_eq!(backoff, RESTART_BACKOFF_MAX);
+    // stayed up long enough to reset
+    assert_eq!(
+      next_backoff(backoff, RESTART_BACKOFF_RESET),
+      RESTART_BACKOFF_MIN
+    );
+  }
+}
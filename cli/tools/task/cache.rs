@@ -0,0 +1,314 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_config::glob::FileCollector;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPatternSet;
+use deno_config::workspace::TaskDefinition;
+use deno_core::anyhow::Context;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_lib::util::checksum;
+use deno_lib::version::DENO_VERSION_INFO;
+use deno_path_util::fs::atomic_write_file_with_retries;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::sys::CliSys;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const OUTPUTS_DIR_NAME: &str = "outputs";
+
+pub struct TaskFingerprintOptions<'a> {
+  pub task_name: &'a str,
+  pub command: &'a str,
+  pub definition: &'a TaskDefinition,
+  pub cwd: &'a Path,
+  pub argv: &'a [String],
+  pub env_vars: &'a HashMap<OsString, OsString>,
+  pub dependency_fingerprints: &'a [String],
+}
+
+/// Computes a fingerprint of everything that can affect the result of a
+/// task: its command, arguments, declared environment variables, the globs of
+/// its outputs, the contents of its input files and the fingerprints of its
+/// dependencies.
+pub fn fingerprint(
+  options: TaskFingerprintOptions,
+) -> Result<String, AnyError> {
+  let TaskFingerprintOptions {
+    task_name,
+    command,
+    definition,
+    cwd,
+    argv,
+    env_vars,
+    dependency_fingerprints,
+  } = options;
+
+  let mut parts: Vec<String> = vec![
+    DENO_VERSION_INFO.deno.to_string(),
+    task_name.to_string(),
+    command.to_string(),
+  ];
+  parts.extend(argv.iter().map(|arg| format!("arg:{}", arg)));
+
+  let mut env_names = definition.env.iter().collect::<Vec<_>>();
+  env_names.sort();
+  env_names.dedup();
+  for name in env_names {
+    let value = env_vars
+      .get(&OsString::from(name))
+      .map(|v| v.to_string_lossy().into_owned());
+    parts.push(match value {
+      Some(value) => format!("env:{}={}", name, value),
+      None => format!("env:{}", name),
+    });
+  }
+
+  // the cached outputs are the files matching these globs
+  parts.extend(
+    definition
+      .outputs
+      .iter()
+      .map(|output| format!("output:{}", output)),
+  );
+
+  for file_path in collect_files(cwd, &definition.inputs)? {
+    let bytes = std::fs::read(&file_path).with_context(|| {
+      format!("Failed reading task input '{}'.", file_path.display())
+    })?;
+    let display_path = file_path
+      .strip_prefix(cwd)
+      .map(path_to_slash)
+      .unwrap_or_else(|_| file_path.to_string_lossy().into_owned());
+    parts.push(format!(
+      "input:{}:{}",
+      display_path,
+      checksum::r#gen(&[&bytes])
+    ));
+  }
+
+  parts.extend(
+    dependency_fingerprints
+      .iter()
+      .map(|fingerprint| format!("dep:{}", fingerprint)),
+  );
+
+  Ok(checksum::r#gen(&[parts.join("\0")]))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskCacheManifest {
+  /// Paths of the outputs relative to the task's directory.
+  outputs: Vec<String>,
+}
+
+/// Content addressed storage of task outputs, keyed by the task's
+/// fingerprint.
+pub struct TaskCache {
+  root: PathBuf,
+}
+
+impl TaskCache {
+  pub fn new(root: PathBuf) -> Self {
+    Self { root }
+  }
+
+  /// Restores the outputs of a previous successful run with the same
+  /// fingerprint into `cwd`. Returns `false` when there is no such run.
+  pub fn restore(
+    &self,
+    fingerprint: &str,
+    cwd: &Path,
+  ) -> Result<bool, AnyError> {
+    let entry_dir = self.root.join(fingerprint);
+    let manifest =
+      match std::fs::read_to_string(entry_dir.join(MANIFEST_FILE_NAME)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+          return Ok(false);
+        }
+        Err(err) => return Err(err.into()),
+      };
+    let Ok(manifest) = serde_json::from_str::<TaskCacheManifest>(&manifest)
+    else {
+      log::debug!("Ignoring corrupt task cache entry {}", fingerprint);
+      return Ok(false);
+    };
+
+    let outputs_dir = entry_dir.join(OUTPUTS_DIR_NAME);
+    for output in &manifest.outputs {
+      let from = outputs_dir.join(output);
+      let to = cwd.join(output);
+      if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      std::fs::copy(&from, &to).with_context(|| {
+        format!("Failed restoring cached task output '{}'.", to.display())
+      })?;
+    }
+    Ok(true)
+  }
+
+  /// Stores the files matching the `outputs` globs of a task that finished
+  /// successfully.
+  pub fn save(
+    &self,
+    fingerprint: &str,
+    cwd: &Path,
+    outputs: &[String],
+  ) -> Result<(), AnyError> {
+    let entry_dir = self.root.join(fingerprint);
+    if entry_dir.exists() {
+      std::fs::remove_dir_all(&entry_dir)?;
+    }
+    let outputs_dir = entry_dir.join(OUTPUTS_DIR_NAME);
+    std::fs::create_dir_all(&outputs_dir)?;
+
+    let mut manifest = TaskCacheManifest {
+      outputs: Vec::new(),
+    };
+    for file_path in collect_files(cwd, outputs)? {
+      let Ok(relative_path) = file_path.strip_prefix(cwd) else {
+        bail!(
+          "Task output '{}' must be inside the task's directory '{}'.",
+          file_path.display(),
+          cwd.display()
+        );
+      };
+      let to = outputs_dir.join(relative_path);
+      if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+      }
+      std::fs::copy(&file_path, &to).with_context(|| {
+        format!("Failed caching task output '{}'.", file_path.display())
+      })?;
+      manifest.outputs.push(path_to_slash(relative_path));
+    }
+
+    // the manifest is written last so that partially written
+    // entries are never considered a cache hit
+    atomic_write_file_with_retries(
+      &CliSys::default(),
+      &entry_dir.join(MANIFEST_FILE_NAME),
+      &serde_json::to_vec(&manifest)?,
+      crate::cache::CACHE_PERM,
+    )?;
+    Ok(())
+  }
+}
+
+fn collect_files(
+  cwd: &Path,
+  path_or_patterns: &[String],
+) -> Result<Vec<PathBuf>, AnyError> {
+  if path_or_patterns.is_empty() {
+    return Ok(Vec::new());
+  }
+  let file_patterns = FilePatterns {
+    base: cwd.to_path_buf(),
+    include: Some(PathOrPatternSet::from_include_relative_path_or_patterns(
+      cwd,
+      path_or_patterns,
+    )?),
+    exclude: Default::default(),
+  };
+  let mut files = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .ignore_node_modules()
+    .collect_file_patterns(&CliSys::default(), &file_patterns);
+  files.sort();
+  Ok(files)
+}
+
+fn path_to_slash(path: &Path) -> String {
+  path
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+  use test_util::TempDir;
+
+  use super::*;
+
+  fn fingerprint_for(
+    temp_dir: &TempDir,
+    definition: &TaskDefinition,
+    env_vars: &HashMap<OsString, OsString>,
+  ) -> String {
+    fingerprint(TaskFingerprintOptions {
+      task_name: "build",
+      command: definition.command.as_deref().unwrap(),
+      definition,
+      cwd: temp_dir.path().as_path(),
+      argv: &[],
+      env_vars,
+      dependency_fingerprints: &[],
+    })
+    .unwrap()
+  }
+
+  #[test]
+  fn fingerprint_changes_with_inputs_env_and_outputs() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("src/a.ts", "a");
+    temp_dir.write("other.ts", "other");
+    let definition = TaskDefinition {
+      inputs: vec!["src/".to_string()],
+      env: vec!["MODE".to_string()],
+      ..TaskDefinition::from_command("deno run build.ts".to_string())
+    };
+    let mut env_vars = HashMap::new();
+
+    let initial = fingerprint_for(&temp_dir, &definition, &env_vars);
+    assert_eq!(initial, fingerprint_for(&temp_dir, &definition, &env_vars));
+
+    // files that aren't inputs don't matter
+    temp_dir.write("other.ts", "changed");
+    assert_eq!(initial, fingerprint_for(&temp_dir, &definition, &env_vars));
+
+    temp_dir.write("src/a.ts", "b");
+    let changed_input = fingerprint_for(&temp_dir, &definition, &env_vars);
+    assert_ne!(initial, changed_input);
+
+    env_vars.insert("MODE".into(), "production".into());
+    let changed_env = fingerprint_for(&temp_dir, &definition, &env_vars);
+    assert_ne!(changed_input, changed_env);
+
+    let definition = TaskDefinition {
+      outputs: vec!["dist/".to_string()],
+      ..definition
+    };
+    assert_ne!(
+      changed_env,
+      fingerprint_for(&temp_dir, &definition, &env_vars)
+    );
+  }
+
+  #[test]
+  fn save_and_restore_outputs() {
+    let temp_dir = TempDir::new();
+    temp_dir.write("gen/a.ts", "a");
+    temp_dir.write("gen/nested/b.ts", "b");
+    let cache = TaskCache::new(temp_dir.path().join("cache").to_path_buf());
+    let cwd = temp_dir.path().as_path();
+
+    assert!(!cache.restore("abc", cwd).unwrap());
+    cache.save("abc", cwd, &["gen/".to_string()]).unwrap();
+
+    temp_dir.remove_dir_all("gen");
+    assert!(cache.restore("abc", cwd).unwrap());
+    assert_eq!(temp_dir.read_to_string("gen/a.ts"), "a");
+    assert_eq!(temp_dir.read_to_string("gen/nested/b.ts"), "b");
+  }
+}
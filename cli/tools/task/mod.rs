@@ -1,5 +1,7 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+mod cache;
+
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -34,6 +36,8 @@ use indexmap::IndexMap;
 use indexmap::IndexSet;
 use regex::Regex;
 
+use self::cache::TaskCache;
+use self::cache::TaskFingerprintOptions;
 use crate::args::CliLockfile;
 use crate::args::CliOptions;
 use crate::args::Flags;
@@ -193,6 +197,8 @@ pub async fn execute_script(
   }
   .unwrap_or_else(|| NonZeroUsize::new(2).unwrap());
 
+  let task_cache = TaskCache::new(factory.deno_dir()?.task_cache_folder_path());
+
   let task_runner = TaskRunner {
     task_flags: &task_flags,
     npm_installer: npm_installer.map(|n| n.as_ref()),
@@ -203,6 +209,7 @@ pub async fn execute_script(
     cli_options,
     maybe_lockfile,
     concurrency: no_of_concurrent_tasks.into(),
+    task_cache,
   };
 
   let kill_signal = KillSignal::default();
@@ -213,11 +220,9 @@ pub async fn execute_script(
           &Url::from_directory_path(cli_options.initial_cwd()).unwrap(),
           None,
           "",
-          &TaskDefinition {
-            command: Some(task_flags.task.as_ref().unwrap().to_string()),
-            dependencies: vec![],
-            description: None,
-          },
+          &TaskDefinition::from_command(
+            task_flags.task.as_ref().unwrap().to_string(),
+          ),
           kill_signal,
           cli_options.argv(),
         )
@@ -258,6 +263,7 @@ struct TaskRunner<'a> {
   cli_options: &'a CliOptions,
   maybe_lockfile: Option<Arc<CliLockfile>>,
   concurrency: usize,
+  task_cache: TaskCache,
 }
 
 impl<'a> TaskRunner<'a> {
@@ -308,9 +314,14 @@ impl<'a> TaskRunner<'a> {
     kill_signal: &KillSignal,
     args: &[String],
   ) -> Result<i32, deno_core::anyhow::Error> {
+    /// Exit code of a finished task along with its fingerprint, if cacheable.
+    type CompletedTask<'a> = (i32, Option<String>, &'a ResolvedTask<'a>);
+
     struct PendingTasksContext<'a> {
       completed: HashSet<usize>,
       running: HashSet<usize>,
+      /// Fingerprints of completed tasks that were cacheable.
+      fingerprints: HashMap<usize, String>,
       tasks: &'a [ResolvedTask<'a>],
     }
 
@@ -319,9 +330,16 @@ impl<'a> TaskRunner<'a> {
         self.completed.len() < self.tasks.len()
       }
 
-      fn mark_complete(&mut self, task: &ResolvedTask) {
+      fn mark_complete(
+        &mut self,
+        task: &ResolvedTask,
+        fingerprint: Option<String>,
+      ) {
         self.running.remove(&task.id);
         self.completed.insert(task.id);
+        if let Some(fingerprint) = fingerprint {
+          self.fingerprints.insert(task.id, fingerprint);
+        }
       }
 
       fn get_next_task<'b>(
@@ -329,9 +347,7 @@ impl<'a> TaskRunner<'a> {
         runner: &'b TaskRunner<'b>,
         kill_signal: &KillSignal,
         argv: &'a [String],
-      ) -> Option<
-        LocalBoxFuture<'b, Result<(i32, &'a ResolvedTask<'a>), AnyError>>,
-      >
+      ) -> Option<LocalBoxFuture<'b, Result<CompletedTask<'a>, AnyError>>>
       where
         'a: 'b,
       {
@@ -357,6 +373,13 @@ impl<'a> TaskRunner<'a> {
             continue;
           }
 
+          // a task can only be cached when all its dependencies were
+          let dependency_fingerprints = task
+            .dependencies
+            .iter()
+            .map(|dep_id| self.fingerprints.get(dep_id).cloned())
+            .collect::<Option<Vec<_>>>();
+
           self.running.insert(task.id);
           let kill_signal = kill_signal.clone();
           return Some(
@@ -364,30 +387,30 @@ impl<'a> TaskRunner<'a> {
               match task.task_or_script {
                 TaskOrScript::Task { task: def, .. } => {
                   runner
-                    .run_deno_task(
+                    .run_deno_task_cached(
                       task.task_or_script.folder_url(),
                       task.task_or_script.package_name(),
                       task.name,
                       def,
+                      dependency_fingerprints,
                       kill_signal,
                       args,
                     )
                     .await
                 }
-                TaskOrScript::Script { details, .. } => {
-                  runner
-                    .run_npm_script(
-                      task.task_or_script.folder_url(),
-                      task.task_or_script.package_name(),
-                      task.name,
-                      &details.tasks,
-                      kill_signal,
-                      args,
-                    )
-                    .await
-                }
+                TaskOrScript::Script { details, .. } => runner
+                  .run_npm_script(
+                    task.task_or_script.folder_url(),
+                    task.task_or_script.package_name(),
+                    task.name,
+                    &details.tasks,
+                    kill_signal,
+                    args,
+                  )
+                  .await
+                  .map(|exit_code| (exit_code, None)),
               }
-              .map(|exit_code| (exit_code, task))
+              .map(|(exit_code, fingerprint)| (exit_code, fingerprint, task))
             }
             .boxed_local(),
           );
@@ -399,6 +422,7 @@ impl<'a> TaskRunner<'a> {
     let mut context = PendingTasksContext {
       completed: HashSet::with_capacity(tasks.len()),
       running: HashSet::with_capacity(self.concurrency),
+      fingerprints: HashMap::with_capacity(tasks.len()),
       tasks: &tasks,
     };
 
@@ -422,17 +446,96 @@ impl<'a> TaskRunner<'a> {
         break;
       };
 
-      let (exit_code, name) = result?;
+      let (exit_code, fingerprint, name) = result?;
       if exit_code > 0 {
         return Ok(exit_code);
       }
 
-      context.mark_complete(name);
+      context.mark_complete(name, fingerprint);
     }
 
     Ok(0)
   }
 
+  /// Runs a deno task, skipping it and restoring its outputs when it
+  /// declares inputs that haven't changed since the last successful run.
+  ///
+  /// Returns the task's fingerprint when it was cacheable so that
+  /// dependent tasks can include it in theirs.
+  #[allow(clippy::too_many_arguments)]
+  async fn run_deno_task_cached(
+    &self,
+    dir_url: &Url,
+    package_name: Option<&str>,
+    task_name: &str,
+    definition: &TaskDefinition,
+    dependency_fingerprints: Option<Vec<String>>,
+    kill_signal: KillSignal,
+    argv: &'a [String],
+  ) -> Result<(i32, Option<String>), AnyError> {
+    let (Some(command), Some(dependency_fingerprints)) = (
+      definition
+        .command
+        .as_ref()
+        .filter(|_| definition.is_cacheable()),
+      dependency_fingerprints,
+    ) else {
+      return self
+        .run_deno_task(
+          dir_url,
+          package_name,
+          task_name,
+          definition,
+          kill_signal,
+          argv,
+        )
+        .await
+        .map(|exit_code| (exit_code, None));
+    };
+
+    let cwd = self.task_cwd(dir_url)?;
+    let fingerprint = cache::fingerprint(TaskFingerprintOptions {
+      task_name,
+      command,
+      definition,
+      cwd: &cwd,
+      argv,
+      env_vars: &self.env_vars,
+      dependency_fingerprints: &dependency_fingerprints,
+    })?;
+    if self.task_cache.restore(&fingerprint, &cwd)? {
+      self.output_task(
+        task_name,
+        package_name,
+        &format!(
+          "{} {}",
+          task_runner::get_script_with_args(command, argv),
+          colors::gray("(cached)")
+        ),
+      );
+      return Ok((0, Some(fingerprint)));
+    }
+
+    let exit_code = self
+      .run_deno_task(
+        dir_url,
+        package_name,
+        task_name,
+        definition,
+        kill_signal,
+        argv,
+      )
+      .await?;
+    if exit_code > 0 {
+      return Ok((exit_code, None));
+    }
+    self
+      .task_cache
+      .save(&fingerprint, &cwd, &definition.outputs)
+      .with_context(|| format!("Failed caching task '{}'.", task_name))?;
+    Ok((exit_code, Some(fingerprint)))
+  }
+
   pub async fn run_deno_task(
     &self,
     dir_url: &Url,
@@ -453,13 +556,7 @@ impl<'a> TaskRunner<'a> {
 
     self.maybe_npm_install().await?;
 
-    let cwd = match &self.task_flags.cwd {
-      Some(path) => canonicalize_path(Path::new(path))
-        .context("failed canonicalizing --cwd")?,
-      None => {
-        normalize_path(Cow::Owned(dir_url.to_file_path().unwrap())).into_owned()
-      }
-    };
+    let cwd = self.task_cwd(dir_url)?;
 
     let custom_commands = task_runner::resolve_custom_commands(
       self.npm_resolver,
@@ -569,6 +666,16 @@ impl<'a> TaskRunner<'a> {
     )
   }
 
+  fn task_cwd(&self, dir_url: &Url) -> Result<PathBuf, AnyError> {
+    Ok(match &self.task_flags.cwd {
+      Some(path) => canonicalize_path(Path::new(path))
+        .context("failed canonicalizing --cwd")?,
+      None => {
+        normalize_path(Cow::Owned(dir_url.to_file_path().unwrap())).into_owned()
+      }
+    })
+  }
+
   async fn maybe_npm_install(&self) -> Result<(), AnyError> {
     if let Some(npm_installer) = self.npm_installer {
       self.progress_bar.deferred_keep_initialize_alive();
@@ -853,11 +960,9 @@ fn print_available_tasks(
           is_root,
           is_deno: false,
           name: name.to_string(),
-          task: deno_config::deno_json::TaskDefinition {
-            command: Some(script.to_string()),
-            dependencies: vec![],
-            description: None,
-          },
+          task: deno_config::deno_json::TaskDefinition::from_command(
+            script.to_string(),
+          ),
         });
       }
     }
@@ -92,6 +92,7 @@ mod channel;
 pub mod fmt;
 pub mod reporters;
 mod sanitizers;
+mod shard;
 
 pub use channel::TestEventReceiver;
 pub use channel::TestEventSender;
@@ -103,6 +104,7 @@ pub use fmt::format_test_error;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
 use reporters::JunitTestReporter;
+use reporters::NodeTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
 use reporters::TestReporter;
@@ -281,6 +283,8 @@ pub struct TestDescription {
   pub location: TestLocation,
   pub sanitize_ops: bool,
   pub sanitize_resources: bool,
+  /// Overrides the `--retry` count for this test.
+  pub retry: Option<usize>,
 }
 
 /// May represent a failure of a test or test step.
@@ -461,6 +465,9 @@ pub enum TestResult {
   Ignored,
   Failed(TestFailure),
   Cancelled,
+  /// Passed after failing at least once, holding the failures of the
+  /// previous attempts.
+  Flaky(Vec<TestFailure>),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -545,6 +552,7 @@ pub struct TestSummary {
   pub passed: usize,
   pub failed: usize,
   pub ignored: usize,
+  pub flaky: usize,
   pub passed_steps: usize,
   pub failed_steps: usize,
   pub ignored_steps: usize,
@@ -572,6 +580,9 @@ pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
   pub trace_leaks: bool,
+  /// How many times a failed test is retried, unless the test sets its own
+  /// `retry` count.
+  pub retry: usize,
 }
 
 impl TestSummary {
@@ -581,6 +592,7 @@ impl TestSummary {
       passed: 0,
       failed: 0,
       ignored: 0,
+      flaky: 0,
       passed_steps: 0,
       failed_steps: 0,
       ignored_steps: 0,
@@ -624,6 +636,9 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
       options.concurrent_jobs > NonZeroUsize::new(1).unwrap(),
       failure_format_options,
     )),
+    TestReporterConfig::NodeTest => {
+      Box::new(NodeTestReporter::new(failure_format_options))
+    }
   };
 
   if let Some(junit_path) = &options.junit_path {
@@ -1027,127 +1042,169 @@ async fn run_tests_for_worker_inner(
     }
     event_tracker.wait(desc)?;
 
-    // Poll event loop once, to allow all ops that are already resolved, but haven't
-    // responded to settle.
-    // TODO(mmastrac): we should provide an API to poll the event loop until no further
-    // progress is made.
-    poll_event_loop(worker).await?;
-
-    // We always capture stats, regardless of sanitization state
-    let before_test_stats = sanitizer_helper.capture_stats();
-
-    let earlier = Instant::now();
-
-    // Execute beforeEach hooks (FIFO order)
-    let mut before_each_hook_errored = false;
-
-    call_hooks(worker, test_hooks.before_each.iter(), |core_error| {
-      match core_error {
-        CoreErrorKind::Js(err) => {
-          before_each_hook_errored = true;
-          let test_result = TestResult::Failed(TestFailure::JsError(err));
+    let max_retries = desc.retry.unwrap_or(options.retry);
+    let mut failed_attempts = Vec::new();
+    loop {
+      let may_retry = failed_attempts.len() < max_retries;
+      if may_retry {
+        event_tracker.hold_step_events();
+      }
+      let attempt = run_test_attempt(
+        worker,
+        desc,
+        &function,
+        &test_hooks,
+        &sanitizer_helper,
+        event_tracker,
+      )
+      .await;
+      let step_events = event_tracker.take_held_step_events();
+      let results = match attempt? {
+        TestAttempt::Completed(results) => results,
+        TestAttempt::UncaughtError(js_error) => {
+          event_tracker.send_events(step_events)?;
+          event_tracker.uncaught_error(specifier.to_string(), js_error)?;
           fail_fast_tracker.add_failure();
-          event_tracker.result(desc, test_result, earlier.elapsed())?;
-          Ok(())
+          event_tracker.cancelled(desc)?;
+          had_uncaught_error = true;
+          break;
         }
-        err => Err(err.into_box().into()),
+      };
+
+      let failure = results.iter().find_map(|(result, _)| match result {
+        TestResult::Failed(failure) => Some(failure.clone()),
+        _ => None,
+      });
+      if let Some(failure) = failure
+        && may_retry
+      {
+        // the steps of a retried attempt aren't reported
+        failed_attempts.push(failure);
+        worker_prepare_for_test(worker);
+        continue;
       }
-    })
-    .await?;
+      event_tracker.send_events(step_events)?;
 
-    // TODO(bartlomieju): this whole block/binding could be reworked into something better
-    let result = if !before_each_hook_errored {
-      let call = worker.js_runtime.call(&function);
-
-      let slow_test_warning =
-        spawn(slow_test_watchdog(event_tracker.clone(), desc.id));
-
-      let result = worker
-        .js_runtime
-        .with_event_loop_promise(call, PollEventLoopOptions::default())
-        .await;
-      slow_test_warning.abort();
-      let result = match result {
-        Ok(r) => r,
-        Err(error) => match error.into_kind() {
-          CoreErrorKind::Js(js_error) => {
-            event_tracker.uncaught_error(specifier.to_string(), js_error)?;
+      for (result, elapsed) in results {
+        let result = match result {
+          TestResult::Ok if !failed_attempts.is_empty() => {
+            TestResult::Flaky(std::mem::take(&mut failed_attempts))
+          }
+          TestResult::Failed(_) => {
             fail_fast_tracker.add_failure();
-            event_tracker.cancelled(desc)?;
-            had_uncaught_error = true;
-            continue;
+            result
           }
-          err => return Err(err.into_box().into()),
-        },
-      };
+          result => result,
+        };
+        event_tracker.result(desc, result, elapsed)?;
+      }
+      break;
+    }
+  }
 
-      // Check the result before we check for leaks
-      let scope = &mut worker.js_runtime.handle_scope();
-      let result = v8::Local::new(scope, result);
-      serde_v8::from_v8::<TestResult>(scope, result)?
-    } else {
-      TestResult::Ignored
-    };
+  event_tracker.completed()?;
 
-    if matches!(result, TestResult::Failed(_)) {
-      fail_fast_tracker.add_failure();
-      event_tracker.result(desc, result.clone(), earlier.elapsed())?;
+  // Execute afterAll hooks (LIFO order)
+  call_hooks(worker, test_hooks.after_all.iter().rev(), |core_error| {
+    match core_error {
+      CoreErrorKind::Js(err) => {
+        event_tracker.uncaught_error(specifier.to_string(), err)?;
+        Ok(())
+      }
+      err => Err(err.into_box().into()),
     }
+  })
+  .await?;
 
-    // Execute afterEach hooks (LIFO order)
-    call_hooks(worker, test_hooks.after_each.iter().rev(), |core_error| {
-      match core_error {
-        CoreErrorKind::Js(err) => {
-          let test_result = TestResult::Failed(TestFailure::JsError(err));
-          fail_fast_tracker.add_failure();
-          event_tracker.result(desc, test_result, earlier.elapsed())?;
-          Ok(())
-        }
-        err => Err(err.into_box().into()),
-      }
-    })
-    .await?;
+  Ok(())
+}
 
-    if matches!(result, TestResult::Failed(_)) {
-      continue;
-    }
+enum TestAttempt {
+  /// The results to report for the test along with the time it took to get
+  /// to each of them. There may be more than one when hooks fail.
+  Completed(Vec<(TestResult, Duration)>),
+  /// The test caused an uncaught error, so it's cancelled along with the
+  /// rest of the module.
+  UncaughtError(Box<JsError>),
+}
 
-    // Await activity stabilization
-    if let Some(diff) = sanitizers::wait_for_activity_to_stabilize(
-      worker,
-      &sanitizer_helper,
-      before_test_stats,
-      desc.sanitize_ops,
-      desc.sanitize_resources,
-    )
-    .await?
-    {
-      let (formatted, trailer_notes) = format_sanitizer_diff(diff);
-      if !formatted.is_empty() {
-        let failure = TestFailure::Leaked(formatted, trailer_notes);
-        fail_fast_tracker.add_failure();
-        event_tracker.result(
-          desc,
-          TestResult::Failed(failure),
-          earlier.elapsed(),
-        )?;
-        continue;
+/// Runs a test once, along with its `beforeEach` and `afterEach` hooks.
+async fn run_test_attempt(
+  worker: &mut MainWorker,
+  desc: &TestDescription,
+  function: &v8::Global<v8::Function>,
+  test_hooks: &TestHooks,
+  sanitizer_helper: &sanitizers::TestSanitizerHelper,
+  event_tracker: &TestEventTracker,
+) -> Result<TestAttempt, RunTestsForWorkerErr> {
+  // Poll event loop once, to allow all ops that are already resolved, but haven't
+  // responded to settle.
+  // TODO(mmastrac): we should provide an API to poll the event loop until no further
+  // progress is made.
+  poll_event_loop(worker).await?;
+
+  // We always capture stats, regardless of sanitization state
+  let before_test_stats = sanitizer_helper.capture_stats();
+
+  let earlier = Instant::now();
+  let mut results = Vec::new();
+
+  // Execute beforeEach hooks (FIFO order)
+  let mut before_each_hook_errored = false;
+
+  call_hooks(worker, test_hooks.before_each.iter(), |core_error| {
+    match core_error {
+      CoreErrorKind::Js(err) => {
+        before_each_hook_errored = true;
+        let test_result = TestResult::Failed(TestFailure::JsError(err));
+        results.push((test_result, earlier.elapsed()));
+        Ok(())
       }
+      err => Err(err.into_box().into()),
     }
+  })
+  .await?;
 
-    // TODO(bartlomieju): using `before_each_hook_errored` is fishy
-    if !before_each_hook_errored {
-      event_tracker.result(desc, result, earlier.elapsed())?;
-    }
-  }
+  // TODO(bartlomieju): this whole block/binding could be reworked into something better
+  let result = if !before_each_hook_errored {
+    let call = worker.js_runtime.call(function);
 
-  event_tracker.completed()?;
+    let slow_test_warning =
+      spawn(slow_test_watchdog(event_tracker.clone(), desc.id));
 
-  // Execute afterAll hooks (LIFO order)
-  call_hooks(worker, test_hooks.after_all.iter().rev(), |core_error| {
+    let result = worker
+      .js_runtime
+      .with_event_loop_promise(call, PollEventLoopOptions::default())
+      .await;
+    slow_test_warning.abort();
+    let result = match result {
+      Ok(r) => r,
+      Err(error) => match error.into_kind() {
+        CoreErrorKind::Js(js_error) => {
+          return Ok(TestAttempt::UncaughtError(js_error));
+        }
+        err => return Err(err.into_box().into()),
+      },
+    };
+
+    // Check the result before we check for leaks
+    let scope = &mut worker.js_runtime.handle_scope();
+    let result = v8::Local::new(scope, result);
+    serde_v8::from_v8::<TestResult>(scope, result)?
+  } else {
+    TestResult::Ignored
+  };
+
+  if matches!(result, TestResult::Failed(_)) {
+    results.push((result.clone(), earlier.elapsed()));
+  }
+
+  // Execute afterEach hooks (LIFO order)
+  call_hooks(worker, test_hooks.after_each.iter().rev(), |core_error| {
     match core_error {
       CoreErrorKind::Js(err) => {
-        event_tracker.uncaught_error(specifier.to_string(), err)?;
+        let test_result = TestResult::Failed(TestFailure::JsError(err));
+        results.push((test_result, earlier.elapsed()));
         Ok(())
       }
       err => Err(err.into_box().into()),
@@ -1155,7 +1212,33 @@ async fn run_tests_for_worker_inner(
   })
   .await?;
 
-  Ok(())
+  if matches!(result, TestResult::Failed(_)) {
+    return Ok(TestAttempt::Completed(results));
+  }
+
+  // Await activity stabilization
+  if let Some(diff) = sanitizers::wait_for_activity_to_stabilize(
+    worker,
+    sanitizer_helper,
+    before_test_stats,
+    desc.sanitize_ops,
+    desc.sanitize_resources,
+  )
+  .await?
+  {
+    let (formatted, trailer_notes) = format_sanitizer_diff(diff);
+    if !formatted.is_empty() {
+      let failure = TestFailure::Leaked(formatted, trailer_notes);
+      results.push((TestResult::Failed(failure), earlier.elapsed()));
+      return Ok(TestAttempt::Completed(results));
+    }
+  }
+
+  // TODO(bartlomieju): using `before_each_hook_errored` is fishy
+  if !before_each_hook_errored {
+    results.push((result, earlier.elapsed()));
+  }
+  Ok(TestAttempt::Completed(results))
 }
 
 static HAS_TEST_RUN_SIGINT_HANDLER: AtomicBool = AtomicBool::new(false);
@@ -1553,6 +1636,37 @@ pub async fn run_tests(
     return Err(anyhow!("No test modules found"));
   }
 
+  let specifiers_with_mode = match &workspace_test_options.shard {
+    Some(test_shard) => {
+      let timings = workspace_test_options
+        .shard_timings
+        .as_ref()
+        .map(|path| {
+          shard::read_junit_timings(&cli_options.initial_cwd().join(path))
+        })
+        .transpose()?;
+      let total = specifiers_with_mode.len();
+      let selected = shard::select_shard(
+        specifiers_with_mode,
+        test_shard,
+        &Url::from_directory_path(cli_options.initial_cwd()).unwrap(),
+        timings.as_ref(),
+      );
+      log::info!(
+        "{}",
+        colors::gray(format!(
+          "Running shard {}/{} ({} of {} test modules)",
+          test_shard.index,
+          test_shard.count,
+          selected.len(),
+          total
+        ))
+      );
+      selected
+    }
+    None => specifiers_with_mode,
+  };
+
   let doc_tests = get_doc_tests(&specifiers_with_mode, file_fetcher).await?;
   let specifiers_for_typecheck_and_test =
     get_target_specifiers(specifiers_with_mode, &doc_tests);
@@ -1608,6 +1722,7 @@ pub async fn run_tests(
         filter: TestFilter::from_flag(&workspace_test_options.filter),
         shuffle: workspace_test_options.shuffle,
         trace_leaks: workspace_test_options.trace_leaks,
+        retry: workspace_test_options.retry,
       },
     },
   )
@@ -1636,6 +1751,7 @@ pub async fn run_tests(
           .into_owned(),
       ),
       &reporters,
+      None,
     ) {
       log::info!("Error generating coverage report: {}", err);
     }
@@ -1819,6 +1935,7 @@ pub async fn run_tests_with_watch(
               filter: TestFilter::from_flag(&workspace_test_options.filter),
               shuffle: workspace_test_options.shuffle,
               trace_leaks: workspace_test_options.trace_leaks,
+              retry: workspace_test_options.retry,
             },
           },
         )
@@ -1870,6 +1987,12 @@ pub struct TestEventTracker {
   op_state: Rc<RefCell<OpState>>,
 }
 
+/// The step events of an attempt that may be retried. They're held back
+/// until it's known whether the attempt is retried, in which case they're
+/// dropped.
+#[derive(Default)]
+pub struct HeldTestStepEvents(pub Vec<TestEvent>);
+
 impl TestEventTracker {
   pub fn new(op_state: Rc<RefCell<OpState>>) -> Self {
     Self { op_state }
@@ -1883,6 +2006,33 @@ impl TestEventTracker {
       .send(event)
   }
 
+  /// Holds back the step events until `take_held_step_events` is called.
+  fn hold_step_events(&self) {
+    self
+      .op_state
+      .borrow_mut()
+      .put(HeldTestStepEvents::default());
+  }
+
+  fn take_held_step_events(&self) -> Vec<TestEvent> {
+    self
+      .op_state
+      .borrow_mut()
+      .try_take::<HeldTestStepEvents>()
+      .map(|held| held.0)
+      .unwrap_or_default()
+  }
+
+  fn send_events(
+    &self,
+    events: Vec<TestEvent>,
+  ) -> Result<(), ChannelClosedError> {
+    for event in events {
+      self.send_event(event)?;
+    }
+    Ok(())
+  }
+
   fn slow(
     &self,
     test_id: usize,
@@ -68,6 +68,14 @@ pub fn format_test_step_for_summary(
   )
 }
 
+pub(super) fn format_flaky_retries(retries: usize) -> String {
+  if retries == 1 {
+    "passed after 1 retry".to_string()
+  } else {
+    format!("passed after {retries} retries")
+  }
+}
+
 pub(super) fn report_sigint(
   writer: &mut dyn std::io::Write,
   cwd: &Url,
@@ -202,6 +210,10 @@ pub(super) fn report_summary(
   )
   .ok();
 
+  if summary.flaky > 0 {
+    write!(summary_result, " | {} flaky", summary.flaky).ok();
+  }
+
   let ignored_steps = get_steps_text(summary.ignored_steps);
   if summary.ignored > 0 || !ignored_steps.is_empty() {
     write!(
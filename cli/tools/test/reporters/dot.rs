@@ -60,7 +60,7 @@ impl DotTestReporter {
 
   fn print_test_result(&mut self, result: &TestResult) {
     let status = match result {
-      TestResult::Ok => fmt_ok(),
+      TestResult::Ok | TestResult::Flaky(_) => fmt_ok(),
       TestResult::Ignored => fmt_ignored(),
       TestResult::Failed(_failure) => fmt_failed(),
       TestResult::Cancelled => fmt_cancelled(),
@@ -126,6 +126,10 @@ impl TestReporter for DotTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::Flaky(_) => {
+        self.summary.passed += 1;
+        self.summary.flaky += 1;
+      }
     }
 
     self.print_test_result(result);
@@ -55,6 +55,20 @@ impl JunitTestReporter {
         description: None,
         reruns: vec![],
       },
+      TestResult::Flaky(failures) => {
+        // the failed attempts are reported as `flakyFailure` elements
+        let mut status = quick_junit::TestCaseStatus::success();
+        status.add_reruns(failures.iter().map(|failure| {
+          let mut rerun =
+            quick_junit::TestRerun::new(quick_junit::NonSuccessKind::Failure);
+          rerun.set_message(failure.overview());
+          rerun.set_description(
+            failure.format(failure_format_options).into_owned(),
+          );
+          rerun
+        }));
+        status
+      }
     }
   }
 
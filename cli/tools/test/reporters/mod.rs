@@ -6,12 +6,14 @@ mod common;
 mod compound;
 mod dot;
 mod junit;
+mod node_test;
 mod pretty;
 mod tap;
 
 pub use compound::CompoundTestReporter;
 pub use dot::DotTestReporter;
 pub use junit::JunitTestReporter;
+pub use node_test::NodeTestReporter;
 pub use pretty::PrettyTestReporter;
 pub use tap::TapTestReporter;
 
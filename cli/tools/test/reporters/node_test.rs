@@ -0,0 +1,301 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use deno_core::serde_json::Value;
+use deno_core::serde_json::json;
+use deno_core::serde_json::{self};
+use deno_path_util::url_to_file_path;
+use deno_runtime::fmt_errors::format_js_error;
+
+use super::*;
+
+/// A test reporter that prints the events of the `TestsStream` returned by
+/// `run()` of `node:test`, as one `{ "type": ..., "data": ... }` JSON object
+/// per line. `run()` spawns `deno test --reporter=node-test` and parses them.
+pub struct NodeTestReporter {
+  failure_format_options: TestFailureFormatOptions,
+  /// The number of results reported for each parent, `0` is the root.
+  test_numbers: HashMap<usize, usize>,
+  passed: usize,
+  failed: usize,
+  skipped: usize,
+  cancelled: usize,
+  top_level: usize,
+}
+
+#[allow(clippy::print_stdout)]
+impl NodeTestReporter {
+  pub fn new(
+    failure_format_options: TestFailureFormatOptions,
+  ) -> NodeTestReporter {
+    NodeTestReporter {
+      failure_format_options,
+      test_numbers: HashMap::new(),
+      passed: 0,
+      failed: 0,
+      skipped: 0,
+      cancelled: 0,
+      top_level: 0,
+    }
+  }
+
+  fn print_event(event_type: &str, data: Value) {
+    let event = json!({ "type": event_type, "data": data });
+    println!(
+      "{}",
+      serde_json::to_string(&event).expect("failed to serialize test event")
+    );
+  }
+
+  fn next_test_number(&mut self, parent_id: usize) -> usize {
+    let n = self.test_numbers.entry(parent_id).or_default();
+    *n += 1;
+    *n
+  }
+
+  fn location(
+    name: &str,
+    nesting: usize,
+    origin: &str,
+    location: &TestLocation,
+  ) -> Value {
+    json!({
+      "name": name,
+      "nesting": nesting,
+      "file": to_file_path(origin),
+      "line": location.line_number,
+      "column": location.column_number,
+    })
+  }
+
+  fn print_result(
+    &mut self,
+    mut data: Value,
+    parent_id: usize,
+    elapsed: u64,
+    failure: Option<&TestFailure>,
+    skip: bool,
+  ) {
+    data["testNumber"] = self.next_test_number(parent_id).into();
+    data["details"] = json!({ "duration_ms": elapsed, "type": "test" });
+    if skip {
+      data["skip"] = true.into();
+    }
+
+    match failure {
+      Some(failure) => {
+        data["details"]["error"] = json!({
+          "message": failure.format(&self.failure_format_options),
+          "failureType": match failure {
+            TestFailure::FailedSteps(_) | TestFailure::IncompleteSteps => {
+              "subtestsFailed"
+            }
+            TestFailure::Incomplete => "cancelledByParent",
+            _ => "testCodeFailure",
+          },
+        });
+        Self::print_event("test:fail", data);
+      }
+      None => Self::print_event("test:pass", data),
+    }
+  }
+}
+
+fn to_file_path(origin: &str) -> String {
+  Url::parse(origin)
+    .ok()
+    .and_then(|url| url_to_file_path(&url).ok())
+    .map(|path| path.to_string_lossy().into_owned())
+    .unwrap_or_else(|| origin.to_string())
+}
+
+impl TestReporter for NodeTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    Self::print_event(
+      "test:enqueue",
+      Self::location(
+        &description.name,
+        0,
+        &description.origin,
+        &description.location,
+      ),
+    );
+  }
+
+  fn report_plan(&mut self, _plan: &TestPlan) {}
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    Self::print_event(
+      "test:start",
+      Self::location(
+        &description.name,
+        0,
+        &description.origin,
+        &description.location,
+      ),
+    );
+  }
+
+  fn report_slow(&mut self, _description: &TestDescription, _elapsed: u64) {}
+
+  fn report_output(&mut self, output: &[u8]) {
+    Self::print_event(
+      "test:stdout",
+      json!({ "message": String::from_utf8_lossy(output) }),
+    );
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    self.top_level += 1;
+    let data = Self::location(
+      &description.name,
+      0,
+      &description.origin,
+      &description.location,
+    );
+    match result {
+      TestResult::Ok | TestResult::Flaky(_) => {
+        self.passed += 1;
+        self.print_result(data, 0, elapsed, None, false);
+      }
+      TestResult::Ignored => {
+        self.skipped += 1;
+        self.print_result(data, 0, elapsed, None, true);
+      }
+      TestResult::Failed(failure) => {
+        self.failed += 1;
+        self.print_result(data, 0, elapsed, Some(failure), false);
+      }
+      TestResult::Cancelled => {
+        self.cancelled += 1;
+        self.print_result(
+          data,
+          0,
+          elapsed,
+          Some(&TestFailure::Incomplete),
+          false,
+        );
+      }
+    }
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.failed += 1;
+    Self::print_event(
+      "test:diagnostic",
+      json!({
+        "message": format!(
+          "Uncaught error from {}: {}",
+          to_file_path(origin),
+          format_js_error(&error),
+        ),
+        "nesting": 0,
+        "file": to_file_path(origin),
+        "level": "error",
+      }),
+    );
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    Self::print_event(
+      "test:enqueue",
+      Self::location(
+        &description.name,
+        description.level,
+        &description.origin,
+        &description.location,
+      ),
+    );
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    Self::print_event(
+      "test:start",
+      Self::location(
+        &description.name,
+        description.level,
+        &description.origin,
+        &description.location,
+      ),
+    );
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let data =
+      Self::location(&desc.name, desc.level, &desc.origin, &desc.location);
+    match result {
+      TestStepResult::Ok => {
+        self.passed += 1;
+        self.print_result(data, desc.parent_id, elapsed, None, false);
+      }
+      TestStepResult::Ignored => {
+        self.skipped += 1;
+        self.print_result(data, desc.parent_id, elapsed, None, true);
+      }
+      TestStepResult::Failed(failure) => {
+        self.failed += 1;
+        self.print_result(data, desc.parent_id, elapsed, Some(failure), false);
+      }
+    }
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    Self::print_event(
+      "test:summary",
+      json!({
+        "counts": {
+          "tests": self.passed + self.failed + self.skipped + self.cancelled,
+          "passed": self.passed,
+          "failed": self.failed,
+          "skipped": self.skipped,
+          "todo": 0,
+          "cancelled": self.cancelled,
+          "suites": 0,
+          "topLevel": self.top_level,
+        },
+        "duration_ms": elapsed.as_millis() as u64,
+        "success": self.failed == 0 && self.cancelled == 0,
+      }),
+    );
+  }
+
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    for id in tests_pending {
+      if let Some(description) = tests.get(id) {
+        self.report_result(description, &TestResult::Cancelled, 0);
+      }
+    }
+  }
+
+  fn report_completed(&mut self) {}
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    Ok(())
+  }
+}
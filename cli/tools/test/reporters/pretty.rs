@@ -267,6 +267,10 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::Flaky(_) => {
+        self.summary.passed += 1;
+        self.summary.flaky += 1;
+      }
     }
 
     if self.parallel {
@@ -283,6 +287,7 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Ignored => colors::yellow("ignored").to_string(),
       TestResult::Failed(failure) => failure.format_label(),
       TestResult::Cancelled => colors::gray("cancelled").to_string(),
+      TestResult::Flaky(_) => colors::yellow("flaky").to_string(),
     };
     write!(&mut self.writer, " {status}").ok();
     if let TestResult::Failed(failure) = result
@@ -290,6 +295,14 @@ impl TestReporter for PrettyTestReporter {
     {
       write!(&mut self.writer, " ({})", inline_summary).ok();
     }
+    if let TestResult::Flaky(failures) = result {
+      write!(
+        &mut self.writer,
+        " ({})",
+        common::format_flaky_retries(failures.len())
+      )
+      .ok();
+    }
     writeln!(
       &mut self.writer,
       " {}",
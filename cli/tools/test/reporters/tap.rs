@@ -168,11 +168,17 @@ impl TestReporter for TapTestReporter {
       self.step_n = 0;
     }
 
+    let flaky_comment;
     let (status, directive) = match result {
       TestResult::Ok => ("ok", ""),
       TestResult::Ignored => ("ok", " # SKIP"),
       TestResult::Failed(_failure) => ("not ok", ""),
       TestResult::Cancelled => ("not ok", ""),
+      TestResult::Flaky(failures) => {
+        flaky_comment =
+          format!(" # flaky, {}", common::format_flaky_retries(failures.len()));
+        ("ok", flaky_comment.as_str())
+      }
     };
     self.n += 1;
     Self::print_line(0, status, self.n, &description.name, directive);
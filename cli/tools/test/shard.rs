@@ -0,0 +1,223 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use deno_core::ModuleSpecifier;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::fmt::to_relative_path_or_remote_url;
+use crate::args::TestShard;
+
+/// Matches the opening tags of suites and cases in a JUnit report.
+static JUNIT_TAG_RE: Lazy<Regex> =
+  lazy_regex::lazy_regex!(r#"<(testsuite|testcase)\b([^>]*)>"#);
+static XML_ATTR_RE: Lazy<Regex> =
+  lazy_regex::lazy_regex!(r#"([\w:-]+)\s*=\s*"([^"]*)""#);
+
+/// Reads the time spent in each test module from a JUnit report, such as one
+/// written by `deno test --reporter=junit`. The keys are the suite names,
+/// which are module paths relative to the cwd of that run.
+pub fn read_junit_timings(
+  path: &Path,
+) -> Result<HashMap<String, Duration>, AnyError> {
+  let text = std::fs::read_to_string(path).with_context(|| {
+    format!("Failed reading shard timings from '{}'.", path.display())
+  })?;
+  Ok(parse_junit_timings(&text))
+}
+
+fn parse_junit_timings(text: &str) -> HashMap<String, Duration> {
+  // deno doesn't set the time of suites, so it's computed from their cases,
+  // but other tools might only provide it on the suite
+  let mut suite_times: HashMap<String, Option<Duration>> = HashMap::new();
+  let mut case_times: HashMap<String, Duration> = HashMap::new();
+  let mut current_suite: Option<String> = None;
+  for captures in JUNIT_TAG_RE.captures_iter(text) {
+    let attrs = XML_ATTR_RE
+      .captures_iter(&captures[2])
+      .map(|attr| (attr.get(1).unwrap().as_str(), unescape_xml(&attr[2])))
+      .collect::<HashMap<_, _>>();
+    let time = attrs
+      .get("time")
+      .and_then(|time| time.parse::<f64>().ok())
+      .filter(|time| time.is_finite() && *time >= 0.0)
+      .map(Duration::from_secs_f64);
+    if &captures[1] == "testsuite" {
+      current_suite = attrs.get("name").cloned();
+      if let Some(name) = &current_suite {
+        suite_times.insert(name.clone(), time);
+      }
+    } else if let (Some(suite), Some(time)) = (&current_suite, time) {
+      *case_times.entry(suite.clone()).or_default() += time;
+    }
+  }
+  suite_times
+    .into_iter()
+    .map(|(name, time)| {
+      let time = time
+        .unwrap_or_else(|| case_times.get(&name).copied().unwrap_or_default());
+      (name, time)
+    })
+    .collect()
+}
+
+fn unescape_xml(text: &str) -> String {
+  text
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&apos;", "'")
+    .replace("&amp;", "&")
+}
+
+/// Selects the test modules that belong to the given shard.
+///
+/// Modules are ordered by their path relative to `cwd` so every shard sees the
+/// same order regardless of where the repository is checked out. Without
+/// timings they are dealt out round robin. With timings, the slowest modules
+/// are assigned first, each one to the shard with the least total time so
+/// far; modules missing from the timings are assumed to take the average time.
+pub fn select_shard<T>(
+  specifiers: Vec<(ModuleSpecifier, T)>,
+  shard: &TestShard,
+  cwd: &Url,
+  timings: Option<&HashMap<String, Duration>>,
+) -> Vec<(ModuleSpecifier, T)> {
+  let mut keyed = specifiers
+    .into_iter()
+    .map(|item| (to_relative_path_or_remote_url(cwd, item.0.as_str()), item))
+    .collect::<Vec<_>>();
+  keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+  let shard_index = shard.index - 1;
+  let assignments = match timings.filter(|timings| !timings.is_empty()) {
+    Some(timings) => {
+      let known = keyed
+        .iter()
+        .filter_map(|(key, _)| timings.get(key))
+        .collect::<Vec<_>>();
+      let average = if known.is_empty() {
+        Duration::from_secs(1)
+      } else {
+        known.iter().copied().sum::<Duration>() / known.len() as u32
+      };
+      let durations = keyed
+        .iter()
+        .map(|(key, _)| timings.get(key).copied().unwrap_or(average))
+        .collect::<Vec<_>>();
+      let mut order = (0..keyed.len()).collect::<Vec<_>>();
+      // stable sort, so ties keep the path order
+      order.sort_by(|a, b| durations[*b].cmp(&durations[*a]));
+      let mut loads = vec![Duration::ZERO; shard.count];
+      let mut assignments = vec![0; keyed.len()];
+      for i in order {
+        let (lightest, _) = loads
+          .iter()
+          .enumerate()
+          .min_by_key(|(index, load)| (**load, *index))
+          .unwrap();
+        loads[lightest] += durations[i];
+        assignments[i] = lightest;
+      }
+      assignments
+    }
+    None => (0..keyed.len()).map(|i| i % shard.count).collect(),
+  };
+
+  keyed
+    .into_iter()
+    .zip(assignments)
+    .filter(|(_, assigned)| *assigned == shard_index)
+    .map(|((_, item), _)| item)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn specifiers(names: &[&str]) -> Vec<(ModuleSpecifier, ())> {
+    names
+      .iter()
+      .map(|name| {
+        (
+          ModuleSpecifier::parse(&format!("file:///project/{name}")).unwrap(),
+          (),
+        )
+      })
+      .collect()
+  }
+
+  fn select(
+    names: &[&str],
+    index: usize,
+    count: usize,
+    timings: Option<&HashMap<String, Duration>>,
+  ) -> Vec<String> {
+    let cwd = Url::parse("file:///project/").unwrap();
+    select_shard(
+      specifiers(names),
+      &TestShard { index, count },
+      &cwd,
+      timings,
+    )
+    .into_iter()
+    .map(|(specifier, _)| {
+      specifier.path().trim_start_matches("/project/").to_string()
+    })
+    .collect()
+  }
+
+  #[test]
+  fn round_robin_without_timings() {
+    let names = ["d_test.ts", "a_test.ts", "c_test.ts", "b_test.ts"];
+    assert_eq!(select(&names, 1, 2, None), vec!["a_test.ts", "c_test.ts"]);
+    assert_eq!(select(&names, 2, 2, None), vec!["b_test.ts", "d_test.ts"]);
+    assert_eq!(select(&names, 3, 3, None), vec!["c_test.ts"]);
+  }
+
+  #[test]
+  fn balances_with_timings() {
+    let names = ["a_test.ts", "b_test.ts", "c_test.ts", "d_test.ts"];
+    let timings = HashMap::from([
+      ("./a_test.ts".to_string(), Duration::from_secs(10)),
+      ("./b_test.ts".to_string(), Duration::from_secs(1)),
+      ("./c_test.ts".to_string(), Duration::from_secs(2)),
+      ("./d_test.ts".to_string(), Duration::from_secs(8)),
+    ]);
+    assert_eq!(
+      select(&names, 1, 2, Some(&timings)),
+      vec!["a_test.ts", "b_test.ts"]
+    );
+    assert_eq!(
+      select(&names, 2, 2, Some(&timings)),
+      vec!["c_test.ts", "d_test.ts"]
+    );
+  }
+
+  #[test]
+  fn parses_junit_timings() {
+    let report = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="deno test" tests="3" failures="0" errors="0" time="1.5">
+    <testsuite name="./a_test.ts" tests="2" disabled="0" errors="0" failures="0">
+        <testcase name="first" classname="first" time="0.25">
+        </testcase>
+        <testcase name="second &amp; third" classname="second" time="0.5"/>
+    </testsuite>
+    <testsuite name="./b&amp;c_test.ts" tests="1" disabled="0" errors="0" failures="0" time="2">
+        <testcase name="first" classname="first" time="0.75"/>
+    </testsuite>
+</testsuites>
+"#;
+    let timings = parse_junit_timings(report);
+    assert_eq!(timings.len(), 2);
+    assert_eq!(timings["./a_test.ts"], Duration::from_millis(750));
+    assert_eq!(timings["./b&c_test.ts"], Duration::from_secs(2));
+  }
+}
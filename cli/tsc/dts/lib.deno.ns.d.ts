@@ -852,6 +852,11 @@ declare namespace Deno {
      *
      * @default {"inherit"} */
     permissions?: PermissionOptions;
+    /** The number of times the test is re-run after it fails. A test that
+     * passes on a retry is reported as flaky.
+     *
+     * Defaults to the value of the `--retry` flag, or `0` when it's not set. */
+    retry?: number;
   }
 
   /** Register a test which will be run when `deno test` is used on the command
@@ -5228,6 +5233,42 @@ declare namespace Deno {
 
     /** The callback which is called when the server starts listening. */
     onListen?: (localAddr: Addr) => void;
+
+    /** Settings for the automatic compression of response bodies. */
+    compression?: ServeCompressionOptions;
+  }
+
+  /** Settings for the automatic compression of response bodies, which are
+   * compressed with zstd, brotli or gzip depending on the `Accept-Encoding`
+   * header of the request.
+   *
+   * @category HTTP Server
+   */
+  export interface ServeCompressionOptions {
+    /** Responses with a known length smaller than this many bytes are sent
+     * uncompressed.
+     *
+     * @default {64}
+     */
+    minSize?: number;
+
+    /** The gzip compression level, from 0 to 9.
+     *
+     * @default {1}
+     */
+    gzipLevel?: number;
+
+    /** The brotli compression quality, from 0 to 11.
+     *
+     * @default {6}
+     */
+    brotliQuality?: number;
+
+    /** The zstd compression level, from 1 to 22.
+     *
+     * @default {3}
+     */
+    zstdLevel?: number;
   }
 
   /**
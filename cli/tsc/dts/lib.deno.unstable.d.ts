@@ -380,6 +380,14 @@ declare namespace Deno {
    * `localStorage` persistence). More information about the origin storage key
    * can be found in the Deno Manual.
    *
+   * A path of `memory:` opens a new database that is only kept in memory, and
+   * `memory:<name>` one that is shared by every `Deno.openKv` call with the
+   * same name. Versionstamps of in-memory databases are deterministic, which
+   * makes them suitable for tests.
+   *
+   * A `postgres://` or `postgresql://` connection string opens a database
+   * stored on a PostgreSQL server, and requires net access to the server.
+   *
    * @tags allow-read, allow-write
    * @category Cloud
    * @experimental
@@ -459,7 +467,7 @@ declare namespace Deno {
    *
    * `schedule` can be a string in the Unix cron format or in JSON format
    * as specified by interface {@linkcode CronSchedule}, where time is specified
-   * using UTC time zone.
+   * using UTC time zone unless the `timezone` option is set.
    *
    * `backoffSchedule` option can be used to specify the retry policy for failed
    * executions. Each element in the array represents the number of milliseconds
@@ -468,13 +476,29 @@ declare namespace Deno {
    * second, 5 seconds, and 10 seconds delay between each retry. There is a
    * limit of 5 retries and a maximum interval of 1 hour (3600000 milliseconds).
    *
+   * `timezone` option can be used to evaluate the schedule in an IANA time
+   * zone, such as `"America/New_York"`, instead of UTC. Times skipped when
+   * clocks go forward run at the first instant after the transition, and
+   * times repeated when clocks go back run only once.
+   *
+   * `catchUp` option persists the time of the last execution so that
+   * executions missed while no process was running the cron can be handled
+   * after a restart: `"skip"` drops them, `"once"` runs a single execution
+   * and `"all"` runs one execution for each missed one (at most 100).
+   * Executions missed more than a week ago are not caught up.
+   *
    * @category Cloud
    * @experimental
    */
   export function cron(
     name: string,
     schedule: string | CronSchedule,
-    options: { backoffSchedule?: number[]; signal?: AbortSignal },
+    options: {
+      backoffSchedule?: number[];
+      signal?: AbortSignal;
+      timezone?: string;
+      catchUp?: "skip" | "once" | "all";
+    },
     handler: () => Promise<void> | void,
   ): Promise<void>;
 
@@ -747,6 +771,44 @@ declare namespace Deno {
     ok: false;
   }
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The state of a message in the queue of a {@linkcode Deno.Kv}:
+   *
+   * - `pending` messages are waiting for their first delivery or a retry.
+   * - `running` messages are being delivered to a queue listener.
+   * - `dead` messages failed their last delivery attempt.
+   *
+   * @category Cloud
+   * @experimental
+   */
+  export type KvQueueMessageState = "pending" | "running" | "dead";
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A message in the queue of a {@linkcode Deno.Kv}, as returned by
+   * {@linkcode Deno.Kv.listQueueMessages}.
+   *
+   * @category Cloud
+   * @experimental
+   */
+  export interface KvQueueMessage {
+    id: string;
+    state: KvQueueMessageState;
+    /** The enqueued value. */
+    value: unknown;
+    /** When the message is delivered if it is pending, when its current
+     * delivery times out if it is running, and when it failed if it is
+     * dead. */
+    timestamp: Date;
+    /** The number of times the message was delivered to a listener, while
+     * delivery tracking was enabled. */
+    deliveryAttempts: number;
+    /** The delays of the remaining retries, in milliseconds. */
+    backoffSchedule: number[];
+    keysIfUndelivered: KvKey[];
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * A check to perform as part of a {@linkcode Deno.AtomicOperation}. The check
@@ -1110,6 +1172,59 @@ declare namespace Deno {
     // deno-lint-ignore no-explicit-any
     listenQueue(handler: (value: any) => Promise<void> | void): Promise<void>;
 
+    /**
+     * List the messages in the database queue. By default messages in every
+     * state are returned, ordered by state and then by the time they are
+     * scheduled to be delivered, failed, or (for running messages) by the
+     * deadline of their current delivery attempt.
+     *
+     * Messages that exhausted their `backoffSchedule` are kept as `"dead"`
+     * messages, in addition to being written to their `keysIfUndelivered`,
+     * until they are purged or redriven. Dead messages and delivery attempts
+     * are only recorded once tracking is enabled for the database with
+     * `deno kv queue track`.
+     *
+     * ```ts
+     * const db = await Deno.openKv("./queue.db");
+     * for (const msg of await db.listQueueMessages({ states: ["dead"] })) {
+     *   console.log(msg.id, msg.deliveryAttempts, msg.value);
+     * }
+     * ```
+     *
+     * Queue administration is only supported by databases backed by a local
+     * SQLite file. Other databases reject with a `NotSupported` error.
+     */
+    listQueueMessages(
+      options?: { states?: KvQueueMessageState[]; limit?: number },
+    ): Promise<KvQueueMessage[]>;
+
+    /**
+     * Delete all messages in the given states from the database queue, and
+     * return the number of messages that were deleted. By default only
+     * `"pending"` messages are deleted. Running messages that are deleted are
+     * still delivered to the listener that is currently processing them, but
+     * are not retried.
+     *
+     * ```ts
+     * const db = await Deno.openKv("./queue.db");
+     * await db.purgeQueue({ states: ["pending", "dead"] });
+     * ```
+     */
+    purgeQueue(options?: { states?: KvQueueMessageState[] }): Promise<number>;
+
+    /**
+     * Enqueue dead messages again for immediate delivery, with the default
+     * backoff schedule and their original `keysIfUndelivered`. If `ids` is
+     * given only those messages are redriven, otherwise all dead messages
+     * are. Returns the number of messages that were redriven.
+     *
+     * ```ts
+     * const db = await Deno.openKv("./queue.db");
+     * await db.redriveQueueMessages();
+     * ```
+     */
+    redriveQueueMessages(ids?: string[]): Promise<number>;
+
     /**
      * Create a new {@linkcode Deno.AtomicOperation} object which can be used to
      * perform an atomic transaction on the database. This does not perform any
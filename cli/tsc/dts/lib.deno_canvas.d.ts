@@ -192,3 +192,437 @@ declare var ImageBitmap: {
   prototype: ImageBitmap;
   new (): ImageBitmap;
 };
+
+/**
+ * The options of {@linkcode OffscreenCanvas.convertToBlob}.
+ *
+ * @category Canvas
+ */
+interface ImageEncodeOptions {
+  /**
+   * The MIME type of the image: `image/png` (default), `image/jpeg` or
+   * `image/webp`. Any other type falls back to `image/png`.
+   */
+  type?: string;
+  /**
+   * A number between 0 and 1 indicating the image quality, used by
+   * `image/jpeg`. WebP images are always encoded losslessly.
+   */
+  quality?: number;
+}
+
+/** @category Canvas */
+type OffscreenRenderingContextId =
+  | "2d"
+  | "bitmaprenderer"
+  | "webgl"
+  | "webgl2"
+  | "webgpu";
+
+/**
+ * A canvas that can be rendered off screen. Only the `"2d"` context is
+ * supported, and it is rasterized on the CPU.
+ *
+ * @category Canvas
+ *
+ * @example
+ * ```ts
+ * const canvas = new OffscreenCanvas(200, 100);
+ * const ctx = canvas.getContext("2d")!;
+ * ctx.fillStyle = "tomato";
+ * ctx.fillRect(10, 10, 180, 80);
+ *
+ * const blob = await canvas.convertToBlob({ type: "image/png" });
+ * await Deno.writeFile("out.png", new Uint8Array(await blob.arrayBuffer()));
+ * ```
+ * @see https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvas
+ */
+interface OffscreenCanvas {
+  /** The height of the canvas. Setting it clears the canvas. */
+  height: number;
+  /** The width of the canvas. Setting it clears the canvas. */
+  width: number;
+  /**
+   * Encode the canvas as an image. Supports `image/png` (the default),
+   * `image/jpeg` and `image/webp`.
+   */
+  convertToBlob(options?: ImageEncodeOptions): Promise<Blob>;
+  /**
+   * Returns the 2D rendering context of the canvas. Other context types
+   * return `null`.
+   */
+  getContext(
+    contextId: "2d",
+    options?: unknown,
+  ): OffscreenCanvasRenderingContext2D | null;
+  getContext(
+    contextId: OffscreenRenderingContextId,
+    options?: unknown,
+  ): OffscreenCanvasRenderingContext2D | null;
+  /**
+   * Creates an {@linkcode ImageBitmap} from the current contents of the
+   * canvas and clears the canvas.
+   */
+  transferToImageBitmap(): ImageBitmap;
+}
+
+/**
+ * A canvas that can be rendered off screen.
+ *
+ * @category Canvas
+ */
+declare var OffscreenCanvas: {
+  prototype: OffscreenCanvas;
+  new (width: number, height: number): OffscreenCanvas;
+};
+
+/** @category Canvas */
+type CanvasImageSource = ImageBitmap | OffscreenCanvas;
+
+/** @category Canvas */
+type CanvasFillRule = "evenodd" | "nonzero";
+
+/** @category Canvas */
+type CanvasLineCap = "butt" | "round" | "square";
+
+/** @category Canvas */
+type CanvasLineJoin = "bevel" | "miter" | "round";
+
+/** @category Canvas */
+type CanvasTextAlign = "center" | "end" | "left" | "right" | "start";
+
+/** @category Canvas */
+type CanvasTextBaseline =
+  | "alphabetic"
+  | "bottom"
+  | "hanging"
+  | "ideographic"
+  | "middle"
+  | "top";
+
+/** @category Canvas */
+type CanvasDirection = "inherit" | "ltr" | "rtl";
+
+/** @category Canvas */
+type ImageSmoothingQuality = "high" | "low" | "medium";
+
+/** @category Canvas */
+type GlobalCompositeOperation =
+  | "color"
+  | "color-burn"
+  | "color-dodge"
+  | "copy"
+  | "darken"
+  | "destination-atop"
+  | "destination-in"
+  | "destination-out"
+  | "destination-over"
+  | "difference"
+  | "exclusion"
+  | "hard-light"
+  | "hue"
+  | "lighten"
+  | "lighter"
+  | "luminosity"
+  | "multiply"
+  | "overlay"
+  | "saturation"
+  | "screen"
+  | "soft-light"
+  | "source-atop"
+  | "source-in"
+  | "source-out"
+  | "source-over"
+  | "xor";
+
+/**
+ * An opaque object describing a gradient, created by
+ * {@linkcode OffscreenCanvasRenderingContext2D.createLinearGradient} or
+ * {@linkcode OffscreenCanvasRenderingContext2D.createRadialGradient}.
+ *
+ * @category Canvas
+ */
+interface CanvasGradient {
+  /**
+   * Adds a color stop at `offset`, a number between 0 and 1. Throws an
+   * `IndexSizeError` if the offset is out of range and a `SyntaxError` if
+   * the color cannot be parsed.
+   */
+  addColorStop(offset: number, color: string): void;
+}
+
+/** @category Canvas */
+declare var CanvasGradient: {
+  prototype: CanvasGradient;
+  new (): never;
+};
+
+/**
+ * The dimensions of a piece of text, as returned by
+ * {@linkcode OffscreenCanvasRenderingContext2D.measureText}.
+ *
+ * @category Canvas
+ */
+interface TextMetrics {
+  readonly actualBoundingBoxAscent: number;
+  readonly actualBoundingBoxDescent: number;
+  readonly actualBoundingBoxLeft: number;
+  readonly actualBoundingBoxRight: number;
+  readonly fontBoundingBoxAscent: number;
+  readonly fontBoundingBoxDescent: number;
+  /** The advance width of the text. */
+  readonly width: number;
+}
+
+/** @category Canvas */
+declare var TextMetrics: {
+  prototype: TextMetrics;
+  new (): never;
+};
+
+/**
+ * The 2D rendering context of an {@linkcode OffscreenCanvas}.
+ *
+ * Shadows, filters, patterns and `Path2D` are not supported. Text is only
+ * drawn with fonts that were added to {@linkcode fonts}.
+ *
+ * @category Canvas
+ * @see https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvasRenderingContext2D
+ */
+interface OffscreenCanvasRenderingContext2D {
+  readonly canvas: OffscreenCanvas;
+
+  save(): void;
+  restore(): void;
+  reset(): void;
+
+  scale(x: number, y: number): void;
+  rotate(angle: number): void;
+  translate(x: number, y: number): void;
+  transform(
+    a: number,
+    b: number,
+    c: number,
+    d: number,
+    e: number,
+    f: number,
+  ): void;
+  setTransform(
+    a: number,
+    b: number,
+    c: number,
+    d: number,
+    e: number,
+    f: number,
+  ): void;
+  resetTransform(): void;
+
+  globalAlpha: number;
+  globalCompositeOperation: GlobalCompositeOperation;
+  imageSmoothingEnabled: boolean;
+  imageSmoothingQuality: ImageSmoothingQuality;
+
+  fillStyle: string | CanvasGradient;
+  strokeStyle: string | CanvasGradient;
+  createLinearGradient(
+    x0: number,
+    y0: number,
+    x1: number,
+    y1: number,
+  ): CanvasGradient;
+  /** The start circle is always treated as having a radius of zero. */
+  createRadialGradient(
+    x0: number,
+    y0: number,
+    r0: number,
+    x1: number,
+    y1: number,
+    r1: number,
+  ): CanvasGradient;
+
+  clearRect(x: number, y: number, w: number, h: number): void;
+  fillRect(x: number, y: number, w: number, h: number): void;
+  strokeRect(x: number, y: number, w: number, h: number): void;
+
+  beginPath(): void;
+  fill(fillRule?: CanvasFillRule): void;
+  stroke(): void;
+  clip(fillRule?: CanvasFillRule): void;
+
+  fillText(text: string, x: number, y: number, maxWidth?: number): void;
+  strokeText(text: string, x: number, y: number, maxWidth?: number): void;
+  measureText(text: string): TextMetrics;
+
+  drawImage(image: CanvasImageSource, dx: number, dy: number): void;
+  drawImage(
+    image: CanvasImageSource,
+    dx: number,
+    dy: number,
+    dw: number,
+    dh: number,
+  ): void;
+  drawImage(
+    image: CanvasImageSource,
+    sx: number,
+    sy: number,
+    sw: number,
+    sh: number,
+    dx: number,
+    dy: number,
+    dw: number,
+    dh: number,
+  ): void;
+
+  createImageData(sw: number, sh: number): ImageData;
+  createImageData(imageData: ImageData): ImageData;
+  getImageData(sx: number, sy: number, sw: number, sh: number): ImageData;
+  putImageData(imageData: ImageData, dx: number, dy: number): void;
+  putImageData(
+    imageData: ImageData,
+    dx: number,
+    dy: number,
+    dirtyX: number,
+    dirtyY: number,
+    dirtyWidth: number,
+    dirtyHeight: number,
+  ): void;
+
+  lineWidth: number;
+  lineCap: CanvasLineCap;
+  lineJoin: CanvasLineJoin;
+  miterLimit: number;
+  lineDashOffset: number;
+  setLineDash(segments: number[]): void;
+  getLineDash(): number[];
+
+  font: string;
+  textAlign: CanvasTextAlign;
+  textBaseline: CanvasTextBaseline;
+  direction: CanvasDirection;
+
+  closePath(): void;
+  moveTo(x: number, y: number): void;
+  lineTo(x: number, y: number): void;
+  quadraticCurveTo(cpx: number, cpy: number, x: number, y: number): void;
+  bezierCurveTo(
+    cp1x: number,
+    cp1y: number,
+    cp2x: number,
+    cp2y: number,
+    x: number,
+    y: number,
+  ): void;
+  arcTo(x1: number, y1: number, x2: number, y2: number, radius: number): void;
+  rect(x: number, y: number, w: number, h: number): void;
+  roundRect(
+    x: number,
+    y: number,
+    w: number,
+    h: number,
+    radii?: number | number[],
+  ): void;
+  arc(
+    x: number,
+    y: number,
+    radius: number,
+    startAngle: number,
+    endAngle: number,
+    counterclockwise?: boolean,
+  ): void;
+  ellipse(
+    x: number,
+    y: number,
+    radiusX: number,
+    radiusY: number,
+    rotation: number,
+    startAngle: number,
+    endAngle: number,
+    counterclockwise?: boolean,
+  ): void;
+}
+
+/** @category Canvas */
+declare var OffscreenCanvasRenderingContext2D: {
+  prototype: OffscreenCanvasRenderingContext2D;
+  new (): never;
+};
+
+/** @category Canvas */
+interface FontFaceDescriptors {
+  /** `normal`, `italic` or `oblique`. Defaults to `normal`. */
+  style?: string;
+  /** `normal`, `bold` or a numeric weight. Defaults to `normal`. */
+  weight?: string;
+}
+
+/** @category Canvas */
+type FontFaceLoadStatus = "error" | "loaded" | "loading" | "unloaded";
+
+/**
+ * A font face that can be added to {@linkcode fonts} to make it available
+ * to canvas text rendering. Only binary font data (TrueType or OpenType) is
+ * supported as a source.
+ *
+ * @category Canvas
+ *
+ * @example
+ * ```ts
+ * const data = await Deno.readFile("./Inter-Regular.ttf");
+ * fonts.add(new FontFace("Inter", data));
+ *
+ * const ctx = new OffscreenCanvas(200, 50).getContext("2d")!;
+ * ctx.font = "24px Inter";
+ * ctx.fillText("Hello", 10, 30);
+ * ```
+ * @see https://developer.mozilla.org/en-US/docs/Web/API/FontFace
+ */
+interface FontFace {
+  readonly family: string;
+  readonly style: string;
+  readonly weight: string;
+  readonly status: FontFaceLoadStatus;
+  readonly loaded: Promise<FontFace>;
+  load(): Promise<FontFace>;
+}
+
+/** @category Canvas */
+declare var FontFace: {
+  prototype: FontFace;
+  new (
+    family: string,
+    source: BufferSource | string,
+    descriptors?: FontFaceDescriptors,
+  ): FontFace;
+};
+
+/**
+ * The set of font faces available to canvas text rendering.
+ *
+ * @category Canvas
+ */
+interface FontFaceSet {
+  readonly ready: Promise<FontFaceSet>;
+  readonly size: number;
+  readonly status: "loaded" | "loading";
+  add(font: FontFace): FontFaceSet;
+  check(font: string, text?: string): boolean;
+  clear(): void;
+  delete(font: FontFace): boolean;
+  has(font: FontFace): boolean;
+  values(): IterableIterator<FontFace>;
+  [Symbol.iterator](): IterableIterator<FontFace>;
+}
+
+/** @category Canvas */
+declare var FontFaceSet: {
+  prototype: FontFaceSet;
+  new (): never;
+};
+
+/**
+ * The font faces available to canvas text rendering. When no face matches
+ * the requested family, the first added face is used.
+ *
+ * @category Canvas
+ */
+declare var fonts: FontFaceSet;
@@ -26,16 +26,28 @@ type HashAlgorithmIdentifier = AlgorithmIdentifier;
 type KeyType = "private" | "public" | "secret";
 /** @category Crypto */
 type KeyUsage =
+  | "decapsulateBits"
+  | "decapsulateKey"
   | "decrypt"
   | "deriveBits"
   | "deriveKey"
+  | "encapsulateBits"
+  | "encapsulateKey"
   | "encrypt"
   | "sign"
   | "unwrapKey"
   | "verify"
   | "wrapKey";
 /** @category Crypto */
-type KeyFormat = "jwk" | "pkcs8" | "raw" | "spki";
+type KeyFormat =
+  | "jwk"
+  | "pkcs8"
+  | "raw"
+  | "raw-private"
+  | "raw-public"
+  | "raw-secret"
+  | "raw-seed"
+  | "spki";
 /** @category Crypto */
 type NamedCurve = string;
 
@@ -81,6 +93,30 @@ interface AesGcmParams extends Algorithm {
   tagLength?: number;
 }
 
+/** @category Crypto */
+interface AeadParams extends Algorithm {
+  iv: BufferSource;
+  additionalData?: BufferSource;
+  tagLength?: number;
+}
+
+/** @category Crypto */
+interface ContextParams extends Algorithm {
+  context?: BufferSource;
+}
+
+/** @category Crypto */
+interface EncapsulatedBits {
+  sharedKey: ArrayBuffer;
+  ciphertext: ArrayBuffer;
+}
+
+/** @category Crypto */
+interface EncapsulatedKey {
+  sharedKey: CryptoKey;
+  ciphertext: ArrayBuffer;
+}
+
 /** @category Crypto */
 interface AesCtrParams extends Algorithm {
   counter: BufferSource;
@@ -435,7 +471,11 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/sign
    */
   sign(
-    algorithm: AlgorithmIdentifier | RsaPssParams | EcdsaParams,
+    algorithm:
+      | AlgorithmIdentifier
+      | RsaPssParams
+      | EcdsaParams
+      | ContextParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -452,7 +492,11 @@ interface SubtleCrypto {
    * @see https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/verify
    */
   verify(
-    algorithm: AlgorithmIdentifier | RsaPssParams | EcdsaParams,
+    algorithm:
+      | AlgorithmIdentifier
+      | RsaPssParams
+      | EcdsaParams
+      | ContextParams,
     key: CryptoKey,
     signature: BufferSource,
     data: BufferSource,
@@ -492,7 +536,8 @@ interface SubtleCrypto {
       | RsaOaepParams
       | AesCbcParams
       | AesGcmParams
-      | AesCtrParams,
+      | AesCtrParams
+      | AeadParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -512,7 +557,8 @@ interface SubtleCrypto {
       | RsaOaepParams
       | AesCbcParams
       | AesGcmParams
-      | AesCtrParams,
+      | AesCtrParams
+      | AeadParams,
     key: CryptoKey,
     data: BufferSource,
   ): Promise<ArrayBuffer>;
@@ -621,6 +667,87 @@ interface SubtleCrypto {
     extractable: boolean,
     keyUsages: KeyUsage[],
   ): Promise<CryptoKey>;
+  /**
+   * Generates a random shared secret and encapsulates it with an ML-KEM
+   * public key.
+   *
+   * @example
+   * ```ts
+   * const { sharedKey, ciphertext } = await crypto.subtle.encapsulateBits(
+   *   "ML-KEM-768",
+   *   publicKey,
+   * );
+   * ```
+   */
+  encapsulateBits(
+    encapsulationAlgorithm: AlgorithmIdentifier,
+    encapsulationKey: CryptoKey,
+  ): Promise<EncapsulatedBits>;
+  /**
+   * Generates a random shared secret, encapsulates it with an ML-KEM public
+   * key and imports it as a key for `sharedKeyAlgorithm`.
+   *
+   * @example
+   * ```ts
+   * const { sharedKey, ciphertext } = await crypto.subtle.encapsulateKey(
+   *   "ML-KEM-768",
+   *   publicKey,
+   *   "AES-GCM",
+   *   false,
+   *   ["encrypt", "decrypt"],
+   * );
+   * ```
+   */
+  encapsulateKey(
+    encapsulationAlgorithm: AlgorithmIdentifier,
+    encapsulationKey: CryptoKey,
+    sharedKeyAlgorithm:
+      | AlgorithmIdentifier
+      | HmacImportParams
+      | AesDerivedKeyParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): Promise<EncapsulatedKey>;
+  /**
+   * Recovers the shared secret from a ciphertext with an ML-KEM private key.
+   *
+   * @example
+   * ```ts
+   * await crypto.subtle.decapsulateBits("ML-KEM-768", privateKey, ciphertext);
+   * ```
+   */
+  decapsulateBits(
+    decapsulationAlgorithm: AlgorithmIdentifier,
+    decapsulationKey: CryptoKey,
+    ciphertext: BufferSource,
+  ): Promise<ArrayBuffer>;
+  /**
+   * Recovers the shared secret from a ciphertext with an ML-KEM private key
+   * and imports it as a key for `sharedKeyAlgorithm`.
+   *
+   * @example
+   * ```ts
+   * await crypto.subtle.decapsulateKey(
+   *   "ML-KEM-768",
+   *   privateKey,
+   *   ciphertext,
+   *   "AES-GCM",
+   *   false,
+   *   ["encrypt", "decrypt"],
+   * );
+   * ```
+   */
+  decapsulateKey(
+    decapsulationAlgorithm: AlgorithmIdentifier,
+    decapsulationKey: CryptoKey,
+    ciphertext: BufferSource,
+    sharedKeyAlgorithm:
+      | AlgorithmIdentifier
+      | HmacImportParams
+      | AesDerivedKeyParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): Promise<CryptoKey>;
 }
 
 /** @category Crypto */
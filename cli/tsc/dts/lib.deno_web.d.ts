@@ -1358,7 +1358,12 @@ interface CompressionStream extends GenericTransformStream {
 }
 
 /** @category Streams */
-type CompressionFormat = "deflate" | "deflate-raw" | "gzip";
+type CompressionFormat =
+  | "deflate"
+  | "deflate-raw"
+  | "gzip"
+  | "brotli"
+  | "zstd";
 
 /**
  * An API for compressing a stream of data.
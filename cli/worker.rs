@@ -19,6 +19,7 @@ use deno_lib::worker::ResolveNpmBinaryEntrypointError;
 use deno_npm_installer::PackageCaching;
 use deno_npm_installer::graph::NpmCachingStrategy;
 use deno_runtime::WorkerExecutionMode;
+use deno_runtime::deno_node::V8CoverageHandlerRc;
 use deno_runtime::deno_permissions::PermissionsContainer;
 use deno_runtime::worker::MainWorker;
 use deno_semver::npm::NpmPackageReqReference;
@@ -233,6 +234,10 @@ impl CliMainWorker {
     self.worker.js_runtime().op_state()
   }
 
+  pub fn isolate_handle(&mut self) -> v8::IsolateHandle {
+    self.worker.js_runtime().v8_isolate().thread_safe_handle()
+  }
+
   pub async fn maybe_setup_hmr_runner(
     &mut self,
   ) -> Result<Option<HmrRunner>, CoreError> {
@@ -274,6 +279,13 @@ impl CliMainWorker {
         PollEventLoopOptions::default(),
       )
       .await?;
+    let js_runtime = self.worker.js_runtime();
+    let v8_coverage_handler =
+      coverage_collector.create_v8_coverage_handler(js_runtime.inspector());
+    js_runtime
+      .op_state()
+      .borrow_mut()
+      .put::<V8CoverageHandlerRc>(v8_coverage_handler);
     Ok(Some(coverage_collector))
   }
 
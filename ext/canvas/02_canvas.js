@@ -0,0 +1,2035 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  op_canvas_2d_arc_to,
+  op_canvas_2d_begin_path,
+  op_canvas_2d_bezier_curve_to,
+  op_canvas_2d_clear,
+  op_canvas_2d_clear_rect,
+  op_canvas_2d_clip,
+  op_canvas_2d_close_path,
+  op_canvas_2d_create,
+  op_canvas_2d_draw_canvas,
+  op_canvas_2d_draw_image,
+  op_canvas_2d_ellipse,
+  op_canvas_2d_encode,
+  op_canvas_2d_fill,
+  op_canvas_2d_fill_rect,
+  op_canvas_2d_fill_text,
+  op_canvas_2d_get_image_data,
+  op_canvas_2d_line_to,
+  op_canvas_2d_measure_text,
+  op_canvas_2d_move_to,
+  op_canvas_2d_put_image_data,
+  op_canvas_2d_quadratic_curve_to,
+  op_canvas_2d_rect,
+  op_canvas_2d_reset,
+  op_canvas_2d_resize,
+  op_canvas_2d_restore,
+  op_canvas_2d_round_rect,
+  op_canvas_2d_save,
+  op_canvas_2d_set_color,
+  op_canvas_2d_set_composite_operation,
+  op_canvas_2d_set_font,
+  op_canvas_2d_set_global_alpha,
+  op_canvas_2d_set_gradient,
+  op_canvas_2d_set_image_smoothing,
+  op_canvas_2d_set_line_dash,
+  op_canvas_2d_set_line_style,
+  op_canvas_2d_set_text_style,
+  op_canvas_2d_set_transform,
+  op_canvas_2d_stroke,
+  op_canvas_2d_stroke_rect,
+  op_canvas_2d_transform,
+  op_canvas_parse_color,
+  op_canvas_register_font,
+  op_canvas_unregister_font,
+} from "ext:core/ops";
+import * as webidl from "ext:deno_webidl/00_webidl.js";
+import { DOMException } from "ext:deno_web/01_dom_exception.js";
+import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
+import { Blob } from "ext:deno_web/09_file.js";
+import {
+  _height,
+  _width,
+  ImageData,
+  ImageDataPrototype,
+} from "ext:deno_web/16_image_data.js";
+import {
+  _bitmapData,
+  _detached,
+  ImageBitmap,
+} from "ext:deno_canvas/01_image.js";
+const {
+  ArrayBufferIsView,
+  ArrayBufferPrototypeSlice,
+  ArrayPrototypeIncludes,
+  ArrayPrototypeIndexOf,
+  ArrayPrototypeJoin,
+  ArrayPrototypeMap,
+  ArrayPrototypePop,
+  ArrayPrototypePush,
+  ArrayPrototypePushApply,
+  ArrayPrototypeSlice,
+  ArrayPrototypeSome,
+  MathAbs,
+  MathCos,
+  MathMax,
+  MathMin,
+  MathSin,
+  NumberIsFinite,
+  NumberParseFloat,
+  NumberPOSITIVE_INFINITY,
+  ObjectAssign,
+  ObjectPrototypeIsPrototypeOf,
+  PromiseReject,
+  PromiseResolve,
+  RangeError,
+  RegExpPrototypeExec,
+  SafeMap,
+  SafeMapIterator,
+  StringPrototypeReplace,
+  StringPrototypeSplit,
+  StringPrototypeStartsWith,
+  StringPrototypeToLowerCase,
+  StringPrototypeTrim,
+  Symbol,
+  SymbolFor,
+  SymbolIterator,
+  TypeError,
+  TypedArrayPrototypeGetBuffer,
+  TypedArrayPrototypeSlice,
+  Uint8Array,
+  Uint8ClampedArray,
+} = primordials;
+
+webidl.converters["sequence<unrestricted double>"] = webidl
+  .createSequenceConverter(webidl.converters["unrestricted double"]);
+
+webidl.converters["OffscreenRenderingContextId"] = webidl
+  .createEnumConverter("OffscreenRenderingContextId", [
+    "2d",
+    "bitmaprenderer",
+    "webgl",
+    "webgl2",
+    "webgpu",
+  ]);
+
+webidl.converters["ImageEncodeOptions"] = webidl.createDictionaryConverter(
+  "ImageEncodeOptions",
+  [
+    {
+      key: "type",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "image/png",
+    },
+    {
+      key: "quality",
+      converter: webidl.converters["unrestricted double"],
+    },
+  ],
+);
+
+webidl.converters["FontFaceDescriptors"] = webidl.createDictionaryConverter(
+  "FontFaceDescriptors",
+  [
+    {
+      key: "style",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "normal",
+    },
+    {
+      key: "weight",
+      converter: webidl.converters["DOMString"],
+      defaultValue: "normal",
+    },
+  ],
+);
+
+const _canvas = Symbol("[[canvas]]");
+const _context = Symbol("[[context]]");
+const _state = Symbol("[[state]]");
+const _stateStack = Symbol("[[stateStack]]");
+const _stops = Symbol("[[stops]]");
+const _version = Symbol("[[version]]");
+const _gradient = Symbol("[[gradient]]");
+const _metrics = Symbol("[[metrics]]");
+const _family = Symbol("[[family]]");
+const _style = Symbol("[[style]]");
+const _weight = Symbol("[[weight]]");
+const _status = Symbol("[[status]]");
+const _source = Symbol("[[source]]");
+const _loaded = Symbol("[[loaded]]");
+const _faces = Symbol("[[faces]]");
+
+const COMPOSITE_OPERATIONS = [
+  "source-over",
+  "source-in",
+  "source-out",
+  "source-atop",
+  "destination-over",
+  "destination-in",
+  "destination-out",
+  "destination-atop",
+  "lighter",
+  "copy",
+  "xor",
+  "multiply",
+  "screen",
+  "overlay",
+  "darken",
+  "lighten",
+  "color-dodge",
+  "color-burn",
+  "hard-light",
+  "soft-light",
+  "difference",
+  "exclusion",
+  "hue",
+  "saturation",
+  "color",
+  "luminosity",
+];
+const LINE_CAPS = ["butt", "round", "square"];
+const LINE_JOINS = ["miter", "round", "bevel"];
+const TEXT_ALIGNS = ["start", "end", "left", "right", "center"];
+const TEXT_BASELINES = [
+  "top",
+  "hanging",
+  "middle",
+  "alphabetic",
+  "ideographic",
+  "bottom",
+];
+const DIRECTIONS = ["ltr", "rtl", "inherit"];
+const IMAGE_SMOOTHING_QUALITIES = ["low", "medium", "high"];
+const FILL_RULES = ["nonzero", "evenodd"];
+
+function defaultState() {
+  return {
+    fillStyle: "#000000",
+    fillGradientVersion: 0,
+    strokeStyle: "#000000",
+    strokeGradientVersion: 0,
+    globalAlpha: 1,
+    globalCompositeOperation: "source-over",
+    lineWidth: 1,
+    lineCap: "butt",
+    lineJoin: "miter",
+    miterLimit: 10,
+    lineDash: [],
+    lineDashOffset: 0,
+    font: "10px sans-serif",
+    textAlign: "start",
+    textBaseline: "alphabetic",
+    direction: "inherit",
+    imageSmoothingEnabled: true,
+    imageSmoothingQuality: "low",
+  };
+}
+
+/**
+ * Convert the leading `count` arguments to `unrestricted double`s. Canvas
+ * methods silently ignore calls with non-finite arguments, which is
+ * signalled by returning `null`.
+ */
+function toFiniteDoubles(args, count, prefix) {
+  webidl.requiredArguments(args.length, count, prefix);
+  const values = [];
+  let finite = true;
+  for (let i = 0; i < count; i++) {
+    const value = webidl.converters["unrestricted double"](
+      args[i],
+      prefix,
+      `Argument ${i + 1}`,
+    );
+    finite &&= NumberIsFinite(value);
+    ArrayPrototypePush(values, value);
+  }
+  return finite ? values : null;
+}
+
+const FONT_PATTERN =
+  /^\s*((?:(?:normal|italic|oblique|small-caps|bold|bolder|lighter|[1-9]00|(?:ultra-|extra-|semi-)?(?:condensed|expanded))\s+)*)(\d*\.?\d+(?:e[+-]?\d+)?)(px|pt|pc|in|cm|mm|em|rem|%)\s*(?:\/\s*[^\s]+\s+)?(.+?)\s*$/i;
+
+const FONT_SIZE_UNITS = {
+  px: 1,
+  pt: 4 / 3,
+  pc: 16,
+  in: 96,
+  cm: 96 / 2.54,
+  mm: 96 / 25.4,
+  // Relative units resolve against the default 10px font.
+  em: 10,
+  rem: 10,
+  "%": 0.1,
+};
+
+/** Parse a CSS `font` shorthand, returning `null` if it is invalid. */
+function parseFont(font) {
+  const match = RegExpPrototypeExec(FONT_PATTERN, font);
+  if (match === null) {
+    return null;
+  }
+  let italic = false;
+  let weight = 400;
+  const modifiers = StringPrototypeSplit(
+    StringPrototypeTrim(StringPrototypeToLowerCase(match[1])),
+    /\s+/,
+  );
+  for (let i = 0; i < modifiers.length; i++) {
+    const modifier = modifiers[i];
+    if (modifier === "italic" || modifier === "oblique") {
+      italic = true;
+    } else if (modifier === "bold" || modifier === "bolder") {
+      weight = 700;
+    } else if (modifier === "lighter") {
+      weight = 100;
+    } else if (RegExpPrototypeExec(/^[1-9]00$/, modifier) !== null) {
+      weight = NumberParseFloat(modifier);
+    }
+  }
+  const size = NumberParseFloat(match[2]) *
+    FONT_SIZE_UNITS[StringPrototypeToLowerCase(match[3])];
+  const families = ArrayPrototypeMap(
+    StringPrototypeSplit(match[4], ","),
+    (family) =>
+      StringPrototypeReplace(
+        StringPrototypeTrim(family),
+        /^(['"])(.*)\1$/,
+        "$2",
+      ),
+  );
+  if (ArrayPrototypeSome(families, (family) => family === "")) {
+    return null;
+  }
+
+  const parts = [];
+  if (italic) {
+    ArrayPrototypePush(parts, "italic");
+  }
+  if (weight === 700) {
+    ArrayPrototypePush(parts, "bold");
+  } else if (weight !== 400) {
+    ArrayPrototypePush(parts, `${weight}`);
+  }
+  ArrayPrototypePush(parts, `${size}px`);
+  ArrayPrototypePush(parts, ArrayPrototypeJoin(families, ", "));
+
+  return {
+    serialized: ArrayPrototypeJoin(parts, " "),
+    families: ArrayPrototypeJoin(families, ","),
+    size,
+    weight,
+    italic,
+  };
+}
+
+class CanvasGradient {
+  [_gradient];
+  [_stops];
+  [_version];
+
+  constructor() {
+    webidl.illegalConstructor();
+  }
+
+  /**
+   * @param {number} offset
+   * @param {string} color
+   */
+  addColorStop(offset, color) {
+    webidl.assertBranded(this, CanvasGradientPrototype);
+    const prefix = "Failed to execute 'addColorStop' on 'CanvasGradient'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    offset = webidl.converters["double"](offset, prefix, "Argument 1");
+    color = webidl.converters["DOMString"](color, prefix, "Argument 2");
+    if (offset < 0 || offset > 1) {
+      throw new DOMException(
+        `${prefix}: The provided value (${offset}) is outside the range (0.0, 1.0)`,
+        "IndexSizeError",
+      );
+    }
+    const parsed = op_canvas_parse_color(color);
+    if (parsed === null) {
+      throw new DOMException(
+        `${prefix}: The value provided ('${color}') could not be parsed as a color`,
+        "SyntaxError",
+      );
+    }
+    ArrayPrototypePush(this[_stops], [offset, parsed]);
+    this[_version]++;
+  }
+}
+const CanvasGradientPrototype = CanvasGradient.prototype;
+
+function createGradient(gradient) {
+  const object = webidl.createBranded(CanvasGradient);
+  object[_gradient] = gradient;
+  object[_stops] = [];
+  object[_version] = 1;
+  return object;
+}
+
+class TextMetrics {
+  [_metrics];
+
+  constructor() {
+    webidl.illegalConstructor();
+  }
+
+  get width() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][0];
+  }
+
+  get actualBoundingBoxLeft() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][1];
+  }
+
+  get actualBoundingBoxRight() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][2];
+  }
+
+  get fontBoundingBoxAscent() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][3];
+  }
+
+  get fontBoundingBoxDescent() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][4];
+  }
+
+  get actualBoundingBoxAscent() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][5];
+  }
+
+  get actualBoundingBoxDescent() {
+    webidl.assertBranded(this, TextMetricsPrototype);
+    return this[_metrics][6];
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(TextMetricsPrototype, this),
+        keys: [
+          "width",
+          "actualBoundingBoxLeft",
+          "actualBoundingBoxRight",
+          "fontBoundingBoxAscent",
+          "fontBoundingBoxDescent",
+          "actualBoundingBoxAscent",
+          "actualBoundingBoxDescent",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+const TextMetricsPrototype = TextMetrics.prototype;
+
+const CONTEXT_PREFIX = "OffscreenCanvasRenderingContext2D";
+
+class OffscreenCanvasRenderingContext2D {
+  [_canvas];
+  [_state];
+  [_stateStack];
+
+  constructor() {
+    webidl.illegalConstructor();
+  }
+
+  get canvas() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_canvas];
+  }
+
+  save() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const state = this[_state];
+    ArrayPrototypePush(
+      this[_stateStack],
+      ObjectAssign({}, state, {
+        lineDash: ArrayPrototypeSlice(state.lineDash),
+      }),
+    );
+    op_canvas_2d_save(this[_canvas][_canvas]);
+  }
+
+  restore() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    if (this[_stateStack].length === 0) {
+      return;
+    }
+    this[_state] = ArrayPrototypePop(this[_stateStack]);
+    op_canvas_2d_restore(this[_canvas][_canvas]);
+  }
+
+  reset() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    resetContext(this);
+    op_canvas_2d_reset(this[_canvas][_canvas]);
+  }
+
+  scale(x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      2,
+      `Failed to execute 'scale' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_transform(
+      this[_canvas][_canvas],
+      args[0],
+      0,
+      0,
+      args[1],
+      0,
+      0,
+    );
+  }
+
+  rotate(angle) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      1,
+      `Failed to execute 'rotate' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    const cos = MathCos(args[0]);
+    const sin = MathSin(args[0]);
+    op_canvas_2d_transform(this[_canvas][_canvas], cos, sin, -sin, cos, 0, 0);
+  }
+
+  translate(x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      2,
+      `Failed to execute 'translate' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_transform(
+      this[_canvas][_canvas],
+      1,
+      0,
+      0,
+      1,
+      args[0],
+      args[1],
+    );
+  }
+
+  transform(a, b, c, d, e, f) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      6,
+      `Failed to execute 'transform' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_transform(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      args[4],
+      args[5],
+    );
+  }
+
+  setTransform(a, b, c, d, e, f) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      6,
+      `Failed to execute 'setTransform' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_set_transform(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      args[4],
+      args[5],
+    );
+  }
+
+  resetTransform() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    op_canvas_2d_set_transform(this[_canvas][_canvas], 1, 0, 0, 1, 0, 0);
+  }
+
+  get globalAlpha() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].globalAlpha;
+  }
+
+  set globalAlpha(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["unrestricted double"](
+      value,
+      `Failed to set 'globalAlpha' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!NumberIsFinite(value) || value < 0 || value > 1) return;
+    this[_state].globalAlpha = value;
+    op_canvas_2d_set_global_alpha(this[_canvas][_canvas], value);
+  }
+
+  get globalCompositeOperation() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].globalCompositeOperation;
+  }
+
+  set globalCompositeOperation(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'globalCompositeOperation' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(COMPOSITE_OPERATIONS, value)) return;
+    this[_state].globalCompositeOperation = value;
+    op_canvas_2d_set_composite_operation(this[_canvas][_canvas], value);
+  }
+
+  get imageSmoothingEnabled() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].imageSmoothingEnabled;
+  }
+
+  set imageSmoothingEnabled(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const state = this[_state];
+    state.imageSmoothingEnabled = webidl.converters["boolean"](value);
+    op_canvas_2d_set_image_smoothing(
+      this[_canvas][_canvas],
+      state.imageSmoothingEnabled,
+      state.imageSmoothingQuality,
+    );
+  }
+
+  get imageSmoothingQuality() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].imageSmoothingQuality;
+  }
+
+  set imageSmoothingQuality(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'imageSmoothingQuality' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(IMAGE_SMOOTHING_QUALITIES, value)) return;
+    const state = this[_state];
+    state.imageSmoothingQuality = value;
+    op_canvas_2d_set_image_smoothing(
+      this[_canvas][_canvas],
+      state.imageSmoothingEnabled,
+      value,
+    );
+  }
+
+  get strokeStyle() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].strokeStyle;
+  }
+
+  set strokeStyle(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    setStyle(this, true, value);
+  }
+
+  get fillStyle() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].fillStyle;
+  }
+
+  set fillStyle(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    setStyle(this, false, value);
+  }
+
+  createLinearGradient(x0, y0, x1, y1) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix =
+      `Failed to execute 'createLinearGradient' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 4, prefix);
+    if (args === null) {
+      throw new TypeError(`${prefix}: The provided double value is non-finite`);
+    }
+    return createGradient({
+      radial: false,
+      x0: args[0],
+      y0: args[1],
+      x1: args[2],
+      y1: args[3],
+      r1: 0,
+    });
+  }
+
+  createRadialGradient(x0, y0, r0, x1, y1, r1) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix =
+      `Failed to execute 'createRadialGradient' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 6, prefix);
+    if (args === null) {
+      throw new TypeError(`${prefix}: The provided double value is non-finite`);
+    }
+    if (args[2] < 0 || args[5] < 0) {
+      throw new DOMException(
+        `${prefix}: The radius provided is negative`,
+        "IndexSizeError",
+      );
+    }
+    // The rasterizer only supports a start circle with a zero radius.
+    return createGradient({
+      radial: true,
+      x0: args[0],
+      y0: args[1],
+      x1: args[3],
+      y1: args[4],
+      r1: args[5],
+    });
+  }
+
+  clearRect(x, y, w, h) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      4,
+      `Failed to execute 'clearRect' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_clear_rect(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+    );
+  }
+
+  fillRect(x, y, w, h) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      4,
+      `Failed to execute 'fillRect' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    syncGradients(this);
+    op_canvas_2d_fill_rect(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+    );
+  }
+
+  strokeRect(x, y, w, h) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      4,
+      `Failed to execute 'strokeRect' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    syncGradients(this);
+    op_canvas_2d_stroke_rect(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+    );
+  }
+
+  beginPath() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    op_canvas_2d_begin_path(this[_canvas][_canvas]);
+  }
+
+  fill(fillRule = "nonzero") {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    fillRule = convertFillRule(fillRule, "fill");
+    syncGradients(this);
+    op_canvas_2d_fill(this[_canvas][_canvas], fillRule === "evenodd");
+  }
+
+  stroke() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    syncGradients(this);
+    op_canvas_2d_stroke(this[_canvas][_canvas]);
+  }
+
+  clip(fillRule = "nonzero") {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    fillRule = convertFillRule(fillRule, "clip");
+    op_canvas_2d_clip(this[_canvas][_canvas], fillRule === "evenodd");
+  }
+
+  fillText(text, x, y, maxWidth = undefined) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    drawText(this, "fillText", arguments, false);
+  }
+
+  strokeText(text, x, y, maxWidth = undefined) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    drawText(this, "strokeText", arguments, true);
+  }
+
+  measureText(text) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'measureText' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    text = webidl.converters["DOMString"](text, prefix, "Argument 1");
+    const metrics = webidl.createBranded(TextMetrics);
+    metrics[_metrics] = op_canvas_2d_measure_text(this[_canvas][_canvas], text);
+    return metrics;
+  }
+
+  drawImage(image, ...args) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'drawImage' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 3, prefix);
+    const count = args.length >= 8 ? 8 : args.length >= 4 ? 4 : 2;
+    const numbers = toFiniteDoubles(args, count, prefix);
+
+    let source, width, height;
+    if (ObjectPrototypeIsPrototypeOf(ImageBitmapPrototype, image)) {
+      if (image[_detached]) {
+        throw new DOMException(
+          `${prefix}: The image source is detached`,
+          "InvalidStateError",
+        );
+      }
+      width = image[_width];
+      height = image[_height];
+    } else if (ObjectPrototypeIsPrototypeOf(OffscreenCanvasPrototype, image)) {
+      width = image[_width];
+      height = image[_height];
+      if (width === 0 || height === 0) {
+        throw new DOMException(
+          `${prefix}: The image argument is a canvas element with a width or height of 0`,
+          "InvalidStateError",
+        );
+      }
+      source = image[_canvas];
+    } else {
+      throw new TypeError(
+        `${prefix}: The provided value is not of type '(ImageBitmap or OffscreenCanvas)'`,
+      );
+    }
+    if (numbers === null) return;
+
+    let sx = 0, sy = 0, sw = width, sh = height;
+    let dx, dy, dw = width, dh = height;
+    if (count === 2) {
+      dx = numbers[0];
+      dy = numbers[1];
+    } else if (count === 4) {
+      dx = numbers[0];
+      dy = numbers[1];
+      dw = numbers[2];
+      dh = numbers[3];
+    } else {
+      sx = numbers[0];
+      sy = numbers[1];
+      sw = numbers[2];
+      sh = numbers[3];
+      dx = numbers[4];
+      dy = numbers[5];
+      dw = numbers[6];
+      dh = numbers[7];
+    }
+
+    if (source !== undefined) {
+      op_canvas_2d_draw_canvas(
+        this[_canvas][_canvas],
+        source,
+        sx,
+        sy,
+        sw,
+        sh,
+        dx,
+        dy,
+        dw,
+        dh,
+      );
+    } else {
+      op_canvas_2d_draw_image(
+        this[_canvas][_canvas],
+        image[_bitmapData],
+        width,
+        height,
+        sx,
+        sy,
+        sw,
+        sh,
+        dx,
+        dy,
+        dw,
+        dh,
+      );
+    }
+  }
+
+  createImageData(sw, sh = undefined) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'createImageData' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    if (ObjectPrototypeIsPrototypeOf(ImageDataPrototype, sw)) {
+      return new ImageData(sw.width, sw.height);
+    }
+    sw = webidl.converters["long"](sw, prefix, "Argument 1", {
+      enforceRange: true,
+    });
+    sh = webidl.converters["long"](sh, prefix, "Argument 2", {
+      enforceRange: true,
+    });
+    if (sw === 0 || sh === 0) {
+      throw new DOMException(
+        `${prefix}: The source ${sw === 0 ? "width" : "height"} is 0`,
+        "IndexSizeError",
+      );
+    }
+    return new ImageData(MathAbs(sw), MathAbs(sh));
+  }
+
+  getImageData(sx, sy, sw, sh) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'getImageData' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 4, prefix);
+    const opts = { enforceRange: true };
+    sx = webidl.converters["long"](sx, prefix, "Argument 1", opts);
+    sy = webidl.converters["long"](sy, prefix, "Argument 2", opts);
+    sw = webidl.converters["long"](sw, prefix, "Argument 3", opts);
+    sh = webidl.converters["long"](sh, prefix, "Argument 4", opts);
+    if (sw === 0 || sh === 0) {
+      throw new DOMException(
+        `${prefix}: The source ${sw === 0 ? "width" : "height"} is 0`,
+        "IndexSizeError",
+      );
+    }
+    if (sw < 0) {
+      sx += sw;
+      sw = -sw;
+    }
+    if (sh < 0) {
+      sy += sh;
+      sh = -sh;
+    }
+    const data = op_canvas_2d_get_image_data(
+      this[_canvas][_canvas],
+      sx,
+      sy,
+      sw,
+      sh,
+    );
+    return new ImageData(
+      new Uint8ClampedArray(TypedArrayPrototypeGetBuffer(data)),
+      sw,
+      sh,
+    );
+  }
+
+  putImageData(
+    imageData,
+    dx,
+    dy,
+    dirtyX = undefined,
+    dirtyY = undefined,
+    dirtyWidth = undefined,
+    dirtyHeight = undefined,
+  ) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'putImageData' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 3, prefix);
+    if (!ObjectPrototypeIsPrototypeOf(ImageDataPrototype, imageData)) {
+      throw new TypeError(
+        `${prefix}: parameter 1 is not of type 'ImageData'`,
+      );
+    }
+    const opts = { enforceRange: true };
+    dx = webidl.converters["long"](dx, prefix, "Argument 2", opts);
+    dy = webidl.converters["long"](dy, prefix, "Argument 3", opts);
+    const width = imageData.width;
+    const height = imageData.height;
+    if (arguments.length >= 7) {
+      dirtyX = webidl.converters["long"](dirtyX, prefix, "Argument 4", opts);
+      dirtyY = webidl.converters["long"](dirtyY, prefix, "Argument 5", opts);
+      dirtyWidth = webidl.converters["long"](
+        dirtyWidth,
+        prefix,
+        "Argument 6",
+        opts,
+      );
+      dirtyHeight = webidl.converters["long"](
+        dirtyHeight,
+        prefix,
+        "Argument 7",
+        opts,
+      );
+    } else {
+      dirtyX = 0;
+      dirtyY = 0;
+      dirtyWidth = width;
+      dirtyHeight = height;
+    }
+    if (dirtyWidth < 0) {
+      dirtyX += dirtyWidth;
+      dirtyWidth = -dirtyWidth;
+    }
+    if (dirtyHeight < 0) {
+      dirtyY += dirtyHeight;
+      dirtyHeight = -dirtyHeight;
+    }
+    const left = MathMax(dirtyX, 0);
+    const top = MathMax(dirtyY, 0);
+    const right = MathMin(dirtyX + dirtyWidth, width);
+    const bottom = MathMin(dirtyY + dirtyHeight, height);
+    if (right <= left || bottom <= top) {
+      return;
+    }
+    op_canvas_2d_put_image_data(
+      this[_canvas][_canvas],
+      imageData.data,
+      width,
+      dx,
+      dy,
+      left,
+      top,
+      right - left,
+      bottom - top,
+    );
+  }
+
+  get lineWidth() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].lineWidth;
+  }
+
+  set lineWidth(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["unrestricted double"](
+      value,
+      `Failed to set 'lineWidth' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!NumberIsFinite(value) || value <= 0) return;
+    this[_state].lineWidth = value;
+    syncLineStyle(this);
+  }
+
+  get lineCap() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].lineCap;
+  }
+
+  set lineCap(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'lineCap' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(LINE_CAPS, value)) return;
+    this[_state].lineCap = value;
+    syncLineStyle(this);
+  }
+
+  get lineJoin() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].lineJoin;
+  }
+
+  set lineJoin(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'lineJoin' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(LINE_JOINS, value)) return;
+    this[_state].lineJoin = value;
+    syncLineStyle(this);
+  }
+
+  get miterLimit() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].miterLimit;
+  }
+
+  set miterLimit(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["unrestricted double"](
+      value,
+      `Failed to set 'miterLimit' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!NumberIsFinite(value) || value <= 0) return;
+    this[_state].miterLimit = value;
+    syncLineStyle(this);
+  }
+
+  setLineDash(segments) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'setLineDash' on '${CONTEXT_PREFIX}'`;
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    segments = webidl.converters["sequence<unrestricted double>"](
+      segments,
+      prefix,
+      "Argument 1",
+    );
+    if (
+      ArrayPrototypeSome(
+        segments,
+        (segment) => !NumberIsFinite(segment) || segment < 0,
+      )
+    ) {
+      return;
+    }
+    if (segments.length % 2 === 1) {
+      ArrayPrototypePushApply(segments, ArrayPrototypeSlice(segments));
+    }
+    const state = this[_state];
+    state.lineDash = segments;
+    op_canvas_2d_set_line_dash(
+      this[_canvas][_canvas],
+      segments,
+      state.lineDashOffset,
+    );
+  }
+
+  getLineDash() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return ArrayPrototypeSlice(this[_state].lineDash);
+  }
+
+  get lineDashOffset() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].lineDashOffset;
+  }
+
+  set lineDashOffset(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["unrestricted double"](
+      value,
+      `Failed to set 'lineDashOffset' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!NumberIsFinite(value)) return;
+    const state = this[_state];
+    state.lineDashOffset = value;
+    op_canvas_2d_set_line_dash(this[_canvas][_canvas], state.lineDash, value);
+  }
+
+  get font() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].font;
+  }
+
+  set font(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'font' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    const font = parseFont(value);
+    if (font === null) return;
+    this[_state].font = font.serialized;
+    op_canvas_2d_set_font(
+      this[_canvas][_canvas],
+      font.families,
+      font.size,
+      font.weight,
+      font.italic,
+    );
+  }
+
+  get textAlign() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].textAlign;
+  }
+
+  set textAlign(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'textAlign' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(TEXT_ALIGNS, value)) return;
+    this[_state].textAlign = value;
+    syncTextStyle(this);
+  }
+
+  get textBaseline() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].textBaseline;
+  }
+
+  set textBaseline(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'textBaseline' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(TEXT_BASELINES, value)) return;
+    this[_state].textBaseline = value;
+    syncTextStyle(this);
+  }
+
+  get direction() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    return this[_state].direction;
+  }
+
+  set direction(value) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    value = webidl.converters["DOMString"](
+      value,
+      `Failed to set 'direction' on '${CONTEXT_PREFIX}'`,
+      "Argument 1",
+    );
+    if (!ArrayPrototypeIncludes(DIRECTIONS, value)) return;
+    this[_state].direction = value;
+    syncTextStyle(this);
+  }
+
+  closePath() {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    op_canvas_2d_close_path(this[_canvas][_canvas]);
+  }
+
+  moveTo(x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      2,
+      `Failed to execute 'moveTo' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_move_to(this[_canvas][_canvas], args[0], args[1]);
+  }
+
+  lineTo(x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      2,
+      `Failed to execute 'lineTo' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_line_to(this[_canvas][_canvas], args[0], args[1]);
+  }
+
+  quadraticCurveTo(cpx, cpy, x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      4,
+      `Failed to execute 'quadraticCurveTo' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_quadratic_curve_to(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+    );
+  }
+
+  bezierCurveTo(cp1x, cp1y, cp2x, cp2y, x, y) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      6,
+      `Failed to execute 'bezierCurveTo' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_bezier_curve_to(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      args[4],
+      args[5],
+    );
+  }
+
+  arcTo(x1, y1, x2, y2, radius) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'arcTo' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 5, prefix);
+    if (args === null) return;
+    if (args[4] < 0) {
+      throw new DOMException(
+        `${prefix}: The radius provided (${args[4]}) is negative`,
+        "IndexSizeError",
+      );
+    }
+    op_canvas_2d_arc_to(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      args[4],
+    );
+  }
+
+  rect(x, y, w, h) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const args = toFiniteDoubles(
+      arguments,
+      4,
+      `Failed to execute 'rect' on '${CONTEXT_PREFIX}'`,
+    );
+    if (args === null) return;
+    op_canvas_2d_rect(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+    );
+  }
+
+  roundRect(x, y, w, h, radii = 0) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'roundRect' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 4, prefix);
+    radii = typeof radii === "object" && radii !== null
+      ? webidl.converters["sequence<unrestricted double>"](
+        radii,
+        prefix,
+        "Argument 5",
+      )
+      : [webidl.converters["unrestricted double"](radii, prefix, "Argument 5")];
+    if (radii.length < 1 || radii.length > 4) {
+      throw new RangeError(
+        `${prefix}: ${radii.length} radii provided. Between one and four radii are necessary`,
+      );
+    }
+    if (args === null || ArrayPrototypeSome(radii, (r) => !NumberIsFinite(r))) {
+      return;
+    }
+    if (ArrayPrototypeSome(radii, (r) => r < 0)) {
+      throw new RangeError(`${prefix}: Radius value is negative`);
+    }
+    let corners;
+    switch (radii.length) {
+      case 1:
+        corners = [radii[0], radii[0], radii[0], radii[0]];
+        break;
+      case 2:
+        corners = [radii[0], radii[1], radii[0], radii[1]];
+        break;
+      case 3:
+        corners = [radii[0], radii[1], radii[2], radii[1]];
+        break;
+      default:
+        corners = radii;
+    }
+    op_canvas_2d_round_rect(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      corners[0],
+      corners[1],
+      corners[2],
+      corners[3],
+    );
+  }
+
+  arc(x, y, radius, startAngle, endAngle, counterclockwise = false) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'arc' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 5, prefix);
+    counterclockwise = webidl.converters["boolean"](counterclockwise);
+    if (args === null) return;
+    if (args[2] < 0) {
+      throw new DOMException(
+        `${prefix}: The radius provided (${args[2]}) is negative`,
+        "IndexSizeError",
+      );
+    }
+    op_canvas_2d_ellipse(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[2],
+      0,
+      args[3],
+      args[4],
+      counterclockwise,
+    );
+  }
+
+  ellipse(
+    x,
+    y,
+    radiusX,
+    radiusY,
+    rotation,
+    startAngle,
+    endAngle,
+    counterclockwise = false,
+  ) {
+    webidl.assertBranded(this, OffscreenCanvasRenderingContext2DPrototype);
+    const prefix = `Failed to execute 'ellipse' on '${CONTEXT_PREFIX}'`;
+    const args = toFiniteDoubles(arguments, 7, prefix);
+    counterclockwise = webidl.converters["boolean"](counterclockwise);
+    if (args === null) return;
+    if (args[2] < 0 || args[3] < 0) {
+      throw new DOMException(
+        `${prefix}: The radius provided is negative`,
+        "IndexSizeError",
+      );
+    }
+    op_canvas_2d_ellipse(
+      this[_canvas][_canvas],
+      args[0],
+      args[1],
+      args[2],
+      args[3],
+      args[4],
+      args[5],
+      args[6],
+      counterclockwise,
+    );
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(
+          OffscreenCanvasRenderingContext2DPrototype,
+          this,
+        ),
+        keys: [
+          "canvas",
+          "fillStyle",
+          "strokeStyle",
+          "globalAlpha",
+          "lineWidth",
+          "font",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+const OffscreenCanvasRenderingContext2DPrototype =
+  OffscreenCanvasRenderingContext2D.prototype;
+
+function resetContext(context) {
+  context[_state] = defaultState();
+  context[_stateStack] = [];
+}
+
+function convertFillRule(fillRule, method) {
+  fillRule = webidl.converters["DOMString"](
+    fillRule,
+    `Failed to execute '${method}' on '${CONTEXT_PREFIX}'`,
+    "Argument 1",
+  );
+  if (!ArrayPrototypeIncludes(FILL_RULES, fillRule)) {
+    throw new TypeError(
+      `Failed to execute '${method}' on '${CONTEXT_PREFIX}': The provided value '${fillRule}' is not a valid enum value of type CanvasFillRule`,
+    );
+  }
+  return fillRule;
+}
+
+function setStyle(context, stroke, value) {
+  const state = context[_state];
+  if (ObjectPrototypeIsPrototypeOf(CanvasGradientPrototype, value)) {
+    if (stroke) {
+      state.strokeStyle = value;
+      state.strokeGradientVersion = 0;
+    } else {
+      state.fillStyle = value;
+      state.fillGradientVersion = 0;
+    }
+    return;
+  }
+  value = webidl.converters["DOMString"](
+    value,
+    `Failed to set '${stroke ? "strokeStyle" : "fillStyle"}' on '${CONTEXT_PREFIX}'`,
+    "Argument 1",
+  );
+  const serialized = op_canvas_2d_set_color(
+    context[_canvas][_canvas],
+    stroke,
+    value,
+  );
+  if (serialized === null) return;
+  if (stroke) {
+    state.strokeStyle = serialized;
+  } else {
+    state.fillStyle = serialized;
+  }
+}
+
+/**
+ * Gradients stay live after they are assigned, so color stops added since
+ * the last draw call are sent before drawing.
+ */
+function syncGradients(context) {
+  const state = context[_state];
+  const handle = context[_canvas][_canvas];
+  const fill = state.fillStyle;
+  if (
+    ObjectPrototypeIsPrototypeOf(CanvasGradientPrototype, fill) &&
+    state.fillGradientVersion !== fill[_version]
+  ) {
+    op_canvas_2d_set_gradient(handle, false, {
+      ...fill[_gradient],
+      stops: fill[_stops],
+    });
+    state.fillGradientVersion = fill[_version];
+  }
+  const stroke = state.strokeStyle;
+  if (
+    ObjectPrototypeIsPrototypeOf(CanvasGradientPrototype, stroke) &&
+    state.strokeGradientVersion !== stroke[_version]
+  ) {
+    op_canvas_2d_set_gradient(handle, true, {
+      ...stroke[_gradient],
+      stops: stroke[_stops],
+    });
+    state.strokeGradientVersion = stroke[_version];
+  }
+}
+
+function syncLineStyle(context) {
+  const state = context[_state];
+  op_canvas_2d_set_line_style(
+    context[_canvas][_canvas],
+    state.lineWidth,
+    ArrayPrototypeIndexOf(LINE_CAPS, state.lineCap),
+    ArrayPrototypeIndexOf(LINE_JOINS, state.lineJoin),
+    state.miterLimit,
+  );
+}
+
+function syncTextStyle(context) {
+  const state = context[_state];
+  let align = state.textAlign;
+  if (state.direction === "rtl") {
+    // `start` and `end` are relative to the text direction.
+    if (align === "start") align = "right";
+    else if (align === "end") align = "left";
+  }
+  op_canvas_2d_set_text_style(
+    context[_canvas][_canvas],
+    align,
+    state.textBaseline,
+  );
+}
+
+function drawText(context, method, args, stroke) {
+  const prefix = `Failed to execute '${method}' on '${CONTEXT_PREFIX}'`;
+  webidl.requiredArguments(args.length, 3, prefix);
+  const text = webidl.converters["DOMString"](args[0], prefix, "Argument 1");
+  const x = webidl.converters["unrestricted double"](
+    args[1],
+    prefix,
+    "Argument 2",
+  );
+  const y = webidl.converters["unrestricted double"](
+    args[2],
+    prefix,
+    "Argument 3",
+  );
+  let maxWidth = NumberPOSITIVE_INFINITY;
+  if (args[3] !== undefined) {
+    maxWidth = webidl.converters["unrestricted double"](
+      args[3],
+      prefix,
+      "Argument 4",
+    );
+    if (!NumberIsFinite(maxWidth)) return;
+  }
+  if (!NumberIsFinite(x) || !NumberIsFinite(y)) return;
+  syncGradients(context);
+  op_canvas_2d_fill_text(
+    context[_canvas][_canvas],
+    text,
+    x,
+    y,
+    maxWidth,
+    stroke,
+  );
+}
+
+class OffscreenCanvas {
+  [_canvas];
+  [_width];
+  [_height];
+  [_context];
+
+  /**
+   * @param {number} width
+   * @param {number} height
+   */
+  constructor(width, height) {
+    const prefix = "Failed to construct 'OffscreenCanvas'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    width = webidl.converters["unsigned long long"](
+      width,
+      prefix,
+      "Argument 1",
+      { enforceRange: true },
+    );
+    height = webidl.converters["unsigned long long"](
+      height,
+      prefix,
+      "Argument 2",
+      { enforceRange: true },
+    );
+    this[webidl.brand] = webidl.brand;
+    this[_width] = width;
+    this[_height] = height;
+    this[_context] = null;
+    this[_canvas] = op_canvas_2d_create(width, height);
+  }
+
+  get width() {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    return this[_width];
+  }
+
+  set width(value) {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    this[_width] = webidl.converters["unsigned long long"](
+      value,
+      "Failed to set 'width' on 'OffscreenCanvas'",
+      "Argument 1",
+      { enforceRange: true },
+    );
+    resizeCanvas(this);
+  }
+
+  get height() {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    return this[_height];
+  }
+
+  set height(value) {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    this[_height] = webidl.converters["unsigned long long"](
+      value,
+      "Failed to set 'height' on 'OffscreenCanvas'",
+      "Argument 1",
+      { enforceRange: true },
+    );
+    resizeCanvas(this);
+  }
+
+  /**
+   * @param {string} contextId
+   * @param {any} options
+   */
+  getContext(contextId, options = null) {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    const prefix = "Failed to execute 'getContext' on 'OffscreenCanvas'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    contextId = webidl.converters["OffscreenRenderingContextId"](
+      contextId,
+      prefix,
+      "Argument 1",
+    );
+    // Only the 2d context is supported.
+    if (contextId !== "2d") {
+      return null;
+    }
+    if (this[_context] === null) {
+      const context = webidl.createBranded(OffscreenCanvasRenderingContext2D);
+      context[_canvas] = this;
+      resetContext(context);
+      this[_context] = context;
+    }
+    return this[_context];
+  }
+
+  /**
+   * @returns {ImageBitmap}
+   */
+  transferToImageBitmap() {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    if (this[_context] === null) {
+      throw new DOMException(
+        "Failed to execute 'transferToImageBitmap' on 'OffscreenCanvas': Cannot transfer an ImageBitmap from an OffscreenCanvas with no context",
+        "InvalidStateError",
+      );
+    }
+    const width = this[_width];
+    const height = this[_height];
+    const imageBitmap = webidl.createBranded(ImageBitmap);
+    imageBitmap[_bitmapData] = op_canvas_2d_get_image_data(
+      this[_canvas],
+      0,
+      0,
+      width,
+      height,
+    );
+    imageBitmap[_width] = width;
+    imageBitmap[_height] = height;
+    op_canvas_2d_clear(this[_canvas]);
+    return imageBitmap;
+  }
+
+  /**
+   * @param {ImageEncodeOptions} options
+   * @returns {Promise<Blob>}
+   */
+  convertToBlob(options = undefined) {
+    webidl.assertBranded(this, OffscreenCanvasPrototype);
+    const prefix = "Failed to execute 'convertToBlob' on 'OffscreenCanvas'";
+    options = webidl.converters["ImageEncodeOptions"](
+      options,
+      prefix,
+      "Argument 1",
+    );
+    if (this[_width] === 0 || this[_height] === 0) {
+      return PromiseReject(
+        new DOMException(
+          `${prefix}: The canvas has ${
+            this[_width] === 0 ? "a width" : "a height"
+          } of 0`,
+          "IndexSizeError",
+        ),
+      );
+    }
+
+    let type = StringPrototypeToLowerCase(options.type);
+    let format;
+    if (type === "image/jpeg") {
+      format = 1;
+    } else if (type === "image/webp") {
+      format = 2;
+    } else {
+      // Unsupported types fall back to PNG.
+      type = "image/png";
+      format = 0;
+    }
+    let quality = options.quality;
+    if (quality === undefined || !(quality >= 0 && quality <= 1)) {
+      quality = 0.92;
+    }
+
+    try {
+      const data = op_canvas_2d_encode(this[_canvas], format, quality);
+      return PromiseResolve(new Blob([data], { type }));
+    } catch (error) {
+      return PromiseReject(error);
+    }
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(OffscreenCanvasPrototype, this),
+        keys: [
+          "width",
+          "height",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+const OffscreenCanvasPrototype = OffscreenCanvas.prototype;
+const ImageBitmapPrototype = ImageBitmap.prototype;
+
+/** Setting either dimension clears the bitmap and resets the context. */
+function resizeCanvas(canvas) {
+  op_canvas_2d_resize(canvas[_canvas], canvas[_width], canvas[_height]);
+  if (canvas[_context] !== null) {
+    resetContext(canvas[_context]);
+  }
+}
+
+class FontFace {
+  [_family];
+  [_style];
+  [_weight];
+  [_status];
+  [_source];
+  [_loaded];
+
+  /**
+   * @param {string} family
+   * @param {BufferSource | string} source
+   * @param {FontFaceDescriptors} descriptors
+   */
+  constructor(family, source, descriptors = {}) {
+    const prefix = "Failed to construct 'FontFace'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    family = webidl.converters["DOMString"](family, prefix, "Argument 1");
+    descriptors = webidl.converters["FontFaceDescriptors"](
+      descriptors,
+      prefix,
+      "Argument 3",
+    );
+    this[webidl.brand] = webidl.brand;
+    this[_family] = family;
+    this[_style] = descriptors.style;
+    this[_weight] = descriptors.weight;
+
+    if (typeof source === "string") {
+      // Only binary font data can be loaded; there is no font fetching.
+      this[_status] = "error";
+      this[_source] = null;
+      this[_loaded] = PromiseReject(
+        new DOMException(
+          "Loading fonts from a URL is not supported, pass the font data as a BufferSource",
+          "NetworkError",
+        ),
+      );
+      return;
+    }
+    source = webidl.converters["BufferSource"](source, prefix, "Argument 2");
+    this[_source] = copyBufferSource(source);
+    this[_status] = "loaded";
+    this[_loaded] = PromiseResolve(this);
+  }
+
+  get family() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_family];
+  }
+
+  get style() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_style];
+  }
+
+  get weight() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_weight];
+  }
+
+  get status() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_status];
+  }
+
+  get loaded() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_loaded];
+  }
+
+  load() {
+    webidl.assertBranded(this, FontFacePrototype);
+    return this[_loaded];
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(FontFacePrototype, this),
+        keys: [
+          "family",
+          "style",
+          "weight",
+          "status",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+const FontFacePrototype = FontFace.prototype;
+
+function copyBufferSource(source) {
+  if (ArrayBufferIsView(source)) {
+    return TypedArrayPrototypeSlice(
+      new Uint8Array(
+        source.buffer,
+        source.byteOffset,
+        source.byteLength,
+      ),
+    );
+  }
+  return new Uint8Array(ArrayBufferPrototypeSlice(source));
+}
+
+function parseFontWeight(weight) {
+  if (weight === "bold") return 700;
+  const value = NumberParseFloat(weight);
+  return NumberIsFinite(value) ? value : 400;
+}
+
+/**
+ * The fonts that are available to canvas text rendering.
+ */
+class FontFaceSet {
+  [_faces];
+
+  constructor() {
+    webidl.illegalConstructor();
+  }
+
+  get ready() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return PromiseResolve(this);
+  }
+
+  get status() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return "loaded";
+  }
+
+  get size() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return this[_faces].size;
+  }
+
+  /**
+   * @param {FontFace} font
+   */
+  add(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const prefix = "Failed to execute 'add' on 'FontFaceSet'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    if (!ObjectPrototypeIsPrototypeOf(FontFacePrototype, font)) {
+      throw new TypeError(`${prefix}: parameter 1 is not of type 'FontFace'`);
+    }
+    if (this[_faces].has(font)) {
+      return this;
+    }
+    if (font[_status] !== "loaded") {
+      throw new DOMException(
+        `${prefix}: The font face has not been loaded`,
+        "InvalidModificationError",
+      );
+    }
+    const style = font[_style];
+    const id = op_canvas_register_font(
+      font[_family],
+      font[_source],
+      parseFontWeight(font[_weight]),
+      style === "italic" || StringPrototypeStartsWith(style, "oblique"),
+    );
+    this[_faces].set(font, id);
+    return this;
+  }
+
+  /**
+   * @param {FontFace} font
+   */
+  delete(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const id = this[_faces].get(font);
+    if (id === undefined) {
+      return false;
+    }
+    op_canvas_unregister_font(id);
+    this[_faces].delete(font);
+    return true;
+  }
+
+  /**
+   * @param {FontFace} font
+   */
+  has(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    return this[_faces].has(font);
+  }
+
+  clear() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    for (const { 1: id } of new SafeMapIterator(this[_faces])) {
+      op_canvas_unregister_font(id);
+    }
+    this[_faces].clear();
+  }
+
+  /**
+   * @param {string} font
+   * @returns {boolean}
+   */
+  check(font) {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    const prefix = "Failed to execute 'check' on 'FontFaceSet'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    font = webidl.converters["DOMString"](font, prefix, "Argument 1");
+    if (parseFont(font) === null) {
+      throw new DOMException(
+        `${prefix}: Could not resolve '${font}' as a font`,
+        "SyntaxError",
+      );
+    }
+    // Every face is already loaded.
+    return true;
+  }
+
+  *values() {
+    webidl.assertBranded(this, FontFaceSetPrototype);
+    for (const { 0: font } of new SafeMapIterator(this[_faces])) {
+      yield font;
+    }
+  }
+
+  [SymbolIterator]() {
+    return this.values();
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
+    return inspect(
+      createFilteredInspectProxy({
+        object: this,
+        evaluate: ObjectPrototypeIsPrototypeOf(FontFaceSetPrototype, this),
+        keys: [
+          "size",
+          "status",
+        ],
+      }),
+      inspectOptions,
+    );
+  }
+}
+const FontFaceSetPrototype = FontFaceSet.prototype;
+
+const fonts = webidl.createBranded(FontFaceSet);
+fonts[_faces] = new SafeMap();
+
+export {
+  CanvasGradient,
+  FontFace,
+  fonts,
+  FontFaceSet,
+  OffscreenCanvas,
+  OffscreenCanvasRenderingContext2D,
+  TextMetrics,
+};
@@ -0,0 +1,1503 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::cell::RefCell;
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::TAU;
+
+use deno_core::GarbageCollected;
+use deno_core::OpState;
+use deno_core::ToJsBuffer;
+use deno_core::op2;
+use image::ExtendedColorType;
+use image::ImageEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use serde::Deserialize;
+use tiny_skia::BlendMode;
+use tiny_skia::Color;
+use tiny_skia::FillRule;
+use tiny_skia::FilterQuality;
+use tiny_skia::GradientStop;
+use tiny_skia::LineCap;
+use tiny_skia::LineJoin;
+use tiny_skia::LinearGradient;
+use tiny_skia::Mask;
+use tiny_skia::Paint;
+use tiny_skia::Path;
+use tiny_skia::PathBuilder;
+use tiny_skia::Pattern;
+use tiny_skia::Pixmap;
+use tiny_skia::Point;
+use tiny_skia::RadialGradient;
+use tiny_skia::Rect;
+use tiny_skia::Shader;
+use tiny_skia::SpreadMode;
+use tiny_skia::Stroke;
+use tiny_skia::StrokeDash;
+use tiny_skia::Transform;
+
+use crate::CanvasError;
+use crate::font::FontRegistry;
+use crate::font::shape_text;
+
+/// The bitmap of an `OffscreenCanvas` together with the state of its
+/// `OffscreenCanvasRenderingContext2D`.
+pub struct Canvas2d(RefCell<Canvas2dInner>);
+
+impl GarbageCollected for Canvas2d {
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"Canvas2d"
+  }
+}
+
+struct Canvas2dInner {
+  width: u32,
+  height: u32,
+  /// `None` when either dimension is zero.
+  pixmap: Option<Pixmap>,
+  state: DrawingState,
+  stack: Vec<DrawingState>,
+  path: CurrentPath,
+}
+
+#[derive(Clone)]
+struct DrawingState {
+  transform: Transform,
+  clip: Option<Mask>,
+  fill_style: Style,
+  stroke_style: Style,
+  line_width: f32,
+  line_cap: LineCap,
+  line_join: LineJoin,
+  miter_limit: f32,
+  line_dash: Vec<f32>,
+  line_dash_offset: f32,
+  global_alpha: f32,
+  blend_mode: BlendMode,
+  image_smoothing: Option<FilterQuality>,
+  font_families: String,
+  font_size: f32,
+  font_weight: u16,
+  font_italic: bool,
+  text_align: TextAlign,
+  text_baseline: TextBaseline,
+}
+
+impl Default for DrawingState {
+  fn default() -> Self {
+    Self {
+      transform: Transform::identity(),
+      clip: None,
+      fill_style: Style::Color(Color::BLACK),
+      stroke_style: Style::Color(Color::BLACK),
+      line_width: 1.0,
+      line_cap: LineCap::Butt,
+      line_join: LineJoin::Miter,
+      miter_limit: 10.0,
+      line_dash: Vec::new(),
+      line_dash_offset: 0.0,
+      global_alpha: 1.0,
+      blend_mode: BlendMode::SourceOver,
+      image_smoothing: Some(FilterQuality::Bilinear),
+      font_families: "sans-serif".to_string(),
+      font_size: 10.0,
+      font_weight: 400,
+      font_italic: false,
+      text_align: TextAlign::Start,
+      text_baseline: TextBaseline::Alphabetic,
+    }
+  }
+}
+
+#[derive(Clone)]
+enum Style {
+  Color(Color),
+  LinearGradient {
+    start: Point,
+    end: Point,
+    stops: Vec<(f32, Color)>,
+  },
+  RadialGradient {
+    start: Point,
+    end: Point,
+    radius: f32,
+    stops: Vec<(f32, Color)>,
+  },
+}
+
+#[derive(Clone, Copy)]
+enum TextAlign {
+  Start,
+  End,
+  Left,
+  Right,
+  Center,
+}
+
+#[derive(Clone, Copy)]
+enum TextBaseline {
+  Top,
+  Hanging,
+  Middle,
+  Alphabetic,
+  Ideographic,
+  Bottom,
+}
+
+/// The current default path. Points are stored in device space, as the
+/// transform in effect when they were added applies to them.
+#[derive(Default)]
+struct CurrentPath {
+  builder: PathBuilder,
+  last_point: Option<Point>,
+  subpath_start: Option<Point>,
+}
+
+impl CurrentPath {
+  fn move_to(&mut self, p: Point) {
+    self.builder.move_to(p.x, p.y);
+    self.last_point = Some(p);
+    self.subpath_start = Some(p);
+  }
+
+  fn line_to(&mut self, p: Point) {
+    if self.last_point.is_none() {
+      self.move_to(p);
+    }
+    self.builder.line_to(p.x, p.y);
+    self.last_point = Some(p);
+  }
+
+  fn quad_to(&mut self, p1: Point, p: Point) {
+    if self.last_point.is_none() {
+      self.move_to(p1);
+    }
+    self.builder.quad_to(p1.x, p1.y, p.x, p.y);
+    self.last_point = Some(p);
+  }
+
+  fn cubic_to(&mut self, p1: Point, p2: Point, p: Point) {
+    if self.last_point.is_none() {
+      self.move_to(p1);
+    }
+    self.builder.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+    self.last_point = Some(p);
+  }
+
+  fn close(&mut self) {
+    if self.last_point.is_some() {
+      self.builder.close();
+      self.last_point = self.subpath_start;
+    }
+  }
+
+  fn to_path(&self) -> Option<Path> {
+    self.builder.clone().finish()
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradientArgs {
+  radial: bool,
+  x0: f32,
+  y0: f32,
+  x1: f32,
+  y1: f32,
+  r1: f32,
+  stops: Vec<(f32, String)>,
+}
+
+fn map_point(transform: &Transform, x: f32, y: f32) -> Point {
+  let mut point = Point::from_xy(x, y);
+  transform.map_point(&mut point);
+  point
+}
+
+fn parse_color(color: &str) -> Option<Color> {
+  let color = csscolorparser::parse(color).ok()?;
+  Color::from_rgba(color.r, color.g, color.b, color.a)
+}
+
+/// Serialize a color the way `fillStyle` and `strokeStyle` report it.
+fn serialize_color(color: Color) -> String {
+  let alpha = (color.alpha() * 1000.0).round() / 1000.0;
+  let color = color.to_color_u8();
+  if color.alpha() == 255 {
+    format!(
+      "#{:02x}{:02x}{:02x}",
+      color.red(),
+      color.green(),
+      color.blue()
+    )
+  } else {
+    format!(
+      "rgba({}, {}, {}, {})",
+      color.red(),
+      color.green(),
+      color.blue(),
+      alpha
+    )
+  }
+}
+
+fn fill_rule(evenodd: bool) -> FillRule {
+  if evenodd {
+    FillRule::EvenOdd
+  } else {
+    FillRule::Winding
+  }
+}
+
+fn rect_path(x: f32, y: f32, w: f32, h: f32) -> Option<Path> {
+  let (x, w) = if w < 0.0 { (x + w, -w) } else { (x, w) };
+  let (y, h) = if h < 0.0 { (y + h, -h) } else { (y, h) };
+  Rect::from_xywh(x, y, w, h).map(PathBuilder::from_rect)
+}
+
+impl Canvas2dInner {
+  fn new(width: u32, height: u32) -> Self {
+    Self {
+      width,
+      height,
+      pixmap: Pixmap::new(width, height),
+      state: DrawingState::default(),
+      stack: Vec::new(),
+      path: CurrentPath::default(),
+    }
+  }
+
+  /// Build a paint for the given style. `shader_transform` maps the
+  /// coordinate space of the geometry being drawn to the user space the
+  /// gradient was defined in.
+  fn paint(&self, stroke: bool, shader_transform: Transform) -> Paint<'static> {
+    let alpha = self.state.global_alpha;
+    let style = if stroke {
+      &self.state.stroke_style
+    } else {
+      &self.state.fill_style
+    };
+    let gradient_stops = |stops: &[(f32, Color)]| {
+      stops
+        .iter()
+        .map(|(offset, color)| {
+          let mut color = *color;
+          color.apply_opacity(alpha);
+          GradientStop::new(*offset, color)
+        })
+        .collect::<Vec<_>>()
+    };
+    let shader = match style {
+      Style::Color(color) => {
+        let mut color = *color;
+        color.apply_opacity(alpha);
+        Some(Shader::SolidColor(color))
+      }
+      Style::LinearGradient { start, end, stops } => LinearGradient::new(
+        *start,
+        *end,
+        gradient_stops(stops),
+        SpreadMode::Pad,
+        shader_transform,
+      ),
+      Style::RadialGradient {
+        start,
+        end,
+        radius,
+        stops,
+      } => RadialGradient::new(
+        *start,
+        *end,
+        *radius,
+        gradient_stops(stops),
+        SpreadMode::Pad,
+        shader_transform,
+      ),
+    };
+
+    Paint {
+      // Degenerate gradients paint nothing.
+      shader: shader.unwrap_or(Shader::SolidColor(Color::TRANSPARENT)),
+      blend_mode: self.state.blend_mode,
+      anti_alias: true,
+      force_hq_pipeline: false,
+    }
+  }
+
+  fn stroke(&self) -> Stroke {
+    let dash = if self.state.line_dash.is_empty() {
+      None
+    } else {
+      StrokeDash::new(self.state.line_dash.clone(), self.state.line_dash_offset)
+    };
+    Stroke {
+      width: self.state.line_width,
+      miter_limit: self.state.miter_limit,
+      line_cap: self.state.line_cap,
+      line_join: self.state.line_join,
+      dash,
+    }
+  }
+
+  /// Fill a path given in the coordinate space described by `transform`.
+  fn fill_path(
+    &mut self,
+    path: &Path,
+    rule: FillRule,
+    transform: Transform,
+    shader_transform: Transform,
+  ) {
+    let paint = self.paint(false, shader_transform);
+    let Some(pixmap) = self.pixmap.as_mut() else {
+      return;
+    };
+    pixmap.fill_path(path, &paint, rule, transform, self.state.clip.as_ref());
+  }
+
+  fn clear_path(&mut self, path: &Path, transform: Transform) {
+    let paint = Paint {
+      blend_mode: BlendMode::Clear,
+      ..Paint::default()
+    };
+    let Some(pixmap) = self.pixmap.as_mut() else {
+      return;
+    };
+    pixmap.fill_path(
+      path,
+      &paint,
+      FillRule::Winding,
+      transform,
+      self.state.clip.as_ref(),
+    );
+  }
+
+  /// Stroke a path given in the coordinate space described by `transform`.
+  fn stroke_path(&mut self, path: &Path, transform: Transform) {
+    if !(self.state.line_width > 0.0) {
+      return;
+    }
+    let paint = self.paint(true, Transform::identity());
+    let stroke = self.stroke();
+    let Some(pixmap) = self.pixmap.as_mut() else {
+      return;
+    };
+    pixmap.stroke_path(
+      path,
+      &paint,
+      &stroke,
+      transform,
+      self.state.clip.as_ref(),
+    );
+  }
+
+  fn user_point(&self, point: Point) -> Option<Point> {
+    let inverse = self.state.transform.invert()?;
+    let mut point = point;
+    inverse.map_point(&mut point);
+    Some(point)
+  }
+
+  /// Add an elliptical arc, in user space, to the current path. The arc is
+  /// connected to the current point with a straight line.
+  #[allow(clippy::too_many_arguments)]
+  fn arc_segment(
+    &mut self,
+    cx: f32,
+    cy: f32,
+    rx: f32,
+    ry: f32,
+    rotation: f32,
+    start: f32,
+    sweep: f32,
+  ) {
+    let transform = self.state.transform;
+    let (sin_r, cos_r) = rotation.sin_cos();
+    let point_at = |angle: f32| {
+      let (sin, cos) = angle.sin_cos();
+      let (x, y) = (rx * cos, ry * sin);
+      map_point(
+        &transform,
+        cx + x * cos_r - y * sin_r,
+        cy + x * sin_r + y * cos_r,
+      )
+    };
+    let tangent_at = |angle: f32| {
+      let (sin, cos) = angle.sin_cos();
+      let (x, y) = (-rx * sin, ry * cos);
+      (x * cos_r - y * sin_r, x * sin_r + y * cos_r)
+    };
+
+    self.path.line_to(point_at(start));
+
+    let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep / segments as f32;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    for i in 0..segments {
+      let a0 = start + step * i as f32;
+      let a1 = a0 + step;
+      let (sin0, cos0) = a0.sin_cos();
+      let (sin1, cos1) = a1.sin_cos();
+      let p0 = (rx * cos0, ry * sin0);
+      let p1 = (rx * cos1, ry * sin1);
+      let (t0x, t0y) = tangent_at(a0);
+      let (t1x, t1y) = tangent_at(a1);
+      let rotate = |(x, y): (f32, f32)| {
+        (cx + x * cos_r - y * sin_r, cy + x * sin_r + y * cos_r)
+      };
+      let (p0x, p0y) = rotate(p0);
+      let (p1x, p1y) = rotate(p1);
+      self.path.cubic_to(
+        map_point(&transform, p0x + k * t0x, p0y + k * t0y),
+        map_point(&transform, p1x - k * t1x, p1y - k * t1y),
+        point_at(a1),
+      );
+    }
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn ellipse(
+    &mut self,
+    x: f32,
+    y: f32,
+    rx: f32,
+    ry: f32,
+    rotation: f32,
+    start: f32,
+    end: f32,
+    counterclockwise: bool,
+  ) {
+    let sweep = if !counterclockwise && end - start >= TAU {
+      TAU
+    } else if counterclockwise && start - end >= TAU {
+      -TAU
+    } else if counterclockwise {
+      -(start - end).rem_euclid(TAU)
+    } else {
+      (end - start).rem_euclid(TAU)
+    };
+    self.arc_segment(x, y, rx, ry, rotation, start, sweep);
+  }
+
+  fn arc_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, radius: f32) {
+    let transform = self.state.transform;
+    let Some(last) = self.path.last_point else {
+      self.path.move_to(map_point(&transform, x1, y1));
+      return;
+    };
+    let Some(p0) = self.user_point(last) else {
+      return;
+    };
+
+    let (v1x, v1y) = (p0.x - x1, p0.y - y1);
+    let (v2x, v2y) = (x2 - x1, y2 - y1);
+    let len1 = v1x.hypot(v1y);
+    let len2 = v2x.hypot(v2y);
+    let cross = v1x * v2y - v1y * v2x;
+    if len1 == 0.0 || len2 == 0.0 || radius == 0.0 || cross.abs() < 1e-6 {
+      self.path.line_to(map_point(&transform, x1, y1));
+      return;
+    }
+
+    let (n1x, n1y) = (v1x / len1, v1y / len1);
+    let (n2x, n2y) = (v2x / len2, v2y / len2);
+    let angle = (n1x * n2x + n1y * n2y).clamp(-1.0, 1.0).acos();
+    let tangent_distance = radius / (angle / 2.0).tan();
+    let (t1x, t1y) = (x1 + n1x * tangent_distance, y1 + n1y * tangent_distance);
+    let (t2x, t2y) = (x1 + n2x * tangent_distance, y1 + n2y * tangent_distance);
+    let (bx, by) = (n1x + n2x, n1y + n2y);
+    let bisector = bx.hypot(by);
+    let center_distance = radius / (angle / 2.0).sin();
+    let cx = x1 + bx / bisector * center_distance;
+    let cy = y1 + by / bisector * center_distance;
+
+    let a0 = (t1y - cy).atan2(t1x - cx);
+    let a1 = (t2y - cy).atan2(t2x - cx);
+    let mut sweep = a1 - a0;
+    if sweep > std::f32::consts::PI {
+      sweep -= TAU;
+    } else if sweep < -std::f32::consts::PI {
+      sweep += TAU;
+    }
+    self.arc_segment(cx, cy, radius, radius, 0.0, a0, sweep);
+  }
+
+  fn rect(&mut self, x: f32, y: f32, w: f32, h: f32) {
+    let transform = self.state.transform;
+    self.path.move_to(map_point(&transform, x, y));
+    self.path.line_to(map_point(&transform, x + w, y));
+    self.path.line_to(map_point(&transform, x + w, y + h));
+    self.path.line_to(map_point(&transform, x, y + h));
+    self.path.close();
+    self.path.move_to(map_point(&transform, x, y));
+  }
+
+  fn round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radii: [f32; 4]) {
+    let (x, w) = if w < 0.0 { (x + w, -w) } else { (x, w) };
+    let (y, h) = if h < 0.0 { (y + h, -h) } else { (y, h) };
+    let [tl, tr, br, bl] = radii;
+    let mut scale = 1.0f32;
+    for (sum, side) in [(tl + tr, w), (bl + br, w), (tl + bl, h), (tr + br, h)]
+    {
+      if sum > side && sum > 0.0 {
+        scale = scale.min(side / sum);
+      }
+    }
+    let [tl, tr, br, bl] = [tl * scale, tr * scale, br * scale, bl * scale];
+
+    let transform = self.state.transform;
+    let pi = std::f32::consts::PI;
+    self.path.move_to(map_point(&transform, x + tl, y));
+    self.arc_segment(x + w - tr, y + tr, tr, tr, 0.0, -FRAC_PI_2, FRAC_PI_2);
+    self.arc_segment(x + w - br, y + h - br, br, br, 0.0, 0.0, FRAC_PI_2);
+    self.arc_segment(x + bl, y + h - bl, bl, bl, 0.0, FRAC_PI_2, FRAC_PI_2);
+    self.arc_segment(x + tl, y + tl, tl, tl, 0.0, pi, FRAC_PI_2);
+    self.path.close();
+    self.path.move_to(map_point(&transform, x, y));
+  }
+
+  fn clip(&mut self, evenodd: bool) {
+    let Some(path) = self.path.to_path() else {
+      // Clipping to an empty path clips everything.
+      self.state.clip = Mask::new(self.width, self.height);
+      return;
+    };
+    let rule = fill_rule(evenodd);
+    match &mut self.state.clip {
+      Some(mask) => {
+        mask.intersect_path(&path, rule, true, Transform::identity())
+      }
+      None => {
+        if let Some(mut mask) = Mask::new(self.width, self.height) {
+          mask.fill_path(&path, rule, true, Transform::identity());
+          self.state.clip = Some(mask);
+        }
+      }
+    }
+  }
+
+  fn draw_text(
+    &mut self,
+    fonts: &FontRegistry,
+    text: &str,
+    x: f32,
+    y: f32,
+    max_width: f32,
+    stroke: bool,
+  ) {
+    let Some(font) = fonts.select(
+      &self.state.font_families,
+      self.state.font_weight,
+      self.state.font_italic,
+    ) else {
+      return;
+    };
+    let run = shape_text(&font, self.state.font_size, text);
+    let Some(path) = run.path else {
+      return;
+    };
+    if max_width <= 0.0 {
+      return;
+    }
+    let scale_x = if run.width > max_width {
+      max_width / run.width
+    } else {
+      1.0
+    };
+    let (dx, dy) = self.text_offset(run.width, run.ascent, run.descent);
+    let transform = self
+      .state
+      .transform
+      .pre_translate(x + dx * scale_x, y + dy)
+      .pre_scale(scale_x, 1.0);
+    if stroke {
+      self.stroke_path(&path, transform);
+    } else {
+      self.fill_path(
+        &path,
+        FillRule::Winding,
+        transform,
+        Transform::identity(),
+      );
+    }
+  }
+
+  /// Offset from the anchor point to the start of the alphabetic baseline.
+  fn text_offset(&self, width: f32, ascent: f32, descent: f32) -> (f32, f32) {
+    let dx = match self.state.text_align {
+      TextAlign::Start | TextAlign::Left => 0.0,
+      TextAlign::Center => -width / 2.0,
+      TextAlign::End | TextAlign::Right => -width,
+    };
+    let dy = match self.state.text_baseline {
+      TextBaseline::Top => ascent,
+      TextBaseline::Hanging => ascent * 0.8,
+      TextBaseline::Middle => (ascent - descent) / 2.0,
+      TextBaseline::Alphabetic => 0.0,
+      TextBaseline::Ideographic | TextBaseline::Bottom => -descent,
+    };
+    (dx, dy)
+  }
+
+  /// Draw straight-alpha RGBA pixels. `source` is `(sx, sy, sw, sh)` in
+  /// image pixels and `dest` is `(dx, dy, dw, dh)` in user space.
+  fn draw_image(
+    &mut self,
+    image: &Pixmap,
+    source: (f32, f32, f32, f32),
+    dest: (f32, f32, f32, f32),
+  ) {
+    let (mut sx, mut sy, mut sw, mut sh) = source;
+    let (mut dx, mut dy, mut dw, mut dh) = dest;
+    if sw < 0.0 {
+      sx += sw;
+      sw = -sw;
+    }
+    if sh < 0.0 {
+      sy += sh;
+      sh = -sh;
+    }
+    if dw < 0.0 {
+      dx += dw;
+      dw = -dw;
+    }
+    if dh < 0.0 {
+      dy += dh;
+      dh = -dh;
+    }
+    if sw == 0.0 || sh == 0.0 {
+      return;
+    }
+
+    // Clip the source rectangle to the image and shrink the destination
+    // rectangle by the same proportion.
+    let (scale_x, scale_y) = (dw / sw, dh / sh);
+    let (iw, ih) = (image.width() as f32, image.height() as f32);
+    let left = sx.max(0.0);
+    let top = sy.max(0.0);
+    let right = (sx + sw).min(iw);
+    let bottom = (sy + sh).min(ih);
+    if right <= left || bottom <= top {
+      return;
+    }
+    dx += (left - sx) * scale_x;
+    dy += (top - sy) * scale_y;
+    dw = (right - left) * scale_x;
+    dh = (bottom - top) * scale_y;
+
+    let Some(rect) = Rect::from_xywh(dx, dy, dw, dh) else {
+      return;
+    };
+    let pattern_transform = Transform::from_translate(dx, dy)
+      .pre_scale(scale_x, scale_y)
+      .pre_translate(-left, -top);
+    let paint = Paint {
+      shader: Pattern::new(
+        image.as_ref(),
+        SpreadMode::Pad,
+        self.state.image_smoothing.unwrap_or(FilterQuality::Nearest),
+        self.state.global_alpha,
+        pattern_transform,
+      ),
+      blend_mode: self.state.blend_mode,
+      anti_alias: true,
+      force_hq_pipeline: false,
+    };
+    let transform = self.state.transform;
+    let clip = self.state.clip.as_ref();
+    if let Some(pixmap) = self.pixmap.as_mut() {
+      pixmap.fill_path(
+        &PathBuilder::from_rect(rect),
+        &paint,
+        FillRule::Winding,
+        transform,
+        clip,
+      );
+    }
+  }
+
+  /// Read a rectangle of the bitmap as straight-alpha RGBA. Pixels outside
+  /// of the bitmap are transparent black.
+  fn read_pixels(&self, x: i32, y: i32, width: u32, height: u32) -> Vec<u8> {
+    let mut out = vec![0; width as usize * height as usize * 4];
+    let Some(pixmap) = self.pixmap.as_ref() else {
+      return out;
+    };
+    for row in 0..height as i32 {
+      let src_y = y + row;
+      if src_y < 0 || src_y >= self.height as i32 {
+        continue;
+      }
+      for col in 0..width as i32 {
+        let src_x = x + col;
+        if src_x < 0 || src_x >= self.width as i32 {
+          continue;
+        }
+        let Some(pixel) = pixmap.pixel(src_x as u32, src_y as u32) else {
+          continue;
+        };
+        let pixel = pixel.demultiply();
+        let offset = (row as usize * width as usize + col as usize) * 4;
+        out[offset..offset + 4].copy_from_slice(&[
+          pixel.red(),
+          pixel.green(),
+          pixel.blue(),
+          pixel.alpha(),
+        ]);
+      }
+    }
+    out
+  }
+}
+
+/// Convert straight-alpha RGBA bytes into a premultiplied pixmap.
+fn pixmap_from_rgba(
+  data: &[u8],
+  width: u32,
+  height: u32,
+) -> Result<Option<Pixmap>, CanvasError> {
+  let Some(mut pixmap) = Pixmap::new(width, height) else {
+    return Ok(None);
+  };
+  if data.len() < width as usize * height as usize * 4 {
+    return Err(CanvasError::NotBigEnoughChunk(width, height));
+  }
+  for (pixel, rgba) in pixmap.pixels_mut().iter_mut().zip(data.chunks_exact(4))
+  {
+    *pixel = tiny_skia::ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+      .premultiply();
+  }
+  Ok(Some(pixmap))
+}
+
+#[op2]
+#[cppgc]
+pub fn op_canvas_2d_create(width: u32, height: u32) -> Canvas2d {
+  Canvas2d(RefCell::new(Canvas2dInner::new(width, height)))
+}
+
+/// Resizing a canvas clears the bitmap and resets the context state.
+#[op2(fast)]
+pub fn op_canvas_2d_resize(#[cppgc] ctx: &Canvas2d, width: u32, height: u32) {
+  *ctx.0.borrow_mut() = Canvas2dInner::new(width, height);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_reset(#[cppgc] ctx: &Canvas2d) {
+  let mut ctx = ctx.0.borrow_mut();
+  let (width, height) = (ctx.width, ctx.height);
+  *ctx = Canvas2dInner::new(width, height);
+}
+
+/// Clear the bitmap to transparent black, keeping the context state.
+#[op2(fast)]
+pub fn op_canvas_2d_clear(#[cppgc] ctx: &Canvas2d) {
+  if let Some(pixmap) = ctx.0.borrow_mut().pixmap.as_mut() {
+    pixmap.fill(Color::TRANSPARENT);
+  }
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_save(#[cppgc] ctx: &Canvas2d) {
+  let mut ctx = ctx.0.borrow_mut();
+  let state = ctx.state.clone();
+  ctx.stack.push(state);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_restore(#[cppgc] ctx: &Canvas2d) {
+  let mut ctx = ctx.0.borrow_mut();
+  if let Some(state) = ctx.stack.pop() {
+    ctx.state = state;
+  }
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_transform(
+  #[cppgc] ctx: &Canvas2d,
+  a: f64,
+  b: f64,
+  c: f64,
+  d: f64,
+  e: f64,
+  f: f64,
+) {
+  let transform = Transform::from_row(
+    a as f32, b as f32, c as f32, d as f32, e as f32, f as f32,
+  );
+  ctx.0.borrow_mut().state.transform = transform;
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_transform(
+  #[cppgc] ctx: &Canvas2d,
+  a: f64,
+  b: f64,
+  c: f64,
+  d: f64,
+  e: f64,
+  f: f64,
+) {
+  let transform = Transform::from_row(
+    a as f32, b as f32, c as f32, d as f32, e as f32, f as f32,
+  );
+  let mut ctx = ctx.0.borrow_mut();
+  ctx.state.transform = ctx.state.transform.pre_concat(transform);
+}
+
+/// Parse a CSS color, returning its serialized form or `null` if the color
+/// is invalid.
+#[op2]
+#[string]
+pub fn op_canvas_parse_color(#[string] color: &str) -> Option<String> {
+  parse_color(color).map(serialize_color)
+}
+
+/// Set `fillStyle` or `strokeStyle` to a CSS color. Invalid colors are
+/// ignored and return `null`.
+#[op2]
+#[string]
+pub fn op_canvas_2d_set_color(
+  #[cppgc] ctx: &Canvas2d,
+  stroke: bool,
+  #[string] color: &str,
+) -> Option<String> {
+  let color = parse_color(color)?;
+  let mut ctx = ctx.0.borrow_mut();
+  if stroke {
+    ctx.state.stroke_style = Style::Color(color);
+  } else {
+    ctx.state.fill_style = Style::Color(color);
+  }
+  Some(serialize_color(color))
+}
+
+#[op2]
+pub fn op_canvas_2d_set_gradient(
+  #[cppgc] ctx: &Canvas2d,
+  stroke: bool,
+  #[serde] gradient: GradientArgs,
+) {
+  let stops = gradient
+    .stops
+    .iter()
+    .filter_map(|(offset, color)| Some((*offset, parse_color(color)?)))
+    .collect();
+  let start = Point::from_xy(gradient.x0, gradient.y0);
+  let end = Point::from_xy(gradient.x1, gradient.y1);
+  let style = if gradient.radial {
+    Style::RadialGradient {
+      start,
+      end,
+      radius: gradient.r1,
+      stops,
+    }
+  } else {
+    Style::LinearGradient { start, end, stops }
+  };
+  let mut ctx = ctx.0.borrow_mut();
+  if stroke {
+    ctx.state.stroke_style = style;
+  } else {
+    ctx.state.fill_style = style;
+  }
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_line_style(
+  #[cppgc] ctx: &Canvas2d,
+  width: f64,
+  cap: u32,
+  join: u32,
+  miter_limit: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  ctx.state.line_width = width as f32;
+  ctx.state.line_cap = match cap {
+    1 => LineCap::Round,
+    2 => LineCap::Square,
+    _ => LineCap::Butt,
+  };
+  ctx.state.line_join = match join {
+    1 => LineJoin::Round,
+    2 => LineJoin::Bevel,
+    _ => LineJoin::Miter,
+  };
+  ctx.state.miter_limit = miter_limit as f32;
+}
+
+#[op2]
+pub fn op_canvas_2d_set_line_dash(
+  #[cppgc] ctx: &Canvas2d,
+  #[serde] segments: Vec<f32>,
+  offset: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  ctx.state.line_dash = segments;
+  ctx.state.line_dash_offset = offset as f32;
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_global_alpha(#[cppgc] ctx: &Canvas2d, alpha: f64) {
+  ctx.0.borrow_mut().state.global_alpha = alpha as f32;
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_composite_operation(
+  #[cppgc] ctx: &Canvas2d,
+  #[string] operation: &str,
+) {
+  let blend_mode = match operation {
+    "source-over" => BlendMode::SourceOver,
+    "source-in" => BlendMode::SourceIn,
+    "source-out" => BlendMode::SourceOut,
+    "source-atop" => BlendMode::SourceAtop,
+    "destination-over" => BlendMode::DestinationOver,
+    "destination-in" => BlendMode::DestinationIn,
+    "destination-out" => BlendMode::DestinationOut,
+    "destination-atop" => BlendMode::DestinationAtop,
+    "lighter" => BlendMode::Plus,
+    "copy" => BlendMode::Source,
+    "xor" => BlendMode::Xor,
+    "multiply" => BlendMode::Multiply,
+    "screen" => BlendMode::Screen,
+    "overlay" => BlendMode::Overlay,
+    "darken" => BlendMode::Darken,
+    "lighten" => BlendMode::Lighten,
+    "color-dodge" => BlendMode::ColorDodge,
+    "color-burn" => BlendMode::ColorBurn,
+    "hard-light" => BlendMode::HardLight,
+    "soft-light" => BlendMode::SoftLight,
+    "difference" => BlendMode::Difference,
+    "exclusion" => BlendMode::Exclusion,
+    "hue" => BlendMode::Hue,
+    "saturation" => BlendMode::Saturation,
+    "color" => BlendMode::Color,
+    "luminosity" => BlendMode::Luminosity,
+    _ => return,
+  };
+  ctx.0.borrow_mut().state.blend_mode = blend_mode;
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_image_smoothing(
+  #[cppgc] ctx: &Canvas2d,
+  enabled: bool,
+  #[string] quality: &str,
+) {
+  ctx.0.borrow_mut().state.image_smoothing = if !enabled {
+    None
+  } else if quality == "low" {
+    Some(FilterQuality::Bilinear)
+  } else {
+    Some(FilterQuality::Bicubic)
+  };
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_font(
+  #[cppgc] ctx: &Canvas2d,
+  #[string] families: &str,
+  size: f64,
+  weight: u32,
+  italic: bool,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  ctx.state.font_families = families.to_string();
+  ctx.state.font_size = size as f32;
+  ctx.state.font_weight = weight.clamp(1, 1000) as u16;
+  ctx.state.font_italic = italic;
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_set_text_style(
+  #[cppgc] ctx: &Canvas2d,
+  #[string] align: &str,
+  #[string] baseline: &str,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  ctx.state.text_align = match align {
+    "end" => TextAlign::End,
+    "left" => TextAlign::Left,
+    "right" => TextAlign::Right,
+    "center" => TextAlign::Center,
+    _ => TextAlign::Start,
+  };
+  ctx.state.text_baseline = match baseline {
+    "top" => TextBaseline::Top,
+    "hanging" => TextBaseline::Hanging,
+    "middle" => TextBaseline::Middle,
+    "ideographic" => TextBaseline::Ideographic,
+    "bottom" => TextBaseline::Bottom,
+    _ => TextBaseline::Alphabetic,
+  };
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_begin_path(#[cppgc] ctx: &Canvas2d) {
+  ctx.0.borrow_mut().path = CurrentPath::default();
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_close_path(#[cppgc] ctx: &Canvas2d) {
+  ctx.0.borrow_mut().path.close();
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_move_to(#[cppgc] ctx: &Canvas2d, x: f64, y: f64) {
+  let mut ctx = ctx.0.borrow_mut();
+  let point = map_point(&ctx.state.transform, x as f32, y as f32);
+  ctx.path.move_to(point);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_line_to(#[cppgc] ctx: &Canvas2d, x: f64, y: f64) {
+  let mut ctx = ctx.0.borrow_mut();
+  let point = map_point(&ctx.state.transform, x as f32, y as f32);
+  ctx.path.line_to(point);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_quadratic_curve_to(
+  #[cppgc] ctx: &Canvas2d,
+  cpx: f64,
+  cpy: f64,
+  x: f64,
+  y: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let transform = ctx.state.transform;
+  ctx.path.quad_to(
+    map_point(&transform, cpx as f32, cpy as f32),
+    map_point(&transform, x as f32, y as f32),
+  );
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_bezier_curve_to(
+  #[cppgc] ctx: &Canvas2d,
+  cp1x: f64,
+  cp1y: f64,
+  cp2x: f64,
+  cp2y: f64,
+  x: f64,
+  y: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let transform = ctx.state.transform;
+  ctx.path.cubic_to(
+    map_point(&transform, cp1x as f32, cp1y as f32),
+    map_point(&transform, cp2x as f32, cp2y as f32),
+    map_point(&transform, x as f32, y as f32),
+  );
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_arc_to(
+  #[cppgc] ctx: &Canvas2d,
+  x1: f64,
+  y1: f64,
+  x2: f64,
+  y2: f64,
+  radius: f64,
+) {
+  ctx.0.borrow_mut().arc_to(
+    x1 as f32,
+    y1 as f32,
+    x2 as f32,
+    y2 as f32,
+    radius as f32,
+  );
+}
+
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_ellipse(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  radius_x: f64,
+  radius_y: f64,
+  rotation: f64,
+  start_angle: f64,
+  end_angle: f64,
+  counterclockwise: bool,
+) {
+  ctx.0.borrow_mut().ellipse(
+    x as f32,
+    y as f32,
+    radius_x as f32,
+    radius_y as f32,
+    rotation as f32,
+    start_angle as f32,
+    end_angle as f32,
+    counterclockwise,
+  );
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_rect(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  w: f64,
+  h: f64,
+) {
+  ctx
+    .0
+    .borrow_mut()
+    .rect(x as f32, y as f32, w as f32, h as f32);
+}
+
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_round_rect(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  w: f64,
+  h: f64,
+  top_left: f64,
+  top_right: f64,
+  bottom_right: f64,
+  bottom_left: f64,
+) {
+  ctx.0.borrow_mut().round_rect(
+    x as f32,
+    y as f32,
+    w as f32,
+    h as f32,
+    [
+      top_left as f32,
+      top_right as f32,
+      bottom_right as f32,
+      bottom_left as f32,
+    ],
+  );
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_fill(#[cppgc] ctx: &Canvas2d, evenodd: bool) {
+  let mut ctx = ctx.0.borrow_mut();
+  let Some(path) = ctx.path.to_path() else {
+    return;
+  };
+  // The path is already in device space, so the gradient has to be mapped
+  // from user space explicitly.
+  let transform = ctx.state.transform;
+  ctx.fill_path(&path, fill_rule(evenodd), Transform::identity(), transform);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_stroke(#[cppgc] ctx: &Canvas2d) {
+  let mut ctx = ctx.0.borrow_mut();
+  let transform = ctx.state.transform;
+  // Line widths and dashes are in user space, so stroke the path in user
+  // space and let the current transform map it back.
+  let Some(path) = ctx
+    .path
+    .to_path()
+    .zip(transform.invert())
+    .and_then(|(path, inverse)| path.transform(inverse))
+  else {
+    return;
+  };
+  ctx.stroke_path(&path, transform);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_clip(#[cppgc] ctx: &Canvas2d, evenodd: bool) {
+  ctx.0.borrow_mut().clip(evenodd);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_fill_rect(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  w: f64,
+  h: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let Some(path) = rect_path(x as f32, y as f32, w as f32, h as f32) else {
+    return;
+  };
+  let transform = ctx.state.transform;
+  ctx.fill_path(&path, FillRule::Winding, transform, Transform::identity());
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_stroke_rect(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  w: f64,
+  h: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let (x, y, w, h) = (x as f32, y as f32, w as f32, h as f32);
+  let mut builder = PathBuilder::new();
+  builder.move_to(x, y);
+  builder.line_to(x + w, y);
+  builder.line_to(x + w, y + h);
+  builder.line_to(x, y + h);
+  builder.close();
+  let Some(path) = builder.finish() else {
+    return;
+  };
+  let transform = ctx.state.transform;
+  ctx.stroke_path(&path, transform);
+}
+
+#[op2(fast)]
+pub fn op_canvas_2d_clear_rect(
+  #[cppgc] ctx: &Canvas2d,
+  x: f64,
+  y: f64,
+  w: f64,
+  h: f64,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let Some(path) = rect_path(x as f32, y as f32, w as f32, h as f32) else {
+    return;
+  };
+  let transform = ctx.state.transform;
+  ctx.clear_path(&path, transform);
+}
+
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_fill_text(
+  state: &OpState,
+  #[cppgc] ctx: &Canvas2d,
+  #[string] text: &str,
+  x: f64,
+  y: f64,
+  max_width: f64,
+  stroke: bool,
+) {
+  let fonts = state.borrow::<FontRegistry>();
+  ctx.0.borrow_mut().draw_text(
+    fonts,
+    text,
+    x as f32,
+    y as f32,
+    max_width as f32,
+    stroke,
+  );
+}
+
+/// Returns the `TextMetrics` of `text` as `[width, actualBoundingBoxLeft,
+/// actualBoundingBoxRight, fontBoundingBoxAscent, fontBoundingBoxDescent,
+/// actualBoundingBoxAscent, actualBoundingBoxDescent]`.
+#[op2]
+#[serde]
+pub fn op_canvas_2d_measure_text(
+  state: &OpState,
+  #[cppgc] ctx: &Canvas2d,
+  #[string] text: &str,
+) -> Vec<f32> {
+  let fonts = state.borrow::<FontRegistry>();
+  let ctx = ctx.0.borrow();
+  let Some(font) = fonts.select(
+    &ctx.state.font_families,
+    ctx.state.font_weight,
+    ctx.state.font_italic,
+  ) else {
+    return vec![0.0; 7];
+  };
+  let run = shape_text(&font, ctx.state.font_size, text);
+  let (dx, dy) = ctx.text_offset(run.width, run.ascent, run.descent);
+  let (left, top, right, bottom) = run.bounds;
+  vec![
+    run.width,
+    -(left + dx),
+    right + dx,
+    run.ascent + dy,
+    run.descent - dy,
+    -(top + dy),
+    bottom + dy,
+  ]
+}
+
+/// Draw straight-alpha RGBA8 or RGBA16 pixels, as held by an `ImageBitmap`.
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_draw_image(
+  #[cppgc] ctx: &Canvas2d,
+  #[buffer] data: &[u8],
+  width: u32,
+  height: u32,
+  sx: f64,
+  sy: f64,
+  sw: f64,
+  sh: f64,
+  dx: f64,
+  dy: f64,
+  dw: f64,
+  dh: f64,
+) -> Result<(), CanvasError> {
+  let pixel_count = width as usize * height as usize;
+  let rgba16;
+  let data = if pixel_count > 0 && data.len() == pixel_count * 8 {
+    // 16-bit bitmaps are stored as native endian RGBA16.
+    rgba16 = data
+      .chunks_exact(2)
+      .map(|c| (u16::from_ne_bytes([c[0], c[1]]) >> 8) as u8)
+      .collect::<Vec<_>>();
+    &rgba16
+  } else {
+    data
+  };
+  let Some(image) = pixmap_from_rgba(data, width, height)? else {
+    return Ok(());
+  };
+  ctx.0.borrow_mut().draw_image(
+    &image,
+    (sx as f32, sy as f32, sw as f32, sh as f32),
+    (dx as f32, dy as f32, dw as f32, dh as f32),
+  );
+  Ok(())
+}
+
+/// Draw the bitmap of another (or the same) canvas.
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_draw_canvas(
+  #[cppgc] ctx: &Canvas2d,
+  #[cppgc] source: &Canvas2d,
+  sx: f64,
+  sy: f64,
+  sw: f64,
+  sh: f64,
+  dx: f64,
+  dy: f64,
+  dw: f64,
+  dh: f64,
+) {
+  let Some(image) = source.0.borrow().pixmap.clone() else {
+    return;
+  };
+  ctx.0.borrow_mut().draw_image(
+    &image,
+    (sx as f32, sy as f32, sw as f32, sh as f32),
+    (dx as f32, dy as f32, dw as f32, dh as f32),
+  );
+}
+
+#[op2]
+#[serde]
+pub fn op_canvas_2d_get_image_data(
+  #[cppgc] ctx: &Canvas2d,
+  x: i32,
+  y: i32,
+  width: u32,
+  height: u32,
+) -> ToJsBuffer {
+  ctx.0.borrow().read_pixels(x, y, width, height).into()
+}
+
+/// Write straight-alpha RGBA pixels without compositing. Only the dirty
+/// rectangle, in image data coordinates, is written.
+#[op2(fast)]
+#[allow(clippy::too_many_arguments)]
+pub fn op_canvas_2d_put_image_data(
+  #[cppgc] ctx: &Canvas2d,
+  #[buffer] data: &[u8],
+  width: u32,
+  dx: i32,
+  dy: i32,
+  dirty_x: i32,
+  dirty_y: i32,
+  dirty_width: i32,
+  dirty_height: i32,
+) {
+  let mut ctx = ctx.0.borrow_mut();
+  let (canvas_width, canvas_height) = (ctx.width as i64, ctx.height as i64);
+  let Some(pixmap) = ctx.pixmap.as_mut() else {
+    return;
+  };
+  let image_height = match width {
+    0 => 0,
+    width => (data.len() / 4 / width as usize) as i64,
+  };
+  // Clamp the dirty rectangle to the image data and to the part of it that
+  // lands on the canvas. `i64` can't overflow with `i32` and `u32` inputs.
+  let (dx, dy) = (dx as i64, dy as i64);
+  let (dirty_x, dirty_y) = (dirty_x as i64, dirty_y as i64);
+  let x_start = dirty_x.max(0).max(-dx);
+  let x_end = (dirty_x + dirty_width as i64)
+    .min(width as i64)
+    .min(canvas_width - dx);
+  let y_start = dirty_y.max(0).max(-dy);
+  let y_end = (dirty_y + dirty_height as i64)
+    .min(image_height)
+    .min(canvas_height - dy);
+  let pixels = pixmap.pixels_mut();
+  for row in y_start..y_end {
+    for col in x_start..x_end {
+      let offset = ((row * width as i64 + col) * 4) as usize;
+      let rgba = &data[offset..offset + 4];
+      pixels[((dy + row) * canvas_width + dx + col) as usize] =
+        tiny_skia::ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+          .premultiply();
+    }
+  }
+}
+
+/// Encode the bitmap. `format` is 0 for PNG, 1 for JPEG and 2 for lossless
+/// WebP; `quality` only applies to JPEG.
+#[op2]
+#[serde]
+pub fn op_canvas_2d_encode(
+  #[cppgc] ctx: &Canvas2d,
+  format: u32,
+  quality: f64,
+) -> Result<ToJsBuffer, CanvasError> {
+  let ctx = ctx.0.borrow();
+  let (width, height) = (ctx.width, ctx.height);
+  let mut out = Vec::new();
+  match format {
+    1 => {
+      // JPEG has no alpha channel; premultiplied color is the bitmap
+      // composited onto opaque black.
+      let rgb = ctx
+        .pixmap
+        .as_ref()
+        .map(|pixmap| {
+          pixmap
+            .data()
+            .chunks_exact(4)
+            .flat_map(|p| [p[0], p[1], p[2]])
+            .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+      let quality = (quality * 100.0).round().clamp(1.0, 100.0) as u8;
+      JpegEncoder::new_with_quality(&mut out, quality).write_image(
+        &rgb,
+        width,
+        height,
+        ExtendedColorType::Rgb8,
+      )?;
+    }
+    2 => {
+      let rgba = ctx.read_pixels(0, 0, width, height);
+      WebPEncoder::new_lossless(&mut out).write_image(
+        &rgba,
+        width,
+        height,
+        ExtendedColorType::Rgba8,
+      )?;
+    }
+    _ => {
+      let rgba = ctx.read_pixels(0, 0, width, height);
+      PngEncoder::new(&mut out).write_image(
+        &rgba,
+        width,
+        height,
+        ExtendedColorType::Rgba8,
+      )?;
+    }
+  }
+  Ok(out.into())
+}
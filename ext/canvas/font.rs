@@ -0,0 +1,179 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::rc::Rc;
+
+use ab_glyph::Font;
+use ab_glyph::FontVec;
+use ab_glyph::OutlineCurve;
+use deno_core::OpState;
+use deno_core::op2;
+use tiny_skia::Path;
+use tiny_skia::PathBuilder;
+
+use crate::CanvasError;
+
+/// Font faces that were added to the `FontFaceSet` of the current global.
+///
+/// There is no system font lookup; text is only rendered with fonts that
+/// were explicitly loaded with `FontFace` and added to `fonts`.
+#[derive(Default)]
+pub struct FontRegistry {
+  next_id: u32,
+  faces: Vec<RegisteredFontFace>,
+}
+
+struct RegisteredFontFace {
+  id: u32,
+  family: String,
+  weight: u16,
+  italic: bool,
+  font: Rc<FontVec>,
+}
+
+impl FontRegistry {
+  /// Pick the face that best matches the given font family list, weight and
+  /// style. Generic and unknown families fall back to the first registered
+  /// face.
+  pub(crate) fn select(
+    &self,
+    families: &str,
+    weight: u16,
+    italic: bool,
+  ) -> Option<Rc<FontVec>> {
+    for family in families.split(',') {
+      let family = family.trim().to_lowercase();
+      let best = self
+        .faces
+        .iter()
+        .filter(|face| face.family == family)
+        .min_by_key(|face| {
+          let style_penalty = if face.italic == italic { 0 } else { 1000 };
+          style_penalty + face.weight.abs_diff(weight) as u32
+        });
+      if let Some(face) = best {
+        return Some(face.font.clone());
+      }
+    }
+    self.faces.first().map(|face| face.font.clone())
+  }
+}
+
+#[op2(fast)]
+pub fn op_canvas_register_font(
+  state: &mut OpState,
+  #[string] family: &str,
+  #[buffer] data: &[u8],
+  weight: u32,
+  italic: bool,
+) -> Result<u32, CanvasError> {
+  let font = FontVec::try_from_vec(data.to_vec())
+    .map_err(|_| CanvasError::InvalidFont)?;
+  let registry = state.borrow_mut::<FontRegistry>();
+  let id = registry.next_id;
+  registry.next_id += 1;
+  registry.faces.push(RegisteredFontFace {
+    id,
+    family: family.trim().to_lowercase(),
+    weight: weight.clamp(1, 1000) as u16,
+    italic,
+    font: Rc::new(font),
+  });
+  Ok(id)
+}
+
+#[op2(fast)]
+pub fn op_canvas_unregister_font(state: &mut OpState, id: u32) {
+  let registry = state.borrow_mut::<FontRegistry>();
+  registry.faces.retain(|face| face.id != id);
+}
+
+/// Glyph outlines of a run of text, positioned relative to the start of the
+/// alphabetic baseline with the y-axis pointing down.
+pub(crate) struct TextRun {
+  pub path: Option<Path>,
+  pub width: f32,
+  pub ascent: f32,
+  pub descent: f32,
+  /// `(left, top, right, bottom)` of the glyph outlines.
+  pub bounds: (f32, f32, f32, f32),
+}
+
+pub(crate) fn shape_text(font: &FontVec, size: f32, text: &str) -> TextRun {
+  let scale = size / font.units_per_em().unwrap_or(1000.0);
+  let mut builder = PathBuilder::new();
+  let mut bounds = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+  let mut x = 0.0;
+  let mut previous = None;
+
+  for c in text.chars() {
+    // Canvas text is a single line; every kind of white space collapses to
+    // a regular space.
+    let c = if c.is_whitespace() { ' ' } else { c };
+    let glyph = font.glyph_id(c);
+    if let Some(previous) = previous {
+      x += font.kern_unscaled(previous, glyph) * scale;
+    }
+    if let Some(outline) = font.outline(glyph) {
+      let map = |p: ab_glyph::Point| (x + p.x * scale, -p.y * scale);
+      let mut last = None;
+      for curve in &outline.curves {
+        let (start, end) = match curve {
+          OutlineCurve::Line(p0, p1) => (*p0, *p1),
+          OutlineCurve::Quad(p0, _, p2) => (*p0, *p2),
+          OutlineCurve::Cubic(p0, _, _, p3) => (*p0, *p3),
+        };
+        if last != Some(start) {
+          if last.is_some() {
+            builder.close();
+          }
+          let (sx, sy) = map(start);
+          builder.move_to(sx, sy);
+        }
+        match curve {
+          OutlineCurve::Line(_, p1) => {
+            let (x1, y1) = map(*p1);
+            builder.line_to(x1, y1);
+          }
+          OutlineCurve::Quad(_, p1, p2) => {
+            let (x1, y1) = map(*p1);
+            let (x2, y2) = map(*p2);
+            builder.quad_to(x1, y1, x2, y2);
+          }
+          OutlineCurve::Cubic(_, p1, p2, p3) => {
+            let (x1, y1) = map(*p1);
+            let (x2, y2) = map(*p2);
+            let (x3, y3) = map(*p3);
+            builder.cubic_to(x1, y1, x2, y2, x3, y3);
+          }
+        }
+        last = Some(end);
+      }
+      if last.is_some() {
+        builder.close();
+      }
+
+      // The corners of `Outline::bounds` are not ordered along the y-axis,
+      // so take the extremes of both.
+      let (x0, y0) = map(outline.bounds.min);
+      let (x1, y1) = map(outline.bounds.max);
+      bounds.0 = bounds.0.min(x0.min(x1));
+      bounds.1 = bounds.1.min(y0.min(y1));
+      bounds.2 = bounds.2.max(x0.max(x1));
+      bounds.3 = bounds.3.max(y0.max(y1));
+    }
+    x += font.h_advance_unscaled(glyph) * scale;
+    previous = Some(glyph);
+  }
+
+  if bounds.0 > bounds.2 {
+    bounds = (0.0, 0.0, 0.0, 0.0);
+  }
+
+  TextRun {
+    path: builder.finish(),
+    width: x,
+    ascent: font.ascent_unscaled() * scale,
+    descent: -font.descent_unscaled() * scale,
+    bounds,
+  }
+}
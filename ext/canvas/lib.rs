@@ -1,7 +1,13 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+mod canvas_2d;
+mod font;
 mod image_ops;
 mod op_create_image_bitmap;
+use canvas_2d::*;
+use font::FontRegistry;
+use font::op_canvas_register_font;
+use font::op_canvas_unregister_font;
 pub use image;
 use image::ColorType;
 use op_create_image_bitmap::op_create_image_bitmap;
@@ -27,6 +33,9 @@ pub enum CanvasError {
   #[class("DOMExceptionInvalidStateError")]
   #[error("The width: {0} or height: {1} could not be zero")]
   InvalidSizeZero(u32, u32),
+  #[class("DOMExceptionSyntaxError")]
+  #[error("Could not parse font data")]
+  InvalidFont,
   #[class(generic)]
   #[error(transparent)]
   Lcms(#[from] lcms2::Error),
@@ -45,6 +54,54 @@ impl CanvasError {
 deno_core::extension!(
   deno_canvas,
   deps = [deno_webidl, deno_web, deno_webgpu],
-  ops = [op_create_image_bitmap],
-  lazy_loaded_esm = ["01_image.js"],
+  ops = [
+    op_create_image_bitmap,
+    op_canvas_register_font,
+    op_canvas_unregister_font,
+    op_canvas_parse_color,
+    op_canvas_2d_create,
+    op_canvas_2d_resize,
+    op_canvas_2d_reset,
+    op_canvas_2d_clear,
+    op_canvas_2d_save,
+    op_canvas_2d_restore,
+    op_canvas_2d_set_transform,
+    op_canvas_2d_transform,
+    op_canvas_2d_set_color,
+    op_canvas_2d_set_gradient,
+    op_canvas_2d_set_line_style,
+    op_canvas_2d_set_line_dash,
+    op_canvas_2d_set_global_alpha,
+    op_canvas_2d_set_composite_operation,
+    op_canvas_2d_set_image_smoothing,
+    op_canvas_2d_set_font,
+    op_canvas_2d_set_text_style,
+    op_canvas_2d_begin_path,
+    op_canvas_2d_close_path,
+    op_canvas_2d_move_to,
+    op_canvas_2d_line_to,
+    op_canvas_2d_quadratic_curve_to,
+    op_canvas_2d_bezier_curve_to,
+    op_canvas_2d_arc_to,
+    op_canvas_2d_ellipse,
+    op_canvas_2d_rect,
+    op_canvas_2d_round_rect,
+    op_canvas_2d_fill,
+    op_canvas_2d_stroke,
+    op_canvas_2d_clip,
+    op_canvas_2d_fill_rect,
+    op_canvas_2d_stroke_rect,
+    op_canvas_2d_clear_rect,
+    op_canvas_2d_fill_text,
+    op_canvas_2d_measure_text,
+    op_canvas_2d_draw_image,
+    op_canvas_2d_draw_canvas,
+    op_canvas_2d_get_image_data,
+    op_canvas_2d_put_image_data,
+    op_canvas_2d_encode,
+  ],
+  lazy_loaded_esm = ["01_image.js", "02_canvas.js"],
+  state = |state| {
+    state.put(FontRegistry::default());
+  },
 );
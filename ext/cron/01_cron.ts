@@ -96,12 +96,19 @@ export function parseScheduleToString(
   }
 }
 
+interface CronOptions {
+  backoffSchedule?: number[];
+  signal?: AbortSignal;
+  timezone?: string;
+  catchUp?: "skip" | "once" | "all";
+}
+
 function cron(
   name: string,
   schedule: string | Deno.CronSchedule,
   handlerOrOptions1:
     | (() => Promise<void> | void)
-    | ({ backoffSchedule?: number[]; signal?: AbortSignal }),
+    | CronOptions,
   handler2?: () => Promise<void> | void,
 ) {
   if (name === undefined) {
@@ -118,9 +125,7 @@ function cron(
   schedule = parseScheduleToString(schedule);
 
   let handler: () => Promise<void> | void;
-  let options:
-    | { backoffSchedule?: number[]; signal?: AbortSignal }
-    | undefined = undefined;
+  let options: CronOptions | undefined = undefined;
 
   if (typeof handlerOrOptions1 === "function") {
     handler = handlerOrOptions1;
@@ -140,6 +145,8 @@ function cron(
     name,
     schedule,
     options?.backoffSchedule,
+    options?.timezone,
+    options?.catchUp,
   );
 
   if (options?.signal) {
@@ -0,0 +1,167 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use async_trait::async_trait;
+
+use crate::CronError;
+use crate::CronHandle;
+use crate::CronHandler;
+use crate::CronSpec;
+use crate::lease::CronLeaseStore;
+use crate::local::CronExecutionHandle;
+use crate::local::LocalCronHandler;
+
+/// How long the lease for a single execution is kept. It only has to
+/// outlive the clock skew between the processes sharing the lease store,
+/// and is kept above the longest backoff so retries stay with the process
+/// that owns the execution.
+const LEASE_TTL_MS: u64 = 2 * 60 * 60 * 1_000; // 2 hours
+
+/// A `CronHandler` for crons that are registered by several processes at
+/// once, such as the workers of `deno serve --parallel` or replicas of the
+/// same program.
+///
+/// Every process schedules the crons locally, but before running an
+/// execution it takes a lease for it from the shared `CronLeaseStore`. Only
+/// the process that gets the lease runs the execution and its retries; the
+/// others skip it and wait for the next one.
+pub struct CoordinatedCronHandler<L: CronLeaseStore> {
+  local: LocalCronHandler,
+  lease_store: Rc<L>,
+  holder_id: String,
+}
+
+impl<L: CronLeaseStore> CoordinatedCronHandler<L> {
+  pub fn new(local: LocalCronHandler, lease_store: L) -> Self {
+    Self {
+      local,
+      lease_store: Rc::new(lease_store),
+      holder_id: uuid::Uuid::new_v4().to_string(),
+    }
+  }
+}
+
+impl<L: CronLeaseStore + 'static> CronHandler for CoordinatedCronHandler<L> {
+  type EH = CoordinatedCronHandle<L>;
+
+  fn create(&self, spec: CronSpec) -> Result<Self::EH, CronError> {
+    let name = spec.name.clone();
+    let inner = self.local.create(spec)?;
+    Ok(CoordinatedCronHandle {
+      name,
+      inner,
+      lease_store: self.lease_store.clone(),
+      holder_id: self.holder_id.clone(),
+      owned_tick: Cell::new(None),
+    })
+  }
+}
+
+pub struct CoordinatedCronHandle<L: CronLeaseStore> {
+  name: String,
+  inner: CronExecutionHandle,
+  lease_store: Rc<L>,
+  holder_id: String,
+  owned_tick: Cell<Option<u64>>,
+}
+
+#[async_trait(?Send)]
+impl<L: CronLeaseStore> CronHandle for CoordinatedCronHandle<L> {
+  async fn next(&self, prev_success: bool) -> Result<bool, CronError> {
+    let mut prev_success = prev_success;
+    loop {
+      if !self.inner.next(prev_success).await? {
+        return Ok(false);
+      }
+      let Some(tick) = self.inner.current_tick() else {
+        return Ok(true);
+      };
+      // Retries belong to the process that ran the failed execution.
+      if self.owned_tick.get() == Some(tick) {
+        return Ok(true);
+      }
+
+      let key = format!("{}/{}", self.name, tick);
+      if self
+        .lease_store
+        .try_acquire(&key, &self.holder_id, LEASE_TTL_MS)
+        .await?
+      {
+        self.owned_tick.set(Some(tick));
+        return Ok(true);
+      }
+
+      // Another process runs this execution; it is not a failure here, so
+      // don't schedule a retry.
+      prev_success = true;
+    }
+  }
+
+  fn close(&self) {
+    self.inner.close();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::CronCatchUp;
+  use crate::lease::SqliteCronLeaseStore;
+
+  #[tokio::test]
+  async fn test_coordinated_cron_handle_next() {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    let dir = tempfile::tempdir().unwrap();
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let midnight = now - now % DAY_MS;
+    // Both processes see that the executions of the last three midnights
+    // were missed, and catch them up.
+    let last_run = midnight - 2 * DAY_MS - DAY_MS / 2;
+    std::fs::write(
+      dir.path().join("coordinated.last_run"),
+      last_run.to_string(),
+    )
+    .unwrap();
+
+    // Stores without a path share one database in the process.
+    let handler = || {
+      CoordinatedCronHandler::new(
+        LocalCronHandler::with_state_dir(dir.path().to_path_buf()),
+        SqliteCronLeaseStore::new(None),
+      )
+    };
+    let spec = || CronSpec {
+      name: "coordinated".to_string(),
+      cron_schedule: "0 0 * * *".to_string(),
+      backoff_schedule: Some(vec![1]),
+      timezone: None,
+      catch_up: Some(CronCatchUp::All),
+    };
+    let (handler_a, handler_b) = (handler(), handler());
+    let a = handler_a.create(spec()).unwrap();
+    let b = handler_b.create(spec()).unwrap();
+    let tick = |handle: &CoordinatedCronHandle<SqliteCronLeaseStore>| {
+      handle.inner.current_tick().unwrap()
+    };
+
+    // Every execution runs in only one of the processes.
+    assert!(a.next(true).await.unwrap());
+    assert_eq!(tick(&a), midnight - 2 * DAY_MS);
+    assert!(b.next(true).await.unwrap());
+    assert_eq!(tick(&b), midnight - DAY_MS);
+    assert!(a.next(true).await.unwrap());
+    assert_eq!(tick(&a), midnight);
+
+    // A failed execution is retried by the process that owns it, even
+    // though the lease is already taken.
+    assert!(b.next(false).await.unwrap());
+    assert_eq!(tick(&b), midnight - DAY_MS);
+    assert!(a.next(false).await.unwrap());
+    assert_eq!(tick(&a), midnight);
+
+    a.close();
+    b.close();
+  }
+}
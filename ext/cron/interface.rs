@@ -21,4 +21,20 @@ pub struct CronSpec {
   pub name: String,
   pub cron_schedule: String,
   pub backoff_schedule: Option<Vec<u32>>,
+  /// IANA time zone the schedule is evaluated in. Defaults to UTC.
+  pub timezone: Option<String>,
+  /// What to do with executions that were missed while no process was
+  /// running the cron. When `None`, the last run is not persisted.
+  pub catch_up: Option<CronCatchUp>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CronCatchUp {
+  /// Drop missed executions and wait for the next scheduled one.
+  Skip,
+  /// Run a single execution if at least one was missed.
+  Once,
+  /// Run one execution for every missed one.
+  All,
 }
@@ -0,0 +1,151 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::cell::OnceCell;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+use deno_core::parking_lot::Mutex;
+use deno_core::unsync::spawn_blocking;
+use rusqlite::Connection;
+use rusqlite::params;
+
+use crate::CronError;
+
+/// Storage for the leases that decide which process runs a cron execution.
+///
+/// A lease is identified by a key and held by a single holder until it
+/// expires. Implementations must make `try_acquire` atomic across all the
+/// processes that share the store.
+#[async_trait(?Send)]
+pub trait CronLeaseStore {
+  /// Takes the lease `key` for `holder` for `ttl_ms` milliseconds. Returns
+  /// `false` if the lease is currently held by another holder.
+  async fn try_acquire(
+    &self,
+    key: &str,
+    holder: &str,
+    ttl_ms: u64,
+  ) -> Result<bool, CronError>;
+}
+
+/// A `CronLeaseStore` backed by a SQLite database, usable by all processes
+/// on the same host. Without a path, the database is kept in memory and
+/// shared by every store of the current process, so only the workers of
+/// this process are coordinated.
+pub struct SqliteCronLeaseStore {
+  path: Option<PathBuf>,
+  connection: OnceCell<Arc<Mutex<Connection>>>,
+}
+
+impl SqliteCronLeaseStore {
+  /// The database is only opened when the first lease is requested.
+  pub fn new(path: Option<PathBuf>) -> Self {
+    Self {
+      path,
+      connection: OnceCell::new(),
+    }
+  }
+
+  fn connection(&self) -> Result<Arc<Mutex<Connection>>, CronError> {
+    if let Some(connection) = self.connection.get() {
+      return Ok(connection.clone());
+    }
+
+    let connection = match &self.path {
+      Some(path) => {
+        if let Some(dir) = path.parent() {
+          std::fs::create_dir_all(dir).map_err(CronError::Io)?;
+        }
+        let connection = Connection::open(path)?;
+        let initial_pragmas = "
+          PRAGMA journal_mode=WAL;
+          PRAGMA synchronous=NORMAL;
+          PRAGMA busy_timeout=5000;
+        ";
+        connection.execute_batch(initial_pragmas)?;
+        create_table(&connection)?;
+        Arc::new(Mutex::new(connection))
+      }
+      None => in_memory_connection()?,
+    };
+    Ok(self.connection.get_or_init(|| connection).clone())
+  }
+}
+
+/// The in-memory database that is shared by every store without a path.
+fn in_memory_connection() -> Result<Arc<Mutex<Connection>>, CronError> {
+  static CONNECTION: OnceLock<Arc<Mutex<Connection>>> = OnceLock::new();
+  if let Some(connection) = CONNECTION.get() {
+    return Ok(connection.clone());
+  }
+  let connection = Connection::open_in_memory()?;
+  create_table(&connection)?;
+  Ok(
+    CONNECTION
+      .get_or_init(|| Arc::new(Mutex::new(connection)))
+      .clone(),
+  )
+}
+
+fn create_table(connection: &Connection) -> Result<(), CronError> {
+  connection.execute(
+    "CREATE TABLE IF NOT EXISTS cron_leases (
+      key TEXT PRIMARY KEY,
+      holder TEXT NOT NULL,
+      expires_at INTEGER NOT NULL
+    )",
+    [],
+  )?;
+  Ok(())
+}
+
+#[async_trait(?Send)]
+impl CronLeaseStore for SqliteCronLeaseStore {
+  async fn try_acquire(
+    &self,
+    key: &str,
+    holder: &str,
+    ttl_ms: u64,
+  ) -> Result<bool, CronError> {
+    let connection = self.connection()?;
+    let key = key.to_string();
+    let holder = holder.to_string();
+    spawn_blocking(move || {
+      let connection = connection.lock();
+      let now = chrono::Utc::now().timestamp_millis();
+      connection.execute(
+        "DELETE FROM cron_leases WHERE expires_at <= ?1",
+        params![now],
+      )?;
+      let changed = connection.execute(
+        "INSERT INTO cron_leases (key, holder, expires_at) VALUES (?1, ?2, ?3)
+          ON CONFLICT (key) DO UPDATE
+            SET holder = excluded.holder, expires_at = excluded.expires_at
+            WHERE cron_leases.holder = excluded.holder",
+        params![key, holder, now + ttl_ms as i64],
+      )?;
+      Ok(changed == 1)
+    })
+    .await?
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_sqlite_lease_store() {
+    let store = SqliteCronLeaseStore::new(None);
+    assert!(store.try_acquire("a/1", "x", 60_000).await.unwrap());
+    assert!(store.try_acquire("a/1", "x", 60_000).await.unwrap());
+    assert!(!store.try_acquire("a/1", "y", 60_000).await.unwrap());
+    assert!(store.try_acquire("a/2", "y", 60_000).await.unwrap());
+
+    // Expired leases can be taken by another holder.
+    assert!(store.try_acquire("b/1", "x", 0).await.unwrap());
+    assert!(store.try_acquire("b/1", "y", 60_000).await.unwrap());
+  }
+}
@@ -1,6 +1,8 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+pub mod coordinated;
 mod interface;
+pub mod lease;
 pub mod local;
 
 use std::borrow::Cow;
@@ -75,9 +77,21 @@ pub enum CronError {
   #[class(type)]
   #[error("Invalid backoff schedule")]
   InvalidBackoff,
+  #[class(type)]
+  #[error("Invalid time zone: {0}")]
+  InvalidTimezone(String),
   #[class(generic)]
   #[error(transparent)]
   AcquireError(#[from] tokio::sync::AcquireError),
+  #[class(generic)]
+  #[error(transparent)]
+  Sqlite(#[from] rusqlite::Error),
+  #[class(generic)]
+  #[error(transparent)]
+  JoinError(#[from] tokio::task::JoinError),
+  #[class(inherit)]
+  #[error(transparent)]
+  Io(std::io::Error),
   #[class(inherit)]
   #[error(transparent)]
   Other(JsErrorBox),
@@ -90,6 +104,8 @@ fn op_cron_create<C>(
   #[string] name: String,
   #[string] cron_schedule: String,
   #[serde] backoff_schedule: Option<Vec<u32>>,
+  #[serde] timezone: Option<String>,
+  #[serde] catch_up: Option<CronCatchUp>,
 ) -> Result<ResourceId, CronError>
 where
   C: CronHandler + 'static,
@@ -108,6 +124,8 @@ where
     name,
     cron_schedule,
     backoff_schedule,
+    timezone,
+    catch_up,
   })?;
 
   let handle_rid = {
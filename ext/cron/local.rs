@@ -1,15 +1,23 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::cell::Cell;
 use std::cell::OnceCell;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::env;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::rc::Weak;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::LocalResult;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono_tz::Tz;
 use deno_core::futures;
 use deno_core::futures::FutureExt;
 use deno_core::unsync::JoinHandle;
@@ -19,6 +27,7 @@ use tokio::sync::Semaphore;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::WeakSender;
 
+use crate::CronCatchUp;
 use crate::CronError;
 use crate::CronHandle;
 use crate::CronHandler;
@@ -29,6 +38,10 @@ const DISPATCH_CONCURRENCY_LIMIT: usize = 50;
 const MAX_BACKOFF_MS: u32 = 60 * 60 * 1_000; // 1 hour
 const MAX_BACKOFF_COUNT: usize = 5;
 const DEFAULT_BACKOFF_SCHEDULE: [u32; 5] = [100, 1_000, 5_000, 30_000, 60_000];
+const MAX_CATCH_UP_EXECUTIONS: usize = 100;
+/// How far back missed executions are looked for, which bounds the number
+/// of scheduled times that are walked when a cron is created.
+const CATCH_UP_LOOKBACK_MS: u64 = 7 * 24 * 60 * 60 * 1_000; // 1 week
 
 pub struct LocalCronHandler {
   cron_schedule_tx: OnceCell<mpsc::Sender<(String, bool)>>,
@@ -40,12 +53,19 @@ pub struct LocalCronHandler {
 struct RuntimeState {
   crons: HashMap<String, Cron>,
   scheduled_deadlines: BTreeMap<u64, Vec<String>>,
+  last_run_store: Option<LastRunStore>,
 }
 
 struct Cron {
   spec: CronSpec,
-  next_tx: mpsc::WeakSender<()>,
+  timezone: Option<Tz>,
+  next_tx: mpsc::WeakSender<u64>,
   current_execution_retries: u32,
+  /// Scheduled time of the execution that is currently running or retried.
+  current_tick: u64,
+  /// Scheduled times of missed executions that still have to run before the
+  /// regular schedule resumes.
+  pending_catch_up: VecDeque<u64>,
 }
 
 impl Cron {
@@ -66,6 +86,17 @@ impl Default for LocalCronHandler {
 
 impl LocalCronHandler {
   pub fn new() -> Self {
+    Self::new_inner(None)
+  }
+
+  /// Creates a handler that persists the last run of crons with a catch-up
+  /// policy in `state_dir`, so that executions missed while the process was
+  /// not running can be caught up after a restart.
+  pub fn with_state_dir(state_dir: PathBuf) -> Self {
+    Self::new_inner(Some(LastRunStore { dir: state_dir }))
+  }
+
+  fn new_inner(last_run_store: Option<LastRunStore>) -> Self {
     Self {
       cron_schedule_tx: OnceCell::new(),
       concurrency_limiter: Arc::new(Semaphore::new(DISPATCH_CONCURRENCY_LIMIT)),
@@ -73,6 +104,7 @@ impl LocalCronHandler {
       runtime_state: Rc::new(RefCell::new(RuntimeState {
         crons: HashMap::new(),
         scheduled_deadlines: BTreeMap::new(),
+        last_run_store,
       })),
     }
   }
@@ -123,9 +155,15 @@ impl LocalCronHandler {
             let now = chrono::Utc::now().timestamp_millis() as u64;
             cron.current_execution_retries += 1;
             now + backoff_ms as u64
+          } else if let Some(tick) = cron.pending_catch_up.pop_front() {
+            cron.current_execution_retries = 0;
+            cron.current_tick = tick;
+            chrono::Utc::now().timestamp_millis() as u64
           } else {
-            let next_ts = compute_next_deadline(&cron.spec.cron_schedule)?;
+            let next_ts =
+              compute_next_deadline(&cron.spec.cron_schedule, cron.timezone)?;
             cron.current_execution_retries = 0;
+            cron.current_tick = next_ts;
             next_ts
           };
           runtime_state
@@ -141,9 +179,9 @@ impl LocalCronHandler {
         let mut runtime_state = runtime_state.borrow_mut();
         runtime_state.get_ready_crons()?
       };
-      for (_, tx) in crons_to_execute {
+      for (_, tx, tick) in crons_to_execute {
         if let Some(tx) = tx.upgrade() {
-          let _ = tx.send(()).await;
+          let _ = tx.send(tick).await;
         }
       }
     }
@@ -153,7 +191,7 @@ impl LocalCronHandler {
 impl RuntimeState {
   fn get_ready_crons(
     &mut self,
-  ) -> Result<Vec<(String, WeakSender<()>)>, CronError> {
+  ) -> Result<Vec<(String, WeakSender<u64>, u64)>, CronError> {
     let now = chrono::Utc::now().timestamp_millis() as u64;
 
     let ready = {
@@ -174,10 +212,15 @@ impl RuntimeState {
             .collect::<Vec<_>>()
         })
         .filter_map(|(_, name)| {
-          self
-            .crons
-            .get(&name)
-            .map(|c| (name.clone(), c.next_tx.clone()))
+          let cron = self.crons.get(&name)?;
+          if cron.spec.catch_up.is_some()
+            && let Some(store) = &self.last_run_store
+          {
+            // Persisting the last run is best effort; a failure only means
+            // that missed executions can't be detected after a restart.
+            let _ = store.write(&name, cron.current_tick);
+          }
+          Some((name.clone(), cron.next_tx.clone(), cron.current_tick))
         })
         .collect::<Vec<_>>()
     };
@@ -224,11 +267,48 @@ impl CronHandler for LocalCronHandler {
       validate_backoff_schedule(backoff_schedule)?;
     }
 
-    let (next_tx, next_rx) = mpsc::channel::<()>(1);
+    let timezone = spec
+      .timezone
+      .as_deref()
+      .map(|timezone| {
+        timezone
+          .parse::<Tz>()
+          .map_err(|_| CronError::InvalidTimezone(timezone.to_string()))
+      })
+      .transpose()?;
+
+    let mut pending_catch_up = VecDeque::new();
+    if let Some(catch_up) = spec.catch_up
+      && let Some(store) = &runtime_state.last_run_store
+    {
+      let now = chrono::Utc::now().timestamp_millis() as u64;
+      match store.read(&spec.name) {
+        Some(last_run) => {
+          let missed =
+            || missed_executions(&spec.cron_schedule, timezone, last_run, now);
+          match catch_up {
+            // Missed executions are dropped, no need to look for them.
+            CronCatchUp::Skip => {}
+            CronCatchUp::Once => pending_catch_up.extend(missed()?.pop_back()),
+            CronCatchUp::All => pending_catch_up = missed()?,
+          }
+        }
+        // First time this cron is seen; use the current time as the
+        // baseline so that executions missed from now on are detected.
+        None => {
+          let _ = store.write(&spec.name, now);
+        }
+      }
+    }
+
+    let (next_tx, next_rx) = mpsc::channel::<u64>(1);
     let cron = Cron {
       spec: spec.clone(),
+      timezone,
       next_tx: next_tx.downgrade(),
       current_execution_retries: 0,
+      current_tick: 0,
+      pending_catch_up,
     };
     runtime_state.crons.insert(spec.name.clone(), cron);
 
@@ -237,6 +317,7 @@ impl CronHandler for LocalCronHandler {
       cron_schedule_tx: self.cron_schedule_tx.get().unwrap().clone(),
       concurrency_limiter: self.concurrency_limiter.clone(),
       runtime_state: Rc::downgrade(&self.runtime_state),
+      current_tick: Cell::new(None),
       inner: RefCell::new(Inner {
         next_rx: Some(next_rx),
         shutdown_tx: Some(next_tx),
@@ -251,12 +332,23 @@ pub struct CronExecutionHandle {
   runtime_state: Weak<RefCell<RuntimeState>>,
   cron_schedule_tx: mpsc::Sender<(String, bool)>,
   concurrency_limiter: Arc<Semaphore>,
+  current_tick: Cell<Option<u64>>,
   inner: RefCell<Inner>,
 }
 
+impl CronExecutionHandle {
+  /// Scheduled time, in milliseconds since the epoch, of the execution that
+  /// was last returned by `next`. Retries of a failed execution keep the
+  /// scheduled time of the original execution, and caught up executions use
+  /// the scheduled time of the execution that was missed.
+  pub fn current_tick(&self) -> Option<u64> {
+    self.current_tick.get()
+  }
+}
+
 struct Inner {
-  next_rx: Option<mpsc::Receiver<()>>,
-  shutdown_tx: Option<mpsc::Sender<()>>,
+  next_rx: Option<mpsc::Receiver<u64>>,
+  shutdown_tx: Option<mpsc::Sender<u64>>,
   permit: Option<OwnedSemaphorePermit>,
 }
 
@@ -277,9 +369,10 @@ impl CronHandle for CronExecutionHandle {
     let Some(mut next_rx) = self.inner.borrow_mut().next_rx.take() else {
       return Ok(false);
     };
-    if next_rx.recv().await.is_none() {
+    let Some(tick) = next_rx.recv().await else {
       return Ok(false);
     };
+    self.current_tick.set(Some(tick));
 
     let permit = self.concurrency_limiter.clone().acquire_owned().await?;
     let mut inner = self.inner.borrow_mut();
@@ -299,7 +392,37 @@ impl CronHandle for CronExecutionHandle {
   }
 }
 
-fn compute_next_deadline(cron_expression: &str) -> Result<u64, CronError> {
+/// Persists the time of the last execution of each cron as a file in `dir`.
+struct LastRunStore {
+  dir: PathBuf,
+}
+
+impl LastRunStore {
+  fn path(&self, name: &str) -> PathBuf {
+    self.dir.join(format!("{name}.last_run"))
+  }
+
+  fn read(&self, name: &str) -> Option<u64> {
+    std::fs::read_to_string(self.path(name))
+      .ok()?
+      .trim()
+      .parse()
+      .ok()
+  }
+
+  fn write(&self, name: &str, timestamp_ms: u64) -> std::io::Result<()> {
+    std::fs::create_dir_all(&self.dir)?;
+    let path = self.path(name);
+    let tmp_path = path.with_extension("last_run.tmp");
+    std::fs::write(&tmp_path, timestamp_ms.to_string())?;
+    std::fs::rename(tmp_path, path)
+  }
+}
+
+fn compute_next_deadline(
+  cron_expression: &str,
+  timezone: Option<Tz>,
+) -> Result<u64, CronError> {
   let now = chrono::Utc::now();
 
   if let Ok(test_schedule) = env::var("DENO_CRON_TEST_SCHEDULE_OFFSET")
@@ -311,12 +434,89 @@ fn compute_next_deadline(cron_expression: &str) -> Result<u64, CronError> {
   let cron = cron_expression
     .parse::<saffron::Cron>()
     .map_err(|_| CronError::InvalidCron)?;
-  let Some(next_deadline) = cron.next_after(now) else {
+  let Some(next_deadline) = next_after(&cron, timezone, now) else {
     return Err(CronError::InvalidCron);
   };
   Ok(next_deadline.timestamp_millis() as u64)
 }
 
+/// Returns the scheduled times of the executions after `last_run_ms` and up
+/// to `now_ms`, capped at the most recent `MAX_CATCH_UP_EXECUTIONS`.
+/// Executions older than `CATCH_UP_LOOKBACK_MS` are not returned.
+fn missed_executions(
+  cron_expression: &str,
+  timezone: Option<Tz>,
+  last_run_ms: u64,
+  now_ms: u64,
+) -> Result<VecDeque<u64>, CronError> {
+  let cron = cron_expression
+    .parse::<saffron::Cron>()
+    .map_err(|_| CronError::InvalidCron)?;
+  let mut missed = VecDeque::new();
+  let start_ms = last_run_ms.max(now_ms.saturating_sub(CATCH_UP_LOOKBACK_MS));
+  let Some(mut time) = DateTime::from_timestamp_millis(start_ms as i64) else {
+    return Ok(missed);
+  };
+  while let Some(next) = next_after(&cron, timezone, time) {
+    let next_ms = next.timestamp_millis() as u64;
+    if next_ms > now_ms {
+      break;
+    }
+    if missed.len() == MAX_CATCH_UP_EXECUTIONS {
+      missed.pop_front();
+    }
+    missed.push_back(next_ms);
+    time = next;
+  }
+  Ok(missed)
+}
+
+/// Returns the first execution of `cron` strictly after `after`, evaluating
+/// the schedule in the wall-clock time of `timezone`.
+///
+/// Wall-clock times that are skipped by a daylight saving transition run at
+/// the first instant after the transition, and wall-clock times that occur
+/// twice run only once.
+fn next_after(
+  cron: &saffron::Cron,
+  timezone: Option<Tz>,
+  after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+  let Some(timezone) = timezone else {
+    return cron.next_after(after);
+  };
+
+  // saffron only knows about UTC, so the local wall-clock time is passed to
+  // it as if it was UTC and the result is mapped back afterwards.
+  let mut wall_clock = after.with_timezone(&timezone).naive_local().and_utc();
+  loop {
+    let candidate = cron.next_after(wall_clock)?;
+    let local = candidate.naive_utc();
+    let instant = match timezone.from_local_datetime(&local) {
+      LocalResult::Single(instant) => Some(instant),
+      LocalResult::Ambiguous(earliest, latest) => {
+        if earliest.with_timezone(&Utc) > after {
+          Some(earliest)
+        } else {
+          Some(latest)
+        }
+      }
+      LocalResult::None => (1..=24 * 60).find_map(|minutes| {
+        timezone
+          .from_local_datetime(&(local + chrono::Duration::minutes(minutes)))
+          .earliest()
+      }),
+    };
+    if let Some(instant) = instant {
+      let instant = instant.with_timezone(&Utc);
+      if instant > after {
+        return Some(instant);
+      }
+    }
+    wall_clock = candidate;
+  }
+}
+
 fn validate_backoff_schedule(
   backoff_schedule: &[u32],
 ) -> Result<(), CronError> {
@@ -336,10 +536,143 @@ mod tests {
   #[test]
   fn test_compute_next_deadline() {
     let now = chrono::Utc::now().timestamp_millis() as u64;
-    assert!(compute_next_deadline("*/1 * * * *").unwrap() > now);
-    assert!(compute_next_deadline("* * * * *").unwrap() > now);
-    assert!(compute_next_deadline("bogus").is_err());
-    assert!(compute_next_deadline("* * * * * *").is_err());
-    assert!(compute_next_deadline("* * *").is_err());
+    assert!(compute_next_deadline("*/1 * * * *", None).unwrap() > now);
+    assert!(compute_next_deadline("* * * * *", None).unwrap() > now);
+    let tz = Some(chrono_tz::Asia::Tokyo);
+    assert!(compute_next_deadline("0 9 * * *", tz).unwrap() > now);
+    assert!(compute_next_deadline("bogus", None).is_err());
+    assert!(compute_next_deadline("* * * * * *", None).is_err());
+    assert!(compute_next_deadline("* * *", None).is_err());
+  }
+
+  fn utc(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+  }
+
+  #[test]
+  fn test_next_after_timezone() {
+    let cron = "0 9 * * *".parse::<saffron::Cron>().unwrap();
+    let tz = Some(chrono_tz::America::New_York);
+    assert_eq!(
+      next_after(&cron, tz, utc("2025-01-10T00:00:00Z")),
+      Some(utc("2025-01-10T14:00:00Z"))
+    );
+    assert_eq!(
+      next_after(&cron, tz, utc("2025-07-10T14:00:00Z")),
+      Some(utc("2025-07-11T13:00:00Z"))
+    );
+
+    // 02:30 does not exist on the day clocks go forward.
+    let cron = "30 2 * * *".parse::<saffron::Cron>().unwrap();
+    assert_eq!(
+      next_after(&cron, tz, utc("2025-03-09T00:00:00Z")),
+      Some(utc("2025-03-09T07:00:00Z"))
+    );
+
+    // 01:30 happens twice on the day clocks go back.
+    let cron = "30 1 * * *".parse::<saffron::Cron>().unwrap();
+    let first = next_after(&cron, tz, utc("2025-11-02T00:00:00Z")).unwrap();
+    assert_eq!(first, utc("2025-11-02T05:30:00Z"));
+    assert_eq!(
+      next_after(&cron, tz, first),
+      Some(utc("2025-11-03T06:30:00Z"))
+    );
+  }
+
+  #[test]
+  fn test_missed_executions() {
+    let ms = |s| utc(s).timestamp_millis() as u64;
+    let last_run = ms("2025-01-10T10:00:00Z");
+    let now = ms("2025-01-10T13:30:00Z");
+    assert_eq!(
+      missed_executions("0 * * * *", None, last_run, now).unwrap(),
+      [
+        ms("2025-01-10T11:00:00Z"),
+        ms("2025-01-10T12:00:00Z"),
+        ms("2025-01-10T13:00:00Z"),
+      ]
+    );
+    assert!(
+      missed_executions("0 0 * * *", None, last_run, now)
+        .unwrap()
+        .is_empty()
+    );
+    let missed = missed_executions("* * * * *", None, last_run, now).unwrap();
+    assert_eq!(missed.len(), MAX_CATCH_UP_EXECUTIONS);
+    assert_eq!(missed.back(), Some(&ms("2025-01-10T13:30:00Z")));
+    // Only the executions of the last week are looked for.
+    let last_run = ms("2024-01-01T00:00:00Z");
+    let missed = missed_executions("0 0 * * *", None, last_run, now).unwrap();
+    assert_eq!(missed.len(), 7);
+    assert_eq!(missed.front(), Some(&ms("2025-01-04T00:00:00Z")));
+    assert_eq!(missed.back(), Some(&ms("2025-01-10T00:00:00Z")));
+  }
+
+  #[tokio::test]
+  async fn test_catch_up_from_last_run_store() {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    let dir = tempfile::tempdir().unwrap();
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let midnight = now - now % DAY_MS;
+    // The last run was in the middle of the day three days ago, so the
+    // executions of the last three midnights were missed.
+    let last_run = midnight - 2 * DAY_MS - DAY_MS / 2;
+    let missed = [midnight - 2 * DAY_MS, midnight - DAY_MS, midnight];
+    for name in ["skip", "once", "all"] {
+      std::fs::write(
+        dir.path().join(format!("{name}.last_run")),
+        last_run.to_string(),
+      )
+      .unwrap();
+    }
+
+    let handler = LocalCronHandler::with_state_dir(dir.path().to_path_buf());
+    let create = |name: &str, catch_up| {
+      handler
+        .create(CronSpec {
+          name: name.to_string(),
+          cron_schedule: "0 0 * * *".to_string(),
+          backoff_schedule: None,
+          timezone: None,
+          catch_up: Some(catch_up),
+        })
+        .unwrap()
+    };
+    let skip = create("skip", CronCatchUp::Skip);
+    let once = create("once", CronCatchUp::Once);
+    let all = create("all", CronCatchUp::All);
+    let store = LastRunStore {
+      dir: dir.path().to_path_buf(),
+    };
+
+    // Skipped executions are never run; the cron waits for the next
+    // midnight.
+    assert!(
+      handler.runtime_state.borrow().crons["skip"]
+        .pending_catch_up
+        .is_empty()
+    );
+    assert_eq!(store.read("skip"), Some(last_run));
+
+    // Only the most recent missed execution is run.
+    assert!(once.next(true).await.unwrap());
+    assert_eq!(once.current_tick(), Some(midnight));
+    assert_eq!(store.read("once"), Some(midnight));
+    assert!(
+      handler.runtime_state.borrow().crons["once"]
+        .pending_catch_up
+        .is_empty()
+    );
+
+    // Every missed execution is run, oldest first.
+    for tick in missed {
+      assert!(all.next(true).await.unwrap());
+      assert_eq!(all.current_tick(), Some(tick));
+      assert_eq!(store.read("all"), Some(tick));
+    }
+
+    skip.close();
+    once.close();
+    all.close();
   }
 }
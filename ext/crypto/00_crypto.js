@@ -22,31 +22,55 @@ import {
   op_crypto_encrypt,
   op_crypto_export_key,
   op_crypto_export_pkcs8_ed25519,
+  op_crypto_export_pkcs8_ed448,
+  op_crypto_export_pkcs8_ml_dsa,
+  op_crypto_export_pkcs8_ml_kem,
   op_crypto_export_pkcs8_x25519,
   op_crypto_export_pkcs8_x448,
   op_crypto_export_spki_ed25519,
+  op_crypto_export_spki_ed448,
+  op_crypto_export_spki_ml_dsa,
+  op_crypto_export_spki_ml_kem,
   op_crypto_export_spki_x25519,
   op_crypto_export_spki_x448,
   op_crypto_generate_ed25519_keypair,
+  op_crypto_generate_ed448_keypair,
   op_crypto_generate_key,
+  op_crypto_generate_ml_dsa_keypair,
+  op_crypto_generate_ml_kem_keypair,
   op_crypto_generate_x25519_keypair,
   op_crypto_generate_x448_keypair,
   op_crypto_get_random_values,
   op_crypto_import_key,
   op_crypto_import_pkcs8_ed25519,
+  op_crypto_import_pkcs8_ed448,
+  op_crypto_import_pkcs8_ml_dsa,
+  op_crypto_import_pkcs8_ml_kem,
   op_crypto_import_pkcs8_x25519,
   op_crypto_import_pkcs8_x448,
   op_crypto_import_spki_ed25519,
+  op_crypto_import_spki_ed448,
+  op_crypto_import_spki_ml_dsa,
+  op_crypto_import_spki_ml_kem,
   op_crypto_import_spki_x25519,
   op_crypto_import_spki_x448,
   op_crypto_jwk_x_ed25519,
+  op_crypto_jwk_x_ed448,
+  op_crypto_ml_dsa_check_public_key,
+  op_crypto_ml_kem_check_public_key,
+  op_crypto_ml_kem_decapsulate,
+  op_crypto_ml_kem_encapsulate,
   op_crypto_random_uuid,
   op_crypto_sign_ed25519,
+  op_crypto_sign_ed448,
   op_crypto_sign_key,
+  op_crypto_sign_ml_dsa,
   op_crypto_subtle_digest,
   op_crypto_unwrap_key,
   op_crypto_verify_ed25519,
+  op_crypto_verify_ed448,
   op_crypto_verify_key,
+  op_crypto_verify_ml_dsa,
   op_crypto_wrap_key,
   op_crypto_x25519_public_key,
 } from "ext:core/ops";
@@ -71,6 +95,7 @@ const {
   SafeWeakMap,
   StringFromCharCode,
   StringPrototypeCharCodeAt,
+  StringPrototypeSlice,
   StringPrototypeToLowerCase,
   StringPrototypeToUpperCase,
   Symbol,
@@ -101,10 +126,19 @@ const recognisedUsages = [
   "deriveBits",
   "wrapKey",
   "unwrapKey",
+  "encapsulateKey",
+  "encapsulateBits",
+  "decapsulateKey",
+  "decapsulateBits",
 ];
 
+// SHA-3 can only be used with `digest()`, not as the hash of other algorithms.
+const digestOnlyAlgorithms = ["SHA3-256", "SHA3-384", "SHA3-512"];
+
 const simpleAlgorithmDictionaries = {
   AesGcmParams: { iv: "BufferSource", additionalData: "BufferSource" },
+  AeadParams: { iv: "BufferSource", additionalData: "BufferSource" },
+  ContextParams: { context: "BufferSource" },
   RsaHashedKeyGenParams: { hash: "HashAlgorithmIdentifier" },
   EcKeyGenParams: {},
   HmacKeyGenParams: { hash: "HashAlgorithmIdentifier" },
@@ -128,6 +162,9 @@ const supportedAlgorithms = {
     "SHA-256": null,
     "SHA-384": null,
     "SHA-512": null,
+    "SHA3-256": null,
+    "SHA3-384": null,
+    "SHA3-512": null,
   },
   "generateKey": {
     "RSASSA-PKCS1-v1_5": "RsaHashedKeyGenParams",
@@ -143,6 +180,14 @@ const supportedAlgorithms = {
     "X25519": null,
     "X448": null,
     "Ed25519": null,
+    "Ed448": null,
+    "ChaCha20-Poly1305": null,
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+    "ML-DSA-44": null,
+    "ML-DSA-65": null,
+    "ML-DSA-87": null,
   },
   "sign": {
     "RSASSA-PKCS1-v1_5": null,
@@ -150,6 +195,10 @@ const supportedAlgorithms = {
     "ECDSA": "EcdsaParams",
     "HMAC": null,
     "Ed25519": null,
+    "Ed448": "ContextParams",
+    "ML-DSA-44": "ContextParams",
+    "ML-DSA-65": "ContextParams",
+    "ML-DSA-87": "ContextParams",
   },
   "verify": {
     "RSASSA-PKCS1-v1_5": null,
@@ -157,6 +206,10 @@ const supportedAlgorithms = {
     "ECDSA": "EcdsaParams",
     "HMAC": null,
     "Ed25519": null,
+    "Ed448": "ContextParams",
+    "ML-DSA-44": "ContextParams",
+    "ML-DSA-65": "ContextParams",
+    "ML-DSA-87": "ContextParams",
   },
   "importKey": {
     "RSASSA-PKCS1-v1_5": "RsaHashedImportParams",
@@ -174,6 +227,14 @@ const supportedAlgorithms = {
     "Ed25519": null,
     "X25519": null,
     "X448": null,
+    "Ed448": null,
+    "ChaCha20-Poly1305": null,
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+    "ML-DSA-44": null,
+    "ML-DSA-65": null,
+    "ML-DSA-87": null,
   },
   "deriveBits": {
     "HKDF": "HkdfParams",
@@ -187,12 +248,14 @@ const supportedAlgorithms = {
     "AES-CBC": "AesCbcParams",
     "AES-GCM": "AesGcmParams",
     "AES-CTR": "AesCtrParams",
+    "ChaCha20-Poly1305": "AeadParams",
   },
   "decrypt": {
     "RSA-OAEP": "RsaOaepParams",
     "AES-CBC": "AesCbcParams",
     "AES-GCM": "AesGcmParams",
     "AES-CTR": "AesCtrParams",
+    "ChaCha20-Poly1305": "AeadParams",
   },
   "get key length": {
     "AES-CBC": "AesDerivedKeyParams",
@@ -202,6 +265,7 @@ const supportedAlgorithms = {
     "HMAC": "HmacImportParams",
     "HKDF": null,
     "PBKDF2": null,
+    "ChaCha20-Poly1305": null,
   },
   "wrapKey": {
     "AES-KW": null,
@@ -209,8 +273,55 @@ const supportedAlgorithms = {
   "unwrapKey": {
     "AES-KW": null,
   },
+  "encapsulate": {
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+  },
+  "decapsulate": {
+    "ML-KEM-512": null,
+    "ML-KEM-768": null,
+    "ML-KEM-1024": null,
+  },
 };
 
+// Algorithms for which "raw-public" and "raw-secret" are spellings of "raw",
+// see https://wicg.github.io/webcrypto-modern-algos/#subtlecrypto-interface-keyformat
+const rawPublicAlgorithms = [
+  "ECDSA",
+  "ECDH",
+  "Ed25519",
+  "Ed448",
+  "X25519",
+  "X448",
+];
+const rawSecretAlgorithms = [
+  "AES-CTR",
+  "AES-CBC",
+  "AES-GCM",
+  "AES-KW",
+  "HMAC",
+  "HKDF",
+  "PBKDF2",
+];
+
+/**
+ * @param {string} format
+ * @param {string} algorithmName
+ * @returns {string}
+ */
+function normalizeRawFormat(format, algorithmName) {
+  if (
+    (format === "raw-public" &&
+      ArrayPrototypeIncludes(rawPublicAlgorithms, algorithmName)) ||
+    (format === "raw-secret" &&
+      ArrayPrototypeIncludes(rawSecretAlgorithms, algorithmName))
+  ) {
+    return "raw";
+  }
+  return format;
+}
+
 const aesJwkAlg = {
   "AES-CTR": {
     128: "A128CTR",
@@ -234,6 +345,33 @@ const aesJwkAlg = {
   },
 };
 
+// Parameters shared by the parameter sets of ML-KEM and ML-DSA, keyed by
+// algorithm family. Private keys are stored as their seed.
+const mlKeyParams = {
+  "ML-KEM": {
+    seedLength: 64,
+    publicUsages: ["encapsulateKey", "encapsulateBits"],
+    privateUsages: ["decapsulateKey", "decapsulateBits"],
+    generateKeyPair: op_crypto_generate_ml_kem_keypair,
+    checkPublicKey: op_crypto_ml_kem_check_public_key,
+    importSpki: op_crypto_import_spki_ml_kem,
+    importPkcs8: op_crypto_import_pkcs8_ml_kem,
+    exportSpki: op_crypto_export_spki_ml_kem,
+    exportPkcs8: op_crypto_export_pkcs8_ml_kem,
+  },
+  "ML-DSA": {
+    seedLength: 32,
+    publicUsages: ["verify"],
+    privateUsages: ["sign"],
+    generateKeyPair: op_crypto_generate_ml_dsa_keypair,
+    checkPublicKey: op_crypto_ml_dsa_check_public_key,
+    importSpki: op_crypto_import_spki_ml_dsa,
+    importPkcs8: op_crypto_import_pkcs8_ml_dsa,
+    exportSpki: op_crypto_export_spki_ml_dsa,
+    exportPkcs8: op_crypto_export_pkcs8_ml_dsa,
+  },
+};
+
 // See https://www.w3.org/TR/WebCryptoAPI/#dfn-normalize-an-algorithm
 // 18.4.4
 function normalizeAlgorithm(algorithm, op) {
@@ -300,6 +438,17 @@ function normalizeAlgorithm(algorithm, op) {
       normalizedAlgorithm[member] = copyBuffer(idlValue);
     } else if (idlType === "HashAlgorithmIdentifier") {
       normalizedAlgorithm[member] = normalizeAlgorithm(idlValue, "digest");
+      if (
+        ArrayPrototypeIncludes(
+          digestOnlyAlgorithms,
+          normalizedAlgorithm[member].name,
+        )
+      ) {
+        throw new DOMException(
+          `${normalizedAlgorithm[member].name} can only be used with digest()`,
+          "NotSupportedError",
+        );
+      }
     } else if (idlType === "AlgorithmIdentifier") {
       // TODO(lucacasonato): implement
       throw new TypeError("Unimplemented");
@@ -501,11 +650,30 @@ function getKeyLength(algorithm) {
       // 1.
       return null;
     }
+    case "ChaCha20-Poly1305": {
+      return 256;
+    }
     default:
       throw new TypeError("Unreachable");
   }
 }
 
+/**
+ * The optional context string of Ed448 and ML-DSA signatures.
+ * @param {object} normalizedAlgorithm
+ * @returns {Uint8Array}
+ */
+function getSignatureContext(normalizedAlgorithm) {
+  const context = normalizedAlgorithm.context ?? new Uint8Array();
+  if (TypedArrayPrototypeGetByteLength(context) > 255) {
+    throw new DOMException(
+      "Context must not be longer than 255 bytes",
+      "OperationError",
+    );
+  }
+  return context;
+}
+
 class SubtleCrypto {
   constructor() {
     webidl.illegalConstructor();
@@ -780,6 +948,25 @@ class SubtleCrypto {
         // 9.
         return TypedArrayPrototypeGetBuffer(plaintext);
       }
+      case "ChaCha20-Poly1305": {
+        normalizeAeadParams(normalizedAlgorithm);
+
+        if (TypedArrayPrototypeGetByteLength(data) < 16) {
+          throw new DOMException(
+            "The provided data is too small",
+            "OperationError",
+          );
+        }
+
+        const plaintext = await op_crypto_decrypt({
+          key: keyData,
+          algorithm: "ChaCha20-Poly1305",
+          iv: normalizedAlgorithm.iv,
+          additionalData: normalizedAlgorithm.additionalData || null,
+        }, data);
+
+        return TypedArrayPrototypeGetBuffer(plaintext);
+      }
       default:
         throw new DOMException("Not implemented", "NotSupportedError");
     }
@@ -924,6 +1111,47 @@ class SubtleCrypto {
         }
         return TypedArrayPrototypeGetBuffer(signature);
       }
+      case "Ed448": {
+        // 1.
+        if (key[_type] !== "private") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        const context = getSignatureContext(normalizedAlgorithm);
+        // Section 5.2.6 of RFC 8032
+        const SIGNATURE_LEN = 57 * 2;
+        const signature = new Uint8Array(SIGNATURE_LEN);
+        if (!op_crypto_sign_ed448(keyData, data, context, signature)) {
+          throw new DOMException(
+            "Failed to sign",
+            "OperationError",
+          );
+        }
+        return TypedArrayPrototypeGetBuffer(signature);
+      }
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        // 1.
+        if (key[_type] !== "private") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        const context = getSignatureContext(normalizedAlgorithm);
+        const signature = op_crypto_sign_ml_dsa(
+          normalizedAlgorithm.name,
+          keyData,
+          data,
+          context,
+        );
+        return TypedArrayPrototypeGetBuffer(signature);
+      }
     }
 
     throw new TypeError("Unreachable");
@@ -973,6 +1201,7 @@ class SubtleCrypto {
     }
 
     const normalizedAlgorithm = normalizeAlgorithm(algorithm, "importKey");
+    format = normalizeRawFormat(format, normalizedAlgorithm.name);
 
     // 8.
     const result = await importKeyInner(
@@ -1012,6 +1241,7 @@ class SubtleCrypto {
     const innerKey = WeakMapPrototypeGet(KEY_STORE, handle);
 
     const algorithmName = key[_algorithm].name;
+    format = normalizeRawFormat(format, algorithmName);
 
     let result;
 
@@ -1035,6 +1265,10 @@ class SubtleCrypto {
         result = exportKeyEd25519(format, key, innerKey);
         break;
       }
+      case "Ed448": {
+        result = exportKeyEd448(format, key, innerKey);
+        break;
+      }
       case "X448": {
         result = exportKeyX448(format, key, innerKey);
         break;
@@ -1050,6 +1284,19 @@ class SubtleCrypto {
         result = exportKeyAES(format, key, innerKey);
         break;
       }
+      case "ChaCha20-Poly1305": {
+        result = exportKeyChaCha20Poly1305(format, key, innerKey);
+        break;
+      }
+      case "ML-KEM-512":
+      case "ML-KEM-768":
+      case "ML-KEM-1024":
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        result = exportKeyMl(format, key, innerKey);
+        break;
+      }
       default:
         throw new DOMException("Not implemented", "NotSupportedError");
     }
@@ -1186,7 +1433,7 @@ class SubtleCrypto {
 
     // 15.
     const result = await this.importKey(
-      "raw",
+      "raw-secret",
       secret,
       normalizedDerivedKeyAlgorithmImport,
       extractable,
@@ -1322,6 +1569,38 @@ class SubtleCrypto {
 
         return op_crypto_verify_ed25519(keyData, data, signature);
       }
+      case "Ed448": {
+        // 1.
+        if (key[_type] !== "public") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        const context = getSignatureContext(normalizedAlgorithm);
+        return op_crypto_verify_ed448(keyData, data, context, signature);
+      }
+      case "ML-DSA-44":
+      case "ML-DSA-65":
+      case "ML-DSA-87": {
+        // 1.
+        if (key[_type] !== "public") {
+          throw new DOMException(
+            "Key type not supported",
+            "InvalidAccessError",
+          );
+        }
+
+        const context = getSignatureContext(normalizedAlgorithm);
+        return op_crypto_verify_ml_dsa(
+          normalizedAlgorithm.name,
+          keyData,
+          data,
+          context,
+          signature,
+        );
+      }
     }
 
     throw new TypeError("Unreachable");
@@ -1667,6 +1946,217 @@ class SubtleCrypto {
     return result;
   }
 
+  /**
+   * @param {AlgorithmIdentifier} encapsulationAlgorithm
+   * @param {CryptoKey} encapsulationKey
+   * @returns {Promise<{ sharedKey: ArrayBuffer, ciphertext: ArrayBuffer }>}
+   */
+  // deno-lint-ignore require-await
+  async encapsulateBits(encapsulationAlgorithm, encapsulationKey) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'encapsulateBits' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    encapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      encapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    encapsulationKey = webidl.converters.CryptoKey(
+      encapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+
+    const normalizedAlgorithm = normalizeAlgorithm(
+      encapsulationAlgorithm,
+      "encapsulate",
+    );
+    const { sharedKey, ciphertext } = encapsulate(
+      normalizedAlgorithm,
+      encapsulationKey,
+      "encapsulateBits",
+    );
+    return {
+      sharedKey: TypedArrayPrototypeGetBuffer(sharedKey),
+      ciphertext: TypedArrayPrototypeGetBuffer(ciphertext),
+    };
+  }
+
+  /**
+   * @param {AlgorithmIdentifier} encapsulationAlgorithm
+   * @param {CryptoKey} encapsulationKey
+   * @param {AlgorithmIdentifier} sharedKeyAlgorithm
+   * @param {boolean} extractable
+   * @param {KeyUsage[]} keyUsages
+   * @returns {Promise<{ sharedKey: CryptoKey, ciphertext: ArrayBuffer }>}
+   */
+  async encapsulateKey(
+    encapsulationAlgorithm,
+    encapsulationKey,
+    sharedKeyAlgorithm,
+    extractable,
+    keyUsages,
+  ) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'encapsulateKey' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 5, prefix);
+    encapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      encapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    encapsulationKey = webidl.converters.CryptoKey(
+      encapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    sharedKeyAlgorithm = webidl.converters.AlgorithmIdentifier(
+      sharedKeyAlgorithm,
+      prefix,
+      "Argument 3",
+    );
+    extractable = webidl.converters.boolean(extractable, prefix, "Argument 4");
+    keyUsages = webidl.converters["sequence<KeyUsage>"](
+      keyUsages,
+      prefix,
+      "Argument 5",
+    );
+
+    const normalizedAlgorithm = normalizeAlgorithm(
+      encapsulationAlgorithm,
+      "encapsulate",
+    );
+    const normalizedSharedKeyAlgorithm = normalizeAlgorithm(
+      sharedKeyAlgorithm,
+      "importKey",
+    );
+    const { sharedKey, ciphertext } = encapsulate(
+      normalizedAlgorithm,
+      encapsulationKey,
+      "encapsulateKey",
+    );
+    return {
+      sharedKey: await this.importKey(
+        "raw-secret",
+        sharedKey,
+        normalizedSharedKeyAlgorithm,
+        extractable,
+        keyUsages,
+      ),
+      ciphertext: TypedArrayPrototypeGetBuffer(ciphertext),
+    };
+  }
+
+  /**
+   * @param {AlgorithmIdentifier} decapsulationAlgorithm
+   * @param {CryptoKey} decapsulationKey
+   * @param {BufferSource} ciphertext
+   * @returns {Promise<ArrayBuffer>}
+   */
+  // deno-lint-ignore require-await
+  async decapsulateBits(decapsulationAlgorithm, decapsulationKey, ciphertext) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'decapsulateBits' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 3, prefix);
+    decapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      decapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    decapsulationKey = webidl.converters.CryptoKey(
+      decapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    ciphertext = webidl.converters.BufferSource(
+      ciphertext,
+      prefix,
+      "Argument 3",
+    );
+
+    const normalizedAlgorithm = normalizeAlgorithm(
+      decapsulationAlgorithm,
+      "decapsulate",
+    );
+    const sharedKey = decapsulate(
+      normalizedAlgorithm,
+      decapsulationKey,
+      copyBuffer(ciphertext),
+      "decapsulateBits",
+    );
+    return TypedArrayPrototypeGetBuffer(sharedKey);
+  }
+
+  /**
+   * @param {AlgorithmIdentifier} decapsulationAlgorithm
+   * @param {CryptoKey} decapsulationKey
+   * @param {BufferSource} ciphertext
+   * @param {AlgorithmIdentifier} sharedKeyAlgorithm
+   * @param {boolean} extractable
+   * @param {KeyUsage[]} keyUsages
+   * @returns {Promise<CryptoKey>}
+   */
+  async decapsulateKey(
+    decapsulationAlgorithm,
+    decapsulationKey,
+    ciphertext,
+    sharedKeyAlgorithm,
+    extractable,
+    keyUsages,
+  ) {
+    webidl.assertBranded(this, SubtleCryptoPrototype);
+    const prefix = "Failed to execute 'decapsulateKey' on 'SubtleCrypto'";
+    webidl.requiredArguments(arguments.length, 6, prefix);
+    decapsulationAlgorithm = webidl.converters.AlgorithmIdentifier(
+      decapsulationAlgorithm,
+      prefix,
+      "Argument 1",
+    );
+    decapsulationKey = webidl.converters.CryptoKey(
+      decapsulationKey,
+      prefix,
+      "Argument 2",
+    );
+    ciphertext = webidl.converters.BufferSource(
+      ciphertext,
+      prefix,
+      "Argument 3",
+    );
+    sharedKeyAlgorithm = webidl.converters.AlgorithmIdentifier(
+      sharedKeyAlgorithm,
+      prefix,
+      "Argument 4",
+    );
+    extractable = webidl.converters.boolean(extractable, prefix, "Argument 5");
+    keyUsages = webidl.converters["sequence<KeyUsage>"](
+      keyUsages,
+      prefix,
+      "Argument 6",
+    );
+
+    const normalizedAlgorithm = normalizeAlgorithm(
+      decapsulationAlgorithm,
+      "decapsulate",
+    );
+    const normalizedSharedKeyAlgorithm = normalizeAlgorithm(
+      sharedKeyAlgorithm,
+      "importKey",
+    );
+    const sharedKey = decapsulate(
+      normalizedAlgorithm,
+      decapsulationKey,
+      copyBuffer(ciphertext),
+      "decapsulateKey",
+    );
+    return await this.importKey(
+      "raw-secret",
+      sharedKey,
+      normalizedSharedKeyAlgorithm,
+      extractable,
+      keyUsages,
+    );
+  }
+
   [SymbolFor("Deno.privateCustomInspect")](inspect, inspectOptions) {
     return `${this.constructor.name} ${inspect({}, inspectOptions)}`;
   }
@@ -2076,8 +2566,7 @@ async function generateKey(normalizedAlgorithm, extractable, usages) {
 
       return { publicKey, privateKey };
     }
-    case "HMAC": {
-      // 1.
+    case "Ed448": {
       if (
         ArrayPrototypeFind(
           usages,
@@ -2087,42 +2576,175 @@ async function generateKey(normalizedAlgorithm, extractable, usages) {
         throw new DOMException("Invalid key usage", "SyntaxError");
       }
 
-      // 2.
-      let length;
-      if (normalizedAlgorithm.length === undefined) {
-        length = null;
-      } else if (normalizedAlgorithm.length !== 0) {
-        length = normalizedAlgorithm.length;
-      } else {
-        throw new DOMException("Invalid length", "OperationError");
+      const ED448_KEY_LEN = 57;
+      const privateKeyData = new Uint8Array(ED448_KEY_LEN);
+      const publicKeyData = new Uint8Array(ED448_KEY_LEN);
+      if (!op_crypto_generate_ed448_keypair(privateKeyData, publicKeyData)) {
+        throw new DOMException("Failed to generate key", "OperationError");
       }
 
-      // 3-4.
-      const keyData = await op_crypto_generate_key({
-        algorithm: "HMAC",
-        hash: normalizedAlgorithm.hash.name,
-        length,
-      });
       const handle = {};
-      WeakMapPrototypeSet(KEY_STORE, handle, {
-        type: "secret",
-        data: keyData,
-      });
+      WeakMapPrototypeSet(KEY_STORE, handle, privateKeyData);
+
+      const publicHandle = {};
+      WeakMapPrototypeSet(KEY_STORE, publicHandle, publicKeyData);
 
-      // 6-10.
       const algorithm = {
         name: algorithmName,
-        hash: {
-          name: normalizedAlgorithm.hash.name,
-        },
-        length: TypedArrayPrototypeGetByteLength(keyData) * 8,
       };
 
-      // 5, 11-13.
-      const key = constructKey(
-        "secret",
+      const publicKey = constructKey(
+        "public",
+        true,
+        usageIntersection(usages, ["verify"]),
+        algorithm,
+        publicHandle,
+      );
+
+      const privateKey = constructKey(
+        "private",
         extractable,
-        usages,
+        usageIntersection(usages, ["sign"]),
+        algorithm,
+        handle,
+      );
+
+      return { publicKey, privateKey };
+    }
+    case "ChaCha20-Poly1305": {
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) =>
+            !ArrayPrototypeIncludes([
+              "encrypt",
+              "decrypt",
+              "wrapKey",
+              "unwrapKey",
+            ], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      const keyData = await op_crypto_generate_key({
+        algorithm: "AES",
+        length: 256,
+      });
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, {
+        type: "secret",
+        data: keyData,
+      });
+
+      const algorithm = {
+        name: algorithmName,
+      };
+
+      return constructKey(
+        "secret",
+        extractable,
+        usages,
+        algorithm,
+        handle,
+      );
+    }
+    case "ML-KEM-512":
+    case "ML-KEM-768":
+    case "ML-KEM-1024":
+    case "ML-DSA-44":
+    case "ML-DSA-65":
+    case "ML-DSA-87": {
+      const params = mlKeyParams[StringPrototypeSlice(algorithmName, 0, 6)];
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) =>
+            !ArrayPrototypeIncludes(params.publicUsages, u) &&
+            !ArrayPrototypeIncludes(params.privateUsages, u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      const seed = new Uint8Array(params.seedLength);
+      const publicKeyData = params.generateKeyPair(algorithmName, seed);
+
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, seed);
+
+      const publicHandle = {};
+      WeakMapPrototypeSet(KEY_STORE, publicHandle, publicKeyData);
+
+      const algorithm = {
+        name: algorithmName,
+      };
+
+      const publicKey = constructKey(
+        "public",
+        true,
+        usageIntersection(usages, params.publicUsages),
+        algorithm,
+        publicHandle,
+      );
+
+      const privateKey = constructKey(
+        "private",
+        extractable,
+        usageIntersection(usages, params.privateUsages),
+        algorithm,
+        handle,
+      );
+
+      return { publicKey, privateKey };
+    }
+    case "HMAC": {
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          usages,
+          (u) => !ArrayPrototypeIncludes(["sign", "verify"], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      // 2.
+      let length;
+      if (normalizedAlgorithm.length === undefined) {
+        length = null;
+      } else if (normalizedAlgorithm.length !== 0) {
+        length = normalizedAlgorithm.length;
+      } else {
+        throw new DOMException("Invalid length", "OperationError");
+      }
+
+      // 3-4.
+      const keyData = await op_crypto_generate_key({
+        algorithm: "HMAC",
+        hash: normalizedAlgorithm.hash.name,
+        length,
+      });
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, {
+        type: "secret",
+        data: keyData,
+      });
+
+      // 6-10.
+      const algorithm = {
+        name: algorithmName,
+        hash: {
+          name: normalizedAlgorithm.hash.name,
+        },
+        length: TypedArrayPrototypeGetByteLength(keyData) * 8,
+      };
+
+      // 5, 11-13.
+      const key = constructKey(
+        "secret",
+        extractable,
+        usages,
         algorithm,
         handle,
       );
@@ -2571,6 +3193,249 @@ function importKeyEd25519(
   }
 }
 
+function importKeyEd448(
+  format,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  switch (format) {
+    case "raw": {
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          keyUsages,
+          (u) => !ArrayPrototypeIncludes(["verify"], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      if (TypedArrayPrototypeGetByteLength(keyData) !== 57) {
+        throw new DOMException("Invalid key data", "DataError");
+      }
+
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, keyData);
+
+      // 2-3.
+      const algorithm = {
+        name: "Ed448",
+      };
+
+      // 4-6.
+      return constructKey(
+        "public",
+        extractable,
+        usageIntersection(keyUsages, recognisedUsages),
+        algorithm,
+        handle,
+      );
+    }
+    case "spki": {
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          keyUsages,
+          (u) => !ArrayPrototypeIncludes(["verify"], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      const publicKeyData = new Uint8Array(57);
+      if (!op_crypto_import_spki_ed448(keyData, publicKeyData)) {
+        throw new DOMException("Invalid key data", "DataError");
+      }
+
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, publicKeyData);
+
+      const algorithm = {
+        name: "Ed448",
+      };
+
+      return constructKey(
+        "public",
+        extractable,
+        usageIntersection(keyUsages, recognisedUsages),
+        algorithm,
+        handle,
+      );
+    }
+    case "pkcs8": {
+      // 1.
+      if (
+        ArrayPrototypeFind(
+          keyUsages,
+          (u) => !ArrayPrototypeIncludes(["sign"], u),
+        ) !== undefined
+      ) {
+        throw new DOMException("Invalid key usage", "SyntaxError");
+      }
+
+      const privateKeyData = new Uint8Array(57);
+      if (!op_crypto_import_pkcs8_ed448(keyData, privateKeyData)) {
+        throw new DOMException("Invalid key data", "DataError");
+      }
+
+      const handle = {};
+      WeakMapPrototypeSet(KEY_STORE, handle, privateKeyData);
+
+      const algorithm = {
+        name: "Ed448",
+      };
+
+      return constructKey(
+        "private",
+        extractable,
+        usageIntersection(keyUsages, recognisedUsages),
+        algorithm,
+        handle,
+      );
+    }
+    case "jwk": {
+      // 1.
+      const jwk = keyData;
+
+      // 2.
+      if (jwk.d !== undefined) {
+        if (
+          ArrayPrototypeFind(
+            keyUsages,
+            (u) =>
+              !ArrayPrototypeIncludes(
+                ["sign"],
+                u,
+              ),
+          ) !== undefined
+        ) {
+          throw new DOMException("Invalid key usage", "SyntaxError");
+        }
+      } else {
+        if (
+          ArrayPrototypeFind(
+            keyUsages,
+            (u) =>
+              !ArrayPrototypeIncludes(
+                ["verify"],
+                u,
+              ),
+          ) !== undefined
+        ) {
+          throw new DOMException("Invalid key usage", "SyntaxError");
+        }
+      }
+
+      // 3.
+      if (jwk.kty !== "OKP") {
+        throw new DOMException("Invalid key type", "DataError");
+      }
+
+      // 4.
+      if (jwk.crv !== "Ed448") {
+        throw new DOMException("Invalid curve", "DataError");
+      }
+
+      // 5.
+      if (
+        keyUsages.length > 0 && jwk.use !== undefined && jwk.use !== "sig"
+      ) {
+        throw new DOMException("Invalid key usage", "DataError");
+      }
+
+      // 6.
+      if (jwk.key_ops !== undefined) {
+        if (
+          ArrayPrototypeFind(
+            jwk.key_ops,
+            (u) => !ArrayPrototypeIncludes(recognisedUsages, u),
+          ) !== undefined
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+
+        if (
+          !ArrayPrototypeEvery(
+            jwk.key_ops,
+            (u) => ArrayPrototypeIncludes(keyUsages, u),
+          )
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+      }
+
+      // 7.
+      if (jwk.ext !== undefined && jwk.ext === false && extractable) {
+        throw new DOMException("Invalid key extractability", "DataError");
+      }
+
+      // 8.
+      if (jwk.d !== undefined) {
+        // https://www.rfc-editor.org/rfc/rfc8037#section-2
+        let privateKeyData;
+        try {
+          privateKeyData = op_crypto_base64url_decode(jwk.d);
+        } catch (_) {
+          throw new DOMException("Invalid private key data", "DataError");
+        }
+        if (TypedArrayPrototypeGetByteLength(privateKeyData) !== 57) {
+          throw new DOMException("Invalid private key data", "DataError");
+        }
+
+        const handle = {};
+        WeakMapPrototypeSet(KEY_STORE, handle, privateKeyData);
+
+        const algorithm = {
+          name: "Ed448",
+        };
+
+        return constructKey(
+          "private",
+          extractable,
+          usageIntersection(keyUsages, recognisedUsages),
+          algorithm,
+          handle,
+        );
+      } else {
+        // https://www.rfc-editor.org/rfc/rfc8037#section-2
+        let publicKeyData;
+        try {
+          publicKeyData = op_crypto_base64url_decode(jwk.x);
+        } catch (_) {
+          throw new DOMException("Invalid public key data", "DataError");
+        }
+        if (TypedArrayPrototypeGetByteLength(publicKeyData) !== 57) {
+          throw new DOMException("Invalid public key data", "DataError");
+        }
+
+        const handle = {};
+        WeakMapPrototypeSet(KEY_STORE, handle, publicKeyData);
+
+        const algorithm = {
+          name: "Ed448",
+        };
+
+        return constructKey(
+          "public",
+          extractable,
+          usageIntersection(keyUsages, recognisedUsages),
+          algorithm,
+          handle,
+        );
+      }
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
 function importKeyX25519(
   format,
   keyData,
@@ -2941,15 +3806,166 @@ function importKeyAES(
           );
       }
 
-      // 6.
+      // 6.
+      if (
+        keyUsages.length > 0 && jwk.use !== undefined && jwk.use !== "enc"
+      ) {
+        throw new DOMException("Invalid key usage", "DataError");
+      }
+
+      // 7.
+      // Section 4.3 of RFC7517
+      if (jwk.key_ops !== undefined) {
+        if (
+          ArrayPrototypeFind(
+            jwk.key_ops,
+            (u) => !ArrayPrototypeIncludes(recognisedUsages, u),
+          ) !== undefined
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+
+        if (
+          !ArrayPrototypeEvery(
+            keyUsages,
+            (u) => ArrayPrototypeIncludes(jwk.key_ops, u),
+          )
+        ) {
+          throw new DOMException(
+            "'key_ops' property of JsonWebKey is invalid",
+            "DataError",
+          );
+        }
+      }
+
+      // 8.
+      if (jwk.ext === false && extractable === true) {
+        throw new DOMException(
+          "'ext' property of JsonWebKey must not be false if extractable is true",
+          "DataError",
+        );
+      }
+
+      break;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, {
+    type: "secret",
+    data,
+  });
+
+  // 4-7.
+  const algorithm = {
+    name: algorithmName,
+    length: TypedArrayPrototypeGetByteLength(data) * 8,
+  };
+
+  const key = constructKey(
+    "secret",
+    extractable,
+    usageIntersection(keyUsages, recognisedUsages),
+    algorithm,
+    handle,
+  );
+
+  // 8.
+  return key;
+}
+
+function exportKeyChaCha20Poly1305(format, key, innerKey) {
+  switch (format) {
+    case "raw-secret": {
+      return TypedArrayPrototypeGetBuffer(innerKey.data);
+    }
+    case "jwk": {
+      const jwk = {
+        kty: "oct",
+      };
+      const data = op_crypto_export_key({
+        format: "jwksecret",
+        algorithm: "AES",
+      }, innerKey);
+      ObjectAssign(jwk, data);
+      jwk.alg = "C20P";
+      jwk.key_ops = key.usages;
+      jwk.ext = key[_extractable];
+      return jwk;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
+// https://wicg.github.io/webcrypto-modern-algos/#chacha20-poly1305-operations
+function importKeyChaCha20Poly1305(
+  format,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  // 1.
+  if (
+    ArrayPrototypeFind(
+      keyUsages,
+      (u) =>
+        !ArrayPrototypeIncludes(
+          ["encrypt", "decrypt", "wrapKey", "unwrapKey"],
+          u,
+        ),
+    ) !== undefined
+  ) {
+    throw new DOMException("Invalid key usage", "SyntaxError");
+  }
+
+  let data = keyData;
+
+  switch (format) {
+    case "raw-secret": {
+      break;
+    }
+    case "jwk": {
+      const jwk = keyData;
+
+      if (jwk.kty !== "oct") {
+        throw new DOMException(
+          "'kty' property of JsonWebKey must be 'oct'",
+          "DataError",
+        );
+      }
+
+      if (jwk.k === undefined) {
+        throw new DOMException(
+          "'k' property of JsonWebKey must be present",
+          "DataError",
+        );
+      }
+
+      const { rawData } = op_crypto_import_key(
+        { algorithm: "AES" },
+        { jwkSecret: jwk },
+      );
+      data = rawData.data;
+
+      if (jwk.alg !== undefined && jwk.alg !== "C20P") {
+        throw new DOMException(
+          `Invalid algorithm: ${jwk.alg}`,
+          "DataError",
+        );
+      }
+
       if (
         keyUsages.length > 0 && jwk.use !== undefined && jwk.use !== "enc"
       ) {
         throw new DOMException("Invalid key usage", "DataError");
       }
 
-      // 7.
-      // Section 4.3 of RFC7517
       if (jwk.key_ops !== undefined) {
         if (
           ArrayPrototypeFind(
@@ -2976,7 +3992,6 @@ function importKeyAES(
         }
       }
 
-      // 8.
       if (jwk.ext === false && extractable === true) {
         throw new DOMException(
           "'ext' property of JsonWebKey must not be false if extractable is true",
@@ -2990,28 +4005,138 @@ function importKeyAES(
       throw new DOMException("Not implemented", "NotSupportedError");
   }
 
+  // 2.
+  if (TypedArrayPrototypeGetByteLength(data) !== 32) {
+    throw new DOMException("Invalid key length", "DataError");
+  }
+
   const handle = {};
   WeakMapPrototypeSet(KEY_STORE, handle, {
     type: "secret",
     data,
   });
 
-  // 4-7.
   const algorithm = {
-    name: algorithmName,
-    length: TypedArrayPrototypeGetByteLength(data) * 8,
+    name: "ChaCha20-Poly1305",
   };
 
-  const key = constructKey(
+  return constructKey(
     "secret",
     extractable,
     usageIntersection(keyUsages, recognisedUsages),
     algorithm,
     handle,
   );
+}
 
-  // 8.
-  return key;
+// https://wicg.github.io/webcrypto-modern-algos/#ml-kem-operations
+// https://wicg.github.io/webcrypto-modern-algos/#ml-dsa-operations
+function importKeyMl(
+  format,
+  normalizedAlgorithm,
+  keyData,
+  extractable,
+  keyUsages,
+) {
+  const algorithmName = normalizedAlgorithm.name;
+  const params = mlKeyParams[StringPrototypeSlice(algorithmName, 0, 6)];
+
+  let type;
+  let data;
+  switch (format) {
+    case "raw-public":
+    case "spki": {
+      type = "public";
+      data = format === "spki"
+        ? params.importSpki(algorithmName, keyData)
+        : (params.checkPublicKey(algorithmName, keyData) ? keyData : null);
+      break;
+    }
+    case "raw-seed":
+    case "pkcs8": {
+      type = "private";
+      data = format === "pkcs8"
+        ? params.importPkcs8(algorithmName, keyData)
+        : (TypedArrayPrototypeGetByteLength(keyData) === params.seedLength
+          ? keyData
+          : null);
+      break;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+
+  // 1.
+  const supportedUsages = type === "public"
+    ? params.publicUsages
+    : params.privateUsages;
+  if (
+    ArrayPrototypeFind(
+      keyUsages,
+      (u) => !ArrayPrototypeIncludes(supportedUsages, u),
+    ) !== undefined
+  ) {
+    throw new DOMException("Invalid key usage", "SyntaxError");
+  }
+
+  if (data === null) {
+    throw new DOMException("Invalid key data", "DataError");
+  }
+
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, data);
+
+  const algorithm = {
+    name: algorithmName,
+  };
+
+  return constructKey(
+    type,
+    extractable,
+    usageIntersection(keyUsages, recognisedUsages),
+    algorithm,
+    handle,
+  );
+}
+
+function exportKeyMl(format, key, innerKey) {
+  const algorithmName = key[_algorithm].name;
+  const params = mlKeyParams[StringPrototypeSlice(algorithmName, 0, 6)];
+
+  switch (format) {
+    case "raw-public":
+    case "spki": {
+      if (key[_type] !== "public") {
+        throw new DOMException(
+          "Key is not a public key",
+          "InvalidAccessError",
+        );
+      }
+
+      if (format === "raw-public") {
+        return TypedArrayPrototypeGetBuffer(innerKey);
+      }
+      const spkiDer = params.exportSpki(algorithmName, innerKey);
+      return TypedArrayPrototypeGetBuffer(spkiDer);
+    }
+    case "raw-seed":
+    case "pkcs8": {
+      if (key[_type] !== "private") {
+        throw new DOMException(
+          "Key is not a private key",
+          "InvalidAccessError",
+        );
+      }
+
+      if (format === "raw-seed") {
+        return TypedArrayPrototypeGetBuffer(innerKey);
+      }
+      const pkcs8Der = params.exportPkcs8(algorithmName, innerKey);
+      return TypedArrayPrototypeGetBuffer(pkcs8Der);
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
 }
 
 function importKeyHMAC(
@@ -3620,6 +4745,36 @@ async function importKeyInner(
         keyUsages,
       );
     }
+    case "Ed448": {
+      return importKeyEd448(
+        format,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
+    case "ChaCha20-Poly1305": {
+      return importKeyChaCha20Poly1305(
+        format,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
+    case "ML-KEM-512":
+    case "ML-KEM-768":
+    case "ML-KEM-1024":
+    case "ML-DSA-44":
+    case "ML-DSA-65":
+    case "ML-DSA-87": {
+      return importKeyMl(
+        format,
+        normalizedAlgorithm,
+        keyData,
+        extractable,
+        keyUsages,
+      );
+    }
     default:
       throw new DOMException("Not implemented", "NotSupportedError");
   }
@@ -4408,6 +5563,65 @@ function exportKeyEd25519(format, key, innerKey) {
   }
 }
 
+function exportKeyEd448(format, key, innerKey) {
+  switch (format) {
+    case "raw": {
+      // 1.
+      if (key[_type] !== "public") {
+        throw new DOMException(
+          "Key is not a public key",
+          "InvalidAccessError",
+        );
+      }
+
+      // 2-3.
+      return TypedArrayPrototypeGetBuffer(innerKey);
+    }
+    case "spki": {
+      // 1.
+      if (key[_type] !== "public") {
+        throw new DOMException(
+          "Key is not a public key",
+          "InvalidAccessError",
+        );
+      }
+
+      const spkiDer = op_crypto_export_spki_ed448(innerKey);
+      return TypedArrayPrototypeGetBuffer(spkiDer);
+    }
+    case "pkcs8": {
+      // 1.
+      if (key[_type] !== "private") {
+        throw new DOMException(
+          "Key is not a public key",
+          "InvalidAccessError",
+        );
+      }
+
+      const pkcs8Der = op_crypto_export_pkcs8_ed448(innerKey);
+      return TypedArrayPrototypeGetBuffer(pkcs8Der);
+    }
+    case "jwk": {
+      const x = key[_type] === "private"
+        ? op_crypto_jwk_x_ed448(innerKey)
+        : op_crypto_base64url_encode(innerKey);
+      const jwk = {
+        kty: "OKP",
+        crv: "Ed448",
+        x,
+        "key_ops": key.usages,
+        ext: key[_extractable],
+      };
+      if (key[_type] === "private") {
+        jwk.d = op_crypto_base64url_encode(innerKey);
+      }
+      return jwk;
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
 function exportKeyX448(format, key, innerKey) {
   switch (format) {
     case "raw": {
@@ -5088,11 +6302,109 @@ async function encrypt(normalizedAlgorithm, key, data) {
       // 8.
       return TypedArrayPrototypeGetBuffer(cipherText);
     }
+    case "ChaCha20-Poly1305": {
+      normalizeAeadParams(normalizedAlgorithm);
+
+      const cipherText = await op_crypto_encrypt({
+        key: keyData,
+        algorithm: "ChaCha20-Poly1305",
+        iv: normalizedAlgorithm.iv,
+        additionalData: normalizedAlgorithm.additionalData || null,
+      }, data);
+
+      return TypedArrayPrototypeGetBuffer(cipherText);
+    }
     default:
       throw new DOMException("Not implemented", "NotSupportedError");
   }
 }
 
+// https://wicg.github.io/webcrypto-modern-algos/#chacha20-poly1305-operations
+function normalizeAeadParams(normalizedAlgorithm) {
+  normalizedAlgorithm.iv = copyBuffer(normalizedAlgorithm.iv);
+  if (TypedArrayPrototypeGetByteLength(normalizedAlgorithm.iv) !== 12) {
+    throw new DOMException(
+      "Initialization vector must be 12 bytes",
+      "OperationError",
+    );
+  }
+  if (
+    normalizedAlgorithm.tagLength !== undefined &&
+    normalizedAlgorithm.tagLength !== 128
+  ) {
+    throw new DOMException(
+      `Invalid tag length: ${normalizedAlgorithm.tagLength}`,
+      "OperationError",
+    );
+  }
+  if (normalizedAlgorithm.additionalData) {
+    normalizedAlgorithm.additionalData = copyBuffer(
+      normalizedAlgorithm.additionalData,
+    );
+  }
+}
+
+// https://wicg.github.io/webcrypto-modern-algos/#ml-kem-operations
+function encapsulate(normalizedAlgorithm, key, usage) {
+  if (normalizedAlgorithm.name !== key[_algorithm].name) {
+    throw new DOMException(
+      "Encapsulation algorithm does not match key algorithm",
+      "InvalidAccessError",
+    );
+  }
+
+  if (!ArrayPrototypeIncludes(key[_usages], usage)) {
+    throw new DOMException(
+      "The requested operation is not valid for the provided key",
+      "InvalidAccessError",
+    );
+  }
+
+  if (key[_type] !== "public") {
+    throw new DOMException(
+      "Key type not supported",
+      "InvalidAccessError",
+    );
+  }
+
+  const keyData = WeakMapPrototypeGet(KEY_STORE, key[_handle]);
+  const { 0: ciphertext, 1: sharedKey } = op_crypto_ml_kem_encapsulate(
+    normalizedAlgorithm.name,
+    keyData,
+  );
+  return { sharedKey, ciphertext };
+}
+
+function decapsulate(normalizedAlgorithm, key, ciphertext, usage) {
+  if (normalizedAlgorithm.name !== key[_algorithm].name) {
+    throw new DOMException(
+      "Decapsulation algorithm does not match key algorithm",
+      "InvalidAccessError",
+    );
+  }
+
+  if (!ArrayPrototypeIncludes(key[_usages], usage)) {
+    throw new DOMException(
+      "The requested operation is not valid for the provided key",
+      "InvalidAccessError",
+    );
+  }
+
+  if (key[_type] !== "private") {
+    throw new DOMException(
+      "Key type not supported",
+      "InvalidAccessError",
+    );
+  }
+
+  const keyData = WeakMapPrototypeGet(KEY_STORE, key[_handle]);
+  return op_crypto_ml_kem_decapsulate(
+    normalizedAlgorithm.name,
+    keyData,
+    ciphertext,
+  );
+}
+
 webidl.configureInterface(SubtleCrypto);
 const subtle = webidl.createBranded(SubtleCrypto);
 
@@ -5200,6 +6512,10 @@ webidl.converters.KeyFormat = webidl.createEnumConverter("KeyFormat", [
   "pkcs8",
   "spki",
   "jwk",
+  "raw-public",
+  "raw-private",
+  "raw-seed",
+  "raw-secret",
 ]);
 
 webidl.converters.KeyUsage = webidl.createEnumConverter("KeyUsage", [
@@ -5211,6 +6527,10 @@ webidl.converters.KeyUsage = webidl.createEnumConverter("KeyUsage", [
   "deriveBits",
   "wrapKey",
   "unwrapKey",
+  "encapsulateKey",
+  "encapsulateBits",
+  "decapsulateKey",
+  "decapsulateBits",
 ]);
 
 webidl.converters["sequence<KeyUsage>"] = webidl.createSequenceConverter(
@@ -5635,6 +6955,41 @@ webidl.converters.AesGcmParams = webidl
 webidl.converters.AesCtrParams = webidl
   .createDictionaryConverter("AesCtrParams", dictAesCtrParams);
 
+const dictAeadParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "iv",
+    converter: webidl.converters["BufferSource"],
+    required: true,
+  },
+  {
+    key: "additionalData",
+    converter: webidl.converters["BufferSource"],
+  },
+  {
+    key: "tagLength",
+    converter: (V, prefix, context, opts) =>
+      webidl.converters["octet"](V, prefix, context, {
+        ...opts,
+        enforceRange: true,
+      }),
+  },
+];
+
+webidl.converters.AeadParams = webidl
+  .createDictionaryConverter("AeadParams", dictAeadParams);
+
+const dictContextParams = [
+  ...new SafeArrayIterator(dictAlgorithm),
+  {
+    key: "context",
+    converter: webidl.converters["BufferSource"],
+  },
+];
+
+webidl.converters.ContextParams = webidl
+  .createDictionaryConverter("ContextParams", dictContextParams);
+
 webidl.converters.CryptoKey = webidl.createInterfaceConverter(
   "CryptoKey",
   CryptoKey.prototype,
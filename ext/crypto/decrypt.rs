@@ -68,6 +68,13 @@ pub enum DecryptAlgorithm {
     length: usize,
     tag_length: usize,
   },
+  #[serde(rename = "ChaCha20-Poly1305", rename_all = "camelCase")]
+  ChaCha20Poly1305 {
+    #[serde(with = "serde_bytes")]
+    iv: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    additional_data: Option<Vec<u8>>,
+  },
 }
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
@@ -133,6 +140,10 @@ pub async fn op_crypto_decrypt(
       length,
       tag_length,
     } => decrypt_aes_gcm(key, length, tag_length, iv, additional_data, &data),
+    DecryptAlgorithm::ChaCha20Poly1305 {
+      iv,
+      additional_data,
+    } => decrypt_chacha20_poly1305(key, iv, additional_data, &data),
   };
   let buf = spawn_blocking(fun).await.unwrap()?;
   Ok(buf.into())
@@ -374,3 +385,34 @@ fn decrypt_aes_gcm(
 
   Ok(plaintext)
 }
+
+fn decrypt_chacha20_poly1305(
+  key: V8RawKeyData,
+  iv: Vec<u8>,
+  additional_data: Option<Vec<u8>>,
+  data: &[u8],
+) -> Result<Vec<u8>, DecryptError> {
+  let key = key.as_secret_key()?;
+  let additional_data = additional_data.unwrap_or_default();
+  if iv.len() != 12 {
+    return Err(DecryptError::InvalidIvLength);
+  }
+
+  // C | T, with a fixed 128-bit tag.
+  let sep = data.len().checked_sub(16).ok_or(DecryptError::Failed)?;
+  let tag = &data[sep..];
+  let mut plaintext = data[..sep].to_vec();
+
+  let cipher = chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+    .map_err(|_| DecryptError::InvalidKeyOrIv)?;
+  cipher
+    .decrypt_in_place_detached(
+      chacha20poly1305::Nonce::from_slice(&iv),
+      &additional_data,
+      &mut plaintext,
+      tag.into(),
+    )
+    .map_err(|_| DecryptError::Failed)?;
+
+  Ok(plaintext)
+}
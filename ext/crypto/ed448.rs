@@ -0,0 +1,219 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use deno_core::ToJsBuffer;
+use deno_core::op2;
+use ed448_goldilocks_plus::SecretKey;
+use ed448_goldilocks_plus::Signature;
+use ed448_goldilocks_plus::SigningKey;
+use ed448_goldilocks_plus::VerifyingKey;
+use elliptic_curve::pkcs8::PrivateKeyInfo;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use spki::der::Decode;
+use spki::der::Encode;
+use spki::der::asn1::BitString;
+
+const ED448_KEY_LEN: usize = 57;
+const ED448_SIGNATURE_LEN: usize = 2 * ED448_KEY_LEN;
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum Ed448Error {
+  #[class("DOMExceptionOperationError")]
+  #[error("Failed to export key")]
+  FailedExport,
+  #[class("DOMExceptionDataError")]
+  #[error("Invalid key data")]
+  InvalidKeyData,
+  #[class(generic)]
+  #[error(transparent)]
+  Der(#[from] spki::der::Error),
+}
+
+fn signing_key(pkey: &[u8]) -> Option<SigningKey> {
+  if pkey.len() != ED448_KEY_LEN {
+    return None;
+  }
+  Some(SigningKey::from(SecretKey::clone_from_slice(pkey)))
+}
+
+/// Verifies a signature with the `context` of RFC 8032 Section 5.2.7.
+fn verify(
+  public_key: &[u8],
+  data: &[u8],
+  context: &[u8],
+  signature: &[u8],
+) -> bool {
+  let Ok(public_key) = <[u8; ED448_KEY_LEN]>::try_from(public_key) else {
+    return false;
+  };
+  let Ok(public_key) = VerifyingKey::from_bytes(&public_key) else {
+    return false;
+  };
+  let Ok(signature) = Signature::try_from(signature) else {
+    return false;
+  };
+  public_key.verify_ctx(&signature, context, data).is_ok()
+}
+
+#[op2(fast)]
+pub fn op_crypto_generate_ed448_keypair(
+  #[buffer] pkey: &mut [u8],
+  #[buffer] pubkey: &mut [u8],
+) -> bool {
+  let mut rng = OsRng;
+  rng.fill_bytes(pkey);
+
+  let Some(key) = signing_key(pkey) else {
+    return false;
+  };
+  pubkey.copy_from_slice(&key.verifying_key().to_bytes());
+  true
+}
+
+/// Signs `data` with the optional `context` of RFC 8032 Section 5.2.6.
+#[op2(fast)]
+pub fn op_crypto_sign_ed448(
+  #[buffer] key: &[u8],
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+  #[buffer] signature: &mut [u8],
+) -> bool {
+  if context.len() > 255 || signature.len() != ED448_SIGNATURE_LEN {
+    return false;
+  }
+  let Some(key) = signing_key(key) else {
+    return false;
+  };
+  let Ok(result) = key.sign_ctx(context, data) else {
+    return false;
+  };
+  signature.copy_from_slice(&result.to_bytes());
+  true
+}
+
+#[op2(fast)]
+pub fn op_crypto_verify_ed448(
+  #[buffer] pubkey: &[u8],
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+  #[buffer] signature: &[u8],
+) -> bool {
+  context.len() <= 255 && verify(pubkey, data, context, signature)
+}
+
+// id-Ed448 OBJECT IDENTIFIER ::= { 1 3 101 113 }
+pub const ED448_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.101.113");
+
+#[op2(fast)]
+pub fn op_crypto_import_spki_ed448(
+  #[buffer] key_data: &[u8],
+  #[buffer] out: &mut [u8],
+) -> bool {
+  // 2-3.
+  let pk_info = match spki::SubjectPublicKeyInfoRef::try_from(key_data) {
+    Ok(pk_info) => pk_info,
+    Err(_) => return false,
+  };
+  // 4.
+  let alg = pk_info.algorithm.oid;
+  if alg != ED448_OID {
+    return false;
+  }
+  // 5.
+  if pk_info.algorithm.parameters.is_some() {
+    return false;
+  }
+  let pubkey = pk_info.subject_public_key.raw_bytes();
+  if pubkey.len() != ED448_KEY_LEN {
+    return false;
+  }
+  out.copy_from_slice(pubkey);
+  true
+}
+
+#[op2(fast)]
+pub fn op_crypto_import_pkcs8_ed448(
+  #[buffer] key_data: &[u8],
+  #[buffer] out: &mut [u8],
+) -> bool {
+  // 2-3.
+  let pk_info = match PrivateKeyInfo::from_der(key_data) {
+    Ok(pk_info) => pk_info,
+    Err(_) => return false,
+  };
+  // 4.
+  let alg = pk_info.algorithm.oid;
+  if alg != ED448_OID {
+    return false;
+  }
+  // 5.
+  if pk_info.algorithm.parameters.is_some() {
+    return false;
+  }
+  // 6.
+  // CurvePrivateKey ::= OCTET STRING
+  if pk_info.private_key.len() != ED448_KEY_LEN + 2 {
+    return false;
+  }
+  out.copy_from_slice(&pk_info.private_key[2..]);
+  true
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_spki_ed448(
+  #[buffer] pubkey: &[u8],
+) -> Result<ToJsBuffer, Ed448Error> {
+  let key_info = spki::SubjectPublicKeyInfo {
+    algorithm: spki::AlgorithmIdentifierRef {
+      // id-Ed448
+      oid: ED448_OID,
+      parameters: None,
+    },
+    subject_public_key: BitString::from_bytes(pubkey)?,
+  };
+  Ok(
+    key_info
+      .to_der()
+      .map_err(|_| Ed448Error::FailedExport)?
+      .into(),
+  )
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_pkcs8_ed448(
+  #[buffer] pkey: &[u8],
+) -> Result<ToJsBuffer, Ed448Error> {
+  use rsa::pkcs1::der::Encode;
+
+  // CurvePrivateKey ::= OCTET STRING
+  let private_key = spki::der::asn1::OctetStringRef::new(pkey)?.to_der()?;
+  let pk_info = rsa::pkcs8::PrivateKeyInfo {
+    public_key: None,
+    algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+      // id-Ed448
+      oid: ED448_OID,
+      parameters: None,
+    },
+    private_key: &private_key,
+  };
+
+  let mut buf = Vec::new();
+  pk_info.encode_to_vec(&mut buf)?;
+  Ok(buf.into())
+}
+
+// 'x' from Section 2 of RFC 8037
+// https://www.rfc-editor.org/rfc/rfc8037#section-2
+#[op2]
+#[string]
+pub fn op_crypto_jwk_x_ed448(
+  #[buffer] pkey: &[u8],
+) -> Result<String, Ed448Error> {
+  let key = signing_key(pkey).ok_or(Ed448Error::InvalidKeyData)?;
+  Ok(BASE64_URL_SAFE_NO_PAD.encode(key.verifying_key().to_bytes()))
+}
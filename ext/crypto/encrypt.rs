@@ -62,6 +62,13 @@ pub enum EncryptAlgorithm {
     length: usize,
     tag_length: usize,
   },
+  #[serde(rename = "ChaCha20-Poly1305", rename_all = "camelCase")]
+  ChaCha20Poly1305 {
+    #[serde(with = "serde_bytes")]
+    iv: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    additional_data: Option<Vec<u8>>,
+  },
   #[serde(rename = "AES-CTR", rename_all = "camelCase")]
   AesCtr {
     #[serde(with = "serde_bytes")]
@@ -120,6 +127,10 @@ pub async fn op_crypto_encrypt(
       length,
       tag_length,
     } => encrypt_aes_gcm(key, length, tag_length, iv, additional_data, &data),
+    EncryptAlgorithm::ChaCha20Poly1305 {
+      iv,
+      additional_data,
+    } => encrypt_chacha20_poly1305(key, iv, additional_data, &data),
     EncryptAlgorithm::AesCtr {
       counter,
       ctr_length,
@@ -284,6 +295,35 @@ fn encrypt_aes_gcm(
   Ok(ciphertext)
 }
 
+fn encrypt_chacha20_poly1305(
+  key: V8RawKeyData,
+  iv: Vec<u8>,
+  additional_data: Option<Vec<u8>>,
+  data: &[u8],
+) -> Result<Vec<u8>, EncryptError> {
+  let key = key.as_secret_key()?;
+  let additional_data = additional_data.unwrap_or_default();
+  if iv.len() != 12 {
+    return Err(EncryptError::InvalidIvLength);
+  }
+
+  let cipher = chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+    .map_err(|_| EncryptError::InvalidKeyOrIv)?;
+  let mut ciphertext = data.to_vec();
+  let tag = cipher
+    .encrypt_in_place_detached(
+      chacha20poly1305::Nonce::from_slice(&iv),
+      &additional_data,
+      &mut ciphertext,
+    )
+    .map_err(|_| EncryptError::Failed)?;
+
+  // C | T
+  ciphertext.extend_from_slice(&tag);
+
+  Ok(ciphertext)
+}
+
 fn encrypt_aes_ctr_gen<B>(
   key: &[u8],
   counter: &[u8],
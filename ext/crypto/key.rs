@@ -90,6 +90,40 @@ impl From<CryptoHash> for &'static digest::Algorithm {
   }
 }
 
+/// Algorithms accepted by `crypto.subtle.digest()`. SHA-3 is only available
+/// as a digest, not as the hash of other algorithms.
+#[derive(Serialize, Deserialize, Copy, Clone)]
+pub enum DigestAlgorithm {
+  #[serde(rename = "SHA-1")]
+  Sha1,
+  #[serde(rename = "SHA-256")]
+  Sha256,
+  #[serde(rename = "SHA-384")]
+  Sha384,
+  #[serde(rename = "SHA-512")]
+  Sha512,
+  #[serde(rename = "SHA3-256")]
+  Sha3_256,
+  #[serde(rename = "SHA3-384")]
+  Sha3_384,
+  #[serde(rename = "SHA3-512")]
+  Sha3_512,
+}
+
+impl From<DigestAlgorithm> for &'static digest::Algorithm {
+  fn from(hash: DigestAlgorithm) -> &'static digest::Algorithm {
+    match hash {
+      DigestAlgorithm::Sha1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+      DigestAlgorithm::Sha256 => &digest::SHA256,
+      DigestAlgorithm::Sha384 => &digest::SHA384,
+      DigestAlgorithm::Sha512 => &digest::SHA512,
+      DigestAlgorithm::Sha3_256 => &digest::SHA3_256,
+      DigestAlgorithm::Sha3_384 => &digest::SHA3_384,
+      DigestAlgorithm::Sha3_512 => &digest::SHA3_512,
+    }
+  }
+}
+
 pub struct HkdfOutput<T>(pub T);
 
 impl hkdf::KeyType for HkdfOutput<usize> {
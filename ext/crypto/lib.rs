@@ -52,17 +52,21 @@ use signature::hazmat::PrehashVerifier; // Re-export rand
 
 mod decrypt;
 mod ed25519;
+mod ed448;
 mod encrypt;
 mod export_key;
 mod generate_key;
 mod import_key;
 mod key;
+mod ml_dsa;
+mod ml_kem;
 mod shared;
 mod x25519;
 mod x448;
 
 pub use crate::decrypt::DecryptError;
 pub use crate::decrypt::op_crypto_decrypt;
+pub use crate::ed448::Ed448Error;
 pub use crate::ed25519::Ed25519Error;
 pub use crate::encrypt::EncryptError;
 pub use crate::encrypt::op_crypto_encrypt;
@@ -75,12 +79,17 @@ pub use crate::import_key::op_crypto_import_key;
 use crate::key::Algorithm;
 use crate::key::CryptoHash;
 use crate::key::CryptoNamedCurve;
+use crate::key::DigestAlgorithm;
 use crate::key::HkdfOutput;
+pub use crate::ml_dsa::MlDsaError;
+pub use crate::ml_kem::MlKemError;
 pub use crate::shared::SharedError;
 use crate::shared::V8RawKeyData;
 pub use crate::x448::X448Error;
 pub use crate::x25519::X25519Error;
 
+pub const UNSTABLE_FEATURE_NAME: &str = "webcrypto";
+
 deno_core::extension!(deno_crypto,
   deps = [ deno_webidl, deno_web ],
   ops = [
@@ -120,6 +129,30 @@ deno_core::extension!(deno_crypto,
     ed25519::op_crypto_export_spki_ed25519,
     ed25519::op_crypto_export_pkcs8_ed25519,
     ed25519::op_crypto_jwk_x_ed25519,
+    ed448::op_crypto_generate_ed448_keypair,
+    ed448::op_crypto_import_spki_ed448,
+    ed448::op_crypto_import_pkcs8_ed448,
+    ed448::op_crypto_sign_ed448,
+    ed448::op_crypto_verify_ed448,
+    ed448::op_crypto_export_spki_ed448,
+    ed448::op_crypto_export_pkcs8_ed448,
+    ed448::op_crypto_jwk_x_ed448,
+    ml_kem::op_crypto_generate_ml_kem_keypair,
+    ml_kem::op_crypto_ml_kem_check_public_key,
+    ml_kem::op_crypto_ml_kem_encapsulate,
+    ml_kem::op_crypto_ml_kem_decapsulate,
+    ml_kem::op_crypto_import_spki_ml_kem,
+    ml_kem::op_crypto_import_pkcs8_ml_kem,
+    ml_kem::op_crypto_export_spki_ml_kem,
+    ml_kem::op_crypto_export_pkcs8_ml_kem,
+    ml_dsa::op_crypto_generate_ml_dsa_keypair,
+    ml_dsa::op_crypto_ml_dsa_check_public_key,
+    ml_dsa::op_crypto_sign_ml_dsa,
+    ml_dsa::op_crypto_verify_ml_dsa,
+    ml_dsa::op_crypto_import_spki_ml_dsa,
+    ml_dsa::op_crypto_import_pkcs8_ml_dsa,
+    ml_dsa::op_crypto_export_spki_ml_dsa,
+    ml_dsa::op_crypto_export_pkcs8_ml_dsa,
   ],
   esm = [ "00_crypto.js" ],
   options = {
@@ -758,7 +791,7 @@ pub fn op_crypto_random_uuid(
 #[op2(async)]
 #[serde]
 pub async fn op_crypto_subtle_digest(
-  #[serde] algorithm: CryptoHash,
+  #[serde] algorithm: DigestAlgorithm,
   #[buffer] data: JsBuffer,
 ) -> Result<ToJsBuffer, CryptoError> {
   let output = spawn_blocking(move || {
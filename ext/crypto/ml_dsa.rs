@@ -0,0 +1,252 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! ML-DSA (FIPS 204) as specified by the WICG "Modern Algorithms in the Web
+//! Cryptography API" proposal.
+//!
+//! Private keys are kept as the 32 byte `xi` seed, the signing key is expanded
+//! from it for every operation.
+
+use std::sync::Arc;
+
+use deno_core::OpState;
+use deno_core::ToJsBuffer;
+use deno_core::op2;
+use deno_features::FeatureChecker;
+use ml_dsa::B32;
+use ml_dsa::EncodedSignature;
+use ml_dsa::EncodedVerifyingKey;
+use ml_dsa::KeyGen;
+use ml_dsa::MlDsa44;
+use ml_dsa::MlDsa65;
+use ml_dsa::MlDsa87;
+use ml_dsa::MlDsaParams;
+use ml_dsa::Signature;
+use ml_dsa::VerifyingKey;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::Deserialize;
+
+use crate::UNSTABLE_FEATURE_NAME;
+use crate::shared::*;
+
+pub const ML_DSA_SEED_LEN: usize = 32;
+
+#[derive(Deserialize, Copy, Clone)]
+pub enum MlDsaAlgorithm {
+  #[serde(rename = "ML-DSA-44")]
+  MlDsa44,
+  #[serde(rename = "ML-DSA-65")]
+  MlDsa65,
+  #[serde(rename = "ML-DSA-87")]
+  MlDsa87,
+}
+
+impl MlDsaAlgorithm {
+  fn oid(self) -> const_oid::ObjectIdentifier {
+    match self {
+      // id-ml-dsa-44 OBJECT IDENTIFIER ::= { sigAlgs 17 }
+      Self::MlDsa44 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.17")
+      }
+      Self::MlDsa65 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18")
+      }
+      Self::MlDsa87 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.19")
+      }
+    }
+  }
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum MlDsaError {
+  #[class("DOMExceptionOperationError")]
+  #[error("Failed to export key")]
+  FailedExport,
+  #[class("DOMExceptionDataError")]
+  #[error("Invalid key data")]
+  InvalidKeyData,
+  #[class("DOMExceptionOperationError")]
+  #[error("Signing failed")]
+  Failed,
+}
+
+fn key_pair<P: MlDsaParams>(
+  seed: &[u8],
+) -> Result<ml_dsa::KeyPair<P>, MlDsaError> {
+  let seed = B32::try_from(seed).map_err(|_| MlDsaError::InvalidKeyData)?;
+  Ok(P::key_gen_internal(&seed))
+}
+
+fn verifying_key<P: MlDsaParams>(
+  pubkey: &[u8],
+) -> Result<VerifyingKey<P>, MlDsaError> {
+  let encoded = EncodedVerifyingKey::<P>::try_from(pubkey)
+    .map_err(|_| MlDsaError::InvalidKeyData)?;
+  Ok(VerifyingKey::decode(&encoded))
+}
+
+fn public_key<P: MlDsaParams>(seed: &[u8]) -> Result<Vec<u8>, MlDsaError> {
+  Ok(key_pair::<P>(seed)?.verifying_key().encode().to_vec())
+}
+
+fn sign<P: MlDsaParams>(
+  seed: &[u8],
+  data: &[u8],
+  context: &[u8],
+) -> Result<Vec<u8>, MlDsaError> {
+  let signature = key_pair::<P>(seed)?
+    .signing_key()
+    .sign_deterministic(data, context)
+    .map_err(|_| MlDsaError::Failed)?;
+  Ok(signature.encode().to_vec())
+}
+
+fn verify<P: MlDsaParams>(
+  pubkey: &[u8],
+  data: &[u8],
+  context: &[u8],
+  signature: &[u8],
+) -> bool {
+  let Ok(vk) = verifying_key::<P>(pubkey) else {
+    return false;
+  };
+  let Some(signature) = EncodedSignature::<P>::try_from(signature)
+    .ok()
+    .and_then(|sig| Signature::decode(&sig))
+  else {
+    return false;
+  };
+  vk.verify_with_context(data, context, &signature)
+}
+
+fn check_public_key(algorithm: MlDsaAlgorithm, pubkey: &[u8]) -> bool {
+  match algorithm {
+    MlDsaAlgorithm::MlDsa44 => verifying_key::<MlDsa44>(pubkey).is_ok(),
+    MlDsaAlgorithm::MlDsa65 => verifying_key::<MlDsa65>(pubkey).is_ok(),
+    MlDsaAlgorithm::MlDsa87 => verifying_key::<MlDsa87>(pubkey).is_ok(),
+  }
+}
+
+/// Fills `seed` with a new random seed and returns the verifying key.
+#[op2]
+#[serde]
+pub fn op_crypto_generate_ml_dsa_keypair(
+  state: &mut OpState,
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] seed: &mut [u8],
+) -> Result<ToJsBuffer, MlDsaError> {
+  state
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(UNSTABLE_FEATURE_NAME, "ML-DSA");
+  OsRng.fill_bytes(seed);
+  let pubkey = match algorithm {
+    MlDsaAlgorithm::MlDsa44 => public_key::<MlDsa44>(seed)?,
+    MlDsaAlgorithm::MlDsa65 => public_key::<MlDsa65>(seed)?,
+    MlDsaAlgorithm::MlDsa87 => public_key::<MlDsa87>(seed)?,
+  };
+  Ok(pubkey.into())
+}
+
+#[op2]
+pub fn op_crypto_ml_dsa_check_public_key(
+  state: &mut OpState,
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] pubkey: &[u8],
+) -> bool {
+  state
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(UNSTABLE_FEATURE_NAME, "ML-DSA");
+  check_public_key(algorithm, pubkey)
+}
+
+/// Signs `data` with the `context` string of FIPS 204 Section 5.2.
+#[op2]
+#[serde]
+pub fn op_crypto_sign_ml_dsa(
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] seed: &[u8],
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+) -> Result<ToJsBuffer, MlDsaError> {
+  let signature = match algorithm {
+    MlDsaAlgorithm::MlDsa44 => sign::<MlDsa44>(seed, data, context)?,
+    MlDsaAlgorithm::MlDsa65 => sign::<MlDsa65>(seed, data, context)?,
+    MlDsaAlgorithm::MlDsa87 => sign::<MlDsa87>(seed, data, context)?,
+  };
+  Ok(signature.into())
+}
+
+#[op2]
+pub fn op_crypto_verify_ml_dsa(
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] pubkey: &[u8],
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+  #[buffer] signature: &[u8],
+) -> bool {
+  match algorithm {
+    MlDsaAlgorithm::MlDsa44 => {
+      verify::<MlDsa44>(pubkey, data, context, signature)
+    }
+    MlDsaAlgorithm::MlDsa65 => {
+      verify::<MlDsa65>(pubkey, data, context, signature)
+    }
+    MlDsaAlgorithm::MlDsa87 => {
+      verify::<MlDsa87>(pubkey, data, context, signature)
+    }
+  }
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_import_spki_ml_dsa(
+  state: &mut OpState,
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] key_data: &[u8],
+) -> Option<ToJsBuffer> {
+  state
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(UNSTABLE_FEATURE_NAME, "ML-DSA");
+  let pubkey = import_spki_raw(key_data, algorithm.oid())?;
+  check_public_key(algorithm, &pubkey).then(|| pubkey.into())
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_import_pkcs8_ml_dsa(
+  state: &mut OpState,
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] key_data: &[u8],
+) -> Option<ToJsBuffer> {
+  state
+    .borrow::<Arc<FeatureChecker>>()
+    .check_or_exit(UNSTABLE_FEATURE_NAME, "ML-DSA");
+  import_pkcs8_seed(key_data, algorithm.oid(), ML_DSA_SEED_LEN).map(Into::into)
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_spki_ml_dsa(
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] pubkey: &[u8],
+) -> Result<ToJsBuffer, MlDsaError> {
+  Ok(
+    export_spki_raw(pubkey, algorithm.oid())
+      .map_err(|_| MlDsaError::FailedExport)?
+      .into(),
+  )
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_pkcs8_ml_dsa(
+  #[serde] algorithm: MlDsaAlgorithm,
+  #[buffer] seed: &[u8],
+) -> Result<ToJsBuffer, MlDsaError> {
+  Ok(
+    export_pkcs8_seed(seed, algorithm.oid())
+      .map_err(|_| MlDsaError::FailedExport)?
+      .into(),
+  )
+}
@@ -0,0 +1,229 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! ML-KEM (FIPS 203) as specified by the WICG "Modern Algorithms in the Web
+//! Cryptography API" proposal.
+//!
+//! Private keys are kept as the 64 byte `d || z` seed, the decapsulation key
+//! is expanded from it for every operation.
+
+use deno_core::ToJsBuffer;
+use deno_core::op2;
+use ml_kem::B32;
+use ml_kem::Ciphertext;
+use ml_kem::EncodedSizeUser;
+use ml_kem::KemCore;
+use ml_kem::MlKem512;
+use ml_kem::MlKem768;
+use ml_kem::MlKem1024;
+use ml_kem::kem::Decapsulate;
+use ml_kem::kem::Encapsulate;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::Deserialize;
+
+use crate::shared::*;
+
+pub const ML_KEM_SEED_LEN: usize = 64;
+
+#[derive(Deserialize, Copy, Clone)]
+pub enum MlKemAlgorithm {
+  #[serde(rename = "ML-KEM-512")]
+  MlKem512,
+  #[serde(rename = "ML-KEM-768")]
+  MlKem768,
+  #[serde(rename = "ML-KEM-1024")]
+  MlKem1024,
+}
+
+impl MlKemAlgorithm {
+  fn oid(self) -> const_oid::ObjectIdentifier {
+    match self {
+      // id-alg-ml-kem-512 OBJECT IDENTIFIER ::= { nistAlgorithm kems(4) 1 }
+      Self::MlKem512 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.1")
+      }
+      Self::MlKem768 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2")
+      }
+      Self::MlKem1024 => {
+        const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.3")
+      }
+    }
+  }
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum MlKemError {
+  #[class("DOMExceptionOperationError")]
+  #[error("Failed to export key")]
+  FailedExport,
+  #[class("DOMExceptionDataError")]
+  #[error("Invalid key data")]
+  InvalidKeyData,
+  #[class("DOMExceptionOperationError")]
+  #[error("Invalid ciphertext")]
+  InvalidCiphertext,
+  #[class("DOMExceptionOperationError")]
+  #[error("Encapsulation failed")]
+  Failed,
+}
+
+fn keypair_from_seed<K: KemCore>(
+  seed: &[u8],
+) -> Result<(K::DecapsulationKey, K::EncapsulationKey), MlKemError> {
+  if seed.len() != ML_KEM_SEED_LEN {
+    return Err(MlKemError::InvalidKeyData);
+  }
+  let (d, z) = seed.split_at(32);
+  let d = B32::try_from(d).map_err(|_| MlKemError::InvalidKeyData)?;
+  let z = B32::try_from(z).map_err(|_| MlKemError::InvalidKeyData)?;
+  Ok(K::generate_deterministic(&d, &z))
+}
+
+/// Decodes an encapsulation key, applying the modulus check of FIPS 203
+/// Section 7.2 by re-encoding it.
+fn encapsulation_key<K: KemCore>(
+  pubkey: &[u8],
+) -> Result<K::EncapsulationKey, MlKemError> {
+  let encoded = ml_kem::Encoded::<K::EncapsulationKey>::try_from(pubkey)
+    .map_err(|_| MlKemError::InvalidKeyData)?;
+  let key = K::EncapsulationKey::from_bytes(&encoded);
+  if key.as_bytes() != encoded {
+    return Err(MlKemError::InvalidKeyData);
+  }
+  Ok(key)
+}
+
+fn public_key<K: KemCore>(seed: &[u8]) -> Result<Vec<u8>, MlKemError> {
+  let (_, ek) = keypair_from_seed::<K>(seed)?;
+  Ok(ek.as_bytes().to_vec())
+}
+
+fn encapsulate<K: KemCore>(
+  pubkey: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), MlKemError> {
+  let ek = encapsulation_key::<K>(pubkey)?;
+  let (ciphertext, shared_key) =
+    ek.encapsulate(&mut OsRng).map_err(|_| MlKemError::Failed)?;
+  Ok((ciphertext.to_vec(), shared_key.to_vec()))
+}
+
+fn decapsulate<K: KemCore>(
+  seed: &[u8],
+  ciphertext: &[u8],
+) -> Result<Vec<u8>, MlKemError> {
+  let (dk, _) = keypair_from_seed::<K>(seed)?;
+  let ciphertext = Ciphertext::<K>::try_from(ciphertext)
+    .map_err(|_| MlKemError::InvalidCiphertext)?;
+  let shared_key = dk
+    .decapsulate(&ciphertext)
+    .map_err(|_| MlKemError::InvalidCiphertext)?;
+  Ok(shared_key.to_vec())
+}
+
+fn check_public_key(algorithm: MlKemAlgorithm, pubkey: &[u8]) -> bool {
+  match algorithm {
+    MlKemAlgorithm::MlKem512 => encapsulation_key::<MlKem512>(pubkey).is_ok(),
+    MlKemAlgorithm::MlKem768 => encapsulation_key::<MlKem768>(pubkey).is_ok(),
+    MlKemAlgorithm::MlKem1024 => encapsulation_key::<MlKem1024>(pubkey).is_ok(),
+  }
+}
+
+/// Fills `seed` with a new random seed and returns the encapsulation key.
+#[op2]
+#[serde]
+pub fn op_crypto_generate_ml_kem_keypair(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] seed: &mut [u8],
+) -> Result<ToJsBuffer, MlKemError> {
+  OsRng.fill_bytes(seed);
+  let pubkey = match algorithm {
+    MlKemAlgorithm::MlKem512 => public_key::<MlKem512>(seed)?,
+    MlKemAlgorithm::MlKem768 => public_key::<MlKem768>(seed)?,
+    MlKemAlgorithm::MlKem1024 => public_key::<MlKem1024>(seed)?,
+  };
+  Ok(pubkey.into())
+}
+
+#[op2]
+pub fn op_crypto_ml_kem_check_public_key(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] pubkey: &[u8],
+) -> bool {
+  check_public_key(algorithm, pubkey)
+}
+
+/// Returns `[ciphertext, sharedKey]`.
+#[op2]
+#[serde]
+pub fn op_crypto_ml_kem_encapsulate(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] pubkey: &[u8],
+) -> Result<(ToJsBuffer, ToJsBuffer), MlKemError> {
+  let (ciphertext, shared_key) = match algorithm {
+    MlKemAlgorithm::MlKem512 => encapsulate::<MlKem512>(pubkey)?,
+    MlKemAlgorithm::MlKem768 => encapsulate::<MlKem768>(pubkey)?,
+    MlKemAlgorithm::MlKem1024 => encapsulate::<MlKem1024>(pubkey)?,
+  };
+  Ok((ciphertext.into(), shared_key.into()))
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_ml_kem_decapsulate(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] seed: &[u8],
+  #[buffer] ciphertext: &[u8],
+) -> Result<ToJsBuffer, MlKemError> {
+  let shared_key = match algorithm {
+    MlKemAlgorithm::MlKem512 => decapsulate::<MlKem512>(seed, ciphertext)?,
+    MlKemAlgorithm::MlKem768 => decapsulate::<MlKem768>(seed, ciphertext)?,
+    MlKemAlgorithm::MlKem1024 => decapsulate::<MlKem1024>(seed, ciphertext)?,
+  };
+  Ok(shared_key.into())
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_import_spki_ml_kem(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] key_data: &[u8],
+) -> Option<ToJsBuffer> {
+  let pubkey = import_spki_raw(key_data, algorithm.oid())?;
+  check_public_key(algorithm, &pubkey).then(|| pubkey.into())
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_import_pkcs8_ml_kem(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] key_data: &[u8],
+) -> Option<ToJsBuffer> {
+  import_pkcs8_seed(key_data, algorithm.oid(), ML_KEM_SEED_LEN).map(Into::into)
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_spki_ml_kem(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] pubkey: &[u8],
+) -> Result<ToJsBuffer, MlKemError> {
+  Ok(
+    export_spki_raw(pubkey, algorithm.oid())
+      .map_err(|_| MlKemError::FailedExport)?
+      .into(),
+  )
+}
+
+#[op2]
+#[serde]
+pub fn op_crypto_export_pkcs8_ml_kem(
+  #[serde] algorithm: MlKemAlgorithm,
+  #[buffer] seed: &[u8],
+) -> Result<ToJsBuffer, MlKemError> {
+  Ok(
+    export_pkcs8_seed(seed, algorithm.oid())
+      .map_err(|_| MlKemError::FailedExport)?
+      .into(),
+  )
+}
@@ -187,3 +187,77 @@ impl V8RawKeyData {
     }
   }
 }
+
+/// Returns the raw public key of a SubjectPublicKeyInfo for `oid`, which
+/// must not have parameters.
+pub fn import_spki_raw(
+  key_data: &[u8],
+  oid: const_oid::ObjectIdentifier,
+) -> Option<Vec<u8>> {
+  let pk_info = spki::SubjectPublicKeyInfoRef::try_from(key_data).ok()?;
+  if pk_info.algorithm.oid != oid || pk_info.algorithm.parameters.is_some() {
+    return None;
+  }
+  Some(pk_info.subject_public_key.raw_bytes().to_vec())
+}
+
+pub fn export_spki_raw(
+  pubkey: &[u8],
+  oid: const_oid::ObjectIdentifier,
+) -> Result<Vec<u8>, spki::der::Error> {
+  use spki::der::Encode;
+
+  spki::SubjectPublicKeyInfo {
+    algorithm: spki::AlgorithmIdentifierRef {
+      oid,
+      parameters: None,
+    },
+    subject_public_key: spki::der::asn1::BitString::from_bytes(pubkey)?,
+  }
+  .to_der()
+}
+
+/// Returns the seed of a PKCS#8 private key for `oid` whose privateKey is the
+/// `seed [0] IMPLICIT OCTET STRING` choice used by ML-KEM and ML-DSA.
+pub fn import_pkcs8_seed(
+  key_data: &[u8],
+  oid: const_oid::ObjectIdentifier,
+  seed_len: usize,
+) -> Option<Vec<u8>> {
+  use spki::der::Decode;
+
+  let pk_info = rsa::pkcs8::PrivateKeyInfo::from_der(key_data).ok()?;
+  if pk_info.algorithm.oid != oid || pk_info.algorithm.parameters.is_some() {
+    return None;
+  }
+  match pk_info.private_key {
+    [0x80, len, seed @ ..]
+      if *len as usize == seed_len && seed.len() == seed_len =>
+    {
+      Some(seed.to_vec())
+    }
+    _ => None,
+  }
+}
+
+pub fn export_pkcs8_seed(
+  seed: &[u8],
+  oid: const_oid::ObjectIdentifier,
+) -> Result<Vec<u8>, spki::der::Error> {
+  use rsa::pkcs1::der::Encode;
+
+  let mut private_key = vec![0x80, seed.len() as u8];
+  private_key.extend_from_slice(seed);
+  let pk_info = rsa::pkcs8::PrivateKeyInfo {
+    public_key: None,
+    algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+      oid,
+      parameters: None,
+    },
+    private_key: &private_key,
+  };
+
+  let mut buf = Vec::new();
+  pk_info.encode_to_vec(&mut buf)?;
+  Ok(buf)
+}
@@ -20,6 +20,7 @@ import {
   op_http_serve,
   op_http_serve_address_override,
   op_http_serve_on,
+  op_http_serve_worker_listening,
   op_http_set_promise_complete,
   op_http_set_response_body_bytes,
   op_http_set_response_body_resource,
@@ -36,6 +37,8 @@ const {
   ArrayPrototypeFind,
   ArrayPrototypeMap,
   ArrayPrototypePush,
+  NumberIsInteger,
+  NumberMAX_SAFE_INTEGER,
   ObjectHasOwn,
   ObjectPrototypeIsPrototypeOf,
   PromisePrototypeCatch,
@@ -43,6 +46,7 @@ const {
   SafePromisePrototypeFinally,
   SafePromiseAll,
   PromisePrototypeThen,
+  RangeError,
   StringPrototypeIncludes,
   StringPrototypeSlice,
   StringPrototypeStartsWith,
@@ -93,7 +97,11 @@ import {
   listenOptionApiName,
   UpgradedConn,
 } from "ext:deno_net/01_net.js";
-import { hasTlsKeyPairOptions, listenTls } from "ext:deno_net/02_tls.js";
+import {
+  hasTlsKeyPairOptions,
+  listenTls,
+  tlsKeysSymbol,
+} from "ext:deno_net/02_tls.js";
 import { SymbolAsyncDispose } from "ext:deno_web/00_infra.js";
 import {
   builtinTracer,
@@ -724,9 +732,62 @@ type RawServeOptions = {
   onError?: (error: unknown) => Response | Promise<Response>;
   onListen?: (params: { hostname: string; port: number }) => void;
   handler?: RawHandler;
+  compression?: RawCompressionOptions;
+};
+
+type RawCompressionOptions = {
+  minSize?: number;
+  gzipLevel?: number;
+  brotliQuality?: number;
+  zstdLevel?: number;
 };
 
+const COMPRESSION_OPTION_RANGES = [
+  ["minSize", 0, NumberMAX_SAFE_INTEGER],
+  ["gzipLevel", 0, 9],
+  ["brotliQuality", 0, 11],
+  ["zstdLevel", 1, 22],
+] as const;
+
+/**
+ * Validates the `compression` option, leaving out unset fields so the
+ * server uses its defaults for them.
+ */
+function validateCompressionOptions(
+  compression: RawCompressionOptions | undefined,
+): RawCompressionOptions | undefined {
+  if (compression === undefined) {
+    return undefined;
+  }
+  if (compression === null || typeof compression !== "object") {
+    throw new TypeError("Invalid type for compression: must be an object");
+  }
+  const validated = { __proto__: null };
+  for (let i = 0; i < COMPRESSION_OPTION_RANGES.length; i++) {
+    const { 0: name, 1: min, 2: max } = COMPRESSION_OPTION_RANGES[i];
+    const value = compression[name];
+    if (value === undefined) {
+      continue;
+    }
+    if (typeof value !== "number" || !NumberIsInteger(value)) {
+      throw new TypeError(
+        `Invalid type for compression.${name}: must be an integer`,
+      );
+    }
+    if (value < min || value > max) {
+      throw new RangeError(
+        `Invalid value for compression.${name}: must be between ${min} and ${max}, received ${value}`,
+      );
+    }
+    validated[name] = value;
+  }
+  return validated;
+}
+
 const kLoadBalanced = Symbol("kLoadBalanced");
+// Serves on a listener that the caller has already bound, used by the
+// `node:cluster` workers to listen on a shared port.
+const kListener = Symbol("kListener");
 
 function formatHostName(hostname: string): string {
   // If the hostname is "0.0.0.0", we display "localhost" in console
@@ -870,12 +931,29 @@ function serveInner(options, handler) {
   const wantsVsock = ObjectHasOwn(options, "cid");
   const wantsTunnel = options.tunnel === true;
   const signal = options.signal;
+  const compression = validateCompressionOptions(options.compression);
   const onError = options.onError ??
     function (error) {
       import.meta.log("error", error);
       return internalServerError();
     };
 
+  if (options[kListener] !== undefined) {
+    const listener = options[kListener];
+    return serveHttpOnListener(
+      listener,
+      signal,
+      handler,
+      onError,
+      compression,
+      () => {
+        if (options.onListen) {
+          options.onListen(listener.addr);
+        }
+      },
+    );
+  }
+
   if (wantsUnix) {
     const listener = listen({
       transport: "unix",
@@ -883,13 +961,20 @@ function serveInner(options, handler) {
       [listenOptionApiName]: "Deno.serve",
     });
     const path = listener.addr.path;
-    return serveHttpOnListener(listener, signal, handler, onError, () => {
-      if (options.onListen) {
-        options.onListen(listener.addr);
-      } else {
-        import.meta.log("info", `Listening on ${path}`);
-      }
-    });
+    return serveHttpOnListener(
+      listener,
+      signal,
+      handler,
+      onError,
+      compression,
+      () => {
+        if (options.onListen) {
+          options.onListen(listener.addr);
+        } else {
+          import.meta.log("info", `Listening on ${path}`);
+        }
+      },
+    );
   }
 
   if (wantsVsock) {
@@ -900,13 +985,20 @@ function serveInner(options, handler) {
       [listenOptionApiName]: "Deno.serve",
     });
     const { cid, port } = listener.addr;
-    return serveHttpOnListener(listener, signal, handler, onError, () => {
-      if (options.onListen) {
-        options.onListen(listener.addr);
-      } else {
-        import.meta.log("info", `Listening on vsock:${cid}:${port}`);
-      }
-    });
+    return serveHttpOnListener(
+      listener,
+      signal,
+      handler,
+      onError,
+      compression,
+      () => {
+        if (options.onListen) {
+          options.onListen(listener.addr);
+        } else {
+          import.meta.log("info", `Listening on vsock:${cid}:${port}`);
+        }
+      },
+    );
   }
 
   if (wantsTunnel) {
@@ -914,21 +1006,28 @@ function serveInner(options, handler) {
       transport: "tunnel",
       [listenOptionApiName]: "Deno.serve",
     });
-    return serveHttpOnListener(listener, signal, handler, onError, () => {
-      if (options.onListen) {
-        options.onListen(listener.addr);
-      } else {
-        const additional = listener.addr.port === 443
-          ? ""
-          : `:${listener.addr.port}`;
-        import.meta.log(
-          "info",
-          `Listening on https://${
-            formatHostName(listener.addr.hostname)
-          }${additional}`,
-        );
-      }
-    });
+    return serveHttpOnListener(
+      listener,
+      signal,
+      handler,
+      onError,
+      compression,
+      () => {
+        if (options.onListen) {
+          options.onListen(listener.addr);
+        } else {
+          const additional = listener.addr.port === 443
+            ? ""
+            : `:${listener.addr.port}`;
+          import.meta.log(
+            "info",
+            `Listening on https://${
+              formatHostName(listener.addr.hostname)
+            }${additional}`,
+          );
+        }
+      },
+    );
   }
 
   const listenOpts = {
@@ -952,13 +1051,16 @@ function serveInner(options, handler) {
 
   let listener;
   if (wantsHttps) {
-    if (!options.cert || !options.key) {
+    if (options[tlsKeysSymbol] !== undefined) {
+      listenOpts[tlsKeysSymbol] = options[tlsKeysSymbol];
+    } else if (!options.cert || !options.key) {
       throw new TypeError(
         "Both 'cert' and 'key' must be provided to enable HTTPS",
       );
+    } else {
+      listenOpts.cert = options.cert;
+      listenOpts.key = options.key;
     }
-    listenOpts.cert = options.cert;
-    listenOpts.key = options.key;
     listenOpts.alpnProtocols = ["h2", "http/1.1"];
     listener = listenTls(listenOpts);
     listenOpts.port = listener.addr.port;
@@ -984,16 +1086,30 @@ function serveInner(options, handler) {
     }
   };
 
-  return serveHttpOnListener(listener, signal, handler, onError, onListen);
+  return serveHttpOnListener(
+    listener,
+    signal,
+    handler,
+    onError,
+    compression,
+    onListen,
+  );
 }
 
 /**
  * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary listener.
  */
-function serveHttpOnListener(listener, signal, handler, onError, onListen) {
+function serveHttpOnListener(
+  listener,
+  signal,
+  handler,
+  onError,
+  compression,
+  onListen,
+) {
   const context = new CallbackContext(
     signal,
-    op_http_serve(listener[internalRidSymbol]),
+    op_http_serve(listener[internalRidSymbol], compression),
     listener,
   );
   const callback = mapToCallback(context, handler, onError);
@@ -1153,11 +1269,13 @@ function registerDeclarativeServer(exports) {
   return ({
     servePort,
     serveHost,
+    serveTlsKeys,
     workerCountWhenMain,
   }) => {
-    Deno.serve({
+    const server = Deno.serve({
       port: servePort,
       hostname: serveHost,
+      [tlsKeysSymbol]: serveTlsKeys,
       [kLoadBalanced]: workerCountWhenMain == null
         ? true
         : workerCountWhenMain > 0,
@@ -1171,7 +1289,7 @@ function registerDeclarativeServer(exports) {
           let target;
           switch (localAddr.transport) {
             case "tcp":
-              target = `http://${
+              target = `${serveTlsKeys ? "https" : "http"}://${
                 formatHostName(localAddr.hostname)
               }:${localAddr.port}/`;
               break;
@@ -1201,11 +1319,19 @@ function registerDeclarativeServer(exports) {
         return exports.fetch(req, connInfo);
       },
     });
+
+    if (workerCountWhenMain == null) {
+      // Let the supervisor of `deno serve --parallel` restart this worker
+      const shutdownRequested = op_http_serve_worker_listening();
+      core.unrefOpPromise(shutdownRequested);
+      PromisePrototypeThen(shutdownRequested, () => server.shutdown());
+    }
   };
 }
 
 export {
   addTrailers,
+  kListener,
   registerDeclarativeServer,
   serve,
   serveHttpOnConnection,
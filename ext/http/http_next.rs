@@ -68,6 +68,7 @@ use crate::request_properties::HttpConnectionProperties;
 use crate::request_properties::HttpListenProperties;
 use crate::request_properties::HttpPropertyExtractor;
 use crate::response_body::Compression;
+use crate::response_body::CompressionOptions;
 use crate::response_body::ResponseBytesInner;
 use crate::service::HttpRecord;
 use crate::service::HttpRecordResponse;
@@ -609,25 +610,28 @@ pub fn op_http_set_response_trailers(
 fn is_request_compressible(
   length: Option<usize>,
   headers: &HeaderMap,
+  options: &CompressionOptions,
 ) -> Compression {
-  if let Some(length) = length {
-    // By the time we add compression headers and Accept-Encoding, it probably doesn't make sense
-    // to compress stuff that's smaller than this.
-    if length < 64 {
-      return Compression::None;
-    }
+  if let Some(length) = length
+    && length < options.min_size
+  {
+    return Compression::None;
   }
 
   let Some(accept_encoding) = headers.get(ACCEPT_ENCODING) else {
     return Compression::None;
   };
 
+  let brotli = Compression::Brotli(options.brotli_quality);
+  let gzip = Compression::GZip(options.gzip_level);
+  let zstd = Compression::Zstd(options.zstd_level);
   match accept_encoding.to_str() {
     // Firefox and Chrome send this -- no need to parse
-    Ok("gzip, deflate, br") => return Compression::Brotli,
-    Ok("gzip, deflate, br, zstd") => return Compression::Brotli,
-    Ok("gzip") => return Compression::GZip,
-    Ok("br") => return Compression::Brotli,
+    Ok("gzip, deflate, br") => return brotli,
+    Ok("gzip, deflate, br, zstd") => return zstd,
+    Ok("gzip") => return gzip,
+    Ok("br") => return brotli,
+    Ok("zstd") => return zstd,
     _ => (),
   }
 
@@ -637,14 +641,20 @@ fn is_request_compressible(
       matches!(
         r,
         Ok((
-          Some(Encoding::Identity | Encoding::Gzip | Encoding::Brotli),
+          Some(
+            Encoding::Identity
+              | Encoding::Gzip
+              | Encoding::Brotli
+              | Encoding::Zstd
+          ),
           _
         ))
       )
     });
   match fly_accept_encoding::preferred(accepted) {
-    Ok(Some(fly_accept_encoding::Encoding::Gzip)) => Compression::GZip,
-    Ok(Some(fly_accept_encoding::Encoding::Brotli)) => Compression::Brotli,
+    Ok(Some(fly_accept_encoding::Encoding::Gzip)) => gzip,
+    Ok(Some(fly_accept_encoding::Encoding::Brotli)) => brotli,
+    Ok(Some(fly_accept_encoding::Encoding::Zstd)) => zstd,
     _ => Compression::None,
   }
 }
@@ -685,9 +695,10 @@ fn modify_compressibility_from_response(
     return Compression::None;
   }
   let encoding = match compression {
-    Compression::Brotli => "br",
-    Compression::GZip => "gzip",
-    _ => unreachable!(),
+    Compression::Brotli(..) => "br",
+    Compression::GZip(..) => "gzip",
+    Compression::Zstd(..) => "zstd",
+    Compression::None => unreachable!(),
   };
   weaken_etag(headers);
   headers.remove(CONTENT_LENGTH);
@@ -739,8 +750,11 @@ fn set_response(
   // The request may have been cancelled by this point and if so, there's no need for us to
   // do all of this work to send the response.
   if !http.cancelled() {
-    let compression =
-      is_request_compressible(length, &http.request_parts().headers);
+    let compression = is_request_compressible(
+      length,
+      &http.request_parts().headers,
+      &http.compression_options(),
+    );
     let mut response_headers =
       std::cell::RefMut::map(http.response_parts(), |this| &mut this.headers);
     let compression =
@@ -1086,13 +1100,16 @@ struct HttpJoinHandle {
 }
 
 impl HttpJoinHandle {
-  fn new(rx: tokio::sync::mpsc::Receiver<Rc<HttpRecord>>) -> Self {
+  fn new(
+    rx: tokio::sync::mpsc::Receiver<Rc<HttpRecord>>,
+    compression: CompressionOptions,
+  ) -> Self {
     Self {
       join_handle: AsyncRefCell::new(None),
       connection_cancel_handle: CancelHandle::new_rc(),
       listen_cancel_handle: CancelHandle::new_rc(),
       rx: AsyncRefCell::new(rx),
-      server_state: HttpServerState::new(),
+      server_state: HttpServerState::new(compression),
     }
   }
 
@@ -1138,6 +1155,7 @@ impl Drop for HttpJoinHandle {
 pub fn op_http_serve<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] listener_rid: ResourceId,
+  #[serde] compression: Option<CompressionOptions>,
 ) -> Result<(ResourceId, &'static str, String, bool), HttpNextError>
 where
   HTTP: HttpPropertyExtractor,
@@ -1148,7 +1166,8 @@ where
   let listen_properties = HTTP::listen_properties_from_listener(&listener)?;
 
   let (tx, rx) = tokio::sync::mpsc::channel(10);
-  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  let resource: Rc<HttpJoinHandle> =
+    Rc::new(HttpJoinHandle::new(rx, compression.unwrap_or_default()));
   let listen_cancel_clone = resource.listen_cancel_handle();
 
   let lifetime = resource.lifetime();
@@ -1194,6 +1213,7 @@ where
 pub fn op_http_serve_on<HTTP>(
   state: Rc<RefCell<OpState>>,
   #[smi] connection_rid: ResourceId,
+  #[serde] compression: Option<CompressionOptions>,
 ) -> Result<(ResourceId, &'static str, String, bool), HttpNextError>
 where
   HTTP: HttpPropertyExtractor,
@@ -1204,7 +1224,8 @@ where
   let listen_properties = HTTP::listen_properties_from_connection(&connection)?;
 
   let (tx, rx) = tokio::sync::mpsc::channel(10);
-  let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle::new(rx));
+  let resource: Rc<HttpJoinHandle> =
+    Rc::new(HttpJoinHandle::new(rx, compression.unwrap_or_default()));
 
   let options = {
     let state = state.borrow();
@@ -156,6 +156,7 @@ deno_core::extension!(
     op_http_write_headers,
     op_http_write_resource,
     op_http_write,
+    op_http_serve_worker_listening,
     http_next::op_http_close_after_finish,
     http_next::op_http_get_request_header,
     http_next::op_http_get_request_headers,
@@ -206,6 +207,7 @@ deno_core::extension!(
     op_http_write_resource,
     op_http_write,
     op_http_notify_serving,
+    op_http_serve_worker_listening,
     http_next::op_http_close_after_finish,
     http_next::op_http_get_request_header,
     http_next::op_http_get_request_headers,
@@ -1804,6 +1806,42 @@ fn op_http_notify_serving() {
   }
 }
 
+/// Lets `deno serve` know when the server of a worker is listening and ask it
+/// to shut down gracefully, so that the worker can be restarted.
+#[derive(Clone, Default)]
+pub struct ServeWorkerHandle(Arc<ServeWorkerHandleInner>);
+
+#[derive(Default)]
+struct ServeWorkerHandleInner {
+  listening: Notify,
+  shutdown: Notify,
+}
+
+impl ServeWorkerHandle {
+  /// Resolves once the server of the worker is listening.
+  pub async fn listening(&self) {
+    self.0.listening.notified().await;
+  }
+
+  /// Stops the server of the worker from accepting new connections. The
+  /// worker exits once the pending requests are handled.
+  pub fn shutdown(&self) {
+    self.0.shutdown.notify_one();
+  }
+}
+
+/// Called once the declarative server of a `deno serve` worker is listening,
+/// resolving when the worker is asked to shut down.
+#[op2(async)]
+async fn op_http_serve_worker_listening(state: Rc<RefCell<OpState>>) {
+  let handle = state.borrow().try_borrow::<ServeWorkerHandle>().cloned();
+  let Some(handle) = handle else {
+    return pending().await;
+  };
+  handle.0.listening.notify_one();
+  handle.0.shutdown.notified().await;
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -19,6 +19,7 @@ use flate2::write::GzEncoder;
 use hyper::body::Frame;
 use hyper::body::SizeHint;
 use pin_project::pin_project;
+use serde::Deserialize;
 
 /// Simplification for nested types we use for our streams. We provide a way to convert from
 /// this type into Hyper's body [`Frame`].
@@ -56,11 +57,43 @@ pub trait PollFrame: Unpin {
   fn size_hint(&self) -> SizeHint;
 }
 
-#[derive(PartialEq, Eq)]
+/// The encoding of a response body, along with the level to compress it at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Compression {
   None,
-  GZip,
-  Brotli,
+  GZip(u32),
+  Brotli(u32),
+  Zstd(i32),
+}
+
+/// Compression settings of a server, from the `compression` option of
+/// `Deno.serve`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CompressionOptions {
+  /// Responses with a known length below this many bytes are sent
+  /// uncompressed.
+  pub min_size: usize,
+  pub gzip_level: u32,
+  pub brotli_quality: u32,
+  pub zstd_level: i32,
+}
+
+impl Default for CompressionOptions {
+  fn default() -> Self {
+    Self {
+      // By the time we add compression headers and Accept-Encoding, it
+      // probably doesn't make sense to compress anything smaller than this.
+      min_size: 64,
+      // See the comment on `GZIP_HEADER`.
+      gzip_level: 1,
+      // Quality level 6 is based on google's nginx default value for
+      // on-the-fly compression
+      // https://github.com/google/ngx_brotli#brotli_comp_level
+      brotli_quality: 6,
+      zstd_level: zstd::DEFAULT_COMPRESSION_LEVEL,
+    }
+  }
 }
 
 pub enum ResponseStream {
@@ -95,6 +128,8 @@ pub enum ResponseBytesInner {
   GZipStream(Box<GZipResponseStream>),
   /// A Brotli stream.
   BrotliStream(Box<BrotliResponseStream>),
+  /// A Zstandard stream.
+  ZstdStream(Box<ZstdResponseStream>),
 }
 
 impl std::fmt::Debug for ResponseBytesInner {
@@ -106,6 +141,7 @@ impl std::fmt::Debug for ResponseBytesInner {
       Self::UncompressedStream(..) => f.write_str("Uncompressed"),
       Self::GZipStream(..) => f.write_str("GZip"),
       Self::BrotliStream(..) => f.write_str("Brotli"),
+      Self::ZstdStream(..) => f.write_str("Zstd"),
     }
   }
 }
@@ -116,6 +152,7 @@ impl ResponseBytesInner {
       Self::Done | Self::Empty | Self::Bytes(..) => {}
       Self::BrotliStream(stm) => stm.abort(),
       Self::GZipStream(stm) => stm.abort(),
+      Self::ZstdStream(stm) => stm.abort(),
       Self::UncompressedStream(stm) => stm.abort(),
     }
   }
@@ -128,18 +165,22 @@ impl ResponseBytesInner {
       Self::UncompressedStream(res) => res.size_hint(),
       Self::GZipStream(..) => SizeHint::default(),
       Self::BrotliStream(..) => SizeHint::default(),
+      Self::ZstdStream(..) => SizeHint::default(),
     }
   }
 
   fn from_stream(compression: Compression, stream: ResponseStream) -> Self {
     match compression {
-      Compression::GZip => {
-        Self::GZipStream(Box::new(GZipResponseStream::new(stream)))
+      Compression::GZip(level) => {
+        Self::GZipStream(Box::new(GZipResponseStream::new(stream, level)))
       }
-      Compression::Brotli => {
-        Self::BrotliStream(Box::new(BrotliResponseStream::new(stream)))
+      Compression::Brotli(quality) => {
+        Self::BrotliStream(Box::new(BrotliResponseStream::new(stream, quality)))
       }
-      _ => Self::UncompressedStream(stream),
+      Compression::Zstd(level) => {
+        Self::ZstdStream(Box::new(ZstdResponseStream::new(stream, level)))
+      }
+      Compression::None => Self::UncompressedStream(stream),
     }
   }
 
@@ -156,44 +197,15 @@ impl ResponseBytesInner {
 
   pub fn from_bufview(compression: Compression, buf: BufView) -> Self {
     match compression {
-      Compression::GZip => {
-        let mut writer =
-          GzEncoder::new(Vec::new(), flate2::Compression::fast());
-        writer.write_all(&buf).unwrap();
-        Self::Bytes(BufView::from(writer.finish().unwrap()))
-      }
-      Compression::Brotli => {
-        // quality level 6 is based on google's nginx default value for
-        // on-the-fly compression
-        // https://github.com/google/ngx_brotli#brotli_comp_level
-        // lgwin 22 is equivalent to brotli window size of (2**22)-16 bytes
-        // (~4MB)
-        let mut writer =
-          brotli::CompressorWriter::new(Vec::new(), 65 * 1024, 6, 22);
-        writer.write_all(&buf).unwrap();
-        writer.flush().unwrap();
-        Self::Bytes(BufView::from(writer.into_inner()))
-      }
-      _ => Self::Bytes(buf),
+      Compression::None => Self::Bytes(buf),
+      compression => Self::Bytes(BufView::from(compress(compression, &buf))),
     }
   }
 
   pub fn from_vec(compression: Compression, vec: Vec<u8>) -> Self {
     match compression {
-      Compression::GZip => {
-        let mut writer =
-          GzEncoder::new(Vec::new(), flate2::Compression::fast());
-        writer.write_all(&vec).unwrap();
-        Self::Bytes(BufView::from(writer.finish().unwrap()))
-      }
-      Compression::Brotli => {
-        let mut writer =
-          brotli::CompressorWriter::new(Vec::new(), 65 * 1024, 6, 22);
-        writer.write_all(&vec).unwrap();
-        writer.flush().unwrap();
-        Self::Bytes(BufView::from(writer.into_inner()))
-      }
-      _ => Self::Bytes(BufView::from(vec)),
+      Compression::None => Self::Bytes(BufView::from(vec)),
+      compression => Self::Bytes(BufView::from(compress(compression, &vec))),
     }
   }
 
@@ -203,6 +215,29 @@ impl ResponseBytesInner {
   }
 }
 
+/// Compresses a complete response body in one go.
+fn compress(compression: Compression, buf: &[u8]) -> Vec<u8> {
+  match compression {
+    Compression::None => buf.to_vec(),
+    Compression::GZip(level) => {
+      let mut writer =
+        GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+      writer.write_all(buf).unwrap();
+      writer.finish().unwrap()
+    }
+    Compression::Brotli(quality) => {
+      // lgwin 22 is equivalent to brotli window size of (2**22)-16 bytes
+      // (~4MB)
+      let mut writer =
+        brotli::CompressorWriter::new(Vec::new(), 65 * 1024, quality, 22);
+      writer.write_all(buf).unwrap();
+      writer.flush().unwrap();
+      writer.into_inner()
+    }
+    Compression::Zstd(level) => zstd::bulk::compress(buf, level).unwrap(),
+  }
+}
+
 pub struct ResourceBodyAdapter {
   auto_close: bool,
   stm: Rc<dyn Resource>,
@@ -314,9 +349,9 @@ pub struct GZipResponseStream {
 }
 
 impl GZipResponseStream {
-  pub fn new(underlying: ResponseStream) -> Self {
+  pub fn new(underlying: ResponseStream, level: u32) -> Self {
     Self {
-      stm: flate2::Compress::new(flate2::Compression::fast(), false),
+      stm: flate2::Compress::new(flate2::Compression::new(level), false),
       crc: flate2::Crc::new(),
       next_buf: None,
       partial: None,
@@ -333,7 +368,7 @@ impl GZipResponseStream {
 /// This is a minimal GZip header suitable for serving data from a webserver. We don't need to provide
 /// most of the information. We're skipping header name, CRC, etc, and providing a null timestamp.
 ///
-/// We're using compression level 1, as higher levels don't produce significant size differences. This
+/// We default to compression level 1, as higher levels don't produce significant size differences. This
 /// is probably the reason why nginx's default gzip compression level is also 1:
 ///
 /// https://nginx.org/en/docs/http/ngx_http_gzip_module.html#gzip_comp_level
@@ -462,12 +497,10 @@ pub struct BrotliResponseStream {
 }
 
 impl BrotliResponseStream {
-  pub fn new(underlying: ResponseStream) -> Self {
+  pub fn new(underlying: ResponseStream, quality: u32) -> Self {
     let mut stm = BrotliEncoderStateStruct::new(StandardAlloc::default());
-    // Quality level 6 is based on google's nginx default value for on-the-fly compression
-    // https://github.com/google/ngx_brotli#brotli_comp_level
     // lgwin 22 is equivalent to brotli window size of (2**22)-16 bytes (~4MB)
-    stm.set_parameter(BrotliEncoderParameter::BROTLI_PARAM_QUALITY, 6);
+    stm.set_parameter(BrotliEncoderParameter::BROTLI_PARAM_QUALITY, quality);
     stm.set_parameter(BrotliEncoderParameter::BROTLI_PARAM_LGWIN, 22);
     Self {
       stm,
@@ -567,6 +600,87 @@ impl PollFrame for BrotliResponseStream {
   }
 }
 
+#[derive(Copy, Clone, Debug)]
+enum ZstdState {
+  Streaming,
+  EndOfStream,
+}
+
+#[pin_project]
+pub struct ZstdResponseStream {
+  state: ZstdState,
+  /// Writes the compressed output into its `Vec`, which is drained after
+  /// every chunk.
+  stm: zstd::stream::write::Encoder<'static, Vec<u8>>,
+  #[pin]
+  underlying: ResponseStream,
+}
+
+impl ZstdResponseStream {
+  pub fn new(underlying: ResponseStream, level: i32) -> Self {
+    Self {
+      state: ZstdState::Streaming,
+      stm: zstd::stream::write::Encoder::new(Vec::new(), level).unwrap(),
+      underlying,
+    }
+  }
+
+  pub fn abort(self) {
+    self.underlying.abort()
+  }
+}
+
+impl PollFrame for ZstdResponseStream {
+  fn poll_frame(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+  ) -> std::task::Poll<ResponseStreamResult> {
+    let this = self.get_mut();
+    let frame = match this.state {
+      ZstdState::Streaming => {
+        ready!(Pin::new(&mut this.underlying).poll_frame(cx))
+      }
+      ZstdState::EndOfStream => {
+        return std::task::Poll::Ready(ResponseStreamResult::EndOfStream);
+      }
+    };
+
+    let res = match frame {
+      ResponseStreamResult::NonEmptyBuf(buf) => {
+        // Flushing ends the current block, so each chunk is sent right away
+        this.stm.write_all(&buf).and_then(|_| this.stm.flush())
+      }
+      ResponseStreamResult::EndOfStream => {
+        this.state = ZstdState::EndOfStream;
+        this.stm.do_finish()
+      }
+      _ => return std::task::Poll::Ready(frame),
+    };
+    let res = match res {
+      Err(err) => {
+        ResponseStreamResult::Error(JsErrorBox::generic(err.to_string()))
+      }
+      Ok(()) => {
+        let output = std::mem::take(this.stm.get_mut());
+        if output.is_empty() {
+          match this.state {
+            ZstdState::Streaming => ResponseStreamResult::NoData,
+            ZstdState::EndOfStream => ResponseStreamResult::EndOfStream,
+          }
+        } else {
+          ResponseStreamResult::NonEmptyBuf(BufView::from(output))
+        }
+      }
+    };
+
+    std::task::Poll::Ready(res)
+  }
+
+  fn size_hint(&self) -> SizeHint {
+    SizeHint::default()
+  }
+}
+
 #[allow(clippy::print_stderr)]
 #[cfg(test)]
 mod tests {
@@ -655,7 +769,7 @@ mod tests {
     }
     let (tx, rx) = tokio::sync::mpsc::channel(1);
     let underlying = ResponseStream::TestChannel(rx);
-    let mut resp = GZipResponseStream::new(underlying);
+    let mut resp = GZipResponseStream::new(underlying, 1);
     let handle = tokio::task::spawn(async move {
       for chunk in v {
         tx.send(chunk.into()).await.ok().unwrap();
@@ -697,7 +811,7 @@ mod tests {
     }
     let (tx, rx) = tokio::sync::mpsc::channel(1);
     let underlying = ResponseStream::TestChannel(rx);
-    let mut resp = BrotliResponseStream::new(underlying);
+    let mut resp = BrotliResponseStream::new(underlying, 6);
     let handle = tokio::task::spawn(async move {
       for chunk in v {
         tx.send(chunk.into()).await.ok().unwrap();
@@ -733,22 +847,65 @@ mod tests {
     handle.await.unwrap();
   }
 
+  async fn test_zstd(i: impl Iterator<Item = Vec<u8>> + Send + 'static) {
+    let v = i.collect::<Vec<_>>();
+    let mut expected: Vec<u8> = vec![];
+    for v in &v {
+      expected.extend(v);
+    }
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    let underlying = ResponseStream::TestChannel(rx);
+    let mut resp = ZstdResponseStream::new(underlying, 3);
+    let handle = tokio::task::spawn(async move {
+      for chunk in v {
+        tx.send(chunk.into()).await.ok().unwrap();
+      }
+    });
+    // Limit how many times we'll loop
+    const LIMIT: usize = 1000;
+    let mut v: Vec<u8> = vec![];
+    for i in 0..=LIMIT {
+      assert_ne!(i, LIMIT);
+      let frame = poll_fn(|cx| Pin::new(&mut resp).poll_frame(cx)).await;
+      if matches!(frame, ResponseStreamResult::EndOfStream) {
+        break;
+      }
+      if matches!(frame, ResponseStreamResult::NoData) {
+        continue;
+      }
+      let ResponseStreamResult::NonEmptyBuf(buf) = frame else {
+        panic!("Unexpected stream type");
+      };
+      assert_ne!(buf.len(), 0);
+      v.extend(&*buf);
+    }
+
+    let v = zstd::stream::decode_all(&*v).unwrap();
+
+    assert_eq!(v, expected);
+
+    handle.await.unwrap();
+  }
+
   #[tokio::test]
   async fn test_simple() {
     test_brotli(vec![b"hello world".to_vec()].into_iter()).await;
     test_gzip(vec![b"hello world".to_vec()].into_iter()).await;
+    test_zstd(vec![b"hello world".to_vec()].into_iter()).await;
   }
 
   #[tokio::test]
   async fn test_empty() {
     test_brotli(vec![].into_iter()).await;
     test_gzip(vec![].into_iter()).await;
+    test_zstd(vec![].into_iter()).await;
   }
 
   #[tokio::test]
   async fn test_simple_zeros() {
     test_brotli(vec![vec![0; 0x10000]].into_iter()).await;
     test_gzip(vec![vec![0; 0x10000]].into_iter()).await;
+    test_zstd(vec![vec![0; 0x10000]].into_iter()).await;
   }
 
   macro_rules! test {
@@ -760,6 +917,8 @@ mod tests {
           super::test_gzip(iter).await;
           let br_iter = super::chunk(super::$vec());
           super::test_brotli(br_iter).await;
+          let zstd_iter = super::chunk(super::$vec());
+          super::test_zstd(zstd_iter).await;
         }
 
         #[tokio::test]
@@ -768,6 +927,8 @@ mod tests {
           super::test_gzip(iter).await;
           let br_iter = super::front_load(super::$vec());
           super::test_brotli(br_iter).await;
+          let zstd_iter = super::front_load(super::$vec());
+          super::test_zstd(zstd_iter).await;
         }
 
         #[tokio::test]
@@ -776,6 +937,8 @@ mod tests {
           super::test_gzip(iter).await;
           let br_iter = super::front_load_but_one(super::$vec());
           super::test_brotli(br_iter).await;
+          let zstd_iter = super::front_load_but_one(super::$vec());
+          super::test_zstd(zstd_iter).await;
         }
 
         #[tokio::test]
@@ -784,6 +947,8 @@ mod tests {
           super::test_gzip(iter).await;
           let br_iter = super::back_load(super::$vec());
           super::test_brotli(br_iter).await;
+          let zstd_iter = super::back_load(super::$vec());
+          super::test_zstd(zstd_iter).await;
         }
 
         #[tokio::test]
@@ -792,6 +957,8 @@ mod tests {
           super::test_gzip(iter).await;
           let br_iter = super::random(super::$vec());
           super::test_brotli(br_iter).await;
+          let zstd_iter = super::random(super::$vec());
+          super::test_zstd(zstd_iter).await;
         }
       }
     };
@@ -33,6 +33,7 @@ use tokio::sync::oneshot;
 use crate::OtelInfo;
 use crate::OtelInfoAttributes;
 use crate::request_properties::HttpConnectionProperties;
+use crate::response_body::CompressionOptions;
 use crate::response_body::ResponseBytesInner;
 use crate::response_body::ResponseStreamResult;
 
@@ -80,6 +81,7 @@ pub(crate) use http_trace;
 
 pub(crate) struct HttpServerStateInner {
   pool: Vec<(Rc<HttpRecord>, HeaderMap)>,
+  compression: CompressionOptions,
 }
 
 /// A signalling version of `Rc` that allows one to poll for when all other references
@@ -140,9 +142,10 @@ impl<T> std::ops::Deref for SignallingRc<T> {
 pub(crate) struct HttpServerState(RefCell<HttpServerStateInner>);
 
 impl HttpServerState {
-  pub fn new() -> SignallingRc<Self> {
+  pub fn new(compression: CompressionOptions) -> SignallingRc<Self> {
     SignallingRc::new(Self(RefCell::new(HttpServerStateInner {
       pool: Vec::new(),
+      compression,
     })))
   }
 }
@@ -456,6 +459,11 @@ impl HttpRecord {
     RefMut::map(self.0.borrow_mut(), |option| option.as_mut().unwrap())
   }
 
+  /// The compression settings of the server that received this request.
+  pub fn compression_options(&self) -> CompressionOptions {
+    self.self_ref().server_state.borrow().compression
+  }
+
   /// Perform the Hyper upgrade on this record.
   pub fn upgrade(&self) -> Result<OnUpgrade, UpgradeUnavailableError> {
     // Manually perform the upgrade. We're peeking into hyper's underlying machinery here a bit
@@ -694,6 +702,9 @@ impl Body for HttpRecordResponse {
         ResponseBytesInner::BrotliStream(stm) => {
           ready!(Pin::new(stm.as_mut()).poll_frame(cx))
         }
+        ResponseBytesInner::ZstdStream(stm) => {
+          ready!(Pin::new(stm.as_mut()).poll_frame(cx))
+        }
       };
       // This is where we retry the NoData response
       if matches!(res, ResponseStreamResult::NoData) {
@@ -807,7 +818,7 @@ mod tests {
   #[tokio::test]
   async fn test_handle_request() -> Result<(), deno_core::error::AnyError> {
     let (tx, mut rx) = tokio::sync::mpsc::channel(10);
-    let server_state = HttpServerState::new();
+    let server_state = HttpServerState::new(Default::default());
     let server_state_check = server_state.clone();
     let request_info = HttpConnectionProperties {
       peer_address: "".into(),
@@ -229,6 +229,69 @@ impl tokio::io::AsyncRead for BiPipeRead {
     self.project().inner.poll_read(cx, buf)
   }
 }
+#[cfg(unix)]
+impl BiPipeRead {
+  /// Like `poll_read`, but also receives the file descriptors that were sent
+  /// alongside the data with `SCM_RIGHTS`, appending them to `fds`.
+  pub fn poll_read_with_fds(
+    self: std::pin::Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut tokio::io::ReadBuf<'_>,
+    fds: &mut Vec<std::os::fd::OwnedFd>,
+  ) -> std::task::Poll<std::io::Result<()>> {
+    use std::os::fd::AsRawFd;
+    use std::os::fd::FromRawFd;
+    use std::os::fd::OwnedFd;
+    use std::os::fd::RawFd;
+
+    use nix::sys::socket::ControlMessageOwned;
+    use nix::sys::socket::MsgFlags;
+    use nix::sys::socket::recvmsg;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let flags = MsgFlags::MSG_CMSG_CLOEXEC;
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let flags = MsgFlags::empty();
+
+    let stream: &tokio::net::UnixStream = self.get_mut().inner.as_ref();
+    loop {
+      std::task::ready!(stream.poll_read_ready(cx))?;
+      let result = stream.try_io(tokio::io::Interest::READABLE, || {
+        let mut iov = [std::io::IoSliceMut::new(buf.initialize_unfilled())];
+        let mut cmsg = nix::cmsg_space!([RawFd; 4]);
+        let msg =
+          recvmsg::<()>(stream.as_raw_fd(), &mut iov, Some(&mut cmsg), flags)?;
+        for cmsg in msg.cmsgs() {
+          if let ControlMessageOwned::ScmRights(received) = cmsg {
+            for fd in received {
+              // SAFETY: the kernel installed a new file descriptor in this
+              // process for us, nothing else owns it.
+              let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+              // Without MSG_CMSG_CLOEXEC the flag can only be set after the
+              // fact, so a concurrent fork can still inherit the fd.
+              #[cfg(not(any(target_os = "android", target_os = "linux")))]
+              nix::fcntl::fcntl(
+                fd.as_raw_fd(),
+                nix::fcntl::FcntlArg::F_SETFD(nix::fcntl::FdFlag::FD_CLOEXEC),
+              )?;
+              fds.push(fd);
+            }
+          }
+        }
+        Ok(msg.bytes)
+      });
+      match result {
+        Ok(nread) => {
+          buf.advance(nread);
+          return std::task::Poll::Ready(Ok(()));
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+        Err(err) => return std::task::Poll::Ready(Err(err)),
+      }
+    }
+  }
+}
+
 impl tokio::io::AsyncRead for BiPipe {
   fn poll_read(
     self: std::pin::Pin<&mut Self>,
@@ -283,6 +346,47 @@ macro_rules! impl_async_write {
 }
 
 impl_async_write!(for BiPipeWrite -> self.inner);
+
+#[cfg(unix)]
+impl BiPipeWrite {
+  /// Writes all of `buf`, sending `fd` with `SCM_RIGHTS` alongside its first
+  /// chunk. `buf` must not be empty.
+  pub async fn write_all_with_fd(
+    &mut self,
+    buf: &[u8],
+    fd: std::os::fd::BorrowedFd<'_>,
+  ) -> Result<(), std::io::Error> {
+    use std::os::fd::AsRawFd;
+
+    use nix::sys::socket::ControlMessage;
+    use nix::sys::socket::MsgFlags;
+    use nix::sys::socket::sendmsg;
+
+    debug_assert!(!buf.is_empty());
+    let fds = [fd.as_raw_fd()];
+    let stream: &tokio::net::UnixStream = self.inner.as_ref();
+    let nwritten = loop {
+      stream.writable().await?;
+      let result = stream.try_io(tokio::io::Interest::WRITABLE, || {
+        let iov = [std::io::IoSlice::new(buf)];
+        let cmsg = [ControlMessage::ScmRights(&fds)];
+        Ok(sendmsg::<()>(
+          stream.as_raw_fd(),
+          &iov,
+          &cmsg,
+          MsgFlags::empty(),
+          None,
+        )?)
+      });
+      match result {
+        Ok(nwritten) => break nwritten,
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+        Err(err) => return Err(err),
+      }
+    };
+    self.write_all(&buf[nwritten..]).await
+  }
+}
 impl_async_write!(for BiPipe -> self.write_end);
 
 /// Creates both sides of a bidirectional pipe, returning the raw
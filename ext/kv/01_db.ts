@@ -10,6 +10,9 @@ import {
   op_kv_dequeue_next_message,
   op_kv_encode_cursor,
   op_kv_finish_dequeued_message,
+  op_kv_queue_list,
+  op_kv_queue_purge,
+  op_kv_queue_redrive,
   op_kv_snapshot_read,
   op_kv_watch,
   op_kv_watch_next,
@@ -24,6 +27,7 @@ const {
   AsyncGeneratorPrototype,
   BigInt,
   BigIntPrototypeToString,
+  Date,
   Error,
   NumberIsNaN,
   Object,
@@ -58,6 +62,7 @@ async function openKv(path: string) {
 }
 
 const maxQueueDelay = 30 * 24 * 60 * 60 * 1000;
+const allQueueMessageStates = ["pending", "running", "dead"];
 
 function validateQueueDelay(delay: number) {
   if (delay < 0) {
@@ -347,6 +352,38 @@ class Kv {
     finishMessageOps.clear();
   }
 
+  async listQueueMessages(
+    options: {
+      states?: Deno.KvQueueMessageState[];
+      limit?: number;
+    } = { __proto__: null },
+  ): Promise<Deno.KvQueueMessage[]> {
+    const messages = await op_kv_queue_list(
+      this.#rid,
+      options.states ?? allQueueMessageStates,
+      options.limit ?? 100,
+    );
+    return ArrayPrototypeMap(messages, (message) => ({
+      id: message.id,
+      state: message.state,
+      value: core.deserialize(message.payload, { forStorage: true }),
+      timestamp: new Date(message.timestamp),
+      deliveryAttempts: message.deliveryAttempts,
+      backoffSchedule: message.backoffSchedule,
+      keysIfUndelivered: message.keysIfUndelivered,
+    }));
+  }
+
+  async purgeQueue(
+    options: { states?: Deno.KvQueueMessageState[] } = { __proto__: null },
+  ): Promise<number> {
+    return await op_kv_queue_purge(this.#rid, options.states ?? ["pending"]);
+  }
+
+  async redriveQueueMessages(ids?: string[]): Promise<number> {
+    return await op_kv_queue_redrive(this.#rid, ids ?? null);
+  }
+
   watch(keys: Deno.KvKey[], options = { __proto__: null }) {
     const raw = options.raw ?? false;
     const rid = op_kv_watch(this.#rid, keys);
@@ -0,0 +1,677 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Online backup, restore, and NDJSON export/import of SQLite backed KV
+//! databases.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use chrono::DateTime;
+use deno_error::JsErrorBox;
+use denokv_proto::AtomicWrite;
+use denokv_proto::Consistency;
+use denokv_proto::Database;
+use denokv_proto::Key;
+use denokv_proto::KeyPart;
+use denokv_proto::KvEntry;
+use denokv_proto::KvValue;
+use denokv_proto::Mutation;
+use denokv_proto::MutationKind;
+use denokv_proto::ReadRange;
+use denokv_proto::SnapshotReadOptions;
+use denokv_proto::decode_key;
+use denokv_proto::encode_key;
+use denokv_sqlite::SqliteConfig;
+use denokv_sqlite::SqliteNotifier;
+use rand::SeedableRng;
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+use rusqlite::OptionalExtension;
+use rusqlite::TransactionBehavior;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::queue_admin::ADMIN_TABLES;
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+const EXPORT_PAGE_SIZE: u32 = 500;
+const IMPORT_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum KvBackupError {
+  #[class(generic)]
+  #[error(transparent)]
+  Sqlite(#[from] rusqlite::Error),
+  #[class(inherit)]
+  #[error(transparent)]
+  Io(#[from] std::io::Error),
+  #[class(inherit)]
+  #[error(transparent)]
+  Kv(JsErrorBox),
+  #[class(type)]
+  #[error("Path is not valid UTF-8: {}", .0.display())]
+  InvalidPath(PathBuf),
+  #[class("AlreadyExists")]
+  #[error("Backup destination already exists: {}", .0.display())]
+  DestinationExists(PathBuf),
+  #[class(generic)]
+  #[error("Backup is corrupted: {0}")]
+  CorruptedBackup(String),
+  #[class(generic)]
+  #[error(
+    "The database schema does not match the schema of the backup; open the database once with the Deno version that created the backup"
+  )]
+  SchemaMismatch,
+  #[class(type)]
+  #[error("Invalid entry on line {line}: {source}")]
+  InvalidEntry {
+    line: usize,
+    #[source]
+    source: serde_json::Error,
+  },
+  #[class(type)]
+  #[error("Invalid key: {0}")]
+  InvalidKey(String),
+}
+
+fn path_str(path: &Path) -> Result<&str, KvBackupError> {
+  path
+    .to_str()
+    .ok_or_else(|| KvBackupError::InvalidPath(path.to_path_buf()))
+}
+
+/// Writes a consistent snapshot of the database at `db_path` to a new file
+/// at `output_path`. Other connections may keep reading and writing the
+/// database while the backup is taken.
+pub fn backup(db_path: &Path, output_path: &Path) -> Result<(), KvBackupError> {
+  if output_path.exists() {
+    return Err(KvBackupError::DestinationExists(output_path.to_path_buf()));
+  }
+  let conn = Connection::open_with_flags(
+    db_path,
+    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+  )?;
+  conn.busy_timeout(BUSY_TIMEOUT)?;
+  // `VACUUM INTO` reads the database in a single read transaction, which
+  // also includes the changes that are still in the write-ahead log.
+  conn.execute("VACUUM INTO ?1", [path_str(output_path)?])?;
+  Ok(())
+}
+
+/// Replaces the contents of the database at `db_path` with the contents of
+/// the backup at `backup_path`.
+///
+/// The database may be in use; the replacement happens in a single write
+/// transaction, so other connections either see the old or the restored
+/// contents. Versionstamps continue from the ones of the backup. The queue
+/// administration tables of either database are left out, since they only
+/// exist once delivery tracking was enabled.
+pub fn restore(
+  backup_path: &Path,
+  db_path: &Path,
+) -> Result<(), KvBackupError> {
+  let backup = Connection::open_with_flags(
+    backup_path,
+    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+  )?;
+  let integrity: String =
+    backup.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+  if integrity != "ok" {
+    return Err(KvBackupError::CorruptedBackup(integrity));
+  }
+
+  if !db_path.exists() {
+    backup.execute("VACUUM INTO ?1", [path_str(db_path)?])?;
+    return Ok(());
+  }
+  drop(backup);
+
+  let mut conn = Connection::open(db_path)?;
+  conn.busy_timeout(BUSY_TIMEOUT)?;
+  conn.execute("ATTACH DATABASE ?1 AS backup", [path_str(backup_path)?])?;
+
+  let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+  let backup_tables = tables(&tx, "backup")?;
+  let main_tables = tables(&tx, "main")?;
+  if main_tables.is_empty() {
+    // The database was never opened by Deno; create the schema of the
+    // backup first.
+    let mut stmt = tx.prepare(
+      "SELECT sql FROM backup.sqlite_schema
+        WHERE type IN ('table', 'index') AND name NOT LIKE 'sqlite_%'
+          AND tbl_name NOT IN (?1, ?2) AND sql IS NOT NULL
+        ORDER BY type DESC",
+    )?;
+    let statements = stmt
+      .query_map(ADMIN_TABLES, |row| row.get::<_, String>(0))?
+      .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+    for sql in statements {
+      tx.execute(&sql, [])?;
+    }
+  } else if main_tables != backup_tables {
+    return Err(KvBackupError::SchemaMismatch);
+  }
+  for name in backup_tables.keys() {
+    tx.execute(&format!("DELETE FROM main.\"{name}\""), [])?;
+    tx.execute(
+      &format!("INSERT INTO main.\"{name}\" SELECT * FROM backup.\"{name}\""),
+      [],
+    )?;
+  }
+  tx.commit()?;
+
+  conn.execute("DETACH DATABASE backup", [])?;
+  Ok(())
+}
+
+/// Returns the name and `CREATE` statement of the tables in `schema`,
+/// except for the queue administration tables.
+fn tables(
+  conn: &Connection,
+  schema: &str,
+) -> rusqlite::Result<BTreeMap<String, String>> {
+  let mut stmt = conn.prepare(&format!(
+    "SELECT name, sql FROM {schema}.sqlite_schema
+      WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+        AND name NOT IN (?1, ?2)"
+  ))?;
+  stmt
+    .query_map(ADMIN_TABLES, |row| Ok((row.get(0)?, row.get(1)?)))?
+    .collect()
+}
+
+/// A key part in the NDJSON export format. Numbers that can't be
+/// represented in JSON (`NaN` and the infinities) are written as strings.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum JsonKeyPart {
+  String(String),
+  Number(serde_json::Value),
+  Bigint(String),
+  Bytes(String),
+  Boolean(bool),
+}
+
+/// A value in the NDJSON export format. `v8` values are the bytes of the V8
+/// serialization of the value, as stored by `Deno.Kv`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum JsonValue {
+  V8(String),
+  Bytes(String),
+  U64(String),
+}
+
+/// A single line of the NDJSON export format.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonEntry {
+  pub key: Vec<JsonKeyPart>,
+  pub value: JsonValue,
+  /// The versionstamp of the entry when it was exported. It is informative
+  /// only; imported entries get a new versionstamp.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub versionstamp: Option<String>,
+  /// When the entry expires, in milliseconds since the Unix epoch.
+  #[serde(
+    default,
+    rename = "expireAt",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub expire_at: Option<u64>,
+}
+
+impl From<KeyPart> for JsonKeyPart {
+  fn from(part: KeyPart) -> Self {
+    match part {
+      KeyPart::String(s) => JsonKeyPart::String(s),
+      KeyPart::Float(n) => JsonKeyPart::Number(
+        serde_json::Number::from_f64(n)
+          .map(serde_json::Value::Number)
+          .unwrap_or_else(|| {
+            let s = if n.is_nan() {
+              "NaN"
+            } else if n > 0.0 {
+              "Infinity"
+            } else {
+              "-Infinity"
+            };
+            serde_json::Value::String(s.to_string())
+          }),
+      ),
+      KeyPart::Int(n) => JsonKeyPart::Bigint(n.to_string()),
+      KeyPart::Bytes(b) => JsonKeyPart::Bytes(BASE64_STANDARD.encode(b)),
+      KeyPart::False => JsonKeyPart::Boolean(false),
+      KeyPart::True => JsonKeyPart::Boolean(true),
+    }
+  }
+}
+
+impl TryFrom<JsonKeyPart> for KeyPart {
+  type Error = KvBackupError;
+  fn try_from(part: JsonKeyPart) -> Result<Self, Self::Error> {
+    let invalid = |what: &str| KvBackupError::InvalidKey(what.to_string());
+    Ok(match part {
+      JsonKeyPart::String(s) => KeyPart::String(s),
+      JsonKeyPart::Number(serde_json::Value::Number(n)) => {
+        KeyPart::Float(n.as_f64().ok_or_else(|| invalid("number"))?)
+      }
+      JsonKeyPart::Number(serde_json::Value::String(s)) => {
+        KeyPart::Float(match s.as_str() {
+          "NaN" => f64::NAN,
+          "Infinity" => f64::INFINITY,
+          "-Infinity" => f64::NEG_INFINITY,
+          _ => return Err(invalid(&s)),
+        })
+      }
+      JsonKeyPart::Number(value) => return Err(invalid(&value.to_string())),
+      JsonKeyPart::Bigint(s) => {
+        KeyPart::Int(s.parse().map_err(|_| invalid(&s))?)
+      }
+      JsonKeyPart::Bytes(s) => {
+        KeyPart::Bytes(BASE64_STANDARD.decode(&s).map_err(|_| invalid(&s))?)
+      }
+      JsonKeyPart::Boolean(false) => KeyPart::False,
+      JsonKeyPart::Boolean(true) => KeyPart::True,
+    })
+  }
+}
+
+impl From<KvValue> for JsonValue {
+  fn from(value: KvValue) -> Self {
+    match value {
+      KvValue::V8(buf) => JsonValue::V8(BASE64_STANDARD.encode(buf)),
+      KvValue::Bytes(buf) => JsonValue::Bytes(BASE64_STANDARD.encode(buf)),
+      KvValue::U64(n) => JsonValue::U64(n.to_string()),
+    }
+  }
+}
+
+impl TryFrom<JsonValue> for KvValue {
+  type Error = String;
+  fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+    Ok(match value {
+      JsonValue::V8(s) => {
+        KvValue::V8(BASE64_STANDARD.decode(&s).map_err(|e| e.to_string())?)
+      }
+      JsonValue::Bytes(s) => {
+        KvValue::Bytes(BASE64_STANDARD.decode(&s).map_err(|e| e.to_string())?)
+      }
+      JsonValue::U64(s) => KvValue::U64(s.parse().map_err(|_| s)?),
+    })
+  }
+}
+
+impl TryFrom<KvEntry> for JsonEntry {
+  type Error = std::io::Error;
+  fn try_from(entry: KvEntry) -> Result<Self, Self::Error> {
+    Ok(JsonEntry {
+      key: decode_key(&entry.key)?
+        .0
+        .into_iter()
+        .map(JsonKeyPart::from)
+        .collect(),
+      value: entry.value.into(),
+      versionstamp: Some(faster_hex::hex_string(&entry.versionstamp)),
+      expire_at: None,
+    })
+  }
+}
+
+/// Parses a key given on the command line: a JSON array whose elements are
+/// either plain strings, numbers and booleans, or typed key parts as they
+/// appear in the export format.
+pub fn parse_key(text: &str) -> Result<Vec<u8>, KvBackupError> {
+  let invalid = || KvBackupError::InvalidKey(text.to_string());
+  let parts: Vec<serde_json::Value> =
+    serde_json::from_str(text).map_err(|_| invalid())?;
+  let parts = parts
+    .into_iter()
+    .map(|part| match part {
+      serde_json::Value::String(s) => Ok(KeyPart::String(s)),
+      serde_json::Value::Bool(b) => {
+        Ok(if b { KeyPart::True } else { KeyPart::False })
+      }
+      serde_json::Value::Number(n) => {
+        Ok(KeyPart::Float(n.as_f64().ok_or_else(invalid)?))
+      }
+      typed @ serde_json::Value::Object(_) => {
+        serde_json::from_value::<JsonKeyPart>(typed)
+          .map_err(|_| invalid())?
+          .try_into()
+      }
+      _ => Err(invalid()),
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(encode_key(&Key(parts))?)
+}
+
+/// The range of keys to export. All keys are exported when every bound is
+/// `None`.
+#[derive(Default)]
+pub struct ExportSelector {
+  /// Encoded prefix; only keys strictly under the prefix are exported.
+  pub prefix: Option<Vec<u8>>,
+  /// Encoded first key to export (inclusive).
+  pub start: Option<Vec<u8>>,
+  /// Encoded key to stop at (exclusive).
+  pub end: Option<Vec<u8>>,
+}
+
+/// Writes the entries selected by `selector` to `writer` as NDJSON, one
+/// `JsonEntry` per line, in key order. The entries are read from a
+/// point-in-time snapshot of the database. Returns the number of entries
+/// written.
+pub async fn export(
+  db_path: &Path,
+  selector: ExportSelector,
+  writer: &mut impl Write,
+) -> Result<u64, KvBackupError> {
+  // Export from a backup, so that the export is consistent even though it
+  // is read in multiple transactions.
+  let snapshot_path = std::env::temp_dir().join(format!(
+    "deno_kv_export_{}_{}.sqlite3",
+    std::process::id(),
+    std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_nanos()
+  ));
+  backup(db_path, &snapshot_path)?;
+  let result = export_snapshot(&snapshot_path, selector, writer).await;
+  let _ = std::fs::remove_file(&snapshot_path);
+  result
+}
+
+async fn export_snapshot(
+  snapshot_path: &Path,
+  selector: ExportSelector,
+  writer: &mut impl Write,
+) -> Result<u64, KvBackupError> {
+  let db = open(snapshot_path)?;
+  // `Database` reads don't return the expiration of the entries, so it is
+  // read from the table of `denokv_sqlite`.
+  let conn = Connection::open_with_flags(
+    snapshot_path,
+    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+  )?;
+  let mut start = selector.start.unwrap_or_else(|| match &selector.prefix {
+    Some(prefix) => prefix.iter().copied().chain(Some(0)).collect(),
+    None => vec![],
+  });
+  let end = selector.end.unwrap_or_else(|| match &selector.prefix {
+    Some(prefix) => prefix.iter().copied().chain(Some(0xff)).collect(),
+    None => vec![0xff],
+  });
+
+  let result = async {
+    let mut count = 0;
+    loop {
+      let mut ranges = db
+        .snapshot_read(
+          vec![ReadRange {
+            start: start.clone(),
+            end: end.clone(),
+            limit: NonZeroU32::new(EXPORT_PAGE_SIZE).unwrap(),
+            reverse: false,
+          }],
+          SnapshotReadOptions {
+            consistency: Consistency::Strong,
+          },
+        )
+        .await
+        .map_err(KvBackupError::Kv)?;
+      let entries = ranges.pop().map(|r| r.entries).unwrap_or_default();
+      let page_len = entries.len();
+      if let Some(last) = entries.last() {
+        start = last.key.iter().copied().chain(Some(0)).collect();
+      }
+      for entry in entries {
+        let expire_at = expiration(&conn, &entry.key)?;
+        let mut entry = JsonEntry::try_from(entry)?;
+        entry.expire_at = expire_at;
+        serde_json::to_writer(&mut *writer, &entry)
+          .map_err(std::io::Error::from)?;
+        writer.write_all(b"\n")?;
+        count += 1;
+      }
+      if page_len < EXPORT_PAGE_SIZE as usize {
+        break;
+      }
+    }
+    writer.flush()?;
+    Ok::<_, KvBackupError>(count)
+  }
+  .await;
+  db.close();
+  result
+}
+
+/// Returns when the entry with the encoded `key` expires, in milliseconds
+/// since the Unix epoch. Entries that don't expire have a negative
+/// expiration.
+fn expiration(conn: &Connection, key: &[u8]) -> rusqlite::Result<Option<u64>> {
+  let expiration_ms = conn
+    .prepare_cached("SELECT expiration_ms FROM kv WHERE k = ?1")?
+    .query_row([key], |row| row.get::<_, i64>(0))
+    .optional()?;
+  Ok(expiration_ms.and_then(|ms| u64::try_from(ms).ok()))
+}
+
+/// Sets every entry of the NDJSON `reader`, in the format written by
+/// `export`, in the database at `db_path`. Existing entries with the same
+/// keys are overwritten. Returns the number of entries imported.
+pub async fn import(
+  db_path: &Path,
+  reader: impl BufRead,
+) -> Result<u64, KvBackupError> {
+  let db = open(db_path)?;
+  let mut count = 0;
+  let mut mutations = Vec::with_capacity(IMPORT_BATCH_SIZE);
+  let result = async {
+    for (index, line) in reader.lines().enumerate() {
+      let line = line?;
+      if line.trim().is_empty() {
+        continue;
+      }
+      let entry: JsonEntry = serde_json::from_str(&line).map_err(|source| {
+        KvBackupError::InvalidEntry {
+          line: index + 1,
+          source,
+        }
+      })?;
+      let key = entry
+        .key
+        .into_iter()
+        .map(KeyPart::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+      let value = KvValue::try_from(entry.value).map_err(|err| {
+        KvBackupError::InvalidEntry {
+          line: index + 1,
+          source: serde::de::Error::custom(err),
+        }
+      })?;
+      let expire_at = entry
+        .expire_at
+        .map(|ms| {
+          i64::try_from(ms)
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| KvBackupError::InvalidEntry {
+              line: index + 1,
+              source: serde::de::Error::custom("expireAt is out of range"),
+            })
+        })
+        .transpose()?;
+      mutations.push(Mutation {
+        key: encode_key(&Key(key))?,
+        kind: MutationKind::Set(value),
+        expire_at,
+      });
+      if mutations.len() == IMPORT_BATCH_SIZE {
+        count += write_batch(&db, &mut mutations).await?;
+      }
+    }
+    count += write_batch(&db, &mut mutations).await?;
+    Ok::<_, KvBackupError>(count)
+  }
+  .await;
+  db.close();
+  result
+}
+
+async fn write_batch(
+  db: &denokv_sqlite::Sqlite,
+  mutations: &mut Vec<Mutation>,
+) -> Result<u64, KvBackupError> {
+  if mutations.is_empty() {
+    return Ok(0);
+  }
+  let len = mutations.len() as u64;
+  db.atomic_write(AtomicWrite {
+    checks: vec![],
+    mutations: std::mem::take(mutations),
+    enqueues: vec![],
+  })
+  .await
+  .map_err(KvBackupError::Kv)?;
+  Ok(len)
+}
+
+pub(crate) fn open(
+  path: &Path,
+) -> Result<denokv_sqlite::Sqlite, KvBackupError> {
+  let path = path.to_path_buf();
+  denokv_sqlite::Sqlite::new(
+    move || {
+      let conn = Connection::open(&path)
+        .map_err(|e| JsErrorBox::generic(e.to_string()))?;
+      conn
+        .pragma_update(None, "journal_mode", "wal")
+        .map_err(|e| JsErrorBox::generic(e.to_string()))?;
+      Ok((conn, Box::new(rand::rngs::StdRng::from_entropy())))
+    },
+    SqliteNotifier::default(),
+    SqliteConfig {
+      batch_timeout: None,
+      num_workers: 1,
+    },
+  )
+  .map_err(KvBackupError::Kv)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_json_key_parts() {
+    let key = Key(vec![
+      KeyPart::String("users".to_string()),
+      KeyPart::Int(42.into()),
+      KeyPart::Float(f64::INFINITY),
+      KeyPart::Bytes(vec![1, 2, 3]),
+      KeyPart::True,
+    ]);
+    let json = key
+      .0
+      .clone()
+      .into_iter()
+      .map(JsonKeyPart::from)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      serde_json::to_string(&json).unwrap(),
+      r#"[{"type":"string","value":"users"},{"type":"bigint","value":"42"},{"type":"number","value":"Infinity"},{"type":"bytes","value":"AQID"},{"type":"boolean","value":true}]"#
+    );
+    let parts = json
+      .into_iter()
+      .map(KeyPart::try_from)
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    assert_eq!(encode_key(&Key(parts)).unwrap(), encode_key(&key).unwrap());
+  }
+
+  #[test]
+  fn test_parse_key() {
+    let expected = encode_key(&Key(vec![
+      KeyPart::String("a".to_string()),
+      KeyPart::Float(1.0),
+      KeyPart::Int(2.into()),
+    ]))
+    .unwrap();
+    assert_eq!(
+      parse_key(r#"["a", 1, {"type": "bigint", "value": "2"}]"#).unwrap(),
+      expected
+    );
+    assert!(parse_key("users").is_err());
+    assert!(parse_key("[null]").is_err());
+  }
+
+  #[tokio::test]
+  async fn test_export_import_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.sqlite3");
+    let target = dir.path().join("target.sqlite3");
+
+    let lines = [
+      r#"{"key":[{"type":"string","value":"a"}],"value":{"type":"u64","value":"7"},"expireAt":4102444800000}"#,
+      r#"{"key":[{"type":"string","value":"b"},{"type":"boolean","value":false}],"value":{"type":"bytes","value":"AQID"}}"#,
+    ];
+    let imported = import(&source, lines.join("\n").as_bytes()).await.unwrap();
+    assert_eq!(imported, 2);
+
+    let mut output = Vec::new();
+    let exported = export(&source, ExportSelector::default(), &mut output)
+      .await
+      .unwrap();
+    assert_eq!(exported, 2);
+    let entries = String::from_utf8(output).unwrap();
+    let entries = entries
+      .lines()
+      .map(|line| serde_json::from_str::<JsonEntry>(line).unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(entries[0].value, JsonValue::U64("7".to_string()));
+    assert_eq!(entries[0].expire_at, Some(4102444800000));
+    assert!(entries[1].versionstamp.is_some());
+    assert_eq!(entries[1].expire_at, None);
+
+    let mut output = Vec::new();
+    let selector = ExportSelector {
+      prefix: Some(parse_key(r#"["b"]"#).unwrap()),
+      ..Default::default()
+    };
+    assert_eq!(export(&source, selector, &mut output).await.unwrap(), 1);
+
+    let backup_path = dir.path().join("backup.sqlite3");
+    backup(&source, &backup_path).unwrap();
+    assert!(matches!(
+      backup(&source, &backup_path),
+      Err(KvBackupError::DestinationExists(_))
+    ));
+    restore(&backup_path, &target).unwrap();
+    // Restoring over an existing database replaces its contents.
+    import(&target, lines[0].replace("\"a\"", "\"c\"").as_bytes())
+      .await
+      .unwrap();
+    restore(&backup_path, &target).unwrap();
+    // The queue administration tables don't make the schemas differ.
+    crate::queue_admin::queue_track(&target, true)
+      .await
+      .unwrap();
+    restore(&backup_path, &target).unwrap();
+    let mut output = Vec::new();
+    assert_eq!(
+      export(&target, ExportSelector::default(), &mut output)
+        .await
+        .unwrap(),
+      2
+    );
+  }
+}
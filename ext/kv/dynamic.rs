@@ -13,9 +13,12 @@ use denokv_proto::WatchStream;
 use crate::AtomicWrite;
 use crate::Database;
 use crate::DatabaseHandler;
+use crate::QueueAdmin;
 use crate::QueueMessageHandle;
 use crate::ReadRange;
 use crate::SnapshotReadOptions;
+use crate::queue_admin::QueueMessageInfo;
+use crate::queue_admin::QueueMessageState;
 use crate::remote::RemoteDbHandlerPermissions;
 use crate::sqlite::SqliteDbHandler;
 use crate::sqlite::SqliteDbHandlerPermissions;
@@ -38,11 +41,20 @@ impl MultiBackendDbHandler {
     versionstamp_rng_seed: Option<u64>,
     http_options: crate::remote::HttpOptions,
   ) -> Self {
+    let postgres = crate::postgres::PostgresDbHandler::<P>::new(
+      http_options.root_cert_store_provider.clone(),
+      http_options.unsafely_ignore_certificate_errors.clone(),
+    );
     Self::new(vec![
       (
         &["https://", "http://"],
         Box::new(crate::remote::RemoteDbHandler::<P>::new(http_options)),
       ),
+      (&["postgres://", "postgresql://"], Box::new(postgres)),
+      (
+        &["memory:"],
+        Box::new(crate::memory::MemoryDbHandler::new()),
+      ),
       (
         &[""],
         Box::new(SqliteDbHandler::<P>::new(
@@ -122,7 +134,7 @@ impl DatabaseHandler for Box<dyn DynamicDbHandler> {
 impl<T, DB> DynamicDbHandler for T
 where
   T: DatabaseHandler<DB = DB>,
-  DB: Database + 'static,
+  DB: Database + QueueAdmin + 'static,
 {
   async fn dyn_open(
     &self,
@@ -153,6 +165,22 @@ pub trait DynamicDb {
   fn dyn_watch(&self, keys: Vec<Vec<u8>>) -> WatchStream;
 
   fn dyn_close(&self);
+
+  async fn dyn_list_queue_messages(
+    &self,
+    states: Vec<QueueMessageState>,
+    limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox>;
+
+  async fn dyn_purge_queue(
+    &self,
+    states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox>;
+
+  async fn dyn_redrive_queue_messages(
+    &self,
+    ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox>;
 }
 
 #[derive(Clone)]
@@ -192,10 +220,35 @@ impl Database for RcDynamicDb {
   }
 }
 
+#[async_trait(?Send)]
+impl QueueAdmin for RcDynamicDb {
+  async fn list_queue_messages(
+    &self,
+    states: Vec<QueueMessageState>,
+    limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+    (*self.0).dyn_list_queue_messages(states, limit).await
+  }
+
+  async fn purge_queue(
+    &self,
+    states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox> {
+    (*self.0).dyn_purge_queue(states).await
+  }
+
+  async fn redrive_queue_messages(
+    &self,
+    ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox> {
+    (*self.0).dyn_redrive_queue_messages(ids).await
+  }
+}
+
 #[async_trait(?Send)]
 impl<T, QMH> DynamicDb for T
 where
-  T: Database<QMH = QMH>,
+  T: Database<QMH = QMH> + QueueAdmin,
   QMH: QueueMessageHandle + 'static,
 {
   async fn dyn_snapshot_read(
@@ -231,4 +284,26 @@ where
   fn dyn_close(&self) {
     self.close()
   }
+
+  async fn dyn_list_queue_messages(
+    &self,
+    states: Vec<QueueMessageState>,
+    limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+    self.list_queue_messages(states, limit).await
+  }
+
+  async fn dyn_purge_queue(
+    &self,
+    states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox> {
+    self.purge_queue(states).await
+  }
+
+  async fn dyn_redrive_queue_messages(
+    &self,
+    ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox> {
+    self.redrive_queue_messages(ids).await
+  }
 }
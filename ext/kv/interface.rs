@@ -8,9 +8,13 @@ use deno_core::OpState;
 use deno_error::JsErrorBox;
 use denokv_proto::Database;
 
+use crate::queue_admin::QueueMessageInfo;
+use crate::queue_admin::QueueMessageState;
+use crate::queue_admin::unsupported_error;
+
 #[async_trait(?Send)]
 pub trait DatabaseHandler {
-  type DB: Database + 'static;
+  type DB: Database + QueueAdmin + 'static;
 
   async fn open(
     &self,
@@ -18,3 +22,31 @@ pub trait DatabaseHandler {
     path: Option<String>,
   ) -> Result<Self::DB, JsErrorBox>;
 }
+
+/// Inspection and administration of the queue of a database. Backends that
+/// don't support it keep the default implementations, which fail with a
+/// `NotSupported` error.
+#[async_trait(?Send)]
+pub trait QueueAdmin {
+  async fn list_queue_messages(
+    &self,
+    _states: Vec<QueueMessageState>,
+    _limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+    Err(unsupported_error())
+  }
+
+  async fn purge_queue(
+    &self,
+    _states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox> {
+    Err(unsupported_error())
+  }
+
+  async fn redrive_queue_messages(
+    &self,
+    _ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox> {
+    Err(unsupported_error())
+  }
+}
@@ -1,8 +1,13 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+pub mod backup;
 pub mod config;
 pub mod dynamic;
 mod interface;
+pub mod memory;
+mod mutation;
+pub mod postgres;
+pub mod queue_admin;
 pub mod remote;
 pub mod sqlite;
 
@@ -59,6 +64,8 @@ use serde::Serialize;
 
 pub use crate::config::*;
 pub use crate::interface::*;
+use crate::queue_admin::QueueMessageInfo;
+use crate::queue_admin::QueueMessageState;
 
 pub const UNSTABLE_FEATURE_NAME: &str = "kv";
 
@@ -72,6 +79,9 @@ deno_core::extension!(deno_kv,
     op_kv_encode_cursor,
     op_kv_dequeue_next_message<DBH>,
     op_kv_finish_dequeued_message<DBH>,
+    op_kv_queue_list<DBH>,
+    op_kv_queue_purge<DBH>,
+    op_kv_queue_redrive<DBH>,
     op_kv_watch<DBH>,
     op_kv_watch_next,
   ],
@@ -599,6 +609,124 @@ where
   Ok(())
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToV8QueueMessage {
+  id: String,
+  state: QueueMessageState,
+  payload: ToJsBuffer,
+  timestamp: f64,
+  delivery_attempts: u32,
+  backoff_schedule: Vec<u32>,
+  keys_if_undelivered: Vec<KvKey>,
+}
+
+impl TryFrom<QueueMessageInfo> for ToV8QueueMessage {
+  type Error = std::io::Error;
+  fn try_from(message: QueueMessageInfo) -> Result<Self, Self::Error> {
+    Ok(ToV8QueueMessage {
+      id: message.id,
+      state: message.state,
+      payload: message.payload.into(),
+      timestamp: message.timestamp.timestamp_millis() as f64,
+      delivery_attempts: message
+        .delivery_attempts
+        .try_into()
+        .unwrap_or(u32::MAX),
+      backoff_schedule: message.backoff_schedule,
+      keys_if_undelivered: message
+        .keys_if_undelivered
+        .iter()
+        .map(|key| {
+          Ok(decode_key(key)?.0.into_iter().map(key_part_to_v8).collect())
+        })
+        .collect::<std::io::Result<_>>()?,
+    })
+  }
+}
+
+fn get_database<DBH: DatabaseHandler + 'static>(
+  state: &RefCell<OpState>,
+  rid: ResourceId,
+) -> Result<DBH::DB, KvError> {
+  let state = state.borrow();
+  let resource = state
+    .resource_table
+    .get::<DatabaseResource<DBH::DB>>(rid)
+    .map_err(KvErrorKind::Resource)?;
+  Ok(resource.db.clone())
+}
+
+#[op2(async)]
+#[serde]
+async fn op_kv_queue_list<DBH>(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] states: Vec<QueueMessageState>,
+  #[smi] limit: u32,
+) -> Result<Vec<ToV8QueueMessage>, KvError>
+where
+  DBH: DatabaseHandler + 'static,
+{
+  let db = get_database::<DBH>(&state, rid)?;
+  let config = {
+    let state = state.borrow();
+    state.borrow::<Rc<KvConfig>>().clone()
+  };
+
+  if limit == 0 {
+    return Err(KvErrorKind::InvalidLimit.into_box());
+  }
+  if limit as usize > config.max_read_entries {
+    return Err(
+      KvErrorKind::TooManyEntries(config.max_read_entries).into_box(),
+    );
+  }
+
+  let messages = db
+    .list_queue_messages(states, limit as usize)
+    .await
+    .map_err(KvErrorKind::Kv)?;
+  Ok(
+    messages
+      .into_iter()
+      .map(TryInto::try_into)
+      .collect::<std::io::Result<_>>()?,
+  )
+}
+
+#[op2(async)]
+#[number]
+async fn op_kv_queue_purge<DBH>(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] states: Vec<QueueMessageState>,
+) -> Result<u64, KvError>
+where
+  DBH: DatabaseHandler + 'static,
+{
+  let db = get_database::<DBH>(&state, rid)?;
+  Ok(db.purge_queue(states).await.map_err(KvErrorKind::Kv)?)
+}
+
+#[op2(async)]
+#[number]
+async fn op_kv_queue_redrive<DBH>(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] ids: Option<Vec<String>>,
+) -> Result<u64, KvError>
+where
+  DBH: DatabaseHandler + 'static,
+{
+  let db = get_database::<DBH>(&state, rid)?;
+  Ok(
+    db.redrive_queue_messages(ids)
+      .await
+      .map_err(KvErrorKind::Kv)?,
+  )
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum KvCheckError {
   #[class(type)]
@@ -0,0 +1,689 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::Utc;
+use deno_core::OpState;
+use deno_core::futures::StreamExt;
+use deno_core::futures::stream;
+use deno_error::JsErrorBox;
+use denokv_proto::AtomicWrite;
+use denokv_proto::CommitResult;
+use denokv_proto::KvEntry;
+use denokv_proto::KvValue;
+use denokv_proto::Mutation;
+use denokv_proto::MutationKind;
+use denokv_proto::QueueMessageHandle;
+use denokv_proto::ReadRange;
+use denokv_proto::ReadRangeOutput;
+use denokv_proto::SnapshotReadOptions;
+use denokv_proto::Versionstamp;
+use denokv_proto::WatchKeyOutput;
+use denokv_proto::WatchStream;
+use tokio::sync::Notify;
+use tokio::sync::watch;
+
+use crate::Database;
+use crate::DatabaseHandler;
+use crate::QueueAdmin;
+use crate::mutation::DEFAULT_BACKOFF_SCHEDULE;
+use crate::mutation::apply_numeric_mutation;
+use crate::mutation::versionstamp;
+use crate::mutation::versionstamped_key;
+
+/// Opens databases that only live in memory, for tests and other ephemeral
+/// uses. `memory:` opens a new empty database every time, while
+/// `memory:<name>` opens a database that is shared by every `Deno.openKv`
+/// call with the same name for the lifetime of the handler.
+///
+/// Versionstamps are assigned from a counter starting at 1 for every
+/// database, so they are the same on every run of a program.
+#[derive(Default)]
+pub struct MemoryDbHandler {
+  stores: RefCell<HashMap<String, Rc<Store>>>,
+}
+
+impl MemoryDbHandler {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn open_named(&self, name: &str) -> MemoryDb {
+    let store = if name.is_empty() {
+      Rc::new(Store::default())
+    } else {
+      self
+        .stores
+        .borrow_mut()
+        .entry(name.to_string())
+        .or_default()
+        .clone()
+    };
+    MemoryDb {
+      store,
+      closed: Rc::new(Cell::new(false)),
+    }
+  }
+}
+
+#[async_trait(?Send)]
+impl DatabaseHandler for MemoryDbHandler {
+  type DB = MemoryDb;
+
+  async fn open(
+    &self,
+    _state: Rc<RefCell<OpState>>,
+    path: Option<String>,
+  ) -> Result<Self::DB, JsErrorBox> {
+    let Some(name) = path.as_deref().and_then(|p| p.strip_prefix("memory:"))
+    else {
+      return Err(JsErrorBox::type_error(format!(
+        "Invalid in-memory database path: {:?}",
+        path
+      )));
+    };
+    Ok(self.open_named(name))
+  }
+}
+
+struct Store {
+  state: RefCell<State>,
+  /// Woken when a message is enqueued or a handle is closed.
+  queue_notify: Notify,
+  /// Holds the version of the last commit, to wake up watchers.
+  changes: watch::Sender<u64>,
+}
+
+impl Default for Store {
+  fn default() -> Self {
+    Self {
+      state: RefCell::new(State::default()),
+      queue_notify: Notify::new(),
+      changes: watch::Sender::new(0),
+    }
+  }
+}
+
+#[derive(Default)]
+struct State {
+  version: u64,
+  entries: BTreeMap<Vec<u8>, StoredEntry>,
+  queue: BTreeMap<(DateTime<Utc>, u64), QueueMessage>,
+  next_message_id: u64,
+}
+
+#[derive(Clone)]
+struct StoredEntry {
+  value: KvValue,
+  versionstamp: Versionstamp,
+  expire_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+struct QueueMessage {
+  payload: Vec<u8>,
+  keys_if_undelivered: Vec<Vec<u8>>,
+  backoff_schedule: Vec<u32>,
+}
+
+impl State {
+  /// Expired entries are kept until they are overwritten, but are never
+  /// visible.
+  fn get(&self, key: &[u8], now: DateTime<Utc>) -> Option<&StoredEntry> {
+    self
+      .entries
+      .get(key)
+      .filter(|entry| entry.expire_at.is_none_or(|expire_at| expire_at > now))
+  }
+
+  fn enqueue(&mut self, deadline: DateTime<Utc>, message: QueueMessage) {
+    let id = self.next_message_id;
+    self.next_message_id += 1;
+    self.queue.insert((deadline, id), message);
+  }
+
+  fn commit(
+    &mut self,
+    write: AtomicWrite,
+    now: DateTime<Utc>,
+  ) -> Result<Option<Versionstamp>, JsErrorBox> {
+    for check in &write.checks {
+      let current = self.get(&check.key, now).map(|entry| entry.versionstamp);
+      if current != check.versionstamp {
+        return Ok(None);
+      }
+    }
+
+    let versionstamp = versionstamp(self.version + 1);
+
+    // Mutations are applied to a copy of the entries they touch first, so
+    // that a failing one leaves the database unchanged.
+    let mut changes: BTreeMap<Vec<u8>, Option<StoredEntry>> = BTreeMap::new();
+    for mutation in write.mutations {
+      let expire_at = mutation.expire_at;
+      let (key, entry) = match mutation.kind {
+        MutationKind::Set(value) => (mutation.key, Some(value)),
+        MutationKind::Delete => (mutation.key, None),
+        kind @ (MutationKind::Sum { .. }
+        | MutationKind::Min(_)
+        | MutationKind::Max(_)) => {
+          let current = match changes.get(&mutation.key) {
+            Some(change) => change.as_ref().map(|entry| entry.value.clone()),
+            None => self
+              .get(&mutation.key, now)
+              .map(|entry| entry.value.clone()),
+          };
+          let value = apply_numeric_mutation(current, &kind)?;
+          (mutation.key, Some(value))
+        }
+        MutationKind::SetSuffixVersionstampedKey(value) => (
+          versionstamped_key(&mutation.key, &versionstamp),
+          Some(value),
+        ),
+      };
+      let entry = entry.map(|value| StoredEntry {
+        value,
+        versionstamp,
+        expire_at,
+      });
+      changes.insert(key, entry);
+    }
+
+    self.version += 1;
+    for (key, entry) in changes {
+      match entry {
+        Some(entry) => self.entries.insert(key, entry),
+        None => self.entries.remove(&key),
+      };
+    }
+    for enqueue in write.enqueues {
+      self.enqueue(
+        enqueue.deadline,
+        QueueMessage {
+          payload: enqueue.payload,
+          keys_if_undelivered: enqueue.keys_if_undelivered,
+          backoff_schedule: enqueue
+            .backoff_schedule
+            .unwrap_or_else(|| DEFAULT_BACKOFF_SCHEDULE.to_vec()),
+        },
+      );
+    }
+    Ok(Some(versionstamp))
+  }
+}
+
+/// A handle to an in-memory database. Closing it only affects this handle;
+/// the data of a named database stays available to other handles.
+#[derive(Clone)]
+pub struct MemoryDb {
+  store: Rc<Store>,
+  closed: Rc<Cell<bool>>,
+}
+
+impl MemoryDb {
+  fn check_open(&self) -> Result<(), JsErrorBox> {
+    if self.closed.get() {
+      return Err(JsErrorBox::generic("Database is closed"));
+    }
+    Ok(())
+  }
+
+  fn read_entries(&self, keys: &[Vec<u8>]) -> Vec<Option<KvEntry>> {
+    let state = self.store.state.borrow();
+    let now = Utc::now();
+    keys
+      .iter()
+      .map(|key| {
+        state.get(key, now).map(|entry| KvEntry {
+          key: key.clone(),
+          value: entry.value.clone(),
+          versionstamp: entry.versionstamp,
+        })
+      })
+      .collect()
+  }
+}
+
+#[async_trait(?Send)]
+impl Database for MemoryDb {
+  type QMH = MemoryQueueMessageHandle;
+
+  async fn snapshot_read(
+    &self,
+    requests: Vec<ReadRange>,
+    _options: SnapshotReadOptions,
+  ) -> Result<Vec<ReadRangeOutput>, JsErrorBox> {
+    self.check_open()?;
+    let state = self.store.state.borrow();
+    let now = Utc::now();
+    let mut outputs = Vec::with_capacity(requests.len());
+    for request in requests {
+      let entries = if request.start < request.end {
+        let range = state.entries.range(request.start..request.end);
+        let range: Box<dyn Iterator<Item = _>> = if request.reverse {
+          Box::new(range.rev())
+        } else {
+          Box::new(range)
+        };
+        range
+          .filter(|(_, entry)| {
+            entry.expire_at.is_none_or(|expire_at| expire_at > now)
+          })
+          .take(request.limit.get() as usize)
+          .map(|(key, entry)| KvEntry {
+            key: key.clone(),
+            value: entry.value.clone(),
+            versionstamp: entry.versionstamp,
+          })
+          .collect()
+      } else {
+        vec![]
+      };
+      outputs.push(ReadRangeOutput { entries });
+    }
+    Ok(outputs)
+  }
+
+  async fn atomic_write(
+    &self,
+    write: AtomicWrite,
+  ) -> Result<Option<CommitResult>, JsErrorBox> {
+    self.check_open()?;
+    let has_enqueues = !write.enqueues.is_empty();
+    let Some(versionstamp) =
+      self.store.state.borrow_mut().commit(write, Utc::now())?
+    else {
+      return Ok(None);
+    };
+    if has_enqueues {
+      self.store.queue_notify.notify_waiters();
+    }
+    self.store.changes.send_modify(|version| *version += 1);
+    Ok(Some(CommitResult { versionstamp }))
+  }
+
+  async fn dequeue_next_message(
+    &self,
+  ) -> Result<Option<Self::QMH>, JsErrorBox> {
+    loop {
+      let notified = self.store.queue_notify.notified();
+      tokio::pin!(notified);
+      notified.as_mut().enable();
+
+      if self.closed.get() {
+        return Ok(None);
+      }
+      let next_deadline = {
+        let mut state = self.store.state.borrow_mut();
+        match state.queue.first_key_value() {
+          Some(((deadline, _), _)) if *deadline <= Utc::now() => {
+            let (_, message) = state.queue.pop_first().unwrap();
+            return Ok(Some(MemoryQueueMessageHandle {
+              db: self.clone(),
+              message,
+            }));
+          }
+          Some(((deadline, _), _)) => Some(*deadline),
+          None => None,
+        }
+      };
+
+      match next_deadline {
+        Some(deadline) => {
+          let delay = (deadline - Utc::now()).to_std().unwrap_or_default();
+          tokio::select! {
+            _ = notified => {}
+            _ = tokio::time::sleep(delay) => {}
+          }
+        }
+        None => notified.await,
+      }
+    }
+  }
+
+  fn watch(&self, keys: Vec<Vec<u8>>) -> WatchStream {
+    let db = self.clone();
+    let receiver = self.store.changes.subscribe();
+    let last: Option<Vec<Option<Versionstamp>>> = None;
+    stream::unfold(
+      (db, keys, receiver, last),
+      |(db, keys, mut receiver, last)| async move {
+        loop {
+          if db.closed.get() {
+            return None;
+          }
+          receiver.borrow_and_update();
+          let entries = db.read_entries(&keys);
+          let versionstamps = entries
+            .iter()
+            .map(|entry| entry.as_ref().map(|entry| entry.versionstamp))
+            .collect::<Vec<_>>();
+          let outputs = match &last {
+            None => entries
+              .into_iter()
+              .map(|entry| WatchKeyOutput::Changed { entry })
+              .collect(),
+            Some(last) => {
+              let mut changed = false;
+              let outputs = entries
+                .into_iter()
+                .zip(last)
+                .map(|(entry, last)| {
+                  if entry.as_ref().map(|entry| entry.versionstamp) == *last {
+                    WatchKeyOutput::Unchanged
+                  } else {
+                    changed = true;
+                    WatchKeyOutput::Changed { entry }
+                  }
+                })
+                .collect::<Vec<_>>();
+              if !changed {
+                if receiver.changed().await.is_err() {
+                  return None;
+                }
+                continue;
+              }
+              outputs
+            }
+          };
+          return Some((
+            Ok(outputs),
+            (db, keys, receiver, Some(versionstamps)),
+          ));
+        }
+      },
+    )
+    .boxed_local()
+  }
+
+  fn close(&self) {
+    self.closed.set(true);
+    self.store.queue_notify.notify_waiters();
+    self.store.changes.send_modify(|_| {});
+  }
+}
+
+impl QueueAdmin for MemoryDb {}
+
+pub struct MemoryQueueMessageHandle {
+  db: MemoryDb,
+  message: QueueMessage,
+}
+
+#[async_trait(?Send)]
+impl QueueMessageHandle for MemoryQueueMessageHandle {
+  async fn take_payload(&mut self) -> Result<Vec<u8>, JsErrorBox> {
+    Ok(self.message.payload.clone())
+  }
+
+  async fn finish(&self, success: bool) -> Result<(), JsErrorBox> {
+    if success {
+      return Ok(());
+    }
+    let store = &self.db.store;
+    let mut message = self.message.clone();
+    if !message.backoff_schedule.is_empty() {
+      let delay = message.backoff_schedule.remove(0);
+      let deadline = Utc::now() + chrono::Duration::milliseconds(delay as i64);
+      store.state.borrow_mut().enqueue(deadline, message);
+      store.queue_notify.notify_waiters();
+      return Ok(());
+    }
+
+    // Out of retries: the message is dead-lettered into its
+    // `keysIfUndelivered`.
+    if message.keys_if_undelivered.is_empty() {
+      return Ok(());
+    }
+    let payload = message.payload;
+    let write = AtomicWrite {
+      checks: vec![],
+      mutations: message
+        .keys_if_undelivered
+        .into_iter()
+        .map(|key| Mutation {
+          key,
+          kind: MutationKind::Set(KvValue::V8(payload.clone())),
+          expire_at: None,
+        })
+        .collect(),
+      enqueues: vec![],
+    };
+    store.state.borrow_mut().commit(write, Utc::now())?;
+    store.changes.send_modify(|version| *version += 1);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::num::NonZeroU32;
+
+  use denokv_proto::Check;
+  use denokv_proto::Consistency;
+  use denokv_proto::Enqueue;
+
+  use super::*;
+
+  fn open() -> MemoryDb {
+    MemoryDbHandler::new().open_named("")
+  }
+
+  fn set(key: &[u8], value: KvValue) -> Mutation {
+    Mutation {
+      key: key.to_vec(),
+      kind: MutationKind::Set(value),
+      expire_at: None,
+    }
+  }
+
+  fn write(mutations: Vec<Mutation>) -> AtomicWrite {
+    AtomicWrite {
+      checks: vec![],
+      mutations,
+      enqueues: vec![],
+    }
+  }
+
+  async fn read_all(db: &MemoryDb) -> Vec<KvEntry> {
+    let mut outputs = db
+      .snapshot_read(
+        vec![ReadRange {
+          start: vec![],
+          end: vec![0xff],
+          limit: NonZeroU32::new(100).unwrap(),
+          reverse: false,
+        }],
+        SnapshotReadOptions {
+          consistency: Consistency::Strong,
+        },
+      )
+      .await
+      .unwrap();
+    outputs.remove(0).entries
+  }
+
+  #[tokio::test]
+  async fn test_deterministic_versionstamps() {
+    for _ in 0..2 {
+      let db = open();
+      let first = db
+        .atomic_write(write(vec![set(b"a", KvValue::U64(1))]))
+        .await
+        .unwrap()
+        .unwrap();
+      let second = db
+        .atomic_write(write(vec![set(b"b", KvValue::U64(2))]))
+        .await
+        .unwrap()
+        .unwrap();
+      assert_eq!(first.versionstamp, versionstamp(1));
+      assert_eq!(second.versionstamp, versionstamp(2));
+    }
+  }
+
+  #[tokio::test]
+  async fn test_checks_and_mutations() {
+    let db = open();
+    let commit = db
+      .atomic_write(write(vec![set(b"a", KvValue::U64(1))]))
+      .await
+      .unwrap()
+      .unwrap();
+
+    let stale = AtomicWrite {
+      checks: vec![Check {
+        key: b"a".to_vec(),
+        versionstamp: None,
+      }],
+      ..write(vec![set(b"a", KvValue::U64(5))])
+    };
+    assert!(db.atomic_write(stale).await.unwrap().is_none());
+
+    let sum = AtomicWrite {
+      checks: vec![Check {
+        key: b"a".to_vec(),
+        versionstamp: Some(commit.versionstamp),
+      }],
+      ..write(vec![
+        Mutation {
+          key: b"a".to_vec(),
+          kind: MutationKind::Sum {
+            value: KvValue::U64(2),
+            min_v8: vec![],
+            max_v8: vec![],
+            clamp: false,
+          },
+          expire_at: None,
+        },
+        Mutation {
+          key: b"a".to_vec(),
+          kind: MutationKind::Max(KvValue::U64(10)),
+          expire_at: None,
+        },
+      ])
+    };
+    assert!(db.atomic_write(sum).await.unwrap().is_some());
+
+    // A failing mutation leaves the database unchanged.
+    let invalid = write(vec![
+      set(b"b", KvValue::U64(1)),
+      Mutation {
+        key: b"c".to_vec(),
+        kind: MutationKind::Min(KvValue::Bytes(vec![])),
+        expire_at: None,
+      },
+    ]);
+    assert!(db.atomic_write(invalid).await.is_err());
+
+    let entries = read_all(&db).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, b"a");
+    assert!(matches!(entries[0].value, KvValue::U64(10)));
+    assert_eq!(entries[0].versionstamp, versionstamp(2));
+  }
+
+  #[tokio::test]
+  async fn test_expiry() {
+    let db = open();
+    let expired = Mutation {
+      expire_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+      ..set(b"a", KvValue::U64(1))
+    };
+    db.atomic_write(write(vec![expired, set(b"b", KvValue::U64(2))]))
+      .await
+      .unwrap();
+    let entries = read_all(&db).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, b"b");
+  }
+
+  #[tokio::test]
+  async fn test_named_databases() {
+    let handler = MemoryDbHandler::new();
+    let a = handler.open_named("shared");
+    a.atomic_write(write(vec![set(b"a", KvValue::U64(1))]))
+      .await
+      .unwrap();
+    a.close();
+    assert!(a.atomic_write(write(vec![])).await.is_err());
+
+    let b = handler.open_named("shared");
+    assert_eq!(read_all(&b).await.len(), 1);
+    let c = handler.open_named("other");
+    assert!(read_all(&c).await.is_empty());
+    let d = handler.open_named("");
+    assert!(read_all(&d).await.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_queue_dead_letter() {
+    let db = open();
+    db.atomic_write(AtomicWrite {
+      enqueues: vec![Enqueue {
+        payload: vec![1, 2, 3],
+        deadline: Utc::now(),
+        keys_if_undelivered: vec![b"failed".to_vec()],
+        backoff_schedule: Some(vec![1]),
+      }],
+      ..write(vec![])
+    })
+    .await
+    .unwrap();
+
+    let mut message = db.dequeue_next_message().await.unwrap().unwrap();
+    assert_eq!(message.take_payload().await.unwrap(), vec![1, 2, 3]);
+    message.finish(false).await.unwrap();
+
+    // The retry is delivered after the backoff.
+    let mut message = db.dequeue_next_message().await.unwrap().unwrap();
+    assert_eq!(message.take_payload().await.unwrap(), vec![1, 2, 3]);
+    message.finish(false).await.unwrap();
+
+    let entries = read_all(&db).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].key, b"failed");
+    assert!(matches!(&entries[0].value, KvValue::V8(v) if v == &[1, 2, 3]));
+
+    db.close();
+    assert!(db.dequeue_next_message().await.unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn test_watch() {
+    let db = open();
+    let mut stream = db.watch(vec![b"a".to_vec(), b"b".to_vec()]);
+    let first = stream.next().await.unwrap().unwrap();
+    assert!(matches!(
+      first.as_slice(),
+      [
+        WatchKeyOutput::Changed { entry: None },
+        WatchKeyOutput::Changed { entry: None }
+      ]
+    ));
+
+    db.atomic_write(write(vec![set(b"c", KvValue::U64(1))]))
+      .await
+      .unwrap();
+    db.atomic_write(write(vec![set(b"b", KvValue::U64(1))]))
+      .await
+      .unwrap();
+    let second = stream.next().await.unwrap().unwrap();
+    assert!(matches!(
+      second.as_slice(),
+      [
+        WatchKeyOutput::Unchanged,
+        WatchKeyOutput::Changed { entry: Some(_) }
+      ]
+    ));
+
+    db.close();
+    assert!(stream.next().await.is_none());
+  }
+}
@@ -0,0 +1,144 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Mutation semantics shared by the backends that are implemented in this
+//! crate, matching the ones of the SQLite backend.
+
+use deno_error::JsErrorBox;
+use denokv_proto::Key;
+use denokv_proto::KeyPart;
+use denokv_proto::KvValue;
+use denokv_proto::MutationKind;
+use denokv_proto::Versionstamp;
+use denokv_proto::encode_key;
+
+pub(crate) const DEFAULT_BACKOFF_SCHEDULE: [u32; 5] =
+  [100, 1_000, 5_000, 30_000, 60_000];
+
+pub(crate) const VALUE_ENCODING_V8: i16 = 1;
+pub(crate) const VALUE_ENCODING_LE64: i16 = 2;
+pub(crate) const VALUE_ENCODING_BYTES: i16 = 3;
+
+/// Versionstamps are the commit version followed by a zero batch index.
+pub(crate) fn versionstamp(version: u64) -> Versionstamp {
+  let mut versionstamp = [0; 10];
+  versionstamp[..8].copy_from_slice(&version.to_be_bytes());
+  versionstamp
+}
+
+pub(crate) fn version_from_versionstamp(versionstamp: &Versionstamp) -> u64 {
+  u64::from_be_bytes(versionstamp[..8].try_into().unwrap())
+}
+
+/// The key written by a `setSuffixVersionstampedKey` mutation: `key` with
+/// the hex encoded versionstamp appended as a string key part.
+pub(crate) fn versionstamped_key(
+  key: &[u8],
+  versionstamp: &Versionstamp,
+) -> Vec<u8> {
+  let suffix = encode_key(&Key(vec![KeyPart::String(faster_hex::hex_string(
+    versionstamp,
+  ))]))
+  .unwrap();
+  [key, &suffix].concat()
+}
+
+pub(crate) fn encode_value(value: &KvValue) -> (Vec<u8>, i16) {
+  match value {
+    KvValue::V8(buf) => (buf.clone(), VALUE_ENCODING_V8),
+    KvValue::Bytes(buf) => (buf.clone(), VALUE_ENCODING_BYTES),
+    KvValue::U64(n) => (n.to_le_bytes().to_vec(), VALUE_ENCODING_LE64),
+  }
+}
+
+pub(crate) fn decode_value(
+  value: Vec<u8>,
+  encoding: i16,
+) -> Result<KvValue, JsErrorBox> {
+  match encoding {
+    VALUE_ENCODING_V8 => Ok(KvValue::V8(value)),
+    VALUE_ENCODING_BYTES => Ok(KvValue::Bytes(value)),
+    VALUE_ENCODING_LE64 => {
+      let bytes: [u8; 8] = value
+        .try_into()
+        .map_err(|_| JsErrorBox::generic("Invalid u64 value in database"))?;
+      Ok(KvValue::U64(u64::from_le_bytes(bytes)))
+    }
+    _ => Err(JsErrorBox::generic(format!(
+      "Unknown value encoding {encoding} in database"
+    ))),
+  }
+}
+
+/// Computes the new value of a key for a `sum`, `min` or `max` mutation,
+/// given its current value.
+pub(crate) fn apply_numeric_mutation(
+  current: Option<KvValue>,
+  kind: &MutationKind,
+) -> Result<KvValue, JsErrorBox> {
+  let (name, operand, op): (_, _, fn(u64, u64) -> u64) = match kind {
+    MutationKind::Sum { value, .. } => ("sum", value, u64::wrapping_add),
+    MutationKind::Min(value) => ("min", value, u64::min),
+    MutationKind::Max(value) => ("max", value, u64::max),
+    _ => unreachable!(),
+  };
+  let KvValue::U64(operand) = operand else {
+    return Err(JsErrorBox::type_error(format!(
+      "Failed to perform '{name}' mutation on a non-U64 operand"
+    )));
+  };
+  match current {
+    None => Ok(KvValue::U64(*operand)),
+    Some(KvValue::U64(current)) => Ok(KvValue::U64(op(current, *operand))),
+    Some(_) => Err(JsErrorBox::type_error(format!(
+      "Failed to perform '{name}' mutation on a non-U64 value in the database"
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_apply_numeric_mutation() {
+    let sum = MutationKind::Sum {
+      value: KvValue::U64(u64::MAX),
+      min_v8: vec![],
+      max_v8: vec![],
+      clamp: false,
+    };
+    assert!(matches!(
+      apply_numeric_mutation(Some(KvValue::U64(2)), &sum),
+      Ok(KvValue::U64(1))
+    ));
+    assert!(matches!(
+      apply_numeric_mutation(None, &MutationKind::Min(KvValue::U64(5))),
+      Ok(KvValue::U64(5))
+    ));
+    assert!(matches!(
+      apply_numeric_mutation(
+        Some(KvValue::U64(3)),
+        &MutationKind::Max(KvValue::U64(5))
+      ),
+      Ok(KvValue::U64(5))
+    ));
+    assert!(
+      apply_numeric_mutation(
+        Some(KvValue::Bytes(vec![])),
+        &MutationKind::Max(KvValue::U64(5))
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn test_versionstamp() {
+    let stamp = versionstamp(0x0102);
+    assert_eq!(stamp, [0, 0, 0, 0, 0, 0, 1, 2, 0, 0]);
+    assert_eq!(version_from_versionstamp(&stamp), 0x0102);
+    let key = versionstamped_key(&[0x02, b'a', 0x00], &stamp);
+    assert_eq!(&key[..4], &[0x02, b'a', 0x00, 0x02]);
+    assert_eq!(&key[4..24], b"00000000000001020000");
+    assert_eq!(key[24], 0x00);
+  }
+}
@@ -0,0 +1,758 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use deno_core::OpState;
+use deno_core::futures::FutureExt;
+use deno_core::futures::StreamExt;
+use deno_core::futures::future::BoxFuture;
+use deno_core::futures::stream;
+use deno_error::JsErrorBox;
+use deno_tls::RootCertStoreProvider;
+use deno_tls::TlsClientConfigOptions;
+use deno_tls::create_client_config;
+use deno_tls::rustls::ClientConfig;
+use deno_tls::rustls::pki_types::InvalidDnsNameError;
+use deno_tls::rustls::pki_types::ServerName;
+use denokv_proto::AtomicWrite;
+use denokv_proto::CommitResult;
+use denokv_proto::KvEntry;
+use denokv_proto::KvValue;
+use denokv_proto::Mutation;
+use denokv_proto::MutationKind;
+use denokv_proto::QueueMessageHandle;
+use denokv_proto::ReadRange;
+use denokv_proto::ReadRangeOutput;
+use denokv_proto::SnapshotReadOptions;
+use denokv_proto::Versionstamp;
+use denokv_proto::WatchKeyOutput;
+use denokv_proto::WatchStream;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::watch;
+use tokio_postgres::AsyncMessage;
+use tokio_postgres::Client;
+use tokio_postgres::IsolationLevel;
+use tokio_postgres::Row;
+use tokio_postgres::Socket;
+use tokio_postgres::Transaction;
+use tokio_postgres::tls::ChannelBinding;
+use tokio_postgres::tls::MakeTlsConnect;
+use tokio_postgres::tls::TlsConnect;
+use tokio_postgres::tls::TlsStream;
+use tokio_rustls::TlsConnector;
+use url::Url;
+
+use crate::Database;
+use crate::DatabaseHandler;
+use crate::QueueAdmin;
+use crate::mutation::DEFAULT_BACKOFF_SCHEDULE;
+use crate::mutation::apply_numeric_mutation;
+use crate::mutation::decode_value;
+use crate::mutation::encode_value;
+use crate::mutation::versionstamp;
+use crate::mutation::versionstamped_key;
+use crate::remote::RemoteDbHandlerPermissions;
+
+/// How long a dequeued message is hidden from other consumers. If the
+/// process handling it dies, the message is delivered again afterwards.
+const QUEUE_LEASE_MS: i64 = 5 * 60 * 1_000; // 5 minutes
+
+/// Messages that become due without a new enqueue are picked up by
+/// polling.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const SCHEMA: &str = "
+  CREATE TABLE IF NOT EXISTS kv (
+    key BYTEA PRIMARY KEY,
+    value BYTEA NOT NULL,
+    encoding SMALLINT NOT NULL,
+    version BIGINT NOT NULL,
+    expires_at BIGINT
+  );
+  CREATE TABLE IF NOT EXISTS kv_meta (
+    id SMALLINT PRIMARY KEY CHECK (id = 0),
+    version BIGINT NOT NULL
+  );
+  INSERT INTO kv_meta (id, version) VALUES (0, 0) ON CONFLICT DO NOTHING;
+  CREATE TABLE IF NOT EXISTS kv_queue (
+    id BIGSERIAL PRIMARY KEY,
+    payload BYTEA NOT NULL,
+    deadline BIGINT NOT NULL,
+    keys_if_undelivered BYTEA[] NOT NULL,
+    backoff_schedule INTEGER[] NOT NULL,
+    locked_until BIGINT
+  );
+  CREATE INDEX IF NOT EXISTS kv_queue_deadline ON kv_queue (deadline);
+";
+
+/// Opens `postgres://` and `postgresql://` databases, so that several
+/// processes can share a database on a PostgreSQL server.
+///
+/// The tables are created in the first schema of the connection's
+/// `search_path` (see `?options=-csearch_path%3D...` to pick another one).
+///
+/// TLS is used as requested by the `sslmode` of the connection string
+/// (`disable`, `prefer` or `require`). Server certificates are verified
+/// against the root certificates of the runtime, the same as for `fetch`.
+pub struct PostgresDbHandler<P: RemoteDbHandlerPermissions + 'static> {
+  root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
+  unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  _p: PhantomData<P>,
+}
+
+impl<P: RemoteDbHandlerPermissions> PostgresDbHandler<P> {
+  pub fn new(
+    root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
+    unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  ) -> Self {
+    Self {
+      root_cert_store_provider,
+      unsafely_ignore_certificate_errors,
+      _p: PhantomData,
+    }
+  }
+
+  fn tls(&self) -> Result<RustlsConnect, JsErrorBox> {
+    let root_cert_store = match &self.root_cert_store_provider {
+      Some(provider) => Some(provider.get_or_try_init()?.clone()),
+      None => None,
+    };
+    let config = create_client_config(TlsClientConfigOptions {
+      root_cert_store,
+      unsafely_ignore_certificate_errors: self
+        .unsafely_ignore_certificate_errors
+        .clone(),
+      ..Default::default()
+    })
+    .map_err(JsErrorBox::from_err)?;
+    Ok(RustlsConnect(Arc::new(config)))
+  }
+}
+
+#[async_trait(?Send)]
+impl<P: RemoteDbHandlerPermissions> DatabaseHandler for PostgresDbHandler<P> {
+  type DB = PostgresDb;
+
+  async fn open(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    path: Option<String>,
+  ) -> Result<Self::DB, JsErrorBox> {
+    let Some(url) = path else {
+      return Err(JsErrorBox::type_error("Missing database url"));
+    };
+    let Ok(parsed_url) = Url::parse(&url) else {
+      return Err(JsErrorBox::type_error(format!(
+        "Invalid database url: {}",
+        url
+      )));
+    };
+
+    {
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions
+        .check_net_url(&parsed_url, "Deno.openKv")
+        .map_err(JsErrorBox::from_err)?;
+    }
+
+    PostgresDb::connect(&url, self.tls()?).await
+  }
+}
+
+/// Makes `tokio_postgres` connect over TLS with rustls.
+struct RustlsConnect(Arc<ClientConfig>);
+
+impl MakeTlsConnect<Socket> for RustlsConnect {
+  type Stream = RustlsStream;
+  type TlsConnect = RustlsConnector;
+  type Error = InvalidDnsNameError;
+
+  fn make_tls_connect(
+    &mut self,
+    domain: &str,
+  ) -> Result<RustlsConnector, InvalidDnsNameError> {
+    Ok(RustlsConnector {
+      config: self.0.clone(),
+      server_name: ServerName::try_from(domain.to_string())?,
+    })
+  }
+}
+
+struct RustlsConnector {
+  config: Arc<ClientConfig>,
+  server_name: ServerName<'static>,
+}
+
+impl TlsConnect<Socket> for RustlsConnector {
+  type Stream = RustlsStream;
+  type Error = std::io::Error;
+  type Future = BoxFuture<'static, std::io::Result<RustlsStream>>;
+
+  fn connect(self, stream: Socket) -> Self::Future {
+    TlsConnector::from(self.config)
+      .connect(self.server_name, stream)
+      .map(|stream| stream.map(RustlsStream))
+      .boxed()
+  }
+}
+
+struct RustlsStream(tokio_rustls::client::TlsStream<Socket>);
+
+impl TlsStream for RustlsStream {
+  // Channel binding is optional with the default `channel_binding=prefer`.
+  fn channel_binding(&self) -> ChannelBinding {
+    ChannelBinding::none()
+  }
+}
+
+impl AsyncRead for RustlsStream {
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    Pin::new(&mut self.0).poll_read(cx, buf)
+  }
+}
+
+impl AsyncWrite for RustlsStream {
+  fn poll_write(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<std::io::Result<usize>> {
+    Pin::new(&mut self.0).poll_write(cx, buf)
+  }
+
+  fn poll_flush(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    Pin::new(&mut self.0).poll_flush(cx)
+  }
+
+  fn poll_shutdown(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    Pin::new(&mut self.0).poll_shutdown(cx)
+  }
+}
+
+fn pg_error(err: tokio_postgres::Error) -> JsErrorBox {
+  JsErrorBox::generic(format!("PostgreSQL error: {err}"))
+}
+
+fn closed_error() -> JsErrorBox {
+  JsErrorBox::generic("Database is closed")
+}
+
+/// Notifications received on the connection, see `LISTEN kv_queue` and
+/// `LISTEN kv_watch`.
+struct Notifications {
+  queue: Notify,
+  changes: watch::Sender<u64>,
+}
+
+impl Notifications {
+  fn wake_all(&self) {
+    self.queue.notify_waiters();
+    self.changes.send_modify(|version| *version += 1);
+  }
+}
+
+#[derive(Clone)]
+pub struct PostgresDb {
+  /// Statements of concurrent operations must not interleave on the
+  /// connection, so every operation holds the lock for its duration.
+  client: Rc<Mutex<Option<Client>>>,
+  notifications: Rc<Notifications>,
+  closed: Rc<Cell<bool>>,
+}
+
+impl PostgresDb {
+  async fn connect(url: &str, tls: RustlsConnect) -> Result<Self, JsErrorBox> {
+    let (client, mut connection) =
+      tokio_postgres::connect(url, tls).await.map_err(pg_error)?;
+
+    let notifications = Rc::new(Notifications {
+      queue: Notify::new(),
+      changes: watch::Sender::new(0),
+    });
+    let connection_notifications = notifications.clone();
+    deno_core::unsync::spawn(async move {
+      let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+      while let Some(message) = messages.next().await {
+        match message {
+          Ok(AsyncMessage::Notification(notification)) => {
+            match notification.channel() {
+              "kv_queue" => connection_notifications.queue.notify_waiters(),
+              "kv_watch" => connection_notifications
+                .changes
+                .send_modify(|version| *version += 1),
+              _ => {}
+            }
+          }
+          Ok(_) => {}
+          Err(err) => {
+            log::error!("KV PostgreSQL connection error: {err}");
+            break;
+          }
+        }
+      }
+      // Let waiting operations run into the closed connection.
+      connection_notifications.wake_all();
+    });
+
+    // Concurrent first opens of the same database must not race on the
+    // schema creation.
+    client
+      .batch_execute(&format!(
+        "BEGIN;
+        SELECT pg_advisory_xact_lock(hashtext('deno_kv'));
+        {SCHEMA}
+        COMMIT;
+        LISTEN kv_queue;
+        LISTEN kv_watch;"
+      ))
+      .await
+      .map_err(pg_error)?;
+
+    Ok(Self {
+      client: Rc::new(Mutex::new(Some(client))),
+      notifications,
+      closed: Rc::new(Cell::new(false)),
+    })
+  }
+
+  fn check_open(&self) -> Result<(), JsErrorBox> {
+    if self.closed.get() {
+      return Err(closed_error());
+    }
+    Ok(())
+  }
+
+  async fn read_entries(
+    &self,
+    keys: &[Vec<u8>],
+  ) -> Result<Vec<Option<KvEntry>>, JsErrorBox> {
+    let client = self.client.lock().await;
+    let client = client.as_ref().ok_or_else(closed_error)?;
+    let rows = client
+      .query(
+        "SELECT key, value, encoding, version FROM kv
+          WHERE key = ANY($1) AND (expires_at IS NULL OR expires_at > $2)",
+        &[&keys, &Utc::now().timestamp_millis()],
+      )
+      .await
+      .map_err(pg_error)?;
+    let mut entries = rows
+      .into_iter()
+      .map(|row| {
+        let entry = row_to_entry(row)?;
+        Ok((entry.key.clone(), entry))
+      })
+      .collect::<Result<HashMap<_, _>, JsErrorBox>>()?;
+    Ok(keys.iter().map(|key| entries.remove(key)).collect())
+  }
+}
+
+fn row_to_entry(row: Row) -> Result<KvEntry, JsErrorBox> {
+  let version: i64 = row.get(3);
+  Ok(KvEntry {
+    key: row.get(0),
+    value: decode_value(row.get(1), row.get(2))?,
+    versionstamp: versionstamp(version as u64),
+  })
+}
+
+/// Runs the checks, mutations and enqueues of `write` in the transaction
+/// `tx` without committing it. Returns `None` if a check fails.
+async fn write_in_transaction(
+  tx: &Transaction<'_>,
+  write: AtomicWrite,
+) -> Result<Option<Versionstamp>, JsErrorBox> {
+  let now = Utc::now().timestamp_millis();
+
+  // Taking the row lock of `kv_meta` serializes all commits, so the checks
+  // below see the latest committed state.
+  let version: i64 = tx
+    .query_one(
+      "UPDATE kv_meta SET version = version + 1 WHERE id = 0 RETURNING version",
+      &[],
+    )
+    .await
+    .map_err(pg_error)?
+    .get(0);
+  let commit_versionstamp = versionstamp(version as u64);
+
+  for check in &write.checks {
+    let row = tx
+      .query_opt(
+        "SELECT version FROM kv
+          WHERE key = $1 AND (expires_at IS NULL OR expires_at > $2)",
+        &[&check.key, &now],
+      )
+      .await
+      .map_err(pg_error)?;
+    let current = row.map(|row| versionstamp(row.get::<_, i64>(0) as u64));
+    if current != check.versionstamp {
+      return Ok(None);
+    }
+  }
+
+  for mutation in write.mutations {
+    let expires_at = mutation.expire_at.map(|t| t.timestamp_millis());
+    let (key, value) = match mutation.kind {
+      MutationKind::Set(value) => (mutation.key, value),
+      MutationKind::Delete => {
+        tx.execute("DELETE FROM kv WHERE key = $1", &[&mutation.key])
+          .await
+          .map_err(pg_error)?;
+        continue;
+      }
+      kind @ (MutationKind::Sum { .. }
+      | MutationKind::Min(_)
+      | MutationKind::Max(_)) => {
+        let row = tx
+          .query_opt(
+            "SELECT value, encoding FROM kv
+              WHERE key = $1 AND (expires_at IS NULL OR expires_at > $2)",
+            &[&mutation.key, &now],
+          )
+          .await
+          .map_err(pg_error)?;
+        let current = row
+          .map(|row| decode_value(row.get(0), row.get(1)))
+          .transpose()?;
+        (mutation.key, apply_numeric_mutation(current, &kind)?)
+      }
+      MutationKind::SetSuffixVersionstampedKey(value) => (
+        versionstamped_key(&mutation.key, &commit_versionstamp),
+        value,
+      ),
+    };
+    let (value, encoding) = encode_value(&value);
+    tx.execute(
+      "INSERT INTO kv (key, value, encoding, version, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (key) DO UPDATE SET
+          value = excluded.value,
+          encoding = excluded.encoding,
+          version = excluded.version,
+          expires_at = excluded.expires_at",
+      &[&key, &value, &encoding, &version, &expires_at],
+    )
+    .await
+    .map_err(pg_error)?;
+  }
+
+  if !write.enqueues.is_empty() {
+    for enqueue in write.enqueues {
+      let backoff_schedule = enqueue
+        .backoff_schedule
+        .unwrap_or_else(|| DEFAULT_BACKOFF_SCHEDULE.to_vec())
+        .into_iter()
+        .map(|delay| delay as i32)
+        .collect::<Vec<_>>();
+      tx.execute(
+        "INSERT INTO kv_queue
+          (payload, deadline, keys_if_undelivered, backoff_schedule)
+          VALUES ($1, $2, $3, $4)",
+        &[
+          &enqueue.payload,
+          &enqueue.deadline.timestamp_millis(),
+          &enqueue.keys_if_undelivered,
+          &backoff_schedule,
+        ],
+      )
+      .await
+      .map_err(pg_error)?;
+    }
+    tx.batch_execute("NOTIFY kv_queue")
+      .await
+      .map_err(pg_error)?;
+  }
+  tx.batch_execute("NOTIFY kv_watch")
+    .await
+    .map_err(pg_error)?;
+
+  Ok(Some(commit_versionstamp))
+}
+
+#[async_trait(?Send)]
+impl Database for PostgresDb {
+  type QMH = PostgresQueueMessageHandle;
+
+  async fn snapshot_read(
+    &self,
+    requests: Vec<ReadRange>,
+    _options: SnapshotReadOptions,
+  ) -> Result<Vec<ReadRangeOutput>, JsErrorBox> {
+    self.check_open()?;
+    let mut client = self.client.lock().await;
+    let client = client.as_mut().ok_or_else(closed_error)?;
+    let tx = client
+      .build_transaction()
+      .isolation_level(IsolationLevel::RepeatableRead)
+      .read_only(true)
+      .start()
+      .await
+      .map_err(pg_error)?;
+    let now = Utc::now().timestamp_millis();
+    let mut outputs = Vec::with_capacity(requests.len());
+    for request in requests {
+      let query = if request.reverse {
+        "SELECT key, value, encoding, version FROM kv
+          WHERE key >= $1 AND key < $2
+            AND (expires_at IS NULL OR expires_at > $3)
+          ORDER BY key DESC LIMIT $4"
+      } else {
+        "SELECT key, value, encoding, version FROM kv
+          WHERE key >= $1 AND key < $2
+            AND (expires_at IS NULL OR expires_at > $3)
+          ORDER BY key ASC LIMIT $4"
+      };
+      let rows = tx
+        .query(
+          query,
+          &[
+            &request.start,
+            &request.end,
+            &now,
+            &(request.limit.get() as i64),
+          ],
+        )
+        .await
+        .map_err(pg_error)?;
+      let entries = rows
+        .into_iter()
+        .map(row_to_entry)
+        .collect::<Result<Vec<_>, _>>()?;
+      outputs.push(ReadRangeOutput { entries });
+    }
+    tx.commit().await.map_err(pg_error)?;
+    Ok(outputs)
+  }
+
+  async fn atomic_write(
+    &self,
+    write: AtomicWrite,
+  ) -> Result<Option<CommitResult>, JsErrorBox> {
+    self.check_open()?;
+    let mut client = self.client.lock().await;
+    let client = client.as_mut().ok_or_else(closed_error)?;
+    let tx = client.transaction().await.map_err(pg_error)?;
+    // A failed check or error drops `tx`, which rolls it back.
+    let Some(versionstamp) = write_in_transaction(&tx, write).await? else {
+      return Ok(None);
+    };
+    tx.commit().await.map_err(pg_error)?;
+    Ok(Some(CommitResult { versionstamp }))
+  }
+
+  async fn dequeue_next_message(
+    &self,
+  ) -> Result<Option<Self::QMH>, JsErrorBox> {
+    loop {
+      let notified = self.notifications.queue.notified();
+      tokio::pin!(notified);
+      notified.as_mut().enable();
+
+      if self.closed.get() {
+        return Ok(None);
+      }
+      let row = {
+        let client = self.client.lock().await;
+        let Some(client) = client.as_ref() else {
+          return Ok(None);
+        };
+        let now = Utc::now().timestamp_millis();
+        client
+          .query_opt(
+            "UPDATE kv_queue SET locked_until = $1
+              WHERE id = (
+                SELECT id FROM kv_queue
+                  WHERE deadline <= $2
+                    AND (locked_until IS NULL OR locked_until <= $2)
+                  ORDER BY deadline
+                  LIMIT 1
+                  FOR UPDATE SKIP LOCKED
+              )
+              RETURNING id, payload, keys_if_undelivered, backoff_schedule",
+            &[&(now + QUEUE_LEASE_MS), &now],
+          )
+          .await
+          .map_err(pg_error)?
+      };
+      if let Some(row) = row {
+        return Ok(Some(PostgresQueueMessageHandle {
+          db: self.clone(),
+          id: row.get(0),
+          payload: row.get(1),
+          keys_if_undelivered: row.get(2),
+          backoff_schedule: row.get(3),
+        }));
+      }
+
+      tokio::select! {
+        _ = notified => {}
+        _ = tokio::time::sleep(QUEUE_POLL_INTERVAL) => {}
+      }
+    }
+  }
+
+  fn watch(&self, keys: Vec<Vec<u8>>) -> WatchStream {
+    let db = self.clone();
+    let receiver = self.notifications.changes.subscribe();
+    let last: Option<Vec<Option<Versionstamp>>> = None;
+    // The state is dropped after an error, which ends the stream.
+    stream::unfold(Some((db, keys, receiver, last)), |state| async move {
+      let (db, keys, mut receiver, last) = state?;
+      loop {
+        if db.closed.get() {
+          return None;
+        }
+        receiver.borrow_and_update();
+        let entries = match db.read_entries(&keys).await {
+          Ok(entries) => entries,
+          Err(err) => return Some((Err(err), None)),
+        };
+        let versionstamps = entries
+          .iter()
+          .map(|entry| entry.as_ref().map(|entry| entry.versionstamp))
+          .collect::<Vec<_>>();
+        let outputs = match &last {
+          None => entries
+            .into_iter()
+            .map(|entry| WatchKeyOutput::Changed { entry })
+            .collect(),
+          Some(last) => {
+            let mut changed = false;
+            let outputs = entries
+              .into_iter()
+              .zip(last)
+              .map(|(entry, last)| {
+                if entry.as_ref().map(|entry| entry.versionstamp) == *last {
+                  WatchKeyOutput::Unchanged
+                } else {
+                  changed = true;
+                  WatchKeyOutput::Changed { entry }
+                }
+              })
+              .collect::<Vec<_>>();
+            if !changed {
+              if receiver.changed().await.is_err() {
+                return None;
+              }
+              continue;
+            }
+            outputs
+          }
+        };
+        return Some((
+          Ok(outputs),
+          Some((db, keys, receiver, Some(versionstamps))),
+        ));
+      }
+    })
+    .boxed_local()
+  }
+
+  fn close(&self) {
+    self.closed.set(true);
+    self.notifications.wake_all();
+    // Dropping the client ends the connection once pending operations are
+    // done.
+    let client = self.client.clone();
+    deno_core::unsync::spawn(async move {
+      client.lock().await.take();
+    });
+  }
+}
+
+impl QueueAdmin for PostgresDb {}
+
+pub struct PostgresQueueMessageHandle {
+  db: PostgresDb,
+  id: i64,
+  payload: Vec<u8>,
+  keys_if_undelivered: Vec<Vec<u8>>,
+  backoff_schedule: Vec<i32>,
+}
+
+#[async_trait(?Send)]
+impl QueueMessageHandle for PostgresQueueMessageHandle {
+  async fn take_payload(&mut self) -> Result<Vec<u8>, JsErrorBox> {
+    Ok(self.payload.clone())
+  }
+
+  async fn finish(&self, success: bool) -> Result<(), JsErrorBox> {
+    let mut client = self.db.client.lock().await;
+    let client = client.as_mut().ok_or_else(closed_error)?;
+
+    if success {
+      client
+        .execute("DELETE FROM kv_queue WHERE id = $1", &[&self.id])
+        .await
+        .map_err(pg_error)?;
+      return Ok(());
+    }
+
+    if let Some((delay, backoff_schedule)) = self.backoff_schedule.split_first()
+    {
+      let deadline = Utc::now().timestamp_millis() + *delay as i64;
+      client
+        .execute(
+          "UPDATE kv_queue
+            SET deadline = $2, backoff_schedule = $3, locked_until = NULL
+            WHERE id = $1",
+          &[&self.id, &deadline, &backoff_schedule],
+        )
+        .await
+        .map_err(pg_error)?;
+      return Ok(());
+    }
+
+    // Out of retries: the message is dead-lettered into its
+    // `keysIfUndelivered`, in the same transaction that removes it.
+    let tx = client.transaction().await.map_err(pg_error)?;
+    tx.execute("DELETE FROM kv_queue WHERE id = $1", &[&self.id])
+      .await
+      .map_err(pg_error)?;
+    if !self.keys_if_undelivered.is_empty() {
+      let write = AtomicWrite {
+        checks: vec![],
+        mutations: self
+          .keys_if_undelivered
+          .iter()
+          .map(|key| Mutation {
+            key: key.clone(),
+            kind: MutationKind::Set(KvValue::V8(self.payload.clone())),
+            expire_at: None,
+          })
+          .collect(),
+        enqueues: vec![],
+      };
+      write_in_transaction(&tx, write).await?;
+    }
+    tx.commit().await.map_err(pg_error)?;
+    Ok(())
+  }
+}
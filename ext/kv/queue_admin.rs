@@ -0,0 +1,729 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Inspection and administration of the queue of SQLite backed KV
+//! databases.
+//!
+//! The queue itself is implemented by `denokv_sqlite`, which keeps waiting
+//! messages in the `queue` table and messages that are being delivered in
+//! `queue_running`. This module adds two tables next to them:
+//!
+//! - `queue_attempts` counts the deliveries of every message.
+//! - `queue_dead` keeps the messages whose last delivery failed, so that
+//!   they can be inspected and redriven.
+//!
+//! Both are only created once delivery tracking is enabled for the database
+//! with `queue_track`, because it adds a trigger and an index to the
+//! `queue_running` table of `denokv_sqlite`. The trigger counts the
+//! deliveries made by any process using the database, while dead messages
+//! are recorded by the process that delivered them, see
+//! `SqliteQueueAdmin::dead_letter_if_exhausted`. `queue_dead` is kept when
+//! tracking is disabled again, so that its messages can still be redriven.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use chrono::DateTime;
+use chrono::Utc;
+use deno_core::unsync::spawn_blocking;
+use deno_error::JsErrorBox;
+use denokv_proto::AtomicWrite;
+use denokv_proto::Database;
+use denokv_proto::Enqueue;
+use denokv_proto::decode_key;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::TransactionBehavior;
+use rusqlite::params;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::backup::JsonKeyPart;
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of messages that are redriven in a single atomic write.
+const REDRIVE_BATCH_SIZE: usize = 100;
+
+/// The tables of this module, which `backup::restore` leaves alone.
+pub(crate) const ADMIN_TABLES: [&str; 2] = ["queue_attempts", "queue_dead"];
+
+const SCHEMA: &str = "
+  CREATE TABLE IF NOT EXISTS queue_attempts (
+    id TEXT PRIMARY KEY,
+    attempts INTEGER NOT NULL
+  );
+  CREATE TABLE IF NOT EXISTS queue_dead (
+    id TEXT PRIMARY KEY,
+    failed_at INTEGER NOT NULL,
+    data BLOB NOT NULL,
+    keys_if_undelivered BLOB NOT NULL,
+    attempts INTEGER NOT NULL
+  );
+";
+
+/// The changes to the tables of `denokv_sqlite` that delivery tracking
+/// needs. The index is used to find the id of a dequeued message.
+const TRACKING_SCHEMA: &str = "
+  CREATE TRIGGER IF NOT EXISTS queue_running_count_attempts
+    AFTER INSERT ON queue_running
+  BEGIN
+    INSERT INTO queue_attempts (id, attempts) VALUES (new.id, 1)
+      ON CONFLICT (id) DO UPDATE SET attempts = attempts + 1;
+  END;
+  CREATE INDEX IF NOT EXISTS queue_running_data ON queue_running (data);
+";
+
+/// Reverts `TRACKING_SCHEMA`. Dead messages are kept, so that they can
+/// still be redriven.
+const UNTRACKING_SCHEMA: &str = "
+  DROP TRIGGER IF EXISTS queue_running_count_attempts;
+  DROP INDEX IF EXISTS queue_running_data;
+  DROP TABLE IF EXISTS queue_attempts;
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueueMessageState {
+  /// Waiting for its first delivery or for a retry.
+  Pending,
+  /// Being delivered.
+  Running,
+  /// Failed its last delivery.
+  Dead,
+}
+
+impl QueueMessageState {
+  pub const ALL: [QueueMessageState; 3] = [
+    QueueMessageState::Pending,
+    QueueMessageState::Running,
+    QueueMessageState::Dead,
+  ];
+}
+
+#[derive(Debug)]
+pub struct QueueMessageInfo {
+  pub id: String,
+  pub state: QueueMessageState,
+  /// The V8 serialized message.
+  pub payload: Vec<u8>,
+  /// When the message is delivered if it is pending, when its delivery
+  /// times out if it is running, and when it failed if it is dead.
+  pub timestamp: DateTime<Utc>,
+  pub delivery_attempts: u64,
+  /// The delays of the remaining retries, in milliseconds.
+  pub backoff_schedule: Vec<u32>,
+  pub keys_if_undelivered: Vec<Vec<u8>>,
+}
+
+/// A queue message in the NDJSON format of `deno kv queue list`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonQueueMessage {
+  pub id: String,
+  pub state: QueueMessageState,
+  /// The base64 encoded V8 serialized message.
+  pub payload: String,
+  pub timestamp: DateTime<Utc>,
+  pub delivery_attempts: u64,
+  pub backoff_schedule: Vec<u32>,
+  pub keys_if_undelivered: Vec<Vec<JsonKeyPart>>,
+}
+
+impl TryFrom<QueueMessageInfo> for JsonQueueMessage {
+  type Error = std::io::Error;
+
+  fn try_from(message: QueueMessageInfo) -> Result<Self, Self::Error> {
+    Ok(JsonQueueMessage {
+      id: message.id,
+      state: message.state,
+      payload: BASE64_STANDARD.encode(&message.payload),
+      timestamp: message.timestamp,
+      delivery_attempts: message.delivery_attempts,
+      backoff_schedule: message.backoff_schedule,
+      keys_if_undelivered: message
+        .keys_if_undelivered
+        .iter()
+        .map(|key| {
+          Ok(
+            decode_key(key)?
+              .0
+              .into_iter()
+              .map(JsonKeyPart::from)
+              .collect(),
+          )
+        })
+        .collect::<Result<_, std::io::Error>>()?,
+    })
+  }
+}
+
+pub(crate) fn unsupported_error() -> JsErrorBox {
+  JsErrorBox::new(
+    "NotSupported",
+    "Queue administration is not supported by this database",
+  )
+}
+
+fn sqlite_error(err: rusqlite::Error) -> JsErrorBox {
+  JsErrorBox::generic(err.to_string())
+}
+
+fn now_ms() -> i64 {
+  Utc::now().timestamp_millis()
+}
+
+/// `denokv_sqlite` stores the backoff schedule and the keys as JSON.
+fn decode_backoff_schedule(text: &str) -> Vec<u32> {
+  serde_json::from_str::<Option<Vec<u32>>>(text)
+    .ok()
+    .flatten()
+    .unwrap_or_default()
+}
+
+fn decode_keys(data: &[u8]) -> Vec<Vec<u8>> {
+  serde_json::from_slice(data).unwrap_or_default()
+}
+
+fn has_schema_entry(
+  conn: &Connection,
+  kind: &str,
+  name: &str,
+) -> rusqlite::Result<bool> {
+  conn
+    .query_row(
+      "SELECT 1 FROM sqlite_master WHERE type = ?1 AND name = ?2",
+      [kind, name],
+      |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+}
+
+/// The administration tables that exist in a database.
+#[derive(Clone, Copy)]
+struct AdminTables {
+  /// Whether delivery tracking is enabled, which also means that
+  /// `queue_attempts` exists.
+  tracking: bool,
+  /// Whether `queue_dead` exists.
+  dead: bool,
+}
+
+impl AdminTables {
+  fn read(conn: &Connection) -> rusqlite::Result<Self> {
+    Ok(Self {
+      tracking: has_schema_entry(
+        conn,
+        "trigger",
+        "queue_running_count_attempts",
+      )?,
+      dead: has_schema_entry(conn, "table", "queue_dead")?,
+    })
+  }
+}
+
+/// Queue administration for a SQLite database, over a connection of its
+/// own.
+pub struct SqliteQueueAdmin {
+  conn: Arc<Mutex<Connection>>,
+  /// The administration tables of the database when it was opened.
+  tables: AdminTables,
+  /// The ids of the running messages delivered through this handle.
+  claimed: RefCell<HashSet<String>>,
+}
+
+impl SqliteQueueAdmin {
+  /// The queue tables must have been created by the backend already.
+  pub fn new(conn: Connection) -> rusqlite::Result<Self> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    let tables = AdminTables::read(&conn)?;
+    Ok(Self {
+      conn: Arc::new(Mutex::new(conn)),
+      tables,
+      claimed: RefCell::new(HashSet::new()),
+    })
+  }
+
+  /// Whether the deliveries made through this handle should be tracked.
+  pub(crate) fn is_tracking(&self) -> bool {
+    self.tables.tracking
+  }
+
+  /// Opens the database at `path` along with its backend.
+  fn open(path: &Path) -> Result<(denokv_sqlite::Sqlite, Self), JsErrorBox> {
+    // Opening the backend first creates the queue tables.
+    let db = crate::backup::open(path).map_err(JsErrorBox::from_err)?;
+    let admin = match Connection::open(path).and_then(Self::new) {
+      Ok(admin) => admin,
+      Err(err) => {
+        db.close();
+        return Err(sqlite_error(err));
+      }
+    };
+    Ok((db, admin))
+  }
+
+  async fn run<T: Send + 'static>(
+    &self,
+    f: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+  ) -> Result<T, JsErrorBox> {
+    let conn = self.conn.clone();
+    spawn_blocking(move || f(&mut conn.lock().unwrap()))
+      .await
+      .unwrap()
+      .map_err(sqlite_error)
+  }
+
+  pub async fn list(
+    &self,
+    states: Vec<QueueMessageState>,
+    limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+    let tables = self.tables;
+    self
+      .run(move |conn| list(conn, tables, &states, limit))
+      .await
+  }
+
+  /// Deletes the messages in the given states, and returns their number.
+  /// Running messages that are deleted are not retried if their delivery
+  /// fails.
+  pub async fn purge(
+    &self,
+    states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox> {
+    let tables = self.tables;
+    self.run(move |conn| purge(conn, tables, &states)).await
+  }
+
+  /// Enqueues dead messages again through `db`, with the default backoff
+  /// schedule, and returns their number. Without `ids`, all dead messages
+  /// are redriven.
+  pub async fn redrive(
+    &self,
+    db: &impl Database,
+    ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox> {
+    if !self.tables.dead {
+      return Ok(0);
+    }
+    let mut count = 0;
+    loop {
+      let ids = ids.clone();
+      let batch = self
+        .run(move |conn| dead_messages(conn, ids.as_deref()))
+        .await?;
+      if batch.is_empty() {
+        return Ok(count);
+      }
+
+      let now = Utc::now();
+      let write = AtomicWrite {
+        checks: vec![],
+        mutations: vec![],
+        enqueues: batch
+          .iter()
+          .map(|message| Enqueue {
+            payload: message.payload.clone(),
+            deadline: now,
+            keys_if_undelivered: message.keys_if_undelivered.clone(),
+            backoff_schedule: None,
+          })
+          .collect(),
+      };
+      // Messages are enqueued before they are removed, so that a failure
+      // in between delivers them twice rather than losing them.
+      if db.atomic_write(write).await?.is_none() {
+        return Err(JsErrorBox::generic("Failed to enqueue messages"));
+      }
+      let ids = batch
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+      count += ids.len() as u64;
+      self
+        .run(move |conn| {
+          let tx = conn.transaction()?;
+          for id in ids {
+            tx.execute("DELETE FROM queue_dead WHERE id = ?1", [id])?;
+          }
+          tx.commit()
+        })
+        .await?;
+    }
+  }
+
+  /// Finds the id of a message that was just dequeued by the backend, which
+  /// doesn't expose it. Running messages are told apart by their payload,
+  /// so messages with identical payloads that are delivered at the same
+  /// time by different processes may be confused with each other. Only
+  /// called when tracking is enabled, which indexes the payloads.
+  pub(crate) async fn claim(&self, payload: Vec<u8>) -> Option<String> {
+    let ids = self
+      .run(move |conn| {
+        let mut stmt = conn.prepare_cached(
+          "SELECT id FROM queue_running WHERE data = ?1 ORDER BY deadline DESC",
+        )?;
+        stmt
+          .query_map([payload], |row| row.get::<_, String>(0))?
+          .collect::<rusqlite::Result<Vec<_>>>()
+      })
+      .await;
+    let ids = match ids {
+      Ok(ids) => ids,
+      Err(err) => {
+        log::warn!("Failed to track the delivery of a queue message: {err}");
+        return None;
+      }
+    };
+    let mut claimed = self.claimed.borrow_mut();
+    let id = ids.into_iter().find(|id| !claimed.contains(id))?;
+    claimed.insert(id.clone());
+    Some(id)
+  }
+
+  /// Records the running message `id` as dead if it has no retries left.
+  /// Must be called before the backend is told about the failure.
+  pub(crate) async fn dead_letter_if_exhausted(&self, id: String) {
+    let result = self
+      .run(move |conn| {
+        let tx =
+          conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let message = tx
+          .query_row(
+            "SELECT r.data, r.backoff_schedule, r.keys_if_undelivered,
+                COALESCE(a.attempts, 0)
+              FROM queue_running r LEFT JOIN queue_attempts a ON a.id = r.id
+              WHERE r.id = ?1",
+            [&id],
+            |row| {
+              Ok((
+                row.get::<_, Vec<u8>>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+                row.get::<_, i64>(3)?,
+              ))
+            },
+          )
+          .optional()?;
+        if let Some((data, backoff_schedule, keys_if_undelivered, attempts)) =
+          message
+          && decode_backoff_schedule(&backoff_schedule).is_empty()
+        {
+          tx.execute(
+            "INSERT OR REPLACE INTO queue_dead
+              (id, failed_at, data, keys_if_undelivered, attempts)
+              VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, now_ms(), data, keys_if_undelivered, attempts],
+          )?;
+        }
+        tx.commit()
+      })
+      .await;
+    if let Err(err) = result {
+      log::warn!("Failed to record a dead queue message: {err}");
+    }
+  }
+
+  /// Forgets the message `id` once the backend is done with its delivery.
+  pub(crate) async fn release(&self, id: String) {
+    self.claimed.borrow_mut().remove(&id);
+    let result = self
+      .run(move |conn| {
+        conn.execute(
+          "DELETE FROM queue_attempts
+            WHERE id = ?1 AND NOT EXISTS (SELECT 1 FROM queue WHERE id = ?1)",
+          [id],
+        )
+      })
+      .await;
+    if let Err(err) = result {
+      log::warn!("Failed to track the delivery of a queue message: {err}");
+    }
+  }
+}
+
+/// Enables or disables delivery tracking for the database at `db_path`.
+/// Processes that have the database open already keep their current
+/// behavior until they open it again.
+pub async fn queue_track(
+  db_path: &Path,
+  enable: bool,
+) -> Result<(), JsErrorBox> {
+  let (db, admin) = SqliteQueueAdmin::open(db_path)?;
+  let result = admin
+    .run(move |conn| {
+      if enable {
+        conn.execute_batch(SCHEMA)?;
+        conn.execute_batch(TRACKING_SCHEMA)
+      } else {
+        conn.execute_batch(UNTRACKING_SCHEMA)
+      }
+    })
+    .await;
+  db.close();
+  result
+}
+
+/// Lists the queue messages of the database at `db_path` that are in one
+/// of `states`.
+pub async fn queue_list(
+  db_path: &Path,
+  states: Vec<QueueMessageState>,
+  limit: usize,
+) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+  let (db, admin) = SqliteQueueAdmin::open(db_path)?;
+  let result = admin.list(states, limit).await;
+  db.close();
+  result
+}
+
+/// Deletes the queue messages of the database at `db_path` that are in one
+/// of `states`, and returns their number.
+pub async fn queue_purge(
+  db_path: &Path,
+  states: Vec<QueueMessageState>,
+) -> Result<u64, JsErrorBox> {
+  let (db, admin) = SqliteQueueAdmin::open(db_path)?;
+  let result = admin.purge(states).await;
+  db.close();
+  result
+}
+
+/// Enqueues the dead messages of the database at `db_path` again, and
+/// returns their number. Without `ids`, all dead messages are redriven.
+pub async fn queue_redrive(
+  db_path: &Path,
+  ids: Option<Vec<String>>,
+) -> Result<u64, JsErrorBox> {
+  let (db, admin) = SqliteQueueAdmin::open(db_path)?;
+  let result = admin.redrive(&db, ids).await;
+  db.close();
+  result
+}
+
+fn list(
+  conn: &Connection,
+  tables: AdminTables,
+  states: &[QueueMessageState],
+  limit: usize,
+) -> rusqlite::Result<Vec<QueueMessageInfo>> {
+  let mut messages = vec![];
+  for state in QueueMessageState::ALL {
+    if !states.contains(&state) || messages.len() >= limit {
+      continue;
+    }
+    // Deliveries are only counted while tracking is enabled.
+    let query = match (state, tables.tracking) {
+      (QueueMessageState::Pending, true) => {
+        "SELECT q.id, q.data, q.ts, q.backoff_schedule, q.keys_if_undelivered,
+            COALESCE(a.attempts, 0)
+          FROM queue q LEFT JOIN queue_attempts a ON a.id = q.id
+          ORDER BY q.ts LIMIT ?1"
+      }
+      (QueueMessageState::Pending, false) => {
+        "SELECT id, data, ts, backoff_schedule, keys_if_undelivered, 0
+          FROM queue ORDER BY ts LIMIT ?1"
+      }
+      (QueueMessageState::Running, true) => {
+        "SELECT r.id, r.data, r.deadline, r.backoff_schedule,
+            r.keys_if_undelivered, COALESCE(a.attempts, 0)
+          FROM queue_running r LEFT JOIN queue_attempts a ON a.id = r.id
+          ORDER BY r.deadline LIMIT ?1"
+      }
+      (QueueMessageState::Running, false) => {
+        "SELECT id, data, deadline, backoff_schedule, keys_if_undelivered, 0
+          FROM queue_running ORDER BY deadline LIMIT ?1"
+      }
+      (QueueMessageState::Dead, _) if !tables.dead => continue,
+      (QueueMessageState::Dead, _) => {
+        "SELECT id, data, failed_at, '[]', keys_if_undelivered, attempts
+          FROM queue_dead ORDER BY failed_at LIMIT ?1"
+      }
+    };
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt.query_map([(limit - messages.len()) as i64], |row| {
+      Ok(QueueMessageInfo {
+        id: row.get(0)?,
+        state,
+        payload: row.get(1)?,
+        timestamp: DateTime::from_timestamp_millis(row.get(2)?)
+          .unwrap_or_default(),
+        backoff_schedule: decode_backoff_schedule(&row.get::<_, String>(3)?),
+        keys_if_undelivered: decode_keys(&row.get::<_, Vec<u8>>(4)?),
+        delivery_attempts: row.get::<_, i64>(5)? as u64,
+      })
+    })?;
+    for message in rows {
+      messages.push(message?);
+    }
+  }
+  Ok(messages)
+}
+
+fn purge(
+  conn: &mut Connection,
+  tables: AdminTables,
+  states: &[QueueMessageState],
+) -> rusqlite::Result<u64> {
+  let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+  let mut count = 0;
+  for state in states {
+    let table = match state {
+      QueueMessageState::Pending => "queue",
+      QueueMessageState::Running => "queue_running",
+      QueueMessageState::Dead if !tables.dead => continue,
+      QueueMessageState::Dead => "queue_dead",
+    };
+    count += tx.execute(&format!("DELETE FROM {table}"), [])? as u64;
+  }
+  if tables.tracking {
+    tx.execute(
+      "DELETE FROM queue_attempts WHERE id NOT IN (
+        SELECT id FROM queue UNION ALL SELECT id FROM queue_running
+      )",
+      [],
+    )?;
+  }
+  tx.commit()?;
+  Ok(count)
+}
+
+struct DeadMessage {
+  id: String,
+  payload: Vec<u8>,
+  keys_if_undelivered: Vec<Vec<u8>>,
+}
+
+fn dead_messages(
+  conn: &Connection,
+  ids: Option<&[String]>,
+) -> rusqlite::Result<Vec<DeadMessage>> {
+  let map_row = |row: &rusqlite::Row| {
+    Ok(DeadMessage {
+      id: row.get(0)?,
+      payload: row.get(1)?,
+      keys_if_undelivered: decode_keys(&row.get::<_, Vec<u8>>(2)?),
+    })
+  };
+  match ids {
+    None => {
+      let mut stmt = conn.prepare(
+        "SELECT id, data, keys_if_undelivered FROM queue_dead
+          ORDER BY failed_at LIMIT ?1",
+      )?;
+      stmt
+        .query_map([REDRIVE_BATCH_SIZE as i64], map_row)?
+        .collect()
+    }
+    Some(ids) => {
+      let mut stmt = conn.prepare(
+        "SELECT id, data, keys_if_undelivered FROM queue_dead WHERE id = ?1",
+      )?;
+      let mut messages = vec![];
+      for id in ids {
+        if let Some(message) = stmt.query_row([id], map_row).optional()? {
+          messages.push(message);
+          if messages.len() == REDRIVE_BATCH_SIZE {
+            break;
+          }
+        }
+      }
+      Ok(messages)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use denokv_proto::QueueMessageHandle;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn test_dead_letter_and_redrive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("kv.sqlite3");
+    let (db, admin) = SqliteQueueAdmin::open(&path).unwrap();
+    assert!(!admin.is_tracking());
+    assert!(!admin.tables.dead);
+    let all = admin.list(QueueMessageState::ALL.to_vec(), 10).await;
+    assert!(all.unwrap().is_empty());
+    db.close();
+    queue_track(&path, true).await.unwrap();
+    let (db, admin) = SqliteQueueAdmin::open(&path).unwrap();
+    assert!(admin.is_tracking());
+    db.atomic_write(AtomicWrite {
+      checks: vec![],
+      mutations: vec![],
+      enqueues: vec![Enqueue {
+        payload: b"message".to_vec(),
+        deadline: Utc::now(),
+        keys_if_undelivered: vec![vec![0x02, b'a', 0x00]],
+        backoff_schedule: Some(vec![]),
+      }],
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    let pending = admin.list(vec![QueueMessageState::Pending], 10).await;
+    assert_eq!(pending.unwrap()[0].delivery_attempts, 0);
+
+    let mut handle = db.dequeue_next_message().await.unwrap().unwrap();
+    let payload = handle.take_payload().await.unwrap();
+    let id = admin.claim(payload).await.unwrap();
+    let running = admin.list(QueueMessageState::ALL.to_vec(), 10).await;
+    let running = running.unwrap();
+    assert_eq!(running.len(), 1);
+    assert_eq!(running[0].id, id);
+    assert_eq!(running[0].state, QueueMessageState::Running);
+    assert_eq!(running[0].delivery_attempts, 1);
+
+    admin.dead_letter_if_exhausted(id.clone()).await;
+    handle.finish(false).await.unwrap();
+    admin.release(id.clone()).await;
+    let dead = admin.list(QueueMessageState::ALL.to_vec(), 10).await;
+    let dead = dead.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].id, id);
+    assert_eq!(dead[0].state, QueueMessageState::Dead);
+    assert_eq!(dead[0].payload, b"message");
+    assert_eq!(dead[0].delivery_attempts, 1);
+    assert_eq!(dead[0].keys_if_undelivered, vec![vec![0x02, b'a', 0x00]]);
+
+    assert_eq!(admin.redrive(&db, Some(vec!["x".into()])).await.unwrap(), 0);
+    assert_eq!(admin.redrive(&db, None).await.unwrap(), 1);
+    let pending = admin.list(QueueMessageState::ALL.to_vec(), 10).await;
+    let pending = pending.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].state, QueueMessageState::Pending);
+    assert_eq!(pending[0].payload, b"message");
+    assert!(!pending[0].backoff_schedule.is_empty());
+
+    assert_eq!(admin.purge(vec![QueueMessageState::Dead]).await.unwrap(), 0);
+    assert_eq!(
+      admin.purge(vec![QueueMessageState::Pending]).await.unwrap(),
+      1
+    );
+    assert!(
+      admin
+        .list(QueueMessageState::ALL.to_vec(), 10)
+        .await
+        .unwrap()
+        .is_empty()
+    );
+    db.close();
+
+    queue_track(&path, false).await.unwrap();
+    let (db, admin) = SqliteQueueAdmin::open(&path).unwrap();
+    assert!(!admin.is_tracking());
+    assert!(admin.tables.dead);
+    db.close();
+  }
+}
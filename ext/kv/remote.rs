@@ -26,6 +26,7 @@ use http_body_util::BodyExt;
 use url::Url;
 
 use crate::DatabaseHandler;
+use crate::QueueAdmin;
 
 #[derive(Clone)]
 pub struct HttpOptions {
@@ -244,3 +245,8 @@ impl<P: RemoteDbHandlerPermissions + 'static> DatabaseHandler
     Ok(remote)
   }
 }
+
+impl<P: RemoteDbHandlerPermissions + 'static> QueueAdmin
+  for Remote<PermissionChecker<P>, FetchClient>
+{
+}
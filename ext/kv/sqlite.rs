@@ -18,6 +18,14 @@ use deno_error::JsErrorBox;
 use deno_permissions::CheckedPath;
 use deno_permissions::OpenAccessKind;
 use deno_permissions::PermissionCheckError;
+use denokv_proto::AtomicWrite;
+use denokv_proto::CommitResult;
+use denokv_proto::Database;
+use denokv_proto::QueueMessageHandle;
+use denokv_proto::ReadRange;
+use denokv_proto::ReadRangeOutput;
+use denokv_proto::SnapshotReadOptions;
+use denokv_proto::WatchStream;
 pub use denokv_sqlite::SqliteBackendError;
 use denokv_sqlite::SqliteConfig;
 use denokv_sqlite::SqliteNotifier;
@@ -25,6 +33,11 @@ use rand::SeedableRng;
 use rusqlite::OpenFlags;
 
 use crate::DatabaseHandler;
+use crate::QueueAdmin;
+use crate::queue_admin::QueueMessageInfo;
+use crate::queue_admin::QueueMessageState;
+use crate::queue_admin::SqliteQueueAdmin;
+use crate::queue_admin::unsupported_error;
 
 static SQLITE_NOTIFIERS_MAP: OnceLock<Mutex<HashMap<PathBuf, SqliteNotifier>>> =
   OnceLock::new();
@@ -89,7 +102,7 @@ enum Mode {
 
 #[async_trait(?Send)]
 impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
-  type DB = denokv_sqlite::Sqlite;
+  type DB = SqliteDb;
 
   async fn open(
     &self,
@@ -136,8 +149,6 @@ impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
 
     let path = validate_path::<P>(&state, path)?;
     let default_storage_dir = self.default_storage_dir.clone();
-    type ConnGen =
-      Arc<dyn Fn() -> rusqlite::Result<rusqlite::Connection> + Send + Sync>;
     let (conn_gen, notifier_key): (ConnGen, _) = spawn_blocking(move || {
       denokv_sqlite::sqlite_retry_loop(move || {
         let mode = match std::env::var("DENO_KV_DB_MODE")
@@ -200,6 +211,7 @@ impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
     .unwrap()
     .map_err(JsErrorBox::from_err)?;
 
+    let has_storage = notifier_key.is_some();
     let notifier = if let Some(notifier_key) = notifier_key {
       SQLITE_NOTIFIERS_MAP
         .get_or_init(Default::default)
@@ -219,10 +231,11 @@ impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
       num_workers: 1,
     };
 
-    denokv_sqlite::Sqlite::new(
+    let backend_conn_gen = conn_gen.clone();
+    let inner = denokv_sqlite::Sqlite::new(
       move || {
         let conn =
-          conn_gen().map_err(|e| JsErrorBox::generic(e.to_string()))?;
+          backend_conn_gen().map_err(|e| JsErrorBox::generic(e.to_string()))?;
         conn
           .pragma_update(None, "journal_mode", "wal")
           .map_err(|e| JsErrorBox::generic(e.to_string()))?;
@@ -237,6 +250,162 @@ impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
       notifier,
       config,
     )
-    .map_err(|e| JsErrorBox::generic(e.to_string()))
+    .map_err(|e| JsErrorBox::generic(e.to_string()))?;
+
+    // In-memory databases can't be opened by a second connection. The
+    // connection that tracks deliveries is only kept if tracking is enabled.
+    let conn_gen = has_storage.then_some(conn_gen);
+    let queue_admin = match conn_gen
+      .as_ref()
+      .map(|conn_gen| conn_gen().and_then(SqliteQueueAdmin::new))
+    {
+      Some(Ok(queue_admin)) if queue_admin.is_tracking() => {
+        Some(Rc::new(queue_admin))
+      }
+      Some(Err(err)) => {
+        log::warn!("Failed to set up queue administration: {err}");
+        None
+      }
+      _ => None,
+    };
+
+    Ok(SqliteDb {
+      inner,
+      conn_gen,
+      queue_admin,
+    })
+  }
+}
+
+type ConnGen =
+  Arc<dyn Fn() -> rusqlite::Result<rusqlite::Connection> + Send + Sync>;
+
+/// A database of the SQLite backend. Deliveries of queue messages are
+/// tracked for queue administration when the database is stored on disk and
+/// tracking was enabled for it.
+#[derive(Clone)]
+pub struct SqliteDb {
+  inner: denokv_sqlite::Sqlite,
+  /// Opens another connection to the database, if it is stored on disk.
+  conn_gen: Option<ConnGen>,
+  /// Set if tracking was enabled when the database was opened.
+  queue_admin: Option<Rc<SqliteQueueAdmin>>,
+}
+
+impl SqliteDb {
+  /// Returns the queue administration of the database, over a new
+  /// connection if deliveries aren't tracked.
+  fn queue_admin(&self) -> Result<Rc<SqliteQueueAdmin>, JsErrorBox> {
+    if let Some(queue_admin) = &self.queue_admin {
+      return Ok(queue_admin.clone());
+    }
+    let conn_gen = self.conn_gen.as_ref().ok_or_else(unsupported_error)?;
+    conn_gen()
+      .and_then(SqliteQueueAdmin::new)
+      .map(Rc::new)
+      .map_err(|e| JsErrorBox::generic(e.to_string()))
+  }
+}
+
+#[async_trait(?Send)]
+impl Database for SqliteDb {
+  type QMH = SqliteQueueMessageHandle;
+
+  async fn snapshot_read(
+    &self,
+    requests: Vec<ReadRange>,
+    options: SnapshotReadOptions,
+  ) -> Result<Vec<ReadRangeOutput>, JsErrorBox> {
+    self.inner.snapshot_read(requests, options).await
+  }
+
+  async fn atomic_write(
+    &self,
+    write: AtomicWrite,
+  ) -> Result<Option<CommitResult>, JsErrorBox> {
+    self.inner.atomic_write(write).await
+  }
+
+  async fn dequeue_next_message(
+    &self,
+  ) -> Result<Option<Self::QMH>, JsErrorBox> {
+    let Some(mut inner) = self.inner.dequeue_next_message().await? else {
+      return Ok(None);
+    };
+    let payload = inner.take_payload().await?;
+    let claim = match &self.queue_admin {
+      Some(queue_admin) if queue_admin.is_tracking() => queue_admin
+        .claim(payload.clone())
+        .await
+        .map(|id| (queue_admin.clone(), id)),
+      _ => None,
+    };
+    Ok(Some(SqliteQueueMessageHandle {
+      inner,
+      payload: Some(payload),
+      claim,
+    }))
+  }
+
+  fn watch(&self, keys: Vec<Vec<u8>>) -> WatchStream {
+    self.inner.watch(keys)
+  }
+
+  fn close(&self) {
+    self.inner.close()
+  }
+}
+
+#[async_trait(?Send)]
+impl QueueAdmin for SqliteDb {
+  async fn list_queue_messages(
+    &self,
+    states: Vec<QueueMessageState>,
+    limit: usize,
+  ) -> Result<Vec<QueueMessageInfo>, JsErrorBox> {
+    self.queue_admin()?.list(states, limit).await
+  }
+
+  async fn purge_queue(
+    &self,
+    states: Vec<QueueMessageState>,
+  ) -> Result<u64, JsErrorBox> {
+    self.queue_admin()?.purge(states).await
+  }
+
+  async fn redrive_queue_messages(
+    &self,
+    ids: Option<Vec<String>>,
+  ) -> Result<u64, JsErrorBox> {
+    self.queue_admin()?.redrive(&self.inner, ids).await
+  }
+}
+
+pub struct SqliteQueueMessageHandle {
+  inner: <denokv_sqlite::Sqlite as Database>::QMH,
+  payload: Option<Vec<u8>>,
+  claim: Option<(Rc<SqliteQueueAdmin>, String)>,
+}
+
+#[async_trait(?Send)]
+impl QueueMessageHandle for SqliteQueueMessageHandle {
+  async fn take_payload(&mut self) -> Result<Vec<u8>, JsErrorBox> {
+    self
+      .payload
+      .take()
+      .ok_or_else(|| JsErrorBox::type_error("Payload already taken"))
+  }
+
+  async fn finish(&self, success: bool) -> Result<(), JsErrorBox> {
+    if let Some((queue_admin, id)) = &self.claim
+      && !success
+    {
+      queue_admin.dead_letter_if_exhausted(id.clone()).await;
+    }
+    let result = self.inner.finish(success).await;
+    if let Some((queue_admin, id)) = &self.claim {
+      queue_admin.release(id.clone()).await;
+    }
+    result
   }
 }
@@ -114,6 +114,9 @@ function hasTlsKeyPairOptions(options) {
   if (options[resolverSymbol] !== undefined) {
     return true;
   }
+  if (options[tlsKeysSymbol] !== undefined) {
+    return true;
+  }
   return (options.cert !== undefined || options.key !== undefined);
 }
 
@@ -130,6 +133,9 @@ function loadTlsKeyPair(api, {
   if (arguments[1][resolverSymbol] !== undefined) {
     return createTlsKeyResolver(arguments[1][resolverSymbol]);
   }
+  if (arguments[1][tlsKeysSymbol] !== undefined) {
+    return arguments[1][tlsKeysSymbol];
+  }
 
   // Check for "pem" format
   if (keyFormat !== undefined && keyFormat !== "pem") {
@@ -225,6 +231,8 @@ function startTlsInternal(
 
 const resolverSymbol = SymbolFor("unstableSniResolver");
 const serverNameSymbol = SymbolFor("unstableServerName");
+// Key material that was already loaded by an op, such as the key of `deno serve`.
+const tlsKeysSymbol = Symbol("tlsKeys");
 
 function createTlsKeyResolver(callback) {
   const { 0: resolver, 1: lookup } = op_tls_cert_resolver_create();
@@ -263,5 +271,6 @@ export {
   startTls,
   startTlsInternal,
   TlsConn,
+  tlsKeysSymbol,
   TlsListener,
 };
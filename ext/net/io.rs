@@ -133,6 +133,20 @@ impl TcpStreamResource {
     self.map_socket(Box::new(move |socket| socket.set_keepalive(keepalive)))
   }
 
+  /// Returns the file descriptor of the socket, which stays owned by the
+  /// resource.
+  #[cfg(unix)]
+  pub fn raw_fd(self: Rc<Self>) -> Result<std::os::fd::RawFd, MapError> {
+    use std::os::fd::AsRawFd;
+
+    if let Some(wr) = RcRef::map(self, |r| &r.wr).try_borrow() {
+      let stream: &tokio::net::TcpStream = (*wr).as_ref();
+      return Ok(stream.as_raw_fd());
+    }
+
+    Err(MapError::NoResources)
+  }
+
   #[allow(clippy::type_complexity)]
   fn map_socket(
     self: Rc<Self>,
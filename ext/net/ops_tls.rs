@@ -573,6 +573,11 @@ where
       tls_config: None,
       server_config_provider: Some(resolver.into_server_config_provider(alpn)),
     },
+    TlsKeys::Files(files) => TlsListener {
+      tcp_listener,
+      tls_config: None,
+      server_config_provider: Some(files.into_server_config_provider(alpn)?),
+    },
   };
 
   let tls_listener_resource = NetworkListenerResource::new(listener);
@@ -124,6 +124,16 @@ impl TcpListener {
     })
   }
 
+  /// Wraps a socket that is already bound and listening, e.g. one that was
+  /// inherited from or sent by another process.
+  pub fn from_std(listener: std::net::TcpListener) -> std::io::Result<Self> {
+    listener.set_nonblocking(true)?;
+    Ok(Self {
+      listener: Some(tokio::net::TcpListener::from_std(listener)?),
+      conn: None,
+    })
+  }
+
   pub async fn accept(
     &self,
   ) -> std::io::Result<(tokio::net::TcpStream, SocketAddr)> {
@@ -136,6 +146,13 @@ impl TcpListener {
   }
 }
 
+#[cfg(unix)]
+impl std::os::fd::AsRawFd for TcpListener {
+  fn as_raw_fd(&self) -> std::os::fd::RawFd {
+    self.listener.as_ref().unwrap().as_raw_fd()
+  }
+}
+
 impl Drop for TcpListener {
   fn drop(&mut self) {
     // If we're in load-balancing mode
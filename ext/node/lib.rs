@@ -38,6 +38,9 @@ pub use node_resolver::DENO_SUPPORTED_BUILTIN_NODE_MODULES as SUPPORTED_BUILTIN_
 pub use node_resolver::PathClean;
 use ops::handle_wrap::AsyncId;
 pub use ops::ipc::ChildPipeFd;
+pub use ops::process::ChildPermissionArgs;
+pub use ops::v8::V8CoverageHandler;
+pub use ops::v8::V8CoverageHandlerRc;
 use ops::vm;
 pub use ops::vm::ContextInitMode;
 pub use ops::vm::VM_CONTEXT_INDEX;
@@ -362,9 +365,14 @@ deno_core::extension!(deno_node,
     ops::fs::op_node_open_sync<P>,
     ops::fs::op_node_open<P>,
     ops::fs::op_node_statfs<P>,
+    ops::tcp_wrap::op_node_tcp_open<P>,
+    ops::tcp_wrap::op_node_tcp_listener_open<P>,
+    ops::tcp_wrap::op_node_tcp_fd,
     ops::winerror::op_node_sys_to_uv_error,
     ops::v8::op_v8_cached_data_version_tag,
+    ops::v8::op_v8_get_heap_space_statistics,
     ops::v8::op_v8_get_heap_statistics,
+    ops::v8::op_v8_heap_snapshot_read,
     ops::v8::op_v8_get_wire_format_version,
     ops::v8::op_v8_new_deserializer,
     ops::v8::op_v8_new_serializer,
@@ -376,14 +384,24 @@ deno_core::extension!(deno_node,
     ops::v8::op_v8_read_value,
     ops::v8::op_v8_release_buffer,
     ops::v8::op_v8_set_treat_array_buffer_views_as_host_objects,
+    ops::v8::op_v8_stop_coverage,
+    ops::v8::op_v8_take_coverage,
+    ops::v8::op_v8_take_heap_snapshot,
     ops::v8::op_v8_transfer_array_buffer,
     ops::v8::op_v8_transfer_array_buffer_de,
     ops::v8::op_v8_write_double,
+    ops::v8::op_v8_write_heap_snapshot<P>,
     ops::v8::op_v8_write_header,
     ops::v8::op_v8_write_raw_bytes,
     ops::v8::op_v8_write_uint32,
     ops::v8::op_v8_write_uint64,
     ops::v8::op_v8_write_value,
+    ops::trace_events::op_node_trace_event,
+    ops::trace_events::op_node_trace_events_category_enabled,
+    ops::trace_events::op_node_trace_events_disable,
+    ops::trace_events::op_node_trace_events_enable<P>,
+    ops::trace_events::op_node_trace_events_gc_hook,
+    ops::trace_events::op_node_trace_events_get_enabled_categories,
     ops::vm::op_vm_create_script,
     ops::vm::op_vm_create_context,
     ops::vm::op_vm_script_run_in_context,
@@ -391,6 +409,46 @@ deno_core::extension!(deno_node,
     ops::vm::op_vm_compile_function,
     ops::vm::op_vm_script_get_source_map_url,
     ops::vm::op_vm_script_create_cached_data,
+    ops::wasi::op_node_wasi_new<P>,
+    ops::wasi::op_node_wasi_args_get,
+    ops::wasi::op_node_wasi_args_sizes_get,
+    ops::wasi::op_node_wasi_environ_get,
+    ops::wasi::op_node_wasi_environ_sizes_get,
+    ops::wasi::op_node_wasi_clock_res_get,
+    ops::wasi::op_node_wasi_clock_time_get,
+    ops::wasi::op_node_wasi_fd_advise,
+    ops::wasi::op_node_wasi_fd_allocate,
+    ops::wasi::op_node_wasi_fd_close,
+    ops::wasi::op_node_wasi_fd_datasync,
+    ops::wasi::op_node_wasi_fd_fdstat_get,
+    ops::wasi::op_node_wasi_fd_fdstat_set_flags,
+    ops::wasi::op_node_wasi_fd_fdstat_set_rights,
+    ops::wasi::op_node_wasi_fd_filestat_get<P>,
+    ops::wasi::op_node_wasi_fd_filestat_set_size,
+    ops::wasi::op_node_wasi_fd_filestat_set_times,
+    ops::wasi::op_node_wasi_fd_pread,
+    ops::wasi::op_node_wasi_fd_prestat_get,
+    ops::wasi::op_node_wasi_fd_prestat_dir_name,
+    ops::wasi::op_node_wasi_fd_pwrite,
+    ops::wasi::op_node_wasi_fd_read,
+    ops::wasi::op_node_wasi_fd_readdir<P>,
+    ops::wasi::op_node_wasi_fd_renumber,
+    ops::wasi::op_node_wasi_fd_seek,
+    ops::wasi::op_node_wasi_fd_sync,
+    ops::wasi::op_node_wasi_fd_tell,
+    ops::wasi::op_node_wasi_fd_write,
+    ops::wasi::op_node_wasi_path_create_directory<P>,
+    ops::wasi::op_node_wasi_path_filestat_get<P>,
+    ops::wasi::op_node_wasi_path_filestat_set_times<P>,
+    ops::wasi::op_node_wasi_path_link<P>,
+    ops::wasi::op_node_wasi_path_open<P>,
+    ops::wasi::op_node_wasi_path_readlink<P>,
+    ops::wasi::op_node_wasi_path_remove_directory<P>,
+    ops::wasi::op_node_wasi_path_rename<P>,
+    ops::wasi::op_node_wasi_path_symlink<P>,
+    ops::wasi::op_node_wasi_path_unlink_file<P>,
+    ops::wasi::op_node_wasi_poll_oneoff,
+    ops::wasi::op_node_wasi_random_get,
     ops::idna::op_node_idna_domain_to_ascii,
     ops::idna::op_node_idna_domain_to_unicode,
     ops::idna::op_node_idna_punycode_to_ascii,
@@ -406,6 +464,10 @@ deno_core::extension!(deno_node,
     ops::http::op_node_http_await_response,
     ops::http2::op_http2_connect,
     ops::http2::op_http2_poll_client_connection,
+    ops::http2::op_http2_client_ping,
+    ops::http2::op_http2_client_settings,
+    ops::http2::op_http2_client_set_local_window_size,
+    ops::http2::op_http2_client_remote_max_concurrent_streams,
     ops::http2::op_http2_client_request,
     ops::http2::op_http2_client_get_response,
     ops::http2::op_http2_client_get_response_body_chunk,
@@ -454,9 +516,11 @@ deno_core::extension!(deno_node,
     ops::worker_threads::op_worker_threads_filename<P, TSys>,
     ops::ipc::op_node_child_ipc_pipe,
     ops::ipc::op_node_ipc_write,
+    ops::ipc::op_node_ipc_write_with_handle,
     ops::ipc::op_node_ipc_read,
     ops::ipc::op_node_ipc_ref,
     ops::ipc::op_node_ipc_unref,
+    ops::process::op_node_child_permission_args,
     ops::process::op_node_process_kill,
     ops::process::op_process_abort,
     ops::tls::op_get_root_certificates,
@@ -469,6 +533,7 @@ deno_core::extension!(deno_node,
     ops::inspector::op_inspector_url,
     ops::inspector::op_inspector_wait,
     ops::inspector::op_inspector_connect<P>,
+    ops::inspector::op_inspector_connect_repl<P>,
     ops::inspector::op_inspector_dispatch,
     ops::inspector::op_inspector_disconnect,
     ops::inspector::op_inspector_emit_protocol_event,
@@ -620,6 +685,7 @@ deno_core::extension!(deno_node,
     "internal/fs/streams.mjs",
     "internal/fs/utils.mjs",
     "internal/fs/handle.ts",
+    "internal/heap_utils.ts",
     "internal/hide_stack_frames.ts",
     "internal/http.ts",
     "internal/http2/util.ts",
@@ -638,6 +704,7 @@ deno_core::extension!(deno_node,
     "internal/readline/promises.mjs",
     "internal/readline/symbols.mjs",
     "internal/readline/utils.mjs",
+    "internal/repl/session.ts",
     "internal/stream_base_commons.ts",
     "internal/streams/add-abort-signal.js",
     "internal/streams/compose.js",
@@ -653,6 +720,10 @@ deno_core::extension!(deno_node,
     "internal/streams/state.js",
     "internal/streams/utils.js",
     "internal/test/binding.ts",
+    "internal/test_runner/mock.ts",
+    "internal/test_runner/mock_timers.ts",
+    "internal/test_runner/runner.ts",
+    "internal/test_runner/tests_stream.ts",
     "internal/timers.mjs",
     "internal/url.ts",
     "internal/util.mjs",
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::future::poll_fn;
 use std::rc::Rc;
 use std::task::Poll;
+use std::time::Instant;
 
 use bytes::Bytes;
 use deno_core::AsyncRefCell;
@@ -19,7 +20,10 @@ use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::error::ResourceError;
+use deno_core::futures::FutureExt;
+use deno_core::futures::task::AtomicWaker;
 use deno_core::op2;
+use deno_core::serde::Deserialize;
 use deno_core::serde::Serialize;
 use deno_net::raw::NetworkStream;
 use deno_net::raw::take_network_stream_resource;
@@ -48,7 +52,13 @@ impl Resource for Http2Client {
 
 #[derive(Debug)]
 pub struct Http2ClientConn {
-  pub conn: AsyncRefCell<h2::client::Connection<NetworkStream, BufView>>,
+  // Not borrowed across awaits, so the connection can be configured while
+  // `op_http2_poll_client_connection` is driving it.
+  pub conn: RefCell<h2::client::Connection<NetworkStream, BufView>>,
+  // Woken after changing the connection state, so the resulting frames are
+  // flushed by the next poll.
+  waker: AtomicWaker,
+  ping_pong: AsyncRefCell<h2::PingPong>,
   cancel_handle: CancelHandle,
 }
 
@@ -138,12 +148,25 @@ pub enum Http2Error {
   ),
 }
 
+/// The `SETTINGS` parameters of a session, named like in `node:http2`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Http2Settings {
+  header_table_size: Option<u32>,
+  enable_push: Option<bool>,
+  initial_window_size: Option<u32>,
+  max_frame_size: Option<u32>,
+  max_concurrent_streams: Option<u32>,
+  max_header_list_size: Option<u32>,
+}
+
 #[op2(async)]
 #[serde]
 pub async fn op_http2_connect(
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
   #[string] url: String,
+  #[serde] settings: Http2Settings,
 ) -> Result<(ResourceId, ResourceId), Http2Error> {
   // No permission check necessary because we're using an existing connection
   let network_stream = {
@@ -153,15 +176,37 @@ pub async fn op_http2_connect(
 
   let url = Url::parse(&url)?;
 
-  let (client, conn) =
-    h2::client::Builder::new().handshake(network_stream).await?;
+  let mut builder = h2::client::Builder::new();
+  if let Some(size) = settings.header_table_size {
+    builder.header_table_size(size);
+  }
+  if let Some(enabled) = settings.enable_push {
+    builder.enable_push(enabled);
+  }
+  if let Some(size) = settings.initial_window_size {
+    builder.initial_window_size(size);
+  }
+  if let Some(size) = settings.max_frame_size {
+    builder.max_frame_size(size);
+  }
+  if let Some(max) = settings.max_concurrent_streams {
+    builder.max_concurrent_streams(max);
+  }
+  if let Some(size) = settings.max_header_list_size {
+    builder.max_header_list_size(size);
+  }
+
+  let (client, mut conn) = builder.handshake(network_stream).await?;
+  let ping_pong = conn.ping_pong().unwrap();
   let mut state = state.borrow_mut();
   let client_rid = state.resource_table.add(Http2Client {
     client: AsyncRefCell::new(client),
     url,
   });
   let conn_rid = state.resource_table.add(Http2ClientConn {
-    conn: AsyncRefCell::new(conn),
+    conn: RefCell::new(conn),
+    waker: AtomicWaker::new(),
+    ping_pong: AsyncRefCell::new(ping_pong),
     cancel_handle: CancelHandle::new(),
   });
   Ok((client_rid, conn_rid))
@@ -299,9 +344,12 @@ pub async fn op_http2_poll_client_connection(
   let resource = state.borrow().resource_table.get::<Http2ClientConn>(rid)?;
 
   let cancel_handle = RcRef::map(resource.clone(), |this| &this.cancel_handle);
-  let mut conn = RcRef::map(resource, |this| &this.conn).borrow_mut().await;
+  let conn = poll_fn(|cx| {
+    resource.waker.register(cx.waker());
+    resource.conn.borrow_mut().poll_unpin(cx)
+  });
 
-  match (&mut *conn).or_cancel(cancel_handle).await {
+  match conn.or_cancel(cancel_handle).await {
     Ok(result) => result?,
     Err(_) => {
       // TODO(bartlomieju): probably need a better mechanism for closing the connection
@@ -313,6 +361,75 @@ pub async fn op_http2_poll_client_connection(
   Ok(())
 }
 
+/// Sends a `PING` and resolves with the round-trip time in milliseconds.
+async fn ping(ping_pong: &mut h2::PingPong) -> Result<f64, Http2Error> {
+  let start = Instant::now();
+  ping_pong.send_ping(h2::Ping::opaque())?;
+  poll_fn(|cx| ping_pong.poll_pong(cx)).await?;
+  Ok(start.elapsed().as_secs_f64() * 1000.0)
+}
+
+#[op2(async)]
+pub async fn op_http2_client_ping(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+) -> Result<f64, Http2Error> {
+  let resource = state.borrow().resource_table.get::<Http2ClientConn>(rid)?;
+  // h2 allows only a single ping in flight.
+  let mut ping_pong =
+    RcRef::map(&resource, |r| &r.ping_pong).borrow_mut().await;
+  ping(&mut ping_pong).await
+}
+
+/// Sends a `SETTINGS` frame and resolves once the peer acknowledged it, with
+/// the round-trip time in milliseconds. Only `initialWindowSize` can change
+/// after the handshake.
+#[op2(async)]
+pub async fn op_http2_client_settings(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[serde] settings: Http2Settings,
+) -> Result<f64, Http2Error> {
+  let resource = state.borrow().resource_table.get::<Http2ClientConn>(rid)?;
+  // Holding the pings also keeps a second `SETTINGS` frame from being sent
+  // before this one is acknowledged, which h2 doesn't allow.
+  let mut ping_pong =
+    RcRef::map(&resource, |r| &r.ping_pong).borrow_mut().await;
+  if let Some(size) = settings.initial_window_size {
+    resource.conn.borrow_mut().set_initial_window_size(size)?;
+    resource.waker.wake();
+  }
+
+  // h2 doesn't report when a `SETTINGS` frame is acknowledged, but peers
+  // process frames in order, so the settings are applied once a `PING` sent
+  // after them is answered.
+  ping(&mut ping_pong).await
+}
+
+#[op2(fast)]
+pub fn op_http2_client_set_local_window_size(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  size: u32,
+) -> Result<(), ResourceError> {
+  let resource = state.resource_table.get::<Http2ClientConn>(rid)?;
+  resource.conn.borrow_mut().set_target_window_size(size);
+  resource.waker.wake();
+  Ok(())
+}
+
+/// The maximum number of concurrent streams that the peer allows, which is
+/// the only remote setting that h2 exposes.
+#[op2(fast)]
+pub fn op_http2_client_remote_max_concurrent_streams(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<u32, ResourceError> {
+  let resource = state.resource_table.get::<Http2ClientConn>(rid)?;
+  let max = resource.conn.borrow().max_concurrent_send_streams();
+  Ok(max.try_into().unwrap_or(u32::MAX))
+}
+
 #[op2(async)]
 #[serde]
 pub async fn op_http2_client_request(
@@ -124,6 +124,36 @@ where
     return Err(InspectorConnectError::ConnectToMainThreadUnsupported);
   }
 
+  Ok(connect_session(isolate, scope, state, callback))
+}
+
+/// Connects the session that `node:repl` evaluates its input with. It can
+/// reach the same inspector as `inspector.Session`, so it needs the same
+/// permission.
+#[op2(stack_trace)]
+#[cppgc]
+pub fn op_inspector_connect_repl<'s, P>(
+  isolate: *mut v8::Isolate,
+  scope: &mut v8::HandleScope<'s>,
+  state: &mut OpState,
+  callback: v8::Local<'s, v8::Function>,
+) -> Result<JSInspectorSession, InspectorConnectError>
+where
+  P: NodePermissions + 'static,
+{
+  state
+    .borrow_mut::<P>()
+    .check_sys("inspector", "node:repl.start()")?;
+
+  Ok(connect_session(isolate, scope, state, callback))
+}
+
+fn connect_session<'s>(
+  isolate: *mut v8::Isolate,
+  scope: &mut v8::HandleScope<'s>,
+  state: &mut OpState,
+  callback: v8::Local<'s, v8::Function>,
+) -> JSInspectorSession {
   let context = scope.get_current_context();
   let context = v8::Global::new(scope, context);
   let callback = v8::Global::new(scope, callback);
@@ -159,9 +189,9 @@ where
     }),
   );
 
-  Ok(JSInspectorSession {
+  JSInspectorSession {
     tx: RefCell::new(Some(tx)),
-  })
+  }
 }
 
 #[op2(fast)]
@@ -178,6 +178,9 @@ mod impl_ {
     #[class(inherit)]
     #[error("failed to serialize json value: {0}")]
     SerdeJson(serde_json::Error),
+    #[class(inherit)]
+    #[error(transparent)]
+    Io(#[from] io::Error),
   }
 
   #[op2(async)]
@@ -226,6 +229,62 @@ mod impl_ {
     })
   }
 
+  /// Like `op_node_ipc_write`, but also sends the file descriptor of a
+  /// handle (`fd`) along with the message, with `SCM_RIGHTS`. Used to send
+  /// `NODE_HANDLE` messages, the handle stays open in this process.
+  #[op2(async)]
+  pub fn op_node_ipc_write_with_handle<'a>(
+    scope: &mut v8::HandleScope<'a>,
+    state: Rc<RefCell<OpState>>,
+    #[smi] rid: ResourceId,
+    value: v8::Local<'a, v8::Value>,
+    fd: i32,
+  ) -> Result<impl Future<Output = Result<(), io::Error>> + use<>, IpcError> {
+    let mut serialized = Vec::with_capacity(64);
+    let mut ser = serde_json::Serializer::new(&mut serialized);
+    serialize_v8_value(scope, value, &mut ser).map_err(IpcError::SerdeJson)?;
+    serialized.push(b'\n');
+
+    let stream = state
+      .borrow()
+      .resource_table
+      .get::<IpcJsonStreamResource>(rid)?;
+
+    #[cfg(unix)]
+    let fd = {
+      use std::os::fd::BorrowedFd;
+
+      if fd < 0 {
+        return Err(
+          io::Error::new(io::ErrorKind::InvalidInput, "invalid handle").into(),
+        );
+      }
+      // SAFETY: `fd` belongs to a handle that is open for the duration of
+      // this call, we only duplicate it.
+      unsafe { BorrowedFd::borrow_raw(fd) }.try_clone_to_owned()?
+    };
+
+    Ok(async move {
+      #[cfg(unix)]
+      {
+        let cancel = stream.cancel.clone();
+        stream
+          .write_msg_bytes_with_fd(&serialized, fd)
+          .or_cancel(cancel)
+          .await??;
+        Ok(())
+      }
+      #[cfg(not(unix))]
+      {
+        let _ = (stream, serialized, fd);
+        Err(io::Error::new(
+          io::ErrorKind::Unsupported,
+          "Sending handles is only supported on Unix",
+        ))
+      }
+    })
+  }
+
   /// Value signaling that the other end ipc channel has closed.
   ///
   /// Node reserves objects of this form (`{ "cmd": "NODE_<something>"`)
@@ -250,13 +309,54 @@ mod impl_ {
     let cancel = stream.cancel.clone();
     let mut stream = RcRef::map(stream, |r| &r.read_half).borrow_mut().await;
     let msgs = stream.read_msg().or_cancel(cancel).await??;
-    if let Some(msg) = msgs {
+    if let Some(mut msg) = msgs {
+      #[cfg(unix)]
+      if msg.get("cmd").and_then(|cmd| cmd.as_str()) == Some("NODE_HANDLE")
+        && let Some(fd) = stream.take_fd()
+      {
+        let handle = open_handle(&mut state.borrow_mut(), &msg["type"], fd);
+        msg["handle"] = handle;
+      }
       Ok(msg)
     } else {
       Ok(stop_sentinel())
     }
   }
 
+  /// Adds a resource for a handle received with a `NODE_HANDLE` message.
+  ///
+  /// Returns `null` if the handle can't be opened, in which case the message
+  /// is delivered without a handle like in Node.
+  #[cfg(unix)]
+  fn open_handle(
+    state: &mut OpState,
+    ty: &serde_json::Value,
+    fd: std::os::fd::OwnedFd,
+  ) -> serde_json::Value {
+    use crate::ops::tcp_wrap::add_tcp_listener;
+    use crate::ops::tcp_wrap::add_tcp_stream;
+
+    match ty.as_str() {
+      // `node:cluster` sends its listening sockets as "net.Native"
+      Some("net.Server" | "net.Native") => match add_tcp_listener(state, fd) {
+        Ok((rid, local_addr)) => serde_json::json!({
+          "rid": rid,
+          "localAddr": local_addr,
+        }),
+        Err(_) => serde_json::Value::Null,
+      },
+      Some("net.Socket") => match add_tcp_stream(state, fd) {
+        Ok((rid, local_addr, remote_addr)) => serde_json::json!({
+          "rid": rid,
+          "localAddr": local_addr,
+          "remoteAddr": remote_addr,
+        }),
+        Err(_) => serde_json::Value::Null,
+      },
+      _ => serde_json::Value::Null,
+    }
+  }
+
   #[op2(fast)]
   pub fn op_node_ipc_ref(state: &mut OpState, #[smi] rid: ResourceId) {
     let stream = state
@@ -17,10 +17,13 @@ pub mod perf_hooks;
 pub mod process;
 pub mod require;
 pub mod sqlite;
+pub mod tcp_wrap;
 pub mod tls;
+pub mod trace_events;
 pub mod util;
 pub mod v8;
 pub mod vm;
+pub mod wasi;
 pub mod winerror;
 pub mod worker_threads;
 pub mod zlib;
@@ -34,6 +34,20 @@ pub fn op_node_process_kill(
   Ok(kill(pid, sig))
 }
 
+/// The permission flags of the process, set by the embedder.
+pub struct ChildPermissionArgs(pub Vec<String>);
+
+/// Returns the permission flags to start a `deno` subprocess with the same
+/// permissions as this process.
+#[op2]
+#[serde]
+pub fn op_node_child_permission_args(state: &mut OpState) -> Vec<String> {
+  state
+    .try_borrow::<ChildPermissionArgs>()
+    .map(|args| args.0.clone())
+    .unwrap_or_default()
+}
+
 #[op2(fast)]
 pub fn op_process_abort() {
   std::process::abort();
@@ -0,0 +1,186 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+use deno_core::OpState;
+use deno_core::ResourceId;
+use deno_core::op2;
+#[cfg(unix)]
+use deno_net::io::TcpStreamResource;
+use deno_net::ops::IpAddr;
+use deno_net::ops::NetError;
+#[cfg(unix)]
+use deno_net::raw::NetworkListenerResource;
+#[cfg(unix)]
+use deno_net::tcp::TcpListener;
+
+use crate::NodePermissions;
+
+/// Wraps the file descriptor of a connected TCP socket, e.g. one that was
+/// inherited from the parent process.
+///
+/// `fd` is an arbitrary number from JavaScript that may be owned by another
+/// resource, so the resource gets a duplicate of it and `fd` stays open.
+#[op2(stack_trace)]
+#[serde]
+pub fn op_node_tcp_open<P>(
+  state: &mut OpState,
+  fd: i32,
+) -> Result<(ResourceId, IpAddr, IpAddr), NetError>
+where
+  P: NodePermissions + 'static,
+{
+  #[cfg(unix)]
+  {
+    let stream = std::net::TcpStream::from(dup_stream_socket(fd)?);
+    stream.local_addr()?;
+    let peer_addr = stream.peer_addr()?;
+    state.borrow_mut::<P>().check_net(
+      (&peer_addr.ip().to_string(), Some(peer_addr.port())),
+      "net.Socket()",
+    )?;
+    add_tcp_stream(state, stream.into())
+  }
+  #[cfg(not(unix))]
+  {
+    let _ = (state, fd);
+    Err(unsupported_handle().into())
+  }
+}
+
+/// Like `op_node_tcp_open`, but for a socket that is already listening.
+#[op2(stack_trace)]
+#[serde]
+pub fn op_node_tcp_listener_open<P>(
+  state: &mut OpState,
+  fd: i32,
+) -> Result<(ResourceId, IpAddr), NetError>
+where
+  P: NodePermissions + 'static,
+{
+  #[cfg(unix)]
+  {
+    let listener = std::net::TcpListener::from(dup_stream_socket(fd)?);
+    let local_addr = listener.local_addr()?;
+    state.borrow_mut::<P>().check_net(
+      (&local_addr.ip().to_string(), Some(local_addr.port())),
+      "net.Server.listen()",
+    )?;
+    add_tcp_listener(state, listener.into())
+  }
+  #[cfg(not(unix))]
+  {
+    let _ = (state, fd);
+    Err(unsupported_handle().into())
+  }
+}
+
+/// Duplicates `fd` if it is a stream socket. The caller keeps owning `fd`.
+#[cfg(unix)]
+fn dup_stream_socket(fd: i32) -> std::io::Result<std::os::fd::OwnedFd> {
+  use std::os::fd::AsRawFd;
+  use std::os::fd::FromRawFd;
+
+  // SAFETY: F_DUPFD_CLOEXEC only reads the descriptor table; it fails with
+  // EBADF if `fd` isn't open.
+  let dup = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+  if dup < 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  // SAFETY: `dup` was just created by fcntl, nothing else owns it.
+  let dup = unsafe { std::os::fd::OwnedFd::from_raw_fd(dup) };
+
+  let mut socket_type: libc::c_int = 0;
+  let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+  // SAFETY: `socket_type` and `len` are valid for writes and `len` is the
+  // size of `socket_type`.
+  let ret = unsafe {
+    libc::getsockopt(
+      dup.as_raw_fd(),
+      libc::SOL_SOCKET,
+      libc::SO_TYPE,
+      &mut socket_type as *mut libc::c_int as *mut libc::c_void,
+      &mut len,
+    )
+  };
+  if ret < 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  if socket_type != libc::SOCK_STREAM {
+    return Err(std::io::Error::new(
+      std::io::ErrorKind::InvalidInput,
+      "The file descriptor is not a TCP socket",
+    ));
+  }
+  Ok(dup)
+}
+
+#[cfg(not(unix))]
+fn unsupported_handle() -> std::io::Error {
+  std::io::Error::new(
+    std::io::ErrorKind::Unsupported,
+    "Opening TCP handles from a file descriptor is only supported on Unix",
+  )
+}
+
+/// Adds a resource for a connected TCP socket, taking ownership of `fd`.
+#[cfg(unix)]
+pub fn add_tcp_stream(
+  state: &mut OpState,
+  fd: std::os::fd::OwnedFd,
+) -> Result<(ResourceId, IpAddr, IpAddr), NetError> {
+  let stream = std::net::TcpStream::from(fd);
+  stream.set_nonblocking(true)?;
+  let stream = tokio::net::TcpStream::from_std(stream)?;
+  let local_addr = stream.local_addr()?;
+  let remote_addr = stream.peer_addr()?;
+  let rid = state
+    .resource_table
+    .add(TcpStreamResource::new(stream.into_split()));
+
+  Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
+}
+
+/// Adds a resource for a listening TCP socket, taking ownership of `fd`.
+#[cfg(unix)]
+pub fn add_tcp_listener(
+  state: &mut OpState,
+  fd: std::os::fd::OwnedFd,
+) -> Result<(ResourceId, IpAddr), NetError> {
+  let listener = TcpListener::from_std(std::net::TcpListener::from(fd))?;
+  let local_addr = listener.local_addr()?;
+  let rid = state
+    .resource_table
+    .add(NetworkListenerResource::new(listener));
+
+  Ok((rid, IpAddr::from(local_addr)))
+}
+
+/// Returns the file descriptor of a TCP connection or listener resource, or
+/// -1 if it doesn't have one (like libuv on Windows).
+///
+/// The listener must not be accepting connections yet.
+#[op2(fast)]
+pub fn op_node_tcp_fd(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+) -> Result<i32, NetError> {
+  #[cfg(unix)]
+  {
+    use std::os::fd::AsRawFd;
+
+    if let Ok(resource) = state.resource_table.get::<TcpStreamResource>(rid) {
+      return resource.raw_fd().map_err(NetError::Map);
+    }
+    let resource = state
+      .resource_table
+      .get::<NetworkListenerResource<TcpListener>>(rid)?;
+    let listener = deno_core::RcRef::map(&resource, |r| &r.listener)
+      .try_borrow()
+      .ok_or(NetError::ListenerBusy)?;
+    Ok(listener.as_raw_fd())
+  }
+  #[cfg(not(unix))]
+  {
+    let _ = (state, rid);
+    Ok(-1)
+  }
+}
@@ -0,0 +1,431 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Collection of trace events in the Chrome trace event format, as written by
+//! Node with `--trace-event-categories` and `node:trace_events`.
+//!
+//! Events are buffered for the whole process and appended in chunks to
+//! `node_trace.${rotation}.log` (or `--trace-event-file-pattern`), which is
+//! completed on exit. The resulting file can be loaded in Perfetto or
+//! `chrome://tracing`.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::Once;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use deno_core::OpState;
+use deno_core::op2;
+use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use deno_core::v8;
+use deno_permissions::OpenAccessKind;
+use serde::Serialize;
+
+use crate::NodePermissions;
+
+const DEFAULT_CATEGORIES: &str = "v8,node,node.async_hooks";
+const DEFAULT_FILE_PATTERN: &str = "node_trace.${rotation}.log";
+/// The number of buffered events that triggers a write to the trace file.
+const FLUSH_THRESHOLD: usize = 1024;
+
+#[derive(Serialize)]
+struct TraceEvent {
+  name: String,
+  cat: String,
+  ph: char,
+  ts: u64,
+  pid: u32,
+  tid: u64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  dur: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  s: Option<char>,
+  args: Value,
+}
+
+struct TraceLog {
+  /// Categories enabled on the command line, these can't be disabled.
+  static_categories: Vec<String>,
+  /// Categories enabled through `Tracing` objects, reference counted.
+  dynamic_categories: HashMap<String, usize>,
+  file_pattern: String,
+  events: Vec<TraceEvent>,
+  /// The trace file, opened by the first write.
+  file: Option<BufWriter<File>>,
+  /// The number of events written to the trace file.
+  written: usize,
+  /// Set when writing failed, after which events are no longer recorded.
+  failed: bool,
+}
+
+impl TraceLog {
+  fn from_node_options() -> Self {
+    let options = std::env::var("NODE_OPTIONS").unwrap_or_default();
+    let mut categories = None;
+    let mut enabled = false;
+    let mut file_pattern = None;
+
+    let mut args = options.split_ascii_whitespace();
+    while let Some(arg) = args.next() {
+      let (flag, value) = match arg.split_once('=') {
+        Some((flag, value)) => (flag, Some(value)),
+        None => (arg, None),
+      };
+      match flag {
+        "--trace-events-enabled" => enabled = true,
+        "--trace-event-categories" => {
+          categories = value.or_else(|| args.next()).map(str::to_string);
+        }
+        "--trace-event-file-pattern" => {
+          file_pattern = value.or_else(|| args.next()).map(str::to_string);
+        }
+        _ => {}
+      }
+    }
+
+    if enabled && categories.is_none() {
+      categories = Some(DEFAULT_CATEGORIES.to_string());
+    }
+    let static_categories = categories
+      .as_deref()
+      .map(split_categories)
+      .unwrap_or_default();
+    if !static_categories.is_empty() {
+      register_flush();
+    }
+
+    Self {
+      static_categories,
+      dynamic_categories: HashMap::new(),
+      file_pattern: file_pattern
+        .unwrap_or_else(|| DEFAULT_FILE_PATTERN.to_string()),
+      events: Vec::new(),
+      file: None,
+      written: 0,
+      failed: false,
+    }
+  }
+
+  fn is_enabled(&self, category: &str) -> bool {
+    self.static_categories.iter().any(|c| c == category)
+      || self.dynamic_categories.contains_key(category)
+  }
+
+  fn is_group_enabled(&self, group: &str) -> bool {
+    group.split(',').any(|c| self.is_enabled(c.trim()))
+  }
+
+  fn enabled_categories(&self) -> Vec<&str> {
+    let mut categories = self
+      .static_categories
+      .iter()
+      .map(String::as_str)
+      .chain(self.dynamic_categories.keys().map(String::as_str))
+      .collect::<Vec<_>>();
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+  }
+
+  fn file_path(&self) -> PathBuf {
+    PathBuf::from(
+      self
+        .file_pattern
+        .replace("${pid}", &std::process::id().to_string())
+        .replace("${rotation}", "1"),
+    )
+  }
+
+  /// Appends the buffered events to the trace file, and completes it if
+  /// `finish` is set.
+  fn write_events(&mut self, finish: bool) {
+    if self.failed || (self.file.is_none() && self.events.is_empty()) {
+      return;
+    }
+    let path = self.file_path();
+    let events = std::mem::take(&mut self.events);
+    let result = (|| {
+      let file = match &mut self.file {
+        Some(file) => file,
+        None => {
+          let mut file = BufWriter::new(File::create(&path)?);
+          file.write_all(b"{\"traceEvents\":[")?;
+          self.file.insert(file)
+        }
+      };
+      for event in &events {
+        if self.written > 0 {
+          file.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut *file, event)?;
+        self.written += 1;
+      }
+      if finish {
+        file.write_all(b"]}")?;
+      }
+      file.flush()
+    })();
+    if let Err(err) = result {
+      log::warn!("Failed to write trace events to {}: {err}", path.display());
+      self.failed = true;
+      self.file = None;
+    }
+  }
+}
+
+static TRACE_LOG: LazyLock<Mutex<TraceLog>> =
+  LazyLock::new(|| Mutex::new(TraceLog::from_node_options()));
+
+fn split_categories(categories: &str) -> Vec<String> {
+  categories
+    .split(',')
+    .map(str::trim)
+    .filter(|c| !c.is_empty())
+    .map(str::to_string)
+    .collect()
+}
+
+fn register_flush() {
+  static REGISTER: Once = Once::new();
+  REGISTER.call_once(|| deno_signals::before_exit(flush));
+}
+
+/// Microseconds since the unix epoch, the clock `performance.timeOrigin` is
+/// based on.
+fn now_micros() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_micros() as u64)
+    .unwrap_or_default()
+}
+
+fn current_tid() -> u64 {
+  static NEXT_TID: AtomicU64 = AtomicU64::new(1);
+  thread_local! {
+    static TID: Cell<u64> = Cell::new(NEXT_TID.fetch_add(1, Ordering::Relaxed));
+  }
+  TID.with(Cell::get)
+}
+
+/// Returns whether any category of the comma separated `category` group is
+/// enabled, either on the command line or through `node:trace_events`.
+pub fn is_category_enabled(category: &str) -> bool {
+  TRACE_LOG.lock().unwrap().is_group_enabled(category)
+}
+
+/// Records an instant event if `category` is enabled.
+pub fn add_instant_event(category: &str, name: &str, args: Value) {
+  add_event(category, name, 'i', None, None, None, args);
+}
+
+fn add_event(
+  category: &str,
+  name: &str,
+  phase: char,
+  id: Option<u64>,
+  timestamp: Option<u64>,
+  duration: Option<u64>,
+  args: Value,
+) {
+  let mut log = TRACE_LOG.lock().unwrap();
+  if log.failed || !log.is_group_enabled(category) {
+    return;
+  }
+  log.events.push(TraceEvent {
+    name: name.to_string(),
+    cat: category.to_string(),
+    ph: phase,
+    ts: timestamp.unwrap_or_else(now_micros),
+    pid: std::process::id(),
+    tid: current_tid(),
+    dur: duration,
+    id: id.map(|id| format!("0x{id:x}")),
+    s: (phase == 'i').then_some('t'),
+    args,
+  });
+  if log.events.len() >= FLUSH_THRESHOLD {
+    log.write_events(false);
+  }
+}
+
+/// Writes the remaining events and completes the trace file. Called before
+/// the process exits.
+pub fn flush() {
+  TRACE_LOG.lock().unwrap().write_events(true);
+}
+
+/// Enables `categories` for a `Tracing` object. The trace file is checked for
+/// write access since it is written on exit.
+#[op2(stack_trace)]
+pub fn op_node_trace_events_enable<P>(
+  state: &mut OpState,
+  #[serde] categories: Vec<String>,
+) -> Result<(), deno_permissions::PermissionCheckError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = TRACE_LOG.lock().unwrap().file_path();
+  state.borrow_mut::<P>().check_open(
+    Cow::Borrowed(Path::new(&path)),
+    OpenAccessKind::Write,
+    Some("node:trace_events.createTracing()"),
+  )?;
+
+  let mut log = TRACE_LOG.lock().unwrap();
+  for category in categories {
+    *log.dynamic_categories.entry(category).or_default() += 1;
+  }
+  drop(log);
+  register_flush();
+  Ok(())
+}
+
+#[op2]
+pub fn op_node_trace_events_disable(#[serde] categories: Vec<String>) {
+  let mut log = TRACE_LOG.lock().unwrap();
+  for category in categories {
+    if let Some(count) = log.dynamic_categories.get_mut(&category) {
+      *count -= 1;
+      if *count == 0 {
+        log.dynamic_categories.remove(&category);
+      }
+    }
+  }
+}
+
+/// Comma separated list of the enabled categories, `undefined` if there are
+/// none.
+#[op2]
+#[string]
+pub fn op_node_trace_events_get_enabled_categories() -> Option<String> {
+  let log = TRACE_LOG.lock().unwrap();
+  let categories = log.enabled_categories();
+  (!categories.is_empty()).then(|| categories.join(","))
+}
+
+#[op2(fast)]
+pub fn op_node_trace_events_category_enabled(#[string] category: &str) -> bool {
+  is_category_enabled(category)
+}
+
+/// Records an event from JS. `timestamp` is in milliseconds since the unix
+/// epoch, like `performance.timeOrigin + performance.now()`.
+#[op2]
+pub fn op_node_trace_event(
+  #[string] phase: &str,
+  #[string] category: &str,
+  #[string] name: &str,
+  #[serde] id: Option<u64>,
+  #[serde] timestamp: Option<f64>,
+  #[serde] args: Option<Value>,
+) {
+  let Some(phase) = phase.chars().next() else {
+    return;
+  };
+  add_event(
+    category,
+    name,
+    phase,
+    id,
+    timestamp.map(|ts| (ts * 1000.0) as u64),
+    None,
+    args.unwrap_or_else(|| Value::Object(Default::default())),
+  );
+}
+
+struct GcTraceData(RefCell<GcTraceDataInner>);
+
+struct GcTraceDataInner {
+  start: Instant,
+  timestamp: u64,
+}
+
+impl GcTraceData {
+  extern "C" fn prologue_callback(
+    isolate: *mut v8::Isolate,
+    _gc_type: v8::GCType,
+    _flags: v8::GCCallbackFlags,
+    _data: *mut c_void,
+  ) {
+    // SAFETY: Isolate is valid during callback
+    let isolate = unsafe { &mut *isolate };
+    let this = isolate.get_slot::<Self>().unwrap();
+    let mut this = this.0.borrow_mut();
+    this.start = Instant::now();
+    this.timestamp = now_micros();
+  }
+
+  extern "C" fn epilogue_callback(
+    isolate: *mut v8::Isolate,
+    gc_type: v8::GCType,
+    _flags: v8::GCCallbackFlags,
+    _data: *mut c_void,
+  ) {
+    // SAFETY: Isolate is valid during callback
+    let isolate = unsafe { &mut *isolate };
+    let this = isolate.get_slot::<Self>().unwrap();
+    let this = this.0.borrow();
+
+    let name = match gc_type {
+      v8::GCType::kGCTypeScavenge => "V8.GCScavenger",
+      v8::GCType::kGCTypeMinorMarkSweep => "V8.GCMinorMS",
+      v8::GCType::kGCTypeMarkSweepCompact => "V8.GCFinalizeMC",
+      v8::GCType::kGCTypeIncrementalMarking => "V8.GCIncrementalMarking",
+      v8::GCType::kGCTypeProcessWeakCallbacks => {
+        "V8.GCPhantomHandleProcessingCallback"
+      }
+      _ => return,
+    };
+    add_event(
+      "v8",
+      name,
+      'X',
+      None,
+      Some(this.timestamp),
+      Some(this.start.elapsed().as_micros() as u64),
+      Value::Object(Default::default()),
+    );
+  }
+}
+
+/// Records garbage collections as `v8` category events, like Node does for
+/// `--trace-event-categories v8`.
+#[op2(fast)]
+pub fn op_node_trace_events_gc_hook(scope: &mut v8::HandleScope) {
+  if scope.get_slot::<GcTraceData>().is_some() {
+    return;
+  }
+
+  scope.set_slot(GcTraceData(RefCell::new(GcTraceDataInner {
+    start: Instant::now(),
+    timestamp: 0,
+  })));
+
+  scope.add_gc_prologue_callback(
+    GcTraceData::prologue_callback,
+    std::ptr::null_mut(),
+    v8::GCType::kGCTypeAll,
+  );
+
+  scope.add_gc_epilogue_callback(
+    GcTraceData::epilogue_callback,
+    std::ptr::null_mut(),
+    v8::GCType::kGCTypeAll,
+  );
+}
@@ -1,16 +1,28 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
 use std::ptr::NonNull;
+use std::rc::Rc;
 
 use deno_core::FastString;
 use deno_core::GarbageCollected;
+use deno_core::OpState;
 use deno_core::ToJsBuffer;
 use deno_core::op2;
 use deno_core::v8;
 use deno_error::JsErrorBox;
+use deno_fs::FileSystemRc;
+use deno_fs::OpenOptions;
+use deno_permissions::OpenAccessKind;
+use serde::Serialize;
 use v8::ValueDeserializerHelper;
 use v8::ValueSerializerHelper;
 
+use crate::NodePermissions;
+
 #[op2(fast)]
 pub fn op_v8_cached_data_version_tag() -> u32 {
   v8::script_compiler::cached_data_version_tag()
@@ -39,6 +51,137 @@ pub fn op_v8_get_heap_statistics(
   buffer[13] = stats.external_memory() as f64;
 }
 
+#[derive(Serialize)]
+pub struct HeapSpaceStatistics {
+  space_name: String,
+  space_size: usize,
+  space_used_size: usize,
+  space_available_size: usize,
+  physical_space_size: usize,
+}
+
+#[op2]
+#[serde]
+pub fn op_v8_get_heap_space_statistics(
+  scope: &mut v8::HandleScope,
+) -> Vec<HeapSpaceStatistics> {
+  (0..scope.number_of_heap_spaces())
+    .filter_map(|index| scope.get_heap_space_statistics(index))
+    .map(|stats| HeapSpaceStatistics {
+      space_name: stats.space_name().to_string_lossy().into_owned(),
+      space_size: stats.space_size(),
+      space_used_size: stats.space_used_size(),
+      space_available_size: stats.space_available_size(),
+      physical_space_size: stats.physical_space_size(),
+    })
+    .collect()
+}
+
+/// A heap snapshot whose JSON chunks are read one at a time by the stream
+/// returned from `v8.getHeapSnapshot()`.
+pub struct HeapSnapshot {
+  chunks: RefCell<VecDeque<Vec<u8>>>,
+}
+
+impl GarbageCollected for HeapSnapshot {
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"HeapSnapshot"
+  }
+}
+
+#[op2]
+#[cppgc]
+pub fn op_v8_take_heap_snapshot(scope: &mut v8::HandleScope) -> HeapSnapshot {
+  let mut chunks = VecDeque::new();
+  scope.take_heap_snapshot(|chunk| {
+    chunks.push_back(chunk.to_vec());
+    true
+  });
+  HeapSnapshot {
+    chunks: RefCell::new(chunks),
+  }
+}
+
+#[op2]
+#[serde]
+pub fn op_v8_heap_snapshot_read(
+  #[cppgc] snapshot: &HeapSnapshot,
+) -> Option<ToJsBuffer> {
+  snapshot
+    .chunks
+    .borrow_mut()
+    .pop_front()
+    .map(ToJsBuffer::from)
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum HeapSnapshotError {
+  #[class(inherit)]
+  #[error(transparent)]
+  Permission(#[from] deno_permissions::PermissionCheckError),
+  #[class(inherit)]
+  #[error(transparent)]
+  Fs(
+    #[from]
+    #[inherit]
+    deno_io::fs::FsError,
+  ),
+}
+
+/// Writes a heap snapshot to `path` as V8 produces it, without holding the
+/// whole snapshot in memory.
+#[op2(fast, stack_trace)]
+pub fn op_v8_write_heap_snapshot<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+  #[string] path: &str,
+) -> Result<(), HeapSnapshotError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = state.borrow_mut::<P>().check_open(
+    Cow::Borrowed(Path::new(path)),
+    OpenAccessKind::Write,
+    Some("node:v8.writeHeapSnapshot()"),
+  )?;
+  let fs = state.borrow::<FileSystemRc>();
+  let file =
+    fs.open_sync(&path, OpenOptions::write(true, false, false, None))?;
+
+  let mut result = Ok(());
+  scope.take_heap_snapshot(|chunk| {
+    result = file.clone().write_all_sync(chunk);
+    result.is_ok()
+  });
+  Ok(result?)
+}
+
+/// Implemented by the embedder to let `v8.takeCoverage()` and
+/// `v8.stopCoverage()` control the coverage collection it started, eg. for
+/// `deno test --coverage`.
+pub trait V8CoverageHandler {
+  /// Writes the coverage collected so far and resets the counters.
+  fn take_coverage(&self);
+  /// Stops collecting coverage, nothing is written on exit afterwards.
+  fn stop_coverage(&self);
+}
+
+pub type V8CoverageHandlerRc = Rc<dyn V8CoverageHandler>;
+
+#[op2(fast)]
+pub fn op_v8_take_coverage(state: &OpState) {
+  if let Some(handler) = state.try_borrow::<V8CoverageHandlerRc>() {
+    handler.take_coverage();
+  }
+}
+
+#[op2(fast)]
+pub fn op_v8_stop_coverage(state: &OpState) {
+  if let Some(handler) = state.try_borrow::<V8CoverageHandlerRc>() {
+    handler.stop_coverage();
+  }
+}
+
 pub struct Serializer<'a> {
   inner: v8::ValueSerializer<'a>,
 }
@@ -0,0 +1,1828 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! The `wasi_snapshot_preview1` system calls of `node:wasi`.
+//!
+//! Every system call receives the memory of the WebAssembly instance and
+//! returns a WASI errno. Preopened directories are accessed through the
+//! `deno_fs::FileSystem` of the runtime, and permission checks throw in the
+//! same way as the rest of `node:fs` instead of returning an errno.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io;
+use std::io::SeekFrom;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use deno_core::GarbageCollected;
+use deno_core::OpState;
+use deno_core::op2;
+use deno_fs::FileSystem;
+use deno_fs::FileSystemRc;
+use deno_fs::FsFileType;
+use deno_fs::OpenOptions;
+use deno_io::fs::File;
+use deno_io::fs::FileResource;
+use deno_io::fs::FsError;
+use deno_io::fs::FsStat;
+use deno_permissions::CheckedPath;
+use deno_permissions::OpenAccessKind;
+use deno_permissions::PermissionCheckError;
+use rand::RngCore;
+
+use crate::NodePermissions;
+
+const API_NAME: &str = "node:wasi";
+
+/// The `errno` of a WASI system call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Errno(u16);
+
+impl Errno {
+  pub const SUCCESS: Self = Self(0);
+  pub const ACCES: Self = Self(2);
+  pub const AGAIN: Self = Self(6);
+  pub const BADF: Self = Self(8);
+  pub const BUSY: Self = Self(10);
+  pub const EXIST: Self = Self(20);
+  pub const FAULT: Self = Self(21);
+  pub const FBIG: Self = Self(22);
+  pub const ILSEQ: Self = Self(25);
+  pub const INTR: Self = Self(27);
+  pub const INVAL: Self = Self(28);
+  pub const IO: Self = Self(29);
+  pub const ISDIR: Self = Self(31);
+  pub const LOOP: Self = Self(32);
+  pub const MFILE: Self = Self(33);
+  pub const MLINK: Self = Self(34);
+  pub const NAMETOOLONG: Self = Self(37);
+  pub const NFILE: Self = Self(41);
+  pub const NODEV: Self = Self(43);
+  pub const NOENT: Self = Self(44);
+  pub const NOMEM: Self = Self(48);
+  pub const NOSPC: Self = Self(51);
+  pub const NOSYS: Self = Self(52);
+  pub const NOTDIR: Self = Self(54);
+  pub const NOTEMPTY: Self = Self(55);
+  pub const NOTSUP: Self = Self(58);
+  pub const NOTTY: Self = Self(59);
+  pub const OVERFLOW: Self = Self(61);
+  pub const PERM: Self = Self(63);
+  pub const PIPE: Self = Self(64);
+  pub const ROFS: Self = Self(69);
+  pub const SPIPE: Self = Self(70);
+  pub const TXTBSY: Self = Self(74);
+  pub const XDEV: Self = Self(75);
+  pub const NOTCAPABLE: Self = Self(76);
+}
+
+impl From<&io::Error> for Errno {
+  fn from(err: &io::Error) -> Self {
+    #[cfg(unix)]
+    if let Some(code) = err.raw_os_error() {
+      match code {
+        libc::EPERM => return Self::PERM,
+        libc::ENOENT => return Self::NOENT,
+        libc::EIO => return Self::IO,
+        libc::EBADF => return Self::BADF,
+        libc::EAGAIN => return Self::AGAIN,
+        libc::ENOMEM => return Self::NOMEM,
+        libc::EACCES => return Self::ACCES,
+        libc::EFAULT => return Self::FAULT,
+        libc::EBUSY => return Self::BUSY,
+        libc::EEXIST => return Self::EXIST,
+        libc::EXDEV => return Self::XDEV,
+        libc::ENODEV => return Self::NODEV,
+        libc::ENOTDIR => return Self::NOTDIR,
+        libc::EISDIR => return Self::ISDIR,
+        libc::EINVAL => return Self::INVAL,
+        libc::ENFILE => return Self::NFILE,
+        libc::EMFILE => return Self::MFILE,
+        libc::ENOTTY => return Self::NOTTY,
+        libc::ETXTBSY => return Self::TXTBSY,
+        libc::EFBIG => return Self::FBIG,
+        libc::ENOSPC => return Self::NOSPC,
+        libc::ESPIPE => return Self::SPIPE,
+        libc::EROFS => return Self::ROFS,
+        libc::EMLINK => return Self::MLINK,
+        libc::EPIPE => return Self::PIPE,
+        libc::ENAMETOOLONG => return Self::NAMETOOLONG,
+        libc::ENOSYS => return Self::NOSYS,
+        libc::ENOTEMPTY => return Self::NOTEMPTY,
+        libc::ELOOP => return Self::LOOP,
+        libc::EOVERFLOW => return Self::OVERFLOW,
+        libc::EINTR => return Self::INTR,
+        _ => {}
+      }
+    }
+    match err.kind() {
+      io::ErrorKind::NotFound => Self::NOENT,
+      io::ErrorKind::PermissionDenied => Self::ACCES,
+      io::ErrorKind::AlreadyExists => Self::EXIST,
+      io::ErrorKind::WouldBlock => Self::AGAIN,
+      io::ErrorKind::InvalidInput => Self::INVAL,
+      io::ErrorKind::InvalidFilename => Self::NAMETOOLONG,
+      io::ErrorKind::Interrupted => Self::INTR,
+      io::ErrorKind::BrokenPipe => Self::PIPE,
+      io::ErrorKind::NotADirectory => Self::NOTDIR,
+      io::ErrorKind::IsADirectory => Self::ISDIR,
+      io::ErrorKind::DirectoryNotEmpty => Self::NOTEMPTY,
+      io::ErrorKind::ReadOnlyFilesystem => Self::ROFS,
+      io::ErrorKind::StorageFull => Self::NOSPC,
+      io::ErrorKind::NotSeekable => Self::SPIPE,
+      io::ErrorKind::FileTooLarge => Self::FBIG,
+      io::ErrorKind::ResourceBusy => Self::BUSY,
+      io::ErrorKind::CrossesDevices => Self::XDEV,
+      io::ErrorKind::TooManyLinks => Self::MLINK,
+      io::ErrorKind::Unsupported => Self::NOTSUP,
+      io::ErrorKind::OutOfMemory => Self::NOMEM,
+      _ => Self::IO,
+    }
+  }
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum WasiError {
+  #[class(inherit)]
+  #[error(transparent)]
+  Permission(#[from] PermissionCheckError),
+  #[class(inherit)]
+  #[error(transparent)]
+  Resource(#[from] deno_core::error::ResourceError),
+  #[class(inherit)]
+  #[error("{0}")]
+  Io(
+    #[from]
+    #[inherit]
+    io::Error,
+  ),
+  /// Returned to the WebAssembly instance instead of being thrown.
+  #[class(generic)]
+  #[error("WASI errno {}", .0.0)]
+  Errno(Errno),
+}
+
+impl From<Errno> for WasiError {
+  fn from(errno: Errno) -> Self {
+    Self::Errno(errno)
+  }
+}
+
+impl From<FsError> for WasiError {
+  fn from(err: FsError) -> Self {
+    match err {
+      FsError::PermissionCheck(err) => Self::Permission(err),
+      FsError::Io(err) => Self::Errno(Errno::from(&err)),
+      FsError::FileBusy => Self::Errno(Errno::BUSY),
+      FsError::NotSupported => Self::Errno(Errno::NOTSUP),
+    }
+  }
+}
+
+type WasiResult<T = ()> = Result<T, WasiError>;
+
+/// Turns the result of a system call into its errno, only throwing
+/// permission errors.
+fn to_errno(result: WasiResult) -> Result<u32, WasiError> {
+  match result {
+    Ok(()) => Ok(Errno::SUCCESS.0 as u32),
+    Err(WasiError::Errno(errno)) => Ok(errno.0 as u32),
+    Err(err) => Err(err),
+  }
+}
+
+mod filetype {
+  pub const UNKNOWN: u8 = 0;
+  pub const BLOCK_DEVICE: u8 = 1;
+  pub const CHARACTER_DEVICE: u8 = 2;
+  pub const DIRECTORY: u8 = 3;
+  pub const REGULAR_FILE: u8 = 4;
+  pub const SOCKET_STREAM: u8 = 6;
+  pub const SYMBOLIC_LINK: u8 = 7;
+}
+
+mod rights {
+  pub const FD_READ: u64 = 1 << 1;
+  pub const FD_WRITE: u64 = 1 << 6;
+  pub const ALL: u64 = (1 << 29) - 1;
+}
+
+const OFLAGS_CREAT: u32 = 1 << 0;
+const OFLAGS_DIRECTORY: u32 = 1 << 1;
+const OFLAGS_EXCL: u32 = 1 << 2;
+const OFLAGS_TRUNC: u32 = 1 << 3;
+
+const FDFLAGS_APPEND: u32 = 1 << 0;
+
+const LOOKUPFLAGS_SYMLINK_FOLLOW: u32 = 1 << 0;
+
+const FSTFLAGS_ATIM: u32 = 1 << 0;
+const FSTFLAGS_ATIM_NOW: u32 = 1 << 1;
+const FSTFLAGS_MTIM: u32 = 1 << 2;
+const FSTFLAGS_MTIM_NOW: u32 = 1 << 3;
+
+const CLOCKID_REALTIME: u32 = 0;
+const CLOCKID_MONOTONIC: u32 = 1;
+const CLOCKID_PROCESS_CPUTIME: u32 = 2;
+const CLOCKID_THREAD_CPUTIME: u32 = 3;
+
+const EVENTTYPE_CLOCK: u8 = 0;
+const SUBCLOCKFLAGS_ABSTIME: u16 = 1 << 0;
+
+const SUBSCRIPTION_SIZE: u32 = 48;
+const EVENT_SIZE: u32 = 32;
+const DIRENT_SIZE: usize = 24;
+
+static MONOTONIC_START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// Returns the address of element `index` of the array at `ptr`, failing
+/// with `EFAULT` if it is outside of the 32 bit address space.
+fn element_ptr(ptr: u32, index: u32, size: u32) -> Result<u32, Errno> {
+  index
+    .checked_mul(size)
+    .and_then(|offset| ptr.checked_add(offset))
+    .ok_or(Errno::FAULT)
+}
+
+/// The memory of the WebAssembly instance, with bounds checked accessors
+/// that fail with `EFAULT`.
+struct Memory<'a>(&'a mut [u8]);
+
+impl Memory<'_> {
+  fn slice(&self, ptr: u32, len: u32) -> Result<&[u8], Errno> {
+    let start = ptr as usize;
+    self.0.get(start..start + len as usize).ok_or(Errno::FAULT)
+  }
+
+  fn slice_mut(&mut self, ptr: u32, len: u32) -> Result<&mut [u8], Errno> {
+    let start = ptr as usize;
+    self
+      .0
+      .get_mut(start..start + len as usize)
+      .ok_or(Errno::FAULT)
+  }
+
+  fn read_u16(&self, ptr: u32) -> Result<u16, Errno> {
+    let bytes = self.slice(ptr, 2)?;
+    Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+  }
+
+  fn read_u32(&self, ptr: u32) -> Result<u32, Errno> {
+    let bytes = self.slice(ptr, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+  }
+
+  fn read_u64(&self, ptr: u32) -> Result<u64, Errno> {
+    let bytes = self.slice(ptr, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+  }
+
+  fn read_str(&self, ptr: u32, len: u32) -> Result<&str, Errno> {
+    std::str::from_utf8(self.slice(ptr, len)?).map_err(|_| Errno::ILSEQ)
+  }
+
+  fn write(&mut self, ptr: u32, bytes: &[u8]) -> Result<(), Errno> {
+    self
+      .slice_mut(ptr, bytes.len() as u32)?
+      .copy_from_slice(bytes);
+    Ok(())
+  }
+
+  fn write_u8(&mut self, ptr: u32, value: u8) -> Result<(), Errno> {
+    self.write(ptr, &[value])
+  }
+
+  fn write_u16(&mut self, ptr: u32, value: u16) -> Result<(), Errno> {
+    self.write(ptr, &value.to_le_bytes())
+  }
+
+  fn write_u32(&mut self, ptr: u32, value: u32) -> Result<(), Errno> {
+    self.write(ptr, &value.to_le_bytes())
+  }
+
+  fn write_u64(&mut self, ptr: u32, value: u64) -> Result<(), Errno> {
+    self.write(ptr, &value.to_le_bytes())
+  }
+
+  /// Reads an array of `iovec`s or `ciovec`s as `(buf, buf_len)` pairs.
+  fn iovecs(&self, ptr: u32, len: u32) -> Result<Vec<(u32, u32)>, Errno> {
+    let size = len.checked_mul(8).ok_or(Errno::INVAL)?;
+    let iovecs = self
+      .slice(ptr, size)?
+      .chunks_exact(8)
+      .map(|iovec| {
+        let buf = u32::from_le_bytes(iovec[..4].try_into().unwrap());
+        let buf_len = u32::from_le_bytes(iovec[4..].try_into().unwrap());
+        (buf, buf_len)
+      })
+      .collect();
+    Ok(iovecs)
+  }
+
+  fn write_filestat(&mut self, ptr: u32, stat: &FsStat) -> Result<(), Errno> {
+    let to_nanos = |ms: Option<u64>| ms.unwrap_or(0).saturating_mul(1_000_000);
+    self.slice_mut(ptr, 64)?.fill(0);
+    self.write_u64(ptr, stat.dev)?;
+    self.write_u64(ptr + 8, stat.ino)?;
+    self.write_u8(ptr + 16, stat_filetype(stat))?;
+    self.write_u64(ptr + 24, stat.nlink)?;
+    self.write_u64(ptr + 32, stat.size)?;
+    self.write_u64(ptr + 40, to_nanos(stat.atime))?;
+    self.write_u64(ptr + 48, to_nanos(stat.mtime))?;
+    self.write_u64(ptr + 56, to_nanos(stat.ctime))
+  }
+}
+
+fn stat_filetype(stat: &FsStat) -> u8 {
+  if stat.is_file {
+    filetype::REGULAR_FILE
+  } else if stat.is_directory {
+    filetype::DIRECTORY
+  } else if stat.is_symlink {
+    filetype::SYMBOLIC_LINK
+  } else if stat.is_char_device {
+    filetype::CHARACTER_DEVICE
+  } else if stat.is_block_device {
+    filetype::BLOCK_DEVICE
+  } else if stat.is_socket {
+    filetype::SOCKET_STREAM
+  } else {
+    filetype::UNKNOWN
+  }
+}
+
+/// A directory opened by the WebAssembly instance. Paths can't leave the
+/// preopened directory it was opened from.
+#[derive(Clone)]
+struct WasiDir {
+  root: Rc<PathBuf>,
+  /// The root with its symlinks resolved.
+  real_root: Rc<PathBuf>,
+  /// The normalized path of the directory relative to the root.
+  relative: Vec<String>,
+  /// The name of the preopened directory as seen by the instance.
+  preopen: Option<String>,
+}
+
+impl WasiDir {
+  fn host_path(&self) -> PathBuf {
+    let mut path = self.root.as_ref().clone();
+    path.extend(&self.relative);
+    path
+  }
+
+  /// Resolves a path relative to this directory, failing with
+  /// `ENOTCAPABLE` when it's absolute or leaves the preopened directory.
+  fn resolve(&self, path: &str) -> Result<(PathBuf, Vec<String>), Errno> {
+    if path.starts_with('/') {
+      return Err(Errno::NOTCAPABLE);
+    }
+    let mut relative = self.relative.clone();
+    for component in path.split('/') {
+      match component {
+        "" | "." => {}
+        ".." => {
+          relative.pop().ok_or(Errno::NOTCAPABLE)?;
+        }
+        component => {
+          // don't let the host interpret it as more than one component
+          let mut components = Path::new(component).components();
+          if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+          ) {
+            return Err(Errno::NOTCAPABLE);
+          }
+          relative.push(component.to_string());
+        }
+      }
+    }
+    let mut host_path = self.root.as_ref().clone();
+    host_path.extend(&relative);
+    Ok((host_path, relative))
+  }
+
+  /// Fails with `ENOTCAPABLE` when a path returned by `resolve` leaves the
+  /// preopened directory through a symlink. The last component is only
+  /// followed when `follow` is set, and a path that doesn't exist is checked
+  /// through its parent.
+  fn check_real_path(
+    &self,
+    fs: &dyn FileSystem,
+    path: &CheckedPath,
+    follow: bool,
+  ) -> WasiResult {
+    if **path == **self.root {
+      return Ok(());
+    }
+    // only used to find where the path leads, the permissions for `path`
+    // are already checked
+    let realpath = |path: &Path| {
+      fs.realpath_sync(&CheckedPath::unsafe_new(Cow::Borrowed(path)))
+    };
+    let real_path = match follow.then(|| realpath(path)) {
+      Some(Ok(real_path)) => real_path,
+      Some(Err(FsError::Io(err))) if err.kind() == io::ErrorKind::NotFound => {
+        // a dangling symlink could create a file outside
+        if fs.lstat_sync(path).is_ok() {
+          return Err(Errno::NOTCAPABLE.into());
+        }
+        real_parent_path(path, realpath)?
+      }
+      Some(Err(err)) => return Err(err.into()),
+      None => real_parent_path(path, realpath)?,
+    };
+    if real_path.starts_with(self.real_root.as_ref()) {
+      Ok(())
+    } else {
+      Err(Errno::NOTCAPABLE.into())
+    }
+  }
+}
+
+/// Returns `path` with the symlinks of its parent directory resolved.
+fn real_parent_path(
+  path: &Path,
+  realpath: impl Fn(&Path) -> Result<PathBuf, FsError>,
+) -> WasiResult<PathBuf> {
+  let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+    return Err(Errno::NOTCAPABLE.into());
+  };
+  Ok(realpath(parent)?.join(name))
+}
+
+#[derive(Clone)]
+enum WasiFd {
+  File {
+    file: Rc<dyn File>,
+    fdflags: u16,
+    rights_base: u64,
+    rights_inheriting: u64,
+  },
+  Dir(WasiDir),
+}
+
+/// The state of a `WASI` instance of `node:wasi`.
+pub struct WasiContext {
+  args: Vec<String>,
+  env: Vec<String>,
+  fds: RefCell<BTreeMap<u32, WasiFd>>,
+}
+
+impl GarbageCollected for WasiContext {
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"WasiContext"
+  }
+}
+
+impl WasiContext {
+  fn fd(&self, fd: u32) -> Result<WasiFd, Errno> {
+    self.fds.borrow().get(&fd).cloned().ok_or(Errno::BADF)
+  }
+
+  fn file(&self, fd: u32) -> Result<Rc<dyn File>, Errno> {
+    match self.fd(fd)? {
+      WasiFd::File { file, .. } => Ok(file),
+      WasiFd::Dir(_) => Err(Errno::ISDIR),
+    }
+  }
+
+  /// Like `file`, but fails with `ENOTCAPABLE` unless the file descriptor
+  /// has all the `rights`.
+  fn file_with_rights(
+    &self,
+    fd: u32,
+    rights: u64,
+  ) -> Result<Rc<dyn File>, Errno> {
+    match self.fd(fd)? {
+      WasiFd::File {
+        file, rights_base, ..
+      } if rights_base & rights == rights => Ok(file),
+      WasiFd::File { .. } => Err(Errno::NOTCAPABLE),
+      WasiFd::Dir(_) => Err(Errno::ISDIR),
+    }
+  }
+
+  fn dir(&self, fd: u32) -> Result<WasiDir, Errno> {
+    match self.fd(fd)? {
+      WasiFd::Dir(dir) => Ok(dir),
+      WasiFd::File { .. } => Err(Errno::NOTDIR),
+    }
+  }
+
+  fn insert(&self, entry: WasiFd) -> Result<u32, Errno> {
+    let mut fds = self.fds.borrow_mut();
+    let fd = (0..=u32::MAX)
+      .find(|fd| !fds.contains_key(fd))
+      .ok_or(Errno::MFILE)?;
+    fds.insert(fd, entry);
+    Ok(fd)
+  }
+
+  fn write_strings(
+    memory: &mut Memory,
+    strings: &[String],
+    ptrs: u32,
+    buf: u32,
+  ) -> WasiResult {
+    // Kept as `u64` so that the end of the last string may be the end of
+    // the address space.
+    let mut offset = u64::from(buf);
+    for (i, string) in strings.iter().enumerate() {
+      let ptr = u32::try_from(offset).map_err(|_| Errno::FAULT)?;
+      memory.write_u32(element_ptr(ptrs, i as u32, 4)?, ptr)?;
+      memory.write(ptr, string.as_bytes())?;
+      offset += string.len() as u64;
+      memory.write_u8(u32::try_from(offset).map_err(|_| Errno::FAULT)?, 0)?;
+      offset += 1;
+    }
+    Ok(())
+  }
+
+  fn write_sizes(
+    memory: &mut Memory,
+    strings: &[String],
+    count_ptr: u32,
+    size_ptr: u32,
+  ) -> WasiResult {
+    let size = strings.iter().map(|s| s.len() + 1).sum::<usize>();
+    memory.write_u32(count_ptr, strings.len() as u32)?;
+    memory.write_u32(size_ptr, size as u32)?;
+    Ok(())
+  }
+}
+
+fn check_path<P>(
+  state: &mut OpState,
+  path: PathBuf,
+  access: OpenAccessKind,
+) -> WasiResult<CheckedPath<'static>>
+where
+  P: NodePermissions + 'static,
+{
+  Ok(state.borrow_mut::<P>().check_open(
+    Cow::Owned(path),
+    access,
+    Some(API_NAME),
+  )?)
+}
+
+/// Checks the permissions for a path returned by `dir.resolve`, and that it
+/// doesn't leave the preopened directory through a symlink.
+fn check_dir_path<P>(
+  state: &mut OpState,
+  dir: &WasiDir,
+  path: PathBuf,
+  access: OpenAccessKind,
+) -> WasiResult<CheckedPath<'static>>
+where
+  P: NodePermissions + 'static,
+{
+  let follow = matches!(
+    access,
+    OpenAccessKind::Read | OpenAccessKind::Write | OpenAccessKind::ReadWrite
+  );
+  let path = check_path::<P>(state, path, access)?;
+  let fs = state.borrow::<FileSystemRc>().clone();
+  dir.check_real_path(&*fs, &path, follow)?;
+  Ok(path)
+}
+
+/// Seconds and nanoseconds since the Unix epoch.
+type Timestamp = (i64, u32);
+
+/// Resolves the access and modification times of a `*_set_times` call,
+/// keeping the current ones when not set.
+fn resolve_times(
+  stat: &FsStat,
+  atim: u64,
+  mtim: u64,
+  fst_flags: u32,
+) -> Result<(Timestamp, Timestamp), Errno> {
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default();
+  let resolve = |set: u32, set_now: u32, nanos: u64, current: Option<u64>| {
+    let time = match (fst_flags & set != 0, fst_flags & set_now != 0) {
+      (true, true) => return Err(Errno::INVAL),
+      (true, false) => Duration::from_nanos(nanos),
+      (false, true) => now,
+      (false, false) => Duration::from_millis(current.unwrap_or(0)),
+    };
+    Ok((time.as_secs() as i64, time.subsec_nanos()))
+  };
+  Ok((
+    resolve(FSTFLAGS_ATIM, FSTFLAGS_ATIM_NOW, atim, stat.atime)?,
+    resolve(FSTFLAGS_MTIM, FSTFLAGS_MTIM_NOW, mtim, stat.mtime)?,
+  ))
+}
+
+#[op2(stack_trace)]
+#[cppgc]
+pub fn op_node_wasi_new<P>(
+  state: &mut OpState,
+  #[serde] args: Vec<String>,
+  #[serde] env: Vec<String>,
+  #[serde] preopens: Vec<(String, String)>,
+  #[serde] stdio: Vec<u32>,
+) -> Result<WasiContext, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut fds = BTreeMap::new();
+  for (fd, rid) in stdio.into_iter().enumerate() {
+    let file = FileResource::get_file(state, rid)?;
+    fds.insert(
+      fd as u32,
+      WasiFd::File {
+        file,
+        fdflags: 0,
+        rights_base: rights::ALL,
+        rights_inheriting: 0,
+      },
+    );
+  }
+
+  let fs = state.borrow::<FileSystemRc>().clone();
+  for (name, host_path) in preopens {
+    let host_path =
+      check_path::<P>(state, PathBuf::from(host_path), OpenAccessKind::Read)?;
+    let stat = fs
+      .stat_sync(&host_path)
+      .map_err(|err| err.into_io_error())?;
+    if !stat.is_directory {
+      return Err(WasiError::Io(io::Error::new(
+        io::ErrorKind::NotADirectory,
+        format!("Preopen '{}' is not a directory", host_path.display()),
+      )));
+    }
+    let real_root = fs
+      .realpath_sync(&host_path)
+      .map_err(|err| err.into_io_error())?;
+    let fd = fds.len() as u32;
+    fds.insert(
+      fd,
+      WasiFd::Dir(WasiDir {
+        root: Rc::new(host_path.into_owned_path()),
+        real_root: Rc::new(real_root),
+        relative: Vec::new(),
+        preopen: Some(name),
+      }),
+    );
+  }
+
+  Ok(WasiContext {
+    args,
+    env,
+    fds: RefCell::new(fds),
+  })
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_args_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  argv: u32,
+  argv_buf: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno(WasiContext::write_strings(
+    &mut memory,
+    &ctx.args,
+    argv,
+    argv_buf,
+  ))
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_args_sizes_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  argc: u32,
+  argv_buf_size: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno(WasiContext::write_sizes(
+    &mut memory,
+    &ctx.args,
+    argc,
+    argv_buf_size,
+  ))
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_environ_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  environ: u32,
+  environ_buf: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno(WasiContext::write_strings(
+    &mut memory,
+    &ctx.env,
+    environ,
+    environ_buf,
+  ))
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_environ_sizes_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  environc: u32,
+  environ_buf_size: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno(WasiContext::write_sizes(
+    &mut memory,
+    &ctx.env,
+    environc,
+    environ_buf_size,
+  ))
+}
+
+fn clock_time(id: u32) -> Result<u64, Errno> {
+  let time = match id {
+    CLOCKID_REALTIME => SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default(),
+    // the CPU time clocks are approximated with the monotonic clock
+    CLOCKID_MONOTONIC | CLOCKID_PROCESS_CPUTIME | CLOCKID_THREAD_CPUTIME => {
+      MONOTONIC_START.elapsed()
+    }
+    _ => return Err(Errno::INVAL),
+  };
+  Ok(time.as_nanos() as u64)
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_clock_res_get(
+  #[buffer] memory: &mut [u8],
+  id: u32,
+  resolution: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    clock_time(id)?;
+    memory.write_u64(resolution, 1_000)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_clock_time_get(
+  #[buffer] memory: &mut [u8],
+  id: u32,
+  #[bigint] _precision: u64,
+  time: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    memory.write_u64(time, clock_time(id)?)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_advise(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  #[bigint] _offset: u64,
+  #[bigint] _len: u64,
+  _advice: u32,
+) -> Result<u32, WasiError> {
+  to_errno(ctx.file(fd).map(|_| ()).map_err(Into::into))
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_allocate(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  #[bigint] offset: u64,
+  #[bigint] len: u64,
+) -> Result<u32, WasiError> {
+  to_errno((|| {
+    let file = ctx.file(fd)?;
+    let size = offset.checked_add(len).ok_or(Errno::FBIG)?;
+    if file.clone().stat_sync()?.size < size {
+      file.truncate_sync(size)?;
+    }
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_close(#[cppgc] ctx: &WasiContext, fd: u32) -> u32 {
+  match ctx.fds.borrow_mut().remove(&fd) {
+    Some(_) => Errno::SUCCESS.0 as u32,
+    None => Errno::BADF.0 as u32,
+  }
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_datasync(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+) -> Result<u32, WasiError> {
+  to_errno((|| Ok(ctx.file(fd)?.datasync_sync()?))())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_sync(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+) -> Result<u32, WasiError> {
+  to_errno((|| Ok(ctx.file(fd)?.sync_sync()?))())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_fdstat_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  buf: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let (filetype, fdflags, rights_base, rights_inheriting) =
+      match ctx.fd(fd)? {
+        WasiFd::File {
+          file,
+          fdflags,
+          rights_base,
+          rights_inheriting,
+        } => {
+          let filetype = match file.stat_sync() {
+            Ok(stat) => stat_filetype(&stat),
+            // stdio that can't be stat'ed
+            Err(_) if fd <= 2 => filetype::CHARACTER_DEVICE,
+            Err(_) => filetype::UNKNOWN,
+          };
+          (filetype, fdflags, rights_base, rights_inheriting)
+        }
+        WasiFd::Dir(_) => (filetype::DIRECTORY, 0, rights::ALL, rights::ALL),
+      };
+    memory.slice_mut(buf, 24)?.fill(0);
+    memory.write_u8(buf, filetype)?;
+    memory.write_u16(buf + 2, fdflags)?;
+    memory.write_u64(buf + 8, rights_base)?;
+    memory.write_u64(buf + 16, rights_inheriting)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_fdstat_set_flags(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  flags: u32,
+) -> u32 {
+  match ctx.fds.borrow_mut().get_mut(&fd) {
+    Some(WasiFd::File { fdflags, .. }) => {
+      *fdflags = flags as u16;
+      Errno::SUCCESS.0 as u32
+    }
+    Some(WasiFd::Dir(_)) => Errno::ISDIR.0 as u32,
+    None => Errno::BADF.0 as u32,
+  }
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_fdstat_set_rights(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  #[bigint] base: u64,
+  #[bigint] inheriting: u64,
+) -> u32 {
+  match ctx.fds.borrow_mut().get_mut(&fd) {
+    Some(WasiFd::File {
+      rights_base,
+      rights_inheriting,
+      ..
+    }) => {
+      // rights can only be dropped
+      if base & !*rights_base != 0 || inheriting & !*rights_inheriting != 0 {
+        return Errno::NOTCAPABLE.0 as u32;
+      }
+      *rights_base = base;
+      *rights_inheriting = inheriting;
+      Errno::SUCCESS.0 as u32
+    }
+    Some(WasiFd::Dir(_)) => Errno::SUCCESS.0 as u32,
+    None => Errno::BADF.0 as u32,
+  }
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_fd_filestat_get<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  buf: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let stat = match ctx.fd(fd)? {
+      WasiFd::File { file, .. } => file.stat_sync()?,
+      WasiFd::Dir(dir) => {
+        let path =
+          check_path::<P>(state, dir.host_path(), OpenAccessKind::Read)?;
+        state.borrow::<FileSystemRc>().stat_sync(&path)?
+      }
+    };
+    memory.write_filestat(buf, &stat)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_filestat_set_size(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  #[bigint] size: u64,
+) -> Result<u32, WasiError> {
+  to_errno((|| Ok(ctx.file(fd)?.truncate_sync(size)?))())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_filestat_set_times(
+  #[cppgc] ctx: &WasiContext,
+  fd: u32,
+  #[bigint] atim: u64,
+  #[bigint] mtim: u64,
+  fst_flags: u32,
+) -> Result<u32, WasiError> {
+  to_errno((|| {
+    let file = ctx.file(fd)?;
+    let stat = file.clone().stat_sync()?;
+    let ((atime_secs, atime_nanos), (mtime_secs, mtime_nanos)) =
+      resolve_times(&stat, atim, mtim, fst_flags)?;
+    file.utime_sync(atime_secs, atime_nanos, mtime_secs, mtime_nanos)?;
+    Ok(())
+  })())
+}
+
+fn read_iovecs(
+  file: Rc<dyn File>,
+  memory: &mut Memory,
+  iovs: u32,
+  iovs_len: u32,
+) -> WasiResult<usize> {
+  let mut total = 0;
+  for (buf, buf_len) in memory.iovecs(iovs, iovs_len)? {
+    let buf = memory.slice_mut(buf, buf_len)?;
+    let read = file.clone().read_sync(buf)?;
+    total += read;
+    if read < buf.len() {
+      break;
+    }
+  }
+  Ok(total)
+}
+
+fn write_iovecs(
+  file: Rc<dyn File>,
+  memory: &Memory,
+  iovs: u32,
+  iovs_len: u32,
+) -> WasiResult<usize> {
+  let mut total = 0;
+  for (buf, buf_len) in memory.iovecs(iovs, iovs_len)? {
+    let buf = memory.slice(buf, buf_len)?;
+    file.clone().write_all_sync(buf)?;
+    total += buf.len();
+  }
+  Ok(total)
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_read(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  iovs: u32,
+  iovs_len: u32,
+  nread: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let file = ctx.file_with_rights(fd, rights::FD_READ)?;
+    let read = read_iovecs(file, &mut memory, iovs, iovs_len)?;
+    memory.write_u32(nread, read as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_pread(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  iovs: u32,
+  iovs_len: u32,
+  #[bigint] offset: u64,
+  nread: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let file = ctx.file_with_rights(fd, rights::FD_READ)?;
+    let position = file.clone().seek_sync(SeekFrom::Current(0))?;
+    file.clone().seek_sync(SeekFrom::Start(offset))?;
+    let result = read_iovecs(file.clone(), &mut memory, iovs, iovs_len);
+    file.seek_sync(SeekFrom::Start(position))?;
+    memory.write_u32(nread, result? as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_write(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  iovs: u32,
+  iovs_len: u32,
+  nwritten: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let file = ctx.file_with_rights(fd, rights::FD_WRITE)?;
+    let written = write_iovecs(file, &memory, iovs, iovs_len)?;
+    memory.write_u32(nwritten, written as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_pwrite(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  iovs: u32,
+  iovs_len: u32,
+  #[bigint] offset: u64,
+  nwritten: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let file = ctx.file_with_rights(fd, rights::FD_WRITE)?;
+    let position = file.clone().seek_sync(SeekFrom::Current(0))?;
+    file.clone().seek_sync(SeekFrom::Start(offset))?;
+    let result = write_iovecs(file.clone(), &memory, iovs, iovs_len);
+    file.seek_sync(SeekFrom::Start(position))?;
+    memory.write_u32(nwritten, result? as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_prestat_get(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  buf: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let Ok(WasiDir {
+      preopen: Some(name),
+      ..
+    }) = ctx.dir(fd)
+    else {
+      return Err(Errno::BADF.into());
+    };
+    memory.slice_mut(buf, 8)?.fill(0);
+    memory.write_u32(buf + 4, name.len() as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_prestat_dir_name(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  path: u32,
+  path_len: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let Ok(WasiDir {
+      preopen: Some(name),
+      ..
+    }) = ctx.dir(fd)
+    else {
+      return Err(Errno::BADF.into());
+    };
+    if (path_len as usize) < name.len() {
+      return Err(Errno::NAMETOOLONG.into());
+    }
+    memory.write(path, name.as_bytes())?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_fd_readdir<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  buf: u32,
+  buf_len: u32,
+  #[bigint] cookie: u64,
+  bufused: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let path = check_path::<P>(state, dir.host_path(), OpenAccessKind::Read)?;
+    let mut entries = state.borrow::<FileSystemRc>().read_dir_sync(&path)?;
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    let entries = [
+      (".".to_string(), filetype::DIRECTORY),
+      ("..".to_string(), filetype::DIRECTORY),
+    ]
+    .into_iter()
+    .chain(entries.into_iter().map(|entry| {
+      let filetype = if entry.is_symlink {
+        filetype::SYMBOLIC_LINK
+      } else if entry.is_directory {
+        filetype::DIRECTORY
+      } else if entry.is_file {
+        filetype::REGULAR_FILE
+      } else {
+        filetype::UNKNOWN
+      };
+      (entry.name, filetype)
+    }));
+
+    // entries that don't fit are truncated, and the instance reads the
+    // directory again from the last cookie it saw
+    let mut out = Vec::with_capacity(buf_len as usize);
+    for (index, (name, filetype)) in entries.enumerate().skip(cookie as usize) {
+      if out.len() >= buf_len as usize {
+        break;
+      }
+      let mut dirent = [0u8; DIRENT_SIZE];
+      dirent[0..8].copy_from_slice(&(index as u64 + 1).to_le_bytes());
+      dirent[16..20].copy_from_slice(&(name.len() as u32).to_le_bytes());
+      dirent[20] = filetype;
+      out.extend_from_slice(&dirent);
+      out.extend_from_slice(name.as_bytes());
+    }
+    out.truncate(buf_len as usize);
+    memory.write(buf, &out)?;
+    memory.write_u32(bufused, out.len() as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_renumber(
+  #[cppgc] ctx: &WasiContext,
+  from: u32,
+  to: u32,
+) -> u32 {
+  let mut fds = ctx.fds.borrow_mut();
+  if !fds.contains_key(&to) {
+    return Errno::BADF.0 as u32;
+  }
+  match fds.remove(&from) {
+    Some(entry) => {
+      fds.insert(to, entry);
+      Errno::SUCCESS.0 as u32
+    }
+    None => Errno::BADF.0 as u32,
+  }
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_seek(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  #[bigint] offset: i64,
+  whence: u32,
+  newoffset: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let pos = match whence {
+      0 => SeekFrom::Start(u64::try_from(offset).map_err(|_| Errno::INVAL)?),
+      1 => SeekFrom::Current(offset),
+      2 => SeekFrom::End(offset),
+      _ => return Err(Errno::INVAL.into()),
+    };
+    let position = ctx.file(fd)?.seek_sync(pos)?;
+    memory.write_u64(newoffset, position)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_fd_tell(
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  offset: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let position = ctx.file(fd)?.seek_sync(SeekFrom::Current(0))?;
+    memory.write_u64(offset, position)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_create_directory<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  path: u32,
+  path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let path =
+      check_dir_path::<P>(state, &dir, path, OpenAccessKind::WriteNoFollow)?;
+    state
+      .borrow::<FileSystemRc>()
+      .mkdir_sync(&path, false, None)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_filestat_get<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  flags: u32,
+  path: u32,
+  path_len: u32,
+  buf: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let fs = state.borrow::<FileSystemRc>().clone();
+    let stat = if flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0 {
+      fs.stat_sync(&check_dir_path::<P>(
+        state,
+        &dir,
+        path,
+        OpenAccessKind::Read,
+      )?)?
+    } else {
+      fs.lstat_sync(&check_dir_path::<P>(
+        state,
+        &dir,
+        path,
+        OpenAccessKind::ReadNoFollow,
+      )?)?
+    };
+    memory.write_filestat(buf, &stat)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_filestat_set_times<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  flags: u32,
+  path: u32,
+  path_len: u32,
+  #[bigint] atim: u64,
+  #[bigint] mtim: u64,
+  fst_flags: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let fs = state.borrow::<FileSystemRc>().clone();
+    let follow = flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+    let path = check_dir_path::<P>(
+      state,
+      &dir,
+      path,
+      if follow {
+        OpenAccessKind::Write
+      } else {
+        OpenAccessKind::WriteNoFollow
+      },
+    )?;
+    let stat = if follow {
+      fs.stat_sync(&path)?
+    } else {
+      fs.lstat_sync(&path)?
+    };
+    let ((atime_secs, atime_nanos), (mtime_secs, mtime_nanos)) =
+      resolve_times(&stat, atim, mtim, fst_flags)?;
+    if follow {
+      fs.utime_sync(&path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)?;
+    } else {
+      fs.lutime_sync(&path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)?;
+    }
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_link<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  old_fd: u32,
+  _old_flags: u32,
+  old_path: u32,
+  old_path_len: u32,
+  new_fd: u32,
+  new_path: u32,
+  new_path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let old_dir = ctx.dir(old_fd)?;
+    let (old_path, _) =
+      old_dir.resolve(memory.read_str(old_path, old_path_len)?)?;
+    let new_dir = ctx.dir(new_fd)?;
+    let (new_path, _) =
+      new_dir.resolve(memory.read_str(new_path, new_path_len)?)?;
+    let old_path =
+      check_dir_path::<P>(state, &old_dir, old_path, OpenAccessKind::Read)?;
+    let new_path = check_dir_path::<P>(
+      state,
+      &new_dir,
+      new_path,
+      OpenAccessKind::WriteNoFollow,
+    )?;
+    state
+      .borrow::<FileSystemRc>()
+      .link_sync(&old_path, &new_path)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_open<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  dirflags: u32,
+  path: u32,
+  path_len: u32,
+  oflags: u32,
+  #[bigint] rights_base: u64,
+  #[bigint] rights_inheriting: u64,
+  fdflags: u32,
+  opened_fd: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (host_path, relative) =
+      dir.resolve(memory.read_str(path, path_len)?)?;
+    let fs = state.borrow::<FileSystemRc>().clone();
+    let follow = dirflags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+    let read = rights_base & rights::FD_READ != 0;
+    let write = rights_base & rights::FD_WRITE != 0
+      || oflags & (OFLAGS_CREAT | OFLAGS_TRUNC) != 0;
+    let access = match (read || !write, write, follow) {
+      (true, true, true) => OpenAccessKind::ReadWrite,
+      (true, true, false) => OpenAccessKind::ReadWriteNoFollow,
+      (_, true, true) => OpenAccessKind::Write,
+      (_, true, false) => OpenAccessKind::WriteNoFollow,
+      (_, false, true) => OpenAccessKind::Read,
+      (_, false, false) => OpenAccessKind::ReadNoFollow,
+    };
+    let checked_path = check_dir_path::<P>(state, &dir, host_path, access)?;
+
+    let is_directory = match fs.stat_sync(&checked_path) {
+      Ok(stat) => stat.is_directory,
+      Err(FsError::Io(err)) if err.kind() == io::ErrorKind::NotFound => false,
+      Err(err) => return Err(err.into()),
+    };
+    let entry = if is_directory {
+      if oflags & (OFLAGS_CREAT | OFLAGS_EXCL) == OFLAGS_CREAT | OFLAGS_EXCL {
+        return Err(Errno::EXIST.into());
+      }
+      if oflags & OFLAGS_TRUNC != 0 || rights_base & rights::FD_WRITE != 0 {
+        return Err(Errno::ISDIR.into());
+      }
+      WasiFd::Dir(WasiDir {
+        root: dir.root.clone(),
+        real_root: dir.real_root.clone(),
+        relative,
+        preopen: None,
+      })
+    } else {
+      if oflags & OFLAGS_DIRECTORY != 0 {
+        return Err(Errno::NOTDIR.into());
+      }
+      let options = OpenOptions {
+        read: read || !write,
+        write: write && fdflags & FDFLAGS_APPEND == 0,
+        create: oflags & OFLAGS_CREAT != 0,
+        truncate: oflags & OFLAGS_TRUNC != 0,
+        append: fdflags & FDFLAGS_APPEND != 0,
+        create_new: oflags & (OFLAGS_CREAT | OFLAGS_EXCL)
+          == OFLAGS_CREAT | OFLAGS_EXCL,
+        custom_flags: None,
+        mode: Some(0o666),
+      };
+      let file = fs.open_sync(&checked_path, options)?;
+      WasiFd::File {
+        file,
+        fdflags: fdflags as u16,
+        rights_base,
+        rights_inheriting,
+      }
+    };
+    let fd = ctx.insert(entry)?;
+    memory.write_u32(opened_fd, fd)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_readlink<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  path: u32,
+  path_len: u32,
+  buf: u32,
+  buf_len: u32,
+  bufused: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let path =
+      check_dir_path::<P>(state, &dir, path, OpenAccessKind::ReadNoFollow)?;
+    let target = state.borrow::<FileSystemRc>().read_link_sync(&path)?;
+    let target = target.to_string_lossy();
+    let target = &target.as_bytes()[..target.len().min(buf_len as usize)];
+    memory.write(buf, target)?;
+    memory.write_u32(bufused, target.len() as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_remove_directory<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  path: u32,
+  path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let path =
+      check_dir_path::<P>(state, &dir, path, OpenAccessKind::WriteNoFollow)?;
+    let fs = state.borrow::<FileSystemRc>();
+    if !fs.lstat_sync(&path)?.is_directory {
+      return Err(Errno::NOTDIR.into());
+    }
+    fs.remove_sync(&path, false)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_rename<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  old_path: u32,
+  old_path_len: u32,
+  new_fd: u32,
+  new_path: u32,
+  new_path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let old_dir = ctx.dir(fd)?;
+    let (old_path, _) =
+      old_dir.resolve(memory.read_str(old_path, old_path_len)?)?;
+    let new_dir = ctx.dir(new_fd)?;
+    let (new_path, _) =
+      new_dir.resolve(memory.read_str(new_path, new_path_len)?)?;
+    let old_path = check_dir_path::<P>(
+      state,
+      &old_dir,
+      old_path,
+      OpenAccessKind::ReadWriteNoFollow,
+    )?;
+    let new_path = check_dir_path::<P>(
+      state,
+      &new_dir,
+      new_path,
+      OpenAccessKind::WriteNoFollow,
+    )?;
+    state
+      .borrow::<FileSystemRc>()
+      .rename_sync(&old_path, &new_path)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_symlink<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  old_path: u32,
+  old_path_len: u32,
+  fd: u32,
+  new_path: u32,
+  new_path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let target = memory.read_str(old_path, old_path_len)?;
+    let dir = ctx.dir(fd)?;
+    let (new_path, relative) =
+      dir.resolve(memory.read_str(new_path, new_path_len)?)?;
+    // the target is stored as is, so it has to stay inside the preopened
+    // directory when resolved from the link's directory
+    let link_dir = WasiDir {
+      relative: relative[..relative.len().saturating_sub(1)].to_vec(),
+      ..dir
+    };
+    link_dir.resolve(target)?;
+    let new_path = check_dir_path::<P>(
+      state,
+      &link_dir,
+      new_path,
+      OpenAccessKind::WriteNoFollow,
+    )?;
+    let target = CheckedPath::unsafe_new(Cow::Owned(PathBuf::from(target)));
+    state.borrow::<FileSystemRc>().symlink_sync(
+      &target,
+      &new_path,
+      None::<FsFileType>,
+    )?;
+    Ok(())
+  })())
+}
+
+#[op2(fast, stack_trace)]
+pub fn op_node_wasi_path_unlink_file<P>(
+  state: &mut OpState,
+  #[cppgc] ctx: &WasiContext,
+  #[buffer] memory: &mut [u8],
+  fd: u32,
+  path: u32,
+  path_len: u32,
+) -> Result<u32, WasiError>
+where
+  P: NodePermissions + 'static,
+{
+  let memory = Memory(memory);
+  to_errno((|| {
+    let dir = ctx.dir(fd)?;
+    let (path, _) = dir.resolve(memory.read_str(path, path_len)?)?;
+    let path =
+      check_dir_path::<P>(state, &dir, path, OpenAccessKind::WriteNoFollow)?;
+    let fs = state.borrow::<FileSystemRc>();
+    if fs.lstat_sync(&path)?.is_directory {
+      return Err(Errno::ISDIR.into());
+    }
+    fs.remove_sync(&path, false)?;
+    Ok(())
+  })())
+}
+
+/// Only clock subscriptions block, by sleeping until the earliest one
+/// expires. File descriptors are always reported as ready.
+#[op2(fast)]
+pub fn op_node_wasi_poll_oneoff(
+  #[buffer] memory: &mut [u8],
+  in_: u32,
+  out: u32,
+  nsubscriptions: u32,
+  nevents: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    if nsubscriptions == 0 {
+      return Err(Errno::INVAL.into());
+    }
+    let size = nsubscriptions
+      .checked_mul(SUBSCRIPTION_SIZE)
+      .ok_or(Errno::INVAL)?;
+    let mut subscriptions = memory.slice(in_, size)?.to_vec();
+    let mut fd_events = Vec::new();
+    let mut earliest_clock: Option<(u64, Duration)> = None;
+    for subscription in
+      subscriptions.chunks_exact_mut(SUBSCRIPTION_SIZE as usize)
+    {
+      let subscription = Memory(subscription);
+      let userdata = subscription.read_u64(0)?;
+      let tag = subscription.slice(8, 1)?[0];
+      if tag == EVENTTYPE_CLOCK {
+        let id = subscription.read_u32(16)?;
+        let timeout = subscription.read_u64(24)?;
+        let flags = subscription.read_u16(40)?;
+        let now = clock_time(id)?;
+        let timeout = if flags & SUBCLOCKFLAGS_ABSTIME != 0 {
+          timeout.saturating_sub(now)
+        } else {
+          timeout
+        };
+        let timeout = Duration::from_nanos(timeout);
+        if earliest_clock.is_none_or(|(_, earliest)| timeout < earliest) {
+          earliest_clock = Some((userdata, timeout));
+        }
+      } else {
+        fd_events.push((userdata, tag));
+      }
+    }
+
+    let mut events = Vec::new();
+    if fd_events.is_empty() {
+      if let Some((userdata, timeout)) = earliest_clock {
+        std::thread::sleep(timeout);
+        events.push((userdata, EVENTTYPE_CLOCK));
+      }
+    } else {
+      events = fd_events;
+    }
+    for (i, (userdata, eventtype)) in events.iter().enumerate() {
+      let mut event = [0; EVENT_SIZE as usize];
+      let mut fields = Memory(&mut event);
+      fields.write_u64(0, *userdata)?;
+      fields.write_u8(10, *eventtype)?;
+      memory.write(element_ptr(out, i as u32, EVENT_SIZE)?, &event)?;
+    }
+    memory.write_u32(nevents, events.len() as u32)?;
+    Ok(())
+  })())
+}
+
+#[op2(fast)]
+pub fn op_node_wasi_random_get(
+  #[buffer] memory: &mut [u8],
+  buf: u32,
+  buf_len: u32,
+) -> Result<u32, WasiError> {
+  let mut memory = Memory(memory);
+  to_errno((|| {
+    rand::thread_rng().fill_bytes(memory.slice_mut(buf, buf_len)?);
+    Ok(())
+  })())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn dir(relative: &[&str]) -> WasiDir {
+    WasiDir {
+      root: Rc::new(PathBuf::from("/sandbox")),
+      real_root: Rc::new(PathBuf::from("/sandbox")),
+      relative: relative.iter().map(|s| s.to_string()).collect(),
+      preopen: None,
+    }
+  }
+
+  #[test]
+  fn resolves_paths_inside_preopen() {
+    let (path, relative) = dir(&["a"]).resolve("b/./c/../d").unwrap();
+    assert_eq!(path, PathBuf::from("/sandbox/a/b/d"));
+    assert_eq!(relative, vec!["a", "b", "d"]);
+    let (path, _) = dir(&["a"]).resolve("..").unwrap();
+    assert_eq!(path, PathBuf::from("/sandbox"));
+  }
+
+  #[test]
+  fn refuses_paths_outside_preopen() {
+    assert_eq!(dir(&[]).resolve("..").unwrap_err(), Errno::NOTCAPABLE);
+    assert_eq!(
+      dir(&["a"]).resolve("b/../../..").unwrap_err(),
+      Errno::NOTCAPABLE
+    );
+    assert_eq!(dir(&[]).resolve("/etc").unwrap_err(), Errno::NOTCAPABLE);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn refuses_symlinks_outside_preopen() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path().join("root");
+    std::fs::create_dir(&root).unwrap();
+    std::fs::write(root.join("file"), "").unwrap();
+    std::fs::write(temp_dir.path().join("secret"), "").unwrap();
+    std::os::unix::fs::symlink(temp_dir.path(), root.join("escape")).unwrap();
+    std::os::unix::fs::symlink(
+      temp_dir.path().join("missing"),
+      root.join("dangling"),
+    )
+    .unwrap();
+    let dir = WasiDir {
+      real_root: Rc::new(std::fs::canonicalize(&root).unwrap()),
+      root: Rc::new(root),
+      relative: Vec::new(),
+      preopen: None,
+    };
+    let check = |path: &str, follow: bool| {
+      let (path, _) = dir.resolve(path).unwrap();
+      match dir.check_real_path(
+        &deno_fs::RealFs,
+        &CheckedPath::unsafe_new(Cow::Owned(path)),
+        follow,
+      ) {
+        Ok(()) => Ok(()),
+        Err(WasiError::Errno(errno)) => Err(errno),
+        Err(err) => panic!("{err}"),
+      }
+    };
+
+    assert_eq!(check(".", true), Ok(()));
+    assert_eq!(check("file", true), Ok(()));
+    assert_eq!(check("new", true), Ok(()));
+    // the link itself is inside
+    assert_eq!(check("escape", false), Ok(()));
+    assert_eq!(check("escape", true), Err(Errno::NOTCAPABLE));
+    assert_eq!(check("escape/secret", false), Err(Errno::NOTCAPABLE));
+    assert_eq!(check("dangling", true), Err(Errno::NOTCAPABLE));
+  }
+
+  #[test]
+  fn writes_memory() {
+    let mut buf = vec![0u8; 16];
+    let mut memory = Memory(&mut buf);
+    memory.write_u32(4, 0x01020304).unwrap();
+    assert_eq!(memory.read_u32(4).unwrap(), 0x01020304);
+    assert_eq!(memory.write_u64(12, 1).unwrap_err(), Errno::FAULT);
+    assert_eq!(memory.iovecs(0, 2).unwrap(), vec![(0, 0x01020304), (0, 0)]);
+    assert_eq!(memory.iovecs(u32::MAX - 3, 1).unwrap_err(), Errno::FAULT);
+    assert_eq!(memory.iovecs(0, u32::MAX).unwrap_err(), Errno::INVAL);
+    assert_eq!(element_ptr(8, 2, 4), Ok(16));
+    assert_eq!(element_ptr(u32::MAX - 4, 2, 4), Err(Errno::FAULT));
+    assert_eq!(element_ptr(0, u32::MAX, 8), Err(Errno::FAULT));
+  }
+}
@@ -45,6 +45,9 @@ function initialize(args) {
       moduleSpecifier,
     );
     internals.__setupChildProcessIpcChannel();
+    internals.__initCluster();
+    internals.__initHeapSnapshotSignal();
+    internals.__initTraceEvents();
     // `Deno[Deno.internal].requireImpl` will be unreachable after this line.
     delete internals.requireImpl;
   } else {
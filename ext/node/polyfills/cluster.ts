@@ -1,70 +1,652 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 // Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
+import { internals, primordials } from "ext:core/mod.js";
 import { EventEmitter } from "node:events";
+import { fork as forkProcess } from "node:child_process";
+import process from "node:process";
+import { nextTick } from "ext:deno_node/_next_tick.ts";
+import {
+  constants as TCPConstants,
+  TCP,
+} from "ext:deno_node/internal_binding/tcp_wrap.ts";
+import { codeMap } from "ext:deno_node/internal_binding/uv.ts";
+import { isWindows } from "ext:deno_node/_util/os.ts";
+
+const {
+  ArrayPrototypeSlice,
+  FunctionPrototypeCall,
+  MapPrototypeClear,
+  MapPrototypeDelete,
+  MapPrototypeForEach,
+  MapPrototypeGet,
+  MapPrototypeSet,
+  ObjectKeys,
+  ObjectValues,
+  ReflectApply,
+  SafeMap,
+  SafeSet,
+  SetPrototypeAdd,
+  SetPrototypeDelete,
+  SetPrototypeGetSize,
+  SetPrototypeHas,
+} = primordials;
+
+export const SCHED_NONE = 1;
+export const SCHED_RR = 2;
+
+const DEFAULT_IPV4_ADDR = "0.0.0.0";
 
-/** A Worker object contains all public information and method about a worker.
- * In the primary it can be obtained using cluster.workers. In a worker it can
- * be obtained using cluster.worker.
- */
-export class Worker {
-  constructor() {
-    notImplemented("cluster.Worker.prototype.constructor");
-  }
-}
-/** Calls .disconnect() on each worker in cluster.workers. */
-export function disconnect() {
-  notImplemented("cluster.disconnect");
-}
-/** Spawn a new worker process. */
-// deno-lint-ignore no-explicit-any
-export function fork(_env?: any): Worker {
-  notImplemented("cluster.fork");
-}
 /** True if the process is a primary. This is determined by
  * the process.env.NODE_UNIQUE_ID. If process.env.NODE_UNIQUE_ID is undefined,
  * then isPrimary is true. */
-// TODO(@marvinhagemeister): Replace this with an env check once
-// we properly set NODE_UNIQUE_ID
-export const isPrimary = true;
+export let isPrimary = true;
 /** True if the process is not a primary (it is the negation of
  * cluster.isPrimary). */
-export const isWorker = false;
+export let isWorker = false;
 /** Deprecated alias for cluster.isPrimary. details. */
-export const isMaster = isPrimary;
+export let isMaster = isPrimary;
 /** The scheduling policy, either cluster.SCHED_RR for round-robin or
  * cluster.SCHED_NONE to leave it to the operating system. This is a global
  * setting and effectively frozen once either the first worker is spawned, or
- * .setupPrimary() is called, whichever comes first. */
-export const schedulingPolicy = undefined;
+ * .setupPrimary() is called, whichever comes first.
+ *
+ * Round-robin isn't implemented yet. The primary sends its listening socket
+ * to the workers, which accept connections on it themselves, so the
+ * operating system distributes the connections between them, as with
+ * cluster.SCHED_NONE. */
+export const schedulingPolicy = SCHED_NONE;
 /** The settings object */
-export const settings = {};
-/** setupPrimary is used to change the default 'fork' behavior. Once called,
- * the settings will be present in cluster.settings. */
-export function setupPrimary() {
-  notImplemented("cluster.setupPrimary");
-}
-/** Deprecated alias for .setupPrimary(). */
-export const setupMaster = setupPrimary;
+export let settings = {};
 /** A reference to the current worker object. Not available in the primary
  * process. */
-export const worker = undefined;
+export let worker: Worker | undefined = undefined;
 /** A hash that stores the active worker objects, keyed by id field. Makes it
  * easy to loop through all the workers. It is only available in the primary
  * process. */
-export const workers = {};
+export const workers: Record<string, Worker> = {};
 
-export const SCHED_NONE = 1;
-export const SCHED_RR = 2;
+// Messages between the primary and its workers are sent over the IPC channel
+// of `child_process.fork()`, tagged so they are emitted as "internalMessage"
+// events instead of "message".
+const callbacks = new SafeMap();
+let seq = 0;
+
+function sendHelper(
+  // deno-lint-ignore no-explicit-any
+  proc: any,
+  // deno-lint-ignore no-explicit-any
+  message: any,
+  // deno-lint-ignore no-explicit-any
+  cb?: (reply: any, handle?: TCP) => void,
+  handle?: TCP,
+) {
+  if (!proc.connected) {
+    return false;
+  }
+
+  message = { cmd: "NODE_CLUSTER", ...message, seq };
+
+  if (typeof cb === "function") {
+    MapPrototypeSet(callbacks, seq, cb);
+  }
+
+  seq += 1;
+  return proc.send(message, handle);
+}
+
+// Returns an internalMessage listener that hands off normal messages
+// to the callback but also handles ack'ing messages.
+function internal(
+  worker: Worker,
+  // deno-lint-ignore no-explicit-any
+  cb: (message: any, handle?: TCP) => void,
+) {
+  // deno-lint-ignore no-explicit-any
+  return function onInternalMessage(message: any, handle?: TCP) {
+    if (message.cmd !== "NODE_CLUSTER") {
+      return;
+    }
+
+    let fn = cb;
+
+    if (message.ack !== undefined) {
+      const callback = MapPrototypeGet(callbacks, message.ack);
+
+      if (callback !== undefined) {
+        fn = callback;
+        MapPrototypeDelete(callbacks, message.ack);
+      }
+    }
+
+    FunctionPrototypeCall(fn, worker, message, handle);
+  };
+}
+
+/** A Worker object contains all public information and method about a worker.
+ * In the primary it can be obtained using cluster.workers. In a worker it can
+ * be obtained using cluster.worker.
+ */
+export class Worker extends EventEmitter {
+  id: number;
+  // deno-lint-ignore no-explicit-any
+  process: any;
+  state: string;
+  exitedAfterDisconnect: boolean | undefined;
+
+  // deno-lint-ignore no-explicit-any
+  constructor(options?: any) {
+    super();
+
+    if (options === null || typeof options !== "object") {
+      options = {};
+    }
+
+    this.exitedAfterDisconnect = undefined;
+    this.state = options.state || "none";
+    this.id = options.id | 0;
+
+    if (options.process) {
+      this.process = options.process;
+      this.process.on(
+        "error",
+        (code: unknown, signal: unknown) => this.emit("error", code, signal),
+      );
+      this.process.on(
+        "message",
+        (message: unknown, handle: unknown) =>
+          this.emit("message", message, handle),
+      );
+    }
+  }
+
+  kill(signo?: string) {
+    this.destroy(signo);
+  }
+
+  send(...args: unknown[]) {
+    return ReflectApply(this.process.send, this.process, args);
+  }
+
+  isDead() {
+    return this.process.exitCode != null || this.process.signalCode != null;
+  }
+
+  isConnected() {
+    return this.process.connected;
+  }
+
+  disconnect() {
+    if (isPrimary) {
+      this.exitedAfterDisconnect = true;
+      sendHelper(this.process, { act: "disconnect" });
+      removeHandlesForWorker(this);
+      removeWorker(this);
+    } else if (this.state !== "disconnecting" && this.state !== "destroying") {
+      this.state = "disconnecting";
+      disconnectWorker(this, false);
+    }
+
+    return this;
+  }
+
+  destroy(signo?: string) {
+    if (isPrimary) {
+      this.process.kill(signo || "SIGTERM");
+      return;
+    }
+
+    if (this.state === "destroying") {
+      return;
+    }
+
+    this.exitedAfterDisconnect = true;
+
+    if (!this.isConnected()) {
+      process.exit(0);
+    } else {
+      this.state = "destroying";
+      sendHelper(
+        process,
+        { act: "exitedAfterDisconnect" },
+        () => process.disconnect(),
+      );
+      process.once("disconnect", () => process.exit(0));
+    }
+  }
+}
+
+// --- primary ---
+
+/**
+ * A socket shared by the workers that listen on the same address. The
+ * primary listens on it without accepting connections, and sends it to every
+ * worker, which accept the connections themselves.
+ */
+class SharedHandle {
+  key: string;
+  errno = 0;
+  handle: TCP | undefined;
+  workers = new SafeSet();
+
+  constructor(key: string, address: string | null, port: number) {
+    this.key = key;
+
+    // Handles can't be sent to child processes on Windows yet.
+    if (isWindows) {
+      this.errno = MapPrototypeGet(codeMap, "ENOTSUP");
+      return;
+    }
+
+    try {
+      const listener = Deno.listen({
+        hostname: address || DEFAULT_IPV4_ADDR,
+        port,
+        transport: "tcp",
+      });
+      this.handle = new TCP(TCPConstants.SERVER, listener);
+    } catch (e) {
+      this.errno = MapPrototypeGet(codeMap, e.code ?? "UNKNOWN") ??
+        MapPrototypeGet(codeMap, "UNKNOWN");
+    }
+  }
+
+  add(worker: Worker, send: (errno: number, handle?: TCP) => void) {
+    SetPrototypeAdd(this.workers, worker.id);
+    send(this.errno, this.handle);
+  }
+
+  remove(worker: Worker) {
+    if (!SetPrototypeHas(this.workers, worker.id)) {
+      return false;
+    }
+
+    SetPrototypeDelete(this.workers, worker.id);
+
+    if (SetPrototypeGetSize(this.workers) !== 0) {
+      return false;
+    }
+
+    this.handle?.close();
+    return true;
+  }
+}
+
+const handles = new SafeMap();
+const intercom = new EventEmitter();
+let ids = 0;
+
+/** setupPrimary is used to change the default 'fork' behavior. Once called,
+ * the settings will be present in cluster.settings. */
+// deno-lint-ignore no-explicit-any
+export function setupPrimary(options?: any) {
+  settings = {
+    args: ArrayPrototypeSlice(process.argv, 2),
+    exec: process.argv[1],
+    execArgv: process.execArgv,
+    silent: false,
+    ...cluster.settings,
+    ...options,
+  };
+  cluster.settings = settings;
+  nextTick(setupSettingsNT, settings);
+}
+/** Deprecated alias for .setupPrimary(). */
+export const setupMaster = setupPrimary;
+
+function setupSettingsNT(settings: Record<string, unknown>) {
+  cluster.emit("setup", settings);
+}
+
+// deno-lint-ignore no-explicit-any
+function createWorkerProcess(id: number, env: any) {
+  // deno-lint-ignore no-explicit-any
+  const settings = cluster.settings as any;
+  const workerEnv = { ...process.env, ...env, NODE_UNIQUE_ID: `${id}` };
+
+  return forkProcess(settings.exec, settings.args, {
+    cwd: settings.cwd,
+    env: workerEnv,
+    serialization: settings.serialization,
+    silent: settings.silent,
+    windowsHide: settings.windowsHide,
+    execArgv: ArrayPrototypeSlice(settings.execArgv),
+    stdio: settings.stdio,
+    gid: settings.gid,
+    uid: settings.uid,
+  });
+}
+
+function removeWorker(worker: Worker) {
+  delete workers[worker.id];
+
+  if (ObjectKeys(workers).length === 0) {
+    intercom.emit("disconnect");
+  }
+}
+
+function removeHandlesForWorker(worker: Worker) {
+  MapPrototypeForEach(handles, (handle: SharedHandle, key: string) => {
+    if (handle.remove(worker)) {
+      MapPrototypeDelete(handles, key);
+    }
+  });
+}
+
+/** Spawn a new worker process. */
+// deno-lint-ignore no-explicit-any
+export function fork(env?: any): Worker {
+  setupPrimary();
+  const id = ++ids;
+  const workerProcess = createWorkerProcess(id, env);
+  const worker = new Worker({
+    id: id,
+    process: workerProcess,
+  });
+
+  worker.on("message", function (message, handle) {
+    cluster.emit("message", this, message, handle);
+  });
+
+  worker.process.once("exit", (exitCode: number, signalCode: string) => {
+    /*
+     * Remove the worker from the workers list only
+     * if it has disconnected, otherwise we might
+     * still want to access it.
+     */
+    if (!worker.isConnected()) {
+      removeHandlesForWorker(worker);
+      removeWorker(worker);
+    }
+
+    worker.exitedAfterDisconnect = !!worker.exitedAfterDisconnect;
+    worker.state = "dead";
+    worker.emit("exit", exitCode, signalCode);
+    cluster.emit("exit", worker, exitCode, signalCode);
+  });
+
+  worker.process.once("disconnect", () => {
+    /*
+     * Now is a good time to remove the handles
+     * associated with this worker because it is
+     * not connected to the primary anymore.
+     */
+    removeHandlesForWorker(worker);
+
+    /*
+     * Remove the worker from the workers list only
+     * if its process has exited. Otherwise, we might
+     * still want to access it.
+     */
+    if (worker.isDead()) {
+      removeWorker(worker);
+    }
+
+    worker.exitedAfterDisconnect = !!worker.exitedAfterDisconnect;
+    worker.state = "disconnected";
+    worker.emit("disconnect");
+    cluster.emit("disconnect", worker);
+  });
+
+  worker.process.on("internalMessage", internal(worker, onPrimaryMessage));
+  nextTick(emitForkNT, worker);
+  workers[worker.id] = worker;
+  return worker;
+}
+
+function emitForkNT(worker: Worker) {
+  cluster.emit("fork", worker);
+}
+
+/** Calls .disconnect() on each worker in cluster.workers. */
+export function disconnect(cb?: () => void) {
+  const values = ObjectValues(workers);
+
+  if (values.length === 0) {
+    nextTick(() => intercom.emit("disconnect"));
+  } else {
+    for (let i = 0; i < values.length; ++i) {
+      if (values[i].isConnected()) {
+        values[i].disconnect();
+      }
+    }
+  }
+
+  if (typeof cb === "function") {
+    intercom.once("disconnect", cb);
+  }
+}
+
+// deno-lint-ignore no-explicit-any
+function onPrimaryMessage(this: Worker, message: any) {
+  switch (message.act) {
+    case "online":
+      this.state = "online";
+      this.emit("online");
+      cluster.emit("online", this);
+      break;
+    case "queryServer":
+      queryServer(this, message);
+      break;
+    case "listening":
+      listening(this, message);
+      break;
+    case "close":
+      closeServer(this, message);
+      break;
+    case "exitedAfterDisconnect":
+      this.exitedAfterDisconnect = true;
+      sendHelper(this.process, { ack: message.seq });
+      break;
+  }
+}
+
+// deno-lint-ignore no-explicit-any
+function queryServer(worker: Worker, message: any) {
+  // Stop processing if worker already disconnecting
+  if (worker.exitedAfterDisconnect) {
+    return;
+  }
+
+  const key = `${message.address}:${message.port}:${message.addressType}:` +
+    `${message.fd}:${message.index}`;
+  let handle = MapPrototypeGet(handles, key);
+
+  if (handle === undefined) {
+    handle = new SharedHandle(key, message.address, message.port);
+    MapPrototypeSet(handles, key, handle);
+  }
+
+  handle.add(worker, (errno: number, listenHandle?: TCP) => {
+    sendHelper(
+      worker.process,
+      {
+        errno,
+        key,
+        ack: message.seq,
+      },
+      undefined,
+      listenHandle,
+    );
+  });
+}
+
+// deno-lint-ignore no-explicit-any
+function listening(worker: Worker, message: any) {
+  const info = {
+    addressType: message.addressType,
+    address: message.address,
+    port: message.port,
+    fd: message.fd,
+  };
+
+  worker.state = "listening";
+  worker.emit("listening", info);
+  cluster.emit("listening", worker, info);
+}
+
+// deno-lint-ignore no-explicit-any
+function closeServer(worker: Worker, message: any) {
+  const key = message.key;
+  const handle = MapPrototypeGet(handles, key);
+
+  if (handle !== undefined && handle.remove(worker)) {
+    MapPrototypeDelete(handles, key);
+  }
+}
+
+// --- worker ---
+
+// the servers of this worker, keyed by the handle key of the primary
+const servers = new SafeMap();
+const indexes = new SafeMap();
+
+function setupWorker() {
+  const current = new Worker({
+    id: +process.env.NODE_UNIQUE_ID! | 0,
+    process,
+    state: "online",
+  });
+  worker = current;
+  cluster.worker = current;
+
+  process.once("disconnect", () => {
+    current.emit("disconnect");
+
+    if (!current.exitedAfterDisconnect) {
+      // Unexpected disconnect, primary exited, or some such nastiness, so
+      // worker exits immediately.
+      process.exit(0);
+    }
+  });
+
+  process.on("internalMessage", internal(current, onWorkerMessage));
+  sendHelper(process, { act: "online" });
+}
+
+// deno-lint-ignore no-explicit-any
+function onWorkerMessage(this: Worker, message: any) {
+  if (message.act === "disconnect") {
+    disconnectWorker(this, true);
+  }
+}
+
+/**
+ * Asks the primary for the socket that the server shares with the other
+ * workers, and calls `cb` with an error code or the listening handle.
+ */
+function _getServer(
+  // deno-lint-ignore no-explicit-any
+  obj: any,
+  // deno-lint-ignore no-explicit-any
+  options: any,
+  cb: (errno: number, handle?: TCP) => void,
+) {
+  const indexesKey = `${options.address}:${options.port}:` +
+    `${options.addressType}:${options.fd}`;
+  let index = MapPrototypeGet(indexes, indexesKey);
+
+  if (index === undefined) {
+    index = 0;
+  } else {
+    index++;
+  }
+
+  MapPrototypeSet(indexes, indexesKey, index);
+
+  const message = {
+    act: "queryServer",
+    index,
+    data: null,
+    ...options,
+  };
+
+  // deno-lint-ignore no-explicit-any
+  sendHelper(process, message, (reply: any, handle?: TCP) => {
+    if (reply.errno) {
+      cb(reply.errno);
+      return;
+    }
+
+    MapPrototypeSet(servers, reply.key, obj);
+    obj.once("close", () => {
+      if (MapPrototypeGet(servers, reply.key) !== obj) {
+        return;
+      }
+      MapPrototypeDelete(servers, reply.key);
+      sendHelper(process, { act: "close", key: reply.key });
+    });
+    cb(0, handle);
+  });
+
+  obj.once("listening", () => {
+    cluster.worker!.state = "listening";
+    const address = obj.address();
+    message.act = "listening";
+    message.port = (address && address.port) || options.port;
+    sendHelper(process, message);
+  });
+}
+
+function disconnectWorker(worker: Worker, primaryInitiated: boolean) {
+  worker.exitedAfterDisconnect = true;
+  let waitingCount = 1;
+
+  function checkWaitingCount() {
+    waitingCount--;
+
+    if (waitingCount === 0) {
+      // If disconnect is worker initiated, wait for ack to be sure
+      // exitedAfterDisconnect is properly set in the primary, otherwise, if
+      // it's primary initiated there's no need to send the
+      // exitedAfterDisconnect message
+      if (primaryInitiated) {
+        process.disconnect();
+      } else {
+        sendHelper(
+          process,
+          { act: "exitedAfterDisconnect" },
+          () => process.disconnect(),
+        );
+      }
+    }
+  }
+
+  // deno-lint-ignore no-explicit-any
+  MapPrototypeForEach(servers, (server: any) => {
+    waitingCount++;
+    server.close(checkWaitingCount);
+  });
+  MapPrototypeClear(servers);
+  checkWaitingCount();
+}
+
+/** Turns this process into a worker if it was forked by a primary. */
+function initCluster() {
+  if (process.env.NODE_UNIQUE_ID === undefined || !process.connected) {
+    return;
+  }
+
+  isPrimary = false;
+  isMaster = false;
+  isWorker = true;
+  cluster.isPrimary = false;
+  cluster.isMaster = false;
+  cluster.isWorker = true;
+  setupWorker();
+}
+
+internals.__initCluster = initCluster;
 
 const cluster = new EventEmitter() as EventEmitter & {
   isWorker: boolean;
   isMaster: boolean;
   isPrimary: boolean;
-  Worker: Worker;
+  Worker: typeof Worker;
+  worker: Worker | undefined;
   workers: Record<string, Worker>;
   settings: Record<string, unknown>;
+  schedulingPolicy: number;
   // deno-lint-ignore no-explicit-any
   setupPrimary(options?: any): void;
   // deno-lint-ignore no-explicit-any
@@ -73,6 +655,7 @@ const cluster = new EventEmitter() as EventEmitter & {
   fork(env: any): Worker;
   // deno-lint-ignore no-explicit-any
   disconnect(cb: any): void;
+  _getServer: typeof _getServer;
   SCHED_NONE: 1;
   SCHED_RR: 2;
 };
@@ -80,12 +663,15 @@ cluster.isWorker = isWorker;
 cluster.isMaster = isMaster;
 cluster.isPrimary = isPrimary;
 cluster.Worker = Worker;
+cluster.worker = worker;
 cluster.workers = workers;
-cluster.settings = {};
+cluster.settings = settings;
+cluster.schedulingPolicy = schedulingPolicy;
 cluster.setupPrimary = setupPrimary;
 cluster.setupMaster = setupMaster;
 cluster.fork = fork;
 cluster.disconnect = disconnect;
+cluster._getServer = _getServer;
 cluster.SCHED_NONE = SCHED_NONE;
 cluster.SCHED_RR = SCHED_RR;
 
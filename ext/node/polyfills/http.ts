@@ -66,9 +66,14 @@ import {
   ERR_INVALID_HTTP_TOKEN,
   ERR_INVALID_PROTOCOL,
   ERR_UNESCAPED_CHARACTERS,
+  uvExceptionWithHostPort,
 } from "ext:deno_node/internal/errors.ts";
 import { getTimerDuration } from "ext:deno_node/internal/timers.mjs";
-import { serve, upgradeHttpRaw } from "ext:deno_http/00_serve.ts";
+import {
+  kListener,
+  serve,
+  upgradeHttpRaw,
+} from "ext:deno_http/00_serve.ts";
 import { headersEntries } from "ext:deno_fetch/20_headers.js";
 import { Response } from "ext:deno_fetch/23_response.js";
 import {
@@ -86,6 +91,8 @@ import { UpgradedConn } from "ext:deno_net/01_net.js";
 import { STATUS_CODES } from "node:_http_server";
 import { methods as METHODS } from "node:_http_common";
 import { deprecate } from "node:util";
+import cluster from "node:cluster";
+import { isIP } from "ext:deno_node/internal/net.ts";
 
 const { internalRidSymbol } = core;
 const { ArrayIsArray, StringPrototypeToLowerCase, SafeArrayIterator } =
@@ -2072,7 +2079,6 @@ export class ServerImpl extends EventEmitter {
   }
 
   _serve() {
-    const ac = new AbortController();
     const handler = (request: Request, info: Deno.ServeHandlerInfo) => {
       const socket = new FakeSocket({
         remoteAddress: info.remoteAddr.hostname,
@@ -2128,12 +2134,44 @@ export class ServerImpl extends EventEmitter {
     if (this.#hasClosed) {
       return;
     }
+
+    // The workers of a cluster share the listening socket of the primary,
+    // TLS servers still listen on their own.
+    if (cluster.isWorker && this._additionalServeOptions === undefined) {
+      const { hostname, port } = this.#addr!;
+      cluster._getServer(
+        this,
+        { address: hostname, port, addressType: isIP(hostname) || 4 },
+        (errno: number, handle?: TCP) => {
+          if (this.#hasClosed) {
+            handle?.close();
+            return;
+          }
+          if (errno) {
+            this.emit(
+              "error",
+              uvExceptionWithHostPort(errno, "bind", hostname, port),
+            );
+            return;
+          }
+
+          this.#serve(handler, { [kListener]: handle!.listener });
+        },
+      );
+      return;
+    }
+
+    this.#serve(handler, this.#addr);
+  }
+
+  #serve(handler: Deno.ServeHandler, listenOptions: object) {
+    const ac = new AbortController();
     this.#ac = ac;
     try {
       this.#server = serve(
         {
           handler: handler as Deno.ServeHandler,
-          ...this.#addr,
+          ...listenOptions,
           signal: ac.signal,
           // @ts-ignore Might be any without `--unstable` flag
           onListen: ({ port }) => {
@@ -10,10 +10,14 @@ import {
   op_http2_client_get_response,
   op_http2_client_get_response_body_chunk,
   op_http2_client_get_response_trailers,
+  op_http2_client_ping,
+  op_http2_client_remote_max_concurrent_streams,
   op_http2_client_request,
   op_http2_client_reset_stream,
   op_http2_client_send_data,
   op_http2_client_send_trailers,
+  op_http2_client_set_local_window_size,
+  op_http2_client_settings,
   op_http2_connect,
   op_http2_poll_client_connection,
   op_http_set_response_trailers,
@@ -28,7 +32,16 @@ import { emitWarning } from "node:process";
 import Stream from "node:stream";
 import { connect as netConnect, Server, Socket, TCP } from "node:net";
 import { connect as tlsConnect } from "node:tls";
-import { TypedArray } from "ext:deno_node/internal/util/types.ts";
+import {
+  isArrayBufferView,
+  TypedArray,
+} from "ext:deno_node/internal/util/types.ts";
+import {
+  validateBuffer,
+  validateFunction,
+  validateInt32,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
 import {
   kHandle,
   kMaybeDestroy,
@@ -49,10 +62,15 @@ import {
   ERR_HTTP2_GOAWAY_SESSION,
   ERR_HTTP2_HEADERS_SENT,
   ERR_HTTP2_INFO_STATUS_NOT_ALLOWED,
+  ERR_HTTP2_INVALID_PACKED_SETTINGS_LENGTH,
   ERR_HTTP2_INVALID_PSEUDOHEADER,
   ERR_HTTP2_INVALID_SESSION,
+  ERR_HTTP2_INVALID_SETTING_VALUE,
   ERR_HTTP2_INVALID_STREAM,
+  ERR_HTTP2_MAX_PENDING_SETTINGS_ACK,
   ERR_HTTP2_NO_SOCKET_MANIPULATION,
+  ERR_HTTP2_PING_CANCEL,
+  ERR_HTTP2_PING_LENGTH,
   ERR_HTTP2_SESSION_ERROR,
   ERR_HTTP2_SOCKET_UNBOUND,
   ERR_HTTP2_STATUS_INVALID,
@@ -62,6 +80,7 @@ import {
   ERR_HTTP2_TRAILERS_ALREADY_SENT,
   ERR_HTTP2_TRAILERS_NOT_READY,
   ERR_HTTP2_UNSUPPORTED_PROTOCOL,
+  ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
   ERR_INVALID_HTTP_TOKEN,
   ERR_SOCKET_CLOSED,
@@ -94,8 +113,10 @@ const kEncrypted = Symbol("encrypted");
 const kID = Symbol("id");
 const kInit = Symbol("init");
 const kInfoHeaders = Symbol("sent-info-headers");
+const kLocalSettings = Symbol("local-settings");
 const kOrigin = Symbol("origin");
 const kPendingRequestCalls = Symbol("kPendingRequestCalls");
+const kRemoteSettings = Symbol("remote-settings");
 const kSentHeaders = Symbol("sent-headers");
 const kSentTrailers = Symbol("sent-trailers");
 const kState = Symbol("state");
@@ -119,6 +140,10 @@ const STREAM_FLAGS_HAS_TRAILERS = 0x20;
 // Maximum number of allowed additional settings
 const MAX_ADDITIONAL_SETTINGS = 10;
 
+// Defaults of the `maxOutstandingPings` and `maxOutstandingSettings` options
+const MAX_OUTSTANDING_PINGS = 10;
+const MAX_OUTSTANDING_SETTINGS = 10;
+
 const SESSION_FLAGS_PENDING = 0x0;
 const SESSION_FLAGS_READY = 0x1;
 const SESSION_FLAGS_CLOSED = 0x2;
@@ -214,6 +239,7 @@ export class Http2Session extends EventEmitter {
       streams: new Map(),
       pendingStreams: new Set(),
       pendingAck: 0,
+      pendingPings: 0,
       writeQueueSize: 0,
       originSet: undefined,
     };
@@ -467,6 +493,9 @@ function assertValidPseudoHeader(header: string) {
 export class ClientHttp2Session extends Http2Session {
   #connectPromise: Promise<void>;
   #refed = true;
+  // Pending op promises that follow the session's `ref()` and `unref()`,
+  // besides the one polling the connection.
+  #ops = new Set<Promise<unknown>>();
 
   constructor(
     // deno-lint-ignore no-explicit-any
@@ -475,6 +504,15 @@ export class ClientHttp2Session extends Http2Session {
     options: Record<string, unknown>,
   ) {
     super(constants.NGHTTP2_SESSION_CLIENT, options, socket);
+    if (options.settings !== undefined) {
+      validateObject(options.settings, "options.settings");
+      validateSettings(options.settings);
+    }
+    this[kLocalSettings] = {
+      ...getDefaultSettings(),
+      ...normalizeSettings(options.settings),
+    };
+    this[kRemoteSettings] = {};
     this[kPendingRequestCalls] = null;
     this[kDenoClientRid] = undefined;
     this[kDenoConnRid] = undefined;
@@ -498,7 +536,11 @@ export class ClientHttp2Session extends Http2Session {
       debugHttp2(">>> before connect");
       const connRid_ = await connPromise;
       // console.log(">>>> awaited connRid", connRid_, url);
-      const [clientRid, connRid] = await op_http2_connect(connRid_, url);
+      const [clientRid, connRid] = await op_http2_connect(
+        connRid_,
+        url,
+        this[kLocalSettings],
+      );
       debugHttp2(">>> after connect", clientRid, connRid);
       this[kDenoClientRid] = clientRid;
       this[kDenoConnRid] = connRid;
@@ -518,14 +560,47 @@ export class ClientHttp2Session extends Http2Session {
       })();
       this[kState].flags |= SESSION_FLAGS_READY;
       this.emit("connect", this, {});
+
+      // The peer sends its SETTINGS before answering the first PING, and
+      // acknowledges ours before it too.
+      this[kState].pendingAck++;
+      this.#trackOp(op_http2_client_ping(connRid)).then(() => {
+        this[kState].pendingAck--;
+        if (this.destroyed) {
+          return;
+        }
+        this[kRemoteSettings] = {
+          ...this[kRemoteSettings],
+          maxConcurrentStreams: op_http2_client_remote_max_concurrent_streams(
+            connRid,
+          ),
+        };
+        this.emit("remoteSettings", this.remoteSettings);
+        this.emit("localSettings", this.localSettings);
+      }, () => {
+        this[kState].pendingAck--;
+      });
     })();
   }
 
+  #trackOp<T>(promise: Promise<T>): Promise<T> {
+    this.#ops.add(promise);
+    if (!this.#refed) {
+      core.unrefOpPromise(promise);
+    }
+    const untrack = () => this.#ops.delete(promise);
+    promise.then(untrack, untrack);
+    return promise;
+  }
+
   ref() {
     this.#refed = true;
     if (this[kPollConnPromise]) {
       core.refOpPromise(this[kPollConnPromise]);
     }
+    for (const promise of this.#ops) {
+      core.refOpPromise(promise);
+    }
   }
 
   unref() {
@@ -533,6 +608,134 @@ export class ClientHttp2Session extends Http2Session {
     if (this[kPollConnPromise]) {
       core.unrefOpPromise(this[kPollConnPromise]);
     }
+    for (const promise of this.#ops) {
+      core.unrefOpPromise(promise);
+    }
+  }
+
+  get localSettings(): Record<string, unknown> {
+    return { ...this[kLocalSettings] };
+  }
+
+  // h2 only exposes the peer's `maxConcurrentStreams`, so the other settings
+  // are left out instead of guessing their values.
+  get remoteSettings(): Record<string, unknown> {
+    return { ...this[kRemoteSettings] };
+  }
+
+  setLocalWindowSize(windowSize: number) {
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    validateInt32(windowSize, "windowSize", 0);
+
+    if (this.connecting) {
+      this.once("connect", () => this.setLocalWindowSize(windowSize));
+      return;
+    }
+    op_http2_client_set_local_window_size(this[kDenoConnRid], windowSize);
+  }
+
+  ping(
+    payload?: Buffer | TypedArray | DataView | PingCallback,
+    callback?: PingCallback,
+  ): boolean {
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    if (typeof payload === "function") {
+      callback = payload;
+      payload = undefined;
+    }
+    if (payload) {
+      validateBuffer(payload, "payload");
+      if (payload.byteLength !== 8) {
+        throw new ERR_HTTP2_PING_LENGTH();
+      }
+    }
+    validateFunction(callback, "callback");
+
+    const state = this[kState];
+    if (state.pendingPings >= MAX_OUTSTANDING_PINGS) {
+      return false;
+    }
+    state.pendingPings++;
+
+    // h2 sends its own opaque payload, the acknowledged payload that is
+    // reported is the one that was passed.
+    const ack = payload
+      ? Buffer.from(payload.buffer, payload.byteOffset, payload.byteLength)
+      : Buffer.alloc(8);
+    this.#connectPromise
+      .then(() => this.#trackOp(op_http2_client_ping(this[kDenoConnRid])))
+      .then((duration) => {
+        state.pendingPings--;
+        callback!(null, duration, ack);
+      }, (err) => {
+        state.pendingPings--;
+        callback!(this.destroyed ? new ERR_HTTP2_PING_CANCEL() : err, 0, ack);
+      });
+    return true;
+  }
+
+  /**
+   * Sends new local settings to the peer. h2 can only change
+   * `initialWindowSize` after the handshake, changing any other setting
+   * throws an `ERR_NOT_IMPLEMENTED` error.
+   */
+  settings(
+    settings: Record<string, unknown>,
+    callback?: (
+      err: Error | null,
+      settings?: Record<string, unknown>,
+      duration?: number,
+    ) => void,
+  ) {
+    if (this.destroyed) {
+      throw new ERR_HTTP2_INVALID_SESSION();
+    }
+    validateObject(settings, "settings");
+    validateSettings(settings);
+    if (callback !== undefined) {
+      validateFunction(callback, "callback");
+    }
+
+    settings = normalizeSettings(settings);
+    for (const name of ObjectKeys(settings)) {
+      // h2 only supports changing the initial window size after the
+      // connection was established.
+      if (
+        name !== "initialWindowSize" &&
+        ObjectPrototypeHasOwnProperty(this[kLocalSettings], name) &&
+        settings[name] !== this[kLocalSettings][name]
+      ) {
+        notImplemented(`Http2Session.settings({ ${name} })`);
+      }
+    }
+
+    const state = this[kState];
+    if (state.pendingAck >= MAX_OUTSTANDING_SETTINGS) {
+      throw new ERR_HTTP2_MAX_PENDING_SETTINGS_ACK();
+    }
+    state.pendingAck++;
+
+    this.#connectPromise
+      .then(() =>
+        this.#trackOp(op_http2_client_settings(this[kDenoConnRid], settings))
+      )
+      .then((duration) => {
+        state.pendingAck--;
+        this[kLocalSettings] = { ...this[kLocalSettings], ...settings };
+        this.emit("localSettings", this.localSettings);
+        callback?.(null, this.localSettings, duration);
+      }, (err) => {
+        state.pendingAck--;
+        if (callback) {
+          callback(err);
+        } else if (!this.destroyed) {
+          this.destroy(err);
+        }
+      });
   }
 
   request(
@@ -2155,21 +2358,183 @@ export const constants = {
 //   constants.HTTP2_HEADER_X_CONTENT_TYPE_OPTIONS,
 // ]);
 
+type PingCallback = (
+  err: Error | null,
+  duration?: number,
+  payload?: Buffer,
+) => void;
+
+const kMaxFrameSize = 2 ** 24 - 1;
+const kMaxInt = 2 ** 32 - 1;
+const kMaxWindowSize = 2 ** 31 - 1;
+
+// The identifier and the valid range of the numeric settings, in the order
+// they are packed in.
+const kNumericSettings = {
+  headerTableSize: [constants.NGHTTP2_SETTINGS_HEADER_TABLE_SIZE, 0, kMaxInt],
+  maxConcurrentStreams: [
+    constants.NGHTTP2_SETTINGS_MAX_CONCURRENT_STREAMS,
+    0,
+    kMaxInt,
+  ],
+  initialWindowSize: [
+    constants.NGHTTP2_SETTINGS_INITIAL_WINDOW_SIZE,
+    0,
+    kMaxWindowSize,
+  ],
+  maxFrameSize: [
+    constants.NGHTTP2_SETTINGS_MAX_FRAME_SIZE,
+    constants.DEFAULT_SETTINGS_MAX_FRAME_SIZE,
+    kMaxFrameSize,
+  ],
+  maxHeaderListSize: [
+    constants.NGHTTP2_SETTINGS_MAX_HEADER_LIST_SIZE,
+    0,
+    kMaxInt,
+  ],
+};
+
+const kBooleanSettings = {
+  enablePush: constants.NGHTTP2_SETTINGS_ENABLE_PUSH,
+  enableConnectProtocol: constants.NGHTTP2_SETTINGS_ENABLE_CONNECT_PROTOCOL,
+};
+
+function validateSettings(settings: Record<string, unknown>) {
+  for (const name of ObjectKeys(kNumericSettings)) {
+    const { 1: min, 2: max } = kNumericSettings[name];
+    const value = settings[name];
+    if (
+      value !== undefined &&
+      (typeof value !== "number" || value < min || value > max)
+    ) {
+      throw new ERR_HTTP2_INVALID_SETTING_VALUE(name, value, min, max);
+    }
+  }
+  const { maxHeaderSize } = settings;
+  if (
+    maxHeaderSize !== undefined &&
+    (typeof maxHeaderSize !== "number" || maxHeaderSize < 0 ||
+      maxHeaderSize > kMaxInt)
+  ) {
+    throw new ERR_HTTP2_INVALID_SETTING_VALUE(
+      "maxHeaderSize",
+      maxHeaderSize,
+      0,
+      kMaxInt,
+    );
+  }
+  for (const name of ObjectKeys(kBooleanSettings)) {
+    const value = settings[name];
+    if (value !== undefined && typeof value !== "boolean") {
+      throw new ERR_HTTP2_INVALID_SETTING_VALUE(name, value);
+    }
+  }
+}
+
+// `maxHeaderSize` is an alias of `maxHeaderListSize`.
+function normalizeSettings(
+  settings: Record<string, unknown> = {},
+): Record<string, unknown> {
+  settings = { ...settings };
+  if (settings.maxHeaderListSize === undefined) {
+    settings.maxHeaderListSize = settings.maxHeaderSize;
+  }
+  if (settings.maxHeaderListSize !== undefined) {
+    settings.maxHeaderSize = settings.maxHeaderListSize;
+  }
+  for (const name of ObjectKeys(settings)) {
+    if (settings[name] === undefined) {
+      delete settings[name];
+    }
+  }
+  return settings;
+}
+
 export function getDefaultSettings(): Record<string, unknown> {
-  notImplemented("http2.getDefaultSettings");
-  return {};
+  return {
+    headerTableSize: constants.DEFAULT_SETTINGS_HEADER_TABLE_SIZE,
+    enablePush: constants.DEFAULT_SETTINGS_ENABLE_PUSH === 1,
+    initialWindowSize: constants.DEFAULT_SETTINGS_INITIAL_WINDOW_SIZE,
+    maxFrameSize: constants.DEFAULT_SETTINGS_MAX_FRAME_SIZE,
+    maxConcurrentStreams: constants.DEFAULT_SETTINGS_MAX_CONCURRENT_STREAMS,
+    maxHeaderListSize: constants.DEFAULT_SETTINGS_MAX_HEADER_LIST_SIZE,
+    maxHeaderSize: constants.DEFAULT_SETTINGS_MAX_HEADER_LIST_SIZE,
+    enableConnectProtocol:
+      constants.DEFAULT_SETTINGS_ENABLE_CONNECT_PROTOCOL === 1,
+  };
 }
 
-export function getPackedSettings(_settings: Record<string, unknown>): Buffer {
-  notImplemented("http2.getPackedSettings");
-  return {};
+export function getPackedSettings(
+  settings: Record<string, unknown> = {},
+): Buffer {
+  validateObject(settings, "settings");
+  validateSettings(settings);
+  settings = normalizeSettings(settings);
+
+  const entries = [];
+  for (const name of ObjectKeys(kNumericSettings)) {
+    if (settings[name] !== undefined) {
+      entries.push([kNumericSettings[name][0], settings[name]]);
+    }
+  }
+  for (const name of ObjectKeys(kBooleanSettings)) {
+    if (settings[name] !== undefined) {
+      entries.push([kBooleanSettings[name], settings[name] ? 1 : 0]);
+    }
+  }
+  entries.sort((a, b) => a[0] - b[0]);
+
+  const buffer = Buffer.alloc(entries.length * 6);
+  for (let i = 0; i < entries.length; i++) {
+    buffer.writeUInt16BE(entries[i][0], i * 6);
+    buffer.writeUInt32BE(entries[i][1], i * 6 + 2);
+  }
+  return buffer;
 }
 
 export function getUnpackedSettings(
-  _buffer: Buffer | TypedArray,
+  buffer: Buffer | TypedArray,
+  options: { validate?: boolean } = {},
 ): Record<string, unknown> {
-  notImplemented("http2.getUnpackedSettings");
-  return {};
+  if (!isArrayBufferView(buffer)) {
+    throw new ERR_INVALID_ARG_TYPE("buf", ["Buffer", "TypedArray"], buffer);
+  }
+  if (buffer.byteLength % 6 !== 0) {
+    throw new ERR_HTTP2_INVALID_PACKED_SETTINGS_LENGTH();
+  }
+
+  const view = new DataView(
+    buffer.buffer,
+    buffer.byteOffset,
+    buffer.byteLength,
+  );
+  const settings: Record<string, unknown> = {};
+  for (let offset = 0; offset < buffer.byteLength; offset += 6) {
+    const id = view.getUint16(offset);
+    const value = view.getUint32(offset + 2);
+    const numeric = ObjectKeys(kNumericSettings).find((name) =>
+      kNumericSettings[name][0] === id
+    );
+    const boolean = ObjectKeys(kBooleanSettings).find((name) =>
+      kBooleanSettings[name] === id
+    );
+    if (numeric !== undefined) {
+      settings[numeric] = value;
+      if (numeric === "maxHeaderListSize") {
+        settings.maxHeaderSize = value;
+      }
+    } else if (boolean !== undefined) {
+      settings[boolean] = value !== 0;
+    } else {
+      settings.customSettings ??= {};
+      settings.customSettings[id] = value;
+    }
+  }
+
+  if (options?.validate) {
+    validateSettings(settings);
+  }
+  return settings;
 }
 
 export const sensitiveHeaders = Symbol("nodejs.http2.sensitiveHeaders");
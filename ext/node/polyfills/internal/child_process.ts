@@ -13,6 +13,7 @@ import {
   op_node_ipc_ref,
   op_node_ipc_unref,
   op_node_ipc_write,
+  op_node_ipc_write_with_handle,
 } from "ext:core/ops";
 import {
   ArrayIsArray,
@@ -37,6 +38,7 @@ import {
   AbortError,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
+  ERR_INVALID_HANDLE_TYPE,
   ERR_IPC_CHANNEL_CLOSED,
   ERR_UNKNOWN_SIGNAL,
 } from "ext:deno_node/internal/errors.ts";
@@ -56,7 +58,12 @@ import process from "node:process";
 import { StringPrototypeSlice } from "ext:deno_node/internal/primordials.mjs";
 import { StreamBase } from "ext:deno_node/internal_binding/stream_wrap.ts";
 import { Pipe, socketType } from "ext:deno_node/internal_binding/pipe_wrap.ts";
-import { Socket } from "node:net";
+import { Server, Socket } from "node:net";
+import { Listener, TcpConn } from "ext:deno_net/01_net.js";
+import {
+  constants as TCPConstants,
+  TCP,
+} from "ext:deno_node/internal_binding/tcp_wrap.ts";
 import {
   kExtraStdio,
   kInputOption,
@@ -1156,6 +1163,8 @@ const kNodeFlagsMap = new Map([
   ["--title", kLongArg],
   ["--tls-cipher-list", kLongArg],
   ["--tls-keylog", kLongArg],
+  ["--trace-event-categories", kLongArg],
+  ["--trace-event-file-pattern", kLongArg],
   ["--unhandled-rejections", kLongArg],
   ["--use-largepages", kLongArg],
   ["--v8-pool-size", kLongArg],
@@ -1273,7 +1282,9 @@ function toDenoArgs(args: string[]): [string[], string[], boolean] {
         // internals are always exposed in Deno.
       } else if (arg === "--permission") {
         // ignore --permission flag
-      } else if (arg === "--pending-deprecation") {
+      } else if (
+        arg === "--pending-deprecation" || arg === "--trace-events-enabled"
+      ) {
         nodeOptions.push(arg);
       } else if (StringPrototypeStartsWith(arg, "--experimental-")) {
         // `--experimental-*` args are ignored, because most experimental Node features
@@ -1307,6 +1318,12 @@ function toDenoArgs(args: string[]): [string[], string[], boolean] {
     } else if (flag === "-p" || flag === "--print") {
       denoArgs.push("eval", "-p", wrapScriptForEval(flagValue));
       useRunArgs = false;
+    } else if (
+      flag === "--trace-event-categories" ||
+      flag === "--trace-event-file-pattern"
+    ) {
+      // Trace events are configured through `NODE_OPTIONS` in Deno.
+      nodeOptions.push(`${flag}=${flagValue}`);
     } else if (isLongWithValue) {
       denoArgs.push(arg);
     } else {
@@ -1382,6 +1399,17 @@ type InternalMessage = {
   cmd: `NODE_${string}`;
 };
 
+type HandleMessage = {
+  cmd: "NODE_HANDLE";
+  type: "net.Native" | "net.Server" | "net.Socket";
+  msg: unknown;
+  handle: {
+    rid: number;
+    localAddr: Deno.NetAddr;
+    remoteAddr?: Deno.NetAddr;
+  } | null;
+};
+
 // deno-lint-ignore no-explicit-any
 function isInternal(msg: any): msg is InternalMessage {
   if (msg && typeof msg === "object") {
@@ -1419,10 +1447,11 @@ export function setupChannel(target: any, ipc: number) {
             // Channel closed.
             target.disconnect();
             return;
+          } else if (cmd === "HANDLE") {
+            nextTick(handleHandleMessage, msg);
+            continue;
           } else {
-            // TODO(nathanwhit): once we add support for sending
-            // handles, if we want to support deno-node IPC interop,
-            // we'll need to handle the NODE_HANDLE_* messages here.
+            nextTick(handleInternalMessage, msg);
             continue;
           }
         }
@@ -1439,16 +1468,69 @@ export function setupChannel(target: any, ipc: number) {
     }
   }
 
-  function handleMessage(msg) {
+  function handleMessage(msg, handle?) {
     if (!target.channel) {
       return;
     }
     if (target.listenerCount("message") !== 0) {
-      target.emit("message", msg);
+      target.emit("message", msg, handle);
       return;
     }
 
-    ArrayPrototypePush(target.channel[kPendingMessages], msg);
+    ArrayPrototypePush(target.channel[kPendingMessages], [msg, handle]);
+  }
+
+  // `NODE_HANDLE` messages carry a server or socket sent with
+  // `target.send(message, handle)`. The handle was already added to the
+  // resource table when the message was read, if it could be opened.
+  function handleHandleMessage(msg: HandleMessage) {
+    if (!msg.handle) {
+      if (isInternal(msg.msg)) {
+        handleInternalMessage(msg.msg);
+      } else {
+        handleMessage(msg.msg);
+      }
+      return;
+    }
+
+    const { rid, localAddr, remoteAddr } = msg.handle;
+    localAddr.transport = "tcp";
+    if (msg.type === "net.Native") {
+      // Listening sockets sent by `node:cluster`, along with its own
+      // messages.
+      const handle = new TCP(
+        TCPConstants.SERVER,
+        new Listener(rid, localAddr, "tcp"),
+      );
+      if (isInternal(msg.msg)) {
+        handleInternalMessage(msg.msg, handle);
+      } else {
+        handleMessage(msg.msg, handle);
+      }
+    } else if (msg.type === "net.Server") {
+      const handle = new TCP(
+        TCPConstants.SERVER,
+        new Listener(rid, localAddr, "tcp"),
+      );
+      const server = new Server();
+      server.listen(handle, () => handleMessage(msg.msg, server));
+    } else {
+      remoteAddr!.transport = "tcp";
+      const handle = new TCP(
+        TCPConstants.SOCKET,
+        new TcpConn(rid, remoteAddr, localAddr),
+      );
+      const socket = new Socket({ handle, readable: true, writable: true });
+      handleMessage(msg.msg, socket);
+    }
+  }
+
+  // used by `node:cluster` to talk between the primary and its workers
+  function handleInternalMessage(msg: InternalMessage, handle?: TCP) {
+    if (!target.channel) {
+      return;
+    }
+    target.emit("internalMessage", msg, handle);
   }
 
   target.on("newListener", () => {
@@ -1456,8 +1538,8 @@ export function setupChannel(target: any, ipc: number) {
       if (!target.channel || !target.listenerCount("message")) {
         return;
       }
-      for (const msg of target.channel[kPendingMessages]) {
-        target.emit("message", msg);
+      for (const { 0: msg, 1: handle } of target.channel[kPendingMessages]) {
+        target.emit("message", msg, handle);
       }
       target.channel[kPendingMessages] = [];
     });
@@ -1481,8 +1563,26 @@ export function setupChannel(target: any, ipc: number) {
       throw new TypeError("ERR_MISSING_ARGS", "message");
     }
 
+    let fd;
     if (handle !== undefined) {
-      notImplemented("ChildProcess.send with handle");
+      let type;
+      let tcp;
+      if (handle instanceof TCP) {
+        type = "net.Native";
+        tcp = handle;
+      } else if (handle instanceof Socket) {
+        type = "net.Socket";
+        tcp = handle._handle;
+      } else if (handle instanceof Server) {
+        type = "net.Server";
+        tcp = handle._handle;
+      }
+      // Only TCP handles can be sent for now.
+      if (type === undefined || !(tcp instanceof TCP)) {
+        throw new ERR_INVALID_HANDLE_TYPE();
+      }
+      fd = tcp.fd;
+      message = { cmd: "NODE_HANDLE", type, msg: message };
     }
 
     if (!target.connected) {
@@ -1500,9 +1600,16 @@ export function setupChannel(target: any, ipc: number) {
     // this acts as a backpressure mechanism.
     const queueOk = [true];
     control.refCounted();
-    op_node_ipc_write(ipc, message, queueOk)
+    const promise = handle === undefined
+      ? op_node_ipc_write(ipc, message, queueOk)
+      : op_node_ipc_write_with_handle(ipc, message, fd);
+    promise
       .then(() => {
         control.unrefCounted();
+        // The socket belongs to the other process now, unless asked otherwise.
+        if (handle instanceof Socket && !options.keepOpen) {
+          handle.destroy();
+        }
         if (callback) {
           nextTick(callback, null);
         }
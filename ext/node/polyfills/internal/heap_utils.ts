@@ -0,0 +1,60 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  op_v8_heap_snapshot_read,
+  op_v8_take_heap_snapshot,
+} from "ext:core/ops";
+import { Buffer } from "node:buffer";
+import { Readable } from "node:stream";
+
+const { ArrayPrototypePush } = primordials;
+
+/** A readable stream of the JSON of a heap snapshot. */
+export class HeapSnapshotStream extends Readable {
+  #read: () => Uint8Array | null;
+
+  constructor(read: () => Uint8Array | null) {
+    super({ autoDestroy: true });
+    this.#read = read;
+  }
+
+  _read() {
+    const chunk = this.#read();
+    this.push(
+      chunk === null
+        ? null
+        : Buffer.from(chunk.buffer, chunk.byteOffset, chunk.byteLength),
+    );
+  }
+}
+
+/** Takes a heap snapshot of this isolate and streams it. */
+export function getHeapSnapshotStream(): HeapSnapshotStream {
+  const snapshot = op_v8_take_heap_snapshot();
+  return new HeapSnapshotStream(() => op_v8_heap_snapshot_read(snapshot));
+}
+
+/**
+ * Takes a heap snapshot of this isolate as a list of chunks, so a worker can
+ * post it to its parent.
+ */
+export function takeHeapSnapshotChunks(): Uint8Array[] {
+  const snapshot = op_v8_take_heap_snapshot();
+  const chunks = [];
+  let chunk = op_v8_heap_snapshot_read(snapshot);
+  while (chunk !== null) {
+    ArrayPrototypePush(chunks, chunk);
+    chunk = op_v8_heap_snapshot_read(snapshot);
+  }
+  return chunks;
+}
+
+/** Streams a heap snapshot that was taken in another isolate. */
+export function heapSnapshotStreamFromChunks(
+  chunks: Uint8Array[],
+): HeapSnapshotStream {
+  let i = 0;
+  return new HeapSnapshotStream(() => i < chunks.length ? chunks[i++] : null);
+}
@@ -0,0 +1,167 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  op_inspector_connect_repl,
+  op_inspector_dispatch,
+} from "ext:core/ops";
+import { ERR_INSPECTOR_COMMAND } from "ext:deno_node/internal/errors.ts";
+
+const {
+  Error,
+  JSONParse,
+  JSONStringify,
+  MapPrototypeDelete,
+  MapPrototypeGet,
+  MapPrototypeSet,
+  ObjectDefineProperty,
+  PromisePrototypeThen,
+  PromiseWithResolvers,
+  ReflectDeleteProperty,
+  SafeMap,
+} = primordials;
+
+// The results of an evaluation are released after they were copied out of
+// the inspector.
+const kResultGroup = "node:repl";
+const kInternalKey = "__denoNodeReplInternal";
+
+interface RemoteObject {
+  type: string;
+  value?: unknown;
+  unserializableValue?: string;
+  objectId?: string;
+}
+
+export interface EvaluateResult {
+  value: unknown;
+  thrown: boolean;
+}
+
+/**
+ * Evaluates code with the same inspector `replMode` as `deno repl`, which
+ * supports top-level await and redeclaring `let` and `const` bindings.
+ */
+export class ReplSession {
+  #connection;
+  #nextId = 1;
+  #callbacks = new SafeMap();
+  // the remote results are copied into this object by the inspector
+  #internal = { __proto__: null };
+  #internalId: Promise<string> | undefined;
+
+  constructor() {
+    this.#connection = op_inspector_connect_repl((message: string) =>
+      this.#onMessage(message)
+    );
+  }
+
+  /**
+   * Evaluates `code` in the global scope, awaiting top-level `await`. With
+   * `throwOnSideEffect` the evaluation is aborted if it would have side
+   * effects, which is how completions preview expressions.
+   */
+  async evaluate(
+    code: string,
+    throwOnSideEffect = false,
+  ): Promise<EvaluateResult> {
+    const { result, exceptionDetails } = await this.#post("Runtime.evaluate", {
+      expression: code,
+      objectGroup: kResultGroup,
+      replMode: true,
+      throwOnSideEffect,
+    });
+    try {
+      if (exceptionDetails === undefined) {
+        return { value: await this.#fromRemote(result), thrown: false };
+      }
+      const value = exceptionDetails.exception === undefined
+        ? new Error(exceptionDetails.text)
+        : await this.#fromRemote(exceptionDetails.exception);
+      return { value, thrown: true };
+    } finally {
+      await this.#post("Runtime.releaseObjectGroup", {
+        objectGroup: kResultGroup,
+      });
+    }
+  }
+
+  /** The names of the `let`, `const` and `class` bindings of the REPL. */
+  async globalLexicalScopeNames(): Promise<string[]> {
+    const { names } = await this.#post("Runtime.globalLexicalScopeNames", {});
+    return names;
+  }
+
+  // Copies the value of a remote object into this isolate's heap, which
+  // is the same one that the inspector evaluated the code in.
+  async #fromRemote(remote: RemoteObject): Promise<unknown> {
+    if (
+      remote.objectId === undefined && remote.unserializableValue === undefined
+    ) {
+      return remote.value;
+    }
+
+    const key = `${this.#nextId}`;
+    await this.#post("Runtime.callFunctionOn", {
+      objectId: await this.#internalObjectId(),
+      functionDeclaration: "function (value, key) { this[key] = value; }",
+      arguments: [
+        remote.objectId !== undefined
+          ? { objectId: remote.objectId }
+          : { unserializableValue: remote.unserializableValue },
+        { value: key },
+      ],
+    });
+    const value = this.#internal[key];
+    ReflectDeleteProperty(this.#internal, key);
+    return value;
+  }
+
+  #internalObjectId(): Promise<string> {
+    if (this.#internalId === undefined) {
+      ObjectDefineProperty(globalThis, kInternalKey, {
+        __proto__: null,
+        value: this.#internal,
+        configurable: true,
+      });
+      this.#internalId = PromisePrototypeThen(
+        this.#post("Runtime.evaluate", {
+          expression: `globalThis.${kInternalKey}`,
+        }),
+        ({ result }) => {
+          ReflectDeleteProperty(globalThis, kInternalKey);
+          return result.objectId;
+        },
+      );
+    }
+    return this.#internalId;
+  }
+
+  #post(method: string, params: object) {
+    const id = this.#nextId++;
+    const { promise, resolve, reject } = PromiseWithResolvers();
+    MapPrototypeSet(this.#callbacks, id, { resolve, reject });
+    op_inspector_dispatch(
+      this.#connection,
+      JSONStringify({ id, method, params }),
+    );
+    // deno-lint-ignore no-explicit-any
+    return promise as Promise<any>;
+  }
+
+  #onMessage(message: string) {
+    const { id, result, error } = JSONParse(message);
+    if (id === undefined) {
+      return;
+    }
+
+    const callbacks = MapPrototypeGet(this.#callbacks, id);
+    MapPrototypeDelete(this.#callbacks, id);
+    if (error !== undefined) {
+      callbacks.reject(new ERR_INSPECTOR_COMMAND(error.code, error.message));
+    } else {
+      callbacks.resolve(result);
+    }
+  }
+}
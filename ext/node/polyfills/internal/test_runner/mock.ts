@@ -0,0 +1,438 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateBoolean,
+  validateFunction,
+  validateInteger,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
+import { MockTimers } from "ext:deno_node/internal/test_runner/mock_timers.ts";
+
+const {
+  ArrayPrototypePush,
+  ArrayPrototypeSlice,
+  Error,
+  MapPrototypeDelete,
+  MapPrototypeGet,
+  MapPrototypeSet,
+  ObjectDefineProperty,
+  ObjectGetOwnPropertyDescriptor,
+  ObjectGetPrototypeOf,
+  Proxy,
+  ReflectApply,
+  ReflectConstruct,
+  ReflectGet,
+  SafeMap,
+} = primordials;
+
+// deno-lint-ignore no-explicit-any
+type AnyFunction = (...args: any[]) => any;
+
+interface MockCall {
+  arguments: unknown[];
+  error: unknown;
+  result: unknown;
+  stack: Error;
+  target: unknown;
+  this: unknown;
+}
+
+// Marks that `method()` wasn't given an implementation, so the original
+// method is called.
+const kDefaultFunction = function () {};
+
+/** The `mock` property of the functions created by `MockTracker`. */
+export class MockFunctionContext {
+  #calls: MockCall[] = [];
+  #mocks = new SafeMap();
+  #implementation: AnyFunction;
+  #restore: (() => void) | undefined;
+  #times: number | undefined;
+
+  constructor(
+    implementation: AnyFunction,
+    restore: (() => void) | undefined,
+    times: number | undefined,
+  ) {
+    this.#implementation = implementation;
+    this.#restore = restore;
+    this.#times = times;
+  }
+
+  /** The calls made to the mock, in order. */
+  get calls(): MockCall[] {
+    return ArrayPrototypeSlice(this.#calls, 0);
+  }
+
+  callCount(): number {
+    return this.#calls.length;
+  }
+
+  mockImplementation(implementation: AnyFunction) {
+    validateFunction(implementation, "implementation");
+    this.#implementation = implementation;
+  }
+
+  mockImplementationOnce(implementation: AnyFunction, onCall?: number) {
+    validateFunction(implementation, "implementation");
+    const nextCall = this.#calls.length;
+    const call = onCall ?? nextCall;
+    validateInteger(call, "onCall", nextCall);
+    MapPrototypeSet(this.#mocks, call, implementation);
+  }
+
+  restore() {
+    if (this.#restore !== undefined) {
+      this.#restore();
+      this.#restore = undefined;
+    }
+  }
+
+  resetCalls() {
+    this.#calls = [];
+  }
+
+  trackCall(call: MockCall) {
+    ArrayPrototypePush(this.#calls, call);
+  }
+
+  nextImpl(): AnyFunction {
+    const nextCall = this.#calls.length;
+    const mock = MapPrototypeGet(this.#mocks, nextCall);
+    const impl = mock ?? this.#implementation;
+
+    if (nextCall + 1 === this.#times) {
+      this.restore();
+    }
+
+    MapPrototypeDelete(this.#mocks, nextCall);
+    return impl;
+  }
+}
+
+interface MockOptions {
+  times?: number;
+  getter?: boolean;
+  setter?: boolean;
+}
+
+function validateTimes(value: unknown, name: string) {
+  if (value === Infinity) {
+    return;
+  }
+
+  validateInteger(value, name, 1);
+}
+
+function findMethodOnPrototypeChain(
+  instance: object,
+  methodName: string | symbol,
+): PropertyDescriptor | undefined {
+  let host = instance;
+  let descriptor;
+
+  while (host !== null) {
+    descriptor = ObjectGetOwnPropertyDescriptor(host, methodName);
+
+    if (descriptor) {
+      break;
+    }
+
+    host = ObjectGetPrototypeOf(host);
+  }
+
+  return descriptor;
+}
+
+/** Creates and tracks mock functions, methods and timers. */
+export class MockTracker {
+  #mocks: MockFunctionContext[] = [];
+  #timers: MockTimers | undefined;
+
+  get timers(): MockTimers {
+    this.#timers ??= new MockTimers();
+    return this.#timers;
+  }
+
+  fn(
+    original: AnyFunction | MockOptions = function () {},
+    implementation: AnyFunction | MockOptions = original,
+    options: MockOptions = {},
+  ) {
+    if (original !== null && typeof original === "object") {
+      options = original;
+      original = function () {};
+      implementation = original;
+    } else if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = original;
+    }
+
+    validateFunction(original, "original");
+    validateFunction(implementation, "implementation");
+    validateObject(options, "options");
+    const { times = Infinity } = options;
+    validateTimes(times, "options.times");
+    const ctx = new MockFunctionContext(
+      implementation as AnyFunction,
+      undefined,
+      times,
+    );
+    return this.#setupMock(ctx, original as AnyFunction);
+  }
+
+  method(
+    // deno-lint-ignore no-explicit-any
+    objectOrFunction: any,
+    methodName: string | symbol,
+    implementation: AnyFunction | MockOptions = kDefaultFunction,
+    options: MockOptions = {},
+  ) {
+    if (
+      objectOrFunction === null ||
+      (typeof objectOrFunction !== "object" &&
+        typeof objectOrFunction !== "function")
+    ) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "object",
+        ["Object", "Function"],
+        objectOrFunction,
+      );
+    }
+
+    if (typeof methodName !== "string" && typeof methodName !== "symbol") {
+      throw new ERR_INVALID_ARG_TYPE(
+        "methodName",
+        ["string", "symbol"],
+        methodName,
+      );
+    }
+
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = kDefaultFunction;
+    }
+
+    validateFunction(implementation, "implementation");
+    validateObject(options, "options");
+
+    const {
+      getter = false,
+      setter = false,
+      times = Infinity,
+    } = options;
+
+    validateBoolean(getter, "options.getter");
+    validateBoolean(setter, "options.setter");
+    validateTimes(times, "options.times");
+
+    if (setter && getter) {
+      throw new ERR_INVALID_ARG_VALUE(
+        "options.setter",
+        setter,
+        "cannot be used with 'options.getter'",
+      );
+    }
+
+    const descriptor = findMethodOnPrototypeChain(
+      objectOrFunction,
+      methodName,
+    );
+
+    let original;
+
+    if (getter) {
+      original = descriptor?.get;
+    } else if (setter) {
+      original = descriptor?.set;
+    } else {
+      original = descriptor?.value;
+    }
+
+    if (typeof original !== "function") {
+      throw new ERR_INVALID_ARG_VALUE(
+        "methodName",
+        original,
+        "must be a method",
+      );
+    }
+
+    const restore = () => {
+      const { configurable, enumerable } = descriptor!;
+      ObjectDefineProperty(objectOrFunction, methodName, {
+        __proto__: null,
+        configurable,
+        enumerable,
+        ...(getter || setter
+          ? { get: descriptor!.get, set: descriptor!.set }
+          : { value: descriptor!.value, writable: descriptor!.writable }),
+      });
+    };
+    const impl = implementation === kDefaultFunction
+      ? original
+      : implementation as AnyFunction;
+    const ctx = new MockFunctionContext(impl, restore, times);
+    const mock = this.#setupMock(ctx, original);
+    const mockDescriptor: PropertyDescriptor = {
+      __proto__: null,
+      configurable: descriptor!.configurable,
+      enumerable: descriptor!.enumerable,
+    } as PropertyDescriptor;
+
+    if (getter) {
+      mockDescriptor.get = mock;
+      mockDescriptor.set = descriptor!.set;
+    } else if (setter) {
+      mockDescriptor.get = descriptor!.get;
+      mockDescriptor.set = mock;
+    } else {
+      mockDescriptor.writable = descriptor!.writable;
+      mockDescriptor.value = mock;
+    }
+
+    ObjectDefineProperty(objectOrFunction, methodName, mockDescriptor);
+
+    return mock;
+  }
+
+  getter(
+    // deno-lint-ignore no-explicit-any
+    object: any,
+    methodName: string | symbol,
+    implementation: AnyFunction | MockOptions = kDefaultFunction,
+    options: MockOptions = {},
+  ) {
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = kDefaultFunction;
+    } else {
+      validateObject(options, "options");
+    }
+
+    const { getter = true } = options;
+
+    if (getter === false) {
+      throw new ERR_INVALID_ARG_VALUE(
+        "options.getter",
+        getter,
+        "cannot be false",
+      );
+    }
+
+    return this.method(object, methodName, implementation, {
+      ...options,
+      getter,
+    });
+  }
+
+  setter(
+    // deno-lint-ignore no-explicit-any
+    object: any,
+    methodName: string | symbol,
+    implementation: AnyFunction | MockOptions = kDefaultFunction,
+    options: MockOptions = {},
+  ) {
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = kDefaultFunction;
+    } else {
+      validateObject(options, "options");
+    }
+
+    const { setter = true } = options;
+
+    if (setter === false) {
+      throw new ERR_INVALID_ARG_VALUE(
+        "options.setter",
+        setter,
+        "cannot be false",
+      );
+    }
+
+    return this.method(object, methodName, implementation, {
+      ...options,
+      setter,
+    });
+  }
+
+  /** Restores all the mocks and forgets about them. */
+  reset() {
+    this.restoreAll();
+    this.#timers?.reset();
+    this.#mocks = [];
+  }
+
+  restoreAll() {
+    for (let i = 0; i < this.#mocks.length; i++) {
+      this.#mocks[i].restore();
+    }
+  }
+
+  #setupMock(ctx: MockFunctionContext, fnToMatch: AnyFunction) {
+    const mock = new Proxy(fnToMatch, {
+      __proto__: null,
+      apply(_fn, thisArg, argList) {
+        const fn = ctx.nextImpl();
+        let result;
+        let error;
+
+        try {
+          result = ReflectApply(fn, thisArg, argList);
+        } catch (err) {
+          error = err;
+          throw err;
+        } finally {
+          ctx.trackCall({
+            arguments: argList,
+            error,
+            result,
+            stack: new Error(),
+            target: undefined,
+            this: thisArg,
+          });
+        }
+
+        return result;
+      },
+      construct(target, argList, newTarget) {
+        const realTarget = ctx.nextImpl();
+        let result;
+        let error;
+
+        try {
+          result = ReflectConstruct(realTarget, argList, newTarget);
+        } catch (err) {
+          error = err;
+          throw err;
+        } finally {
+          ctx.trackCall({
+            arguments: argList,
+            error,
+            result,
+            stack: new Error(),
+            target,
+            this: result,
+          });
+        }
+
+        return result;
+      },
+      get(target, property, receiver) {
+        if (property === "mock") {
+          return ctx;
+        }
+
+        return ReflectGet(target, property, receiver);
+      },
+    } as ProxyHandler<AnyFunction>);
+
+    ArrayPrototypePush(this.#mocks, ctx);
+    return mock;
+  }
+}
@@ -0,0 +1,381 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import {
+  ERR_INVALID_ARG_VALUE,
+  ERR_INVALID_STATE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateArray,
+  validateFunction,
+  validateNumber,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
+import timers from "node:timers";
+
+const {
+  ArrayPrototypeIncludes,
+  ArrayPrototypePush,
+  ArrayPrototypeSlice,
+  ArrayPrototypeSplice,
+  Date,
+  DatePrototypeGetTime,
+  DatePrototypeToString,
+  ObjectDefineProperty,
+  ObjectGetOwnPropertyDescriptor,
+  ObjectPrototypeIsPrototypeOf,
+  ObjectSetPrototypeOf,
+  ReflectApply,
+  ReflectConstruct,
+  SymbolDispose,
+  SymbolToPrimitive,
+} = primordials;
+
+const kSupportedApis = ["setTimeout", "setInterval", "setImmediate", "Date"];
+const TIMEOUT_MAX = 2 ** 31 - 1;
+
+type TimerType = "timeout" | "interval" | "immediate";
+
+interface Timer {
+  id: number;
+  type: TimerType;
+  callback: (...args: unknown[]) => void;
+  args: unknown[];
+  delay: number;
+  runAt: number;
+}
+
+/** The handle returned by the mocked `setTimeout()` and `setInterval()`. */
+class MockTimeout {
+  #timers: MockTimers;
+  #timer: Timer;
+  #ref = true;
+
+  constructor(timers: MockTimers, timer: Timer) {
+    this.#timers = timers;
+    this.#timer = timer;
+  }
+
+  ref() {
+    this.#ref = true;
+    return this;
+  }
+
+  unref() {
+    this.#ref = false;
+    return this;
+  }
+
+  hasRef() {
+    return this.#ref;
+  }
+
+  refresh() {
+    this.#timers.refresh(this.#timer);
+    return this;
+  }
+
+  [SymbolToPrimitive]() {
+    return this.#timer.id;
+  }
+}
+
+function getTimerId(timer: unknown): number | undefined {
+  if (ObjectPrototypeIsPrototypeOf(MockTimeout.prototype, timer)) {
+    return (timer as MockTimeout)[SymbolToPrimitive]();
+  }
+  if (typeof timer === "number") {
+    return timer;
+  }
+  return undefined;
+}
+
+/**
+ * Fake timers for `t.mock.timers`, time only moves forward with `tick()`,
+ * `runAll()` and `setTime()`.
+ */
+export class MockTimers {
+  #enabled = false;
+  #now = 0;
+  #nextId = 1;
+  #timers: Timer[] = [];
+  #apis: string[] = [];
+  // the replaced properties and their original descriptors
+  #replaced: [object, string, PropertyDescriptor | undefined][] = [];
+
+  enable(options: { apis?: string[]; now?: number | Date } = {}) {
+    if (this.#enabled) {
+      throw new ERR_INVALID_STATE("MockTimers is already enabled!");
+    }
+
+    validateObject(options, "options");
+    const { apis = kSupportedApis } = options;
+    let { now = 0 } = options;
+    validateArray(apis, "options.apis");
+    for (let i = 0; i < apis.length; i++) {
+      if (!ArrayPrototypeIncludes(kSupportedApis, apis[i])) {
+        throw new ERR_INVALID_ARG_VALUE(
+          "options.apis",
+          apis[i],
+          `option ${apis[i]} is not supported`,
+        );
+      }
+    }
+    if (ObjectPrototypeIsPrototypeOf(Date.prototype, now)) {
+      now = DatePrototypeGetTime(now as Date);
+    }
+    validateNumber(now, "options.now", 0);
+
+    this.#now = now as number;
+    this.#apis = ArrayPrototypeSlice(apis, 0);
+    this.#enabled = true;
+    this.#patch();
+  }
+
+  tick(milliseconds = 1) {
+    this.#assertEnabled();
+    validateNumber(milliseconds, "milliseconds", 0);
+
+    const target = this.#now + milliseconds;
+    let timer = this.#nextTimer(target);
+
+    while (timer !== undefined) {
+      this.#now = timer.runAt;
+
+      if (timer.type === "interval") {
+        timer.runAt += timer.delay;
+      } else {
+        this.#remove(timer.id);
+      }
+
+      ReflectApply(timer.callback, undefined, timer.args);
+      timer = this.#nextTimer(target);
+    }
+
+    this.#now = target;
+  }
+
+  runAll() {
+    this.#assertEnabled();
+
+    let last = this.#now;
+    for (let i = 0; i < this.#timers.length; i++) {
+      if (this.#timers[i].runAt > last) {
+        last = this.#timers[i].runAt;
+      }
+    }
+
+    this.tick(last - this.#now);
+  }
+
+  setTime(milliseconds: number) {
+    this.#assertEnabled();
+    validateNumber(milliseconds, "milliseconds", 0);
+    this.#now = milliseconds;
+  }
+
+  reset() {
+    if (!this.#enabled) {
+      return;
+    }
+
+    this.#enabled = false;
+    this.#restore();
+    this.#timers = [];
+    this.#now = 0;
+  }
+
+  [SymbolDispose]() {
+    this.reset();
+  }
+
+  /** Reschedules the timer with its delay from now. */
+  refresh(timer: Timer) {
+    timer.runAt = this.#now + timer.delay;
+    if (timer.type === "timeout" && !this.#has(timer.id)) {
+      ArrayPrototypePush(this.#timers, timer);
+    }
+  }
+
+  #assertEnabled() {
+    if (!this.#enabled) {
+      throw new ERR_INVALID_STATE(
+        "You should enable MockTimers first by calling the .enable function",
+      );
+    }
+  }
+
+  // Returns the timer that is due next at or before `target`, the earliest
+  // created one first.
+  #nextTimer(target: number): Timer | undefined {
+    let next;
+    for (let i = 0; i < this.#timers.length; i++) {
+      const timer = this.#timers[i];
+      if (
+        timer.runAt <= target &&
+        (next === undefined || timer.runAt < next.runAt ||
+          (timer.runAt === next.runAt && timer.id < next.id))
+      ) {
+        next = timer;
+      }
+    }
+    return next;
+  }
+
+  #has(id: number) {
+    for (let i = 0; i < this.#timers.length; i++) {
+      if (this.#timers[i].id === id) {
+        return true;
+      }
+    }
+    return false;
+  }
+
+  #remove(id: number | undefined) {
+    for (let i = 0; i < this.#timers.length; i++) {
+      if (this.#timers[i].id === id) {
+        ArrayPrototypeSplice(this.#timers, i, 1);
+        return;
+      }
+    }
+  }
+
+  #schedule(
+    type: TimerType,
+    callback: (...args: unknown[]) => void,
+    delay: number | undefined,
+    args: unknown[],
+  ): Timer {
+    validateFunction(callback, "callback");
+
+    if (type === "immediate") {
+      delay = 0;
+    } else {
+      delay = +(delay ?? 1);
+      if (!(delay >= 1 && delay <= TIMEOUT_MAX)) {
+        delay = 1;
+      }
+    }
+
+    const timer = {
+      id: this.#nextId++,
+      type,
+      callback,
+      args,
+      delay,
+      runAt: this.#now + delay,
+    };
+    ArrayPrototypePush(this.#timers, timer);
+    return timer;
+  }
+
+  #replace(target: object, key: string, value: unknown) {
+    ArrayPrototypePush(this.#replaced, [
+      target,
+      key,
+      ObjectGetOwnPropertyDescriptor(target, key),
+    ]);
+    ObjectDefineProperty(target, key, {
+      __proto__: null,
+      value,
+      writable: true,
+      configurable: true,
+      enumerable: true,
+    });
+  }
+
+  #restore() {
+    for (let i = this.#replaced.length - 1; i >= 0; i--) {
+      const { 0: target, 1: key, 2: descriptor } = this.#replaced[i];
+      if (descriptor === undefined) {
+        delete target[key];
+      } else {
+        ObjectDefineProperty(target, key, descriptor);
+      }
+    }
+    this.#replaced = [];
+  }
+
+  #patch() {
+    const clear = (timer: unknown) => this.#remove(getTimerId(timer));
+    const targets = [globalThis, timers];
+
+    for (let i = 0; i < targets.length; i++) {
+      const target = targets[i];
+
+      if (ArrayPrototypeIncludes(this.#apis, "setTimeout")) {
+        this.#replace(
+          target,
+          "setTimeout",
+          (callback, delay, ...args) =>
+            new MockTimeout(
+              this,
+              this.#schedule("timeout", callback, delay, args),
+            ),
+        );
+        this.#replace(target, "clearTimeout", clear);
+      }
+
+      if (ArrayPrototypeIncludes(this.#apis, "setInterval")) {
+        this.#replace(
+          target,
+          "setInterval",
+          (callback, delay, ...args) =>
+            new MockTimeout(
+              this,
+              this.#schedule("interval", callback, delay, args),
+            ),
+        );
+        this.#replace(target, "clearInterval", clear);
+      }
+
+      if (ArrayPrototypeIncludes(this.#apis, "setImmediate")) {
+        this.#replace(
+          target,
+          "setImmediate",
+          (callback, ...args) =>
+            new MockTimeout(
+              this,
+              this.#schedule("immediate", callback, 0, args),
+            ),
+        );
+        this.#replace(target, "clearImmediate", clear);
+      }
+    }
+
+    if (ArrayPrototypeIncludes(this.#apis, "Date")) {
+      this.#replace(globalThis, "Date", this.#createDate());
+    }
+  }
+
+  #createDate() {
+    const getNow = () => this.#now;
+
+    function MockDate(...args: unknown[]) {
+      if (new.target === undefined) {
+        return DatePrototypeToString(new Date(getNow()));
+      }
+      if (args.length === 0) {
+        return ReflectConstruct(Date, [getNow()], new.target);
+      }
+      return ReflectConstruct(Date, args, new.target);
+    }
+
+    ObjectSetPrototypeOf(MockDate, Date);
+    ObjectDefineProperty(MockDate, "prototype", {
+      __proto__: null,
+      value: Date.prototype,
+      writable: false,
+    });
+    ObjectDefineProperty(MockDate, "now", {
+      __proto__: null,
+      value: function now() {
+        return getNow();
+      },
+      writable: true,
+      configurable: true,
+    });
+    return MockDate;
+  }
+}
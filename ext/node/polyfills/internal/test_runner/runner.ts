@@ -0,0 +1,152 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import { op_node_child_permission_args } from "ext:core/ops";
+import { spawn } from "node:child_process";
+import process from "node:process";
+import { createInterface } from "node:readline";
+import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  validateAbortSignal,
+  validateArray,
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
+import { kEmptyObject } from "ext:deno_node/internal/util.mjs";
+import {
+  kEmitMessage,
+  TestsStream,
+} from "ext:deno_node/internal/test_runner/tests_stream.ts";
+
+const {
+  ArrayIsArray,
+  ArrayPrototypeJoin,
+  ArrayPrototypePush,
+  ArrayPrototypePushApply,
+  Error,
+  JSONParse,
+  ObjectPrototypeIsPrototypeOf,
+  RegExpPrototype,
+} = primordials;
+
+interface RunOptions {
+  files?: string[];
+  cwd?: string;
+  concurrency?: number | boolean;
+  signal?: AbortSignal;
+  testNamePatterns?: string | RegExp | (string | RegExp)[];
+}
+
+// Joins the patterns into a regex for `deno test --filter`, a test runs if
+// its name matches any of them.
+function toFilter(patterns: RunOptions["testNamePatterns"]) {
+  if (patterns === undefined) {
+    return undefined;
+  }
+
+  const list = ArrayIsArray(patterns) ? patterns : [patterns];
+  const sources: string[] = [];
+  for (let i = 0; i < list.length; i++) {
+    const pattern = list[i];
+    if (ObjectPrototypeIsPrototypeOf(RegExpPrototype, pattern)) {
+      ArrayPrototypePush(sources, (pattern as RegExp).source);
+    } else if (typeof pattern === "string") {
+      ArrayPrototypePush(sources, pattern);
+    } else {
+      throw new ERR_INVALID_ARG_TYPE(
+        "options.testNamePatterns",
+        ["string", "RegExp"],
+        pattern,
+      );
+    }
+  }
+
+  return `/${ArrayPrototypeJoin(sources, "|")}/`;
+}
+
+// deno-lint-ignore no-explicit-any
+function toTestFailure(error: any) {
+  const cause = new Error(error.message);
+  const failure = new Error(error.message, { cause });
+  // deno-lint-ignore no-explicit-any
+  (failure as any).code = "ERR_TEST_FAILURE";
+  // deno-lint-ignore no-explicit-any
+  (failure as any).failureType = error.failureType;
+  return failure;
+}
+
+/**
+ * Runs the test files in a `deno test` subprocess, and returns the stream of
+ * the events that its reporter prints.
+ */
+export function run(options: RunOptions = kEmptyObject) {
+  validateObject(options, "options");
+
+  const {
+    files,
+    cwd = process.cwd(),
+    concurrency,
+    signal,
+    testNamePatterns,
+  } = options;
+
+  if (files !== undefined) {
+    validateArray(files, "options.files");
+  }
+  validateString(cwd, "options.cwd");
+  if (signal !== undefined) {
+    validateAbortSignal(signal, "options.signal");
+  }
+
+  // the tests get the permissions of this process
+  const args = ["test"];
+  ArrayPrototypePushApply(args, op_node_child_permission_args());
+  ArrayPrototypePush(args, "--no-check", "--reporter=node-test");
+  const env = { ...process.env };
+  if (concurrency === true) {
+    ArrayPrototypePush(args, "--parallel");
+  } else if (typeof concurrency === "number" && concurrency > 1) {
+    ArrayPrototypePush(args, "--parallel");
+    env.DENO_JOBS = `${concurrency}`;
+  }
+  const filter = toFilter(testNamePatterns);
+  if (filter !== undefined) {
+    ArrayPrototypePush(args, `--filter=${filter}`);
+  }
+  if (files !== undefined) {
+    for (let i = 0; i < files.length; i++) {
+      validateString(files[i], `options.files[${i}]`);
+      ArrayPrototypePush(args, files[i]);
+    }
+  }
+
+  const stream = new TestsStream();
+  const child = spawn(Deno.execPath(), args, {
+    cwd,
+    env,
+    signal,
+    stdio: ["ignore", "pipe", "inherit"],
+  });
+  // an aborted run ends the stream like a finished one
+  child.on("error", () => {});
+
+  const lines = createInterface({ input: child.stdout! });
+  lines.on("line", (line: string) => {
+    let event;
+    try {
+      event = JSONParse(line);
+    } catch {
+      stream[kEmitMessage]("test:stdout", { message: `${line}\n` });
+      return;
+    }
+
+    if (event.type === "test:fail") {
+      event.data.details.error = toTestFailure(event.data.details.error);
+    }
+    stream[kEmitMessage](event.type, event.data);
+  });
+  lines.on("close", () => stream.end());
+
+  return stream;
+}
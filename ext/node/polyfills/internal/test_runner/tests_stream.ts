@@ -0,0 +1,62 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
+
+import { primordials } from "ext:core/mod.js";
+import { Readable } from "node:stream";
+
+const {
+  ArrayPrototypePush,
+  ArrayPrototypeShift,
+  NumberMAX_SAFE_INTEGER,
+  Symbol,
+} = primordials;
+
+export const kEmitMessage = Symbol("kEmitMessage");
+
+/**
+ * The stream of test events returned by `run()`. Every event is emitted with
+ * its type, e.g. `test:pass`, and read as a `{ type, data }` object.
+ */
+export class TestsStream extends Readable {
+  #buffer: unknown[] = [];
+  #canPush = true;
+
+  constructor() {
+    super({
+      __proto__: null,
+      objectMode: true,
+      highWaterMark: NumberMAX_SAFE_INTEGER,
+    });
+  }
+
+  _read() {
+    this.#canPush = true;
+
+    while (this.#buffer.length > 0) {
+      const obj = ArrayPrototypeShift(this.#buffer);
+
+      if (!this.#tryPush(obj)) {
+        return;
+      }
+    }
+  }
+
+  [kEmitMessage](type: string, data: unknown) {
+    this.emit(type, data);
+    this.#tryPush({ __proto__: null, type, data });
+  }
+
+  end() {
+    this.#tryPush(null);
+  }
+
+  #tryPush(message: unknown) {
+    if (this.#canPush) {
+      this.#canPush = this.push(message);
+    } else {
+      ArrayPrototypePush(this.#buffer, message);
+    }
+
+    return this.#canPush;
+  }
+}
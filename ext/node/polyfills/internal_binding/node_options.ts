@@ -5,7 +5,9 @@ const {
   SafeMap,
   ArrayPrototypeForEach,
   SafeRegExp,
+  StringPrototypeSlice,
   StringPrototypeSplit,
+  StringPrototypeStartsWith,
 } = primordials;
 
 // This module ports:
@@ -17,7 +19,7 @@ const {
  * This function is expensive to execute. `getOptionValue` in `internal/options.ts`
  * should be used instead to get a specific option. */
 export function getOptions() {
-  const options = new SafeMap([
+  const options = new SafeMap<string, { value: boolean | string }>([
     ["--warnings", { value: true }],
     ["--pending-deprecation", { value: false }],
   ]);
@@ -36,6 +38,11 @@ export function getOptions() {
         break;
       // TODO(kt3k): Handle other options.
       default:
+        if (StringPrototypeStartsWith(arg, "--heapsnapshot-signal=")) {
+          options.set("--heapsnapshot-signal", {
+            value: StringPrototypeSlice(arg, 22),
+          });
+        }
         break;
     }
   });
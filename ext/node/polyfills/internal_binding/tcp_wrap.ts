@@ -27,10 +27,15 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
-import { op_net_connect_tcp } from "ext:core/ops";
-import { TcpConn } from "ext:deno_net/01_net.js";
+import {
+  op_net_connect_tcp,
+  op_node_tcp_fd,
+  op_node_tcp_listener_open,
+  op_node_tcp_open,
+} from "ext:core/ops";
+import { Listener, TcpConn } from "ext:deno_net/01_net.js";
 import { core } from "ext:core/mod.js";
-const { internalFdSymbol } = core;
+const { internalFdSymbol, internalRidSymbol } = core;
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { unreachable } from "ext:deno_node/_util/asserts.ts";
 import { ConnectionWrap } from "ext:deno_node/internal_binding/connection_wrap.ts";
@@ -100,6 +105,7 @@ export class TCP extends ConnectionWrap {
 
   #backlog?: number;
   #listener!: Deno.Listener;
+  #listenerFd?: number;
   #connections = 0;
 
   #closed = false;
@@ -110,9 +116,10 @@ export class TCP extends ConnectionWrap {
   /**
    * Creates a new TCP class instance.
    * @param type The socket type.
-   * @param conn Optional connection object to wrap.
+   * @param conn Optional connection object to wrap, or listener object for
+   * servers.
    */
-  constructor(type: number, conn?: Deno.Conn) {
+  constructor(type: number, conn?: Deno.Conn | Deno.Listener) {
     let provider: providerType;
 
     switch (type) {
@@ -131,7 +138,14 @@ export class TCP extends ConnectionWrap {
       }
     }
 
-    super(provider, conn);
+    super(
+      provider,
+      provider === providerType.TCPWRAP ? conn as Deno.Conn : undefined,
+    );
+
+    if (conn && provider === providerType.TCPSERVERWRAP) {
+      this.#setListener(conn as Deno.Listener);
+    }
 
     // TODO(cmorten): the handling of new connections and construction feels
     // a little off. Suspect duplicating in some fashion.
@@ -147,28 +161,63 @@ export class TCP extends ConnectionWrap {
     }
   }
 
+  /** The listener of a server handle, e.g. to serve HTTP on it. */
+  get listener(): Deno.Listener | undefined {
+    return this.#listener;
+  }
+
   get fd() {
-    return this[kStreamBaseField]?.[internalFdSymbol];
+    if (this.#listener) {
+      return this.#listenerFd;
+    }
+
+    const conn = this[kStreamBaseField];
+    if (!conn) {
+      return undefined;
+    }
+
+    return conn[internalFdSymbol] ?? op_node_tcp_fd(conn[internalRidSymbol]);
   }
 
   /**
-   * Opens a file descriptor.
+   * Opens a file descriptor. The handle uses a duplicate of it, so `fd` stays
+   * open.
    * @param fd The file descriptor to open.
    * @return An error status code.
    */
-  open(_fd: number): number {
-    // REF: https://github.com/denoland/deno/issues/6529
-    notImplemented("TCP.prototype.open");
+  open(fd: number): number {
+    try {
+      if (this.provider === providerType.TCPSERVERWRAP) {
+        const { 0: rid, 1: addr } = op_node_tcp_listener_open(fd);
+        addr.transport = "tcp";
+        this.#setListener(new Listener(rid, addr, "tcp"));
+      } else {
+        const { 0: rid, 1: localAddr, 2: remoteAddr } = op_node_tcp_open(fd);
+        localAddr.transport = "tcp";
+        remoteAddr.transport = "tcp";
+        this.#address = localAddr.hostname;
+        this.#port = localAddr.port;
+        this.#remoteAddress = remoteAddr.hostname;
+        this.#remotePort = remoteAddr.port;
+        this.#remoteFamily = isIP(remoteAddr.hostname);
+        this[kStreamBaseField] = new TcpConn(rid, remoteAddr, localAddr, fd);
+      }
+    } catch (e) {
+      return codeMap.get(e.code ?? "UNKNOWN") ?? codeMap.get("UNKNOWN")!;
+    }
+
+    return 0;
   }
 
   /**
    * Bind to an IPv4 address.
    * @param address The hostname to bind to.
    * @param port The port to bind to
+   * @param flags The `UV_TCP_*` flags of the socket.
    * @return An error status code.
    */
-  bind(address: string, port: number): number {
-    return this.#bind(address, port, 0);
+  bind(address: string, port: number, flags = 0): number {
+    return this.#bind(address, port, flags);
   }
 
   /**
@@ -211,27 +260,28 @@ export class TCP extends ConnectionWrap {
   listen(backlog: number): number {
     this.#backlog = ceilPowOf2(backlog + 1);
 
-    const listenOptions = {
-      hostname: this.#address!,
-      port: this.#port!,
-      transport: "tcp" as const,
-    };
+    // Handles opened from a file descriptor or received over IPC are
+    // listening already.
+    if (!this.#listener) {
+      const listenOptions = {
+        hostname: this.#address!,
+        port: this.#port!,
+        transport: "tcp" as const,
+      };
 
-    let listener;
+      let listener;
 
-    try {
-      listener = Deno.listen(listenOptions);
-    } catch (e) {
-      if (e instanceof Deno.errors.NotCapable) {
-        throw e;
+      try {
+        listener = Deno.listen(listenOptions);
+      } catch (e) {
+        if (e instanceof Deno.errors.NotCapable) {
+          throw e;
+        }
+        return codeMap.get(e.code ?? "UNKNOWN") ?? codeMap.get("UNKNOWN")!;
       }
-      return codeMap.get(e.code ?? "UNKNOWN") ?? codeMap.get("UNKNOWN")!;
-    }
 
-    const address = listener.addr as Deno.NetAddr;
-    this.#address = address.hostname;
-    this.#port = address.port;
-    this.#listener = listener;
+      this.#setListener(listener);
+    }
 
     // TODO(kt3k): Delays the accept() call 2 ticks. Deno.Listener can't be closed
     // synchronously when accept() is called. By delaying the accept() call,
@@ -407,6 +457,17 @@ export class TCP extends ConnectionWrap {
     return 0;
   }
 
+  #setListener(listener: Deno.Listener) {
+    const address = listener.addr as Deno.NetAddr;
+    this.#address = address.hostname;
+    this.#port = address.port;
+    this.#listener = listener;
+    // The file descriptor can't be looked up anymore once we start accepting
+    // connections, so get it now in case the server is sent to a child
+    // process.
+    this.#listenerFd = op_node_tcp_fd(listener[internalRidSymbol]);
+  }
+
   /** Handle backoff delays following an unsuccessful accept. */
   async #acceptBackoff() {
     // Backoff after transient errors to allow time for the system to
@@ -23,10 +23,10 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import { BlockList, SocketAddress } from "ext:deno_node/internal/blocklist.mjs";
 
 import { EventEmitter } from "node:events";
+import cluster from "node:cluster";
 import {
   isIP,
   isIPv4,
@@ -1242,7 +1242,17 @@ export function Socket(options) {
     this._handle = options.handle;
     this[asyncIdSymbol] = _getNewAsyncId(this._handle);
   } else if (options.fd !== undefined) {
-    notImplemented("net.Socket.prototype.constructor with fd option");
+    const { fd } = options;
+    validateInt32(fd, "fd", 0);
+
+    // Only TCP sockets are supported, `guessHandleType()` works on resource
+    // ids rather than file descriptors.
+    this._handle = new TCP(TCPConstants.SOCKET);
+    const err = this._handle.open(fd);
+    if (err) {
+      throw errnoException(err, "open");
+    }
+    this[asyncIdSymbol] = _getNewAsyncId(this._handle);
   }
 
   const onread = options.onread;
@@ -1949,16 +1959,11 @@ function _listenInCluster(
 ) {
   exclusive = !!exclusive;
 
-  // TODO(cmorten): here we deviate somewhat from the Node implementation which
-  // makes use of the https://nodejs.org/api/cluster.html module to run servers
-  // across a "cluster" of Node processes to take advantage of multi-core
-  // systems.
-  //
-  // Though Deno has has a Worker capability from which we could simulate this,
-  // for now we assert that we are _always_ on the primary process.
-  const isPrimary = true;
+  // Only TCP sockets can be shared with the workers of a cluster, so pipes
+  // and file descriptors are always listened on exclusively.
+  const isTCP = addressType === 4 || addressType === 6;
 
-  if (isPrimary || exclusive) {
+  if (cluster.isPrimary || exclusive || !isTCP) {
     // Will create a new handle
     // _listen2 sets up the listened handle, it is still named like this
     // to avoid breaking code that wraps this method
@@ -1966,6 +1971,29 @@ function _listenInCluster(
 
     return;
   }
+
+  const serverQuery = {
+    address,
+    port,
+    addressType,
+    fd,
+    flags,
+  };
+
+  // Get the primary's server handle, and listen on it
+  cluster._getServer(server, serverQuery, listenOnPrimaryHandle);
+
+  function listenOnPrimaryHandle(err: number, handle?: TCP) {
+    if (err) {
+      const ex = uvExceptionWithHostPort(err, "bind", address, port);
+
+      return server.emit("error", ex);
+    }
+
+    // The handle is listening already, `_listen2` starts accepting on it.
+    server._handle = handle!;
+    server._listen2(address, port, addressType, backlog, fd, flags);
+  }
 }
 
 function _lookupAndListen(
@@ -2088,7 +2116,7 @@ export function _createServerHandle(
     } else if (addressType === 6) {
       err = (handle as TCP).bind6(address, port ?? 0, flags ?? 0);
     } else {
-      err = (handle as TCP).bind(address, port ?? 0);
+      err = (handle as TCP).bind(address, port ?? 0, flags ?? 0);
     }
   }
 
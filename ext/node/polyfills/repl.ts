@@ -1,18 +1,567 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 // Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
+// TODO(petamoriken): enable prefer-primordials for node polyfills
+// deno-lint-ignore-file prefer-primordials
+
 import { primordials } from "ext:core/mod.js";
-import { notImplemented } from "ext:deno_node/_utils.ts";
-const { Symbol } = primordials;
+import { readFile, readFileSync, writeFile, writeFileSync } from "node:fs";
+import process from "node:process";
+import { Interface } from "node:readline";
+import { inspect } from "ext:deno_node/internal/util/inspect.mjs";
+import { ReplSession } from "ext:deno_node/internal/repl/session.ts";
+import { ERR_INVALID_REPL_EVAL_CONFIG } from "ext:deno_node/internal/errors.ts";
+import {
+  validateFunction,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
+const { ObjectSetPrototypeOf, SafeSet, Symbol } = primordials;
 
 export const REPL_MODE_SLOPPY = Symbol("repl-sloppy");
 export const REPL_MODE_STRICT = Symbol("repl-strict");
 
-export class REPLServer {
-  constructor() {
-    notImplemented("REPLServer.prototype.constructor");
+const kBufferedCommand = Symbol("kBufferedCommand");
+const kInitialPrompt = Symbol("kInitialPrompt");
+const kPendingLines = Symbol("kPendingLines");
+const kEvaluating = Symbol("kEvaluating");
+const kSawSIGINT = Symbol("kSawSIGINT");
+
+// V8 reports these for input that can still become valid with more lines,
+// those are buffered instead of being reported as errors.
+const kRecoverableMessages = [
+  "Unexpected end of input",
+  "missing ) after argument list",
+  "Unterminated template literal",
+];
+
+const kKeywords = [
+  "async",
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "import",
+  "in",
+  "instanceof",
+  "let",
+  "new",
+  "null",
+  "return",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+];
+
+// All REPL servers evaluate through one inspector session, which is only
+// connected once something is evaluated.
+let session: ReplSession | undefined;
+function getSession(): ReplSession {
+  session ??= new ReplSession();
+  return session;
+}
+
+export class Recoverable extends SyntaxError {
+  err: Error;
+
+  constructor(err: Error) {
+    super();
+    this.err = err;
+  }
+}
+
+export const writer = (obj: unknown) => inspect(obj, writer.options);
+writer.options = { ...inspect.defaultOptions, showProxy: true };
+
+function isRecoverableError(e: unknown) {
+  return e instanceof SyntaxError &&
+    kRecoverableMessages.some((message) => e.message.startsWith(message));
+}
+
+// Evaluates in the global scope with the same semantics as `deno repl`, so
+// top-level `await` works and `let`/`const` bindings can be redeclared.
+function defaultEval(
+  // deno-lint-ignore no-explicit-any
+  this: any,
+  code: string,
+  _context: object,
+  _file: string,
+  cb: (err: unknown, result?: unknown) => void,
+) {
+  const prefix = this.replMode === REPL_MODE_STRICT
+    ? "'use strict'; void 0;\n"
+    : "";
+
+  (async () => {
+    const session = getSession();
+    // Like `deno repl`, statements starting with `{` are tried as an object
+    // literal first and as a block if that doesn't parse.
+    if (/^\s*\{/.test(code) && !/;\s*$/.test(code)) {
+      const result = await session.evaluate(`${prefix}(${code.trim()})\n`);
+      if (!result.thrown || !(result.value instanceof SyntaxError)) {
+        return result;
+      }
+    }
+    return await session.evaluate(prefix + code);
+  })().then(({ value, thrown }) => {
+    if (!thrown) {
+      cb(null, value);
+    } else if (isRecoverableError(value)) {
+      cb(new Recoverable(value as Error));
+    } else {
+      cb(value);
+    }
+  }, cb);
+}
+
+function propertyNames(value: unknown): string[] {
+  const names = [];
+  let obj = Object(value);
+  while (obj !== null) {
+    for (const name of Object.getOwnPropertyNames(obj)) {
+      if (/^[\p{ID_Start}$_][\p{ID_Continue}$\u200c\u200d]*$/u.test(name)) {
+        names.push(name);
+      }
+    }
+    obj = Object.getPrototypeOf(obj);
+  }
+  return names;
+}
+
+async function completionNames(expr: string): Promise<string[]> {
+  const session = getSession();
+  if (expr === "") {
+    return [
+      ...propertyNames(globalThis),
+      ...await session.globalLexicalScopeNames(),
+      ...kKeywords,
+    ];
+  }
+  // Getters with side effects make the preview throw instead of running.
+  const { value, thrown } = await session.evaluate(expr, true);
+  if (thrown || value === null || value === undefined) {
+    return [];
+  }
+  return propertyNames(value);
+}
+
+function complete(
+  // deno-lint-ignore no-explicit-any
+  this: any,
+  line: string,
+  callback: (err: Error | null, result: [string[], string]) => void,
+) {
+  if (this[kBufferedCommand] === "") {
+    const command = /^\s*\.(\w*)$/.exec(line);
+    if (command !== null) {
+      const completions = Object.keys(this.commands)
+        .filter((name) => name.startsWith(command[1]))
+        .sort()
+        .map((name) => `.${name}`);
+      callback(null, [completions, `.${command[1]}`]);
+      return;
+    }
+  }
+
+  const match = /((?:[\w$]+\.)*)([\w$]*)$/.exec(line)!;
+  // Members of computed expressions, like `foo().bar`, aren't previewed.
+  if (line[match.index - 1] === ".") {
+    callback(null, [[], match[0]]);
+    return;
+  }
+  const [completeOn, object, partial] = match;
+  completionNames(object.slice(0, -1)).then((names) => {
+    const completions = [...new SafeSet(names)]
+      .filter((name) => name.startsWith(partial))
+      .sort()
+      .map((name) => object + name);
+    callback(null, [completions, completeOn]);
+  }, () => callback(null, [[], completeOn]));
+}
+
+// deno-lint-ignore no-explicit-any
+export function REPLServer(this: any, ...args: any[]) {
+  if (!(this instanceof REPLServer)) {
+    // deno-lint-ignore no-explicit-any
+    return new (REPLServer as any)(...args);
+  }
+
+  let [prompt, stream, eval_, useGlobal, ignoreUndefined, replMode] = args;
+  let options;
+  if (prompt !== null && typeof prompt === "object") {
+    // An options object was given.
+    options = { ...prompt };
+    stream = options.stream || options.socket;
+    eval_ = options.eval;
+    useGlobal = options.useGlobal;
+    ignoreUndefined = options.ignoreUndefined;
+    prompt = options.prompt;
+    replMode = options.replMode;
+  } else {
+    options = {};
+  }
+
+  if (!options.input && !options.output) {
+    // Legacy API, passing a duplex `stream` or the `process` object.
+    stream ||= process;
+    options.input = stream.stdin || stream;
+    options.output = stream.stdout || stream;
+  }
+
+  if (options.terminal === undefined) {
+    options.terminal = options.output.isTTY;
+  }
+  options.terminal = !!options.terminal;
+
+  if (options.terminal && options.useColors === undefined) {
+    options.useColors = options.output.hasColors?.() ?? true;
+  }
+
+  if (options.breakEvalOnSigint && eval_) {
+    throw new ERR_INVALID_REPL_EVAL_CONFIG();
+  }
+
+  if (eval_ !== undefined) {
+    validateFunction(eval_, "options.eval");
+  }
+
+  this.useColors = !!options.useColors;
+  this.useGlobal = !!useGlobal;
+  this.ignoreUndefined = !!ignoreUndefined;
+  this.replMode = replMode || REPL_MODE_SLOPPY;
+  this.eval = eval_ || defaultEval;
+  this.writer = options.writer || writer;
+  if (this.writer === writer) {
+    writer.options.colors = this.useColors;
   }
+  // Input is evaluated in the global scope, so that's also the context
+  // exposed to commands and custom `eval` functions.
+  this.context = globalThis;
+  this.commands = { __proto__: null };
+  this.lines = [];
+  this.last = undefined;
+  this[kBufferedCommand] = "";
+  this[kPendingLines] = [];
+  this[kEvaluating] = false;
+  this[kSawSIGINT] = false;
+
+  Interface.call(this, {
+    input: options.input,
+    output: options.output,
+    completer: options.completer || complete,
+    terminal: options.terminal,
+    historySize: options.historySize,
+    prompt: prompt !== undefined ? prompt : "> ",
+  });
+
+  defineDefaultCommands(this);
+
+  this.on("close", () => this.emit("exit"));
+
+  this.on("line", (line: string) => {
+    this[kSawSIGINT] = false;
+    this[kPendingLines].push(line);
+    processPendingLines(this);
+  });
+
+  this.on("SIGINT", () => {
+    const empty = this.line.length === 0 && this[kBufferedCommand] === "";
+    this.clearLine();
+    if (empty) {
+      if (this[kSawSIGINT]) {
+        this.close();
+        return;
+      }
+      this.output.write(
+        "(To exit, press Ctrl+C again or Ctrl+D or type .exit)\n",
+      );
+      this[kSawSIGINT] = true;
+    } else {
+      this[kSawSIGINT] = false;
+    }
+    this.clearBufferedCommand();
+    this.displayPrompt();
+  });
+
+  this.displayPrompt();
+}
+ObjectSetPrototypeOf(REPLServer.prototype, Interface.prototype);
+ObjectSetPrototypeOf(REPLServer, Interface);
+
+// Evaluation is asynchronous, so lines that arrive in the meantime are
+// queued to keep their results in order.
+// deno-lint-ignore no-explicit-any
+function processPendingLines(self: any) {
+  if (self[kEvaluating] || self[kPendingLines].length === 0) {
+    return;
+  }
+  self[kEvaluating] = true;
+  onLine(self, self[kPendingLines].shift(), () => {
+    self[kEvaluating] = false;
+    processPendingLines(self);
+  });
+}
+
+// deno-lint-ignore no-explicit-any
+function onLine(self: any, cmd: string, done: () => void) {
+  const trimmed = cmd.trim();
+
+  // REPL commands start with a single `.`, unlike numbers such as `.5`.
+  if (
+    trimmed.startsWith(".") && !trimmed.startsWith("..") &&
+    Number.isNaN(Number.parseFloat(trimmed))
+  ) {
+    const match = /^\.([^\s]+)\s*(.*)$/.exec(trimmed);
+    const command = match !== null ? self.commands[match[1]] : undefined;
+    if (command !== undefined) {
+      command.action.call(self, match![2]);
+      done();
+      return;
+    }
+    if (self[kBufferedCommand] === "") {
+      self.output.write("Invalid REPL keyword\n");
+      self.displayPrompt();
+      done();
+      return;
+    }
+  }
+
+  if (self[kBufferedCommand] === "" && trimmed === "") {
+    self.displayPrompt();
+    done();
+    return;
+  }
+
+  self.lines.push(cmd);
+  const evalCmd = self[kBufferedCommand] + cmd + "\n";
+  self.eval(
+    evalCmd,
+    self.context,
+    "REPL",
+    function finish(e: unknown, ret?: unknown) {
+      if (e instanceof Recoverable) {
+        self[kBufferedCommand] = evalCmd;
+        self.displayPrompt();
+        done();
+        return;
+      }
+
+      self.clearBufferedCommand();
+      if (e) {
+        self.output.write(
+          `Uncaught ${inspect(e, { colors: self.useColors })}\n`,
+        );
+      } else if (
+        arguments.length === 2 && (!self.ignoreUndefined || ret !== undefined)
+      ) {
+        self.last = ret;
+        self.output.write(`${self.writer(ret)}\n`);
+      }
+      self.displayPrompt();
+      done();
+    },
+  );
+}
+
+REPLServer.prototype.setPrompt = function setPrompt(prompt: string) {
+  this[kInitialPrompt] = prompt;
+  Interface.prototype.setPrompt.call(this, prompt);
+};
+
+REPLServer.prototype.displayPrompt = function displayPrompt(
+  preserveCursor?: boolean,
+) {
+  if (this.closed) {
+    return;
+  }
+  const prompt = this[kBufferedCommand] === "" ? this[kInitialPrompt] : "| ";
+  Interface.prototype.setPrompt.call(this, prompt);
+  this.prompt(preserveCursor);
+};
+
+REPLServer.prototype.clearBufferedCommand = function clearBufferedCommand() {
+  this[kBufferedCommand] = "";
+};
+
+REPLServer.prototype.defineCommand = function defineCommand(
+  keyword: string,
+  // deno-lint-ignore no-explicit-any
+  cmd: any,
+) {
+  if (typeof cmd === "function") {
+    cmd = { action: cmd };
+  } else {
+    validateFunction(cmd.action, "cmd.action");
+  }
+  this.commands[keyword] = cmd;
+};
+
+REPLServer.prototype.setupHistory = function setupHistory(
+  historyPath: string,
+  cb: (err: Error | null, repl?: unknown) => void,
+) {
+  validateString(historyPath, "historyPath");
+  validateFunction(cb, "cb");
+  if (historyPath.trim() === "") {
+    process.nextTick(cb, null, this);
+    return;
+  }
+
+  readFile(historyPath, "utf8", (err, data) => {
+    if (err && err.code !== "ENOENT") {
+      cb(err);
+      return;
+    }
+    if (data) {
+      this.history = data.split(/\r?\n/)
+        .filter((line) => line.trim() !== "")
+        .slice(0, this.historySize);
+    }
+
+    // Only one write is in flight at a time, the latest history is written
+    // once it finishes.
+    let writing = false;
+    let pending = false;
+    const flush = () => {
+      writing = true;
+      pending = false;
+      writeFile(historyPath, this.history.join("\n"), (err) => {
+        writing = false;
+        if (err) {
+          this.emit("error", err);
+        } else if (pending) {
+          flush();
+        }
+      });
+    };
+    this.on("history", () => {
+      if (writing) {
+        pending = true;
+      } else {
+        flush();
+      }
+    });
+
+    cb(null, this);
+  });
+};
+
+// deno-lint-ignore no-explicit-any
+function defineDefaultCommands(repl: any) {
+  repl.defineCommand("break", {
+    help: "Sometimes you get stuck, this gets you out",
+    action() {
+      this.clearBufferedCommand();
+      this.displayPrompt();
+    },
+  });
+
+  repl.defineCommand("clear", {
+    help: "Break, and also clear the local context",
+    action() {
+      this.clearBufferedCommand();
+      this.lines = [];
+      this.emit("reset", this.context);
+      this.displayPrompt();
+    },
+  });
+
+  repl.defineCommand("exit", {
+    help: "Exit the REPL",
+    action() {
+      this.close();
+    },
+  });
+
+  repl.defineCommand("help", {
+    help: "Print this help message",
+    action() {
+      const names = Object.keys(this.commands).sort();
+      const width = Math.max(...names.map((name) => name.length));
+      for (const name of names) {
+        const help = this.commands[name].help || "";
+        this.output.write(`.${name.padEnd(width)}    ${help}\n`);
+      }
+      this.output.write(
+        "\nPress Ctrl+C to abort current expression, Ctrl+D to exit the REPL\n",
+      );
+      this.displayPrompt();
+    },
+  });
+
+  repl.defineCommand("save", {
+    help: "Save all evaluated commands in this REPL session to a file",
+    action(file: string) {
+      try {
+        writeFileSync(file, this.lines.join("\n"));
+        this.output.write(`Session saved to: ${file}\n`);
+      } catch {
+        this.output.write(`Failed to save: ${file}\n`);
+      }
+      this.displayPrompt();
+    },
+  });
+
+  repl.defineCommand("load", {
+    help: "Load JS from a file into the REPL session",
+    action(file: string) {
+      let data;
+      try {
+        data = readFileSync(file, "utf8");
+      } catch {
+        this.output.write(`Failed to load: ${file}\n`);
+        this.displayPrompt();
+        return;
+      }
+      // The lines are evaluated as if they were typed, before any input
+      // that is still queued.
+      this[kPendingLines].unshift(...data.split(/\r?\n/));
+    },
+  });
+}
+
+export function start(
+  prompt?: unknown,
+  source?: unknown,
+  eval_?: unknown,
+  useGlobal?: boolean,
+  ignoreUndefined?: boolean,
+  replMode?: symbol,
+) {
+  // deno-lint-ignore no-explicit-any
+  return new (REPLServer as any)(
+    prompt,
+    source,
+    eval_,
+    useGlobal,
+    ignoreUndefined,
+    replMode,
+  );
 }
+
 export const builtinModules = [
   "assert",
   "async_hooks",
@@ -58,14 +607,13 @@ export const builtinModules = [
   "zlib",
 ];
 export const _builtinLibs = builtinModules;
-export function start() {
-  notImplemented("repl.start");
-}
 export default {
   REPLServer,
+  Recoverable,
   builtinModules,
   _builtinLibs,
   start,
+  writer,
   REPL_MODE_SLOPPY,
   REPL_MODE_STRICT,
 };
@@ -5,14 +5,14 @@ const {
   PromisePrototypeThen,
   ArrayPrototypePush,
   ArrayPrototypeForEach,
+  FunctionPrototypeCall,
   SafePromiseAll,
   TypeError,
-  SafeArrayIterator,
-  SafePromisePrototypeFinally,
   Symbol,
 } = primordials;
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import assert from "node:assert";
+import { MockTracker } from "ext:deno_node/internal/test_runner/mock.ts";
+import { run } from "ext:deno_node/internal/test_runner/runner.ts";
 
 const methodsToCopy = [
   "deepEqual",
@@ -47,28 +47,133 @@ function getAssertObject() {
   return assertObject;
 }
 
-export function run() {
-  notImplemented("test.run");
-}
-
 function noop() {}
 
 const skippedSymbol = Symbol("skipped");
+const kHooks = Symbol("kHooks");
+const kParent = Symbol("kParent");
+const kFinalize = Symbol("kFinalize");
+
+/** The hooks registered on a test context, a suite or the root. */
+class TestHooks {
+  before: ((t?: NodeTestContext) => unknown)[] = [];
+  after: ((t?: NodeTestContext) => unknown)[] = [];
+  beforeEach: ((t: NodeTestContext) => unknown)[] = [];
+  afterEach: ((t: NodeTestContext) => unknown)[] = [];
+  #before: Promise<void> | undefined;
+
+  // Tests that start while the hooks are running wait for them to finish.
+  runBefore(t?: NodeTestContext): Promise<void> {
+    this.#before ??= runHooks(this.before, t);
+    return this.#before;
+  }
+}
+
+interface HooksOwner {
+  [kHooks]: TestHooks;
+  [kParent]: HooksOwner | undefined;
+}
+
+const rootHooks: HooksOwner = {
+  [kHooks]: new TestHooks(),
+  [kParent]: undefined,
+};
+
+function validateHook(name: string, fn: unknown) {
+  if (typeof fn !== "function") {
+    throw new TypeError(`${name}() requires a function`);
+  }
+}
+
+async function runHooks(
+  hooks: ((t?: NodeTestContext) => unknown)[],
+  t?: NodeTestContext,
+) {
+  for (let i = 0; i < hooks.length; i++) {
+    await FunctionPrototypeCall(hooks[i], t, t);
+  }
+}
+
+// The `before` hooks run once per owner, the outermost first.
+async function runBefore(owner: HooksOwner | undefined) {
+  if (owner === undefined) {
+    return;
+  }
+  await runBefore(owner[kParent]);
+  await owner[kHooks].runBefore(
+    owner === rootHooks ? undefined : owner as NodeTestContext,
+  );
+}
+
+// The `beforeEach` hooks of the outermost owner run first.
+async function runBeforeEach(owner: HooksOwner | undefined, t) {
+  if (owner === undefined) {
+    return;
+  }
+  await runBeforeEach(owner[kParent], t);
+  await runHooks(owner[kHooks].beforeEach, t);
+}
+
+// The `afterEach` hooks of the innermost owner run first.
+async function runAfterEach(owner: HooksOwner | undefined, t) {
+  if (owner === undefined) {
+    return;
+  }
+  await runHooks(owner[kHooks].afterEach, t);
+  await runAfterEach(owner[kParent], t);
+}
+
+/**
+ * Runs the body of a test with the hooks of its parents, then cleans up the
+ * mocks and aborts the signal of its context.
+ */
+async function runTestBody(
+  context: NodeTestContext,
+  fn: (t: NodeTestContext) => unknown,
+) {
+  const parent = context[kParent];
+  try {
+    await runBefore(parent);
+    await runBeforeEach(parent, context);
+    await FunctionPrototypeCall(fn, context, context);
+    await runHooks(context[kHooks].after, context);
+    await runAfterEach(parent, context);
+  } catch (err) {
+    if (!context[skippedSymbol]) {
+      throw err;
+    }
+    try {
+      await runHooks(context[kHooks].after, context);
+      await runAfterEach(parent, context);
+    } catch { /* ignore, test is already failing */ }
+  } finally {
+    context[kFinalize]();
+  }
+}
 
 class NodeTestContext {
   #denoContext: Deno.TestContext;
-  #afterHooks: (() => void)[] = [];
-  #beforeHooks: (() => void)[] = [];
-  #parent: NodeTestContext | undefined;
+  #parent: HooksOwner;
+  #name: string;
   #skipped = false;
+  #runOnly = false;
+  #mock: MockTracker | undefined;
+  #abortController = new AbortController();
+  [kHooks] = new TestHooks();
 
-  constructor(t: Deno.TestContext, parent: NodeTestContext | undefined) {
+  constructor(t: Deno.TestContext, parent: HooksOwner, name: string) {
     this.#denoContext = t;
     this.#parent = parent;
+    this.#name = name;
+  }
+
+  get [kParent]() {
+    return this.#parent;
   }
 
   get [skippedSymbol]() {
-    return this.#skipped || (this.#parent?.[skippedSymbol] ?? false);
+    return this.#skipped ||
+      ((this.#parent as NodeTestContext)[skippedSymbol] ?? false);
   }
 
   get assert() {
@@ -76,13 +181,18 @@ class NodeTestContext {
   }
 
   get signal() {
-    notImplemented("test.TestContext.signal");
-    return null;
+    return this.#abortController.signal;
   }
 
   get name() {
-    notImplemented("test.TestContext.name");
-    return null;
+    return this.#name;
+  }
+
+  get fullName() {
+    const parentName = (this.#parent as NodeTestContext).fullName;
+    return parentName === undefined
+      ? this.#name
+      : `${parentName} > ${this.#name}`;
   }
 
   diagnostic(message) {
@@ -91,13 +201,12 @@ class NodeTestContext {
   }
 
   get mock() {
-    notImplemented("test.TestContext.mock");
-    return null;
+    this.#mock ??= new MockTracker();
+    return this.#mock;
   }
 
-  runOnly() {
-    notImplemented("test.TestContext.runOnly");
-    return null;
+  runOnly(shouldRunOnlyTests) {
+    this.#runOnly = !!shouldRunOnlyTests;
   }
 
   skip() {
@@ -114,38 +223,16 @@ class NodeTestContext {
     const prepared = prepareOptions(name, options, fn, {});
     // deno-lint-ignore no-this-alias
     const parentContext = this;
-    const after = async () => {
-      for (const hook of new SafeArrayIterator(this.#afterHooks)) {
-        await hook();
-      }
-    };
-    const before = async () => {
-      for (const hook of new SafeArrayIterator(this.#beforeHooks)) {
-        await hook();
-      }
-    };
     return PromisePrototypeThen(
       this.#denoContext.step({
         name: prepared.name,
-        fn: async (denoTestContext) => {
-          const newNodeTextContext = new NodeTestContext(
-            denoTestContext,
-            parentContext,
-          );
-          try {
-            await before();
-            await prepared.fn(newNodeTextContext);
-            await after();
-          } catch (err) {
-            if (!newNodeTextContext[skippedSymbol]) {
-              throw err;
-            }
-            try {
-              await after();
-            } catch { /* ignore, test is already failing */ }
-          }
-        },
-        ignore: prepared.options.todo || prepared.options.skip,
+        fn: (denoTestContext) =>
+          runTestBody(
+            new NodeTestContext(denoTestContext, parentContext, prepared.name),
+            prepared.fn,
+          ),
+        ignore: prepared.options.todo || prepared.options.skip ||
+          (this.#runOnly && !prepared.options.only),
         sanitizeExit: false,
         sanitizeOps: false,
         sanitizeResources: false,
@@ -155,25 +242,28 @@ class NodeTestContext {
   }
 
   before(fn, _options) {
-    if (typeof fn !== "function") {
-      throw new TypeError("before() requires a function");
-    }
-    ArrayPrototypePush(this.#beforeHooks, fn);
+    validateHook("before", fn);
+    ArrayPrototypePush(this[kHooks].before, fn);
   }
 
   after(fn, _options) {
-    if (typeof fn !== "function") {
-      throw new TypeError("after() requires a function");
-    }
-    ArrayPrototypePush(this.#afterHooks, fn);
+    validateHook("after", fn);
+    ArrayPrototypePush(this[kHooks].after, fn);
+  }
+
+  beforeEach(fn, _options) {
+    validateHook("beforeEach", fn);
+    ArrayPrototypePush(this[kHooks].beforeEach, fn);
   }
 
-  beforeEach(_fn, _options) {
-    notImplemented("test.TestContext.beforeEach");
+  afterEach(fn, _options) {
+    validateHook("afterEach", fn);
+    ArrayPrototypePush(this[kHooks].afterEach, fn);
   }
 
-  afterEach(_fn, _options) {
-    notImplemented("test.TestContext.afterEach");
+  [kFinalize]() {
+    this.#abortController.abort();
+    this.#mock?.reset();
   }
 }
 
@@ -181,31 +271,41 @@ let currentSuite: TestSuite | null = null;
 
 class TestSuite {
   #denoTestContext: Deno.TestContext;
+  #parent: HooksOwner;
+  #name: string;
   steps: Promise<boolean>[] = [];
+  [kHooks] = new TestHooks();
 
-  constructor(t: Deno.TestContext) {
+  constructor(t: Deno.TestContext, parent: HooksOwner, name: string) {
     this.#denoTestContext = t;
+    this.#parent = parent;
+    this.#name = name;
+  }
+
+  get [kParent]() {
+    return this.#parent;
+  }
+
+  get name() {
+    return this.#name;
+  }
+
+  get fullName() {
+    const parentName = (this.#parent as TestSuite).fullName;
+    return parentName === undefined
+      ? this.#name
+      : `${parentName} > ${this.#name}`;
   }
 
   addTest(name, options, fn, overrides) {
     const prepared = prepareOptions(name, options, fn, overrides);
     const step = this.#denoTestContext.step({
       name: prepared.name,
-      fn: async (denoTestContext) => {
-        const newNodeTextContext = new NodeTestContext(
-          denoTestContext,
-          undefined,
-        );
-        try {
-          return await prepared.fn(newNodeTextContext);
-        } catch (err) {
-          if (newNodeTextContext[skippedSymbol]) {
-            return undefined;
-          } else {
-            throw err;
-          }
-        }
-      },
+      fn: (denoTestContext) =>
+        runTestBody(
+          new NodeTestContext(denoTestContext, this, prepared.name),
+          prepared.fn,
+        ),
       ignore: prepared.options.todo || prepared.options.skip,
       sanitizeExit: false,
       sanitizeOps: false,
@@ -220,7 +320,7 @@ class TestSuite {
     const { promise, resolve } = Promise.withResolvers();
     const step = this.#denoTestContext.step({
       name: prepared.name,
-      fn: wrapSuiteFn(prepared.fn, resolve),
+      fn: wrapSuiteFn(prepared.fn, this, prepared.name, resolve),
       ignore: prepared.options.todo || prepared.options.skip,
       sanitizeExit: false,
       sanitizeOps: false,
@@ -260,15 +360,10 @@ function prepareOptions(name, options, fn, overrides) {
   return { fn, options: finalOptions, name };
 }
 
-function wrapTestFn(fn, resolve) {
+function wrapTestFn(fn, name, resolve) {
   return async function (t) {
-    const nodeTestContext = new NodeTestContext(t, undefined);
     try {
-      await fn(nodeTestContext);
-    } catch (err) {
-      if (!nodeTestContext[skippedSymbol]) {
-        throw err;
-      }
+      await runTestBody(new NodeTestContext(t, rootHooks, name), fn);
     } finally {
       resolve();
     }
@@ -284,7 +379,7 @@ function prepareDenoTest(name, options, fn, overrides) {
 
   const denoTestOptions = {
     name: prepared.name,
-    fn: wrapTestFn(prepared.fn, resolve),
+    fn: wrapTestFn(prepared.fn, prepared.name, resolve),
     only: prepared.options.only,
     ignore: prepared.options.todo || prepared.options.skip,
     sanitizeExit: false,
@@ -295,16 +390,23 @@ function prepareDenoTest(name, options, fn, overrides) {
   return promise;
 }
 
-function wrapSuiteFn(fn, resolve) {
-  return function (t) {
+function wrapSuiteFn(fn, parent: HooksOwner, name: string, resolve) {
+  return async function (t) {
     const prevSuite = currentSuite;
-    const suite = currentSuite = new TestSuite(t);
+    const suite = currentSuite = new TestSuite(t, parent, name);
     try {
-      fn();
+      try {
+        fn();
+      } finally {
+        currentSuite = prevSuite;
+      }
+      // also runs the hooks of suites without tests
+      await runBefore(suite);
+      await SafePromiseAll(suite.steps);
+      await runHooks(suite[kHooks].after);
     } finally {
-      currentSuite = prevSuite;
+      resolve();
     }
-    return SafePromisePrototypeFinally(SafePromiseAll(suite.steps), resolve);
   };
 }
 
@@ -316,7 +418,7 @@ function prepareDenoTestForSuite(name, options, fn, overrides) {
 
   const denoTestOptions = {
     name: prepared.name,
-    fn: wrapSuiteFn(prepared.fn, resolve),
+    fn: wrapSuiteFn(prepared.fn, rootHooks, prepared.name, resolve),
     only: prepared.options.only,
     ignore: prepared.options.todo || prepared.options.skip,
     sanitizeExit: false,
@@ -393,60 +495,50 @@ it.only = function only(name, options, fn) {
   return test.only(name, options, fn);
 };
 
-export function before() {
-  notImplemented("test.before");
+// Hooks outside of a suite apply to all the tests of the module.
+function currentHooks(): TestHooks {
+  return (currentSuite ?? rootHooks)[kHooks];
 }
 
-export function after() {
-  notImplemented("test.after");
+let rootAfterRegistered = false;
+
+export function before(fn, _options) {
+  validateHook("before", fn);
+  ArrayPrototypePush(currentHooks().before, fn);
 }
 
-export function beforeEach() {
-  notImplemented("test.beforeEach");
+export function after(fn, _options) {
+  validateHook("after", fn);
+  if (currentSuite === null && !rootAfterRegistered) {
+    rootAfterRegistered = true;
+    Deno.test.afterAll(() => runHooks(rootHooks[kHooks].after));
+  }
+  ArrayPrototypePush(currentHooks().after, fn);
 }
 
-export function afterEach() {
-  notImplemented("test.afterEach");
+export function beforeEach(fn, _options) {
+  validateHook("beforeEach", fn);
+  ArrayPrototypePush(currentHooks().beforeEach, fn);
+}
+
+export function afterEach(fn, _options) {
+  validateHook("afterEach", fn);
+  ArrayPrototypePush(currentHooks().afterEach, fn);
 }
 
 test.it = it;
 test.describe = describe;
 test.suite = suite;
+test.before = before;
+test.after = after;
+test.beforeEach = beforeEach;
+test.afterEach = afterEach;
+test.run = run;
 
-export const mock = {
-  fn: () => {
-    notImplemented("test.mock.fn");
-  },
-  getter: () => {
-    notImplemented("test.mock.getter");
-  },
-  method: () => {
-    notImplemented("test.mock.method");
-  },
-  reset: () => {
-    notImplemented("test.mock.reset");
-  },
-  restoreAll: () => {
-    notImplemented("test.mock.restoreAll");
-  },
-  setter: () => {
-    notImplemented("test.mock.setter");
-  },
-  timers: {
-    enable: () => {
-      notImplemented("test.mock.timers.enable");
-    },
-    reset: () => {
-      notImplemented("test.mock.timers.reset");
-    },
-    tick: () => {
-      notImplemented("test.mock.timers.tick");
-    },
-    runAll: () => {
-      notImplemented("test.mock.timers.runAll");
-    },
-  },
-};
+export const mock = new MockTracker();
+test.mock = mock;
+
+export { run };
 
 test.test = test;
 
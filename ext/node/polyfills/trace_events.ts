@@ -1,24 +1,200 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 
-import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+// TODO(petamoriken): enable prefer-primordials for node polyfills
+// deno-lint-ignore-file prefer-primordials
+
+import { internals } from "ext:core/mod.js";
+import {
+  op_node_trace_event,
+  op_node_trace_events_disable,
+  op_node_trace_events_enable,
+  op_node_trace_events_gc_hook,
+  op_node_trace_events_get_enabled_categories,
+} from "ext:core/ops";
+import {
+  ERR_TRACE_EVENTS_CATEGORY_REQUIRED,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateObject,
+  validateStringArray,
+} from "ext:deno_node/internal/validators.mjs";
+import {
+  AsyncHook,
+  executionAsyncId,
+} from "ext:deno_node/internal/async_hooks.ts";
+import { performance } from "ext:deno_web/15_performance.js";
+import process from "node:process";
+
+const kMaxTracingCount = 10;
+const kPerfCategory = "node,node.perf,node.perf.usertiming";
+const kAsyncHooksCategory = "node,node.async_hooks";
+
+const enabledTracingObjects = new Set<Tracing>();
+let enabledCategories = new Set<string>();
+
+function isCategoryEnabled(group: string): boolean {
+  return group.split(",").some((category) => enabledCategories.has(category));
+}
+
+function trace(
+  phase: string,
+  category: string,
+  name: string,
+  id?: number,
+  timestamp?: number,
+  args?: Record<string, unknown>,
+) {
+  op_node_trace_event(phase, category, name, id, timestamp, args);
+}
 
 class Tracing {
-  enabled = false;
-  categories = "";
+  #categories: string[];
+  #enabled = false;
+
+  constructor(categories: string[]) {
+    this.#categories = categories;
+  }
+
+  enable() {
+    if (!this.#enabled) {
+      op_node_trace_events_enable(this.#categories);
+      this.#enabled = true;
+      enabledTracingObjects.add(this);
+      if (enabledTracingObjects.size > kMaxTracingCount) {
+        process.emitWarning(
+          "Possible trace_events memory leak detected. There are more than " +
+            `${kMaxTracingCount} enabled Tracing objects.`,
+        );
+      }
+      updateTraceHooks();
+    }
+  }
+
+  disable() {
+    if (this.#enabled) {
+      op_node_trace_events_disable(this.#categories);
+      this.#enabled = false;
+      enabledTracingObjects.delete(this);
+      updateTraceHooks();
+    }
+  }
+
+  get enabled() {
+    return this.#enabled;
+  }
+
+  get categories() {
+    return this.#categories.join(",");
+  }
+}
+
+function createTracing(options: { categories: string[] }) {
+  validateObject(options, "options");
+  validateStringArray(options.categories, "options.categories");
+
+  if (options.categories.length <= 0) {
+    throw new ERR_TRACE_EVENTS_CATEGORY_REQUIRED();
+  }
+
+  return new Tracing(options.categories);
+}
+
+function getEnabledCategories(): string | undefined {
+  return op_node_trace_events_get_enabled_categories() ?? undefined;
 }
 
-function createTracing(opts) {
-  if (typeof opts !== "object" || opts == null) {
-    throw new ERR_INVALID_ARG_TYPE("options", "Object", opts);
+let perfHooksInstalled = false;
+let measureId = 0;
+
+// User timing marks and measures are recorded like Node records them from
+// `performance.mark()` and `performance.measure()`.
+function installPerfHooks() {
+  if (perfHooksInstalled) {
+    return;
   }
+  perfHooksInstalled = true;
 
-  return new Tracing(opts);
+  const mark = performance.mark;
+  const measure = performance.measure;
+  performance.mark = function (...args: Parameters<typeof mark>) {
+    const entry = mark.apply(performance, args);
+    if (isCategoryEnabled(kPerfCategory)) {
+      trace(
+        "R",
+        kPerfCategory,
+        entry.name,
+        undefined,
+        performance.timeOrigin + entry.startTime,
+      );
+    }
+    return entry;
+  };
+  performance.measure = function (...args: Parameters<typeof measure>) {
+    const entry = measure.apply(performance, args);
+    if (isCategoryEnabled(kPerfCategory)) {
+      const id = ++measureId;
+      const start = performance.timeOrigin + entry.startTime;
+      trace("b", kPerfCategory, entry.name, id, start);
+      trace("e", kPerfCategory, entry.name, id, start + entry.duration);
+    }
+    return entry;
+  };
 }
 
-function getEnabledCategories() {
-  return "";
+const typeMemory = new Map<number, string>();
+
+// Records the lifetime of async resources and their callbacks as nested
+// async events, see Node's lib/internal/trace_events_async_hooks.js.
+const asyncHook = new AsyncHook({
+  init(asyncId: number, type: string, triggerAsyncId: number) {
+    typeMemory.set(asyncId, type);
+    trace("b", kAsyncHooksCategory, type, asyncId, undefined, {
+      data: { triggerAsyncId, executionAsyncId: executionAsyncId() },
+    });
+  },
+  before(asyncId: number) {
+    const type = typeMemory.get(asyncId);
+    if (type === undefined) return;
+    trace("b", kAsyncHooksCategory, `${type}_CALLBACK`, asyncId);
+  },
+  after(asyncId: number) {
+    const type = typeMemory.get(asyncId);
+    if (type === undefined) return;
+    trace("e", kAsyncHooksCategory, `${type}_CALLBACK`, asyncId);
+  },
+  destroy(asyncId: number) {
+    const type = typeMemory.get(asyncId);
+    if (type === undefined) return;
+    trace("e", kAsyncHooksCategory, type, asyncId);
+    typeMemory.delete(asyncId);
+  },
+});
+let asyncHookEnabled = false;
+
+function updateTraceHooks() {
+  enabledCategories = new Set(
+    op_node_trace_events_get_enabled_categories()?.split(","),
+  );
+
+  if (isCategoryEnabled("v8")) {
+    op_node_trace_events_gc_hook();
+  }
+  if (isCategoryEnabled(kPerfCategory)) {
+    installPerfHooks();
+  }
+  if (isCategoryEnabled(kAsyncHooksCategory) !== asyncHookEnabled) {
+    asyncHookEnabled = !asyncHookEnabled;
+    if (asyncHookEnabled) {
+      asyncHook.enable();
+    } else {
+      asyncHook.disable();
+      typeMemory.clear();
+    }
+  }
 }
 
+internals.__initTraceEvents = updateTraceHooks;
+
 export { createTracing, getEnabledCategories };
 
 export default {
@@ -6,10 +6,11 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
-import { primordials } from "ext:core/mod.js";
+import { internals, primordials } from "ext:core/mod.js";
 const { ObjectPrototypeToString, SymbolSpecies } = primordials;
 import {
   op_v8_cached_data_version_tag,
+  op_v8_get_heap_space_statistics,
   op_v8_get_heap_statistics,
   op_v8_get_wire_format_version,
   op_v8_new_deserializer,
@@ -22,10 +23,13 @@ import {
   op_v8_read_value,
   op_v8_release_buffer,
   op_v8_set_treat_array_buffer_views_as_host_objects,
+  op_v8_stop_coverage,
+  op_v8_take_coverage,
   op_v8_transfer_array_buffer,
   op_v8_transfer_array_buffer_de,
   op_v8_write_double,
   op_v8_write_header,
+  op_v8_write_heap_snapshot,
   op_v8_write_raw_bytes,
   op_v8_write_uint32,
   op_v8_write_uint64,
@@ -33,21 +37,55 @@ import {
 } from "ext:core/ops";
 
 import { Buffer } from "node:buffer";
+import process from "node:process";
+import workerThreads from "node:worker_threads";
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
+import { getHeapSnapshotStream } from "ext:deno_node/internal/heap_utils.ts";
+import { getOptionValue } from "ext:deno_node/internal/options.ts";
+import { validateObject } from "ext:deno_node/internal/validators.mjs";
+import { getValidatedPathToString } from "ext:deno_node/internal/fs/utils.mjs";
 
 export function cachedDataVersionTag() {
   return op_v8_cached_data_version_tag();
 }
 export function getHeapCodeStatistics() {
-  notImplemented("v8.getHeapCodeStatistics");
+  // V8 doesn't expose its code statistics to embedders other than Node.js,
+  // so they are estimated from the spaces that hold code and bytecode.
+  let codeAndMetadataSize = 0;
+  let bytecodeAndMetadataSize = 0;
+  for (const space of op_v8_get_heap_space_statistics()) {
+    if (
+      space.space_name === "code_space" ||
+      space.space_name === "code_large_object_space"
+    ) {
+      codeAndMetadataSize += space.space_used_size;
+    } else if (
+      space.space_name === "trusted_space" ||
+      space.space_name === "trusted_large_object_space"
+    ) {
+      bytecodeAndMetadataSize += space.space_used_size;
+    }
+  }
+
+  return {
+    code_and_metadata_size: codeAndMetadataSize,
+    bytecode_and_metadata_size: bytecodeAndMetadataSize,
+    external_script_source_size: 0,
+    cpu_profiler_metadata_size: 0,
+  };
 }
-export function getHeapSnapshot() {
-  notImplemented("v8.getHeapSnapshot");
+export function getHeapSnapshot(options?: {
+  exposeInternals?: boolean;
+  exposeNumericValues?: boolean;
+}) {
+  if (options !== undefined) {
+    validateObject(options, "options");
+  }
+  return getHeapSnapshotStream();
 }
 export function getHeapSpaceStatistics() {
-  notImplemented("v8.getHeapSpaceStatistics");
+  return op_v8_get_heap_space_statistics();
 }
 
 const buffer = new Float64Array(14);
@@ -83,15 +121,71 @@ export function setFlagsFromString() {
   // Notice: "or it may simply do nothing". This is what we're gonna do,
   // this function will just be a no-op.
 }
+// Coverage is collected when running with `--coverage` or
+// `DENO_COVERAGE_DIR`, otherwise these are no-ops like in Node.js without
+// `NODE_V8_COVERAGE`.
 export function stopCoverage() {
-  notImplemented("v8.stopCoverage");
+  op_v8_stop_coverage();
 }
 export function takeCoverage() {
-  notImplemented("v8.takeCoverage");
+  op_v8_take_coverage();
 }
-export function writeHeapSnapshot() {
-  notImplemented("v8.writeHeapSnapshot");
+
+let heapSnapshotSequence = 0;
+
+function pad(n: number, width = 2) {
+  return `${n}`.padStart(width, "0");
 }
+
+// Like Node.js, eg. `Heap.20190718.133405.15554.0.001.heapsnapshot`.
+function getDefaultHeapSnapshotFilename() {
+  const now = new Date();
+  const date = `${now.getFullYear()}${pad(now.getMonth() + 1)}` +
+    pad(now.getDate());
+  const time = pad(now.getHours()) + pad(now.getMinutes()) +
+    pad(now.getSeconds());
+  const sequence = pad(++heapSnapshotSequence, 3);
+  const { pid } = process;
+  const { threadId } = workerThreads;
+  return `Heap.${date}.${time}.${pid}.${threadId}.${sequence}.heapsnapshot`;
+}
+
+export function writeHeapSnapshot(
+  filename?: string | Buffer | URL,
+  options?: { exposeInternals?: boolean; exposeNumericValues?: boolean },
+) {
+  if (options !== undefined) {
+    validateObject(options, "options");
+  }
+  if (filename === undefined) {
+    filename = getDefaultHeapSnapshotFilename();
+  } else {
+    filename = getValidatedPathToString(filename);
+  }
+  op_v8_write_heap_snapshot(filename);
+  return filename;
+}
+
+// Writes a heap snapshot whenever the process receives the signal given
+// with `--heapsnapshot-signal` in `NODE_OPTIONS`.
+function initHeapSnapshotSignal() {
+  const signal = getOptionValue("--heapsnapshot-signal");
+  if (!signal) {
+    return;
+  }
+
+  try {
+    Deno.addSignalListener(signal as Deno.Signal, () => {
+      writeHeapSnapshot();
+    });
+  } catch (e) {
+    process.emitWarning(
+      `Could not listen for --heapsnapshot-signal=${signal}: ${e.message}`,
+    );
+  }
+}
+
+internals.__initHeapSnapshotSignal = initHeapSnapshotSignal;
 // deno-lint-ignore no-explicit-any
 export function serialize(value: any) {
   const ser = new DefaultSerializer();
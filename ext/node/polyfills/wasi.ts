@@ -1,15 +1,433 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
 import { primordials } from "ext:core/mod.js";
+import {
+  op_node_wasi_args_get,
+  op_node_wasi_args_sizes_get,
+  op_node_wasi_clock_res_get,
+  op_node_wasi_clock_time_get,
+  op_node_wasi_environ_get,
+  op_node_wasi_environ_sizes_get,
+  op_node_wasi_fd_advise,
+  op_node_wasi_fd_allocate,
+  op_node_wasi_fd_close,
+  op_node_wasi_fd_datasync,
+  op_node_wasi_fd_fdstat_get,
+  op_node_wasi_fd_fdstat_set_flags,
+  op_node_wasi_fd_fdstat_set_rights,
+  op_node_wasi_fd_filestat_get,
+  op_node_wasi_fd_filestat_set_size,
+  op_node_wasi_fd_filestat_set_times,
+  op_node_wasi_fd_pread,
+  op_node_wasi_fd_prestat_dir_name,
+  op_node_wasi_fd_prestat_get,
+  op_node_wasi_fd_pwrite,
+  op_node_wasi_fd_read,
+  op_node_wasi_fd_readdir,
+  op_node_wasi_fd_renumber,
+  op_node_wasi_fd_seek,
+  op_node_wasi_fd_sync,
+  op_node_wasi_fd_tell,
+  op_node_wasi_fd_write,
+  op_node_wasi_new,
+  op_node_wasi_path_create_directory,
+  op_node_wasi_path_filestat_get,
+  op_node_wasi_path_filestat_set_times,
+  op_node_wasi_path_link,
+  op_node_wasi_path_open,
+  op_node_wasi_path_readlink,
+  op_node_wasi_path_remove_directory,
+  op_node_wasi_path_rename,
+  op_node_wasi_path_symlink,
+  op_node_wasi_path_unlink_file,
+  op_node_wasi_poll_oneoff,
+  op_node_wasi_random_get,
+} from "ext:core/ops";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+  ERR_WASI_ALREADY_STARTED,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateArray,
+  validateBoolean,
+  validateFunction,
+  validateInt32,
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
+import { resolve } from "node:path";
+import process from "node:process";
 
-const { Error } = primordials;
+const {
+  ArrayPrototypeMap,
+  ArrayPrototypePush,
+  ObjectEntries,
+  ObjectPrototypeIsPrototypeOf,
+  String,
+  Symbol,
+  Uint8Array,
+} = primordials;
 
-class Context {
-  constructor() {
-    throw new Error("Context is currently not supported");
+const WebAssemblyMemoryPrototype = globalThis.WebAssembly.Memory.prototype;
+
+const kExitCode = Symbol("kExitCode");
+const kSetMemory = Symbol("kSetMemory");
+const kStarted = Symbol("kStarted");
+const kInstance = Symbol("kInstance");
+const kBindingName = Symbol("kBindingName");
+
+// errnos of the system calls that are not supported
+const ENOSYS = 52;
+const ENOTSUP = 58;
+
+const kEmptyMemory = new Uint8Array(0);
+
+function createWasiImport(wasi, context, returnOnExit) {
+  let memory = null;
+  // the memory can grow, so its buffer is fetched for every call
+  const mem = () =>
+    memory === null ? kEmptyMemory : new Uint8Array(memory.buffer);
+  const notSupported = () => ENOTSUP;
+
+  const wasiImport = {
+    args_get: (argv, argvBuf) =>
+      op_node_wasi_args_get(context, mem(), argv, argvBuf),
+    args_sizes_get: (argc, argvBufSize) =>
+      op_node_wasi_args_sizes_get(context, mem(), argc, argvBufSize),
+    environ_get: (environ, environBuf) =>
+      op_node_wasi_environ_get(context, mem(), environ, environBuf),
+    environ_sizes_get: (environc, environBufSize) =>
+      op_node_wasi_environ_sizes_get(
+        context,
+        mem(),
+        environc,
+        environBufSize,
+      ),
+    clock_res_get: (id, resolution) =>
+      op_node_wasi_clock_res_get(mem(), id, resolution),
+    clock_time_get: (id, precision, time) =>
+      op_node_wasi_clock_time_get(mem(), id, precision, time),
+    fd_advise: (fd, offset, len, advice) =>
+      op_node_wasi_fd_advise(context, fd, offset, len, advice),
+    fd_allocate: (fd, offset, len) =>
+      op_node_wasi_fd_allocate(context, fd, offset, len),
+    fd_close: (fd) => op_node_wasi_fd_close(context, fd),
+    fd_datasync: (fd) => op_node_wasi_fd_datasync(context, fd),
+    fd_fdstat_get: (fd, buf) =>
+      op_node_wasi_fd_fdstat_get(context, mem(), fd, buf),
+    fd_fdstat_set_flags: (fd, flags) =>
+      op_node_wasi_fd_fdstat_set_flags(context, fd, flags),
+    fd_fdstat_set_rights: (fd, base, inheriting) =>
+      op_node_wasi_fd_fdstat_set_rights(context, fd, base, inheriting),
+    fd_filestat_get: (fd, buf) =>
+      op_node_wasi_fd_filestat_get(context, mem(), fd, buf),
+    fd_filestat_set_size: (fd, size) =>
+      op_node_wasi_fd_filestat_set_size(context, fd, size),
+    fd_filestat_set_times: (fd, atim, mtim, fstFlags) =>
+      op_node_wasi_fd_filestat_set_times(context, fd, atim, mtim, fstFlags),
+    fd_pread: (fd, iovs, iovsLen, offset, nread) =>
+      op_node_wasi_fd_pread(context, mem(), fd, iovs, iovsLen, offset, nread),
+    fd_prestat_get: (fd, buf) =>
+      op_node_wasi_fd_prestat_get(context, mem(), fd, buf),
+    fd_prestat_dir_name: (fd, path, pathLen) =>
+      op_node_wasi_fd_prestat_dir_name(context, mem(), fd, path, pathLen),
+    fd_pwrite: (fd, iovs, iovsLen, offset, nwritten) =>
+      op_node_wasi_fd_pwrite(
+        context,
+        mem(),
+        fd,
+        iovs,
+        iovsLen,
+        offset,
+        nwritten,
+      ),
+    fd_read: (fd, iovs, iovsLen, nread) =>
+      op_node_wasi_fd_read(context, mem(), fd, iovs, iovsLen, nread),
+    fd_readdir: (fd, buf, bufLen, cookie, bufused) =>
+      op_node_wasi_fd_readdir(context, mem(), fd, buf, bufLen, cookie, bufused),
+    fd_renumber: (from, to) => op_node_wasi_fd_renumber(context, from, to),
+    fd_seek: (fd, offset, whence, newOffset) =>
+      op_node_wasi_fd_seek(context, mem(), fd, offset, whence, newOffset),
+    fd_sync: (fd) => op_node_wasi_fd_sync(context, fd),
+    fd_tell: (fd, offset) => op_node_wasi_fd_tell(context, mem(), fd, offset),
+    fd_write: (fd, iovs, iovsLen, nwritten) =>
+      op_node_wasi_fd_write(context, mem(), fd, iovs, iovsLen, nwritten),
+    path_create_directory: (fd, path, pathLen) =>
+      op_node_wasi_path_create_directory(context, mem(), fd, path, pathLen),
+    path_filestat_get: (fd, flags, path, pathLen, buf) =>
+      op_node_wasi_path_filestat_get(
+        context,
+        mem(),
+        fd,
+        flags,
+        path,
+        pathLen,
+        buf,
+      ),
+    path_filestat_set_times: (fd, flags, path, pathLen, atim, mtim, fstFlags) =>
+      op_node_wasi_path_filestat_set_times(
+        context,
+        mem(),
+        fd,
+        flags,
+        path,
+        pathLen,
+        atim,
+        mtim,
+        fstFlags,
+      ),
+    path_link: (
+      oldFd,
+      oldFlags,
+      oldPath,
+      oldPathLen,
+      newFd,
+      newPath,
+      newPathLen,
+    ) =>
+      op_node_wasi_path_link(
+        context,
+        mem(),
+        oldFd,
+        oldFlags,
+        oldPath,
+        oldPathLen,
+        newFd,
+        newPath,
+        newPathLen,
+      ),
+    path_open: (
+      fd,
+      dirflags,
+      path,
+      pathLen,
+      oflags,
+      rightsBase,
+      rightsInheriting,
+      fdflags,
+      openedFd,
+    ) =>
+      op_node_wasi_path_open(
+        context,
+        mem(),
+        fd,
+        dirflags,
+        path,
+        pathLen,
+        oflags,
+        rightsBase,
+        rightsInheriting,
+        fdflags,
+        openedFd,
+      ),
+    path_readlink: (fd, path, pathLen, buf, bufLen, bufused) =>
+      op_node_wasi_path_readlink(
+        context,
+        mem(),
+        fd,
+        path,
+        pathLen,
+        buf,
+        bufLen,
+        bufused,
+      ),
+    path_remove_directory: (fd, path, pathLen) =>
+      op_node_wasi_path_remove_directory(context, mem(), fd, path, pathLen),
+    path_rename: (fd, oldPath, oldPathLen, newFd, newPath, newPathLen) =>
+      op_node_wasi_path_rename(
+        context,
+        mem(),
+        fd,
+        oldPath,
+        oldPathLen,
+        newFd,
+        newPath,
+        newPathLen,
+      ),
+    path_symlink: (oldPath, oldPathLen, fd, newPath, newPathLen) =>
+      op_node_wasi_path_symlink(
+        context,
+        mem(),
+        oldPath,
+        oldPathLen,
+        fd,
+        newPath,
+        newPathLen,
+      ),
+    path_unlink_file: (fd, path, pathLen) =>
+      op_node_wasi_path_unlink_file(context, mem(), fd, path, pathLen),
+    poll_oneoff: (inPtr, outPtr, nsubscriptions, nevents) =>
+      op_node_wasi_poll_oneoff(mem(), inPtr, outPtr, nsubscriptions, nevents),
+    proc_exit: (rval) => {
+      if (returnOnExit) {
+        wasi[kExitCode] = rval;
+        throw kExitCode;
+      }
+      process.exit(rval);
+    },
+    proc_raise: () => ENOSYS,
+    random_get: (buf, bufLen) => op_node_wasi_random_get(mem(), buf, bufLen),
+    sched_yield: () => 0,
+    sock_accept: notSupported,
+    sock_recv: notSupported,
+    sock_send: notSupported,
+    sock_shutdown: notSupported,
+  };
+
+  const setMemory = (value) => {
+    if (!ObjectPrototypeIsPrototypeOf(WebAssemblyMemoryPrototype, value)) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports.memory",
+        "WebAssembly.Memory",
+        value,
+      );
+    }
+    memory = value;
+  };
+
+  return { wasiImport, setMemory };
+}
+
+class WASI {
+  wasiImport: Record<string, (...args: unknown[]) => unknown>;
+
+  constructor(options = {}) {
+    validateObject(options, "options");
+
+    validateString(options.version, "options.version");
+    switch (options.version) {
+      case "unstable":
+        this[kBindingName] = "wasi_unstable";
+        break;
+      case "preview1":
+        this[kBindingName] = "wasi_snapshot_preview1";
+        break;
+      default:
+        throw new ERR_INVALID_ARG_VALUE(
+          "options.version",
+          options.version,
+          "unsupported WASI version",
+        );
+    }
+
+    if (options.args !== undefined) {
+      validateArray(options.args, "options.args");
+    }
+    const args = ArrayPrototypeMap(options.args || [], String);
+
+    const env = [];
+    if (options.env !== undefined) {
+      validateObject(options.env, "options.env");
+      const entries = ObjectEntries(options.env);
+      for (let i = 0; i < entries.length; ++i) {
+        const { 0: key, 1: value } = entries[i];
+        if (value !== undefined) {
+          ArrayPrototypePush(env, `${key}=${value}`);
+        }
+      }
+    }
+
+    const preopens = [];
+    if (options.preopens !== undefined) {
+      validateObject(options.preopens, "options.preopens");
+      const entries = ObjectEntries(options.preopens);
+      for (let i = 0; i < entries.length; ++i) {
+        const { 0: key, 1: value } = entries[i];
+        ArrayPrototypePush(preopens, [String(key), resolve(String(value))]);
+      }
+    }
+
+    const { stdin = 0, stdout = 1, stderr = 2 } = options;
+    validateInt32(stdin, "options.stdin", 0);
+    validateInt32(stdout, "options.stdout", 0);
+    validateInt32(stderr, "options.stderr", 0);
+
+    let returnOnExit = true;
+    if (options.returnOnExit !== undefined) {
+      validateBoolean(options.returnOnExit, "options.returnOnExit");
+      returnOnExit = options.returnOnExit;
+    }
+
+    const context = op_node_wasi_new(args, env, preopens, [
+      stdin,
+      stdout,
+      stderr,
+    ]);
+    const { wasiImport, setMemory } = createWasiImport(
+      this,
+      context,
+      returnOnExit,
+    );
+    this.wasiImport = wasiImport;
+    this[kSetMemory] = setMemory;
+    this[kStarted] = false;
+    this[kExitCode] = 0;
+    this[kInstance] = undefined;
+  }
+
+  finalizeBindings(instance, { memory = instance?.exports?.memory } = {}) {
+    if (this[kStarted]) {
+      throw new ERR_WASI_ALREADY_STARTED();
+    }
+
+    validateObject(instance, "instance");
+    validateObject(instance.exports, "instance.exports");
+
+    this[kSetMemory](memory);
+    this[kInstance] = instance;
+    this[kStarted] = true;
+  }
+
+  // Must not export _initialize, must export _start
+  start(instance) {
+    this.finalizeBindings(instance);
+
+    const { _start, _initialize } = this[kInstance].exports;
+    validateFunction(_start, "instance.exports._start");
+    if (_initialize !== undefined) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports._initialize",
+        "undefined",
+        _initialize,
+      );
+    }
+
+    try {
+      _start();
+    } catch (err) {
+      if (err !== kExitCode) {
+        throw err;
+      }
+    }
+
+    return this[kExitCode];
+  }
+
+  // Must not export _start, may optionally export _initialize
+  initialize(instance) {
+    this.finalizeBindings(instance);
+
+    const { _start, _initialize } = this[kInstance].exports;
+    if (_start !== undefined) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports._start",
+        "undefined",
+        _start,
+      );
+    }
+    if (_initialize !== undefined) {
+      validateFunction(_initialize, "instance.exports._initialize");
+      _initialize();
+    }
+  }
+
+  getImportObject() {
+    return { [this[kBindingName]]: this.wasiImport };
   }
 }
 
-export const WASI = Context;
+export { WASI };
 
 export default { WASI };
@@ -30,8 +30,16 @@ import { BroadcastChannel } from "ext:deno_broadcast_channel/01_broadcast_channe
 import { untransferableSymbol } from "ext:deno_node/internal_binding/util.ts";
 import process from "node:process";
 import { createRequire } from "node:module";
+import { ERR_WORKER_NOT_RUNNING } from "ext:deno_node/internal/errors.ts";
+import { validateObject } from "ext:deno_node/internal/validators.mjs";
+import {
+  heapSnapshotStreamFromChunks,
+  takeHeapSnapshotChunks,
+} from "ext:deno_node/internal/heap_utils.ts";
 
 const {
+  ArrayPrototypeConcat,
+  ArrayPrototypeMap,
   encodeURIComponent,
   Error,
   FunctionPrototypeCall,
@@ -39,7 +47,9 @@ const {
   JSONStringify,
   ObjectHasOwn,
   ObjectPrototypeIsPrototypeOf,
+  PromiseReject,
   PromiseResolve,
+  PromiseWithResolvers,
   SafeMap,
   SafeSet,
   SafeWeakMap,
@@ -69,6 +79,12 @@ function isWorkerOnlineMsg(data: unknown): data is WorkerOnlineMsg {
     (data as { "type": unknown })["type"] === "WORKER_ONLINE";
 }
 
+// Sent over the internal port that every `Worker` shares with its thread,
+// which doesn't keep either of them alive.
+type InternalMsg =
+  | { type: "HEAP_SNAPSHOT_REQUEST"; id: number }
+  | { type: "HEAP_SNAPSHOT"; id: number; chunks: Uint8Array[] };
+
 export interface WorkerOptions {
   // only for typings
   argv?: unknown[];
@@ -99,6 +115,13 @@ class NodeWorker extends EventEmitter {
   #messagePromise = undefined;
   #controlPromise = undefined;
   #workerOnline = false;
+  #internalPort: MessagePort;
+  #heapSnapshotRequests = new SafeMap<
+    number,
+    // deno-lint-ignore no-explicit-any
+    { resolve: (stream: any) => void; reject: (err: Error) => void }
+  >();
+  #nextHeapSnapshotId = 0;
   // "RUNNING" | "CLOSED" | "TERMINATED"
   // "TERMINATED" means that any controls or messages received will be
   // discarded. "CLOSED" means that we have received a control
@@ -159,12 +182,15 @@ class NodeWorker extends EventEmitter {
     if (options?.env) {
       env_ = JSONParse(JSONStringify(options?.env));
     }
+    const { port1: internalPort, port2: workerInternalPort } =
+      new MessageChannel();
     const serializedWorkerMetadata = serializeJsMessageData({
       workerData: options?.workerData,
       environmentData: environmentData,
       env: env_,
       isWorkerThread: true,
-    }, options?.transferList ?? []);
+      internalPort: workerInternalPort,
+    }, ArrayPrototypeConcat(options?.transferList ?? [], [workerInternalPort]));
     const id = op_create_worker(
       {
         // deno-lint-ignore prefer-primordials
@@ -180,6 +206,9 @@ class NodeWorker extends EventEmitter {
     );
     this.#id = id;
     this.threadId = id;
+    this.#internalPort = webMessagePortToNodeMessagePort(internalPort);
+    this.#internalPort.on("message", this.#onInternalMessage);
+    this.#internalPort.on("close", this.#onInternalPortClose);
     this.#pollControl();
     this.#pollMessages();
     process.nextTick(() => process.emit("worker", this));
@@ -209,6 +238,30 @@ class NodeWorker extends EventEmitter {
     }
   }
 
+  #onInternalMessage = (message: InternalMsg) => {
+    if (message.type !== "HEAP_SNAPSHOT") {
+      return;
+    }
+
+    const request = this.#heapSnapshotRequests.get(message.id);
+    if (request === undefined) {
+      return;
+    }
+    this.#heapSnapshotRequests.delete(message.id);
+    if (this.#heapSnapshotRequests.size === 0) {
+      this.#internalPort.unref();
+    }
+    request.resolve(heapSnapshotStreamFromChunks(message.chunks));
+  };
+
+  #onInternalPortClose = () => {
+    // deno-lint-ignore prefer-primordials
+    for (const request of this.#heapSnapshotRequests.values()) {
+      request.reject(new ERR_WORKER_NOT_RUNNING());
+    }
+    this.#heapSnapshotRequests.clear();
+  };
+
   #handleError(err) {
     this.emit("error", err);
   }
@@ -314,6 +367,7 @@ class NodeWorker extends EventEmitter {
     if (this.#status !== "TERMINATED") {
       this.#status = "TERMINATED";
       op_host_terminate_worker(this.#id);
+      this.#internalPort.close();
       this.emit("exit", 0);
     }
     return PromiseResolve(0);
@@ -327,8 +381,28 @@ class NodeWorker extends EventEmitter {
     this[privateWorkerRef](false);
   }
 
-  readonly getHeapSnapshot = () =>
-    notImplemented("Worker.prototype.getHeapSnapshot");
+  // https://nodejs.org/api/worker_threads.html#workergetheapsnapshotoptions
+  getHeapSnapshot(options?: {
+    exposeInternals?: boolean;
+    exposeNumericValues?: boolean;
+  }) {
+    if (options !== undefined) {
+      validateObject(options, "options");
+    }
+    if (this.#status !== "RUNNING") {
+      return PromiseReject(new ERR_WORKER_NOT_RUNNING());
+    }
+
+    const id = this.#nextHeapSnapshotId++;
+    const { promise, resolve, reject } = PromiseWithResolvers();
+    this.#heapSnapshotRequests.set(id, { resolve, reject });
+    // keep the process alive until the worker answers
+    this.#internalPort.ref();
+    this.#internalPort.postMessage(
+      { type: "HEAP_SNAPSHOT_REQUEST", id } satisfies InternalMsg,
+    );
+    return promise;
+  }
   // fake performance
   readonly performance = globalThis.performance;
 }
@@ -408,6 +482,9 @@ internals.__initWorkerThreads = (
       workerData = metadata.workerData;
       environmentData = metadata.environmentData;
       isWorkerThread = metadata.isWorkerThread;
+      if (metadata.internalPort) {
+        serveInternalPort(metadata.internalPort);
+      }
       const env = metadata.env;
       if (env) {
         process.env = env;
@@ -487,6 +564,23 @@ internals.__initWorkerThreads = (
   }
 };
 
+// Answers the requests that the `Worker` in the parent thread sends over the
+// internal port.
+function serveInternalPort(port: MessagePort) {
+  webMessagePortToNodeMessagePort(port);
+  port.on("message", (message: InternalMsg) => {
+    if (message.type !== "HEAP_SNAPSHOT_REQUEST") {
+      return;
+    }
+
+    const chunks = takeHeapSnapshotChunks();
+    port.postMessage(
+      { type: "HEAP_SNAPSHOT", id: message.id, chunks } satisfies InternalMsg,
+      ArrayPrototypeMap(chunks, (chunk) => chunk.buffer),
+    );
+  });
+}
+
 export function getEnvironmentData(key: unknown) {
   return environmentData.get(key);
 }
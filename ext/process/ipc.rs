@@ -160,6 +160,21 @@ impl IpcJsonStreamResource {
     write_half.write_all(msg).await?;
     Ok(())
   }
+
+  /// writes _newline terminated_ JSON message to the IPC pipe, passing `fd`
+  /// to the other end along with it.
+  #[cfg(unix)]
+  pub async fn write_msg_bytes_with_fd(
+    self: Rc<Self>,
+    msg: &[u8],
+    fd: std::os::fd::OwnedFd,
+  ) -> Result<(), io::Error> {
+    use std::os::fd::AsFd;
+
+    let mut write_half = RcRef::map(self, |r| &r.write_half).borrow_mut().await;
+    write_half.write_all_with_fd(msg, fd.as_fd()).await?;
+    Ok(())
+  }
 }
 
 // Initial capacity of the buffered reader and the JSON backing buffer.
@@ -222,10 +237,16 @@ pub enum IpcJsonStreamError {
 // JSON serialization stream over IPC pipe.
 //
 // `\n` is used as a delimiter between messages.
+//
+// File descriptors sent along with the messages (see
+// `IpcJsonStreamResource::write_msg_bytes_with_fd`) are queued up in the
+// order they are received, and can be claimed with `take_fd`.
 pub struct IpcJsonStream {
   pipe: BiPipeRead,
   buffer: Vec<u8>,
   read_buffer: ReadBuffer,
+  #[cfg(unix)]
+  fds: Vec<std::os::fd::OwnedFd>,
 }
 
 impl IpcJsonStream {
@@ -234,15 +255,35 @@ impl IpcJsonStream {
       pipe,
       buffer: Vec::with_capacity(INITIAL_CAPACITY),
       read_buffer: ReadBuffer::new(),
+      #[cfg(unix)]
+      fds: Vec::new(),
+    }
+  }
+
+  /// Takes the oldest received file descriptor that hasn't been claimed yet.
+  #[cfg(unix)]
+  pub fn take_fd(&mut self) -> Option<std::os::fd::OwnedFd> {
+    if self.fds.is_empty() {
+      None
+    } else {
+      Some(self.fds.remove(0))
     }
   }
 
   pub async fn read_msg(
     &mut self,
   ) -> Result<Option<serde_json::Value>, IpcJsonStreamError> {
+    #[cfg(unix)]
+    let reader = &mut FdCollectingPipe {
+      pipe: &mut self.pipe,
+      fds: &mut self.fds,
+    };
+    #[cfg(not(unix))]
+    let reader = &mut self.pipe;
+
     let mut json = None;
     let nread = read_msg_inner(
-      &mut self.pipe,
+      reader,
       &mut self.buffer,
       &mut json,
       &mut self.read_buffer,
@@ -273,6 +314,26 @@ impl IpcJsonStream {
   }
 }
 
+/// Reads from the pipe, collecting the file descriptors that were sent along
+/// with the data.
+#[cfg(unix)]
+struct FdCollectingPipe<'a> {
+  pipe: &'a mut BiPipeRead,
+  fds: &'a mut Vec<std::os::fd::OwnedFd>,
+}
+
+#[cfg(unix)]
+impl AsyncRead for FdCollectingPipe<'_> {
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    let this = &mut *self;
+    Pin::new(&mut *this.pipe).poll_read_with_fds(cx, buf, this.fds)
+  }
+}
+
 pin_project! {
     #[must_use = "futures do nothing unless you `.await` or poll them"]
     struct ReadMsgInner<'a, R: ?Sized> {
@@ -528,6 +589,46 @@ mod tests {
     Ok(())
   }
 
+  #[cfg(unix)]
+  #[tokio::test]
+  async fn unix_ipc_json_with_fd() -> Result<(), Box<dyn std::error::Error>> {
+    let (a, b) = tokio::net::UnixStream::pair()?;
+    let sender = Rc::new(IpcJsonStreamResource::from_stream(
+      a,
+      super::IpcRefTracker::new_test(),
+    ));
+    let receiver = Rc::new(IpcJsonStreamResource::from_stream(
+      b,
+      super::IpcRefTracker::new_test(),
+    ));
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    sender
+      .clone()
+      .write_msg_bytes(&json_to_bytes(json!("no fd")))
+      .await?;
+    sender
+      .write_msg_bytes_with_fd(
+        &json_to_bytes(json!({ "cmd": "NODE_HANDLE" })),
+        listener.into(),
+      )
+      .await?;
+
+    let mut ipc = RcRef::map(receiver, |r| &r.read_half).borrow_mut().await;
+    let msg = ipc.read_msg().await?.unwrap();
+    assert_eq!(msg, json!("no fd"));
+    let msg = ipc.read_msg().await?.unwrap();
+    assert_eq!(msg, json!({ "cmd": "NODE_HANDLE" }));
+
+    let fd = ipc.take_fd().unwrap();
+    assert!(ipc.take_fd().is_none());
+    let received = std::net::TcpListener::from(fd);
+    assert_eq!(received.local_addr()?, addr);
+
+    Ok(())
+  }
+
   #[tokio::test]
   async fn unix_ipc_json_invalid() -> Result<(), Box<dyn std::error::Error>> {
     let (ipc, mut fd2) = pair().await;
@@ -3,6 +3,7 @@ use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Cursor;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use deno_error::JsErrorBox;
@@ -51,6 +52,16 @@ pub enum TlsError {
   #[class("InvalidData")]
   #[error("Unable to decode key")]
   KeyDecode,
+  #[class(inherit)]
+  #[error("Unable to read '{}': {error}", path.display())]
+  ReadFile {
+    path: PathBuf,
+    #[inherit]
+    error: std::io::Error,
+  },
+  #[class(type)]
+  #[error("Client certificates must be provided as a certificate and key")]
+  UnsupportedClientKey,
 }
 
 /// Lazily resolves the root cert store.
@@ -306,7 +317,9 @@ pub fn create_client_config(
         .with_client_auth_cert(cert_chain, private_key.clone_key())
         .expect("invalid client key or certificate"),
       TlsKeys::Null => client_config.with_no_client_auth(),
-      TlsKeys::Resolver(_) => unimplemented!(),
+      TlsKeys::Resolver(_) | TlsKeys::Files(_) => {
+        return Err(TlsError::UnsupportedClientKey);
+      }
     };
 
     add_alpn(&mut client, socket_use);
@@ -339,7 +352,9 @@ pub fn create_client_config(
       .with_client_auth_cert(cert_chain, private_key.clone_key())
       .expect("invalid client key or certificate"),
     TlsKeys::Null => client_config.with_no_client_auth(),
-    TlsKeys::Resolver(_) => unimplemented!(),
+    TlsKeys::Resolver(_) | TlsKeys::Files(_) => {
+      return Err(TlsError::UnsupportedClientKey);
+    }
   };
 
   add_alpn(&mut client, socket_use);
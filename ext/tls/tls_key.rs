@@ -10,6 +10,9 @@
 //! requires polling of the `TlsKeyLookup` lookup queue. The underlying channels that used for
 //! key lookup can handle closing one end of the pair, in which case they will just
 //! attempt to clean up the associated resources.
+//!
+//! Servers can also read their key from files with `TlsKeyFiles`, which are read again
+//! whenever they change.
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -17,13 +20,20 @@ use std::fmt::Debug;
 use std::future::Future;
 use std::future::poll_fn;
 use std::future::ready;
+use std::io::BufReader;
 use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use deno_core::futures::FutureExt;
 use deno_core::futures::future::Either;
 use deno_core::unsync::spawn;
+use deno_core::unsync::spawn_blocking;
 use rustls::ServerConfig;
 use rustls_tokio_stream::ServerConfigProvider;
 use tokio::sync::broadcast;
@@ -32,6 +42,10 @@ use tokio::sync::oneshot;
 use webpki::types::CertificateDer;
 use webpki::types::PrivateKeyDer;
 
+use crate::TlsError;
+use crate::load_certs;
+use crate::load_private_keys;
+
 #[derive(Debug, thiserror::Error)]
 pub enum TlsKeyError {
   #[error(transparent)]
@@ -64,6 +78,7 @@ pub enum TlsKeys {
   Null,
   Static(TlsKey),
   Resolver(TlsKeyResolver),
+  Files(TlsKeyFiles),
 }
 
 pub struct TlsKeysHolder(RefCell<TlsKeys>);
@@ -92,7 +107,7 @@ impl TryInto<Option<TlsKey>> for TlsKeys {
     match self {
       Self::Null => Ok(None),
       Self::Static(key) => Ok(Some(key)),
-      Self::Resolver(_) => Err(self),
+      Self::Resolver(_) | Self::Files(_) => Err(self),
     }
   }
 }
@@ -106,6 +121,112 @@ impl From<Option<TlsKey>> for TlsKeys {
   }
 }
 
+/// How often a server checks whether its key files have changed.
+const TLS_KEY_FILES_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// PEM files containing a certificate chain and its private key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsKeyFiles {
+  pub cert: PathBuf,
+  pub key: PathBuf,
+}
+
+impl TlsKeyFiles {
+  pub fn load(&self) -> Result<TlsKey, TlsError> {
+    let read = |path: &Path| {
+      std::fs::read(path).map_err(|error| TlsError::ReadFile {
+        path: path.to_path_buf(),
+        error,
+      })
+    };
+    let cert = load_certs(&mut BufReader::new(read(&self.cert)?.as_slice()))?;
+    let key = load_private_keys(&read(&self.key)?)?
+      .into_iter()
+      .next()
+      .unwrap();
+    Ok(TlsKey(cert, key))
+  }
+
+  fn modified(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified =
+      |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    (modified(&self.cert), modified(&self.key))
+  }
+
+  fn server_config(
+    &self,
+    alpn: &[Vec<u8>],
+  ) -> Result<Arc<ServerConfig>, TlsError> {
+    let TlsKey(cert, key) = self.load()?;
+    let mut tls_config = ServerConfig::builder()
+      .with_no_client_auth()
+      .with_single_cert(cert, key)?;
+    tls_config.alpn_protocols = alpn.to_vec();
+    Ok(tls_config.into())
+  }
+
+  /// Creates a provider that serves the current contents of the files. A
+  /// background task checks them for changes once per second, so rotated
+  /// certificates are picked up without restarting the server and handshakes
+  /// never wait for the file system. If the new files can't be loaded, the
+  /// previous key keeps being used.
+  pub fn into_server_config_provider(
+    self,
+    alpn: Vec<Vec<u8>>,
+  ) -> Result<ServerConfigProvider, TlsError> {
+    let config = self.watch(alpn, TLS_KEY_FILES_CHECK_INTERVAL)?;
+    Ok(Arc::new(move |_hello| {
+      ready(Ok(config.lock().unwrap().clone())).boxed()
+    }))
+  }
+
+  /// Loads the files and spawns the task that reloads them every `interval`
+  /// when they changed. The task stops once the returned config is dropped.
+  fn watch(
+    self,
+    alpn: Vec<Vec<u8>>,
+    interval: Duration,
+  ) -> Result<Arc<Mutex<Arc<ServerConfig>>>, TlsError> {
+    let mut modified = self.modified();
+    let config = Arc::new(Mutex::new(self.server_config(&alpn)?));
+    let weak_config = Arc::downgrade(&config);
+    spawn(async move {
+      loop {
+        tokio::time::sleep(interval).await;
+        if weak_config.strong_count() == 0 {
+          break;
+        }
+        let files = self.clone();
+        let alpn = alpn.clone();
+        let previous = modified;
+        let Ok((current, reloaded)) = spawn_blocking(move || {
+          let current = files.modified();
+          let reloaded =
+            (current != previous).then(|| files.server_config(&alpn));
+          (current, reloaded)
+        })
+        .await
+        else {
+          break;
+        };
+        modified = current;
+        let Some(config) = weak_config.upgrade() else {
+          break;
+        };
+        match reloaded {
+          Some(Ok(new_config)) => *config.lock().unwrap() = new_config,
+          Some(Err(err)) => log::warn!(
+            "Failed to reload the TLS certificate, the previous one is still used: {}",
+            err
+          ),
+          None => {}
+        }
+      }
+    });
+    Ok(config)
+  }
+}
+
 enum TlsKeyState {
   Resolving(broadcast::Receiver<Result<TlsKey, ErrorType>>),
   Resolved(Result<TlsKey, ErrorType>),
@@ -351,4 +472,45 @@ pub mod tests {
 
     task.await.unwrap();
   }
+
+  #[tokio::test]
+  async fn test_key_files_reload() {
+    let testdata =
+      std::path::PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap())
+        .join("../../tests/testdata/tls");
+    let dir = tempfile::tempdir().unwrap();
+    let files = TlsKeyFiles {
+      cert: dir.path().join("cert.pem"),
+      key: dir.path().join("key.pem"),
+    };
+    let copy_key = |name: &str| {
+      std::fs::copy(testdata.join(format!("{name}.crt")), &files.cert).unwrap();
+      std::fs::copy(testdata.join(format!("{name}.key")), &files.key).unwrap();
+    };
+    let is_current = |config: &Arc<Mutex<Arc<ServerConfig>>>,
+                      expected: &Arc<ServerConfig>| {
+      Arc::ptr_eq(&config.lock().unwrap(), expected)
+    };
+
+    copy_key("localhost");
+    let config = files
+      .clone()
+      .watch(vec![], Duration::from_millis(10))
+      .unwrap();
+    let initial = config.lock().unwrap().clone();
+
+    // A certificate that can't be loaded keeps the previous one.
+    std::fs::write(&files.cert, "invalid").unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(is_current(&config, &initial));
+
+    copy_key("self-signed-hostname");
+    for _ in 0..100 {
+      if !is_current(&config, &initial) {
+        return;
+      }
+      tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the TLS key files were not reloaded");
+  }
 }
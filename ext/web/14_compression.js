@@ -27,6 +27,8 @@ webidl.converters.CompressionFormat = webidl.createEnumConverter(
     "deflate",
     "deflate-raw",
     "gzip",
+    "brotli",
+    "zstd",
   ],
 );
 
@@ -3,6 +3,8 @@
 use std::cell::RefCell;
 use std::io::Write;
 
+use brotli::CompressorWriter as BrotliEncoder;
+use brotli::DecompressorWriter as BrotliDecoder;
 use deno_core::op2;
 use flate2::Compression;
 use flate2::write::DeflateDecoder;
@@ -11,6 +13,18 @@ use flate2::write::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::write::ZlibDecoder;
 use flate2::write::ZlibEncoder;
+use zstd::stream::raw::Decoder as ZstdRawDecoder;
+use zstd::stream::write::Encoder as ZstdEncoder;
+use zstd::stream::zio::Writer as ZstdWriter;
+
+// quality level 6 is based on google's nginx default value for on-the-fly
+// compression, and lgwin 22 is a window size of ~4MB
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 6;
+const BROTLI_LGWIN: u32 = 22;
+
+// the zstd crate picks its default level (3) when given 0
+const ZSTD_LEVEL: i32 = 0;
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum CompressionError {
@@ -38,7 +52,6 @@ impl deno_core::GarbageCollected for CompressionResource {
 }
 
 /// https://wicg.github.io/compression/#supported-formats
-#[derive(Debug)]
 enum Inner {
   DeflateDecoder(ZlibDecoder<Vec<u8>>),
   DeflateEncoder(ZlibEncoder<Vec<u8>>),
@@ -46,6 +59,30 @@ enum Inner {
   DeflateRawEncoder(DeflateEncoder<Vec<u8>>),
   GzDecoder(GzDecoder<Vec<u8>>),
   GzEncoder(GzEncoder<Vec<u8>>),
+  BrotliDecoder(Box<BrotliDecoder<Vec<u8>>>),
+  BrotliEncoder(Box<BrotliEncoder<Vec<u8>>>),
+  // the zio writer is used directly, as unlike `zstd::stream::write::Decoder`
+  // it reports truncated input when finishing
+  ZstdDecoder(ZstdWriter<Vec<u8>, ZstdRawDecoder<'static>>),
+  ZstdEncoder(ZstdEncoder<'static, Vec<u8>>),
+}
+
+impl std::fmt::Debug for Inner {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let name = match self {
+      Inner::DeflateDecoder(_) => "DeflateDecoder",
+      Inner::DeflateEncoder(_) => "DeflateEncoder",
+      Inner::DeflateRawDecoder(_) => "DeflateRawDecoder",
+      Inner::DeflateRawEncoder(_) => "DeflateRawEncoder",
+      Inner::GzDecoder(_) => "GzDecoder",
+      Inner::GzEncoder(_) => "GzEncoder",
+      Inner::BrotliDecoder(_) => "BrotliDecoder",
+      Inner::BrotliEncoder(_) => "BrotliEncoder",
+      Inner::ZstdDecoder(_) => "ZstdDecoder",
+      Inner::ZstdEncoder(_) => "ZstdEncoder",
+    };
+    f.write_str(name)
+  }
 }
 
 #[op2]
@@ -68,6 +105,22 @@ pub fn op_compression_new(
     ("gzip", false) => {
       Inner::GzEncoder(GzEncoder::new(w, Compression::default()))
     }
+    ("brotli", true) => {
+      Inner::BrotliDecoder(Box::new(BrotliDecoder::new(w, BROTLI_BUFFER_SIZE)))
+    }
+    ("brotli", false) => Inner::BrotliEncoder(Box::new(BrotliEncoder::new(
+      w,
+      BROTLI_BUFFER_SIZE,
+      BROTLI_QUALITY,
+      BROTLI_LGWIN,
+    ))),
+    ("zstd", true) => Inner::ZstdDecoder(ZstdWriter::new(
+      w,
+      ZstdRawDecoder::new().map_err(CompressionError::Io)?,
+    )),
+    ("zstd", false) => Inner::ZstdEncoder(
+      ZstdEncoder::new(w, ZSTD_LEVEL).map_err(CompressionError::Io)?,
+    ),
     _ => return Err(CompressionError::UnsupportedFormat),
   };
   Ok(CompressionResource(RefCell::new(Some(inner))))
@@ -112,6 +165,26 @@ pub fn op_compression_write(
       d.flush().map_err(CompressionError::Io)?;
       d.get_mut().drain(..)
     }
+    Inner::BrotliDecoder(d) => {
+      d.write_all(input).map_err(CompressionError::IoTypeError)?;
+      d.flush().map_err(CompressionError::Io)?;
+      d.get_mut().drain(..)
+    }
+    Inner::BrotliEncoder(d) => {
+      d.write_all(input).map_err(CompressionError::IoTypeError)?;
+      d.flush().map_err(CompressionError::Io)?;
+      d.get_mut().drain(..)
+    }
+    Inner::ZstdDecoder(d) => {
+      d.write_all(input).map_err(CompressionError::IoTypeError)?;
+      d.flush().map_err(CompressionError::Io)?;
+      d.writer_mut().drain(..)
+    }
+    Inner::ZstdEncoder(d) => {
+      d.write_all(input).map_err(CompressionError::IoTypeError)?;
+      d.flush().map_err(CompressionError::Io)?;
+      d.get_mut().drain(..)
+    }
   }
   .collect();
   Ok(out)
@@ -143,6 +216,18 @@ pub fn op_compression_finish(
     }
     Inner::GzDecoder(d) => d.finish().map_err(CompressionError::IoTypeError),
     Inner::GzEncoder(d) => d.finish().map_err(CompressionError::IoTypeError),
+    Inner::BrotliDecoder(d) => d.into_inner().map_err(|_| {
+      CompressionError::IoTypeError(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Invalid Data",
+      ))
+    }),
+    Inner::BrotliEncoder(d) => Ok(d.into_inner()),
+    Inner::ZstdDecoder(mut d) => d
+      .finish()
+      .map(|_| d.into_inner().0)
+      .map_err(CompressionError::IoTypeError),
+    Inner::ZstdEncoder(d) => d.finish().map_err(CompressionError::IoTypeError),
   };
   match out {
     Err(err) => {
@@ -29,6 +29,7 @@ use url::Url;
 
 use crate::UrlToFilePathError;
 use crate::glob::FilePatterns;
+use crate::glob::PathOrPattern;
 use crate::glob::PathOrPatternSet;
 use crate::import_map::imports_values;
 use crate::import_map::scope_values;
@@ -83,6 +84,17 @@ pub enum IntoResolvedErrorKind {
   #[class(inherit)]
   #[error(transparent)]
   UndefinedPermission(#[from] UndefinedPermissionError),
+  #[class(inherit)]
+  #[error("Invalid coverage threshold pattern: {0}")]
+  InvalidCoverageThresholdPattern(crate::glob::PathOrPatternParseError),
+  #[class(type)]
+  #[error(
+    "Coverage threshold for {name} must be a percentage between 0 and 100, but got {value}"
+  )]
+  InvalidCoverageThreshold { name: &'static str, value: f64 },
+  #[class(type)]
+  #[error("\"cert\" and \"key\" must be specified together")]
+  ServeCertWithoutKey,
 }
 
 #[derive(Debug, Error, JsError)]
@@ -749,20 +761,44 @@ pub struct TaskDefinition {
   pub dependencies: Vec<String>,
   #[serde(default)]
   pub description: Option<String>,
+  /// Files, directories or globs the task reads from. When specified, the
+  /// task is cached based on their contents.
+  #[serde(default)]
+  pub inputs: Vec<String>,
+  /// Files, directories or globs the task writes to. These are stored in
+  /// the cache and restored when the task is skipped.
+  #[serde(default)]
+  pub outputs: Vec<String>,
+  /// Names of environment variables that affect the task's result.
+  #[serde(default)]
+  pub env: Vec<String>,
 }
 
 #[cfg(test)]
 impl From<&str> for TaskDefinition {
   fn from(value: &str) -> Self {
+    Self::from_command(value.to_string())
+  }
+}
+
+impl TaskDefinition {
+  pub fn from_command(command: String) -> Self {
     Self {
-      command: Some(value.to_string()),
-      dependencies: vec![],
+      command: Some(command),
+      dependencies: Vec::new(),
       description: None,
+      inputs: Vec::new(),
+      outputs: Vec::new(),
+      env: Vec::new(),
     }
   }
-}
 
-impl TaskDefinition {
+  /// Whether the task declares inputs and can be skipped when they
+  /// haven't changed.
+  pub fn is_cacheable(&self) -> bool {
+    self.command.is_some() && !self.inputs.is_empty()
+  }
+
   pub fn deserialize_tasks<'de, D>(
     deserializer: D,
   ) -> Result<IndexMap<String, TaskDefinition>, D::Error>
@@ -793,11 +829,9 @@ impl TaskDefinition {
           access.next_entry::<String, serde_json::Value>()?
         {
           let task_def = match value {
-            serde_json::Value::String(command) => TaskDefinition {
-              command: Some(command),
-              dependencies: Vec::new(),
-              description: None,
-            },
+            serde_json::Value::String(command) => {
+              TaskDefinition::from_command(command)
+            }
             serde_json::Value::Object(_) => {
               serde_json::from_value(value).map_err(serde::de::Error::custom)?
             }
@@ -955,6 +989,154 @@ pub struct DeployConfig {
   pub app: String,
 }
 
+/// Minimum coverage percentages enforced by `deno coverage`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoverageThresholds {
+  pub lines: Option<f64>,
+  pub branches: Option<f64>,
+  pub functions: Option<f64>,
+}
+
+impl CoverageThresholds {
+  pub fn is_empty(&self) -> bool {
+    self.lines.is_none() && self.branches.is_none() && self.functions.is_none()
+  }
+
+  /// Returns these thresholds with the ones set in `other` taking precedence.
+  pub fn merge(self, other: &CoverageThresholds) -> CoverageThresholds {
+    CoverageThresholds {
+      lines: other.lines.or(self.lines),
+      branches: other.branches.or(self.branches),
+      functions: other.functions.or(self.functions),
+    }
+  }
+
+  fn validate(&self) -> Result<(), IntoResolvedErrorKind> {
+    for (name, value) in [
+      ("lines", self.lines),
+      ("branches", self.branches),
+      ("functions", self.functions),
+    ] {
+      if let Some(value) = value
+        && !(0.0..=100.0).contains(&value)
+      {
+        return Err(IntoResolvedErrorKind::InvalidCoverageThreshold {
+          name,
+          value,
+        });
+      }
+    }
+    Ok(())
+  }
+}
+
+/// `coverage.thresholds` config representation for serde. Any key other than
+/// `lines`, `branches` and `functions` is a path or glob with its own
+/// thresholds.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+struct SerializedCoverageThresholds {
+  pub lines: Option<f64>,
+  pub branches: Option<f64>,
+  pub functions: Option<f64>,
+  #[serde(flatten)]
+  pub globs: IndexMap<String, CoverageThresholds>,
+}
+
+/// `coverage` config representation for serde
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct SerializedCoverageConfig {
+  pub thresholds: SerializedCoverageThresholds,
+}
+
+impl SerializedCoverageConfig {
+  pub fn into_resolved(
+    self,
+    config_file_specifier: &Url,
+  ) -> Result<CoverageConfig, IntoResolvedError> {
+    let config_dir = url_to_file_path(&url_parent(config_file_specifier))?;
+    let thresholds = CoverageThresholds {
+      lines: self.thresholds.lines,
+      branches: self.thresholds.branches,
+      functions: self.thresholds.functions,
+    };
+    thresholds.validate()?;
+    let mut file_thresholds = Vec::with_capacity(self.thresholds.globs.len());
+    for (pattern, thresholds) in self.thresholds.globs {
+      thresholds.validate()?;
+      let path_or_pattern = PathOrPattern::from_relative(&config_dir, &pattern)
+        .map_err(IntoResolvedErrorKind::InvalidCoverageThresholdPattern)?;
+      file_thresholds.push(CoverageFileThresholds {
+        pattern,
+        path_or_pattern,
+        thresholds,
+      });
+    }
+    Ok(CoverageConfig {
+      thresholds,
+      file_thresholds,
+    })
+  }
+}
+
+/// Thresholds for the files matching a path or glob.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoverageFileThresholds {
+  /// The path or glob as written in the config file.
+  pub pattern: String,
+  pub path_or_pattern: PathOrPattern,
+  pub thresholds: CoverageThresholds,
+}
+
+/// `serve` config representation for serde
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct SerializedServeConfig {
+  pub cert: Option<String>,
+  pub key: Option<String>,
+}
+
+impl SerializedServeConfig {
+  pub fn into_resolved(
+    self,
+    config_file_specifier: &Url,
+  ) -> Result<ServeConfig, IntoResolvedError> {
+    let config_dir = url_to_file_path(&url_parent(config_file_specifier))?;
+    let tls = match (self.cert, self.key) {
+      (Some(cert), Some(key)) => Some(ServeTlsConfig {
+        cert: config_dir.join(cert),
+        key: config_dir.join(key),
+      }),
+      (None, None) => None,
+      _ => return Err(IntoResolvedErrorKind::ServeCertWithoutKey.into_box()),
+    };
+    Ok(ServeConfig { tls })
+  }
+}
+
+/// Paths of the PEM encoded certificate chain and private key that
+/// `deno serve` uses to serve HTTPS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServeTlsConfig {
+  pub cert: PathBuf,
+  pub key: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServeConfig {
+  pub tls: Option<ServeTlsConfig>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageConfig {
+  pub thresholds: CoverageThresholds,
+  /// Files matching one of these are checked against its thresholds instead
+  /// of the global ones, in declaration order.
+  pub file_thresholds: Vec<CoverageFileThresholds>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfigFileJson {
@@ -976,6 +1158,8 @@ pub struct ConfigFileJson {
   pub permissions: Option<Value>,
   pub publish: Option<Value>,
   pub deploy: Option<Value>,
+  pub coverage: Option<Value>,
+  pub serve: Option<Value>,
 
   pub name: Option<String>,
   pub version: Option<String>,
@@ -1922,6 +2106,52 @@ impl ConfigFile {
     }
   }
 
+  pub fn to_coverage_config(
+    &self,
+  ) -> Result<Option<CoverageConfig>, ToInvalidConfigError> {
+    match self.json.coverage.clone() {
+      Some(config) => {
+        let serialized: SerializedCoverageConfig =
+          serde_json::from_value(config).map_err(|error| {
+            ToInvalidConfigError::Parse {
+              config: "coverage",
+              source: error,
+            }
+          })?;
+        serialized
+          .into_resolved(&self.specifier)
+          .map(Some)
+          .map_err(|error| ToInvalidConfigError::InvalidConfig {
+            config: "coverage",
+            source: error,
+          })
+      }
+      None => Ok(None),
+    }
+  }
+
+  pub fn to_serve_config(
+    &self,
+  ) -> Result<Option<ServeConfig>, ToInvalidConfigError> {
+    match self.json.serve.clone() {
+      Some(config) => {
+        let serialized: SerializedServeConfig = serde_json::from_value(config)
+          .map_err(|error| ToInvalidConfigError::Parse {
+            config: "serve",
+            source: error,
+          })?;
+        serialized
+          .into_resolved(&self.specifier)
+          .map(Some)
+          .map_err(|error| ToInvalidConfigError::InvalidConfig {
+            config: "serve",
+            source: error,
+          })
+      }
+      None => Ok(None),
+    }
+  }
+
   pub fn resolve_tasks_config(
     &self,
   ) -> Result<IndexMap<String, TaskDefinition>, ResolveTaskConfigError> {
@@ -2132,6 +2362,12 @@ mod tests {
           "description": "Build client project",
           "command": "deno run -A client.js",
           "dependencies": ["build"]
+        },
+        "codegen": {
+          "command": "deno run -A codegen.ts",
+          "inputs": ["schema/**/*.json", "codegen.ts"],
+          "outputs": ["gen/"],
+          "env": ["CODEGEN_TARGET"]
         }
       },
       "unstable": ["kv", "ffi"]
@@ -2215,7 +2451,21 @@ mod tests {
       TaskDefinition {
         description: Some("Build client project".to_string()),
         command: Some("deno run -A client.js".to_string()),
-        dependencies: vec!["build".to_string()]
+        dependencies: vec!["build".to_string()],
+        inputs: vec![],
+        outputs: vec![],
+        env: vec![],
+      }
+    );
+    assert_eq!(
+      tasks_config["codegen"],
+      TaskDefinition {
+        description: None,
+        command: Some("deno run -A codegen.ts".to_string()),
+        dependencies: vec![],
+        inputs: vec!["schema/**/*.json".to_string(), "codegen.ts".to_string()],
+        outputs: vec!["gen/".to_string()],
+        env: vec!["CODEGEN_TARGET".to_string()],
       }
     );
 
@@ -2364,6 +2614,87 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_parse_config_coverage() {
+    let config_text = r#"{
+      "coverage": {
+        "thresholds": {
+          "lines": 80,
+          "branches": 70.5,
+          "src/legacy/**/*.ts": { "lines": 50 }
+        }
+      }
+    }"#;
+    let config_specifier = Url::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+
+    let coverage_config = config_file.to_coverage_config().unwrap().unwrap();
+    assert_eq!(
+      coverage_config.thresholds,
+      CoverageThresholds {
+        lines: Some(80.0),
+        branches: Some(70.5),
+        functions: None,
+      }
+    );
+    assert_eq!(coverage_config.file_thresholds.len(), 1);
+    let file_thresholds = &coverage_config.file_thresholds[0];
+    assert_eq!(file_thresholds.pattern, "src/legacy/**/*.ts");
+    assert_eq!(
+      file_thresholds.thresholds,
+      CoverageThresholds {
+        lines: Some(50.0),
+        ..Default::default()
+      }
+    );
+    assert_eq!(
+      file_thresholds
+        .path_or_pattern
+        .matches_path(Path::new("/deno/src/legacy/a/b.ts")),
+      crate::glob::PathGlobMatch::Matched
+    );
+
+    for config_text in [
+      r#"{ "coverage": { "thresholds": { "lines": 101 } } }"#,
+      r#"{ "coverage": { "thresholds": { "src/": { "line": 50 } } } }"#,
+      r#"{ "coverage": { "threshold": {} } }"#,
+    ] {
+      let config_specifier = Url::parse("file:///deno/deno.json").unwrap();
+      let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+      assert!(config_file.to_coverage_config().is_err(), "{config_text}");
+    }
+  }
+
+  #[test]
+  fn test_parse_config_serve() {
+    let config_text = r#"{
+      "serve": {
+        "cert": "./certs/cert.pem",
+        "key": "/etc/ssl/key.pem"
+      }
+    }"#;
+    let config_specifier = Url::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+
+    let serve_config = config_file.to_serve_config().unwrap().unwrap();
+    assert_eq!(
+      serve_config.tls,
+      Some(ServeTlsConfig {
+        cert: PathBuf::from("/deno/certs/cert.pem"),
+        key: PathBuf::from("/etc/ssl/key.pem"),
+      })
+    );
+
+    for config_text in [
+      r#"{ "serve": { "cert": "cert.pem" } }"#,
+      r#"{ "serve": { "certFile": "cert.pem" } }"#,
+    ] {
+      let config_specifier = Url::parse("file:///deno/deno.json").unwrap();
+      let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+      assert!(config_file.to_serve_config().is_err(), "{config_text}");
+    }
+  }
+
   #[test]
   fn test_parse_config_with_global_files_only() {
     let config_text = r#"{
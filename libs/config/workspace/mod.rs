@@ -47,6 +47,7 @@ use crate::deno_json::ConfigFile;
 use crate::deno_json::ConfigFileError;
 use crate::deno_json::ConfigFileRc;
 use crate::deno_json::ConfigFileReadError;
+use crate::deno_json::CoverageConfig;
 use crate::deno_json::DeployConfig;
 use crate::deno_json::FmtConfig;
 use crate::deno_json::FmtOptionsConfig;
@@ -57,6 +58,7 @@ use crate::deno_json::NodeModulesDirParseError;
 use crate::deno_json::PermissionsConfig;
 use crate::deno_json::PermissionsObjectWithBase;
 use crate::deno_json::PublishConfig;
+use crate::deno_json::ServeConfig;
 pub use crate::deno_json::TaskDefinition;
 use crate::deno_json::TestConfig;
 use crate::deno_json::ToInvalidConfigError;
@@ -2227,6 +2229,44 @@ impl WorkspaceDirectory {
     Ok(config)
   }
 
+  pub fn to_coverage_config(
+    &self,
+  ) -> Result<Option<CoverageConfig>, ToInvalidConfigError> {
+    let config = if let Some(deno_json) = self.deno_json.as_ref() {
+      if let Some(config) = deno_json.member.to_coverage_config()? {
+        Some(config)
+      } else {
+        match &deno_json.root {
+          Some(root) => root.to_coverage_config()?,
+          None => None,
+        }
+      }
+    } else {
+      None
+    };
+
+    Ok(config)
+  }
+
+  pub fn to_serve_config(
+    &self,
+  ) -> Result<Option<ServeConfig>, ToInvalidConfigError> {
+    let config = if let Some(deno_json) = self.deno_json.as_ref() {
+      if let Some(config) = deno_json.member.to_serve_config()? {
+        Some(config)
+      } else {
+        match &deno_json.root {
+          Some(root) => root.to_serve_config()?,
+          None => None,
+        }
+      }
+    } else {
+      None
+    };
+
+    Ok(config)
+  }
+
   /// Removes any "include" patterns from the root files that have
   /// a base in another workspace member.
   fn exclude_includes_with_member_for_base_for_root(
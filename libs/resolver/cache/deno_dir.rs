@@ -120,6 +120,18 @@ impl<TSys: DiskCacheSys> DenoDir<TSys> {
     self.root.join("check_cache_v2")
   }
 
+  /// Folder used for storing the outputs of cached tasks.
+  pub fn task_cache_folder_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
+  /// Folder used for storing the results saved with
+  /// `deno bench --save-baseline`.
+  pub fn bench_baselines_folder_path(&self) -> PathBuf {
+    self.root.join("bench_baselines")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
@@ -208,6 +208,14 @@ pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
     config_option: ConfigFileOption::SameAsFlagName,
     env_var: None,
   },
+  UnstableFeatureDescription {
+    name: "webcrypto",
+    help_text: "Enable unstable WebCrypto algorithms",
+    show_in_help: true,
+    kind: UnstableFeatureKind::Runtime,
+    config_option: ConfigFileOption::SameAsFlagName,
+    env_var: None,
+  },
   UnstableFeatureDescription {
     name: "webgpu",
     help_text: "Enable unstable WebGPU APIs",
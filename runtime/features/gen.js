@@ -23,6 +23,7 @@ export const unstableIds = {
   temporal: 21,
   unsafeProto: 22,
   vsock: 23,
-  webgpu: 24,
-  workerOptions: 25,
+  webcrypto: 24,
+  webgpu: 25,
+  workerOptions: 26,
 };
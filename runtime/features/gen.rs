@@ -223,12 +223,21 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "vsock",
   },
+  UnstableFeatureDefinition {
+    name: "webcrypto",
+    flag_name: "unstable-webcrypto",
+    help_text: "Enable unstable WebCrypto algorithms",
+    show_in_help: true,
+    id: 24,
+    kind: UnstableFeatureKind::Runtime,
+    config_file_option: "webcrypto",
+  },
   UnstableFeatureDefinition {
     name: "webgpu",
     flag_name: "unstable-webgpu",
     help_text: "Enable unstable WebGPU APIs",
     show_in_help: true,
-    id: 24,
+    id: 25,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "webgpu",
   },
@@ -237,7 +246,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-worker-options",
     help_text: "Enable unstable Web Worker APIs",
     show_in_help: true,
-    id: 25,
+    id: 26,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "worker-options",
   },
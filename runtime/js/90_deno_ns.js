@@ -223,6 +223,8 @@ ObjectDefineProperties(denoNsUnstableById[unstableIds.net], {
 
 // denoNsUnstableById[unstableIds.unsafeProto] = { __proto__: null }
 
+// denoNsUnstableById[unstableIds.webcrypto] = { __proto__: null }
+
 denoNsUnstableById[unstableIds.webgpu] = {
   UnsafeWindowSurface: webgpuSurface.UnsafeWindowSurface,
 };
@@ -39,6 +39,7 @@ import * as webgpuSurface from "ext:deno_webgpu/02_surface.js";
 import { unstableIds } from "ext:runtime/90_deno_ns.js";
 
 const loadImage = core.createLazyLoader("ext:deno_canvas/01_image.js");
+const loadCanvas = core.createLazyLoader("ext:deno_canvas/02_canvas.js");
 const loadWebTransport = core.createLazyLoader("ext:deno_web/webtransport.js");
 
 // https://developer.mozilla.org/en-US/docs/Web/API/WindowOrWorkerGlobalScope
@@ -46,6 +47,10 @@ const windowOrWorkerGlobalScope = {
   AbortController: core.propNonEnumerable(abortSignal.AbortController),
   AbortSignal: core.propNonEnumerable(abortSignal.AbortSignal),
   Blob: core.propNonEnumerable(file.Blob),
+  CanvasGradient: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.CanvasGradient,
+    loadCanvas,
+  ),
   ByteLengthQueuingStrategy: core.propNonEnumerable(
     streams.ByteLengthQueuingStrategy,
   ),
@@ -63,6 +68,14 @@ const windowOrWorkerGlobalScope = {
   EventTarget: core.propNonEnumerable(event.EventTarget),
   File: core.propNonEnumerable(file.File),
   FileReader: core.propNonEnumerable(fileReader.FileReader),
+  FontFace: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.FontFace,
+    loadCanvas,
+  ),
+  FontFaceSet: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.FontFaceSet,
+    loadCanvas,
+  ),
   FormData: core.propNonEnumerable(formData.FormData),
   Headers: core.propNonEnumerable(headers.Headers),
   ImageData: core.propNonEnumerable(imageData.ImageData),
@@ -71,6 +84,14 @@ const windowOrWorkerGlobalScope = {
     loadImage,
   ),
   MessageEvent: core.propNonEnumerable(event.MessageEvent),
+  OffscreenCanvas: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.OffscreenCanvas,
+    loadCanvas,
+  ),
+  OffscreenCanvasRenderingContext2D: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.OffscreenCanvasRenderingContext2D,
+    loadCanvas,
+  ),
   Performance: core.propNonEnumerable(performance.Performance),
   PerformanceEntry: core.propNonEnumerable(performance.PerformanceEntry),
   PerformanceMark: core.propNonEnumerable(performance.PerformanceMark),
@@ -87,6 +108,10 @@ const windowOrWorkerGlobalScope = {
   TextEncoder: core.propNonEnumerable(encoding.TextEncoder),
   TextDecoderStream: core.propNonEnumerable(encoding.TextDecoderStream),
   TextEncoderStream: core.propNonEnumerable(encoding.TextEncoderStream),
+  TextMetrics: core.propNonEnumerableLazyLoaded(
+    (canvas) => canvas.TextMetrics,
+    loadCanvas,
+  ),
   TransformStream: core.propNonEnumerable(streams.TransformStream),
   URL: core.propNonEnumerable(url.URL),
   URLPattern: core.propNonEnumerable(urlPattern.URLPattern),
@@ -131,6 +156,11 @@ const windowOrWorkerGlobalScope = {
     get: caches.cacheStorage,
   },
   CacheStorage: core.propNonEnumerable(caches.CacheStorage),
+  fonts: {
+    enumerable: false,
+    configurable: true,
+    get: () => loadCanvas().fonts,
+  },
   Cache: core.propNonEnumerable(caches.Cache),
   console: core.propNonEnumerable(
     new console.Console((msg, level) => core.print(msg, level > 1)),
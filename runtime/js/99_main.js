@@ -11,6 +11,7 @@ import {
   op_bootstrap_is_from_unconfigured_runtime,
   op_bootstrap_no_color,
   op_bootstrap_pid,
+  op_bootstrap_serve_tls_keys,
   op_bootstrap_stderr_no_color,
   op_bootstrap_stdout_no_color,
   op_get_ext_import_meta_proto,
@@ -816,6 +817,7 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
       13: otelConfig,
       15: standalone,
       16: autoServe,
+      17: serveTls,
     } = runtimeOptions;
 
     denoNs.build.standalone = standalone;
@@ -887,6 +889,9 @@ function bootstrapMainRuntime(runtimeOptions, warmup = false) {
             serve({
               servePort,
               serveHost,
+              serveTlsKeys: serveTls
+                ? op_bootstrap_serve_tls_keys()
+                : undefined,
               workerCountWhenMain: serveIsMain_
                 ? serveWorkerCountOrIndex_
                 : undefined,
@@ -3,6 +3,8 @@
 use deno_core::OpState;
 use deno_core::op2;
 use deno_terminal::colors::ColorLevel;
+use deno_tls::TlsKeys;
+use deno_tls::TlsKeysHolder;
 use serde::Serialize;
 
 use crate::BootstrapOptions;
@@ -22,6 +24,7 @@ deno_core::extension!(
     op_bootstrap_stderr_no_color,
     op_bootstrap_unstable_args,
     op_bootstrap_is_from_unconfigured_runtime,
+    op_bootstrap_serve_tls_keys,
     op_snapshot_options,
   ],
   options = {
@@ -168,3 +171,12 @@ pub fn op_bootstrap_stderr_no_color(_state: &mut OpState) -> bool {
 pub fn op_bootstrap_is_from_unconfigured_runtime(state: &mut OpState) -> bool {
   state.borrow::<IsFromUnconfiguredRuntime>().0
 }
+
+/// The key of the server started by `deno serve`, which is read from the
+/// files passed to it and reloaded when they change.
+#[op2]
+#[cppgc]
+pub fn op_bootstrap_serve_tls_keys(state: &mut OpState) -> TlsKeysHolder {
+  let files = state.borrow::<BootstrapOptions>().serve_tls.clone();
+  TlsKeysHolder::from(files.map(TlsKeys::Files).unwrap_or_default())
+}
@@ -37,7 +37,6 @@ use deno_core::serde::Deserialize;
 use deno_core::serde::Serialize;
 use deno_core::serde_json::json;
 use deno_core::v8;
-use deno_cron::local::LocalCronHandler;
 use deno_error::JsErrorClass;
 use deno_fs::FileSystem;
 use deno_io::Stdio;
@@ -70,6 +69,7 @@ use crate::worker::FormatJsErrorFn;
 use crate::worker::MEMORY_TRIM_HANDLER_ENABLED;
 #[cfg(target_os = "linux")]
 use crate::worker::SIGUSR2_RX;
+use crate::worker::create_cron_handler;
 use crate::worker::create_op_metrics;
 use crate::worker::create_validate_import_attributes_callback;
 
@@ -404,6 +404,9 @@ pub struct WebWorkerOptions {
   pub format_js_error_fn: Option<Arc<FormatJsErrorFn>>,
   pub worker_type: WorkerThreadType,
   pub cache_storage_dir: Option<std::path::PathBuf>,
+  /// Directory for the state of the origin of the worker. Only used to
+  /// coordinate `Deno.cron` executions with other workers of the origin.
+  pub origin_storage_dir: Option<std::path::PathBuf>,
   pub stdio: Stdio,
   pub trace_ops: Option<Vec<String>>,
   pub close_on_idle: bool,
@@ -572,7 +575,9 @@ impl WebWorker {
         ),
         deno_kv::KvConfig::builder().build(),
       ),
-      deno_cron::deno_cron::init(LocalCronHandler::new()),
+      deno_cron::deno_cron::init(create_cron_handler(
+        options.origin_storage_dir.as_deref(),
+      )),
       deno_napi::deno_napi::init::<PermissionsContainer>(
         services.deno_rt_native_addon_loader.clone(),
       ),
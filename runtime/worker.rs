@@ -1,6 +1,7 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 #[cfg(target_os = "linux")]
@@ -35,6 +36,8 @@ use deno_core::error::CoreError;
 use deno_core::error::JsError;
 use deno_core::merge_op_metrics;
 use deno_core::v8;
+use deno_cron::coordinated::CoordinatedCronHandler;
+use deno_cron::lease::SqliteCronLeaseStore;
 use deno_cron::local::LocalCronHandler;
 use deno_fs::FileSystem;
 use deno_io::Stdio;
@@ -292,6 +295,24 @@ impl Default for WorkerOptions {
   }
 }
 
+/// Creates the `Deno.cron` handler of a worker. Workers that share
+/// `origin_storage_dir`, in this or other processes, run every execution of
+/// a cron once. Without it, only the workers of the current process are
+/// coordinated.
+pub fn create_cron_handler(
+  origin_storage_dir: Option<&Path>,
+) -> CoordinatedCronHandler<SqliteCronLeaseStore> {
+  let cron_dir = origin_storage_dir.map(|dir| dir.join("cron"));
+  let local = cron_dir
+    .clone()
+    .map(LocalCronHandler::with_state_dir)
+    .unwrap_or_default();
+  CoordinatedCronHandler::new(
+    local,
+    SqliteCronLeaseStore::new(cron_dir.map(|dir| dir.join("leases.sqlite3"))),
+  )
+}
+
 pub fn create_op_metrics(
   enable_op_summary_metrics: bool,
   trace_ops: Option<Vec<String>>,
@@ -303,6 +324,17 @@ pub fn create_op_metrics(
   let mut op_metrics_factory_fn: Option<OpMetricsFactoryFn> = None;
   let now = Instant::now();
   let max_len: Rc<std::cell::Cell<usize>> = Default::default();
+  // With the `deno.ops` trace event category enabled, ops are recorded in the
+  // trace file instead of being printed, even without `--strace-ops`. Ops are
+  // instrumented when the worker is created, so the category has to be
+  // enabled with `NODE_OPTIONS` rather than by `node:trace_events`.
+  let trace_ops_events =
+    deno_node::ops::trace_events::is_category_enabled("deno.ops");
+  let trace_ops = match trace_ops {
+    Some(patterns) => Some(patterns),
+    None if trace_ops_events => Some(vec![]),
+    None => None,
+  };
   if let Some(patterns) = trace_ops {
     /// Match an op name against a list of patterns
     fn matches_pattern(patterns: &[String], name: &str) -> bool {
@@ -329,6 +361,21 @@ pub fn create_op_metrics(
         return None;
       }
 
+      if trace_ops_events {
+        return Some(Rc::new(
+          move |op: &deno_core::_ops::OpCtx, event, source| {
+            deno_node::ops::trace_events::add_instant_event(
+              "deno.ops",
+              op.decl().name,
+              deno_core::serde_json::json!({
+                "event": format!("{event:?}"),
+                "source": format!("{source:?}"),
+              }),
+            );
+          },
+        ));
+      }
+
       max_len.set(max_len.get().max(decl.name.len()));
       let max_len = max_len.clone();
       Some(Rc::new(
@@ -582,6 +629,9 @@ impl MainWorker {
           ),
           deno_kv::KvConfig::builder().build(),
         ),
+        deno_cron::deno_cron::args(create_cron_handler(
+          options.origin_storage_dir.as_deref(),
+        )),
         deno_napi::deno_napi::args::<PermissionsContainer>(
           services.deno_rt_native_addon_loader.clone(),
         ),
@@ -751,6 +801,9 @@ impl MainWorker {
       if let Some(node_ipc_fd) = options.node_ipc_fd {
         state.put(deno_node::ChildPipeFd(node_ipc_fd));
       }
+      state.put(deno_node::ChildPermissionArgs(
+        options.permission_args.clone(),
+      ));
     }
 
     let scope = &mut self.js_runtime.handle_scope();
@@ -1065,7 +1118,9 @@ fn common_extensions<
     deno_net::deno_net::lazy_init::<PermissionsContainer>(),
     deno_tls::deno_tls::init(),
     deno_kv::deno_kv::lazy_init::<MultiBackendDbHandler>(),
-    deno_cron::deno_cron::init(LocalCronHandler::new()),
+    deno_cron::deno_cron::lazy_init::<
+      CoordinatedCronHandler<SqliteCronLeaseStore>,
+    >(),
     deno_napi::deno_napi::lazy_init::<PermissionsContainer>(),
     deno_http::deno_http::lazy_init(),
     deno_io::deno_io::lazy_init(),
@@ -7,6 +7,7 @@ use deno_core::ModuleSpecifier;
 use deno_core::v8;
 use deno_telemetry::OtelConfig;
 use deno_terminal::colors;
+use deno_tls::TlsKeyFiles;
 use serde::Serialize;
 
 /// The execution mode for this worker. Some modes may have implicit behaviour.
@@ -110,11 +111,15 @@ pub struct BootstrapOptions {
   pub argv0: Option<String>,
   pub node_debug: Option<String>,
   pub node_ipc_fd: Option<i64>,
+  /// The permission flags of the process, passed on to the `deno`
+  /// subprocesses started by `run()` of `node:test`.
+  pub permission_args: Vec<String>,
   pub mode: WorkerExecutionMode,
   pub no_legacy_abort: bool,
   // Used by `deno serve`
   pub serve_port: Option<u16>,
   pub serve_host: Option<String>,
+  pub serve_tls: Option<TlsKeyFiles>,
   pub auto_serve: bool,
   pub otel_config: OtelConfig,
   pub close_on_idle: bool,
@@ -150,10 +155,12 @@ impl Default for BootstrapOptions {
       argv0: None,
       node_debug: None,
       node_ipc_fd: None,
+      permission_args: Default::default(),
       mode: WorkerExecutionMode::None,
       no_legacy_abort: false,
       serve_port: Default::default(),
       serve_host: Default::default(),
+      serve_tls: Default::default(),
       otel_config: Default::default(),
       close_on_idle: false,
     }
@@ -205,6 +212,8 @@ struct BootstrapV8<'a>(
   bool,
   // auto serve
   bool,
+  // serve tls
+  bool,
 );
 
 impl BootstrapOptions {
@@ -238,6 +247,7 @@ impl BootstrapOptions {
       self.close_on_idle,
       self.is_standalone,
       self.auto_serve,
+      self.serve_tls.is_some(),
     );
 
     bootstrap.serialize(ser).unwrap()
@@ -62,6 +62,7 @@ util::unit_test_factory!(
     mkdir_test,
     navigator_test,
     net_test,
+    offscreen_canvas_test,
     network_interfaces_test,
     os_test,
     ops_test,
@@ -152,6 +153,10 @@ fn js_unit_test(test: String) {
     deno = deno.arg("--unstable-broadcast-channel");
   }
 
+  if test == "webcrypto_test" {
+    deno = deno.arg("--unstable-webcrypto");
+  }
+
   if test == "cron_test" {
     deno = deno.arg("--unstable-cron");
   }
@@ -95,6 +95,7 @@ util::unit_test_factory!(
     string_decoder_test,
     timers_test,
     tls_test,
+    trace_events_test,
     tty_test,
     util_test,
     v8_test,
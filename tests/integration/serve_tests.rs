@@ -270,6 +270,40 @@ async fn deno_serve_parallel() {
   );
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn deno_serve_parallel_rolling_restart() {
+  use nix::sys::signal;
+  use nix::sys::signal::Signal;
+  use nix::unistd::Pid;
+
+  let client = ServeClient::builder()
+    .entry_point("./serve/parallel.ts")
+    .worker_count(Some(4))
+    .build();
+  client.endpoint();
+  tokio::time::sleep(Duration::from_millis(1000)).await;
+
+  let pid = Pid::from_raw(client.child.borrow().id() as i32);
+  signal::kill(pid, Signal::SIGHUP).unwrap();
+  tokio::time::sleep(Duration::from_millis(3000)).await;
+
+  // still serving after the restart
+  for _ in 0..10 {
+    let response = timeout(Duration::from_secs(2), client.get().send())
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(200, response.status());
+  }
+
+  let output = client.output();
+  eprintln!("serve output:\n{output}");
+  assert!(output.contains("deno serve: Restarting 3 workers"));
+  // the main worker and 3 workers, followed by the 3 restarted workers
+  assert_eq!(output.matches("starting serve").count(), 7);
+}
+
 #[tokio::test]
 async fn deno_run_serve_with_tcp_from_env() {
   let mut child = util::deno_cmd()
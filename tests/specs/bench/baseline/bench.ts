@@ -0,0 +1,10 @@
+Deno.bench("add", () => {
+  let sum = 0;
+  for (let i = 0; i < 100; i++) {
+    sum += i;
+  }
+});
+
+Deno.bench("concat", { group: "strings" }, () => {
+  "a".concat("b");
+});
@@ -0,0 +1,7 @@
+export function double(a: number) {
+  return a * 2;
+}
+
+export function unused() {
+  return 0;
+}
@@ -0,0 +1,8 @@
+import { double } from "./lib/util.ts";
+
+export function abs(a: number) {
+  if (a < 0) {
+    return -a;
+  }
+  return double(a) / 2;
+}
@@ -0,0 +1,7 @@
+import { abs } from "./math.ts";
+
+Deno.test("abs", () => {
+  if (abs(1) !== 1) {
+    throw new Error("fail");
+  }
+});
@@ -0,0 +1,14 @@
+const coverages = [];
+for (const entry of Deno.readDirSync("cov")) {
+  const coverage = JSON.parse(Deno.readTextFileSync(`cov/${entry.name}`));
+  if (coverage.url.endsWith("/source.js")) {
+    coverages.push(coverage);
+  }
+}
+
+console.log("files:", coverages.length);
+for (const { functionName, ranges } of coverages[0].functions) {
+  if (functionName) {
+    console.log(functionName, ranges[0].count);
+  }
+}
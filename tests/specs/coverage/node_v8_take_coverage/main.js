@@ -0,0 +1,8 @@
+import { stopCoverage, takeCoverage } from "node:v8";
+import { add, sub } from "./source.js";
+
+add(1, 2);
+takeCoverage();
+stopCoverage();
+// not covered, the coverage was stopped
+sub(2, 1);
@@ -0,0 +1,13 @@
+import { add, mul } from "./math.ts";
+
+Deno.test("add", () => {
+  if (add(1, 2) !== 3) {
+    throw new Error("fail");
+  }
+});
+
+Deno.test("mul", () => {
+  if (mul(2, 3) !== 6) {
+    throw new Error("fail");
+  }
+});
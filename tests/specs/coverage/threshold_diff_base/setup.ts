@@ -0,0 +1,31 @@
+function git(...args: string[]) {
+  const output = new Deno.Command("git", {
+    args: [
+      "-c",
+      "user.name=Deno",
+      "-c",
+      "user.email=deno@example.com",
+      "-c",
+      "commit.gpgsign=false",
+      ...args,
+    ],
+  }).outputSync();
+  if (!output.success) {
+    throw new Error(new TextDecoder().decode(output.stderr));
+  }
+}
+
+git("init", "--quiet");
+git("add", "math.ts");
+git("commit", "--quiet", "-m", "base");
+
+// change the untested `sub` and add a tested `mul`
+const math = Deno.readTextFileSync("math.ts");
+Deno.writeTextFileSync(
+  "math.ts",
+  `${math.replace("return a - b;", "return -b + a;")}
+export function mul(a: number, b: number) {
+  return a * b;
+}
+`,
+);
@@ -0,0 +1,8 @@
+import { add } from "./math.ts";
+import "./legacy/old.ts";
+
+Deno.test("add", () => {
+  if (add(1, 2) !== 3) {
+    throw new Error("fail");
+  }
+});
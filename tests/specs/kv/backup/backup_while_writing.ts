@@ -0,0 +1,28 @@
+const writer = new Deno.Command(Deno.execPath(), {
+  args: ["run", "-A", "--unstable-kv", "writer.ts"],
+  stdout: "piped",
+}).spawn();
+const reader = writer.stdout.getReader();
+await reader.read();
+
+const backup = await new Deno.Command(Deno.execPath(), {
+  args: ["kv", "backup", "kv.sqlite3", "backup.sqlite3"],
+}).output();
+writer.kill();
+await reader.cancel();
+await writer.status;
+if (!backup.success) {
+  throw new Error(new TextDecoder().decode(backup.stderr));
+}
+
+const db = await Deno.openKv("backup.sqlite3");
+const writes = (await db.get<Deno.KvU64>(["writes"])).value!.value;
+let entries = 0n;
+for await (const _ of db.list({ prefix: ["log"] })) {
+  entries++;
+}
+db.close();
+if (entries !== writes || writes < 100n) {
+  throw new Error(`Backup has ${entries} log entries, but ${writes} writes`);
+}
+console.log("backup is consistent");
@@ -0,0 +1,5 @@
+const db = await Deno.openKv("kv.sqlite3");
+await db.set(["counter"], new Deno.KvU64(5n));
+await db.set(["users", "alice"], "Alice");
+await db.set(["users", "bob"], "Bob", { expireIn: 24 * 60 * 60 * 1000 });
+db.close();
@@ -0,0 +1,11 @@
+const db = await Deno.openKv("kv.sqlite3");
+for (let i = 0;; i++) {
+  // every log entry is counted in the same transaction
+  await db.atomic()
+    .set(["log", i], i)
+    .sum(["writes"], 1n)
+    .commit();
+  if (i === 100) {
+    console.log("writing");
+  }
+}
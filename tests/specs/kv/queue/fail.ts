@@ -0,0 +1,14 @@
+const db = await Deno.openKv("kv.sqlite3");
+await db.enqueue("later", { delay: 60_000 });
+const listener = db.listenQueue(() => {
+  throw new Error("failed to deliver");
+});
+await db.enqueue("failing", {
+  keysIfUndelivered: [["undelivered"]],
+  backoffSchedule: [],
+});
+while ((await db.get(["undelivered"])).value === null) {
+  await new Promise((resolve) => setTimeout(resolve, 10));
+}
+db.close();
+await listener;
@@ -0,0 +1,35 @@
+import cluster from "node:cluster";
+import http from "node:http";
+import net from "node:net";
+
+const kind = process.argv[2];
+
+if (cluster.isPrimary) {
+  const worker = cluster.fork();
+  worker.on("exit", (code) => console.log(`worker exited with ${code}`));
+  worker.on("listening", ({ port }) => {
+    const received = (stream) => {
+      stream.setEncoding("utf8");
+      let data = "";
+      stream.on("data", (chunk) => data += chunk);
+      stream.on("end", () => {
+        console.log(`primary received: ${data}`);
+        cluster.disconnect();
+      });
+    };
+    if (kind === "http") {
+      // without keep-alive, so the worker's server can close
+      http.get({ host: "127.0.0.1", port, agent: false }, received);
+    } else {
+      received(net.connect(port, "127.0.0.1"));
+    }
+  });
+} else if (kind === "http") {
+  http.createServer((_req, res) => {
+    res.end(`http from worker ${cluster.worker.id}`);
+  }).listen(0, "127.0.0.1");
+} else {
+  net.createServer((socket) => {
+    socket.end(`net from worker ${cluster.worker.id}`);
+  }).listen(0, "127.0.0.1");
+}
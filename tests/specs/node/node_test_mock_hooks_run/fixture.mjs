@@ -0,0 +1,19 @@
+import assert from "node:assert";
+import test from "node:test";
+
+test("passes", () => {});
+
+test("fails", () => {
+  throw new Error("boom");
+});
+
+test("with subtest", async (t) => {
+  await t.test("subtest", () => {});
+});
+
+test("has the permissions of the parent", () => {
+  const { state: read } = Deno.permissions.querySync({ name: "read" });
+  assert.strictEqual(read, "granted");
+  const { state: net } = Deno.permissions.querySync({ name: "net" });
+  assert.strictEqual(net, "prompt");
+});
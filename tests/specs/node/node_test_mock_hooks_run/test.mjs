@@ -0,0 +1,141 @@
+import assert from "node:assert";
+import test, {
+  afterEach,
+  before,
+  beforeEach,
+  describe,
+  it,
+  mock,
+  run,
+} from "node:test";
+
+const events = [];
+const suiteEvents = [];
+
+beforeEach(() => events.push("root beforeEach"));
+afterEach(() => events.push("root afterEach"));
+
+test("mock.fn tracks calls", (t) => {
+  const fn = t.mock.fn((a, b) => a + b);
+  assert.strictEqual(fn(1, 2), 3);
+  fn.mock.mockImplementationOnce(() => 42);
+  assert.strictEqual(fn(1, 2), 42);
+  assert.strictEqual(fn(2, 2), 4);
+  assert.strictEqual(fn.mock.callCount(), 3);
+  assert.deepStrictEqual(fn.mock.calls[0].arguments, [1, 2]);
+  assert.strictEqual(fn.mock.calls[1].result, 42);
+});
+
+test("mock.method is restored after the test", async (t) => {
+  const obj = { get: () => "real" };
+  await t.test("mocks", (t) => {
+    t.mock.method(obj, "get", () => "mocked");
+    assert.strictEqual(obj.get(), "mocked");
+    assert.strictEqual(obj.get.mock.callCount(), 1);
+  });
+  assert.strictEqual(obj.get(), "real");
+
+  const spy = mock.method(obj, "get");
+  assert.strictEqual(obj.get(), "real");
+  assert.strictEqual(spy.mock.callCount(), 1);
+  mock.restoreAll();
+  assert.strictEqual(obj.get.mock, undefined);
+});
+
+test("mock.timers", (t) => {
+  t.mock.timers.enable({ apis: ["setTimeout", "setInterval", "Date"] });
+  const calls = [];
+  setTimeout(() => calls.push("timeout"), 100);
+  const interval = setInterval(() => calls.push("interval"), 40);
+  t.mock.timers.tick(99);
+  assert.deepStrictEqual(calls, ["interval", "interval"]);
+  t.mock.timers.tick(1);
+  assert.deepStrictEqual(calls, ["interval", "interval", "timeout"]);
+  clearInterval(interval);
+  assert.strictEqual(Date.now(), 100);
+  t.mock.timers.reset();
+  assert.notStrictEqual(Date.now(), 100);
+});
+
+test("context", async (t) => {
+  assert.strictEqual(t.name, "context");
+  const signal = t.signal;
+  t.beforeEach((t) => events.push(`beforeEach ${t.name}`));
+  t.afterEach((t) => events.push(`afterEach ${t.name}`));
+  await t.test("child", (t) => {
+    assert.strictEqual(t.fullName, "context > child");
+  });
+  assert.strictEqual(signal.aborted, false);
+});
+
+describe("suite hooks", () => {
+  let count = 0;
+  beforeEach(() => count++);
+  it("first", () => assert.strictEqual(count, 1));
+  it("second", () => assert.strictEqual(count, 2));
+});
+
+before(() => suiteEvents.push("root before"));
+
+describe("outer", () => {
+  before(() => suiteEvents.push("outer before"));
+  beforeEach(() => suiteEvents.push("outer beforeEach"));
+  describe("inner", () => {
+    before(() => suiteEvents.push("inner before"));
+    it("nested", () => suiteEvents.push("nested"));
+  });
+});
+
+test("nested suite hooks ran in order", () => {
+  // the `before` hooks of all the enclosing suites ran once
+  assert.deepStrictEqual(suiteEvents, [
+    "root before",
+    "outer before",
+    "inner before",
+    "outer beforeEach",
+    "nested",
+  ]);
+});
+
+test("hooks ran in order", () => {
+  // the root hooks also run for the subtests
+  assert.deepStrictEqual(events.slice(0, 14), [
+    "root beforeEach",
+    "root afterEach",
+    "root beforeEach",
+    "root beforeEach",
+    "root afterEach",
+    "root afterEach",
+    "root beforeEach",
+    "root afterEach",
+    "root beforeEach",
+    "root beforeEach",
+    "beforeEach child",
+    "afterEach child",
+    "root afterEach",
+    "root afterEach",
+  ]);
+});
+
+test("run() streams the events of the test files", async () => {
+  const results = [];
+  const stream = run({ files: ["fixture.mjs"] });
+  stream.on("test:fail", (data) => {
+    assert.strictEqual(data.details.error.code, "ERR_TEST_FAILURE");
+  });
+  for await (const { type, data } of stream) {
+    if (type === "test:pass" || type === "test:fail") {
+      results.push(`${type} ${data.nesting} ${data.name}`);
+    } else if (type === "test:summary") {
+      results.push(`summary ${data.counts.passed}/${data.counts.tests}`);
+    }
+  }
+  assert.deepStrictEqual(results, [
+    "test:pass 0 passes",
+    "test:fail 0 fails",
+    "test:pass 1 subtest",
+    "test:pass 0 with subtest",
+    "test:pass 0 has the permissions of the parent",
+    "summary 4/5",
+  ]);
+});
@@ -0,0 +1,6 @@
+import repl from "node:repl";
+import { PassThrough } from "node:stream";
+
+const input = new PassThrough();
+repl.start({ input, output: process.stdout, prompt: "> ", terminal: false });
+input.end("1 + 1\n");
@@ -0,0 +1,5 @@
+const { publicKey } = await crypto.subtle.generateKey("ML-DSA-44", true, [
+  "sign",
+  "verify",
+]) as CryptoKeyPair;
+console.log(publicKey.algorithm.name);
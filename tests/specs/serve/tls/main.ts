@@ -0,0 +1,19 @@
+(async () => {
+  for (let i = 0; i < 1000; i++) {
+    try {
+      const resp = await fetch("https://localhost:12370/");
+      console.log(await resp.text());
+      Deno.exit(0);
+    } catch {
+      await new Promise((r) => setTimeout(r, 10));
+    }
+  }
+
+  Deno.exit(2);
+})();
+
+export default {
+  fetch(_req) {
+    return new Response("Hello over TLS!");
+  },
+} satisfies Deno.ServeDefaultExport;
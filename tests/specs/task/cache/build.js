@@ -0,0 +1,2 @@
+const types = Deno.readTextFileSync("gen/types.js").trim();
+console.log("building with", types);
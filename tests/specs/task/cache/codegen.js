@@ -0,0 +1,4 @@
+const schema = Deno.readTextFileSync("src/schema.txt").trim();
+Deno.mkdirSync("gen", { recursive: true });
+Deno.writeTextFileSync("gen/types.js", `export const schema = "${schema}";\n`);
+console.log("generated", schema);
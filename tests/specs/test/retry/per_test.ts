@@ -0,0 +1,14 @@
+let attempts = 0;
+
+Deno.test({
+  name: "flaky",
+  retry: 2,
+  fn() {
+    attempts++;
+    if (attempts < 3) {
+      throw new Error(`failed on attempt ${attempts}`);
+    }
+  },
+});
+
+Deno.test("passes", () => {});
@@ -0,0 +1,14 @@
+let attempts = 0;
+
+Deno.test("flaky", () => {
+  attempts++;
+  if (attempts < 2) {
+    throw new Error("failed on the first attempt");
+  }
+});
+
+Deno.test("always fails", () => {
+  throw new Error("always fails");
+});
+
+Deno.test("passes", () => {});
@@ -0,0 +1,10 @@
+let attempts = 0;
+
+Deno.test("flaky steps", async (t) => {
+  attempts++;
+  await t.step("step", () => {
+    if (attempts < 2) {
+      throw new Error("failed on the first attempt");
+    }
+  });
+});
@@ -113,6 +113,50 @@ Deno.test(function invalidBackoffScheduleTest() {
   );
 });
 
+Deno.test(function invalidTimezoneTest() {
+  assertThrows(
+    () =>
+      Deno.cron(
+        "abc",
+        "*/1 * * * *",
+        { timezone: "Mars/Olympus_Mons" },
+        () => {},
+      ),
+    TypeError,
+    "Invalid time zone: Mars/Olympus_Mons",
+  );
+});
+
+Deno.test(async function timezoneAndCatchUp() {
+  assertThrows(
+    () =>
+      Deno.cron(
+        "abc",
+        "0 9 * * *",
+        { catchUp: "sometimes" as "once" },
+        () => {},
+      ),
+    TypeError,
+  );
+
+  // Catching up is tested against a stale last run file in
+  // ext/cron/local.rs; here the options only have to be accepted.
+  const ac = new AbortController();
+  const c = Deno.cron(
+    "abc",
+    "0 9 * * *",
+    { timezone: "Asia/Tokyo", catchUp: "once", signal: ac.signal },
+    () => {},
+  );
+  assertThrows(
+    () => Deno.cron("abc", "0 9 * * *", () => {}),
+    TypeError,
+    "Cron with this name already exists",
+  );
+  ac.abort();
+  await c;
+});
+
 Deno.test(async function tooManyCrons() {
   const crons: Promise<void>[] = [];
   const ac = new AbortController();
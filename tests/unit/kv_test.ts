@@ -47,6 +47,73 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "openKv memory: deterministic versionstamps",
+  permissions: {},
+  async fn() {
+    for (let i = 0; i < 2; i++) {
+      const db = await Deno.openKv("memory:");
+      try {
+        const res1 = await db.set(["a"], 1);
+        const res2 = await db.set(["b"], 2);
+        assertEquals(res1.versionstamp, "00000000000000010000");
+        assertEquals(res2.versionstamp, "00000000000000020000");
+      } finally {
+        db.close();
+      }
+    }
+  },
+});
+
+Deno.test({
+  name: "openKv memory: named databases are shared",
+  permissions: {},
+  async fn() {
+    const db1 = await Deno.openKv("memory:shared");
+    await db1.set(["a"], "b");
+    db1.close();
+
+    const db2 = await Deno.openKv("memory:shared");
+    const db3 = await Deno.openKv("memory:other");
+    try {
+      assertEquals((await db2.get(["a"])).value, "b");
+      assertEquals((await db3.get(["a"])).value, null);
+    } finally {
+      db2.close();
+      db3.close();
+    }
+  },
+});
+
+Deno.test({
+  name: "openKv postgres: requires net permission",
+  permissions: {},
+  async fn() {
+    await assertRejects(
+      async () => await Deno.openKv("postgres://localhost:5432/kv"),
+      Deno.errors.NotCapable,
+    );
+  },
+});
+
+// When set to a connection string, for example
+// `postgres://postgres@localhost/deno_kv_test`, the database and queue tests
+// also run against that PostgreSQL database. Its KV tables are emptied before
+// every test.
+const postgresUrl = Deno.env.get("DENO_KV_TEST_POSTGRES_URL");
+
+async function openPostgres(url: string): Promise<Deno.Kv> {
+  const db = await Deno.openKv(url);
+  const keys = [];
+  for await (const entry of db.list({ prefix: [] })) {
+    keys.push(entry.key);
+  }
+  for (const key of keys) {
+    await db.delete(key);
+  }
+  return db;
+}
+
 function dbTest(name: string, fn: (db: Deno.Kv) => Promise<void> | void) {
   Deno.test({
     name,
@@ -61,6 +128,19 @@ function dbTest(name: string, fn: (db: Deno.Kv) => Promise<void> | void) {
       }
     },
   });
+  if (postgresUrl) {
+    Deno.test({
+      name: `${name} (postgres)`,
+      async fn() {
+        const db = await openPostgres(postgresUrl);
+        try {
+          await fn(db);
+        } finally {
+          db.close();
+        }
+      },
+    });
+  }
 }
 
 function queueTest(name: string, fn: (db: Deno.Kv) => Promise<void>) {
@@ -73,6 +153,14 @@ function queueTest(name: string, fn: (db: Deno.Kv) => Promise<void>) {
       await fn(db);
     },
   });
+  if (postgresUrl) {
+    Deno.test({
+      name: `${name} (postgres)`,
+      async fn() {
+        await fn(await openPostgres(postgresUrl));
+      },
+    });
+  }
 }
 
 const ZERO_VERSIONSTAMP = "00000000000000000000";
@@ -1945,6 +2033,78 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "queue administration",
+  async fn() {
+    const filename = await Deno.makeTempFile({ prefix: "queue_db" });
+    try {
+      let db = await Deno.openKv(filename);
+      await db.enqueue("later", { delay: 60000 });
+      let messages = await db.listQueueMessages();
+      assertEquals(messages.length, 1);
+      assertEquals(messages[0].state, "pending");
+      assertEquals(messages[0].value, "later");
+      assertEquals(messages[0].deliveryAttempts, 0);
+      assertEquals(await db.purgeQueue({ states: ["dead"] }), 0);
+      assertEquals(await db.purgeQueue(), 1);
+      assertEquals(await db.listQueueMessages(), []);
+      db.close();
+
+      // dead messages are only recorded once tracking is enabled
+      const { success } = await new Deno.Command(Deno.execPath(), {
+        args: ["kv", "queue", "track", filename],
+        stderr: "null",
+      }).output();
+      assert(success);
+
+      db = await Deno.openKv(filename);
+      const listener = db.listenQueue(() => {
+        throw new Error("dequeue error");
+      });
+      await db.enqueue("failing", {
+        keysIfUndelivered: [["queue_failed"]],
+        backoffSchedule: [],
+      });
+      while ((await db.get(["queue_failed"])).value === null) {
+        await sleep(10);
+      }
+      db.close();
+      await listener;
+
+      db = await Deno.openKv(filename);
+      try {
+        messages = await db.listQueueMessages({ states: ["dead"] });
+        assertEquals(messages.length, 1);
+        assertEquals(messages[0].value, "failing");
+        assertEquals(messages[0].deliveryAttempts, 1);
+        assertEquals(messages[0].keysIfUndelivered, [["queue_failed"]]);
+        assertEquals(await db.redriveQueueMessages(["unknown"]), 0);
+        assertEquals(await db.redriveQueueMessages([messages[0].id]), 1);
+        messages = await db.listQueueMessages();
+        assertEquals(messages.length, 1);
+        assertEquals(messages[0].state, "pending");
+        assertEquals(messages[0].value, "failing");
+      } finally {
+        db.close();
+      }
+    } finally {
+      try {
+        await Deno.remove(filename);
+      } catch {
+        // pass
+      }
+    }
+  },
+});
+
+dbTest("queue administration is not supported in memory", async (db) => {
+  await assertRejects(
+    () => db.listQueueMessages(),
+    Deno.errors.NotSupported,
+  );
+  await assertRejects(() => db.purgeQueue(), Deno.errors.NotSupported);
+});
+
 dbTest("Invalid backoffSchedule", async (db) => {
   await assertRejects(
     async () => {
@@ -0,0 +1,279 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+import { assert, assertEquals, assertThrows } from "./test_util.ts";
+
+function pixel(
+  ctx: OffscreenCanvasRenderingContext2D,
+  x: number,
+  y: number,
+): number[] {
+  return Array.from(ctx.getImageData(x, y, 1, 1).data);
+}
+
+Deno.test(function offscreenCanvasSize() {
+  const canvas = new OffscreenCanvas(20, 10);
+  assertEquals(canvas.width, 20);
+  assertEquals(canvas.height, 10);
+  assertEquals(canvas.getContext("webgl"), null);
+  const ctx = canvas.getContext("2d")!;
+  assert(ctx instanceof OffscreenCanvasRenderingContext2D);
+  assertEquals(canvas.getContext("2d"), ctx);
+  assertEquals(ctx.canvas, canvas);
+
+  ctx.fillRect(0, 0, 20, 10);
+  ctx.lineWidth = 4;
+  canvas.width = 5;
+  assertEquals(ctx.lineWidth, 1);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+});
+
+Deno.test(function offscreenCanvasFillRect() {
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d")!;
+  ctx.fillStyle = "red";
+  assertEquals(ctx.fillStyle, "#ff0000");
+  ctx.fillStyle = "not a color";
+  assertEquals(ctx.fillStyle, "#ff0000");
+  ctx.fillRect(2, 2, 4, 4);
+  assertEquals(pixel(ctx, 3, 3), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 7, 7), [0, 0, 0, 0]);
+
+  ctx.clearRect(0, 0, 10, 10);
+  assertEquals(pixel(ctx, 3, 3), [0, 0, 0, 0]);
+
+  ctx.fillStyle = "rgba(0, 0, 255, 0.5)";
+  assertEquals(ctx.fillStyle, "rgba(0, 0, 255, 0.5)");
+});
+
+Deno.test(function offscreenCanvasPathAndTransform() {
+  const ctx = new OffscreenCanvas(20, 20).getContext("2d")!;
+  ctx.translate(10, 10);
+  ctx.beginPath();
+  ctx.moveTo(-5, -5);
+  ctx.lineTo(5, -5);
+  ctx.lineTo(5, 5);
+  ctx.lineTo(-5, 5);
+  ctx.closePath();
+  ctx.fillStyle = "#00ff00";
+  ctx.fill();
+  assertEquals(pixel(ctx, 10, 10), [0, 255, 0, 255]);
+  assertEquals(pixel(ctx, 2, 2), [0, 0, 0, 0]);
+
+  ctx.resetTransform();
+  ctx.beginPath();
+  ctx.arc(10, 10, 3, 0, Math.PI * 2);
+  ctx.fillStyle = "blue";
+  ctx.fill();
+  assertEquals(pixel(ctx, 10, 10), [0, 0, 255, 255]);
+  assertEquals(pixel(ctx, 6, 6), [0, 255, 0, 255]);
+});
+
+Deno.test(function offscreenCanvasSaveRestoreAndClip() {
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d")!;
+  ctx.save();
+  ctx.fillStyle = "red";
+  ctx.globalAlpha = 0.5;
+  ctx.beginPath();
+  ctx.rect(0, 0, 5, 10);
+  ctx.clip();
+  ctx.globalAlpha = 1;
+  ctx.fillRect(0, 0, 10, 10);
+  ctx.restore();
+  assertEquals(ctx.fillStyle, "#000000");
+  assertEquals(ctx.globalAlpha, 1);
+  assertEquals(pixel(ctx, 2, 5), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 7, 5), [0, 0, 0, 0]);
+
+  // The clip is part of the saved state.
+  ctx.fillRect(0, 0, 10, 10);
+  assertEquals(pixel(ctx, 7, 5), [0, 0, 0, 255]);
+});
+
+Deno.test(function offscreenCanvasGradient() {
+  const ctx = new OffscreenCanvas(100, 1).getContext("2d")!;
+  const gradient = ctx.createLinearGradient(0, 0, 100, 0);
+  assertThrows(() => gradient.addColorStop(2, "red"), DOMException);
+  assertThrows(() => gradient.addColorStop(0, "nope"), DOMException);
+  gradient.addColorStop(0, "black");
+  ctx.fillStyle = gradient;
+  assertEquals(ctx.fillStyle, gradient);
+  // Stops added after the gradient is assigned still apply.
+  gradient.addColorStop(1, "white");
+  ctx.fillRect(0, 0, 100, 1);
+  const [left] = pixel(ctx, 1, 0);
+  const [right] = pixel(ctx, 98, 0);
+  assert(left < 20);
+  assert(right > 235);
+});
+
+Deno.test(function offscreenCanvasImageData() {
+  const ctx = new OffscreenCanvas(4, 4).getContext("2d")!;
+  const imageData = ctx.createImageData(2, 2);
+  imageData.data.set([
+    ...[255, 0, 0, 255],
+    ...[0, 255, 0, 255],
+    ...[0, 0, 255, 255],
+    ...[255, 255, 255, 255],
+  ]);
+  ctx.putImageData(imageData, 1, 1);
+  assertEquals(pixel(ctx, 1, 1), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 2, 2), [255, 255, 255, 255]);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+  assertEquals(
+    Array.from(ctx.getImageData(1, 1, 2, 2).data),
+    Array.from(imageData.data),
+  );
+  assertThrows(() => ctx.getImageData(0, 0, 0, 1), DOMException);
+  // Pixels that would land outside of the canvas are dropped, however far
+  // away they are.
+  ctx.putImageData(imageData, 2147483647, -2147483648);
+  ctx.putImageData(imageData, -2147483648, 0, 0, 0, 2147483647, 2147483647);
+  assertEquals(pixel(ctx, 1, 1), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+});
+
+Deno.test(async function offscreenCanvasDrawImage() {
+  const source = new OffscreenCanvas(2, 1);
+  source.getContext("2d")!.putImageData(
+    new ImageData(
+      new Uint8ClampedArray([255, 0, 0, 255, 0, 0, 255, 255]),
+      2,
+      1,
+    ),
+    0,
+    0,
+  );
+  const bitmap = await createImageBitmap(await source.convertToBlob());
+  const ctx = new OffscreenCanvas(4, 2).getContext("2d")!;
+  ctx.imageSmoothingEnabled = false;
+  ctx.drawImage(bitmap, 0, 0, 4, 2);
+  assertEquals(pixel(ctx, 0, 0), [255, 0, 0, 255]);
+  assertEquals(pixel(ctx, 3, 1), [0, 0, 255, 255]);
+
+  const target = new OffscreenCanvas(4, 2).getContext("2d")!;
+  target.drawImage(ctx.canvas, 2, 0, 2, 2, 0, 0, 2, 2);
+  assertEquals(pixel(target, 0, 0), [0, 0, 255, 255]);
+  assertEquals(pixel(target, 3, 0), [0, 0, 0, 0]);
+
+  bitmap.close();
+  assertThrows(() => ctx.drawImage(bitmap, 0, 0), DOMException);
+});
+
+Deno.test(function offscreenCanvasTransferToImageBitmap() {
+  const canvas = new OffscreenCanvas(3, 2);
+  assertThrows(() => canvas.transferToImageBitmap(), DOMException);
+  const ctx = canvas.getContext("2d")!;
+  ctx.fillRect(0, 0, 3, 2);
+  const bitmap = canvas.transferToImageBitmap();
+  assertEquals(bitmap.width, 3);
+  assertEquals(bitmap.height, 2);
+  assertEquals(pixel(ctx, 0, 0), [0, 0, 0, 0]);
+});
+
+Deno.test(async function offscreenCanvasConvertToBlob() {
+  const canvas = new OffscreenCanvas(8, 8);
+  const ctx = canvas.getContext("2d")!;
+  ctx.fillStyle = "red";
+  ctx.fillRect(0, 0, 8, 8);
+
+  const png = await canvas.convertToBlob();
+  assertEquals(png.type, "image/png");
+  const pngBytes = new Uint8Array(await png.arrayBuffer());
+  assertEquals(Array.from(pngBytes.subarray(1, 4)), [0x50, 0x4e, 0x47]);
+  const bitmap = await createImageBitmap(png);
+  assertEquals(bitmap.width, 8);
+
+  const jpeg = await canvas.convertToBlob({
+    type: "image/jpeg",
+    quality: 0.5,
+  });
+  assertEquals(jpeg.type, "image/jpeg");
+  const jpegBytes = new Uint8Array(await jpeg.arrayBuffer());
+  assertEquals(Array.from(jpegBytes.subarray(0, 2)), [0xff, 0xd8]);
+
+  const webp = await canvas.convertToBlob({ type: "image/webp" });
+  assertEquals(webp.type, "image/webp");
+  const webpBytes = new Uint8Array(await webp.arrayBuffer());
+  assertEquals(new TextDecoder().decode(webpBytes.subarray(8, 12)), "WEBP");
+
+  const fallback = await canvas.convertToBlob({ type: "image/gif" });
+  assertEquals(fallback.type, "image/png");
+});
+
+Deno.test(function offscreenCanvasText() {
+  const ctx = new OffscreenCanvas(10, 10).getContext("2d")!;
+  assertEquals(ctx.font, "10px sans-serif");
+  ctx.font = "italic bold 12pt 'Open Sans', serif";
+  assertEquals(ctx.font, "italic bold 16px Open Sans, serif");
+  ctx.font = "invalid";
+  assertEquals(ctx.font, "italic bold 16px Open Sans, serif");
+  ctx.textAlign = "center";
+  ctx.textAlign = "nope" as CanvasTextAlign;
+  assertEquals(ctx.textAlign, "center");
+
+  // Without any font face nothing is drawn.
+  ctx.fillText("Hello", 0, 10);
+  assertEquals(ctx.measureText("Hello").width, 0);
+  assertEquals(pixel(ctx, 5, 5), [0, 0, 0, 0]);
+});
+
+// `box.ttf` maps "H" to a box filling its 1000 unit advance from the baseline
+// up to the 800 unit ascent, and "g" to a box 500 units wide from 200 units
+// below the baseline to 400 units above it.
+Deno.test(async function offscreenCanvasTextWithFontFace() {
+  const face = new FontFace(
+    "Box",
+    await Deno.readFile("tests/testdata/font/box.ttf"),
+  );
+  assert(!Object.keys(globalThis).includes("fonts"));
+  fonts.add(face);
+  try {
+    const ctx = new OffscreenCanvas(20, 10).getContext("2d")!;
+    ctx.font = "10px Box";
+    ctx.fillText("Hg", 0, 8);
+    assertEquals(pixel(ctx, 5, 4), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 12, 6), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 12, 9), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 12, 2), [0, 0, 0, 0]);
+    assertEquals(pixel(ctx, 5, 9), [0, 0, 0, 0]);
+    assertEquals(pixel(ctx, 17, 5), [0, 0, 0, 0]);
+
+    const metrics = ctx.measureText("Hg");
+    assertEquals(metrics.width, 15);
+    assertEquals(metrics.actualBoundingBoxRight, 15);
+    assertEquals(metrics.actualBoundingBoxAscent, 8);
+    assertEquals(metrics.actualBoundingBoxDescent, 2);
+    assertEquals(metrics.fontBoundingBoxAscent, 8);
+    assertEquals(metrics.fontBoundingBoxDescent, 2);
+
+    ctx.textAlign = "center";
+    ctx.textBaseline = "middle";
+    const centered = ctx.measureText("Hg");
+    assertEquals(centered.actualBoundingBoxLeft, 7.5);
+    assertEquals(centered.actualBoundingBoxRight, 7.5);
+    assertEquals(centered.actualBoundingBoxAscent, 5);
+    assertEquals(centered.actualBoundingBoxDescent, 5);
+
+    // maxWidth squeezes the text horizontally around its anchor.
+    ctx.clearRect(0, 0, 20, 10);
+    ctx.fillText("Hg", 10, 5, 7.5);
+    assertEquals(pixel(ctx, 8, 4), [0, 0, 0, 255]);
+    assertEquals(pixel(ctx, 4, 4), [0, 0, 0, 0]);
+    assertEquals(pixel(ctx, 15, 4), [0, 0, 0, 0]);
+  } finally {
+    fonts.delete(face);
+  }
+  const ctx = new OffscreenCanvas(20, 10).getContext("2d")!;
+  ctx.font = "10px Box";
+  assertEquals(ctx.measureText("Hg").width, 0);
+});
+
+Deno.test(function fontFaceSet() {
+  const face = new FontFace("Broken", new Uint8Array([1, 2, 3]));
+  assertEquals(face.status, "loaded");
+  assertThrows(() => fonts.add(face), DOMException);
+  assertEquals(fonts.has(face), false);
+
+  const url = new FontFace("Remote", "url(font.ttf)");
+  assertEquals(url.status, "error");
+  url.loaded.catch(() => {});
+});
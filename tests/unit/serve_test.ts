@@ -2865,6 +2865,103 @@ for (const testCase of compressionTestCases) {
   );
 }
 
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerCompressionZstd() {
+    const listeningDeferred = Promise.withResolvers<void>();
+    const ac = new AbortController();
+    const text = "hello world ".repeat(100);
+    await using server = Deno.serve({
+      handler: () =>
+        new Response(text, { headers: { "Content-Type": "text/plain" } }),
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(listeningDeferred.resolve),
+      onError: createOnErrorCb(ac),
+    });
+    try {
+      await listeningDeferred.promise;
+      for (const acceptEncoding of ["zstd", "gzip, deflate, br, zstd"]) {
+        // fetch doesn't decode zstd, so the body is left compressed
+        const resp = await fetch(`http://127.0.0.1:${servePort}/`, {
+          headers: { "Accept-Encoding": acceptEncoding },
+        });
+        assertEquals(resp.headers.get("content-encoding"), "zstd");
+        assertEquals(resp.headers.get("vary"), "Accept-Encoding");
+        const body = await new Response(
+          resp.body!.pipeThrough(new DecompressionStream("zstd")),
+        ).text();
+        assertEquals(body, text);
+      }
+    } finally {
+      ac.abort();
+      await server.finished;
+    }
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerCompressionMinSize() {
+    const listeningDeferred = Promise.withResolvers<void>();
+    const ac = new AbortController();
+    await using server = Deno.serve({
+      handler: (request) => {
+        const length = Number(new URL(request.url).searchParams.get("length"));
+        return new Response("a".repeat(length), {
+          headers: { "Content-Type": "text/plain" },
+        });
+      },
+      port: servePort,
+      signal: ac.signal,
+      compression: { minSize: 1024, zstdLevel: 19 },
+      onListen: onListen(listeningDeferred.resolve),
+      onError: createOnErrorCb(ac),
+    });
+    try {
+      await listeningDeferred.promise;
+      for (const [length, encoding] of [[1023, null], [1024, "zstd"]]) {
+        const resp = await fetch(
+          `http://127.0.0.1:${servePort}/?length=${length}`,
+          { headers: { "Accept-Encoding": "zstd" } },
+        );
+        await resp.body?.cancel();
+        assertEquals(resp.headers.get("content-encoding"), encoding);
+      }
+    } finally {
+      ac.abort();
+      await server.finished;
+    }
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  function httpServerCompressionInvalidOptions() {
+    const handler = () => new Response();
+    assertThrows(
+      () =>
+        Deno.serve({
+          handler,
+          port: servePort,
+          compression: { gzipLevel: 10 },
+        }),
+      RangeError,
+      "Invalid value for compression.gzipLevel: must be between 0 and 9, received 10",
+    );
+    assertThrows(
+      () =>
+        Deno.serve({
+          handler,
+          port: servePort,
+          compression: { zstdLevel: 1.5 },
+        }),
+      TypeError,
+      "Invalid type for compression.zstdLevel: must be an integer",
+    );
+  },
+);
+
 Deno.test(
   { permissions: { net: true, write: true, read: true } },
   async function httpServerPostFile() {
@@ -1,5 +1,6 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 import {
+  assert,
   assertEquals,
   assertRejects,
   assertThrows,
@@ -487,6 +488,8 @@ Deno.test(async function compressionStreamWritableMayBeAborted() {
     new CompressionStream("gzip").writable.getWriter().abort(),
     new CompressionStream("deflate").writable.getWriter().abort(),
     new CompressionStream("deflate-raw").writable.getWriter().abort(),
+    new CompressionStream("brotli").writable.getWriter().abort(),
+    new CompressionStream("zstd").writable.getWriter().abort(),
   ]);
 });
 
@@ -495,6 +498,8 @@ Deno.test(async function compressionStreamReadableMayBeCancelled() {
     new CompressionStream("gzip").readable.getReader().cancel(),
     new CompressionStream("deflate").readable.getReader().cancel(),
     new CompressionStream("deflate-raw").readable.getReader().cancel(),
+    new CompressionStream("brotli").readable.getReader().cancel(),
+    new CompressionStream("zstd").readable.getReader().cancel(),
   ]);
 });
 
@@ -503,6 +508,8 @@ Deno.test(async function decompressionStreamWritableMayBeAborted() {
     new DecompressionStream("gzip").writable.getWriter().abort(),
     new DecompressionStream("deflate").writable.getWriter().abort(),
     new DecompressionStream("deflate-raw").writable.getWriter().abort(),
+    new DecompressionStream("brotli").writable.getWriter().abort(),
+    new DecompressionStream("zstd").writable.getWriter().abort(),
   ]);
 });
 
@@ -511,6 +518,8 @@ Deno.test(async function decompressionStreamReadableMayBeCancelled() {
     new DecompressionStream("gzip").readable.getReader().cancel(),
     new DecompressionStream("deflate").readable.getReader().cancel(),
     new DecompressionStream("deflate-raw").readable.getReader().cancel(),
+    new DecompressionStream("brotli").readable.getReader().cancel(),
+    new DecompressionStream("zstd").readable.getReader().cancel(),
   ]);
 });
 
@@ -539,6 +548,39 @@ Deno.test(async function decompressionStreamInvalidGzipStillReported() {
   );
 });
 
+Deno.test(async function compressionStreamBrotliAndZstdRoundTrip() {
+  const input = new TextEncoder().encode("hello world ".repeat(1000));
+  for (const format of ["brotli", "zstd"] as const) {
+    const compressed = await new Response(
+      ReadableStream.from([input]).pipeThrough(new CompressionStream(format)),
+    ).bytes();
+    assert(compressed.byteLength < input.byteLength);
+    const decompressed = await new Response(
+      ReadableStream.from([compressed]).pipeThrough(
+        new DecompressionStream(format),
+      ),
+    ).bytes();
+    assertEquals(decompressed, input);
+  }
+});
+
+Deno.test(async function decompressionStreamTruncatedZstdReported() {
+  const compressed = await new Response(
+    ReadableStream.from([new Uint8Array(1024).fill(1)]).pipeThrough(
+      new CompressionStream("zstd"),
+    ),
+  ).bytes();
+  await assertRejects(
+    () =>
+      new Response(
+        ReadableStream.from([compressed.subarray(0, compressed.length - 4)])
+          .pipeThrough(new DecompressionStream("zstd")),
+      ).bytes(),
+    TypeError,
+    "incomplete frame",
+  );
+});
+
 Deno.test(function readableStreamFromWithStringThrows() {
   assertThrows(
     // @ts-expect-error: primitives are not acceptable
@@ -60,6 +60,18 @@ Deno.test(function testWrongOverloads() {
   );
 });
 
+Deno.test(function invalidRetryCount() {
+  for (const retry of [-1, 1.5, NaN]) {
+    assertThrows(
+      () => {
+        Deno.test({ name: "some name", retry, fn: () => {} });
+      },
+      TypeError,
+      "Invalid test retry count: expected a non-negative integer",
+    );
+  }
+});
+
 Deno.test(function nameOfTestCaseCantBeEmpty() {
   assertThrows(
     () => {
@@ -2162,3 +2162,499 @@ Deno.test(async function x25519ExportJwk() {
   assert(jwk.d);
   assert(jwk.x);
 });
+
+Deno.test(async function sha3Digest() {
+  const data = new TextEncoder().encode("abc");
+  const digest = await crypto.subtle.digest("SHA3-256", data);
+  assertEquals(
+    new Uint8Array(digest),
+    new Uint8Array([
+      58, 152, 93, 167, 79, 226, 37, 178, 4, 92, 23, 45, 107, 211, 144, 189,
+      133, 95, 8, 110, 62, 157, 82, 91, 70, 191, 226, 69, 17, 67, 21, 50,
+    ]),
+  );
+
+  await assertRejects(
+    () =>
+      crypto.subtle.generateKey(
+        { name: "HMAC", hash: "SHA3-256" },
+        true,
+        ["sign"],
+      ),
+    DOMException,
+    "SHA3-256 can only be used with digest()",
+  );
+});
+
+Deno.test(async function chacha20Poly1305EncryptDecrypt() {
+  const key = await crypto.subtle.generateKey(
+    { name: "ChaCha20-Poly1305" },
+    true,
+    ["encrypt", "decrypt"],
+  ) as CryptoKey;
+  const iv = crypto.getRandomValues(new Uint8Array(12));
+  const additionalData = new Uint8Array([1, 2, 3]);
+  const data = new TextEncoder().encode("hello world");
+
+  const encrypted = await crypto.subtle.encrypt(
+    { name: "ChaCha20-Poly1305", iv, additionalData },
+    key,
+    data,
+  );
+  assertEquals(encrypted.byteLength, data.byteLength + 16);
+
+  const decrypted = await crypto.subtle.decrypt(
+    { name: "ChaCha20-Poly1305", iv, additionalData },
+    key,
+    encrypted,
+  );
+  assertEquals(new Uint8Array(decrypted), data);
+
+  await assertRejects(
+    () =>
+      crypto.subtle.decrypt(
+        { name: "ChaCha20-Poly1305", iv },
+        key,
+        encrypted,
+      ),
+    DOMException,
+  );
+
+  const jwk = await crypto.subtle.exportKey("jwk", key);
+  assertEquals(jwk.alg, "C20P");
+  const raw = await crypto.subtle.exportKey("raw-secret", key);
+  assertEquals(raw.byteLength, 32);
+});
+
+Deno.test(async function ed448SignVerify() {
+  const keyPair = await crypto.subtle.generateKey(
+    { name: "Ed448" },
+    true,
+    ["sign", "verify"],
+  ) as CryptoKeyPair;
+  const data = new TextEncoder().encode("hello world");
+  const context = new Uint8Array([1, 2, 3]);
+
+  const signature = await crypto.subtle.sign(
+    { name: "Ed448", context },
+    keyPair.privateKey,
+    data,
+  );
+  assertEquals(signature.byteLength, 114);
+  assert(
+    await crypto.subtle.verify(
+      { name: "Ed448", context },
+      keyPair.publicKey,
+      signature,
+      data,
+    ),
+  );
+  assert(
+    !await crypto.subtle.verify(
+      "Ed448",
+      keyPair.publicKey,
+      signature,
+      data,
+    ),
+  );
+
+  const pkcs8 = await crypto.subtle.exportKey("pkcs8", keyPair.privateKey);
+  const privateKey = await crypto.subtle.importKey(
+    "pkcs8",
+    pkcs8,
+    "Ed448",
+    true,
+    ["sign"],
+  );
+  const spki = await crypto.subtle.exportKey("spki", keyPair.publicKey);
+  const publicKey = await crypto.subtle.importKey(
+    "spki",
+    spki,
+    "Ed448",
+    true,
+    ["verify"],
+  );
+  const signature2 = await crypto.subtle.sign("Ed448", privateKey, data);
+  assert(await crypto.subtle.verify("Ed448", publicKey, signature2, data));
+
+  const jwk = await crypto.subtle.exportKey("jwk", keyPair.privateKey);
+  assertEquals(jwk.kty, "OKP");
+  assertEquals(jwk.crv, "Ed448");
+  assertEquals(
+    jwk.x,
+    (await crypto.subtle.exportKey("jwk", keyPair.publicKey)).x,
+  );
+});
+
+Deno.test(async function mlKemEncapsulateDecapsulate() {
+  for (const name of ["ML-KEM-512", "ML-KEM-768", "ML-KEM-1024"]) {
+    const keyPair = await crypto.subtle.generateKey(
+      { name },
+      true,
+      ["encapsulateBits", "decapsulateBits", "decapsulateKey"],
+    ) as CryptoKeyPair;
+
+    const { sharedKey, ciphertext } = await crypto.subtle.encapsulateBits(
+      name,
+      keyPair.publicKey,
+    );
+    assertEquals(sharedKey.byteLength, 32);
+    const decapsulated = await crypto.subtle.decapsulateBits(
+      name,
+      keyPair.privateKey,
+      ciphertext,
+    );
+    assertEquals(new Uint8Array(decapsulated), new Uint8Array(sharedKey));
+
+    const aesKey = await crypto.subtle.decapsulateKey(
+      name,
+      keyPair.privateKey,
+      ciphertext,
+      "AES-GCM",
+      true,
+      ["encrypt"],
+    );
+    assertEquals(
+      new Uint8Array(await crypto.subtle.exportKey("raw", aesKey)),
+      new Uint8Array(sharedKey),
+    );
+
+    // Round trip the keys through their serialized formats.
+    const seed = await crypto.subtle.exportKey("raw-seed", keyPair.privateKey);
+    assertEquals(seed.byteLength, 64);
+    const pkcs8 = await crypto.subtle.exportKey("pkcs8", keyPair.privateKey);
+    const privateKey = await crypto.subtle.importKey(
+      "pkcs8",
+      pkcs8,
+      name,
+      true,
+      ["decapsulateBits"],
+    );
+    assertEquals(
+      new Uint8Array(await crypto.subtle.exportKey("raw-seed", privateKey)),
+      new Uint8Array(seed),
+    );
+    const spki = await crypto.subtle.exportKey("spki", keyPair.publicKey);
+    const publicKey = await crypto.subtle.importKey(
+      "spki",
+      spki,
+      name,
+      true,
+      ["encapsulateBits"],
+    );
+    assertEquals(
+      new Uint8Array(await crypto.subtle.exportKey("raw-public", publicKey)),
+      new Uint8Array(
+        await crypto.subtle.exportKey("raw-public", keyPair.publicKey),
+      ),
+    );
+  }
+});
+
+Deno.test(async function mlDsaSignVerify() {
+  for (const name of ["ML-DSA-44", "ML-DSA-65", "ML-DSA-87"]) {
+    const keyPair = await crypto.subtle.generateKey(
+      { name },
+      true,
+      ["sign", "verify"],
+    ) as CryptoKeyPair;
+    const data = new TextEncoder().encode("hello world");
+
+    const signature = await crypto.subtle.sign(
+      name,
+      keyPair.privateKey,
+      data,
+    );
+    assert(
+      await crypto.subtle.verify(name, keyPair.publicKey, signature, data),
+    );
+    assert(
+      !await crypto.subtle.verify(
+        { name, context: new Uint8Array([1]) },
+        keyPair.publicKey,
+        signature,
+        data,
+      ),
+    );
+
+    const seed = await crypto.subtle.exportKey("raw-seed", keyPair.privateKey);
+    assertEquals(seed.byteLength, 32);
+    const privateKey = await crypto.subtle.importKey(
+      "raw-seed",
+      seed,
+      name,
+      true,
+      ["sign"],
+    );
+    // Signing is deterministic.
+    assertEquals(
+      new Uint8Array(await crypto.subtle.sign(name, privateKey, data)),
+      new Uint8Array(signature),
+    );
+  }
+});
+
+function hexToBytes(hex: string): Uint8Array {
+  const bytes = new Uint8Array(hex.length / 2);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = parseInt(hex.slice(i * 2, i * 2 + 2), 16);
+  }
+  return bytes;
+}
+
+function bytesToHex(bytes: ArrayBuffer | Uint8Array): string {
+  return Array.from(new Uint8Array(bytes))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+async function sha256Hex(data: ArrayBuffer): Promise<string> {
+  return bytesToHex(await crypto.subtle.digest("SHA-256", data));
+}
+
+// FIPS 202, Appendix A and the NIST example values.
+Deno.test(async function sha3KnownAnswers() {
+  const abc = new TextEncoder().encode("abc");
+  assertEquals(
+    bytesToHex(await crypto.subtle.digest("SHA3-256", new Uint8Array())),
+    "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+  );
+  assertEquals(
+    bytesToHex(await crypto.subtle.digest("SHA3-384", abc)),
+    "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2" +
+      "98d88cea927ac7f539f1edf228376d25",
+  );
+  assertEquals(
+    bytesToHex(await crypto.subtle.digest("SHA3-512", abc)),
+    "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e" +
+      "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+  );
+});
+
+// RFC 8439, Section 2.8.2.
+Deno.test(async function chacha20Poly1305KnownAnswer() {
+  const key = await crypto.subtle.importKey(
+    "raw-secret",
+    hexToBytes(
+      "808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f",
+    ),
+    "ChaCha20-Poly1305",
+    false,
+    ["encrypt", "decrypt"],
+  );
+  const params = {
+    name: "ChaCha20-Poly1305",
+    iv: hexToBytes("070000004041424344454647"),
+    additionalData: hexToBytes("50515253c0c1c2c3c4c5c6c7"),
+  };
+  const plaintext = new TextEncoder().encode(
+    "Ladies and Gentlemen of the class of '99: If I could offer you only " +
+      "one tip for the future, sunscreen would be it.",
+  );
+  const expected =
+    "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6" +
+    "3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36" +
+    "92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc" +
+    "3ff4def08e4b7a9de576d26586cec64b6116" +
+    // Tag
+    "1ae10b594f09e26a7e902ecbd0600691";
+  const ciphertext = await crypto.subtle.encrypt(params, key, plaintext);
+  assertEquals(bytesToHex(ciphertext), expected);
+  assertEquals(
+    new Uint8Array(
+      await crypto.subtle.decrypt(params, key, hexToBytes(expected)),
+    ),
+    plaintext,
+  );
+});
+
+// RFC 8032, Section 7.4.
+Deno.test(async function ed448KnownAnswers() {
+  const vectors = [
+    {
+      secretKey:
+        "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3" +
+        "528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+      publicKey:
+        "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778" +
+        "edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+      message: "",
+      context: "",
+      signature:
+        "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f" +
+        "2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a" +
+        "9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4db" +
+        "b61149f05a7363268c71d95808ff2e652600",
+    },
+    {
+      secretKey:
+        "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463a" +
+        "fbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+      publicKey:
+        "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c086" +
+        "6aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+      message: "03",
+      context: "",
+      signature:
+        "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f435" +
+        "2541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cb" +
+        "cee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0f" +
+        "f3348ab21aa4adafd1d234441cf807c03a00",
+    },
+    {
+      secretKey:
+        "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463a" +
+        "fbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+      publicKey:
+        "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c086" +
+        "6aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+      message: "03",
+      context: "666f6f",
+      signature:
+        "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b32a89f7d2" +
+        "151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea000c85741de5c8da" +
+        "1144a6a1aba7f96de42505d7a7298524fda538fccbbb754f578c1cad10d54d0d" +
+        "5428407e85dcbc98a49155c13764e66c3c00",
+    },
+  ];
+  const toBase64Url = (hex: string) =>
+    btoa(String.fromCharCode(...hexToBytes(hex)))
+      .replaceAll("+", "-")
+      .replaceAll("/", "_")
+      .replaceAll("=", "");
+
+  for (const vector of vectors) {
+    const privateKey = await crypto.subtle.importKey(
+      "jwk",
+      {
+        kty: "OKP",
+        crv: "Ed448",
+        d: toBase64Url(vector.secretKey),
+        x: toBase64Url(vector.publicKey),
+      },
+      "Ed448",
+      true,
+      ["sign"],
+    );
+    // The public key is derived from the private key on export.
+    assertEquals(
+      (await crypto.subtle.exportKey("jwk", privateKey)).x,
+      toBase64Url(vector.publicKey),
+    );
+    const publicKey = await crypto.subtle.importKey(
+      "raw",
+      hexToBytes(vector.publicKey),
+      "Ed448",
+      true,
+      ["verify"],
+    );
+    const params = { name: "Ed448", context: hexToBytes(vector.context) };
+    const message = hexToBytes(vector.message);
+
+    const signature = await crypto.subtle.sign(params, privateKey, message);
+    assertEquals(bytesToHex(signature), vector.signature);
+    assert(
+      await crypto.subtle.verify(
+        params,
+        publicKey,
+        hexToBytes(vector.signature),
+        message,
+      ),
+    );
+    const tampered = hexToBytes(vector.signature);
+    tampered[0] ^= 1;
+    assert(!await crypto.subtle.verify(params, publicKey, tampered, message));
+  }
+});
+
+// The private keys are the seeds 00 01 02 ..., the expected values were
+// computed with OpenSSL 3.5, an independent implementation of FIPS 203 and
+// FIPS 204. They depend on the whole expanded private key.
+Deno.test(async function mlKemKnownAnswers() {
+  const seed = new Uint8Array(64).map((_, i) => i);
+  const privateKey = await crypto.subtle.importKey(
+    "raw-seed",
+    seed,
+    "ML-KEM-768",
+    false,
+    ["decapsulateBits"],
+  );
+  const ciphertext = Uint8Array.from(
+    atob(
+      "jDtg4CR2KGLIVoxfAnUk5dvbs5j4niYUDz72rT96rPN9d7pZdfywAcX6k1Zqy2oU8ktoxWD9" +
+      "T75Eoow6NqQLnMyzKxY5is0YGC2sn9IicCkYjRWJMmtf42lEHmC9rm/CH2fFxOacI13rxmk5" +
+      "6bXRMDlLHy3pKkI72W4mk68lzRBj6IRU/f6/m4+XXF8Mrwzn1eMSr5MCSnuMw0ld7VixfHlW" +
+      "JSsF5O1MFPSBbggicptELS2PK4+3hg48CEgLJWdLCn1APg7uqT2zoPc2WU7yxLbsaUdY/Zyu" +
+      "vYoCvrmpiVsfP16i6eccDy9t6Ne333UD8HK6rjj0ah0v+FmG08yoK9JekEpfqZDdZeUIWDSA" +
+      "Bb6Mx/1ytNj7m8ZSoI9VC9cj+T0Gyhh34PSlUThWz2nPdRhUHo/jpEF0lSsujCALdY9eNiP4" +
+      "HYxW21fBsvzWiJqw7AbyTT/0094c8I3hXxbZ84flNFZqym5Z3KGOVAWERuq0MkGFyTUW4GGg" +
+      "pNrejtHYDomWbY0ctr6/jIOjEKewTUYT0YFrE9eNi+YbKwp4a4bJQJqI/hbpqsOHzhL7QgHf" +
+      "FsmS+g6ZaX/36K9DlKvQCBkZN3OxBnF2AWljsdACx0BuywttbqTzqo3ZtgY5xtS0veSqzWiJ" +
+      "ar4uuYtorpq9aFIJWEGfkVLNnZJvtr1981Zalgi0P0oDfEgluNQQb7cGkdTf4ewHaY31nb6G" +
+      "zOl2YfWYzy78tygcxIdzdVHYMkhWQJTRmTJ5JaMrNVsIEg9fCdFO/Lnjt2pA0XWMyJD6BArS" +
+      "XMrsjlstLJAMQUNIcQigNHiRxePU+dbjtEmGnD4EZDocEfH7qlZxydi0fFRajrN8paoMpgQP" +
+      "0mESxqoySJg2x/DxlJ5H4qOHacMRrm/SvEYULh6hp9wiOXkK6DDNu1kf0bcmmoBaIsqcx5DJ" +
+      "sp9b6LzfWC1B86oOquzNwJDV39F+X51nlozj9D4SYN2xCf+97SZZ0eiWRQpgknB2cZGBX4Gj" +
+      "9tocQIPm8eRKtOEWsfuOQXQz5qciEs3dmzt6LWdwQaCeeUr1Bph2GMaAwIRqpUW/seOQaKk8" +
+      "J19E0M00UIrRri2IGl2RG1tEmjAZp/vkcSa1rX+d51hR42inCSCCAzU/bD1uJO4+PP2l82m7" +
+      "EAQinTK61bxK/HcnCsdnc6FKaCIH8F3v2sRClj+Y8SEER3Ll3Lrr7d8oyKldV0QjtSxD21r3" +
+      "yFsPjMMe4FK9uoIKPrRg5c6FmzQ9ZxwgNpI3f5TivgEG839iELovL4X9mjCdGDhHPsyjncK9" +
+      "TvnazgF4she8TBrvKD3VBqAS9hVujaSWzSGEAm1pv45kM6ikMjfQ7ko8FWBmaf+bzUSAw9vA" +
+      "iOMeM45pRY4qkR4vT75IRh57npxb0TM5TV70uF/lN7EOmXT2pibTTrTAABsiYbHCgX4M8kDC" +
+      "Bb/9pLjft3w=",
+    ),
+    (c) => c.charCodeAt(0),
+  );
+  assertEquals(
+    bytesToHex(
+      await crypto.subtle.decapsulateBits("ML-KEM-768", privateKey, ciphertext),
+    ),
+    "5eca6b18f9893cdd69eca641c51b6a29d43d1552fdf0fc8ade07336d641dde28",
+  );
+});
+
+Deno.test(async function mlDsaKnownAnswers() {
+  const seed = new Uint8Array(32).map((_, i) => i);
+  const message = new TextEncoder().encode("abc");
+  // SHA-256 of the signatures, which are a few kilobytes long.
+  const signatureHashes = {
+    "ML-DSA-44":
+      "9c2afc5db0e15c199977be79eaee52ca0b538a1b6f8039dcac032a06120cfa87",
+    "ML-DSA-65":
+      "4b01e98dfaa062b0ef64c949f50d2ee923d82f721379727d160ce6fccaa8a6e6",
+    "ML-DSA-87":
+      "91e6609815657b405b6868f9d7ef71ee7882e62a3f5609657156007cdd9a2137",
+  };
+  for (const [name, hash] of Object.entries(signatureHashes)) {
+    const privateKey = await crypto.subtle.importKey(
+      "raw-seed",
+      seed,
+      name,
+      false,
+      ["sign"],
+    );
+    // Signing is deterministic (FIPS 204, Algorithm 2 with rnd = 0).
+    assertEquals(
+      await sha256Hex(await crypto.subtle.sign(name, privateKey, message)),
+      hash,
+    );
+  }
+
+  const privateKey = await crypto.subtle.importKey(
+    "raw-seed",
+    seed,
+    "ML-DSA-44",
+    false,
+    ["sign"],
+  );
+  assertEquals(
+    await sha256Hex(
+      await crypto.subtle.sign(
+        { name: "ML-DSA-44", context: new TextEncoder().encode("foo") },
+        privateKey,
+        message,
+      ),
+    ),
+    "8eb18837f8b24b1c6abe70095bc31b05305b62b034f7dc27f469b92a008773b1",
+  );
+});
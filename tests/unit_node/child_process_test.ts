@@ -2,6 +2,7 @@
 
 import CP from "node:child_process";
 import { Buffer } from "node:buffer";
+import net from "node:net";
 import {
   assert,
   assertEquals,
@@ -1188,3 +1189,79 @@ Deno.test(async function experimentalFlag() {
 
   await timeout.promise;
 });
+
+async function readFromPort(port: number): Promise<string> {
+  const conn = await Deno.connect({ hostname: "127.0.0.1", port });
+  return await new Response(conn.readable).text();
+}
+
+Deno.test({
+  name: "[node/child_process] send a server handle to a child",
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    const script = path.join(
+      path.dirname(path.fromFileUrl(import.meta.url)),
+      "testdata",
+      "child_process_send_handle.mjs",
+    );
+    const server = net.createServer();
+    const listening = Promise.withResolvers<void>();
+    server.listen(0, "127.0.0.1", () => listening.resolve());
+    await listening.promise;
+    const { port } = server.address() as net.AddressInfo;
+
+    const child = CP.fork(script, [], {
+      stdio: ["inherit", "inherit", "inherit", "ipc"],
+    });
+    const ready = Promise.withResolvers<void>();
+    child.on("message", (msg) => {
+      if (msg === "ready") ready.resolve();
+    });
+    child.send("server", server);
+    await ready.promise;
+
+    // Stop accepting in the parent, so that the child gets the connection.
+    const closed = Promise.withResolvers<void>();
+    server.close(() => closed.resolve());
+    await closed.promise;
+
+    assertEquals(await readFromPort(port), "handled by child");
+
+    const exited = Promise.withResolvers<void>();
+    child.on("close", () => exited.resolve());
+    child.kill();
+    await exited.promise;
+  },
+});
+
+Deno.test({
+  name: "[node/child_process] send a socket handle to a child",
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    const script = path.join(
+      path.dirname(path.fromFileUrl(import.meta.url)),
+      "testdata",
+      "child_process_send_handle.mjs",
+    );
+    const child = CP.fork(script, [], {
+      stdio: ["inherit", "inherit", "inherit", "ipc"],
+    });
+    const server = net.createServer((socket) => {
+      child.send("socket", socket);
+    });
+    const listening = Promise.withResolvers<void>();
+    server.listen(0, "127.0.0.1", () => listening.resolve());
+    await listening.promise;
+    const { port } = server.address() as net.AddressInfo;
+
+    assertEquals(await readFromPort(port), "handled by child");
+
+    const closed = Promise.withResolvers<void>();
+    server.close(() => closed.resolve());
+    await closed.promise;
+    const exited = Promise.withResolvers<void>();
+    child.on("close", () => exited.resolve());
+    child.kill();
+    await exited.promise;
+  },
+});
@@ -1,5 +1,6 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
-import { assertEquals } from "@std/assert";
+import { assert, assertEquals } from "@std/assert";
+import * as path from "@std/path";
 import cluster from "node:cluster";
 import * as clusterNamed from "node:cluster";
 
@@ -41,3 +42,55 @@ Deno.test("[node/cluster] has all node exports", () => {
   // @ts-ignore Our @types/node version is too old
   assertEquals(cluster.isMaster, clusterNamed.isMaster);
 });
+
+Deno.test({
+  name: "[node/cluster] workers share a listening socket",
+  // handles can't be sent to child processes on Windows
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    const script = path.join(
+      path.dirname(path.fromFileUrl(import.meta.url)),
+      "testdata",
+      "cluster_worker.mjs",
+    );
+    cluster.setupPrimary({ exec: script });
+    assertEquals(cluster.settings.exec, script);
+
+    const ports = new Set<number>();
+    const listening = Promise.withResolvers<void>();
+    cluster.on("listening", (_worker, address) => {
+      ports.add(address.port);
+      const workers = Object.values(cluster.workers!);
+      if (workers.every((worker) => worker!.state === "listening")) {
+        listening.resolve();
+      }
+    });
+
+    const first = cluster.fork();
+    const second = cluster.fork();
+    assertEquals(Object.keys(cluster.workers!).length, 2);
+    await listening.promise;
+
+    // both workers accept on the socket of the primary
+    assertEquals(ports.size, 1);
+    const [port] = ports;
+    const res = await fetch(`http://127.0.0.1:${port}/`);
+    const id = Number(await res.text());
+    assert(id === first.id || id === second.id);
+
+    const exited = Promise.withResolvers<void>();
+    let exits = 0;
+    cluster.on("exit", (worker, code) => {
+      assertEquals(code, 0);
+      assert(worker.exitedAfterDisconnect);
+      if (++exits === 2) {
+        exited.resolve();
+      }
+    });
+    const disconnected = Promise.withResolvers<void>();
+    cluster.disconnect(() => disconnected.resolve());
+    await Promise.all([disconnected.promise, exited.promise]);
+    assertEquals(cluster.workers, {});
+    cluster.removeAllListeners();
+  },
+});
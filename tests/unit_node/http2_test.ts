@@ -7,7 +7,7 @@ import { Buffer } from "node:buffer";
 import { readFile } from "node:fs/promises";
 import { join } from "node:path";
 import * as net from "node:net";
-import { assert, assertEquals } from "@std/assert";
+import { assert, assertEquals, assertThrows } from "@std/assert";
 import { curlRequest } from "../unit/test_util.ts";
 import { createRequire } from "node:module";
 const require = createRequire(import.meta.url);
@@ -448,6 +448,75 @@ Deno.test("[node/http2 client] connection states", async () => {
   assertEquals(actual, expected);
 });
 
+Deno.test("[node/http2 client] ping, settings and flow control", async () => {
+  const url = "http://127.0.0.1:4246";
+  const client = http2.connect(url, { settings: { initialWindowSize: 1024 } });
+  client.on("error", (err) => console.error(err));
+
+  const remoteSettings = Promise.withResolvers<http2.Settings>();
+  client.once("remoteSettings", remoteSettings.resolve);
+  assertEquals(client.localSettings.initialWindowSize, 1024);
+  assertEquals(client.remoteSettings, {});
+  // only the settings that h2 exposes are reported
+  const { maxConcurrentStreams, ...unknown } = await remoteSettings.promise;
+  assert(maxConcurrentStreams! > 0);
+  assertEquals(unknown, {});
+  assert(!client.pendingSettingsAck);
+
+  const payload = Buffer.from("abcdefgh");
+  const pong = Promise.withResolvers<[number, Buffer]>();
+  assert(client.ping(payload, (err, duration, ack) => {
+    if (err) pong.reject(err);
+    else pong.resolve([duration, ack]);
+  }));
+  const [duration, ack] = await pong.promise;
+  assert(duration >= 0);
+  assertEquals(ack, payload);
+
+  client.setLocalWindowSize(1024 * 1024);
+
+  const settingsAck = Promise.withResolvers<http2.Settings>();
+  client.settings({ initialWindowSize: 65535 }, (err, settings) => {
+    if (err) settingsAck.reject(err);
+    else settingsAck.resolve(settings);
+  });
+  assert(client.pendingSettingsAck);
+  assertEquals((await settingsAck.promise).initialWindowSize, 65535);
+  assertEquals(client.localSettings.initialWindowSize, 65535);
+
+  // the other settings can't be changed after the handshake
+  assertThrows(
+    () => client.settings({ maxFrameSize: 32768 }),
+    Error,
+    "Not implemented: Http2Session.settings({ maxFrameSize })",
+  );
+
+  const closed = Promise.withResolvers<void>();
+  client.close(closed.resolve);
+  await closed.promise;
+});
+
+Deno.test("[node/http2] packed settings", () => {
+  const packed = http2.getPackedSettings({
+    enablePush: false,
+    initialWindowSize: 1024,
+  });
+  assertEquals(
+    packed,
+    Buffer.from([0, 2, 0, 0, 0, 0, 0, 4, 0, 0, 4, 0]),
+  );
+  assertEquals(http2.getUnpackedSettings(packed), {
+    enablePush: false,
+    initialWindowSize: 1024,
+  });
+  assertEquals(http2.getDefaultSettings().initialWindowSize, 65535);
+  assertThrows(
+    () => http2.getPackedSettings({ maxFrameSize: 1 }),
+    RangeError,
+    'Invalid value for setting "maxFrameSize": 1',
+  );
+});
+
 Deno.test("request and response exports", () => {
   assert(http2.Http2ServerRequest);
   assert(http2.Http2ServerResponse);
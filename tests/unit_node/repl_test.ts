@@ -1,8 +1,9 @@
 // deno-lint-ignore-file no-undef
 // Copyright 2018-2025 the Deno authors. MIT license.
 
-import repl from "node:repl";
-import { assert } from "@std/assert";
+import repl, { type ReplOptions } from "node:repl";
+import { assert, assertEquals } from "@std/assert";
+import { PassThrough, Writable } from "node:stream";
 
 Deno.test({
   name: "repl module exports",
@@ -15,3 +16,102 @@ Deno.test({
     assert(typeof repl._builtinLibs !== "undefined");
   },
 });
+
+function startRepl(options: ReplOptions = {}) {
+  const input = new PassThrough();
+  let output = "";
+  const server = repl.start({
+    input,
+    output: new Writable({
+      write(chunk, _encoding, callback) {
+        output += chunk;
+        callback();
+      },
+    }),
+    prompt: "> ",
+    terminal: false,
+    ...options,
+  });
+  const { promise: done, resolve } = Promise.withResolvers<void>();
+  server.defineCommand("done", () => resolve());
+  return {
+    server,
+    // Commands run after all input before them was evaluated.
+    async run(source: string) {
+      input.end(`${source}.done\n`);
+      await done;
+      return output;
+    },
+  };
+}
+
+Deno.test("[node/repl] evaluates input with top-level await", async () => {
+  const { run } = startRepl();
+  const output = await run(
+    "1 + 1\nconst answer = await Promise.resolve(40)\nanswer + 2\n" +
+      "const answer = 1\nanswer\n",
+  );
+  assertEquals(output, "> 2\n> undefined\n> 42\n> undefined\n> 1\n> ");
+});
+
+Deno.test("[node/repl] buffers incomplete input", async () => {
+  const { run } = startRepl({ ignoreUndefined: true });
+  const output = await run(
+    "function replTestAdd(a, b) {\nreturn a + b;\n}\nreplTestAdd(1, 2)\n",
+  );
+  assertEquals(output, "> | | > 3\n> ");
+});
+
+Deno.test("[node/repl] reports uncaught errors", async () => {
+  const { run } = startRepl();
+  const output = await run("throw 'boom'\n.nope\n");
+  assertEquals(output, "> Uncaught 'boom'\n> Invalid REPL keyword\n> ");
+});
+
+Deno.test("[node/repl] defineCommand and context", async () => {
+  const { server, run } = startRepl();
+  server.context.replTestValue = "from context";
+  server.defineCommand("greet", {
+    help: "Greet someone",
+    action(name: string) {
+      this.output.write(`Hello, ${name}!\n`);
+      this.displayPrompt();
+    },
+  });
+  const output = await run(".greet Deno\nreplTestValue\n");
+  assertEquals(output, "> Hello, Deno!\n> 'from context'\n> ");
+  delete server.context.replTestValue;
+});
+
+Deno.test("[node/repl] custom eval and writer", async () => {
+  const { run } = startRepl({
+    eval(cmd, _context, _file, callback) {
+      callback(null, cmd.trim().toUpperCase());
+    },
+    writer: (value: unknown) => `<${value}>`,
+  });
+  const output = await run("hello\n");
+  assertEquals(output, "> <HELLO>\n> ");
+});
+
+Deno.test("[node/repl] setupHistory persists history", async () => {
+  const historyPath = Deno.makeTempFileSync();
+  Deno.writeTextFileSync(historyPath, "previous\n");
+  const input = new PassThrough();
+  const server = repl.start({
+    input,
+    output: new Writable({ write: (_chunk, _encoding, cb) => cb() }),
+    terminal: true,
+  });
+  const { promise, resolve, reject } = Promise.withResolvers<void>();
+  server.setupHistory(historyPath, (err) => err ? reject(err) : resolve());
+  await promise;
+  // @ts-ignore history is not in the declaration files
+  assertEquals(server.history, ["previous"]);
+  input.write("1 + 1\r");
+  while (Deno.readTextFileSync(historyPath) !== "1 + 1\nprevious") {
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+  server.close();
+  Deno.removeSync(historyPath);
+});
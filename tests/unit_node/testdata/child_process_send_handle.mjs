@@ -0,0 +1,12 @@
+import process from "node:process";
+
+process.on("message", (msg, handle) => {
+  if (msg === "server") {
+    handle.on("connection", (socket) => {
+      socket.end("handled by child");
+    });
+    process.send("ready");
+  } else if (msg === "socket") {
+    handle.end("handled by child");
+  }
+});
@@ -0,0 +1,10 @@
+import cluster from "node:cluster";
+import http from "node:http";
+
+if (!cluster.isWorker) {
+  throw new Error("expected to be started as a cluster worker");
+}
+
+http.createServer((_req, res) => {
+  res.end(`${cluster.worker.id}`);
+}).listen(0, "127.0.0.1");
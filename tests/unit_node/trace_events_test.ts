@@ -0,0 +1,105 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+import { createTracing, getEnabledCategories } from "node:trace_events";
+import { assert, assertEquals, assertThrows } from "@std/assert";
+
+Deno.test("[node/trace_events] createTracing validates options", () => {
+  assertThrows(
+    // deno-lint-ignore no-explicit-any
+    () => createTracing(undefined as any),
+    TypeError,
+    'The "options" argument must be of type object',
+  );
+  assertThrows(
+    () => createTracing({ categories: [] }),
+    TypeError,
+    "At least one category is required",
+  );
+});
+
+Deno.test("[node/trace_events] enable and disable categories", () => {
+  const tracing = createTracing({ categories: ["test.a", "test.b"] });
+  assertEquals(tracing.categories, "test.a,test.b");
+  assertEquals(tracing.enabled, false);
+  assertEquals(getEnabledCategories(), undefined);
+
+  tracing.enable();
+  assertEquals(tracing.enabled, true);
+  assertEquals(getEnabledCategories(), "test.a,test.b");
+
+  const other = createTracing({ categories: ["test.b"] });
+  other.enable();
+  tracing.disable();
+  assertEquals(getEnabledCategories(), "test.b");
+  other.disable();
+  assertEquals(getEnabledCategories(), undefined);
+});
+
+Deno.test("[node/trace_events] writes Chrome trace file", async () => {
+  const dir = Deno.makeTempDirSync();
+  try {
+    const output = await new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        `
+        import { performance } from "node:perf_hooks";
+        import { getEnabledCategories } from "node:trace_events";
+        console.log(getEnabledCategories());
+        performance.mark("start");
+        performance.measure("work", "start");
+        `,
+      ],
+      env: {
+        NODE_OPTIONS:
+          `--trace-event-categories=node.perf --trace-event-file-pattern=${dir}/trace-\${pid}.log`,
+      },
+    }).output();
+    assert(output.success);
+    assertEquals(new TextDecoder().decode(output.stdout).trim(), "node.perf");
+
+    const [file] = [...Deno.readDirSync(dir)];
+    assert(file.name.startsWith("trace-"));
+    const { traceEvents } = JSON.parse(
+      Deno.readTextFileSync(`${dir}/${file.name}`),
+    );
+    const mark = traceEvents.find((e: { ph: string }) => e.ph === "R");
+    assertEquals(mark.name, "start");
+    assertEquals(mark.cat, "node,node.perf,node.perf.usertiming");
+    const measure = traceEvents.filter((e: { name: string }) =>
+      e.name === "work"
+    );
+    assertEquals(measure.map((e: { ph: string }) => e.ph), ["b", "e"]);
+    assertEquals(measure[0].id, measure[1].id);
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
+
+Deno.test("[node/trace_events] records ops without --strace-ops", async () => {
+  const dir = Deno.makeTempDirSync();
+  try {
+    const output = await new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "for (let i = 0; i < 2000; i++) Deno.cwd();",
+      ],
+      env: {
+        NODE_OPTIONS:
+          `--trace-event-categories=deno.ops --trace-event-file-pattern=${dir}/trace.log`,
+      },
+    }).output();
+    assert(output.success);
+
+    // More events than are buffered at once, so the file is written in
+    // several chunks.
+    const { traceEvents } = JSON.parse(
+      Deno.readTextFileSync(`${dir}/trace.log`),
+    );
+    const cwd = traceEvents.filter((e: { name: string }) =>
+      e.name === "op_fs_cwd"
+    );
+    assert(cwd.length >= 2000);
+    assertEquals(cwd[0].cat, "deno.ops");
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
@@ -2,11 +2,16 @@
 import {
   cachedDataVersionTag,
   deserialize,
+  getHeapCodeStatistics,
+  getHeapSnapshot,
+  getHeapSpaceStatistics,
   getHeapStatistics,
   serialize,
   setFlagsFromString,
+  writeHeapSnapshot,
 } from "node:v8";
-import { assertEquals } from "@std/assert";
+import { text } from "node:stream/consumers";
+import { assert, assertEquals, assertMatch } from "@std/assert";
 
 // https://github.com/nodejs/node/blob/a2bbe5ff216bc28f8dac1c36a8750025a93c3827/test/parallel/test-v8-version-tag.js#L6
 Deno.test({
@@ -64,3 +69,71 @@ Deno.test({
     assertEquals(d, { a: 1 });
   },
 });
+
+Deno.test({
+  name: "getHeapSpaceStatistics",
+  fn() {
+    const spaces = getHeapSpaceStatistics();
+    assert(spaces.length > 0);
+    assert(spaces.some((space) => space.space_name === "old_space"));
+    for (const space of spaces) {
+      assertEquals(Object.keys(space), [
+        "space_name",
+        "space_size",
+        "space_used_size",
+        "space_available_size",
+        "physical_space_size",
+      ]);
+    }
+  },
+});
+
+Deno.test({
+  name: "getHeapCodeStatistics",
+  fn() {
+    const stats = getHeapCodeStatistics();
+    assertEquals(Object.keys(stats).sort(), [
+      "bytecode_and_metadata_size",
+      "code_and_metadata_size",
+      "cpu_profiler_metadata_size",
+      "external_script_source_size",
+    ]);
+  },
+});
+
+Deno.test({
+  name: "getHeapSnapshot streams the snapshot",
+  async fn() {
+    const snapshot = JSON.parse(await text(getHeapSnapshot()));
+    assert(Array.isArray(snapshot.nodes));
+    assert(snapshot.snapshot.node_count > 0);
+  },
+});
+
+Deno.test({
+  name: "writeHeapSnapshot",
+  fn() {
+    const dir = Deno.makeTempDirSync();
+    try {
+      const path = `${dir}/test.heapsnapshot`;
+      assertEquals(writeHeapSnapshot(path), path);
+      const snapshot = JSON.parse(Deno.readTextFileSync(path));
+      assert(snapshot.snapshot.node_count > 0);
+
+      const cwd = Deno.cwd();
+      Deno.chdir(dir);
+      try {
+        const filename = writeHeapSnapshot();
+        assertMatch(
+          filename,
+          new RegExp(`^Heap\\.\\d{8}\\.\\d{6}\\.${Deno.pid}\\.0\\.\\d{3}\\.`),
+        );
+        assert(Deno.statSync(`${dir}/${filename}`).isFile);
+      } finally {
+        Deno.chdir(cwd);
+      }
+    } finally {
+      Deno.removeSync(dir, { recursive: true });
+    }
+  },
+});
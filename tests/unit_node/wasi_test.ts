@@ -1,7 +1,131 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
-import wasi from "node:wasi";
-import { assertThrows } from "@std/assert";
+import { WASI } from "node:wasi";
+import fs from "node:fs";
+import { join } from "node:path";
+import { assertEquals, assertThrows } from "@std/assert";
 
-Deno.test("[node/wasi] - WASI should throw (not implemented)", () => {
-  assertThrows(() => new wasi.WASI());
+// The following blob can be created by taking the following s-expr and pass
+// it through wat2wasm.
+//    (module
+//      (import "wasi_snapshot_preview1" "path_open"
+//        (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32)
+//          (result i32)))
+//      (import "wasi_snapshot_preview1" "fd_write"
+//        (func $fd_write (param i32 i32 i32 i32) (result i32)))
+//      (import "wasi_snapshot_preview1" "proc_exit"
+//        (func $proc_exit (param i32)))
+//      (memory (export "memory") 1)
+//      (data (i32.const 0) "\10\00\00\00\06\00\00\00")
+//      (data (i32.const 16) "hello\n")
+//      (data (i32.const 28) "\07\00\00\00out.txt")
+//      (func (export "_start") (local $errno i32)
+//        ;; creates the file at the path stored at 32, with its length at 28,
+//        ;; in the first preopened directory
+//        (local.set $errno
+//          (call $path_open (i32.const 3) (i32.const 0) (i32.const 32)
+//            (i32.load (i32.const 28)) (i32.const 9) (i64.const 64)
+//            (i64.const 0) (i32.const 0) (i32.const 48)))
+//        (if (local.get $errno)
+//          (then (call $proc_exit (local.get $errno))))
+//        (drop (call $fd_write (i32.load (i32.const 48)) (i32.const 0)
+//          (i32.const 1) (i32.const 8)))
+//        (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1)
+//          (i32.const 8)))
+//        (call $proc_exit (i32.const 3)))
+//    )
+// deno-fmt-ignore
+const helloWasm = new Uint8Array([
+  0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x1d, 0x04, 0x60,
+  0x09, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7e, 0x7e, 0x7f, 0x7f, 0x01, 0x7f,
+  0x60, 0x04, 0x7f, 0x7f, 0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x01, 0x7f, 0x00,
+  0x60, 0x00, 0x00, 0x02, 0x69, 0x03, 0x16, 0x77, 0x61, 0x73, 0x69, 0x5f,
+  0x73, 0x6e, 0x61, 0x70, 0x73, 0x68, 0x6f, 0x74, 0x5f, 0x70, 0x72, 0x65,
+  0x76, 0x69, 0x65, 0x77, 0x31, 0x09, 0x70, 0x61, 0x74, 0x68, 0x5f, 0x6f,
+  0x70, 0x65, 0x6e, 0x00, 0x00, 0x16, 0x77, 0x61, 0x73, 0x69, 0x5f, 0x73,
+  0x6e, 0x61, 0x70, 0x73, 0x68, 0x6f, 0x74, 0x5f, 0x70, 0x72, 0x65, 0x76,
+  0x69, 0x65, 0x77, 0x31, 0x08, 0x66, 0x64, 0x5f, 0x77, 0x72, 0x69, 0x74,
+  0x65, 0x00, 0x01, 0x16, 0x77, 0x61, 0x73, 0x69, 0x5f, 0x73, 0x6e, 0x61,
+  0x70, 0x73, 0x68, 0x6f, 0x74, 0x5f, 0x70, 0x72, 0x65, 0x76, 0x69, 0x65,
+  0x77, 0x31, 0x09, 0x70, 0x72, 0x6f, 0x63, 0x5f, 0x65, 0x78, 0x69, 0x74,
+  0x00, 0x02, 0x03, 0x02, 0x01, 0x03, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07,
+  0x13, 0x02, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x06,
+  0x5f, 0x73, 0x74, 0x61, 0x72, 0x74, 0x00, 0x03, 0x0a, 0x46, 0x01, 0x44,
+  0x01, 0x01, 0x7f, 0x41, 0x03, 0x41, 0x00, 0x41, 0x20, 0x41, 0x1c, 0x28,
+  0x02, 0x00, 0x41, 0x09, 0x42, 0xc0, 0x00, 0x42, 0x00, 0x41, 0x00, 0x41,
+  0x30, 0x10, 0x00, 0x21, 0x00, 0x20, 0x00, 0x04, 0x40, 0x20, 0x00, 0x10,
+  0x02, 0x0b, 0x41, 0x30, 0x28, 0x02, 0x00, 0x41, 0x00, 0x41, 0x01, 0x41,
+  0x08, 0x10, 0x01, 0x1a, 0x41, 0x01, 0x41, 0x00, 0x41, 0x01, 0x41, 0x08,
+  0x10, 0x01, 0x1a, 0x41, 0x03, 0x10, 0x02, 0x0b, 0x0b, 0x2d, 0x01, 0x00,
+  0x41, 0x00, 0x0b, 0x27, 0x10, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x68, 0x65, 0x6c, 0x6c,
+  0x6f, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00,
+  0x6f, 0x75, 0x74, 0x2e, 0x74, 0x78, 0x74
+]);
+
+async function instantiate(wasi: WASI) {
+  return await WebAssembly.instantiate(
+    new WebAssembly.Module(helloWasm),
+    wasi.getImportObject(),
+  );
+}
+
+Deno.test("[node/wasi] - WASI validates its options", () => {
+  assertThrows(() => new WASI(), TypeError, "options.version");
+  assertThrows(
+    () => new WASI({ version: "preview2" }),
+    TypeError,
+    "unsupported WASI version",
+  );
+  assertThrows(
+    // @ts-expect-error invalid args
+    () => new WASI({ version: "preview1", args: "a" }),
+    TypeError,
+    "options.args",
+  );
+});
+
+Deno.test("[node/wasi] - WASI writes to preopens and stdio", async () => {
+  const dir = Deno.makeTempDirSync();
+  const stdout = fs.openSync(join(dir, "stdout.txt"), "w");
+  try {
+    const wasi = new WASI({
+      version: "preview1",
+      preopens: { "/sandbox": dir },
+      stdout,
+    });
+    const instance = await instantiate(wasi);
+    assertEquals(wasi.start(instance), 3);
+    assertEquals(Deno.readTextFileSync(join(dir, "out.txt")), "hello\n");
+    assertEquals(Deno.readTextFileSync(join(dir, "stdout.txt")), "hello\n");
+    assertThrows(() => wasi.start(instance), Error, "already started");
+  } finally {
+    fs.closeSync(stdout);
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
+
+Deno.test("[node/wasi] - WASI can't leave preopened directories", async () => {
+  const dir = Deno.makeTempDirSync();
+  try {
+    Deno.mkdirSync(join(dir, "sandbox"));
+    const wasi = new WASI({
+      version: "preview1",
+      preopens: { "/sandbox": join(dir, "sandbox") },
+    });
+    const instance = await instantiate(wasi);
+    const memory = new Uint8Array(
+      (instance.exports.memory as WebAssembly.Memory).buffer,
+    );
+    const path = new TextEncoder().encode("../out.txt");
+    memory.set(path, 32);
+    memory[28] = path.length;
+    // ENOTCAPABLE
+    assertEquals(wasi.start(instance), 76);
+    assertThrows(
+      () => Deno.statSync(join(dir, "out.txt")),
+      Deno.errors.NotFound,
+    );
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
 });
@@ -4,6 +4,7 @@ import {
   assert,
   assertEquals,
   assertObjectMatch,
+  assertRejects,
   assertThrows,
   fail,
 } from "@std/assert";
@@ -11,6 +12,7 @@ import { fromFileUrl, relative, SEPARATOR } from "@std/path";
 import * as workerThreads from "node:worker_threads";
 import { EventEmitter, once } from "node:events";
 import process from "node:process";
+import { text } from "node:stream/consumers";
 
 Deno.test("[node/worker_threads] BroadcastChannel is exported", () => {
   assertEquals<unknown>(workerThreads.BroadcastChannel, BroadcastChannel);
@@ -155,6 +157,36 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "[node/worker_threads] Worker getHeapSnapshot",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      globalThis.leak = { workerHeapSnapshotMarker: true };
+      parentPort.postMessage("ready");
+      setInterval(() => {}, 1000);
+      `,
+      {
+        eval: true,
+      },
+    );
+    await once(worker, "message");
+
+    const stream = await worker.getHeapSnapshot();
+    const snapshot = JSON.parse(await text(stream));
+    assert(snapshot.snapshot.node_count > 0);
+    assert(snapshot.strings.includes("workerHeapSnapshotMarker"));
+
+    await worker.terminate();
+    await assertRejects(
+      () => worker.getHeapSnapshot(),
+      Error,
+      "Worker instance not running",
+    );
+  },
+});
+
 Deno.test({
   name: "[node/worker_threads] worker thread with type module",
   async fn() {